@@ -1,9 +1,13 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use intent_engine::db::models::TaskSortBy;
 use intent_engine::db::{create_pool, run_migrations};
 use intent_engine::events::EventManager;
+use intent_engine::plan::{PlanExecutor, PlanRequest, PriorityValue, TaskTree};
 use intent_engine::report::ReportManager;
+use intent_engine::search::SearchManager;
 use intent_engine::tasks::{TaskManager, TaskUpdate};
 use std::hint::black_box;
+use std::time::{Duration, Instant};
 use tempfile::TempDir;
 use tokio::runtime::Runtime;
 
@@ -151,7 +155,7 @@ fn bench_event_list(c: &mut Criterion) {
                 // Create events
                 for i in 0..size {
                     event_mgr
-                        .add_event(task.id, "test", &format!("Event {}", i))
+                        .add_event(task.id, "note", &format!("Event {}", i))
                         .await
                         .unwrap();
                 }
@@ -173,6 +177,7 @@ fn bench_report_summary(c: &mut Criterion) {
     let rt = Runtime::new().unwrap();
 
     let mut group = c.benchmark_group("report_summary");
+    group.sample_size(10);
 
     for size in [100, 1000, 5000].iter() {
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
@@ -195,7 +200,7 @@ fn bench_report_summary(c: &mut Criterion) {
                         },
                         _ => {
                             task_mgr.start_task(task.id, false).await.unwrap();
-                            task_mgr.done_task(false).await.unwrap();
+                            task_mgr.done_task().await.unwrap();
                         },
                     }
                 }
@@ -239,6 +244,185 @@ fn bench_task_hierarchy(c: &mut Criterion) {
     group.finish();
 }
 
+/// A plan of `size` tasks: groups of ten children, each group depending on the last
+fn plan_of(size: usize) -> PlanRequest {
+    let groups = (0..size / 10)
+        .map(|g| TaskTree {
+            name: Some(format!("Group {}", g)),
+            spec: Some(format!("Spec for group {}", g)),
+            depends_on: (g > 0).then(|| vec![format!("Group {}", g - 1)]),
+            children: Some(
+                (1..10)
+                    .map(|i| TaskTree {
+                        name: Some(format!("Task {}.{}", g, i)),
+                        spec: Some(format!("Spec for task {}.{}", g, i)),
+                        priority: Some(PriorityValue::Medium),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        })
+        .collect();
+    PlanRequest {
+        tasks: groups,
+        ..Default::default()
+    }
+}
+
+fn bench_plan_import(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("plan_import");
+    group.sample_size(10);
+
+    for size in [100, 1000, 10_000] {
+        let request = plan_of(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &request, |b, request| {
+            // Only the import is timed, not creating the database
+            b.to_async(&rt).iter_custom(|iters| async move {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    let (_temp_dir, pool) = setup_test_db().await;
+                    let start = Instant::now();
+                    let result = PlanExecutor::new(&pool).execute(request).await.unwrap();
+                    elapsed += start.elapsed();
+                    assert_eq!(result.created_count, size);
+                }
+                elapsed
+            });
+        });
+    }
+    group.finish();
+}
+
+/// A database holding `plan_of(size)`, with one event per task
+async fn seeded_db(size: usize) -> (TempDir, sqlx::SqlitePool) {
+    let (temp_dir, pool) = setup_test_db().await;
+    let mut request = plan_of(size);
+    for (g, group) in request.tasks.iter_mut().enumerate() {
+        for (i, child) in group.children.iter_mut().flatten().enumerate() {
+            child.priority = Some(match (g + i) % 4 {
+                0 => PriorityValue::Critical,
+                1 => PriorityValue::High,
+                2 => PriorityValue::Medium,
+                _ => PriorityValue::Low,
+            });
+        }
+    }
+    PlanExecutor::new(&pool).execute(&request).await.unwrap();
+
+    let events = EventManager::new(&pool);
+    for id in 1..=size as i64 {
+        let text = if id % 50 == 0 {
+            format!("Decided to cache the tokenizer output for task {}", id)
+        } else {
+            format!("Progress note {} on the parser", id)
+        };
+        events.add_event(id, "note", &text).await.unwrap();
+    }
+    (temp_dir, pool)
+}
+
+fn bench_find_tasks_sort_modes(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let (_temp_dir, pool) = rt.block_on(seeded_db(5000));
+
+    let mut group = c.benchmark_group("find_tasks_sorted");
+    for (name, sort_by) in [
+        ("id", TaskSortBy::Id),
+        ("priority", TaskSortBy::Priority),
+        ("time", TaskSortBy::Time),
+        ("focus_aware", TaskSortBy::FocusAware),
+    ] {
+        group.bench_function(name, |b| {
+            b.to_async(&rt).iter(|| async {
+                black_box(
+                    TaskManager::new(&pool)
+                        .find_tasks(Some("todo"), None, Some(sort_by), Some(100), None)
+                        .await
+                        .unwrap(),
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_search_fts(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("search_fts");
+    for size in [1000, 10_000] {
+        let (_temp_dir, pool) = rt.block_on(seeded_db(size));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.to_async(&rt).iter(|| async {
+                black_box(
+                    SearchManager::new(&pool)
+                        .search("tokenizer", true, true, Some(20), None, false)
+                        .await
+                        .unwrap(),
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+/// A chain of `depth` nested tasks, each level with three todo siblings,
+/// focused on the deepest task
+async fn deep_tree(depth: usize) -> (TempDir, sqlx::SqlitePool) {
+    let (temp_dir, pool) = setup_test_db().await;
+    let task_mgr = TaskManager::new(&pool);
+    let mut parent_id = None;
+    for level in 0..depth {
+        let task = task_mgr
+            .add_task(
+                &format!("Level {}", level),
+                Some("spec"),
+                parent_id,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        for sibling in 0..3 {
+            task_mgr
+                .add_task(
+                    &format!("Level {} sibling {}", level, sibling),
+                    Some("spec"),
+                    parent_id,
+                    None,
+                    Some(sibling + 1),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        parent_id = Some(task.id);
+    }
+    task_mgr
+        .start_task(parent_id.unwrap(), false)
+        .await
+        .unwrap();
+    (temp_dir, pool)
+}
+
+fn bench_pick_next_deep(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("pick_next_deep");
+    for depth in [10, 50, 200] {
+        let (_temp_dir, pool) = rt.block_on(deep_tree(depth));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, _| {
+            b.to_async(&rt)
+                .iter(|| async { black_box(TaskManager::new(&pool).pick_next().await.unwrap()) });
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_task_add,
@@ -249,5 +433,9 @@ criterion_group!(
     bench_event_list,
     bench_report_summary,
     bench_task_hierarchy,
+    bench_plan_import,
+    bench_find_tasks_sort_modes,
+    bench_search_fts,
+    bench_pick_next_deep,
 );
 criterion_main!(benches);
@@ -0,0 +1,201 @@
+//! Compare the latest `cargo bench` run with the baseline kept in the repository
+//!
+//! Criterion keeps its results under `target/criterion`, which is neither
+//! shared nor kept. `benches/baseline.json` records the median time of every
+//! benchmark so regressions show up across machines and months:
+//!
+//! ```text
+//! make bench-compare     # cargo bench, then compare with the baseline
+//! make bench-baseline    # cargo bench, then write the baseline
+//! ```
+//!
+//! Options: `--update` writes the baseline instead of comparing,
+//! `--threshold <percent>` (default 15) is the slowdown counted as a
+//! regression, `--criterion-dir <dir>` points at another results directory.
+//! Exits with status 1 when a benchmark regressed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const BASELINE_FILE: &str = "benches/baseline.json";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Baseline {
+    /// Where the numbers were measured; only for the reader
+    machine: String,
+    /// Median time per iteration in nanoseconds, by benchmark ID
+    median_ns: BTreeMap<String, f64>,
+}
+
+fn main() -> ExitCode {
+    let mut update = false;
+    let mut threshold = 15.0;
+    let mut criterion_dir = std::env::var("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("target"))
+        .join("criterion");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--update" => update = true,
+            "--threshold" => match args.next().and_then(|v| v.parse().ok()) {
+                Some(value) => threshold = value,
+                None => return usage("--threshold needs a number (percent)"),
+            },
+            "--criterion-dir" => match args.next() {
+                Some(dir) => criterion_dir = PathBuf::from(dir),
+                None => return usage("--criterion-dir needs a directory"),
+            },
+            other => return usage(&format!("unknown argument '{}'", other)),
+        }
+    }
+
+    let current = match collect_results(&criterion_dir) {
+        Ok(results) if !results.is_empty() => results,
+        Ok(_) => {
+            eprintln!(
+                "No benchmark results in {}; run 'cargo bench' first",
+                criterion_dir.display()
+            );
+            return ExitCode::FAILURE;
+        },
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", criterion_dir.display(), e);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    if update {
+        let baseline = Baseline {
+            machine: std::env::var("BENCH_MACHINE")
+                .unwrap_or_else(|_| format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)),
+            median_ns: current,
+        };
+        let json = serde_json::to_string_pretty(&baseline).expect("baseline serializes");
+        if let Err(e) = std::fs::write(BASELINE_FILE, json + "\n") {
+            eprintln!("Cannot write {}: {}", BASELINE_FILE, e);
+            return ExitCode::FAILURE;
+        }
+        println!(
+            "Wrote {} benchmarks to {}",
+            baseline.median_ns.len(),
+            BASELINE_FILE
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let baseline: Baseline = match std::fs::read_to_string(BASELINE_FILE)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+    {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Cannot read {}: {}", BASELINE_FILE, e);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    println!("Baseline measured on: {}", baseline.machine);
+    println!(
+        "{:<40} {:>12} {:>12} {:>8}",
+        "benchmark", "baseline", "current", "change"
+    );
+    let mut regressions = 0;
+    for (id, now) in &current {
+        let Some(before) = baseline.median_ns.get(id) else {
+            println!(
+                "{:<40} {:>12} {:>12} {:>8}",
+                id,
+                "-",
+                format_ns(*now),
+                "new"
+            );
+            continue;
+        };
+        let change = (now / before - 1.0) * 100.0;
+        let flag = if change > threshold {
+            regressions += 1;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "{:<40} {:>12} {:>12} {:>+7.1}%{}",
+            id,
+            format_ns(*before),
+            format_ns(*now),
+            change,
+            flag
+        );
+    }
+    for id in baseline
+        .median_ns
+        .keys()
+        .filter(|id| !current.contains_key(*id))
+    {
+        println!("{:<40} (not run)", id);
+    }
+
+    if regressions > 0 {
+        eprintln!(
+            "{} benchmark(s) more than {}% slower than the baseline",
+            regressions, threshold
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage(error: &str) -> ExitCode {
+    eprintln!("{}", error);
+    eprintln!(
+        "Usage: cargo run --release --example bench_compare -- [--update] [--threshold <percent>] [--criterion-dir <dir>]"
+    );
+    ExitCode::FAILURE
+}
+
+/// Median of the latest run of every benchmark under Criterion's output
+/// directory (`<id>/new/benchmark.json` and `estimates.json`)
+fn collect_results(dir: &Path) -> std::io::Result<BTreeMap<String, f64>> {
+    let mut results = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == "new") {
+                if let Some((id, median)) = read_result(&path) {
+                    results.insert(id, median);
+                }
+            } else {
+                pending.push(path);
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn read_result(dir: &Path) -> Option<(String, f64)> {
+    let read = |name: &str| -> Option<serde_json::Value> {
+        serde_json::from_str(&std::fs::read_to_string(dir.join(name)).ok()?).ok()
+    };
+    let id = read("benchmark.json")?["full_id"].as_str()?.to_string();
+    let median = read("estimates.json")?["median"]["point_estimate"].as_f64()?;
+    Some((id, median))
+}
+
+fn format_ns(ns: f64) -> String {
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{:.0} ns", ns)
+    }
+}
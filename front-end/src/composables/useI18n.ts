@@ -34,6 +34,8 @@ const dictionary = {
         'ENTRIES': 'ENTRIES',
         'ADD_NOTE': 'ADD_NOTE...',
         'NO_DATA_STREAM': 'NO_DATA_STREAM',
+        'LOAD_OLDER_EVENTS': 'Load older events',
+        'LOAD_MORE_TASKS': 'Load more tasks',
         'DELETE_LOG': 'Delete Event',
         'DELETE_LOG_CONFIRM': 'Delete this event?',
         'TYPE_DECISION': 'Decision',
@@ -46,6 +48,14 @@ const dictionary = {
         'RESTART': 'RESTART',
         'MARK_TODO': 'MARK TODO',
         'MARK_DONE': 'MARK DONE',
+        'APPROVE': 'APPROVE',
+        'SEND_BACK': 'SEND BACK',
+        'CLAIMED_BY': 'CLAIMED BY {session}',
+        'LEASE_UNTIL': 'Lease expires {time}',
+        'BLOCKED': 'BLOCKED',
+        'ACCEPTANCE_CRITERIA': 'ACCEPTANCE CRITERIA',
+        'LINKS': 'LINKS',
+        'ESTIMATE': 'ESTIMATE',
         'DELETE_TASK_CONFIRM': 'Delete task "{name}"?',
         'TOTAL_TASKS': 'TASKS: {count}'
     },
@@ -80,6 +90,8 @@ const dictionary = {
         'ENTRIES': '条记录',
         'ADD_NOTE': '添加笔记...',
         'NO_DATA_STREAM': '暂无数据流',
+        'LOAD_OLDER_EVENTS': '加载更早的事件',
+        'LOAD_MORE_TASKS': '加载更多任务',
         'DELETE_LOG': '删除事件',
         'DELETE_LOG_CONFIRM': '确认删除这个事件？',
         'TYPE_DECISION': '决策',
@@ -92,6 +104,14 @@ const dictionary = {
         'RESTART': '重新开始',
         'MARK_TODO': '标记为待办',
         'MARK_DONE': '标记为完成',
+        'APPROVE': '批准',
+        'SEND_BACK': '退回',
+        'CLAIMED_BY': '已被 {session} 领取',
+        'LEASE_UNTIL': '租约到期时间 {time}',
+        'BLOCKED': '受阻',
+        'ACCEPTANCE_CRITERIA': '验收标准',
+        'LINKS': '链接',
+        'ESTIMATE': '预估',
         'DELETE_TASK_CONFIRM': '确认删除任务 "{name}"？',
         'TOTAL_TASKS': '任务数: {count}'
     }
@@ -1,5 +1,6 @@
 import { createRouter, createWebHashHistory } from 'vue-router'
 import DashboardView from '../views/DashboardView.vue'
+import SettingsView from '../views/SettingsView.vue'
 
 const router = createRouter({
     history: createWebHashHistory(),
@@ -13,6 +14,11 @@ const router = createRouter({
             path: '/task/:id',
             name: 'task-detail',
             component: DashboardView
+        },
+        {
+            path: '/settings',
+            name: 'settings',
+            component: SettingsView
         }
     ]
 })
@@ -1,18 +1,76 @@
 import { defineStore } from 'pinia'
-import { ref, computed } from 'vue'
+import { ref, computed, watch } from 'vue'
+
+// Events fetched per timeline page
+const EVENT_PAGE_SIZE = 50
+// Children fetched per tree level and page
+const TREE_PAGE_SIZE = 100
 
 export interface Task {
     id: number
     name: string
     spec: string | null
-    status: 'todo' | 'doing' | 'done'
+    status: 'todo' | 'doing' | 'review' | 'done'
     priority: number | null
     parent_id: number | null
     created_at: string
     owner: 'human' | 'ai'
+    /// Live claim by an agent session (task detail only)
+    lease?: TaskLease | null
+    /// Parsed spec frontmatter (task detail only)
+    spec_meta?: SpecMeta | null
+    /// Why the task cannot move; absent when it is not blocked
+    blocked_reason?: string
+    /// Number of children (tree pages only)
+    child_count?: number
     // ... other fields
 }
 
+/// One page of a task's children, or of root tasks
+export interface ChildrenPage {
+    parent_id: number | null
+    tasks: Task[]
+    total_count: number
+    has_more: boolean
+    limit: number
+    next_cursor?: number
+}
+
+export interface SpecMeta {
+    acceptance_criteria?: string[]
+    links?: string[]
+    estimate_minutes?: number
+    fields?: Record<string, unknown>
+}
+
+export interface EstimateRollup {
+    total_minutes: number
+    remaining_minutes: number
+    unestimated: number
+}
+
+/// Rollup over a task and its descendants (tree badges)
+export interface SubtreeStats {
+    root_id: number
+    total_tasks: number
+    todo: number
+    doing: number
+    review: number
+    done: number
+    completion_percent: number
+    estimate: EstimateRollup
+    total_complexity: number
+    /// Stalled subtree task first, then what each one waits on
+    blocking_chain?: Task[]
+}
+
+export interface TaskLease {
+    task_id: number
+    assignee: string
+    claimed_at: string
+    lease_expires_at: string
+}
+
 export interface ApprovalResponse {
     task_id: number
     passphrase: string
@@ -45,6 +103,52 @@ export interface Project {
     mcp_connected?: boolean
 }
 
+export interface Setting {
+    key: string
+    kind: 'bool' | 'text' | 'url' | 'number' | 'secret' | 'list'
+    category: string
+    description: string
+    default?: string
+    env_var?: string
+    store: 'state' | 'config_file'
+    value: string | null
+    env_override: boolean
+}
+
+export interface SettingChange {
+    id: number
+    key: string
+    old_value: string | null
+    new_value: string | null
+    source: string
+    changed_at: string
+}
+
+export interface ProjectOverview {
+    name: string
+    path: string
+    is_host: boolean
+    is_active: boolean
+    task_counts: Record<string, number>
+    focus: { session_id: string, task_id: number, task_name: string, task_status: string }[]
+    last_activity?: number
+    error?: string
+}
+
+export interface CrossProjectLink {
+    from_project_name: string
+    from_project_path: string
+    blocked_task_id: number
+    blocked_task_name: string
+    project_path: string
+    project_name: string
+    blocking_task_id: number
+    blocking_task_name?: string
+    blocking_task_status?: string
+    satisfied: boolean
+    error?: string
+}
+
 export interface PaginationState {
     page: number
     limit: number
@@ -57,11 +161,22 @@ export const useAppStore = defineStore('app', () => {
     const isConnected = ref(false)
     const tasks = ref<Task[]>([])
     const events = ref<Event[]>([])
+    // Cursor for older events (null when the full history is loaded)
+    const eventsCursor = ref<number | null>(null)
     const searchResults = ref<UnifiedSearchResult[]>([])
+    // Subtree rollups keyed by root task id, dropped whenever tasks reload
+    const subtreeStats = ref<Record<number, SubtreeStats>>({})
+    const pendingSubtreeStats = new Set<number>()
+    // Loaded tree levels keyed by parent id ('root' for top level tasks)
+    const treeCursors = ref<Record<string, number | null>>({})
+    const expandedTaskIds = ref(new Set<number>())
     const currentTaskId = ref<number | null>(null)
     const viewingTaskId = ref<number | null>(null)
     const projects = ref<Project[]>([])
     const currentProject = ref<Project | null>(null)
+    // Cross-project summary keyed by project path
+    const projectOverviews = ref<Record<string, ProjectOverview>>({})
+    const crossProjectLinks = ref<CrossProjectLink[]>([])
     const isCyberpunkMode = ref(false)
     const lastError = ref<string | null>(null)
 
@@ -175,17 +290,36 @@ export const useAppStore = defineStore('app', () => {
         }
     }
 
+    // Protocol v2: only follow the topics this view shows
+    function subscribeTopics() {
+        if (!ws || ws.readyState !== WebSocket.OPEN) return
+        const topics = ['tasks', 'workspace']
+        if (viewingTaskId.value) topics.push(`events:task:${viewingTaskId.value}`)
+        ws.send(JSON.stringify({
+            version: '1.0',
+            type: 'subscribe',
+            payload: { topics },
+            timestamp: new Date().toISOString()
+        }))
+    }
+
+    watch(viewingTaskId, () => subscribeTopics())
+
     function handleMessage(msg: any) {
         if (!msg) return
         switch (msg.type) {
+            case 'welcome':
+                if (msg.payload.capabilities?.includes('topics')) subscribeTopics()
+                break
             case 'init':
                 projects.value = msg.payload.projects
                 fetchCurrentTask().then(() => fetchTasks())
+                fetchGlobalOverview()
                 break
             case 'task_created':
             case 'task_updated':
             case 'task_deleted':
-                fetchTasks(undefined, undefined, pagination.value.page)
+                fetchTasks()
                 if (viewingTaskId.value) fetchTaskDetail(viewingTaskId.value)
                 break
             case 'event_created':
@@ -194,9 +328,11 @@ export const useAppStore = defineStore('app', () => {
                 }
                 break
             case 'db_operation':
+            case 'db_patch':
                 const op = msg.payload
+                fetchGlobalOverview()
                 if (op.entity === 'task') {
-                    fetchTasks(undefined, undefined, pagination.value.page)
+                    fetchTasks()
                     // Always refresh current task state as it might have changed (e.g. start/done)
                     fetchCurrentTask()
                     if (viewingTaskId.value && op.affected_ids.includes(viewingTaskId.value)) {
@@ -210,6 +346,26 @@ export const useAppStore = defineStore('app', () => {
                     }
                 }
                 break
+            case 'resync':
+                // Server dropped notifications for this client; refetch everything
+                fetchCurrentTask().then(() => fetchTasks())
+                if (viewingTaskId.value) {
+                    fetchTaskDetail(viewingTaskId.value)
+                    fetchEvents(viewingTaskId.value)
+                }
+                break
+            case 'focus_reminder':
+                // A focus session's time is up; the desktop notification may be off
+                if ('Notification' in window && Notification.permission !== 'denied') {
+                    const { task, minutes } = msg.payload
+                    Notification.requestPermission().then(permission => {
+                        if (permission !== 'granted') return
+                        new Notification('Focus session over', {
+                            body: `${minutes} min on #${task.id} ${task.name} are up`
+                        })
+                    })
+                }
+                break
             case 'project_online':
                 {
                     const newProject = msg.payload.project
@@ -238,31 +394,44 @@ export const useAppStore = defineStore('app', () => {
         }
     }
 
-    async function fetchTasks(status?: string, parentId?: number | null, page: number = 1) {
+    async function fetchChildrenPage(parentId: number | null, cursor?: number | null): Promise<ChildrenPage> {
+        let url = `/api/tasks/tree?limit=${TREE_PAGE_SIZE}`
+        if (parentId !== null) url += `&parent=${parentId}`
+        if (cursor) url += `&cursor=${cursor}`
+        const res = await fetch(url)
+        if (!res.ok) throw new Error('Failed to fetch tasks')
+        const json = await res.json()
+        return json.data
+    }
+
+    // Reload root tasks and the first page of every expanded task, one level each
+    async function fetchTasks() {
         try {
-            const offset = (page - 1) * pagination.value.limit
-            let url = `/api/tasks?offset=${offset}&limit=${pagination.value.limit}`
-            if (status) url += `&status=${status}`
-            if (parentId !== undefined) {
-                url += `&parent=${parentId === null ? 'null' : parentId}`
+            const parents: (number | null)[] = [null, ...expandedTaskIds.value]
+            const pages = await Promise.all(parents.map(id => fetchChildrenPage(id)))
+
+            subtreeStats.value = {}
+            const cursors: Record<string, number | null> = {}
+            const loaded = new Map<number, Task>()
+            pages.forEach((page, i) => {
+                cursors[parents[i] ?? 'root'] = page.next_cursor ?? null
+                page.tasks.forEach(task => loaded.set(task.id, task))
+            })
+            // Drop levels whose parent is no longer shown (collapsed or deleted higher up)
+            let kept = [...loaded.values()]
+            for (;;) {
+                const ids = new Set(kept.map(t => t.id))
+                const next = kept.filter(t => !t.parent_id || ids.has(t.parent_id))
+                if (next.length === kept.length) break
+                kept = next
             }
-
-            const res = await fetch(url)
-            if (!res.ok) throw new Error('Failed to fetch tasks')
-            const json = await res.json()
-
-            if (json.data.tasks) {
-                tasks.value = json.data.tasks
-                pagination.value = {
-                    page: Math.floor(json.data.offset / json.data.limit) + 1,
-                    limit: json.data.limit,
-                    total: json.data.total_count,
-                    totalPages: Math.ceil(json.data.total_count / json.data.limit)
-                }
-            } else {
-                // Fallback for unexpected structure
-                tasks.value = json.data
-                pagination.value.total = json.data.length
+            tasks.value = kept
+            treeCursors.value = cursors
+            pagination.value = {
+                page: 1,
+                limit: TREE_PAGE_SIZE,
+                total: pages[0]!.total_count,
+                totalPages: 1
             }
 
             // Validation and auto-select logic
@@ -275,59 +444,42 @@ export const useAppStore = defineStore('app', () => {
                     fetchTaskDetail(tasks.value[0].id)
                 }
             }
-
-            validateTaskOrder(tasks.value)
         } catch (e) {
             console.error('Failed to fetch tasks:', e)
             lastError.value = (e as Error).message
         }
     }
 
-    function validateTaskOrder(taskList: Task[]) {
-        if (taskList.length < 2) return
-
-        for (let i = 0; i < taskList.length - 1; i++) {
-            const current = taskList[i]
-            const next = taskList[i + 1]
-            if (!current || !next) continue
-
-            // 1. Focus check (if current is focused, it should be first - but we might be past the first item)
-            // Actually, if 'next' is focused and 'current' is not, that's an error.
-            const isCurrentFocused = current.id === currentTaskId.value
-            const isNextFocused = next.id === currentTaskId.value
-
-            if (isNextFocused && !isCurrentFocused) {
-                console.error(`[Sort Error] Focused task ${next.id} is not at the top. Found after ${current.id}`)
-                continue
-            }
-            if (isCurrentFocused) continue // Focused task is allowed to be first
-
-            // 2. Status check: doing < todo < done
-            const statusOrder = { 'doing': 0, 'todo': 1, 'done': 2 }
-            const currentStatusScore = statusOrder[current.status]
-            const nextStatusScore = statusOrder[next.status]
-
-            if (currentStatusScore > nextStatusScore) {
-                console.error(`[Sort Error] Status order violated. ${current.status} (ID: ${current.id}) came before ${next.status} (ID: ${next.id})`)
-                continue
-            }
-            if (currentStatusScore < nextStatusScore) continue
-
-            // 3. Priority check: 1 < 2 < 3 < null (999)
-            const getPriority = (p: number | null) => p === null ? 999 : p
-            const currentPriority = getPriority(current.priority)
-            const nextPriority = getPriority(next.priority)
-
-            if (currentPriority > nextPriority) {
-                console.error(`[Sort Error] Priority order violated. P${current.priority} (ID: ${current.id}) came before P${next.priority} (ID: ${next.id})`)
-                continue
-            }
-            if (currentPriority < nextPriority) continue
+    // Append the next page of a tree level (null for root tasks)
+    async function loadMoreChildren(parentId: number | null) {
+        const key = parentId ?? 'root'
+        const cursor = treeCursors.value[key]
+        if (!cursor) return
+        try {
+            const page = await fetchChildrenPage(parentId, cursor)
+            const known = new Set(tasks.value.map(t => t.id))
+            tasks.value.push(...page.tasks.filter(t => !known.has(t.id)))
+            treeCursors.value[key] = page.next_cursor ?? null
+        } catch (e) {
+            console.error('Failed to load more tasks:', e)
+        }
+    }
 
-            // 4. Creation Time (ID) check: Ascending
-            if (current.id > next.id) {
-                console.error(`[Sort Error] ID/Time order violated. ID ${current.id} came before ID ${next.id}`)
-            }
+    // Expand a tree node, loading its first page of children on demand
+    async function toggleExpanded(id: number) {
+        if (expandedTaskIds.value.has(id)) {
+            expandedTaskIds.value.delete(id)
+            return
+        }
+        expandedTaskIds.value.add(id)
+        if (id in treeCursors.value) return
+        try {
+            const page = await fetchChildrenPage(id)
+            const known = new Set(tasks.value.map(t => t.id))
+            tasks.value.push(...page.tasks.filter(t => !known.has(t.id)))
+            treeCursors.value[id] = page.next_cursor ?? null
+        } catch (e) {
+            console.error('Failed to load children:', e)
         }
     }
 
@@ -399,16 +551,45 @@ export const useAppStore = defineStore('app', () => {
         }
     }
 
+    async function fetchSubtreeStats(id: number) {
+        if (pendingSubtreeStats.has(id)) return
+        pendingSubtreeStats.add(id)
+        try {
+            const res = await fetch(`/api/tasks/${id}/stats`)
+            if (!res.ok) throw new Error('Failed to fetch subtree stats')
+            const data = await res.json()
+            subtreeStats.value[id] = data.data
+        } catch (e) {
+            console.error('Failed to fetch subtree stats:', e)
+        } finally {
+            pendingSubtreeStats.delete(id)
+        }
+    }
+
     async function fetchEvents(taskId: number) {
         try {
-            const res = await fetch(`/api/tasks/${taskId}/events`)
+            const res = await fetch(`/api/tasks/${taskId}/events?limit=${EVENT_PAGE_SIZE}`)
             const data = await res.json()
-            events.value = data.data || []
+            events.value = data.data?.events || []
+            eventsCursor.value = data.data?.next_before ?? null
         } catch (e) {
             console.error('Failed to fetch events:', e)
         }
     }
 
+    // Append the next (older) page of the event timeline
+    async function loadMoreEvents(taskId: number) {
+        if (eventsCursor.value === null) return
+        try {
+            const res = await fetch(`/api/tasks/${taskId}/events?limit=${EVENT_PAGE_SIZE}&before=${eventsCursor.value}`)
+            const data = await res.json()
+            events.value = [...events.value, ...(data.data?.events || [])]
+            eventsCursor.value = data.data?.next_before ?? null
+        } catch (e) {
+            console.error('Failed to load more events:', e)
+        }
+    }
+
     async function addTask(name: string, parentId?: number | null, priority?: number | null, _spec?: string) {
         try {
             await fetch('/api/tasks', {
@@ -421,7 +602,8 @@ export const useAppStore = defineStore('app', () => {
                     spec: _spec
                 })
             })
-            fetchTasks(undefined, parentId, pagination.value.page)
+            if (parentId) expandedTaskIds.value.add(parentId)
+            fetchTasks()
         } catch (e) {
             console.error('Failed to add task:', e)
         }
@@ -452,7 +634,7 @@ export const useAppStore = defineStore('app', () => {
             tasks.value = tasks.value.filter(t => t.id !== id)
             if (currentTaskId.value === id) currentTaskId.value = null
             if (viewingTaskId.value === id) viewingTaskId.value = null
-            fetchTasks(undefined, undefined, pagination.value.page)
+            fetchTasks()
         } catch (e) {
             console.error('Failed to delete task:', e)
         }
@@ -594,7 +776,11 @@ export const useAppStore = defineStore('app', () => {
             if (targetProject) currentProject.value = targetProject
 
             tasks.value = []
+            subtreeStats.value = {}
+            treeCursors.value = {}
+            expandedTaskIds.value = new Set()
             events.value = []
+            eventsCursor.value = null
             currentTaskId.value = null
             viewingTaskId.value = null
             searchResults.value = []
@@ -606,6 +792,71 @@ export const useAppStore = defineStore('app', () => {
         }
     }
 
+    async function fetchGlobalOverview() {
+        try {
+            const res = await fetch('/api/global/overview')
+            if (!res.ok) return
+            const json = await res.json()
+            const overviews: Record<string, ProjectOverview> = {}
+            for (const overview of json.data as ProjectOverview[]) {
+                overviews[overview.path] = overview
+                // Registered projects without a live connection still get a tab
+                if (!projects.value.some(p => p.path === overview.path)) {
+                    projects.value.push({ name: overview.name, path: overview.path, is_online: overview.is_host })
+                }
+            }
+            projectOverviews.value = overviews
+
+            const linksRes = await fetch('/api/global/links')
+            if (linksRes.ok) {
+                crossProjectLinks.value = (await linksRes.json()).data as CrossProjectLink[]
+            }
+        } catch (e) {
+            console.error('Failed to fetch project overview:', e)
+        }
+    }
+
+    async function fetchSettings(): Promise<Setting[]> {
+        try {
+            const res = await fetch('/api/settings', { cache: 'no-store' })
+            if (!res.ok) return []
+            const json = await res.json()
+            return json.data as Setting[]
+        } catch (e) {
+            console.error('Failed to fetch settings:', e)
+            return []
+        }
+    }
+
+    // Resolves to the validation errors (empty on success)
+    async function saveSettings(values: Record<string, string | null>): Promise<string[]> {
+        try {
+            const res = await fetch('/api/settings', {
+                method: 'PUT',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ values })
+            })
+            if (res.ok) return []
+            const json = await res.json()
+            return json.details?.errors ?? [json.message]
+        } catch (e) {
+            console.error('Failed to save settings:', e)
+            return [(e as Error).message]
+        }
+    }
+
+    async function fetchSettingsAudit(limit: number = 50): Promise<SettingChange[]> {
+        try {
+            const res = await fetch(`/api/settings/audit?limit=${limit}`, { cache: 'no-store' })
+            if (!res.ok) return []
+            const json = await res.json()
+            return json.data as SettingChange[]
+        } catch (e) {
+            console.error('Failed to fetch settings audit:', e)
+            return []
+        }
+    }
+
     function removeProject(path: string) {
         const idx = projects.value.findIndex(p => p.path === path)
         if (idx >= 0) projects.value.splice(idx, 1)
@@ -642,11 +893,17 @@ export const useAppStore = defineStore('app', () => {
         isConnected,
         tasks,
         events,
+        eventsCursor,
         searchResults,
+        subtreeStats,
+        treeCursors,
+        expandedTaskIds,
         currentTaskId,
         viewingTaskId,
         projects,
         currentProject,
+        projectOverviews,
+        crossProjectLinks,
         isCyberpunkMode,
         lastError,
         pagination,
@@ -655,9 +912,13 @@ export const useAppStore = defineStore('app', () => {
         currentTaskDetail,
         connect,
         fetchTasks,
+        loadMoreChildren,
+        toggleExpanded,
         fetchCurrentTask,
         fetchTaskDetail,
+        fetchSubtreeStats,
         fetchEvents,
+        loadMoreEvents,
         addTask,
         updateTask,
         deleteTask,
@@ -672,6 +933,10 @@ export const useAppStore = defineStore('app', () => {
         pickNextTask,
         search,
         switchProject,
+        fetchGlobalOverview,
+        fetchSettings,
+        saveSettings,
+        fetchSettingsAudit,
         removeProject,
         removeProjectFromRegistry,
         toggleTheme
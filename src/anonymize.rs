@@ -0,0 +1,451 @@
+//! Anonymized database export for bug reports
+//!
+//! Produces a copy of the project database in which every piece of user text
+//! (task names, specs, events, metadata, suggestions, links) is replaced by a
+//! placeholder of the same shape. Structure is kept intact: IDs, statuses,
+//! priorities, owners, timestamps, the task tree and dependencies. Config
+//! values (including API keys) and the settings audit log are dropped.
+//!
+//! Placeholders are shape-preserving: each run of letters or digits is
+//! replaced by pseudo-random characters of the same length and case, while
+//! whitespace and punctuation stay put (so Markdown and JSON keep their form).
+//! Equal words map to equal placeholders within one export, so repeated names
+//! and search hits still line up; the mapping uses a random per-export key and
+//! cannot be reversed from the output.
+
+use crate::error::{IntentError, Result};
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::path::Path;
+
+/// Replaces text with same-shaped placeholders
+pub struct Anonymizer {
+    key: RandomState,
+}
+
+impl Default for Anonymizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Anonymizer {
+    /// Create an anonymizer with a fresh random key
+    pub fn new() -> Self {
+        Self {
+            key: RandomState::new(),
+        }
+    }
+
+    /// Anonymize free text, keeping whitespace, punctuation and run lengths
+    pub fn text(&self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut word = String::new();
+
+        for c in input.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                self.flush_word(&mut word, &mut output);
+                output.push(c);
+            }
+        }
+        self.flush_word(&mut word, &mut output);
+
+        output
+    }
+
+    /// Anonymize a JSON document's keys and string values, keeping it valid JSON
+    ///
+    /// Falls back to [`Anonymizer::text`] if the input is not JSON.
+    pub fn json(&self, input: &str) -> String {
+        match serde_json::from_str::<serde_json::Value>(input) {
+            Ok(value) => self.json_value(value).to_string(),
+            Err(_) => self.text(input),
+        }
+    }
+
+    fn json_value(&self, value: serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+
+        match value {
+            Value::String(s) => Value::String(self.text(&s)),
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|v| self.json_value(v)).collect())
+            },
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| (self.text(&k), self.json_value(v)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    fn flush_word(&self, word: &mut String, output: &mut String) {
+        if word.is_empty() {
+            return;
+        }
+
+        let mut state = self.key.hash_one(word.as_str());
+        for c in word.chars() {
+            state = splitmix64(state);
+            output.push(placeholder_char(c, state));
+        }
+        word.clear();
+    }
+}
+
+/// Pick a placeholder of the same class as `c`
+fn placeholder_char(c: char, random: u64) -> char {
+    if c.is_ascii_digit() {
+        (b'0' + (random % 10) as u8) as char
+    } else if c.is_ascii_uppercase() {
+        (b'A' + (random % 26) as u8) as char
+    } else if c.is_ascii() {
+        (b'a' + (random % 26) as u8) as char
+    } else {
+        // Any other letter (CJK, accented, ...) becomes a CJK ideograph:
+        // same character count, and the trigram tokenizer treats it alike
+        char::from_u32(0x4E00 + (random % 0x5000) as u32).unwrap_or('x')
+    }
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Summary of an anonymized export
+#[derive(Debug, Clone, Serialize)]
+pub struct AnonymizeReport {
+    pub output: String,
+    pub tasks: i64,
+    pub events: i64,
+    pub dependencies: i64,
+    pub suggestions: i64,
+    pub bytes: u64,
+}
+
+/// Write an anonymized copy of the database behind `pool` to `output`
+///
+/// The source database is only read. Fails if `output` already exists.
+pub async fn export_anonymized(pool: &SqlitePool, output: &Path) -> Result<AnonymizeReport> {
+    if output.exists() {
+        return Err(IntentError::ActionNotAllowed(format!(
+            "{} already exists",
+            output.display()
+        )));
+    }
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(output.to_string_lossy().to_string())
+        .execute(pool)
+        .await?;
+
+    // A single-file database (no WAL) is easier to attach to a bug report
+    let copy = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(
+            SqliteConnectOptions::new()
+                .filename(output)
+                .journal_mode(SqliteJournalMode::Delete),
+        )
+        .await?;
+
+    let result = anonymize_database(&copy, &Anonymizer::new()).await;
+    copy.close().await;
+
+    match result {
+        Ok(mut report) => {
+            report.output = output.display().to_string();
+            report.bytes = std::fs::metadata(output)?.len();
+            Ok(report)
+        },
+        Err(e) => {
+            // Never leave a half-anonymized copy behind
+            let _ = std::fs::remove_file(output);
+            Err(e)
+        },
+    }
+}
+
+/// (id, name, spec, active_form, metadata)
+type TaskText = (i64, String, Option<String>, Option<String>, Option<String>);
+
+/// Anonymize a database in place
+async fn anonymize_database(pool: &SqlitePool, anonymizer: &Anonymizer) -> Result<AnonymizeReport> {
+    let mut tx = pool.begin().await?;
+
+    let tasks: Vec<TaskText> =
+        sqlx::query_as("SELECT id, name, spec, active_form, metadata FROM tasks")
+            .fetch_all(&mut *tx)
+            .await?;
+    for (id, name, spec, active_form, metadata) in &tasks {
+        sqlx::query(
+            "UPDATE tasks SET name = ?, spec = ?, active_form = ?, metadata = ? WHERE id = ?",
+        )
+        .bind(anonymizer.text(name))
+        .bind(spec.as_deref().map(|s| anonymizer.text(s)))
+        .bind(active_form.as_deref().map(|s| anonymizer.text(s)))
+        .bind(metadata.as_deref().map(|s| anonymizer.json(s)))
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let events: Vec<(i64, String)> = sqlx::query_as("SELECT id, discussion_data FROM events")
+        .fetch_all(&mut *tx)
+        .await?;
+    for (id, data) in &events {
+        sqlx::query("UPDATE events SET discussion_data = ? WHERE id = ?")
+            .bind(anonymizer.text(data))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let revisions: Vec<(i64, String)> = sqlx::query_as("SELECT id, spec FROM spec_revisions")
+        .fetch_all(&mut *tx)
+        .await?;
+    for (id, spec) in &revisions {
+        sqlx::query("UPDATE spec_revisions SET spec = ? WHERE id = ?")
+            .bind(anonymizer.text(spec))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let suggestions: Vec<(i64, String)> = sqlx::query_as("SELECT id, content FROM suggestions")
+        .fetch_all(&mut *tx)
+        .await?;
+    for (id, content) in &suggestions {
+        sqlx::query("UPDATE suggestions SET content = ? WHERE id = ?")
+            .bind(anonymizer.text(content))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let links: Vec<(i64, String, Option<String>, Option<String>)> =
+        sqlx::query_as("SELECT id, url, title, last_error FROM task_links")
+            .fetch_all(&mut *tx)
+            .await?;
+    for (id, url, title, last_error) in &links {
+        sqlx::query("UPDATE task_links SET url = ?, title = ?, last_error = ? WHERE id = ?")
+            .bind(anonymizer.text(url))
+            .bind(title.as_deref().map(|t| anonymizer.text(t)))
+            .bind(last_error.as_deref().map(|e| anonymizer.text(e)))
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // Config may hold API keys and endpoints; the audit log holds their history
+    sqlx::query("DELETE FROM workspace_state WHERE key != 'schema_version'")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM settings_audit")
+        .execute(&mut *tx)
+        .await?;
+    // Parsed from the original specs; anonymized frontmatter is no longer YAML
+    sqlx::query("DELETE FROM spec_meta")
+        .execute(&mut *tx)
+        .await?;
+    // The plan journal keys task ids by their original names
+    sqlx::query("DELETE FROM plan_journal")
+        .execute(&mut *tx)
+        .await?;
+    // Queued Dashboard notifications carry full task and event copies
+    sqlx::query("DELETE FROM notification_queue")
+        .execute(&mut *tx)
+        .await?;
+    // A copy must not pass for the original replica on a sync hub
+    sqlx::query("DELETE FROM sync_rows")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE sync_state SET value = lower(hex(randomblob(8))) WHERE key = 'replica_id'")
+        .execute(&mut *tx)
+        .await?;
+
+    // The FTS indexes still hold the original terms; rebuild them from the new content
+    sqlx::query("INSERT INTO tasks_fts(tasks_fts) VALUES('rebuild')")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("INSERT INTO events_fts(events_fts) VALUES('rebuild')")
+        .execute(&mut *tx)
+        .await?;
+
+    let dependencies: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dependencies")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    // Drop freed pages, which would still contain the original text
+    sqlx::query("VACUUM").execute(pool).await?;
+
+    Ok(AnonymizeReport {
+        output: String::new(),
+        tasks: tasks.len() as i64,
+        events: events.len() as i64,
+        dependencies,
+        suggestions: suggestions.len() as i64,
+        bytes: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_handlers::config_commands::config_set;
+    use crate::db::{create_pool, run_migrations};
+    use crate::dependencies::add_dependency;
+    use crate::events::EventManager;
+    use crate::tasks::TaskManager;
+
+    #[test]
+    fn test_text_preserves_shape() {
+        let anonymizer = Anonymizer::new();
+        let input = "## Fix JWT login (v2)\n- call `auth::verify()` 3 times, 用户";
+        let output = anonymizer.text(input);
+
+        assert_eq!(output.chars().count(), input.chars().count());
+        assert_ne!(output, input);
+        for (a, b) in input.chars().zip(output.chars()) {
+            assert_eq!(a.is_alphanumeric(), b.is_alphanumeric(), "{a} vs {b}");
+            if !a.is_alphanumeric() {
+                assert_eq!(a, b);
+            }
+            assert_eq!(a.is_ascii_uppercase(), b.is_ascii_uppercase());
+            assert_eq!(a.is_ascii_digit(), b.is_ascii_digit());
+        }
+        assert!(output.starts_with("## "));
+    }
+
+    #[test]
+    fn test_text_is_consistent_per_word() {
+        let anonymizer = Anonymizer::new();
+        let output = anonymizer.text("login login logout");
+        let words: Vec<&str> = output.split(' ').collect();
+        assert_eq!(words[0], words[1]);
+        assert_ne!(words[0], words[2]);
+    }
+
+    #[test]
+    fn test_json_stays_valid() {
+        let anonymizer = Anonymizer::new();
+        let output = anonymizer.json(r#"{"team": "payments\nsecret", "points": 3, "tags": ["a"]}"#);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let object = value.as_object().unwrap();
+        assert_eq!(object.len(), 3);
+        assert!(object.values().any(|v| v == 3));
+        assert!(!output.contains("payments"));
+    }
+
+    #[tokio::test]
+    async fn test_export_anonymized() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = create_pool(&dir.path().join("source.db")).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let task_mgr = TaskManager::new(&pool);
+        let parent = task_mgr
+            .add_task(
+                "Acme billing migration",
+                Some("Move invoices"),
+                None,
+                None,
+                Some(1),
+                None,
+            )
+            .await
+            .unwrap();
+        let child = task_mgr
+            .add_task(
+                "Acme refunds",
+                None,
+                Some(parent.id),
+                Some("ai"),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        add_dependency(&pool, child.id, parent.id).await.ok();
+        EventManager::new(&pool)
+            .add_event(child.id, "decision", "Use Acme's Stripe account")
+            .await
+            .unwrap();
+        crate::links::LinkManager::new(&pool)
+            .add(
+                parent.id,
+                &crate::links::LinkInput::Full {
+                    url: "https://wiki.example.com/Acme/billing".to_string(),
+                    title: Some("Acme invoices".to_string()),
+                    kind: None,
+                },
+            )
+            .await
+            .unwrap();
+        config_set(&pool, "llm.api_key", "sk-very-secret")
+            .await
+            .unwrap();
+
+        let output = dir.path().join("repro.db");
+        let report = export_anonymized(&pool, &output).await.unwrap();
+        assert_eq!(report.tasks, 2);
+        assert_eq!(report.events, 1);
+
+        // Refuses to overwrite
+        assert!(export_anonymized(&pool, &output).await.is_err());
+
+        // Source is untouched
+        let source_name: String = sqlx::query_scalar("SELECT name FROM tasks WHERE id = ?")
+            .bind(parent.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(source_name, "Acme billing migration");
+
+        let copy = create_pool(&output).await.unwrap();
+        let tasks = TaskManager::new(&copy)
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap()
+            .tasks;
+        let copied_parent = tasks.iter().find(|t| t.id == parent.id).unwrap();
+        let copied_child = tasks.iter().find(|t| t.id == child.id).unwrap();
+        assert_eq!(copied_child.parent_id, Some(parent.id));
+        assert_eq!(copied_child.owner, "ai");
+        assert_eq!(copied_parent.priority, Some(1));
+        assert_eq!(copied_parent.name.len(), "Acme billing migration".len());
+        assert_ne!(copied_parent.name, "Acme billing migration");
+        // "Acme" appears in both names and maps to the same placeholder
+        assert_eq!(&copied_parent.name[..4], &copied_child.name[..4]);
+
+        let secrets: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM workspace_state WHERE key LIKE 'llm.%'")
+                .fetch_one(&copy)
+                .await
+                .unwrap();
+        assert_eq!(secrets, 0);
+
+        let hits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM events_fts WHERE events_fts MATCH 'Stripe'")
+                .fetch_one(&copy)
+                .await
+                .unwrap();
+        assert_eq!(hits, 0);
+        copy.close().await;
+
+        let raw = std::fs::read(&output).unwrap();
+        let needle = b"Acme";
+        assert!(!raw.windows(needle.len()).any(|w| w == needle));
+    }
+}
@@ -0,0 +1,1295 @@
+//! Project archives: export tasks, events and dependencies to a portable JSON
+//! file, and merge archives back into (possibly non-empty) projects.
+//!
+//! Exports are streamed row by row ([`ArchiveManager::export_to`]), either as
+//! one JSON document or as NDJSON ([`ExportFormat`]), so a project with
+//! millions of events never has to fit in memory.
+//!
+//! Importing never reuses archive IDs directly. Each archive task is matched
+//! against the target project:
+//!
+//! - **Unchanged**: a task with the same name and identical content exists;
+//!   the archive task maps onto it and only missing events are added.
+//! - **Content conflict**: a task with the same name exists but differs
+//!   (spec, status, priority, ...).
+//! - **ID collision**: no task with that name exists, but the archive task's ID
+//!   is taken by a different task in the target project.
+//! - **New**: neither; the task is created with a fresh ID.
+//!
+//! Conflicts must be resolved with [`ConflictResolution`] (a default for all
+//! conflicts and/or per-task choices). Until every conflict has a resolution,
+//! nothing is written. The whole import runs in one transaction.
+
+use crate::db::models::{Event, Task, TaskLink, TaskVisibility};
+use crate::error::{IntentError, Result};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+
+/// Current archive format version
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Portable snapshot of a project or task subtree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectArchive {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Root of the exported subtree (None for a full project export)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_task_id: Option<i64>,
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub dependencies: Vec<ArchiveDependency>,
+    #[serde(default)]
+    pub links: Vec<TaskLink>,
+}
+
+impl ProjectArchive {
+    /// Read an archive written in either [`ExportFormat`]
+    pub fn parse(content: &str) -> std::result::Result<Self, serde_json::Error> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let Some(Ok(ArchiveRecord::Header {
+            version,
+            exported_at,
+            root_task_id,
+        })) = lines.next().map(serde_json::from_str::<ArchiveRecord>)
+        else {
+            return serde_json::from_str(content);
+        };
+
+        let mut archive = ProjectArchive {
+            version,
+            exported_at,
+            root_task_id,
+            tasks: Vec::new(),
+            events: Vec::new(),
+            dependencies: Vec::new(),
+            links: Vec::new(),
+        };
+        for line in lines {
+            match serde_json::from_str(line)? {
+                ArchiveRecord::Header { .. } => {
+                    return Err(serde::de::Error::custom("second header record"))
+                },
+                ArchiveRecord::Task(task) => archive.tasks.push(task),
+                ArchiveRecord::Event(event) => archive.events.push(event),
+                ArchiveRecord::Dependency(dependency) => archive.dependencies.push(dependency),
+                ArchiveRecord::Link(link) => archive.links.push(link),
+            }
+        }
+        Ok(archive)
+    }
+}
+
+/// Layout of an exported archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// One [`ProjectArchive`] JSON document, one row per line
+    #[default]
+    Json,
+    /// One record per line, tagged by `"type"`: a header, then every task,
+    /// event, dependency and link
+    Ndjson,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            other => Err(IntentError::InvalidInput(format!(
+                "Invalid export format '{}'. Expected json or ndjson",
+                other
+            ))),
+        }
+    }
+}
+
+/// Row counts of a streamed export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ExportSummary {
+    pub tasks: usize,
+    pub events: usize,
+    pub dependencies: usize,
+    pub links: usize,
+}
+
+/// One line of an NDJSON archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ArchiveRecord {
+    Header {
+        version: u32,
+        exported_at: DateTime<Utc>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        root_task_id: Option<i64>,
+    },
+    Task(Task),
+    Event(Event),
+    Dependency(ArchiveDependency),
+    Link(TaskLink),
+}
+
+/// Writes archive records as they are read, in either format
+struct ArchiveWriter<W: Write> {
+    out: W,
+    format: ExportFormat,
+    /// Whether the current JSON array has an element yet
+    in_array: bool,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    fn new(out: W, format: ExportFormat) -> Self {
+        Self {
+            out,
+            format,
+            in_array: false,
+        }
+    }
+
+    fn header(&mut self, version: u32, root_task_id: Option<i64>) -> Result<()> {
+        let exported_at = Utc::now();
+        match self.format {
+            ExportFormat::Json => {
+                write!(self.out, "{{\"version\":{},\"exported_at\":", version)?;
+                serde_json::to_writer(&mut self.out, &exported_at)?;
+                if let Some(root) = root_task_id {
+                    write!(self.out, ",\"root_task_id\":{}", root)?;
+                }
+            },
+            ExportFormat::Ndjson => self.record(ArchiveRecord::Header {
+                version,
+                exported_at,
+                root_task_id,
+            })?,
+        }
+        Ok(())
+    }
+
+    /// Start the array of `name` (JSON only; NDJSON records carry their type)
+    fn section(&mut self, name: &str) -> Result<()> {
+        if self.format == ExportFormat::Json {
+            if name != "tasks" {
+                write!(self.out, "\n]")?;
+            }
+            write!(self.out, ",\"{}\":[", name)?;
+            self.in_array = false;
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, record: ArchiveRecord) -> Result<()> {
+        match self.format {
+            ExportFormat::Json => {
+                writeln!(self.out, "{}", if self.in_array { "," } else { "" })?;
+                self.in_array = true;
+                match record {
+                    ArchiveRecord::Header { .. } => {},
+                    ArchiveRecord::Task(task) => serde_json::to_writer(&mut self.out, &task)?,
+                    ArchiveRecord::Event(event) => serde_json::to_writer(&mut self.out, &event)?,
+                    ArchiveRecord::Dependency(dependency) => {
+                        serde_json::to_writer(&mut self.out, &dependency)?
+                    },
+                    ArchiveRecord::Link(link) => serde_json::to_writer(&mut self.out, &link)?,
+                }
+            },
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut self.out, &record)?;
+                writeln!(self.out)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        if self.format == ExportFormat::Json {
+            writeln!(self.out, "\n]}}")?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Dependency edge between two archive tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArchiveDependency {
+    pub blocking_task_id: i64,
+    pub blocked_task_id: i64,
+}
+
+/// How to resolve an import conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Keep the existing task untouched
+    Skip,
+    /// Replace the existing task's content with the archive version
+    Overwrite,
+    /// Import the archive task as a new task alongside the existing one
+    Duplicate,
+}
+
+impl std::str::FromStr for ConflictResolution {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "duplicate" => Ok(Self::Duplicate),
+            other => Err(IntentError::InvalidInput(format!(
+                "Invalid conflict resolution '{}'. Expected skip, overwrite or duplicate",
+                other
+            ))),
+        }
+    }
+}
+
+/// Why an archive task conflicts with the target project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Same name, different content
+    ContentMismatch,
+    /// Archive ID is used by an unrelated task
+    IdCollision,
+}
+
+/// A single conflict found during import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub archive_task_id: i64,
+    pub existing_task_id: i64,
+    pub kind: ConflictKind,
+    /// Archive task name
+    pub name: String,
+    /// Name of the existing task (differs from `name` for ID collisions)
+    pub existing_name: String,
+    /// Fields whose values differ
+    pub fields: Vec<String>,
+    /// Chosen resolution (None = unresolved)
+    pub resolution: Option<ConflictResolution>,
+}
+
+/// Options controlling an import
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Resolution applied to every conflict without a per-task choice
+    pub on_conflict: Option<ConflictResolution>,
+    /// Per-task choices, keyed by archive task ID
+    pub resolutions: HashMap<i64, ConflictResolution>,
+    /// Parent for archive tasks whose parent is not in the archive
+    pub parent_id: Option<i64>,
+    /// Only detect conflicts; write nothing
+    pub dry_run: bool,
+}
+
+/// Outcome of an import (or of a dry run)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// Whether changes were written
+    pub applied: bool,
+    pub conflicts: Vec<ImportConflict>,
+    /// Conflicts without a resolution (import refused while > 0)
+    pub unresolved: usize,
+    pub tasks_created: usize,
+    pub tasks_updated: usize,
+    pub tasks_skipped: usize,
+    pub tasks_unchanged: usize,
+    pub events_imported: usize,
+    pub dependencies_imported: usize,
+    #[serde(default)]
+    pub links_imported: usize,
+    /// Archive task ID → task ID in this project
+    pub id_map: BTreeMap<i64, i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Planned action for one archive task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskAction {
+    Create,
+    Unchanged(i64),
+    Overwrite(i64),
+    /// Keep the existing task; children and mapping still point to it
+    KeepExisting(i64),
+    /// Do not import at all
+    Drop,
+}
+
+pub struct ArchiveManager<'a> {
+    pool: &'a SqlitePool,
+    /// Whether private tasks are exported
+    scope: TaskVisibility,
+}
+
+impl<'a> ArchiveManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self {
+            pool,
+            scope: crate::project::task_scope(),
+        }
+    }
+
+    /// Use an explicit task scope instead of the process-wide `--scope`
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Export the whole project, or the subtree rooted at `root_task_id`
+    ///
+    /// Private tasks (and their events) are left out unless the scope is private.
+    /// Everything is loaded into memory; see [`export_to`](Self::export_to)
+    /// for large projects.
+    pub async fn export(&self, root_task_id: Option<i64>) -> Result<ProjectArchive> {
+        let sql = self.export_tasks_sql(root_task_id).await?;
+        let mut query = sqlx::query_as(&sql);
+        if let Some(root) = root_task_id {
+            query = query.bind(root);
+        }
+        let tasks: Vec<Task> = query.fetch_all(self.pool).await?;
+
+        let ids: HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+
+        let events: Vec<Event> = sqlx::query_as::<_, Event>(&format!(
+            "{} ORDER BY id",
+            crate::sql_constants::SELECT_EVENT_BASE
+        ))
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .filter(|e| ids.contains(&e.task_id))
+        .collect();
+
+        let dependencies: Vec<ArchiveDependency> = sqlx::query_as(
+            "SELECT blocking_task_id, blocked_task_id FROM dependencies ORDER BY id",
+        )
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .filter(|d: &ArchiveDependency| {
+            ids.contains(&d.blocking_task_id) && ids.contains(&d.blocked_task_id)
+        })
+        .collect();
+
+        let links: Vec<TaskLink> = crate::links::LinkManager::new(self.pool)
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|l| ids.contains(&l.task_id))
+            .collect();
+
+        Ok(ProjectArchive {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            root_task_id,
+            tasks,
+            events,
+            dependencies,
+            links,
+        })
+    }
+
+    /// Stream the same export as [`export`](Self::export) into `out`
+    ///
+    /// Rows are written as they come off the cursor, inside one read
+    /// transaction so that tasks, events, dependencies and links match. Only the
+    /// IDs of exported tasks are kept in memory.
+    pub async fn export_to<W: Write>(
+        &self,
+        root_task_id: Option<i64>,
+        format: ExportFormat,
+        out: W,
+    ) -> Result<ExportSummary> {
+        let sql = self.export_tasks_sql(root_task_id).await?;
+        let mut tx = self.pool.begin().await?;
+        let mut writer = ArchiveWriter::new(out, format);
+        let mut summary = ExportSummary::default();
+        writer.header(ARCHIVE_VERSION, root_task_id)?;
+
+        writer.section("tasks")?;
+        let mut ids = HashSet::new();
+        {
+            let mut query = sqlx::query_as::<_, Task>(&sql);
+            if let Some(root) = root_task_id {
+                query = query.bind(root);
+            }
+            let mut rows = query.fetch(&mut *tx);
+            while let Some(task) = rows.try_next().await? {
+                ids.insert(task.id);
+                writer.record(ArchiveRecord::Task(task))?;
+                summary.tasks += 1;
+            }
+        }
+
+        writer.section("events")?;
+        {
+            let sql = format!("{} ORDER BY id", crate::sql_constants::SELECT_EVENT_BASE);
+            let mut rows = sqlx::query_as::<_, Event>(&sql).fetch(&mut *tx);
+            while let Some(event) = rows.try_next().await? {
+                if ids.contains(&event.task_id) {
+                    writer.record(ArchiveRecord::Event(event))?;
+                    summary.events += 1;
+                }
+            }
+        }
+
+        writer.section("dependencies")?;
+        {
+            let mut rows = sqlx::query_as::<_, ArchiveDependency>(
+                "SELECT blocking_task_id, blocked_task_id FROM dependencies ORDER BY id",
+            )
+            .fetch(&mut *tx);
+            while let Some(dependency) = rows.try_next().await? {
+                if ids.contains(&dependency.blocking_task_id)
+                    && ids.contains(&dependency.blocked_task_id)
+                {
+                    writer.record(ArchiveRecord::Dependency(dependency))?;
+                    summary.dependencies += 1;
+                }
+            }
+        }
+
+        writer.section("links")?;
+        {
+            let sql = format!(
+                "SELECT {} FROM task_links ORDER BY id",
+                crate::links::LINK_COLUMNS
+            );
+            let mut rows = sqlx::query_as::<_, TaskLink>(&sql).fetch(&mut *tx);
+            while let Some(link) = rows.try_next().await? {
+                if ids.contains(&link.task_id) {
+                    writer.record(ArchiveRecord::Link(link))?;
+                    summary.links += 1;
+                }
+            }
+        }
+
+        writer.finish()?;
+        Ok(summary)
+    }
+
+    /// Query for the exported tasks, binding the root task ID if there is one
+    async fn export_tasks_sql(&self, root_task_id: Option<i64>) -> Result<String> {
+        let filter = self.scope.sql_filter();
+        let Some(root) = root_task_id else {
+            return Ok(format!(
+                "{} WHERE {} ORDER BY id",
+                crate::sql_constants::SELECT_TASK_FULL,
+                filter
+            ));
+        };
+
+        let exists: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND {})",
+            filter
+        ))
+        .bind(root)
+        .fetch_one(self.pool)
+        .await?;
+        if !exists {
+            return Err(IntentError::TaskNotFound(root));
+        }
+
+        Ok(format!(
+            r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT ?
+                UNION ALL
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT {} FROM tasks WHERE id IN (SELECT id FROM subtree) AND {} ORDER BY id
+            "#,
+            crate::sql_constants::TASK_COLUMNS,
+            filter
+        ))
+    }
+
+    /// Import an archive, merging it into the current project
+    ///
+    /// Returns the report without writing anything when `dry_run` is set or
+    /// when some conflicts have no resolution (`applied == false`).
+    pub async fn import(
+        &self,
+        archive: &ProjectArchive,
+        options: &ImportOptions,
+    ) -> Result<ImportReport> {
+        if archive.version > ARCHIVE_VERSION {
+            return Err(IntentError::InvalidInput(format!(
+                "Archive version {} is newer than supported version {}",
+                archive.version, ARCHIVE_VERSION
+            )));
+        }
+        validate_archive(archive)?;
+
+        if let Some(parent) = options.parent_id {
+            let exists: bool = sqlx::query_scalar(crate::sql_constants::CHECK_TASK_EXISTS)
+                .bind(parent)
+                .fetch_one(self.pool)
+                .await?;
+            if !exists {
+                return Err(IntentError::TaskNotFound(parent));
+            }
+        }
+
+        let existing: Vec<Task> = sqlx::query_as(crate::sql_constants::SELECT_TASK_FULL)
+            .fetch_all(self.pool)
+            .await?;
+        let (actions, mut report) = plan_actions(archive, &existing, options);
+
+        if options.dry_run || report.unresolved > 0 {
+            return Ok(report);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // Parents first so that children can reference mapped parent IDs
+        for task in ordered_for_insert(&archive.tasks) {
+            let action = actions[&task.id];
+            match action {
+                TaskAction::Create => {
+                    let parent_id = match task.parent_id {
+                        Some(pid) if report.id_map.contains_key(&pid) => Some(report.id_map[&pid]),
+                        _ => options.parent_id,
+                    };
+                    let new_id = insert_task(&mut tx, task, parent_id).await?;
+                    report.id_map.insert(task.id, new_id);
+                    report.tasks_created += 1;
+                },
+                TaskAction::Overwrite(target) => {
+                    overwrite_task(&mut tx, target, task).await?;
+                    report.id_map.insert(task.id, target);
+                    report.tasks_updated += 1;
+                },
+                TaskAction::Unchanged(target) => {
+                    report.id_map.insert(task.id, target);
+                    report.tasks_unchanged += 1;
+                },
+                TaskAction::KeepExisting(target) => {
+                    report.id_map.insert(task.id, target);
+                    report.tasks_skipped += 1;
+                },
+                TaskAction::Drop => report.tasks_skipped += 1,
+            }
+        }
+
+        // Events: only for tasks whose content came from (or matches) the archive
+        for event in &archive.events {
+            let target = match actions.get(&event.task_id) {
+                Some(TaskAction::Create)
+                | Some(TaskAction::Overwrite(_))
+                | Some(TaskAction::Unchanged(_)) => report.id_map[&event.task_id],
+                _ => continue,
+            };
+
+            let exists: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM events
+                    WHERE task_id = ? AND log_type = ? AND discussion_data = ?
+                      AND datetime(timestamp) = datetime(?)
+                )
+                "#,
+            )
+            .bind(target)
+            .bind(&event.log_type)
+            .bind(&event.discussion_data)
+            .bind(event.timestamp)
+            .fetch_one(&mut *tx)
+            .await?;
+            if exists {
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO events (task_id, timestamp, log_type, discussion_data, visibility, actor) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(target)
+            .bind(event.timestamp)
+            .bind(&event.log_type)
+            .bind(&event.discussion_data)
+            .bind(event.visibility)
+            .bind(event.actor.as_ref().map(sqlx::types::Json))
+            .execute(&mut *tx)
+            .await?;
+            report.events_imported += 1;
+        }
+
+        for dep in &archive.dependencies {
+            let (Some(&blocking), Some(&blocked)) = (
+                report.id_map.get(&dep.blocking_task_id),
+                report.id_map.get(&dep.blocked_task_id),
+            ) else {
+                continue;
+            };
+            if blocking == blocked {
+                continue;
+            }
+
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM dependencies WHERE blocking_task_id = ? AND blocked_task_id = ?)",
+            )
+            .bind(blocking)
+            .bind(blocked)
+            .fetch_one(&mut *tx)
+            .await?;
+            if exists {
+                continue;
+            }
+
+            if crate::dependencies::check_circular_dependency(&mut *tx, blocking, blocked).await? {
+                report.warnings.push(format!(
+                    "Skipped dependency {} -> {}: would create a cycle with existing dependencies",
+                    blocking, blocked
+                ));
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO dependencies (blocking_task_id, blocked_task_id) VALUES (?, ?)",
+            )
+            .bind(blocking)
+            .bind(blocked)
+            .execute(&mut *tx)
+            .await?;
+            report.dependencies_imported += 1;
+        }
+
+        // Links, like events, follow task content; a URL already linked is kept
+        for link in &archive.links {
+            let target = match actions.get(&link.task_id) {
+                Some(TaskAction::Create)
+                | Some(TaskAction::Overwrite(_))
+                | Some(TaskAction::Unchanged(_)) => report.id_map[&link.task_id],
+                _ => continue,
+            };
+
+            let inserted = sqlx::query(
+                "INSERT OR IGNORE INTO task_links (task_id, url, title, kind, created_at) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(target)
+            .bind(&link.url)
+            .bind(&link.title)
+            .bind(&link.kind)
+            .bind(link.created_at)
+            .execute(&mut *tx)
+            .await?;
+            report.links_imported += inserted.rows_affected() as usize;
+        }
+
+        tx.commit().await?;
+        report.applied = true;
+
+        Ok(report)
+    }
+}
+
+/// Reject archives whose internal references are inconsistent
+fn validate_archive(archive: &ProjectArchive) -> Result<()> {
+    let mut ids = HashSet::new();
+    for task in &archive.tasks {
+        if !ids.insert(task.id) {
+            return Err(IntentError::InvalidInput(format!(
+                "Archive contains task {} more than once",
+                task.id
+            )));
+        }
+    }
+
+    if let Some(event) = archive.events.iter().find(|e| !ids.contains(&e.task_id)) {
+        return Err(IntentError::InvalidInput(format!(
+            "Archive event {} references task {} which is not in the archive",
+            event.id, event.task_id
+        )));
+    }
+
+    if let Some(link) = archive.links.iter().find(|l| !ids.contains(&l.task_id)) {
+        return Err(IntentError::InvalidInput(format!(
+            "Archive link {} references task {} which is not in the archive",
+            link.id, link.task_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Classify every archive task and apply the chosen resolutions
+fn plan_actions(
+    archive: &ProjectArchive,
+    existing: &[Task],
+    options: &ImportOptions,
+) -> (HashMap<i64, TaskAction>, ImportReport) {
+    let by_id: HashMap<i64, &Task> = existing.iter().map(|t| (t.id, t)).collect();
+    let mut by_name: HashMap<&str, Vec<&Task>> = HashMap::new();
+    for task in existing {
+        by_name.entry(task.name.as_str()).or_default().push(task);
+    }
+
+    let mut actions = HashMap::new();
+    let mut report = ImportReport::default();
+
+    for task in &archive.tasks {
+        // Prefer the same-ID task among name matches, else the oldest one
+        let name_match = by_name.get(task.name.as_str()).and_then(|candidates| {
+            candidates
+                .iter()
+                .find(|c| c.id == task.id)
+                .or_else(|| candidates.first())
+                .copied()
+        });
+
+        let (existing_task, kind) = match name_match {
+            Some(found) => {
+                if differing_fields(task, found).is_empty() {
+                    actions.insert(task.id, TaskAction::Unchanged(found.id));
+                    continue;
+                }
+                (found, ConflictKind::ContentMismatch)
+            },
+            None => match by_id.get(&task.id) {
+                Some(found) => (*found, ConflictKind::IdCollision),
+                None => {
+                    actions.insert(task.id, TaskAction::Create);
+                    continue;
+                },
+            },
+        };
+
+        let resolution = options
+            .resolutions
+            .get(&task.id)
+            .copied()
+            .or(options.on_conflict);
+
+        let action = match (resolution, kind) {
+            (None, _) => {
+                report.unresolved += 1;
+                TaskAction::Drop
+            },
+            (Some(ConflictResolution::Skip), ConflictKind::ContentMismatch) => {
+                TaskAction::KeepExisting(existing_task.id)
+            },
+            (Some(ConflictResolution::Skip), ConflictKind::IdCollision) => TaskAction::Drop,
+            (Some(ConflictResolution::Overwrite), _) => TaskAction::Overwrite(existing_task.id),
+            (Some(ConflictResolution::Duplicate), _) => TaskAction::Create,
+        };
+        actions.insert(task.id, action);
+
+        report.conflicts.push(ImportConflict {
+            archive_task_id: task.id,
+            existing_task_id: existing_task.id,
+            kind,
+            name: task.name.clone(),
+            existing_name: existing_task.name.clone(),
+            fields: differing_fields(task, existing_task)
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            resolution,
+        });
+    }
+
+    (actions, report)
+}
+
+/// Content fields that differ between an archive task and an existing task
+fn differing_fields(archive: &Task, existing: &Task) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if archive.name != existing.name {
+        fields.push("name");
+    }
+    if archive.spec.as_deref().unwrap_or("") != existing.spec.as_deref().unwrap_or("") {
+        fields.push("spec");
+    }
+    if archive.status != existing.status {
+        fields.push("status");
+    }
+    if archive.priority != existing.priority {
+        fields.push("priority");
+    }
+    if archive.complexity != existing.complexity {
+        fields.push("complexity");
+    }
+    if archive.estimate_minutes != existing.estimate_minutes {
+        fields.push("estimate_minutes");
+    }
+    if archive.owner != existing.owner {
+        fields.push("owner");
+    }
+    if archive.active_form != existing.active_form {
+        fields.push("active_form");
+    }
+    let metadata = |t: &Task| {
+        t.metadata
+            .clone()
+            .filter(|m| !m.is_empty() && m != "{}")
+            .unwrap_or_default()
+    };
+    if metadata(archive) != metadata(existing) {
+        fields.push("metadata");
+    }
+    fields
+}
+
+/// Order tasks so that every parent precedes its children
+fn ordered_for_insert(tasks: &[Task]) -> Vec<&Task> {
+    let ids: HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+    let mut done: HashSet<i64> = HashSet::new();
+    let mut ordered = Vec::with_capacity(tasks.len());
+    let mut remaining: Vec<&Task> = tasks.iter().collect();
+
+    while !remaining.is_empty() {
+        let before = remaining.len();
+        remaining.retain(|task| {
+            let ready = match task.parent_id {
+                Some(pid) if ids.contains(&pid) => done.contains(&pid),
+                _ => true,
+            };
+            if ready {
+                done.insert(task.id);
+                ordered.push(*task);
+            }
+            !ready
+        });
+
+        // Parent cycle inside the archive: import the rest as roots
+        if remaining.len() == before {
+            ordered.append(&mut remaining);
+        }
+    }
+
+    ordered
+}
+
+async fn insert_task(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    task: &Task,
+    parent_id: Option<i64>,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO tasks (parent_id, name, spec, status, complexity, priority,
+                           first_todo_at, first_doing_at, first_done_at,
+                           active_form, owner, metadata, sort_order, estimate_minutes,
+                           visibility, blocked_reason)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(parent_id)
+    .bind(&task.name)
+    .bind(&task.spec)
+    .bind(&task.status)
+    .bind(task.complexity)
+    .bind(task.priority)
+    .bind(task.first_todo_at)
+    .bind(task.first_doing_at)
+    .bind(task.first_done_at)
+    .bind(&task.active_form)
+    .bind(&task.owner)
+    .bind(task.metadata.as_deref().unwrap_or("{}"))
+    .bind(task.sort_order)
+    .bind(task.estimate_minutes)
+    .bind(task.visibility)
+    .bind(&task.blocked_reason)
+    .execute(&mut **tx)
+    .await?;
+
+    let id = result.last_insert_rowid();
+    crate::spec_meta::sync(tx, id, task.spec.as_deref()).await?;
+    Ok(id)
+}
+
+/// Replace an existing task's content (its position in the tree is kept)
+async fn overwrite_task(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    target_id: i64,
+    task: &Task,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE tasks
+        SET name = ?, spec = ?, status = ?, complexity = ?, priority = ?,
+            first_todo_at = ?, first_doing_at = ?, first_done_at = ?,
+            active_form = ?, owner = ?, metadata = ?, estimate_minutes = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&task.name)
+    .bind(&task.spec)
+    .bind(&task.status)
+    .bind(task.complexity)
+    .bind(task.priority)
+    .bind(task.first_todo_at)
+    .bind(task.first_doing_at)
+    .bind(task.first_done_at)
+    .bind(&task.active_form)
+    .bind(&task.owner)
+    .bind(task.metadata.as_deref().unwrap_or("{}"))
+    .bind(task.estimate_minutes)
+    .bind(target_id)
+    .execute(&mut **tx)
+    .await?;
+
+    crate::spec_meta::sync(tx, target_id, task.spec.as_deref()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    async fn sample_archive() -> ProjectArchive {
+        let ctx = sample_project().await;
+        ArchiveManager::new(ctx.pool()).export(None).await.unwrap()
+    }
+
+    async fn sample_project() -> TestContext {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let parent = task_mgr
+            .add_task("Auth", Some("JWT auth"), None, None, None, None)
+            .await
+            .unwrap();
+        let child = task_mgr
+            .add_task("Login form", None, Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+        let other = task_mgr
+            .add_task("Docs", None, None, None, None, None)
+            .await
+            .unwrap();
+        crate::dependencies::add_dependency(ctx.pool(), child.id, other.id)
+            .await
+            .unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(parent.id, "decision", "Use JWT")
+            .await
+            .unwrap();
+        crate::links::LinkManager::new(ctx.pool())
+            .add(
+                parent.id,
+                &crate::links::LinkInput::Url("docs/auth.md".to_string()),
+            )
+            .await
+            .unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_export_to_streams_both_formats() {
+        let ctx = sample_project().await;
+        let manager = ArchiveManager::new(ctx.pool());
+        let expected = manager.export(None).await.unwrap();
+        let ids = |archive: &ProjectArchive| {
+            (
+                archive.tasks.iter().map(|t| t.id).collect::<Vec<_>>(),
+                archive.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+                archive.dependencies.clone(),
+                archive.links.iter().map(|l| l.id).collect::<Vec<_>>(),
+            )
+        };
+
+        for format in [ExportFormat::Json, ExportFormat::Ndjson] {
+            let mut out = Vec::new();
+            let summary = manager.export_to(None, format, &mut out).await.unwrap();
+            assert_eq!(
+                summary,
+                ExportSummary {
+                    tasks: 3,
+                    events: 1,
+                    dependencies: 1,
+                    links: 1,
+                }
+            );
+
+            let content = String::from_utf8(out).unwrap();
+            let archive = ProjectArchive::parse(&content).unwrap();
+            assert_eq!(archive.version, ARCHIVE_VERSION);
+            assert_eq!(archive.root_task_id, None);
+            assert_eq!(ids(&archive), ids(&expected), "{:?}", format);
+            if format == ExportFormat::Ndjson {
+                // Header, 3 tasks, 1 event, 1 dependency, 1 link
+                assert_eq!(content.lines().count(), 7);
+                assert!(content.starts_with("{\"type\":\"header\""));
+            }
+        }
+
+        let mut out = Vec::new();
+        manager
+            .export_to(Some(1), ExportFormat::Json, &mut out)
+            .await
+            .unwrap();
+        let subtree = ProjectArchive::parse(std::str::from_utf8(&out).unwrap()).unwrap();
+        assert_eq!(subtree.root_task_id, Some(1));
+        assert_eq!(subtree.tasks.len(), 2);
+        // The dependency points outside the subtree
+        assert!(subtree.dependencies.is_empty());
+
+        let missing = manager
+            .export_to(Some(999), ExportFormat::Ndjson, Vec::new())
+            .await;
+        assert!(matches!(missing, Err(IntentError::TaskNotFound(999))));
+    }
+
+    #[tokio::test]
+    async fn test_export_subtree() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let parent = task_mgr
+            .add_task("Parent", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .add_task("Child", None, Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .add_task("Unrelated", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let archive = ArchiveManager::new(ctx.pool())
+            .export(Some(parent.id))
+            .await
+            .unwrap();
+        assert_eq!(archive.root_task_id, Some(parent.id));
+        assert_eq!(
+            archive
+                .tasks
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Parent", "Child"]
+        );
+
+        let missing = ArchiveManager::new(ctx.pool()).export(Some(999)).await;
+        assert!(matches!(missing, Err(IntentError::TaskNotFound(999))));
+    }
+
+    #[tokio::test]
+    async fn test_import_into_empty_project() {
+        let archive = sample_archive().await;
+        let ctx = TestContext::new().await;
+
+        let report = ArchiveManager::new(ctx.pool())
+            .import(&archive, &ImportOptions::default())
+            .await
+            .unwrap();
+
+        assert!(report.applied);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.tasks_created, 3);
+        assert_eq!(report.events_imported, 1);
+        assert_eq!(report.dependencies_imported, 1);
+        assert_eq!(report.links_imported, 1);
+
+        let links = crate::links::LinkManager::new(ctx.pool())
+            .list(report.id_map[&1])
+            .await
+            .unwrap();
+        assert_eq!(links[0].url, "docs/auth.md");
+        assert_eq!(links[0].kind, "doc");
+
+        let child = TaskManager::new(ctx.pool())
+            .get_task(report.id_map[&2])
+            .await
+            .unwrap();
+        assert_eq!(child.parent_id, Some(report.id_map[&1]));
+    }
+
+    #[tokio::test]
+    async fn test_reimport_is_idempotent() {
+        let archive = sample_archive().await;
+        let ctx = TestContext::new().await;
+        let manager = ArchiveManager::new(ctx.pool());
+
+        manager
+            .import(&archive, &ImportOptions::default())
+            .await
+            .unwrap();
+        let report = manager
+            .import(&archive, &ImportOptions::default())
+            .await
+            .unwrap();
+
+        assert!(report.applied);
+        assert_eq!(report.tasks_unchanged, 3);
+        assert_eq!(report.tasks_created, 0);
+        assert_eq!(report.events_imported, 0);
+        assert_eq!(report.dependencies_imported, 0);
+        assert_eq!(report.links_imported, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conflicts_require_resolution() {
+        let archive = sample_archive().await;
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        // Same name, different spec
+        task_mgr
+            .add_task("Auth", Some("OAuth instead"), None, None, None, None)
+            .await
+            .unwrap();
+        // Occupies archive ID 2 with an unrelated task
+        task_mgr
+            .add_task("Unrelated", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let manager = ArchiveManager::new(ctx.pool());
+        let report = manager
+            .import(&archive, &ImportOptions::default())
+            .await
+            .unwrap();
+
+        assert!(!report.applied);
+        assert_eq!(report.unresolved, 2);
+        let kinds: Vec<_> = report.conflicts.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![ConflictKind::ContentMismatch, ConflictKind::IdCollision]
+        );
+        assert_eq!(report.conflicts[0].fields, vec!["spec"]);
+
+        // Nothing was written
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(ctx.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_conflict_resolutions() {
+        let archive = sample_archive().await;
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let existing_auth = task_mgr
+            .add_task("Auth", Some("OAuth instead"), None, None, None, None)
+            .await
+            .unwrap();
+        let unrelated = task_mgr
+            .add_task("Unrelated", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let mut options = ImportOptions {
+            on_conflict: Some(ConflictResolution::Overwrite),
+            ..Default::default()
+        };
+        // Keep the unrelated task that collides with archive ID 2
+        options.resolutions.insert(2, ConflictResolution::Duplicate);
+
+        let report = ArchiveManager::new(ctx.pool())
+            .import(&archive, &options)
+            .await
+            .unwrap();
+
+        assert!(report.applied);
+        assert_eq!(report.tasks_updated, 1);
+        assert_eq!(report.tasks_created, 2);
+        assert_eq!(report.id_map[&1], existing_auth.id);
+
+        let auth = task_mgr.get_task(existing_auth.id).await.unwrap();
+        assert_eq!(auth.spec.as_deref(), Some("JWT auth"));
+        let unrelated = task_mgr.get_task(unrelated.id).await.unwrap();
+        assert_eq!(unrelated.name, "Unrelated");
+
+        // The imported child hangs under the overwritten parent
+        let child = task_mgr.get_task(report.id_map[&2]).await.unwrap();
+        assert_eq!(child.name, "Login form");
+        assert_eq!(child.parent_id, Some(existing_auth.id));
+    }
+
+    #[tokio::test]
+    async fn test_skip_keeps_existing_task() {
+        let archive = sample_archive().await;
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let existing = task_mgr
+            .add_task("Auth", Some("Mine"), None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .update_task(
+                existing.id,
+                TaskUpdate {
+                    priority: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let report = ArchiveManager::new(ctx.pool())
+            .import(
+                &archive,
+                &ImportOptions {
+                    on_conflict: Some(ConflictResolution::Skip),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.tasks_skipped, 1);
+        assert_eq!(report.events_imported, 0);
+        let kept = task_mgr.get_task(existing.id).await.unwrap();
+        assert_eq!(kept.spec.as_deref(), Some("Mine"));
+    }
+
+    #[test]
+    fn test_export_format_from_str() {
+        assert_eq!(
+            "ndjson".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Ndjson
+        );
+        assert!("yaml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_resolution_from_str() {
+        assert_eq!(
+            "duplicate".parse::<ConflictResolution>().unwrap(),
+            ConflictResolution::Duplicate
+        );
+        assert!("merge".parse::<ConflictResolution>().is_err());
+    }
+
+    #[test]
+    fn test_validate_archive_rejects_orphan_events() {
+        let archive = ProjectArchive {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            root_task_id: None,
+            tasks: vec![],
+            events: vec![Event {
+                id: 1,
+                task_id: 7,
+                timestamp: Utc::now(),
+                log_type: "note".to_string(),
+                discussion_data: "x".to_string(),
+                visibility: Default::default(),
+                actor: None,
+            }],
+            dependencies: vec![],
+            links: vec![],
+        };
+        assert!(matches!(
+            validate_archive(&archive),
+            Err(IntentError::InvalidInput(_))
+        ));
+    }
+}
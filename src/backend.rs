@@ -1,16 +1,27 @@
-//! Backend trait abstractions for SQLite and Neo4j.
+//! Backend trait abstractions for SQLite, Neo4j and Postgres.
 //!
-//! These traits define the shared interface that both storage backends implement.
+//! These traits define the shared interface that every storage backend implements.
 //! Methods that exist only on one backend (e.g. SQLite's `spawn_subtask`, Neo4j's
 //! `delete_task_cascade`) remain on their concrete structs.
+//!
+//! Trait methods have no "not supported" defaults: a backend that lacks a
+//! feature implements the method with an error naming the backend, so adding a
+//! method means deciding what each backend does with it.
+//!
+//! [`Backend`] ties the per-area traits together: `ProjectContext`,
+//! `Neo4jContext` and `PostgresContext` implement it, and the
+//! `[storage] backend` config key picks which one `ie` runs against.
 
 use std::future::Future;
 
 use crate::db::models::{
-    DoneTaskResponse, Event, PaginatedTasks, PickNextResponse, StatusResponse, Task, TaskContext,
-    TaskSortBy, TaskWithEvents,
+    ChecklistItem, ChildrenPage, CloneSubtreeResponse, CompletionOutcome, DoneTaskResponse, Event,
+    EventVisibility, NewEvent, PaginatedSearchResults, PaginatedTasks, PickNextResponse,
+    SpecRevision, StatusResponse, SubtreeStats, Task, TaskContext, TaskLease, TaskSortBy,
+    TaskTransition, TaskWithEvents,
 };
 use crate::error::Result;
+use crate::event_types::EventTypes;
 use crate::plan::{PlanRequest, PlanResult};
 use crate::tasks::TaskUpdate;
 use crate::workspace::CurrentTaskResponse;
@@ -23,6 +34,20 @@ pub trait TaskBackend: Send + Sync {
 
     fn get_task_with_events(&self, id: i64) -> impl Future<Output = Result<TaskWithEvents>> + Send;
 
+    /// Recorded versions of a task's spec, oldest first
+    fn spec_history(&self, id: i64) -> impl Future<Output = Result<Vec<SpecRevision>>> + Send;
+
+    /// One page of a task's children (root tasks for None), with child counts
+    fn find_children(
+        &self,
+        parent_id: Option<i64>,
+        cursor: Option<i64>,
+        limit: Option<i64>,
+    ) -> impl Future<Output = Result<ChildrenPage>> + Send;
+
+    /// Recorded status changes of a task, oldest first
+    fn transitions(&self, id: i64) -> impl Future<Output = Result<Vec<TaskTransition>>> + Send;
+
     fn get_task_ancestry(&self, task_id: i64) -> impl Future<Output = Result<Vec<Task>>> + Send;
 
     fn get_task_context(&self, id: i64) -> impl Future<Output = Result<TaskContext>> + Send;
@@ -41,6 +66,15 @@ pub trait TaskBackend: Send + Sync {
 
     fn get_descendants(&self, task_id: i64) -> impl Future<Output = Result<Vec<Task>>> + Send;
 
+    /// Counts, estimates and complexity summed over a task and its descendants
+    fn get_subtree_stats(&self, root_id: i64) -> impl Future<Output = Result<SubtreeStats>> + Send {
+        async move {
+            let mut tasks = vec![self.get_task(root_id).await?];
+            tasks.extend(self.get_descendants(root_id).await?);
+            Ok(SubtreeStats::from_tasks(root_id, &tasks))
+        }
+    }
+
     fn get_status(
         &self,
         task_id: i64,
@@ -102,18 +136,121 @@ pub trait TaskBackend: Send + Sync {
         with_events: bool,
     ) -> impl Future<Output = Result<TaskWithEvents>> + Send;
 
-    fn done_task(
+    /// `start_task` past the project's WIP limits
+    ///
+    /// Backends without WIP limits just start the task.
+    fn start_task_over_wip_limit(
         &self,
-        is_ai_caller: bool,
+        id: i64,
+        with_events: bool,
+    ) -> impl Future<Output = Result<TaskWithEvents>> + Send {
+        self.start_task(id, with_events)
+    }
+
+    fn done_task(&self) -> impl Future<Output = Result<DoneTaskResponse>> + Send;
+
+    fn done_task_by_id(&self, id: i64) -> impl Future<Output = Result<DoneTaskResponse>> + Send;
+
+    /// `done_task` that also records what the task produced
+    ///
+    /// Backends that cannot store outcomes only accept `None`.
+    fn done_task_with_outcome(
+        &self,
+        outcome: Option<CompletionOutcome>,
     ) -> impl Future<Output = Result<DoneTaskResponse>> + Send;
 
-    fn done_task_by_id(
+    /// `done_task_by_id` that also records what the task produced
+    fn done_task_by_id_with_outcome(
         &self,
         id: i64,
-        is_ai_caller: bool,
+        outcome: Option<CompletionOutcome>,
     ) -> impl Future<Output = Result<DoneTaskResponse>> + Send;
 
+    /// Complete a task that is waiting in 'review'
+    ///
+    /// Backends without a review gate never move tasks to 'review' themselves,
+    /// so approving is a plain completion once the status and the actor
+    /// (`IE_ACTOR`; only humans approve) have been checked.
+    fn approve_task(&self, id: i64) -> impl Future<Output = Result<DoneTaskResponse>> + Send {
+        async move {
+            let task = self.get_task(id).await?;
+            if task.status != "review" {
+                return Err(crate::error::IntentError::ActionNotAllowed(format!(
+                    "Task #{} is not awaiting review (status: {})",
+                    id, task.status
+                )));
+            }
+            if crate::db::models::Actor::from_env().is_ai() {
+                return Err(crate::error::IntentError::ActionNotAllowed(format!(
+                    "Task #{} must be approved by a human, not an AI agent",
+                    id
+                )));
+            }
+            self.done_task_by_id(id).await
+        }
+    }
+
     fn pick_next(&self) -> impl Future<Output = Result<PickNextResponse>> + Send;
+
+    // ── Leases (multi-agent claiming) ───────────────────────────────
+
+    /// Atomically lease the next available task to `session_id` and start it
+    fn claim_next_for(
+        &self,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> impl Future<Output = Result<Option<Task>>> + Send;
+
+    fn renew_lease(
+        &self,
+        task_id: i64,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> impl Future<Output = Result<TaskLease>> + Send;
+
+    fn release_lease(
+        &self,
+        task_id: i64,
+        session_id: &str,
+    ) -> impl Future<Output = Result<bool>> + Send;
+
+    /// `pick_next` with every open task scored and explained
+    fn explain_next(&self) -> impl Future<Output = Result<PickNextResponse>> + Send;
+
+    /// Recompute priority boosts of waiting todo tasks
+    fn age_priorities(&self) -> impl Future<Output = Result<crate::priority::AgingResult>> + Send;
+
+    /// Flag stale doing tasks and, if asked or configured, move them back to todo
+    fn sweep_stale(
+        &self,
+        days: Option<f64>,
+        demote: bool,
+    ) -> impl Future<Output = Result<crate::stale::StaleSweep>> + Send;
+
+    /// Suggest a complexity from similar completed tasks; with `apply`, store it
+    fn estimate_complexity(
+        &self,
+        id: i64,
+        apply: bool,
+    ) -> impl Future<Output = Result<Option<crate::priority::ComplexityEstimate>>> + Send;
+
+    /// Checklist items of a task in order
+    fn checklist(&self, task_id: i64) -> impl Future<Output = Result<Vec<ChecklistItem>>> + Send;
+
+    /// Check or uncheck a checklist item (1-based position)
+    fn set_checklist_item(
+        &self,
+        task_id: i64,
+        position: i64,
+        checked: bool,
+    ) -> impl Future<Output = Result<ChecklistItem>> + Send;
+
+    /// Copy a task and its descendants
+    fn clone_subtree(
+        &self,
+        id: i64,
+        options: crate::tasks::CloneOptions,
+    ) -> impl Future<Output = Result<CloneSubtreeResponse>> + Send;
 }
 
 /// Session/workspace focus management.
@@ -144,6 +281,22 @@ pub trait EventBackend: Send + Sync {
         discussion_data: &str,
     ) -> impl Future<Output = Result<Event>> + Send;
 
+    /// Add an event at a given visibility level
+    ///
+    /// Backends without visibility support only accept `Normal`.
+    fn add_event_with_visibility(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+        visibility: EventVisibility,
+    ) -> impl Future<Output = Result<Event>> + Send;
+
+    /// Add several events in one transaction, in the given order
+    ///
+    /// Backends without batch support reject it.
+    fn add_events(&self, events: &[NewEvent]) -> impl Future<Output = Result<Vec<Event>>> + Send;
+
     fn list_events(
         &self,
         task_id: Option<i64>,
@@ -151,9 +304,68 @@ pub trait EventBackend: Send + Sync {
         log_type: Option<String>,
         since: Option<String>,
     ) -> impl Future<Output = Result<Vec<Event>>> + Send;
+
+    /// Event types this backend accepts
+    ///
+    /// Backends without a project config only know the built-in types.
+    fn event_types(&self) -> Result<EventTypes> {
+        Ok(EventTypes::builtin())
+    }
 }
 
 /// Batch plan execution.
 pub trait PlanBackend: Send + Sync {
     fn execute(&self, request: &PlanRequest) -> impl Future<Output = Result<PlanResult>> + Send;
 }
+
+/// Full-text search over tasks and events.
+pub trait SearchBackend: Send + Sync {
+    fn search(
+        &self,
+        query: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> impl Future<Output = Result<PaginatedSearchResults>> + Send;
+
+    /// Only return events of these types; an empty list means all
+    ///
+    /// Backends without type filtering reject a non-empty list.
+    fn filter_event_types(self, event_types: Vec<String>) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+/// A storage backend: hands out the per-area managers of one project.
+pub trait Backend: Send + Sync {
+    type Tasks<'a>: TaskBackend
+    where
+        Self: 'a;
+    type Workspace<'a>: WorkspaceBackend
+    where
+        Self: 'a;
+    type Events<'a>: EventBackend
+    where
+        Self: 'a;
+    type Plans<'a>: PlanBackend
+    where
+        Self: 'a;
+    type Search<'a>: SearchBackend
+    where
+        Self: 'a;
+
+    /// Name as used by the `[storage] backend` config key
+    fn name(&self) -> &'static str;
+
+    fn tasks(&self) -> Self::Tasks<'_>;
+
+    fn workspace(&self) -> Self::Workspace<'_>;
+
+    fn events(&self) -> Self::Events<'_>;
+
+    /// Plan executor; root-level tasks of a plan go under `default_parent`
+    fn plans(&self, default_parent: Option<i64>) -> Self::Plans<'_>;
+
+    fn search(&self) -> Self::Search<'_>;
+}
@@ -0,0 +1,424 @@
+//! Project bootstrap from repository analysis
+//!
+//! `ie init --analyze` proposes a first plan from work that is already
+//! written down in the repository, so a new project does not start from a
+//! blank tree:
+//!
+//! - the README's first heading and paragraph name and describe the root task,
+//! - every markdown file with open checklist items (`- [ ] ...`) becomes a
+//!   task with one subtask per item,
+//! - `TODO` and `FIXME` comments in source files become subtasks of a
+//!   "Resolve code TODOs" task, FIXMEs with high priority, each with its
+//!   location as spec.
+//!
+//! Hidden directories, dependency and build output directories (`target`,
+//! `node_modules`, ...) and files over 1 MiB are not read. The proposal is a
+//! plain [`PlanRequest`]: nothing is created until it is executed.
+
+use crate::error::Result;
+use crate::plan::{PlanRequest, PriorityValue, TaskTree};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Largest file that is read
+const MAX_FILE_BYTES: u64 = 1 << 20;
+/// Most files read in one analysis
+const MAX_FILES: usize = 10_000;
+/// Longest task name taken from a comment or checklist item
+const MAX_NAME_CHARS: usize = 100;
+/// Most subtasks per proposed task; further findings are only counted
+const MAX_GROUP_TASKS: usize = 50;
+
+/// Directories never descended into, besides hidden ones
+const SKIPPED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    "out",
+    "venv",
+    "__pycache__",
+];
+
+/// Extensions of files searched for TODO comments
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "c", "h", "cc", "cpp", "hpp", "cs",
+    "rb", "php", "swift", "scala", "sh", "lua", "sql", "vue", "svelte", "toml", "yaml", "yml",
+];
+
+/// What `analyze` found and the plan it proposes
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoAnalysis {
+    /// README the root task was described from, relative to the root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    pub files_scanned: usize,
+    pub checklist_items: usize,
+    pub todo_comments: usize,
+    /// Findings left out of the plan past the per-task limit
+    pub omitted: usize,
+    pub plan: PlanRequest,
+}
+
+/// An open item found in a file
+#[derive(Debug, Clone, PartialEq)]
+struct Finding {
+    path: String,
+    line: usize,
+    text: String,
+    fixme: bool,
+}
+
+/// Scan the repository at `root` and propose a plan
+pub fn analyze(root: &Path) -> Result<RepoAnalysis> {
+    let mut files = Vec::new();
+    collect_files(root, root, &mut files)?;
+
+    let mut files_scanned = 0;
+    let mut checklists: Vec<(String, Vec<Finding>)> = Vec::new();
+    let mut todos = Vec::new();
+    for (rel, path) in &files {
+        let Ok(text) = std::fs::read_to_string(path) else {
+            continue; // binary or unreadable
+        };
+        files_scanned += 1;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        if extension == "md" || extension == "markdown" {
+            let items = checklist_items(rel, &text);
+            if !items.is_empty() {
+                checklists.push((rel.clone(), items));
+            }
+        } else if SOURCE_EXTENSIONS.contains(&extension.as_str()) {
+            todos.extend(todo_comments(rel, &text));
+        }
+    }
+
+    let readme = files
+        .iter()
+        .map(|(rel, _)| rel)
+        .find(|rel| !rel.contains('/') && rel.to_ascii_lowercase().starts_with("readme"))
+        .cloned();
+    let (title, summary) = match &readme {
+        Some(rel) => std::fs::read_to_string(root.join(rel))
+            .map(|text| readme_intro(&text))
+            .unwrap_or_default(),
+        None => (None, None),
+    };
+    let root_name = title.unwrap_or_else(|| {
+        root.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Project".to_string())
+    });
+
+    let mut used = HashSet::from([root_name.clone()]);
+    let mut omitted = 0;
+    let mut children = Vec::new();
+    let checklist_count = checklists.iter().map(|(_, items)| items.len()).sum();
+    for (rel, items) in checklists {
+        omitted += items.len().saturating_sub(MAX_GROUP_TASKS);
+        children.push(TaskTree {
+            name: Some(unique_name(&mut used, format!("Open items in {}", rel))),
+            spec: Some(format!("Open checklist items from `{}`", rel)),
+            children: Some(finding_tasks(&mut used, items)),
+            ..Default::default()
+        });
+    }
+    let todo_count = todos.len();
+    if !todos.is_empty() {
+        omitted += todos.len().saturating_sub(MAX_GROUP_TASKS);
+        children.push(TaskTree {
+            name: Some(unique_name(&mut used, "Resolve code TODOs".to_string())),
+            spec: Some("TODO and FIXME comments found in the code".to_string()),
+            children: Some(finding_tasks(&mut used, todos)),
+            ..Default::default()
+        });
+    }
+
+    let root_task = TaskTree {
+        name: Some(root_name),
+        spec: summary,
+        children: (!children.is_empty()).then_some(children),
+        ..Default::default()
+    };
+    Ok(RepoAnalysis {
+        readme,
+        files_scanned,
+        checklist_items: checklist_count,
+        todo_comments: todo_count,
+        omitted,
+        plan: PlanRequest {
+            tasks: vec![root_task],
+            ..Default::default()
+        },
+    })
+}
+
+/// Files under `dir` in path order, as (path relative to `root`, path)
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, std::path::PathBuf)>,
+) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if files.len() >= MAX_FILES {
+            return Ok(());
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect_files(root, &path, files)?;
+            }
+        } else if file_type.is_file() && entry.metadata().is_ok_and(|m| m.len() <= MAX_FILE_BYTES) {
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((rel, path));
+        }
+    }
+    Ok(())
+}
+
+/// Title (first `#` heading) and first paragraph of a README
+fn readme_intro(text: &str) -> (Option<String>, Option<String>) {
+    let mut title = None;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("# ") {
+            if title.is_none() && paragraph.is_empty() {
+                title = Some(heading.trim().to_string()).filter(|t| !t.is_empty());
+                continue;
+            }
+        }
+        // Badges, language links, HTML, rules and further headings are not prose
+        let prose = !line.is_empty()
+            && !["#", "[", "**[", "![", "<", "---"]
+                .iter()
+                .any(|prefix| line.starts_with(prefix));
+        if prose {
+            paragraph.push(line);
+        } else if !paragraph.is_empty() {
+            break;
+        }
+    }
+    let summary = paragraph.join(" ");
+    let summary = Some(summary.trim_matches('*').trim().to_string()).filter(|p| !p.is_empty());
+    (title, summary)
+}
+
+/// Unchecked `- [ ]` items of a markdown file, outside code blocks
+fn checklist_items(path: &str, text: &str) -> Vec<Finding> {
+    let mut items = Vec::new();
+    let mut in_code = false;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_start();
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let item = ["- [ ] ", "* [ ] ", "+ [ ] "]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim);
+        if let Some(item) = item.filter(|i| !i.is_empty()) {
+            items.push(Finding {
+                path: path.to_string(),
+                line: index + 1,
+                text: item.to_string(),
+                fixme: false,
+            });
+        }
+    }
+    items
+}
+
+/// `TODO` and `FIXME` comments of a source file
+///
+/// The keyword must follow a comment marker (`//`, `#`, `/*`, `*`, `--`,
+/// `;`) outside a string literal, so identifiers and strings mentioning it
+/// are not taken.
+fn todo_comments(path: &str, text: &str) -> Vec<Finding> {
+    let mut found = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        for keyword in ["TODO", "FIXME"] {
+            let Some(at) = line.find(keyword) else {
+                continue;
+            };
+            let before = line[..at].trim_end();
+            let after_comment_marker = ["//", "#", "/*", "*", "--", ";"]
+                .iter()
+                .any(|marker| before.ends_with(marker));
+            // An odd number of quotes before it means a string literal
+            let in_string = before.matches('"').count() % 2 == 1;
+            if !after_comment_marker || in_string {
+                continue;
+            }
+            let rest = &line[at + keyword.len()..];
+            if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                continue; // TODOS, FIXMED, ...
+            }
+            // Skip an owner like TODO(alice), then separators
+            let rest = match rest.strip_prefix('(') {
+                Some(inner) => inner.split_once(')').map_or("", |(_, r)| r),
+                None => rest,
+            };
+            let text = rest
+                .trim_start_matches([':', '-', ' '])
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim();
+            found.push(Finding {
+                path: path.to_string(),
+                line: index + 1,
+                text: text.to_string(),
+                fixme: keyword == "FIXME",
+            });
+            break;
+        }
+    }
+    found
+}
+
+/// Subtasks for the first `MAX_GROUP_TASKS` findings
+fn finding_tasks(used: &mut HashSet<String>, findings: Vec<Finding>) -> Vec<TaskTree> {
+    findings
+        .into_iter()
+        .take(MAX_GROUP_TASKS)
+        .map(|finding| {
+            let location = format!("{}:{}", finding.path, finding.line);
+            let name = if finding.text.is_empty() {
+                format!("Resolve TODO at {}", location)
+            } else {
+                truncate(&finding.text, MAX_NAME_CHARS)
+            };
+            TaskTree {
+                name: Some(unique_name(used, name)),
+                spec: Some(if finding.text.is_empty() {
+                    format!("Found at `{}`", location)
+                } else {
+                    format!("{}\n\nFound at `{}`", finding.text, location)
+                }),
+                priority: finding.fixme.then_some(PriorityValue::High),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// `name`, or `name (2)`, `name (3)`... when a plan task already has it
+fn unique_name(used: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{} ({})", name, n);
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_proposes_plan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("README.md"),
+            "# Widget Service\n\n[![CI](badge.svg)](ci)\n**[Deutsch](README.de.md)**\n\nServes widgets\nover HTTP.\n\n## Roadmap\n\n- [x] Listing\n- [ ] Pagination\n- [ ] Auth\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/main.rs"),
+            "fn main() {\n    // TODO(sam): handle SIGTERM\n    let todo_list = 1; // FIXME: overflow on big input */\n    println!(\"// TODO: not a comment\");\n}\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target/gen.rs"), "// TODO: generated").unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".git/notes.md"), "- [ ] hidden").unwrap();
+
+        let analysis = analyze(root).unwrap();
+        assert_eq!(analysis.readme.as_deref(), Some("README.md"));
+        assert_eq!(analysis.checklist_items, 2);
+        assert_eq!(analysis.todo_comments, 2);
+        assert_eq!(analysis.omitted, 0);
+
+        let root_task = &analysis.plan.tasks[0];
+        assert_eq!(root_task.name.as_deref(), Some("Widget Service"));
+        assert_eq!(root_task.spec.as_deref(), Some("Serves widgets over HTTP."));
+        let groups = root_task.children.as_ref().unwrap();
+        assert_eq!(groups[0].name.as_deref(), Some("Open items in README.md"));
+        let items: Vec<_> = groups[0]
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|t| t.name.clone().unwrap())
+            .collect();
+        assert_eq!(items, vec!["Pagination", "Auth"]);
+
+        let todos = groups[1].children.as_ref().unwrap();
+        assert_eq!(todos[0].name.as_deref(), Some("handle SIGTERM"));
+        assert_eq!(todos[0].priority, None);
+        assert!(todos[0]
+            .spec
+            .as_deref()
+            .unwrap()
+            .ends_with("Found at `src/main.rs:2`"));
+        assert_eq!(todos[1].name.as_deref(), Some("overflow on big input"));
+        assert_eq!(todos[1].priority, Some(PriorityValue::High));
+
+        crate::plan_validation::validate_request(&analysis.plan).unwrap();
+    }
+
+    #[test]
+    fn test_unique_names_and_truncation() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_name(&mut used, "Fix".to_string()), "Fix");
+        assert_eq!(unique_name(&mut used, "Fix".to_string()), "Fix (2)");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("abc", 4), "abc");
+    }
+}
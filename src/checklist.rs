@@ -0,0 +1,249 @@
+//! Checklists: lightweight steps inside a task
+//!
+//! Not every step deserves a subtask. A plan can give a task
+//! `"checklist": ["Write migration", "Backfill"]`; items are numbered from 1
+//! and checked off with `ie task check <id> <n>`. Tasks carry their
+//! completion in `Task::checklist`, and completing a task with unchecked
+//! items warns instead of failing.
+
+use crate::db::models::{ChecklistItem, ChecklistProgress, Task};
+use crate::error::{IntentError, Result};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+
+const ITEM_COLUMNS: &str = "task_id, position, text, checked, checked_at";
+
+pub struct ChecklistManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> ChecklistManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Items of a task in order
+    pub async fn list(&self, task_id: i64) -> Result<Vec<ChecklistItem>> {
+        let exists: bool = sqlx::query_scalar(crate::sql_constants::CHECK_TASK_EXISTS)
+            .bind(task_id)
+            .fetch_one(self.pool)
+            .await?;
+        if !exists {
+            return Err(IntentError::TaskNotFound(task_id));
+        }
+        Ok(sqlx::query_as(&format!(
+            "SELECT {} FROM checklist_items WHERE task_id = ? ORDER BY position",
+            ITEM_COLUMNS
+        ))
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?)
+    }
+
+    /// Check or uncheck item `position` (1-based)
+    pub async fn set_checked(
+        &self,
+        task_id: i64,
+        position: i64,
+        checked: bool,
+    ) -> Result<ChecklistItem> {
+        let result = sqlx::query(
+            "UPDATE checklist_items SET checked = ?, checked_at = ? WHERE task_id = ? AND position = ?",
+        )
+        .bind(checked)
+        .bind(checked.then(Utc::now))
+        .bind(task_id)
+        .bind(position)
+        .execute(self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(self.missing_item(task_id, position).await);
+        }
+        self.get(task_id, position).await
+    }
+
+    /// Flip item `position` (1-based)
+    pub async fn toggle(&self, task_id: i64, position: i64) -> Result<ChecklistItem> {
+        let item = self.get(task_id, position).await?;
+        self.set_checked(task_id, position, !item.checked).await
+    }
+
+    async fn get(&self, task_id: i64, position: i64) -> Result<ChecklistItem> {
+        let item = sqlx::query_as(&format!(
+            "SELECT {} FROM checklist_items WHERE task_id = ? AND position = ?",
+            ITEM_COLUMNS
+        ))
+        .bind(task_id)
+        .bind(position)
+        .fetch_optional(self.pool)
+        .await?;
+        match item {
+            Some(item) => Ok(item),
+            None => Err(self.missing_item(task_id, position).await),
+        }
+    }
+
+    async fn missing_item(&self, task_id: i64, position: i64) -> IntentError {
+        match self.list(task_id).await {
+            Ok(items) => IntentError::InvalidInput(format!(
+                "Task #{} has no checklist item {} (it has {})",
+                task_id,
+                position,
+                items.len()
+            )),
+            Err(e) => e,
+        }
+    }
+}
+
+/// Replace a task's checklist within a transaction
+///
+/// Items whose text is unchanged keep their checked state, so a plan can
+/// restate a checklist without unchecking it. An empty list removes it.
+pub async fn set_in_tx(conn: &mut SqliteConnection, task_id: i64, items: &[String]) -> Result<()> {
+    let previous: Vec<(String, bool, Option<chrono::DateTime<Utc>>)> =
+        sqlx::query_as("SELECT text, checked, checked_at FROM checklist_items WHERE task_id = ?")
+            .bind(task_id)
+            .fetch_all(&mut *conn)
+            .await?;
+    let previous: HashMap<String, (bool, Option<chrono::DateTime<Utc>>)> = previous
+        .into_iter()
+        .map(|(text, checked, at)| (text, (checked, at)))
+        .collect();
+
+    sqlx::query("DELETE FROM checklist_items WHERE task_id = ?")
+        .bind(task_id)
+        .execute(&mut *conn)
+        .await?;
+    let items = items.iter().map(|i| i.trim()).filter(|i| !i.is_empty());
+    for (i, text) in items.enumerate() {
+        let (checked, checked_at) = previous.get(text).copied().unwrap_or((false, None));
+        sqlx::query(
+            "INSERT OR IGNORE INTO checklist_items (task_id, position, text, checked, checked_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(i as i64 + 1)
+        .bind(text)
+        .bind(checked)
+        .bind(checked_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Warning about unchecked items, for when the task is completed anyway
+pub async fn unchecked_warning(
+    conn: &mut SqliteConnection,
+    task_id: i64,
+) -> Result<Option<String>> {
+    let items: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT text, checked FROM checklist_items WHERE task_id = ? ORDER BY position",
+    )
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let unchecked: Vec<&str> = items
+        .iter()
+        .filter(|(_, checked)| !checked)
+        .map(|(text, _)| text.as_str())
+        .collect();
+    if unchecked.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Task #{} has {} of {} checklist items unchecked: {}",
+        task_id,
+        unchecked.len(),
+        items.len(),
+        unchecked.join(", ")
+    )))
+}
+
+/// Checklist progress of the tasks that have a checklist
+pub async fn progress(
+    conn: &mut SqliteConnection,
+    task_ids: &[i64],
+) -> Result<HashMap<i64, ChecklistProgress>> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let placeholders = vec!["?"; task_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT task_id, SUM(checked), COUNT(*) FROM checklist_items WHERE task_id IN ({}) GROUP BY task_id",
+        placeholders
+    );
+    let mut query = sqlx::query_as::<_, (i64, i64, i64)>(&sql);
+    for id in task_ids {
+        query = query.bind(id);
+    }
+    Ok(query
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|(id, checked, total)| (id, ChecklistProgress::new(checked, total)))
+        .collect())
+}
+
+/// Fill in `Task::checklist` for `tasks`
+pub async fn attach_progress(pool: &SqlitePool, tasks: &mut [Task]) -> Result<()> {
+    let ids: Vec<i64> = tasks.iter().map(|t| t.id).collect();
+    let mut conn = pool.acquire().await?;
+    let progress = progress(&mut conn, &ids).await?;
+    for task in tasks {
+        task.checklist = progress.get(&task.id).copied();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::{PlanExecutor, PlanRequest};
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    fn plan(json: &str) -> PlanRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checklist_from_plan_and_toggle() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let result = PlanExecutor::new(pool)
+            .execute(&plan(
+                r#"{"tasks": [{"name": "Release", "checklist": ["Tag", "Publish", "Announce"]}]}"#,
+            ))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let id = result.task_id_map["Release"];
+
+        let checklist = ChecklistManager::new(pool);
+        assert!(checklist.toggle(id, 2).await.unwrap().checked);
+        assert!(checklist.set_checked(id, 4, true).await.is_err());
+
+        let tasks = TaskManager::new(pool);
+        let task = tasks.get_task(id).await.unwrap();
+        assert_eq!(task.checklist, Some(ChecklistProgress::new(1, 3)));
+        assert_eq!(task.checklist.unwrap().percent, 33);
+
+        // Restating the checklist keeps checked items checked
+        PlanExecutor::new(pool)
+            .execute(&plan(
+                r#"{"tasks": [{"name": "Release", "checklist": ["Publish", "Announce"]}]}"#,
+            ))
+            .await
+            .unwrap();
+        let items = checklist.list(id).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items[0].checked && items[0].position == 1);
+
+        // Unchecked items do not block completion, they are reported
+        let done = tasks.done_task_by_id(id).await.unwrap();
+        assert_eq!(done.warnings.len(), 1);
+        assert!(done.warnings[0].contains("1 of 2 checklist items"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::db::models::{EventVisibility, TaskVisibility};
 use clap::{Parser, Subcommand};
 
 const LONG_ABOUT: &str = r#"
@@ -38,7 +39,7 @@ Documentation:
 #[command(long_about = LONG_ABOUT)]
 #[command(version)]
 pub struct Cli {
-    /// Enable verbose output (-v)
+    /// Enable verbose output and a database timing summary on stderr (-v)
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
@@ -50,10 +51,20 @@ pub struct Cli {
     #[arg(long)]
     pub json: bool,
 
+    /// Open the database read-only: the command fails rather than change it
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    /// Task scope: shared (default) or private, which also shows private tasks
+    #[arg(long, global = true, default_value = "shared")]
+    pub scope: TaskVisibility,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+// Parsed once per process, so the size of the task variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Clone)]
 pub enum Commands {
     /// Create or update task structures declaratively
@@ -62,6 +73,19 @@ pub enum Commands {
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
+
+        /// Check the plan without changing anything
+        #[arg(long)]
+        validate: bool,
+
+        /// Print the JSON Schema of plan input and exit (stdin is not read)
+        #[arg(long, conflicts_with = "validate")]
+        schema: bool,
+
+        /// Save the plan as this workspace instead of executing it
+        /// (see 'ie workspace')
+        #[arg(long, conflicts_with_all = ["validate", "schema"])]
+        workspace: Option<String>,
     },
 
     /// Record events (decisions, blockers, milestones, notes)
@@ -73,18 +97,40 @@ pub enum Commands {
     ///   ie log blocker "API rate limit hit" --task 42
     ///   ie log milestone "MVP complete"
     ///   ie log note "Consider caching optimization"
+    ///   ie log note "Retried with backoff" --timestamp 30m
+    ///   ie log deploy "v1.2 to staging"   # custom type from [events] types
+    ///   echo '[{"type":"decision","message":"Use SQLite"}]' | ie log --stdin-json
+    ///
+    /// With --stdin-json, stdin holds a JSON array of events, each with
+    /// "type", "message" and optional "task", "timestamp" and "visibility"
+    /// (defaulting to the flags). They are recorded in order, in one transaction.
     Log {
-        /// Event type: decision, blocker, milestone, note
-        #[arg(value_enum)]
-        event_type: LogEventType,
+        /// Event type: decision, blocker, milestone, note, or a type listed
+        /// under [events] types in .intent-engine/config.toml
+        #[arg(value_name = "TYPE", required_unless_present = "stdin_json")]
+        event_type: Option<String>,
 
         /// Event message (markdown supported)
-        message: String,
+        #[arg(required_unless_present = "stdin_json")]
+        message: Option<String>,
 
         /// Target task ID (optional, uses current focused task if not specified)
         #[arg(long)]
         task: Option<i64>,
 
+        /// Visibility: normal (shared history), verbose (scratch/debug
+        /// reasoning) or private. Non-normal events are hidden unless requested.
+        #[arg(long, default_value = "normal")]
+        visibility: EventVisibility,
+
+        /// When it happened, to backdate the event: RFC 3339 or a duration ago (e.g. 30m)
+        #[arg(long, value_name = "TIME")]
+        timestamp: Option<String>,
+
+        /// Read a JSON array of events from stdin
+        #[arg(long, conflicts_with_all = ["event_type", "message", "timestamp"])]
+        stdin_json: bool,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -101,14 +147,22 @@ pub enum Commands {
     ///   ie search "todo"           # Only todo tasks
     ///   ie search "done"           # Only completed tasks
     ///
-    /// FTS5 search examples (full-text search):
+    /// Full-text search examples (all terms must match):
     ///   ie search "JWT authentication"
-    ///   ie search "API AND client"
+    ///   ie search '"token refresh" auth*'   # Phrase and prefix
+    ///   ie search "name:login"              # Task names only (also spec:)
     ///   ie search "blocker" --events --no-tasks
+    ///   ie search --semantic "retry failed uploads"   # Similar meaning
     Search {
-        /// Search query: status keywords (todo/doing/done) or FTS5 syntax
+        /// Search query: status keywords (todo/doing/done), or words, "phrases",
+        /// prefix* and name:/spec: filters
         query: String,
 
+        /// Rank by meaning with embeddings instead of keywords (requires the
+        /// `semantic` build feature)
+        #[arg(long)]
+        semantic: bool,
+
         /// Search in tasks (default: true)
         #[arg(long, default_value = "true")]
         tasks: bool,
@@ -117,6 +171,10 @@ pub enum Commands {
         #[arg(long, default_value = "true")]
         events: bool,
 
+        /// Only events of this type (repeatable); tasks are not affected
+        #[arg(long = "event-type", value_name = "TYPE", conflicts_with = "semantic")]
+        event_types: Vec<String>,
+
         /// Maximum number of results (default: 20)
         #[arg(long)]
         limit: Option<i64>,
@@ -145,6 +203,7 @@ pub enum Commands {
     /// Examples:
     ///   ie init                    # Initialize in current directory
     ///   ie init --at /my/project   # Initialize at specific directory
+    ///   ie init --analyze          # Also propose a plan from the repository
     Init {
         /// Custom directory to initialize (default: current directory)
         #[arg(long)]
@@ -153,14 +212,105 @@ pub enum Commands {
         /// Re-initialize even if .intent-engine already exists
         #[arg(long)]
         force: bool,
+
+        /// Propose an initial plan from the README, TODO/FIXME comments and
+        /// open markdown checklists, saved to .intent-engine/bootstrap-plan.json
+        #[arg(long)]
+        analyze: bool,
+
+        /// Execute the proposed plan right away (requires --analyze)
+        #[arg(long, requires = "analyze")]
+        apply: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
     },
 
     /// Dashboard management commands
     #[command(subcommand)]
     Dashboard(DashboardCommands),
 
-    /// Check system health and dependencies
-    Doctor,
+    /// Check system health and database integrity
+    ///
+    /// Reports the database location, Dashboard status and integrity problems:
+    /// search index desync, events and dependencies pointing at missing tasks,
+    /// focus on missing tasks, and schema drift.
+    ///
+    /// Examples:
+    ///   ie doctor
+    ///   ie doctor --fix            # Rebuild search indexes and repair references
+    Doctor {
+        /// Repair the problems found
+        #[arg(long)]
+        fix: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Follow task and event changes
+    ///
+    /// Prints one line per change as it happens, from any process. With
+    /// --format json each line is a JSON object (NDJSON) with a "type" of
+    /// task_created, task_updated, task_deleted or event_added.
+    ///
+    /// Examples:
+    ///   ie watch
+    ///   ie watch --task 42                 # Task 42 and its subtasks only
+    ///   ie watch --format json | jq .type
+    Watch {
+        /// Only follow this task and its descendants
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Print a shell completion script
+    ///
+    /// Examples:
+    ///   ie completions bash > ~/.local/share/bash-completion/completions/ie
+    ///   ie completions zsh > "${fpath[1]}/_ie"
+    ///   ie completions fish > ~/.config/fish/completions/ie.fish
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
+
+    /// Describe the command-line interface
+    ///
+    /// With --json, prints every command with its arguments, plus the JSON
+    /// Schema of plan input, for wrappers and editor plugins.
+    ///
+    /// Examples:
+    ///   ie introspect
+    ///   ie introspect --json
+    Introspect {
+        /// Print the full machine-readable description
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Error codes returned by the CLI and the Dashboard API
+    ///
+    /// Every error carries a stable code (IE0001, ...) next to its message,
+    /// in the "error_code" field of JSON output. Messages change between
+    /// releases; codes do not.
+    #[command(subcommand)]
+    Errors(ErrorsCommands),
+
+    /// Interactive terminal UI: task tree, focus and recent events
+    ///
+    /// Refreshes live when tasks change from any process, without the
+    /// dashboard running.
+    ///
+    /// Keys: ↑/↓ or j/k move, s start (and focus), d done, t back to todo,
+    ///       l log a note, r refresh, q quit
+    Tui,
 
     /// Show current task context (focus spotlight)
     ///
@@ -174,6 +324,11 @@ pub enum Commands {
     ///   ie status              # Show current focused task context
     ///   ie status 42           # Show task 42's context (without changing focus)
     ///   ie status -e           # Include event history
+    ///   ie status --max-tokens 1500  # Ranked restore bundle within a budget
+    ///
+    /// With --max-tokens or --max-chars, prints a restore bundle instead: the
+    /// task, the unfinished tasks it is blocked on, its decisions and other
+    /// events, then parent and children, in that order, as much as fits.
     Status {
         /// Task ID to inspect (optional, defaults to current focused task)
         task_id: Option<i64>,
@@ -182,6 +337,18 @@ pub enum Commands {
         #[arg(short = 'e', long)]
         with_events: bool,
 
+        /// Print a restore bundle of at most this many tokens (~4 chars each)
+        #[arg(long, conflicts_with = "max_chars")]
+        max_tokens: Option<usize>,
+
+        /// Print a restore bundle of at most this many characters
+        #[arg(long)]
+        max_chars: Option<usize>,
+
+        /// Highest event visibility to show: normal, verbose or all
+        #[arg(long, default_value = "normal")]
+        event_visibility: EventVisibility,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -227,237 +394,1152 @@ pub enum Commands {
     ///   ie config unset llm.model
     #[command(subcommand)]
     Config(ConfigCommands),
-}
 
-#[derive(Subcommand, Clone)]
-pub enum SuggestionsCommands {
-    /// List all active suggestions
+    /// Commands run on task and session events
     ///
-    /// Shows suggestions that haven't been dismissed yet.
+    /// Hooks are shell commands listed per event in the [hooks] table of
+    /// .intent-engine/config.toml. They get a JSON payload on stdin and run
+    /// in the project root after the change was made. Events: task_created,
+    /// task_started, task_completed, plan_executed, session_restored.
     ///
     /// Examples:
-    ///   ie suggestions list
-    ///   ie suggestions list --format json
-    List {
-        /// Output format (text or json)
-        #[arg(long, default_value = "text")]
-        format: String,
-    },
+    ///   ie hooks list
+    ///   ie hooks test task_completed
+    #[command(subcommand)]
+    Hooks(HooksCommands),
 
-    /// Dismiss a specific suggestion
+    /// Dependency analysis
     ///
-    /// Mark a suggestion as read/acted upon so it won't be shown again.
+    /// Examples:
+    ///   ie deps suggest --task 42
+    ///   ie deps suggest --task 42 --format json
+    #[command(subcommand)]
+    Deps(DepsCommands),
+
+    /// Compact workspace status for shell prompts
+    ///
+    /// Prints a short segment such as "⚒ #42 fix-login (3 todo)" (focused task
+    /// and todo count), or nothing outside a project. The result is cached per
+    /// session and refreshed only when the database changes, keeping prompt
+    /// rendering under 50ms.
     ///
     /// Examples:
-    ///   ie suggestions dismiss 5
-    ///   ie suggestions dismiss --all
-    Dismiss {
-        /// Suggestion ID to dismiss (omit to use --all)
-        id: Option<i64>,
+    ///   ie prompt-segment
+    ///   eval "$(ie prompt-segment --bash)"   # in ~/.bashrc
+    ///   eval "$(ie prompt-segment --zsh)"    # in ~/.zshrc
+    PromptSegment {
+        /// Print the zsh init snippet
+        #[arg(long, conflicts_with = "bash")]
+        zsh: bool,
+
+        /// Print the bash init snippet
+        #[arg(long)]
+        bash: bool,
 
-        /// Dismiss all active suggestions
+        /// Bypass the status cache
         #[arg(long)]
-        all: bool,
+        no_cache: bool,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
 
-    /// Clear all dismissed suggestions from database
+    /// Export tasks, events and dependencies to a file
     ///
-    /// Permanently removes dismissed suggestions to clean up the database.
-    /// Active (non-dismissed) suggestions are not affected.
+    /// Examples:
+    ///   ie export archive -o project.json
+    ///   ie export archive --task 42 -o auth.json   # Subtree of task 42
+    ///   ie export archive --format ndjson -o project.ndjson
+    ///   ie export anonymized -o repro.db           # Shareable copy for bug reports
+    ///   ie export traces                           # OpenTelemetry traces to otel.endpoint
+    #[command(subcommand)]
+    Export(ExportCommands),
+
+    /// Database maintenance
     ///
     /// Examples:
-    ///   ie suggestions clear
-    Clear {
-        /// Output format (text or json)
-        #[arg(long, default_value = "text")]
-        format: String,
-    },
-}
+    ///   ie db maintain             # ANALYZE, VACUUM if worthwhile, WAL checkpoint
+    ///   ie db maintain --full      # Always VACUUM
+    ///   ie db compact 42           # Roll old events of task 42 into a digest
+    ///   ie db encrypt              # Encrypt the database (encryption builds)
+    #[command(subcommand)]
+    Db(DbCommands),
 
-#[derive(Subcommand, Clone)]
-pub enum ConfigCommands {
-    /// Set a configuration value
+    /// Progress reports
     ///
     /// Examples:
-    ///   ie config set llm.endpoint "http://localhost:8080/v1/chat/completions"
-    ///   ie config set llm.api_key "sk-your-key"
-    Set {
-        /// Configuration key (e.g., llm.endpoint)
-        key: String,
+    ///   ie report milestone "v1.0"
+    ///   ie report milestone "v1.0" --format json
+    ///   ie report aging
+    ///   ie report cycle-time --since 30d --percentiles
+    #[command(subcommand)]
+    Report(ReportCommands),
 
-        /// Configuration value
-        value: String,
+    /// Notes for the next agent session
+    ///
+    /// Leave a handoff when a session ends: the state of the work and what to
+    /// do next. 'ie status' shows the latest one first.
+    ///
+    /// Examples:
+    ///   ie handoff create --summary "Login works" --next-steps "Add refresh tokens"
+    ///   ie handoff list
+    #[command(subcommand)]
+    Handoff(HandoffCommands),
 
-        /// Output format (text or json)
-        #[arg(long, default_value = "text")]
-        format: String,
-    },
+    /// Alternative plans kept beside the task tree
+    ///
+    /// 'ie plan --workspace <name>' saves a plan under a name instead of
+    /// executing it, so alternatives do not overwrite each other. Compare
+    /// them, then promote one into the task tree ('main').
+    ///
+    /// Examples:
+    ///   ie plan --workspace approach-a < a.json
+    ///   ie workspace list
+    ///   ie workspace diff approach-a approach-b
+    ///   ie workspace diff approach-a            # against the task tree
+    ///   ie workspace promote approach-a
+    #[command(subcommand)]
+    Workspace(WorkspaceCommands),
 
-    /// Get a configuration value
+    /// Interrupt the current task, come back to it, timebox work on it
     ///
-    /// Sensitive values (api_key, secret) are automatically masked.
+    /// 'ie focus push' starts another task and keeps the current focus on a
+    /// per-session stack; 'ie focus pop' focuses it again. 'ie focus start'
+    /// timeboxes work on the current task. 'ie status' shows both.
     ///
     /// Examples:
-    ///   ie config get llm.endpoint
-    ///   ie config get llm.api_key    # Shows masked value
-    Get {
-        /// Configuration key
-        key: String,
+    ///   ie focus push 57       # Jump to urgent task 57
+    ///   ie task done           # Finish it
+    ///   ie focus pop           # Back to where you were
+    ///   ie focus list
+    ///   ie focus start --minutes 45
+    ///   ie focus stop --note "Parser done, tests next"
+    #[command(subcommand)]
+    Focus(FocusCommands),
 
-        /// Output format (text or json)
-        #[arg(long, default_value = "text")]
-        format: String,
-    },
+    /// URLs and documents tasks refer to
+    ///
+    /// Plans set a task's links with "links"; these commands edit them one
+    /// at a time. The kind (doc, issue, pr, design or other) is guessed from
+    /// the URL unless given. 'ie db maintain' links the URLs that specs and
+    /// events mention; 'ie links check' finds the dead ones.
+    ///
+    /// Examples:
+    ///   ie links add 42 https://github.com/org/repo/issues/12
+    ///   ie links add 42 docs/auth.md --title "Auth design" --kind design
+    ///   ie links list --task 42
+    ///   ie links remove 42 docs/auth.md
+    ///   ie links check
+    #[command(subcommand)]
+    Links(LinksCommands),
 
-    /// List configuration entries
+    /// Tasks waiting on a human, most urgent first
+    ///
+    /// Lists tasks an agent flagged with needs_human (and why), tasks
+    /// awaiting review and unfinished human-owned tasks. Flagged tasks come
+    /// first, then reviews, then tasks that block the most other work.
     ///
     /// Examples:
-    ///   ie config list
-    ///   ie config list --prefix llm
-    List {
-        /// Filter by key prefix (e.g., "llm" shows all llm.* keys)
-        #[arg(long)]
-        prefix: Option<String>,
+    ///   ie inbox
+    ///   ie inbox --limit 5 --format json
+    Inbox {
+        /// Maximum number of tasks to show
+        #[arg(long, default_value = "20")]
+        limit: i64,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
 
-    /// Remove a configuration entry
+    /// Project health score: is this project on track?
+    ///
+    /// Scores blocked tasks, stale work in progress, dependency bottlenecks,
+    /// tasks without specs and the velocity trend from 0 to 100, and combines
+    /// them into one health score. Name an insight to list the tasks behind it.
     ///
     /// Examples:
-    ///   ie config unset llm.model
-    Unset {
-        /// Configuration key to remove
-        key: String,
+    ///   ie insights
+    ///   ie insights --format json
+    ///   ie insights blocked           # Tasks behind one insight
+    ///   ie insights stale-wip --format json
+    Insights {
+        /// Insight to drill into: blocked, stale-wip, bottlenecks, missing-specs or velocity
+        kind: Option<String>,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
 
-    /// Test LLM endpoint connectivity
+    /// What happened across all tasks, newest first
     ///
-    /// Sends a simple test message to verify the configured LLM endpoint is working.
+    /// Merges logged events with task creation, first start and first
+    /// completion into one chronological feed.
     ///
     /// Examples:
-    ///   ie config test-llm
-    ///   ie config test-llm --prompt "Hello, introduce yourself"
-    TestLlm {
-        /// Custom test prompt (default: "你好，请用一句话介绍你自己")
+    ///   ie activity --since 1d
+    ///   ie activity --task 42 --limit 100
+    ///   ie activity --type decision --format json
+    Activity {
+        /// Only activity within this window (e.g. "1d", "12h")
         #[arg(long)]
-        prompt: Option<String>,
+        since: Option<String>,
+
+        /// Only activity of this task
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Only events of this type
+        #[arg(long = "type")]
+        event_type: Option<String>,
+
+        /// Maximum number of entries
+        #[arg(long, default_value = "50")]
+        limit: i64,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
-}
 
-#[derive(Subcommand, Clone)]
-pub enum TaskCommands {
-    /// Create a new task
+    /// Import an archive created by 'ie export archive'
+    ///
+    /// Archive tasks are matched against existing tasks. Tasks with the same
+    /// name and content are reused; tasks with the same name but different
+    /// content, or whose ID is taken by another task, are reported as
+    /// conflicts. Nothing is written until every conflict has a resolution:
+    ///   skip       Keep the existing task
+    ///   overwrite  Replace the existing task's content
+    ///   duplicate  Import as a new task alongside the existing one
+    ///
+    /// The import runs in a single transaction.
     ///
     /// Examples:
-    ///   ie task create "Implement auth"
-    ///   ie task create "Add tests" --description "Unit + integration tests" --parent 42
-    ///   ie task create "Fix bug" --status doing --priority 1
-    Create {
-        /// Task name
-        name: String,
-
-        /// Task description/spec (markdown supported)
-        #[arg(short, long)]
-        description: Option<String>,
-
-        /// Parent task ID (0 = root task, omit = auto-parent to current focus)
-        #[arg(short, long)]
-        parent: Option<i64>,
-
-        /// Initial status (default: todo)
-        #[arg(short, long, default_value = "todo")]
-        status: String,
-
-        /// Priority (1=critical, 2=high, 3=medium, 4=low)
+    ///   ie import auth.json --dry-run                 # Show conflict report
+    ///   ie import auth.json --on-conflict skip
+    ///   ie import auth.json --on-conflict skip --resolve 3=overwrite
+    ///   ie import auth.json --parent 10               # Attach roots under task 10
+    Import {
+        /// Archive file to import
+        file: String,
+
+        /// Resolution for all conflicts (skip, overwrite, duplicate)
         #[arg(long)]
-        priority: Option<i32>,
+        on_conflict: Option<String>,
 
-        /// Task owner (default: human)
-        #[arg(long, default_value = "human")]
-        owner: String,
+        /// Per-task resolution as ARCHIVE_ID=CHOICE (repeatable)
+        #[arg(long = "resolve", value_name = "ID=CHOICE")]
+        resolve: Vec<String>,
 
-        /// Metadata key=value pairs (e.g., --metadata type=epic --metadata tag=auth)
+        /// Parent task for archive roots
         #[arg(long)]
-        metadata: Vec<String>,
-
-        /// IDs of tasks that block this task (this task depends on them)
-        #[arg(long = "blocked-by")]
-        blocked_by: Vec<i64>,
+        parent: Option<i64>,
 
-        /// IDs of tasks that this task blocks (they depend on this task)
+        /// Report conflicts without importing
         #[arg(long)]
-        blocks: Vec<i64>,
+        dry_run: bool,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
 
-    /// Get task details
+    /// Sync tasks, events and dependencies with a remote hub
+    ///
+    /// The hub is a directory (e.g. on a shared drive), an http(s):// URL
+    /// taking GET/PUT (IE_SYNC_TOKEN is sent as bearer token) or an s3://
+    /// prefix (AWS_* credentials). Set it with `[sync] remote` in
+    /// .intent-engine/config.toml or pass --remote.
+    ///
+    /// Rows changed both here and on the hub since the last sync are settled
+    /// by the conflict policy:
+    ///   lamport  Higher Lamport clock wins; every machine agrees (default)
+    ///   newest   Later wall-clock change wins
+    ///   local    This machine wins
+    ///   remote   The hub wins
     ///
     /// Examples:
-    ///   ie task get 42
-    ///   ie task get 42 --with-events
-    ///   ie task get 42 --with-context
-    Get {
-        /// Task ID
-        id: i64,
+    ///   ie sync push
+    ///   ie sync pull --conflict remote
+    ///   ie sync status --remote /mnt/share/ie-hub
+    #[command(subcommand)]
+    Sync(SyncCommands),
 
-        /// Include event history
-        #[arg(short = 'e', long)]
-        with_events: bool,
+    /// Developer tools (builds with the `dev` feature)
+    ///
+    /// Examples:
+    ///   ie dev seed                        # Small sample project
+    ///   ie dev seed --profile large        # ~6,000 tasks for Dashboard and benchmarks
+    ///   ie dev seed --profile deep --seed 7
+    #[cfg(feature = "dev")]
+    #[command(subcommand)]
+    Dev(DevCommands),
+}
 
-        /// Include full context (ancestors, siblings, children, dependencies)
-        #[arg(short = 'c', long)]
-        with_context: bool,
+impl Commands {
+    /// Whether this invocation follows the `output` envelope contract, so
+    /// failures must be reported as an envelope on stdout too
+    pub fn uses_output_envelope(&self) -> bool {
+        let format = match self {
+            Commands::Dashboard(DashboardCommands::Status { json: true, .. }) => return true,
+            Commands::Init { format, .. }
+            | Commands::Doctor { format, .. }
+            | Commands::Log { format, .. }
+            | Commands::Db(DbCommands::Maintain { format, .. })
+            | Commands::Db(DbCommands::Encrypt { format })
+            | Commands::Db(DbCommands::Decrypt { format })
+            | Commands::Dashboard(DashboardCommands::Status { format, .. }) => format,
+            #[cfg(feature = "dev")]
+            Commands::Dev(DevCommands::Seed { format, .. }) => format,
+            _ => return false,
+        };
+        crate::output::is_json(format)
+    }
+
+    /// Whether this invocation writes outside `ProjectContext`, so
+    /// `--read-only` cannot be enforced and must be refused up front
+    pub fn always_writes(&self) -> bool {
+        matches!(
+            self,
+            Commands::Init { .. }
+                | Commands::Doctor { fix: true, .. }
+                | Commands::Db(DbCommands::Encrypt { .. } | DbCommands::Decrypt { .. })
+                | Commands::Dashboard(
+                    DashboardCommands::Start { .. }
+                        | DashboardCommands::InstallService { .. }
+                        | DashboardCommands::UninstallService
+                        | DashboardCommands::RunService { .. }
+                )
+        )
+    }
+}
+
+#[derive(Subcommand, Clone)]
+pub enum DbCommands {
+    /// Refresh planner statistics, compact the file and truncate the WAL
+    ///
+    /// VACUUM runs only when at least a quarter of the file is free pages,
+    /// unless --full is given. Stale doing tasks are swept first, as by
+    /// `ie task stale`, and URLs mentioned in specs and events become task
+    /// links (see 'ie links'). The Dashboard runs the same maintenance
+    /// periodically for the projects it serves.
+    Maintain {
+        /// Always VACUUM, even with little free space
+        #[arg(long)]
+        full: bool,
 
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
 
-    /// Update an existing task
+    /// Roll old events of a task into a digest event
     ///
-    /// Examples:
-    ///   ie task update 42 --name "New name"
-    ///   ie task update 42 --description "Updated spec" --priority 1
-    ///   ie task update 42 --status doing
-    ///   ie task update 42 --metadata type=epic --metadata "key="  (delete key)
-    Update {
+    /// The most recent events stay as they are; older ones are replaced by a
+    /// single "digest" event and moved to the events archive. Private events
+    /// and earlier digests are never compacted.
+    Compact {
         /// Task ID
-        id: i64,
+        task_id: i64,
 
-        /// New task name
+        /// Recent events to keep as they are
+        #[arg(long, default_value_t = crate::compaction::DEFAULT_KEEP_RECENT)]
+        keep: usize,
+
+        /// Write the digest with the configured LLM (see 'ie config test-llm')
         #[arg(long)]
-        name: Option<String>,
+        llm: bool,
 
-        /// New description/spec
-        #[arg(short, long)]
-        description: Option<String>,
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 
-        /// New status (todo, doing, done)
-        #[arg(short, long)]
-        status: Option<String>,
+    /// Encrypt the project database with SQLCipher
+    ///
+    /// Uses the key in IE_DB_KEY, or generates one and stores it in the OS
+    /// keychain. Needs a build with the `encryption` feature. Stop the
+    /// Dashboard first: the file is rewritten and replaced.
+    Encrypt {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Turn an encrypted project database back into plaintext
+    ///
+    /// Removes the key from the OS keychain afterwards.
+    Decrypt {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum SyncCommands {
+    /// Upload local changes (pulls first)
+    Push {
+        /// Hub to use instead of `[sync] remote`
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Conflict policy (lamport, newest, local, remote)
+        #[arg(long)]
+        conflict: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Apply the hub's changes locally
+    Pull {
+        /// Hub to use instead of `[sync] remote`
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Conflict policy (lamport, newest, local, remote)
+        #[arg(long)]
+        conflict: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show unpushed local changes and what a pull would bring
+    Status {
+        /// Hub to use instead of `[sync] remote`
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[cfg(feature = "dev")]
+#[derive(Subcommand, Clone)]
+pub enum DevCommands {
+    /// Fill an empty project database with reproducible sample data
+    ///
+    /// Profiles: small (~20 tasks), large (~6,000 tasks with subtasks) and
+    /// deep (task chains 24 levels deep). The same profile and seed always
+    /// give the same tasks, statuses, dependencies and events; only the
+    /// timestamps follow the clock.
+    Seed {
+        /// Data profile (small, large, deep)
+        #[arg(long, default_value = "small")]
+        profile: String,
+
+        /// Random seed
+        #[arg(long, default_value_t = crate::seed::DEFAULT_SEED)]
+        seed: u64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ReportCommands {
+    /// Completion, blocking chains and at-risk tasks of a milestone
+    ///
+    /// Tasks join a milestone through the "milestone" field in plan input.
+    Milestone {
+        /// Milestone name
+        name: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Tasks in progress, longest running first, with their owner
+    Aging {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Pull request description of a task and its subtree, in markdown
+    ///
+    /// Gathers the task's spec, its completed subtasks with their outcomes,
+    /// the decision events below it and the task's own outcome. A template
+    /// set as [report] pr_template in config.toml replaces the layout; it
+    /// can use {title}, {task_id}, {spec}, {changes}, {decisions},
+    /// {outcome} and {remaining}.
+    Pr {
+        /// Task the pull request implements
+        #[arg(long)]
+        task: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Standup summary in markdown: completed, in-progress and blocked tasks
+    ///
+    /// Lists tasks completed since the cutoff with their outcomes, tasks in
+    /// progress with their latest event, and unfinished tasks that are
+    /// blocked or had a blocker logged since the cutoff.
+    Standup {
+        /// Cutoff: duration (e.g. "1d", "12h"), date, today or yesterday
+        #[arg(long, default_value = "1d")]
+        since: String,
+
+        /// Only tasks with this owner
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Time from first start to first completion of done tasks
+    CycleTime {
+        /// Only tasks completed within this window (e.g. "7d", "24h")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Also report the 50th, 75th, 90th and 95th percentiles
+        #[arg(long)]
+        percentiles: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum HandoffCommands {
+    /// Record a handoff (about the focused task unless --task is given)
+    Create {
+        /// State of the work
+        #[arg(long)]
+        summary: String,
+
+        /// What the next session should do
+        #[arg(long)]
+        next_steps: Option<String>,
+
+        /// Task the handoff is about
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show recent handoffs, newest first
+    List {
+        /// Only handoffs about this task
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Maximum number of handoffs
+        #[arg(long, default_value_t = 5)]
+        limit: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum FocusCommands {
+    /// Start a task, keeping the current focus on the stack
+    Push {
+        /// Task ID
+        id: i64,
+
+        /// Start the task even past the WIP limits in .intent-engine/config.toml
+        #[arg(long)]
+        over_wip: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Focus the task on top of the stack again
+    ///
+    /// Tasks done or deleted since they were pushed are skipped.
+    Pop {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show the stack, top first
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Timebox work on the current task
+    ///
+    /// A running Dashboard reminds you when the time is up.
+    Start {
+        /// Length of the session in minutes
+        #[arg(long, default_value_t = 25)]
+        minutes: u32,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// End the focus session and log it on its task
+    Stop {
+        /// Text appended to the logged session summary
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show the running focus session
+    Status {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum LinksCommands {
+    /// Link a task to a URL or document; linking it again updates title and kind
+    Add {
+        /// Task ID
+        task_id: i64,
+
+        /// URL or path
+        url: String,
+
+        /// Short description of what the link points to
+        #[arg(long)]
+        title: Option<String>,
+
+        /// doc, issue, pr, design or other (guessed from the URL by default)
+        #[arg(long)]
+        kind: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// List links, of one task or of all tasks
+    List {
+        /// Only links of this task
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Remove a task's link
+    Remove {
+        /// Task ID
+        task_id: i64,
+
+        /// URL or path, as listed
+        url: String,
+    },
+
+    /// Check that links still lead somewhere and flag dead ones
+    ///
+    /// URLs are requested (HEAD, or GET where HEAD is not allowed) and
+    /// repository paths looked up under the project root. A link is dead when
+    /// there is no response, the status is 400 or above (401 and 403 aside),
+    /// or the file is missing. Results are kept and shown by 'ie links list'.
+    Check {
+        /// Only links of this task
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Seconds to wait for each response
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum WorkspaceCommands {
+    /// List workspaces, most recently updated first
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Print a workspace's plan (JSON, ready for 'ie plan')
+    Show {
+        /// Workspace name
+        name: String,
+    },
+
+    /// Compare two workspaces by task name
+    ///
+    /// 'main' is the task tree: against it, the diff shows what promoting
+    /// the other workspace would create and change.
+    Diff {
+        /// Workspace on the left
+        left: String,
+
+        /// Workspace on the right
+        #[arg(default_value = "main")]
+        right: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Execute a workspace's plan into the task tree, as 'ie plan' would
+    Promote {
+        /// Workspace name
+        name: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Delete a workspace (tasks already promoted stay)
+    Delete {
+        /// Workspace name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ExportCommands {
+    /// Export a portable JSON archive (whole project or one subtree)
+    ///
+    /// Rows are streamed as they are read, so any number of events fits.
+    /// With --format ndjson every task, event and dependency is one line;
+    /// 'ie import' reads both formats.
+    Archive {
+        /// Only export this task and its descendants
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Archive format (json or ndjson)
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+
+    /// Write a copy of the database with all text replaced by placeholders
+    ///
+    /// Names, specs, events and metadata become same-shaped placeholders;
+    /// IDs, statuses, priorities, timestamps, the task tree and dependencies
+    /// are kept. Config values (API keys) are dropped. Use it to share a
+    /// reproduction database without leaking content.
+    Anonymized {
+        /// Output database file (must not exist)
+        #[arg(short, long)]
+        output: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Export the task timeline as OpenTelemetry traces
+    ///
+    /// Each task is a span (child of its parent task's span), its events and
+    /// status changes are span events. Sends OTLP/HTTP JSON to otel.endpoint
+    /// (or OTEL_EXPORTER_OTLP_ENDPOINT); without an endpoint, or with
+    /// --output, writes the payload instead.
+    Traces {
+        /// Only export this task and its descendants
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// OTLP/HTTP endpoint (overrides otel.endpoint)
+        #[arg(long)]
+        endpoint: Option<String>,
+
+        /// Write the OTLP JSON payload to this file instead of sending it
+        #[arg(short, long, conflicts_with = "endpoint")]
+        output: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum HooksCommands {
+    /// Show the configured hooks, timeout and failure policy
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Run the hooks of an event with a test payload and report each one
+    Test {
+        /// Event whose hooks to run
+        event: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum DepsCommands {
+    /// Propose likely dependencies and duplicates of a task among open tasks
+    ///
+    /// Scores unfinished tasks that are not yet linked to the task by how
+    /// similar their names and specs are and whether one spec names the
+    /// other. Nothing is changed; confirm a suggestion with
+    /// `ie task update --add-blocked-by` or `--add-blocks`.
+    Suggest {
+        /// Task to find dependencies for
+        #[arg(long)]
+        task: i64,
+
+        /// Maximum number of suggestions
+        #[arg(long, default_value = "10")]
+        limit: usize,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Make a task wait for a task of another project
+    ///
+    /// The other task is given as <project>#<task id>, the project by root
+    /// path or registered name. 'ie task start' refuses the task until the
+    /// other task is finished.
+    ///
+    /// Examples:
+    ///   ie deps link --task 5 --blocked-by api-server#12
+    ///   ie deps link --task 5 --blocked-by ~/src/api-server#12
+    Link {
+        /// Task that waits
+        #[arg(long)]
+        task: i64,
+
+        /// Task of another project it waits for (<project>#<task id>)
+        #[arg(long)]
+        blocked_by: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Remove a dependency on a task of another project
+    Unlink {
+        /// Task that waits
+        #[arg(long)]
+        task: i64,
+
+        /// Task of another project it waits for (<project>#<task id>)
+        #[arg(long)]
+        blocked_by: String,
+    },
+
+    /// List dependencies on tasks of other projects, with their state
+    Links {
+        /// Only the dependencies of this task
+        #[arg(long)]
+        task: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ErrorsCommands {
+    /// List every error code with its name and meaning
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum SuggestionsCommands {
+    /// List all active suggestions
+    ///
+    /// Shows suggestions that haven't been dismissed yet.
+    ///
+    /// Examples:
+    ///   ie suggestions list
+    ///   ie suggestions list --format json
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Dismiss a specific suggestion
+    ///
+    /// Mark a suggestion as read/acted upon so it won't be shown again.
+    ///
+    /// Examples:
+    ///   ie suggestions dismiss 5
+    ///   ie suggestions dismiss --all
+    Dismiss {
+        /// Suggestion ID to dismiss (omit to use --all)
+        id: Option<i64>,
+
+        /// Dismiss all active suggestions
+        #[arg(long)]
+        all: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Clear all dismissed suggestions from database
+    ///
+    /// Permanently removes dismissed suggestions to clean up the database.
+    /// Active (non-dismissed) suggestions are not affected.
+    ///
+    /// Examples:
+    ///   ie suggestions clear
+    Clear {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ConfigCommands {
+    /// Set a configuration value
+    ///
+    /// tasks.capacity_limit and the policy.* settings are written to
+    /// .intent-engine/config.toml; other keys are stored in the database.
+    ///
+    /// Examples:
+    ///   ie config set llm.endpoint "http://localhost:8080/v1/chat/completions"
+    ///   ie config set llm.api_key "sk-your-key"
+    ///   ie config set tasks.capacity_limit 5
+    Set {
+        /// Configuration key (e.g., llm.endpoint)
+        key: String,
+
+        /// Configuration value
+        value: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Get a configuration value
+    ///
+    /// Sensitive values (api_key, secret) are automatically masked.
+    ///
+    /// Examples:
+    ///   ie config get llm.endpoint
+    ///   ie config get llm.api_key    # Shows masked value
+    Get {
+        /// Configuration key
+        key: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// List configuration entries
+    ///
+    /// Examples:
+    ///   ie config list
+    ///   ie config list --prefix llm
+    List {
+        /// Filter by key prefix (e.g., "llm" shows all llm.* keys)
+        #[arg(long)]
+        prefix: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Remove a configuration entry
+    ///
+    /// Examples:
+    ///   ie config unset llm.model
+    Unset {
+        /// Configuration key to remove
+        key: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Test LLM endpoint connectivity
+    ///
+    /// Sends a simple test message to verify the configured LLM endpoint is working.
+    ///
+    /// Examples:
+    ///   ie config test-llm
+    ///   ie config test-llm --prompt "Hello, introduce yourself"
+    TestLlm {
+        /// Custom test prompt (default: "你好，请用一句话介绍你自己")
+        #[arg(long)]
+        prompt: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum TaskCommands {
+    /// Create a new task
+    ///
+    /// Examples:
+    ///   ie task create "Implement auth"
+    ///   ie task create "Add tests" --description "Unit + integration tests" --parent 42
+    ///   ie task create "Fix bug" --status doing --priority 1
+    ///   ie task create "Draft salary review" --private
+    Create {
+        /// Task name
+        name: String,
+
+        /// Task description/spec (markdown supported)
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Parent task ID (0 = root task, omit = auto-parent to current focus)
+        #[arg(short, long)]
+        parent: Option<i64>,
+
+        /// Initial status (default: todo)
+        #[arg(short, long, default_value = "todo")]
+        status: String,
+
+        /// Priority (1=critical, 2=high, 3=medium, 4=low)
+        #[arg(long)]
+        priority: Option<i32>,
+
+        /// Task owner (default: human, or tasks.default_owner in config.toml)
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Metadata key=value pairs (e.g., --metadata type=epic --metadata tag=auth)
+        #[arg(long)]
+        metadata: Vec<String>,
+
+        /// IDs of tasks that block this task (this task depends on them)
+        #[arg(long = "blocked-by")]
+        blocked_by: Vec<i64>,
+
+        /// IDs of tasks that this task blocks (they depend on this task)
+        #[arg(long)]
+        blocks: Vec<i64>,
+
+        /// Make the task private: hidden unless ie runs with --scope private
+        #[arg(long)]
+        private: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Get task details
+    ///
+    /// Examples:
+    ///   ie task get 42
+    ///   ie task get 42 --with-events
+    ///   ie task get 42 --with-context
+    ///   ie task show 42 --with-context      # 'show' is an alias of 'get'
+    #[command(visible_alias = "show")]
+    Get {
+        /// Task ID
+        id: i64,
+
+        /// Include event history
+        #[arg(short = 'e', long)]
+        with_events: bool,
+
+        /// Include full context (ancestors, siblings, children, dependencies, links)
+        #[arg(short = 'c', long)]
+        with_context: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Update an existing task
+    ///
+    /// Examples:
+    ///   ie task update 42 --name "New name"
+    ///   ie task update 42 --description "Updated spec" --priority 1
+    ///   ie task update 42 --status doing
+    ///   ie task update 42 --estimate 90
+    ///   ie task update 42 --needs-human "Which OAuth provider?"
+    ///   ie --scope private task update 42 --visibility shared
+    ///   ie task update 42 --metadata type=epic --metadata "key="  (delete key)
+    Update {
+        /// Task ID
+        id: i64,
+
+        /// New task name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// New description/spec
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// New status (todo, doing, review, done)
+        #[arg(short, long)]
+        status: Option<String>,
 
         /// New priority (1=critical, 2=high, 3=medium, 4=low)
         #[arg(long)]
         priority: Option<i32>,
 
+        /// Estimated effort in minutes
+        #[arg(long = "estimate", value_name = "MINUTES")]
+        estimate_minutes: Option<i64>,
+
         /// New active form text
         #[arg(long)]
         active_form: Option<String>,
@@ -466,6 +1548,18 @@ pub enum TaskCommands {
         #[arg(long)]
         owner: Option<String>,
 
+        /// Flag the task as waiting on a human, with the reason ("" clears it)
+        #[arg(long, value_name = "REASON")]
+        needs_human: Option<String>,
+
+        /// Mark the task as blocked, with the reason ("" unblocks it)
+        #[arg(long, value_name = "REASON")]
+        blocked: Option<String>,
+
+        /// Visibility of the task and its subtree (shared, private)
+        #[arg(long, value_name = "LEVEL")]
+        visibility: Option<TaskVisibility>,
+
         /// New parent task ID (0 = make root task)
         #[arg(long)]
         parent: Option<i64>,
@@ -503,7 +1597,7 @@ pub enum TaskCommands {
     ///   ie task list --parent 42
     ///   ie task list --tree
     List {
-        /// Filter by status (todo, doing, done)
+        /// Filter by status (todo, doing, review, done, blocked)
         #[arg(short, long)]
         status: Option<String>,
 
@@ -555,6 +1649,7 @@ pub enum TaskCommands {
     /// Examples:
     ///   ie task start 42
     ///   ie task start 42 --description "Starting with validation layer"
+    ///   ie task start 42 --over-wip
     Start {
         /// Task ID
         id: i64,
@@ -563,6 +1658,10 @@ pub enum TaskCommands {
         #[arg(short, long)]
         description: Option<String>,
 
+        /// Start the task even past the WIP limits in .intent-engine/config.toml
+        #[arg(long)]
+        over_wip: bool,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -570,13 +1669,166 @@ pub enum TaskCommands {
 
     /// Mark a task as done
     ///
+    /// With `review.required` enabled, AI-owned tasks move to 'review'
+    /// instead and wait for `ie task approve`.
+    ///
     /// Examples:
     ///   ie task done         # Complete current focused task
     ///   ie task done 42      # Focus task 42 then complete it
+    ///   ie task done 42 --outcome "JWT login works" --artifact src/auth.rs
     Done {
         /// Task ID (optional, defaults to current focused task)
         id: Option<i64>,
 
+        /// What the task produced; shown with the task and in reports
+        #[arg(long)]
+        outcome: Option<String>,
+
+        /// A file, URL or other thing the task produced (repeatable)
+        #[arg(long = "artifact", value_name = "ARTIFACT", requires = "outcome")]
+        artifacts: Vec<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show how a task's spec changed between two revisions
+    ///
+    /// Every spec change is recorded; revision 1 is the earliest known spec.
+    ///
+    /// Examples:
+    ///   ie task spec-diff 42              # First revision vs latest
+    ///   ie task spec-diff 42 --rev 2..4   # Revision 2 vs revision 4
+    ///   ie task spec-diff 42 --rev 3      # Revision 2 vs revision 3
+    SpecDiff {
+        /// Task ID
+        id: i64,
+
+        /// Revision range `A..B` (either end may be omitted), or a single revision
+        #[arg(long)]
+        rev: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// List one page of a task's children, or of root tasks
+    ///
+    /// Each child shows how many children it has in turn, so large trees can
+    /// be walked one level at a time. Pass the printed cursor to get the next page.
+    ///
+    /// Examples:
+    ///   ie task children              # Root tasks
+    ///   ie task children 42 --limit 20
+    ///   ie task children 42 --cursor 57 --format json
+    Children {
+        /// Parent task ID (omit for root tasks)
+        parent: Option<i64>,
+
+        /// ID of the last task of the previous page
+        #[arg(long)]
+        cursor: Option<i64>,
+
+        /// Maximum number of children (default 100, at most 500)
+        #[arg(long)]
+        limit: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show every status change of a task
+    ///
+    /// Lists each change with its time, where it came from (cli, dashboard
+    /// or plan) and the session that made it, if IE_SESSION_ID was set.
+    ///
+    /// Examples:
+    ///   ie task transitions 42
+    ///   ie task transitions 42 --format json
+    Transitions {
+        /// Task ID
+        id: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Approve a task waiting in review and mark it done
+    ///
+    /// Examples:
+    ///   ie task approve 42
+    Approve {
+        /// Task ID
+        id: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Copy a task and all its subtasks
+    ///
+    /// The copy sits next to the original, with statuses reset to todo and
+    /// dependencies inside the subtree pointing at the copies.
+    ///
+    /// Examples:
+    ///   ie task clone 12 --name "Release 1.3"
+    ///   ie task clone 12 --with-events --keep-status
+    Clone {
+        /// ID of the root task to copy
+        id: i64,
+
+        /// Name of the copied root task (default: "<original name> (copy)")
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Copy the events of every task too
+        #[arg(long)]
+        with_events: bool,
+
+        /// Keep statuses instead of resetting them to todo
+        #[arg(long)]
+        keep_status: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Check off a checklist item, or show the checklist
+    ///
+    /// Items are numbered from 1 in the order the plan listed them.
+    ///
+    /// Examples:
+    ///   ie task check 42 2
+    ///   ie task check 42
+    Check {
+        /// Task ID
+        id: i64,
+
+        /// Item number; omit to show the checklist
+        item: Option<i64>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Uncheck a checklist item
+    ///
+    /// Examples:
+    ///   ie task uncheck 42 2
+    Uncheck {
+        /// Task ID
+        id: i64,
+
+        /// Item number
+        item: i64,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
@@ -589,37 +1841,146 @@ pub enum TaskCommands {
     ///
     /// Examples:
     ///   ie task next
+    ///   ie task next --explain
     Next {
+        /// Also score every open task and say why it ranks where it does
+        #[arg(long)]
+        explain: bool,
+
         /// Output format (text or json)
         #[arg(long, default_value = "text")]
         format: String,
     },
-}
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-pub enum LogEventType {
-    Decision,
-    Blocker,
-    Milestone,
-    Note,
-}
+    /// Claim the next available task for this agent session and start it
+    ///
+    /// For several agents working in parallel: each claim leases one todo
+    /// task (unblocked, without unfinished children) to the session, so no
+    /// two sessions start the same task. Leases expire unless renewed, after
+    /// which the task can be claimed again. The session is --session, else
+    /// IE_SESSION_ID.
+    ///
+    /// Examples:
+    ///   IE_SESSION_ID=agent-1 ie task claim
+    ///   ie task claim --session agent-2 --lease-minutes 60
+    Claim {
+        /// Session ID of the claiming agent
+        #[arg(long)]
+        session: Option<String>,
 
-impl LogEventType {
-    pub fn as_str(&self) -> &str {
-        match self {
-            LogEventType::Decision => "decision",
-            LogEventType::Blocker => "blocker",
-            LogEventType::Milestone => "milestone",
-            LogEventType::Note => "note",
-        }
-    }
+        /// Minutes until the lease expires
+        #[arg(long, default_value_t = crate::tasks::DEFAULT_LEASE_MINUTES)]
+        lease_minutes: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Extend this session's lease on a task
+    ///
+    /// Examples:
+    ///   ie task renew 42 --lease-minutes 30
+    Renew {
+        /// Task ID
+        id: i64,
+
+        /// Session ID holding the lease
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Minutes from now until the lease expires
+        #[arg(long, default_value_t = crate::tasks::DEFAULT_LEASE_MINUTES)]
+        lease_minutes: i64,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Give up this session's lease on a task
+    ///
+    /// Examples:
+    ///   ie task release 42
+    Release {
+        /// Task ID
+        id: i64,
+
+        /// Session ID holding the lease
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Raise the effective priority of todo tasks that have waited long
+    ///
+    /// `ie task next` and the Dashboard do this on their own; the curve is
+    /// set by the priority.aging.* settings (see `ie config`).
+    ///
+    /// Examples:
+    ///   ie task age
+    ///   ie config set priority.aging.curve log
+    Age {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Flag doing tasks without activity for days, optionally moving them back to todo
+    ///
+    /// A doing task is stale after stale.days days (default 14) without
+    /// events or moves to doing; each stale spell is logged as a `stale`
+    /// event. With stale.demote set (see `ie config`) or --demote, stale
+    /// tasks go back to todo. `ie db maintain` and the Dashboard run this on
+    /// their own.
+    ///
+    /// Examples:
+    ///   ie task stale
+    ///   ie task stale --days 7 --demote
+    Stale {
+        /// Days without activity, instead of the stale.days setting
+        #[arg(long)]
+        days: Option<f64>,
+
+        /// Move stale tasks back to todo
+        #[arg(long)]
+        demote: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Suggest a complexity (1-10) from similar completed tasks
+    ///
+    /// Weighs the recorded complexity, or else the cycle time, of the done
+    /// tasks whose names and specs are most like this one.
+    ///
+    /// Examples:
+    ///   ie task estimate 42
+    ///   ie task estimate 42 --apply
+    Estimate {
+        /// Task ID
+        id: i64,
+
+        /// Store the estimate as the task's complexity
+        #[arg(long)]
+        apply: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand, Clone)]
 pub enum DashboardCommands {
     /// Start the Dashboard server
     Start {
-        /// Port to bind (default: 11391)
+        /// Port to bind (default: dashboard.port in config.toml, else 11391)
         #[arg(long)]
         port: Option<u16>,
 
@@ -640,10 +2001,21 @@ pub enum DashboardCommands {
     },
 
     /// Show Dashboard status
+    ///
+    /// Reports the PID, uptime, restarts after crashes and connected clients
+    /// of a running Dashboard, and removes the PID file a crashed one left.
     Status {
         /// Show all instances
         #[arg(long)]
         all: bool,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+
+        /// Same as --format json
+        #[arg(long)]
+        json: bool,
     },
 
     /// List registered projects
@@ -651,4 +2023,144 @@ pub enum DashboardCommands {
 
     /// Open Dashboard in browser
     Open,
+
+    /// Run the Dashboard as a service that survives the terminal
+    ///
+    /// Writes a systemd user unit (Linux) or launchd agent (macOS) that starts
+    /// the Dashboard of the current project at login, and starts it now. On
+    /// Windows it creates a service in the Service Control Manager, which
+    /// needs an elevated prompt. The service restarts the Dashboard after a
+    /// crash but not after `ie dashboard stop`; logs go to the daily rotated
+    /// ~/.intent-engine/logs/dashboard.log.
+    ///
+    /// Examples:
+    ///   ie dashboard install-service
+    ///   ie dashboard install-service --print > ie-dashboard.service
+    InstallService {
+        /// Port to bind (default: dashboard.port in config.toml, else 11391)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Print the unit file, plist or sc.exe commands instead of installing
+        #[arg(long)]
+        print: bool,
+
+        /// Replace an installed service
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Stop the Dashboard service and remove it
+    UninstallService,
+
+    /// Entry point of the Windows service (started by the Service Control Manager)
+    #[command(hide = true)]
+    RunService {
+        #[arg(long)]
+        port: u16,
+
+        /// Project root the Dashboard serves
+        #[arg(long)]
+        project: String,
+    },
+
+    /// Manage Dashboard access tokens
+    ///
+    /// Once a token exists, every Dashboard request (HTTP and WebSocket)
+    /// must present one. Revoking the last token disables authentication.
+    #[command(subcommand)]
+    Token(TokenCommands),
+
+    /// Manage scheduled reports
+    ///
+    /// A running Dashboard generates each report on its cron schedule (local
+    /// time) and writes it as JSON to a directory or POSTs it to a webhook.
+    ///
+    /// Examples:
+    ///   ie dashboard schedule add "0 9 * * 1-5" --report summary --dir ~/reports
+    ///   ie dashboard schedule add "0 17 * * 5" --report cycle-time --since 7d --webhook https://example.com/hook
+    ///   ie dashboard schedule list
+    #[command(subcommand)]
+    Schedule(ScheduleCommands),
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ScheduleCommands {
+    /// Schedule a report for the current project
+    Add {
+        /// Five-field cron expression: minute hour day-of-month month day-of-week
+        cron: String,
+
+        /// Report to generate (summary, aging, cycle-time, milestone)
+        #[arg(long)]
+        report: String,
+
+        /// Time window for summary (default 1d) and cycle-time reports
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Milestone name, for milestone reports
+        #[arg(long)]
+        milestone: Option<String>,
+
+        /// Write each report as a JSON file in this directory
+        #[arg(long, conflicts_with = "webhook", required_unless_present = "webhook")]
+        dir: Option<String>,
+
+        /// POST each report as JSON to this URL
+        #[arg(long)]
+        webhook: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// List scheduled reports
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Remove a scheduled report by ID
+    Remove {
+        /// Job ID (from 'ie dashboard schedule list')
+        id: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum TokenCommands {
+    /// Create a new access token (shown once)
+    Create {
+        /// Label to identify the token
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// List access tokens
+    List {
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Revoke an access token by ID
+    Revoke {
+        /// Token ID (from 'ie dashboard token list')
+        id: String,
+
+        /// Output format (text or json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 }
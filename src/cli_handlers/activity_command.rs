@@ -0,0 +1,53 @@
+use crate::db::models::{ActivityFilter, ActivityKind};
+use crate::error::Result;
+use crate::events::EventManager;
+use crate::project::ProjectContext;
+
+/// Longest message excerpt shown per entry in text output
+const MESSAGE_EXCERPT_CHARS: usize = 80;
+
+/// Handle `ie activity`: the cross-task activity feed
+pub async fn handle_activity(limit: i64, filter: ActivityFilter, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load_read_only().await?;
+    let entries = EventManager::new(&ctx.pool)
+        .recent_activity(limit, &filter)
+        .await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No activity");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let what = match entry.kind {
+            ActivityKind::Created => "created".to_string(),
+            ActivityKind::Started => "started".to_string(),
+            ActivityKind::Completed => "completed".to_string(),
+            ActivityKind::Event => {
+                let message = entry.message.as_deref().unwrap_or_default();
+                let first_line = message.lines().next().unwrap_or_default();
+                let mut excerpt: String = first_line.chars().take(MESSAGE_EXCERPT_CHARS).collect();
+                if excerpt.len() < message.len() {
+                    excerpt.push('…');
+                }
+                format!(
+                    "{}: {}",
+                    entry.log_type.as_deref().unwrap_or("event"),
+                    excerpt
+                )
+            },
+        };
+        println!(
+            "{}  #{} {}  {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M UTC"),
+            entry.task_id,
+            entry.task_name,
+            what
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use crate::anonymize::export_anonymized;
+use crate::archive::{
+    ArchiveManager, ConflictKind, ConflictResolution, ExportFormat, ImportOptions, ImportReport,
+    ProjectArchive,
+};
+use crate::cli::ExportCommands;
+use crate::error::{IntentError, Result};
+use crate::otel::{OtelConfig, TraceExporter};
+use crate::project::ProjectContext;
+use std::collections::HashMap;
+
+/// Handle `ie export` subcommands
+pub async fn handle_export_command(cmd: ExportCommands) -> Result<()> {
+    match cmd {
+        ExportCommands::Archive {
+            task,
+            output,
+            format,
+        } => handle_export_archive(task, output, &format).await,
+        ExportCommands::Anonymized { output, format } => {
+            handle_export_anonymized(&output, &format).await
+        },
+        ExportCommands::Traces {
+            task,
+            endpoint,
+            output,
+            format,
+        } => handle_export_traces(task, endpoint, output, &format).await,
+    }
+}
+
+async fn handle_export_anonymized(output: &str, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let report = export_anonymized(&ctx.pool, std::path::Path::new(output)).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Wrote anonymized database to {} ({} tasks, {} events, {} dependencies)",
+            report.output, report.tasks, report.events, report.dependencies
+        );
+        println!("Review it before sharing: timestamps and the task structure are kept as-is.");
+    }
+
+    Ok(())
+}
+
+async fn handle_export_traces(
+    task: Option<i64>,
+    endpoint: Option<String>,
+    output: Option<String>,
+    format: &str,
+) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let config = OtelConfig::load(&ctx.pool).await?;
+    let batch = TraceExporter::new(&ctx.pool, config.service_name)
+        .collect(task)
+        .await?;
+
+    let summary = match (output, endpoint.or(config.endpoint)) {
+        (Some(path), _) => {
+            std::fs::write(&path, serde_json::to_string_pretty(&batch.to_otlp_json())?)?;
+            eprintln!("Wrote OTLP payload to {}", path);
+            batch.summary(None)
+        },
+        (None, Some(endpoint)) => {
+            let url = batch.send(&endpoint).await?;
+            batch.summary(Some(url))
+        },
+        (None, None) => {
+            // Nothing configured: the payload itself is the output
+            println!("{}", serde_json::to_string_pretty(&batch.to_otlp_json())?);
+            return Ok(());
+        },
+    };
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        println!(
+            "Exported {} spans with {} span events{}",
+            summary.spans,
+            summary.span_events,
+            summary
+                .endpoint
+                .as_deref()
+                .map(|url| format!(" to {}", url))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+async fn handle_export_archive(
+    task: Option<i64>,
+    output: Option<String>,
+    format: &str,
+) -> Result<()> {
+    let format: ExportFormat = format.parse()?;
+    let ctx = ProjectContext::load_or_init().await?;
+    let manager = ArchiveManager::new(&ctx.pool);
+
+    match output {
+        Some(path) => {
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            let summary = match manager.export_to(task, format, file).await {
+                Ok(summary) => summary,
+                Err(e) => {
+                    // Don't leave a truncated archive behind
+                    let _ = std::fs::remove_file(&path);
+                    return Err(e);
+                },
+            };
+            eprintln!(
+                "Exported {} tasks, {} events, {} dependencies, {} links to {}",
+                summary.tasks, summary.events, summary.dependencies, summary.links, path
+            );
+        },
+        None => {
+            let stdout = std::io::BufWriter::new(std::io::stdout());
+            manager.export_to(task, format, stdout).await?;
+        },
+    }
+
+    Ok(())
+}
+
+/// Handle `ie import`
+pub async fn handle_import_command(
+    file: &str,
+    on_conflict: Option<&str>,
+    resolve: &[String],
+    parent: Option<i64>,
+    dry_run: bool,
+    format: &str,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)?;
+    let archive = ProjectArchive::parse(&content)
+        .map_err(|e| IntentError::InvalidInput(format!("Invalid archive '{}': {}", file, e)))?;
+
+    let options = ImportOptions {
+        on_conflict: on_conflict.map(str::parse).transpose()?,
+        resolutions: parse_resolutions(resolve)?,
+        parent_id: parent,
+        dry_run,
+    };
+
+    let ctx = ProjectContext::load_or_init().await?;
+    let report = ArchiveManager::new(&ctx.pool)
+        .import(&archive, &options)
+        .await?;
+
+    if report.applied {
+        crate::dashboard::cli_notifier::CliNotifier::new()
+            .notify_task_changed(
+                None,
+                "imported",
+                Some(ctx.root.to_string_lossy().to_string()),
+            )
+            .await;
+    }
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report, dry_run);
+    }
+
+    if !dry_run && report.unresolved > 0 {
+        return Err(IntentError::ActionNotAllowed(format!(
+            "{} unresolved conflict(s); nothing was imported. Use --on-conflict or --resolve ID=skip|overwrite|duplicate",
+            report.unresolved
+        )));
+    }
+
+    Ok(())
+}
+
+/// Parse repeated `ID=CHOICE` arguments
+fn parse_resolutions(values: &[String]) -> Result<HashMap<i64, ConflictResolution>> {
+    values
+        .iter()
+        .map(|value| {
+            let (id, choice) = value.split_once('=').ok_or_else(|| {
+                IntentError::InvalidInput(format!(
+                    "Invalid --resolve '{}'. Expected ID=skip|overwrite|duplicate",
+                    value
+                ))
+            })?;
+            let id = id.trim().parse::<i64>().map_err(|_| {
+                IntentError::InvalidInput(format!("Invalid task ID in --resolve '{}'", value))
+            })?;
+            Ok((id, choice.trim().parse()?))
+        })
+        .collect()
+}
+
+fn print_report(report: &ImportReport, dry_run: bool) {
+    if !report.conflicts.is_empty() {
+        println!("Conflicts ({}):", report.conflicts.len());
+        for conflict in &report.conflicts {
+            let detail = match conflict.kind {
+                ConflictKind::ContentMismatch => format!(
+                    "same name as #{}, differs in {}",
+                    conflict.existing_task_id,
+                    conflict.fields.join(", ")
+                ),
+                ConflictKind::IdCollision => format!("ID taken by \"{}\"", conflict.existing_name),
+            };
+            let resolution = match conflict.resolution {
+                Some(ConflictResolution::Skip) => "skip",
+                Some(ConflictResolution::Overwrite) => "overwrite",
+                Some(ConflictResolution::Duplicate) => "duplicate",
+                None => "UNRESOLVED",
+            };
+            println!(
+                "  #{} {} ({}) -> {}",
+                conflict.archive_task_id, conflict.name, detail, resolution
+            );
+        }
+        println!();
+    }
+
+    if dry_run {
+        println!("Dry run: nothing was imported.");
+        return;
+    }
+    if !report.applied {
+        return;
+    }
+
+    println!(
+        "Imported: {} created, {} updated, {} skipped, {} unchanged",
+        report.tasks_created, report.tasks_updated, report.tasks_skipped, report.tasks_unchanged
+    );
+    println!(
+        "          {} events, {} dependencies, {} links",
+        report.events_imported, report.dependencies_imported, report.links_imported
+    );
+    for warning in &report.warnings {
+        println!("Warning: {}", warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resolutions() {
+        let parsed =
+            parse_resolutions(&["3=overwrite".to_string(), "7 = skip".to_string()]).unwrap();
+        assert_eq!(parsed[&3], ConflictResolution::Overwrite);
+        assert_eq!(parsed[&7], ConflictResolution::Skip);
+
+        assert!(parse_resolutions(&["3".to_string()]).is_err());
+        assert!(parse_resolutions(&["x=skip".to_string()]).is_err());
+        assert!(parse_resolutions(&["3=merge".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Commands run against the Neo4j and Postgres backends
+//!
+//! Shared by the `ie-neo4j` binary and by `ie` in projects configured with
+//! `[storage] backend = "neo4j"` or `"postgres"`. Everything goes through the
+//! backend traits, so the handlers are the same ones `ie` uses for SQLite.
+
+use std::future::Future;
+
+use crate::backend::Backend;
+use crate::cli::{Cli, Commands};
+use crate::cli_handlers::{
+    execute_under_focus, handle_completions, handle_errors_command, handle_introspect, handle_log,
+    handle_search, handle_status, handle_task_command, print_plan_result, print_plan_schema,
+    print_plan_validation, read_stdin, LogInput,
+};
+use crate::error::{IntentError, Result};
+use crate::hooks::HookEvent;
+use crate::plan::{cleanup_included_files, parse_request, process_file_includes};
+use crate::project::ProjectContext;
+
+/// Run `cli` on Neo4j; `bin` is the program name for completions and errors
+#[cfg(feature = "neo4j")]
+pub async fn run_neo4j(cli: &Cli, bin: &str) -> Result<()> {
+    run_on_backend(cli, bin, "Neo4j", crate::neo4j::Neo4jContext::connect).await
+}
+
+/// Run `cli` on Postgres; `bin` is the program name for completions and errors
+#[cfg(feature = "postgres")]
+pub async fn run_postgres(cli: &Cli, bin: &str) -> Result<()> {
+    run_on_backend(
+        cli,
+        bin,
+        "Postgres",
+        crate::postgres::PostgresContext::connect,
+    )
+    .await
+}
+
+/// Run `cli` on the backend `connect` opens; `label` names it in errors
+async fn run_on_backend<B, F, Fut>(cli: &Cli, bin: &str, label: &str, connect: F) -> Result<()>
+where
+    B: Backend,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<B>>,
+{
+    if cli.read_only {
+        return Err(IntentError::InvalidInput(format!(
+            "--read-only is not supported by the {label} backend"
+        )));
+    }
+
+    match cli.command.clone() {
+        Commands::Status {
+            task_id,
+            with_events,
+            max_tokens,
+            max_chars,
+            event_visibility,
+            format,
+        } => {
+            if max_tokens.is_some() || max_chars.is_some() {
+                return Err(IntentError::InvalidInput(format!(
+                    "Restore budgets are not supported by the {label} backend"
+                )));
+            }
+            if !event_visibility.is_normal() {
+                return Err(IntentError::InvalidInput(format!(
+                    "{label} backend only stores normal events"
+                )));
+            }
+            let ctx = connect().await?;
+            handle_status(
+                &ctx.tasks(),
+                &ctx.workspace(),
+                task_id,
+                with_events,
+                &format,
+            )
+            .await?;
+        },
+
+        Commands::Task(task_cmd) => {
+            let ctx = connect().await?;
+            handle_task_command(&ctx.tasks(), &ctx.workspace(), task_cmd).await?;
+        },
+
+        Commands::Log {
+            event_type,
+            message,
+            task,
+            visibility,
+            timestamp,
+            stdin_json,
+            format,
+        } => {
+            let ctx = connect().await?;
+            let input = LogInput::from_args(event_type, message, timestamp, stdin_json)?;
+            handle_log(
+                &ctx.events(),
+                &ctx.workspace(),
+                input,
+                task,
+                visibility,
+                &format,
+            )
+            .await?;
+        },
+
+        Commands::Plan {
+            format,
+            validate,
+            schema,
+            workspace,
+        } => {
+            if schema {
+                return print_plan_schema();
+            }
+            if workspace.is_some() {
+                return Err(IntentError::InvalidInput(format!(
+                    "Plan workspaces are not supported by the {label} backend"
+                )));
+            }
+
+            let json_input = read_stdin()?;
+            let mut request = parse_request(&json_input)?;
+
+            let file_include_result =
+                process_file_includes(&mut request).map_err(IntentError::InvalidInput)?;
+
+            if validate {
+                return print_plan_validation(&request, &format);
+            }
+
+            let ctx = connect().await?;
+            let result = execute_under_focus(&ctx, &request).await?;
+
+            if result.success && !file_include_result.files_to_delete.is_empty() {
+                cleanup_included_files(&file_include_result.files_to_delete);
+            }
+
+            print_plan_result(&result, &format)?;
+            if result.success {
+                ProjectContext::configured_hooks()?.fire(HookEvent::PlanExecuted, &result)?;
+            }
+        },
+
+        Commands::Search {
+            query,
+            tasks,
+            events,
+            limit,
+            offset,
+            since,
+            until,
+            event_types,
+            format,
+            semantic,
+        } => {
+            if semantic {
+                return Err(IntentError::InvalidInput(format!(
+                    "--semantic is not supported by the {label} backend"
+                )));
+            }
+            let ctx = connect().await?;
+            handle_search(
+                &ctx,
+                &query,
+                tasks,
+                events,
+                event_types,
+                limit,
+                offset,
+                since,
+                until,
+                &format,
+            )
+            .await?
+        },
+
+        Commands::Completions { shell } => handle_completions(shell, bin),
+
+        Commands::Introspect { json } => handle_introspect(json, bin)?,
+
+        Commands::Errors(errors_cmd) => handle_errors_command(errors_cmd)?,
+
+        _ => {
+            return Err(IntentError::InvalidInput(format!(
+                "Command not yet implemented for the {label} backend. Currently supported: \
+                 {bin} status, {bin} task *, {bin} log, {bin} plan, {bin} search, \
+                 {bin} completions, {bin} introspect, {bin} errors"
+            )));
+        },
+    }
+
+    Ok(())
+}
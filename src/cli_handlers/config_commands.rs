@@ -1,6 +1,7 @@
 use crate::cli::ConfigCommands;
 use crate::error::{IntentError, Result};
 use crate::project::ProjectContext;
+use crate::settings::{SettingStore, SettingsManager, SETTINGS};
 use serde_json::json;
 use sqlx::SqlitePool;
 
@@ -8,13 +9,13 @@ use sqlx::SqlitePool;
 const PROTECTED_KEYS: &[&str] = &["schema_version"];
 
 /// Keys whose values should be masked in output
-fn is_sensitive_key(key: &str) -> bool {
+pub(crate) fn is_sensitive_key(key: &str) -> bool {
     let lower = key.to_lowercase();
     lower.contains("api_key") || lower.contains("secret")
 }
 
 /// Mask a sensitive value for display: show first 4 chars + ********
-fn mask_value(value: &str) -> String {
+pub(crate) fn mask_value(value: &str) -> String {
     if value.len() <= 4 {
         "********".to_string()
     } else {
@@ -42,7 +43,9 @@ async fn handle_set(key: &str, value: &str, format: &str) -> Result<()> {
     }
 
     let ctx = ProjectContext::load_or_init().await?;
-    config_set(&ctx.pool, key, value).await?;
+    SettingsManager::new(&ctx.pool)
+        .set(key, Some(value), "cli")
+        .await?;
 
     let display_value = if is_sensitive_key(key) {
         mask_value(value)
@@ -68,7 +71,7 @@ async fn handle_set(key: &str, value: &str, format: &str) -> Result<()> {
 
 async fn handle_get(key: &str, format: &str) -> Result<()> {
     let ctx = ProjectContext::load_or_init().await?;
-    let value = config_get(&ctx.pool, key).await?;
+    let value = SettingsManager::new(&ctx.pool).get(key).await?;
 
     match value {
         Some(v) => {
@@ -110,7 +113,17 @@ async fn handle_get(key: &str, format: &str) -> Result<()> {
 
 async fn handle_list(prefix: Option<&str>, format: &str) -> Result<()> {
     let ctx = ProjectContext::load_or_init().await?;
-    let entries = config_list(&ctx.pool, prefix).await?;
+    let mut entries = config_list(&ctx.pool, prefix).await?;
+    // Capacity and policy settings are kept in config.toml
+    let settings = SettingsManager::new(&ctx.pool);
+    for spec in SETTINGS.iter().filter(|spec| {
+        spec.store == SettingStore::ConfigFile && prefix.is_none_or(|p| spec.key.starts_with(p))
+    }) {
+        if let Some(value) = settings.get(spec.key).await? {
+            entries.push((spec.key.to_string(), value));
+        }
+    }
+    entries.sort();
 
     if format == "json" {
         let items: Vec<serde_json::Value> = entries
@@ -155,7 +168,10 @@ async fn handle_unset(key: &str, format: &str) -> Result<()> {
     }
 
     let ctx = ProjectContext::load_or_init().await?;
-    let deleted = config_delete(&ctx.pool, key).await?;
+    let deleted = SettingsManager::new(&ctx.pool)
+        .set(key, None, "cli")
+        .await?
+        .is_some();
 
     if format == "json" {
         println!(
@@ -1,9 +1,25 @@
-use crate::cli::DashboardCommands;
+use crate::cli::{DashboardCommands, ScheduleCommands, TokenCommands};
+use crate::dashboard::auth::{with_cli_token, TokenStore};
+use crate::dashboard::daemon::{PidFile, PidState};
 use crate::error::{IntentError, Result};
 use crate::project::ProjectContext;
 
 /// Dashboard server default port
-pub const DASHBOARD_PORT: u16 = 11391;
+pub const DASHBOARD_PORT: u16 = crate::config::DEFAULT_DASHBOARD_PORT;
+
+/// Port of the Dashboard for the current project (`dashboard.port` in config.toml)
+fn configured_port() -> u16 {
+    let Some(root) = ProjectContext::find_project_root() else {
+        return DASHBOARD_PORT;
+    };
+    match crate::config::ProjectConfig::for_project_root(&root) {
+        Ok(config) => config.dashboard.port,
+        Err(e) => {
+            tracing::warn!(error = %e, "Ignoring invalid project config");
+            DASHBOARD_PORT
+        },
+    }
+}
 
 /// Send HTTP shutdown request to Dashboard
 async fn send_shutdown_request(port: u16) -> Result<()> {
@@ -16,9 +32,12 @@ async fn send_shutdown_request(port: u16) -> Result<()> {
             IntentError::OtherError(anyhow::anyhow!("Failed to create HTTP client: {}", e))
         })?;
 
-    let response = client.post(&url).send().await.map_err(|e| {
-        IntentError::OtherError(anyhow::anyhow!("Failed to send shutdown request: {}", e))
-    })?;
+    let response = with_cli_token(client.post(&url))
+        .send()
+        .await
+        .map_err(|e| {
+            IntentError::OtherError(anyhow::anyhow!("Failed to send shutdown request: {}", e))
+        })?;
 
     if response.status().is_success() {
         Ok(())
@@ -58,6 +77,34 @@ pub async fn check_dashboard_health(port: u16) -> bool {
     }
 }
 
+/// Body of `/api/health` (pid, uptime, clients, ...) of the Dashboard on `port`
+async fn fetch_health(port: u16) -> Option<serde_json::Value> {
+    let url = format!("http://127.0.0.1:{}/api/health", port);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(2))
+        .build()
+        .ok()?;
+    let response = client.get(&url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Uptime as its two largest units, e.g. `3h 12m`
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 /// Check Dashboard status and return formatted JSON result
 pub async fn check_dashboard_status() -> serde_json::Value {
     use serde_json::json;
@@ -121,7 +168,7 @@ pub async fn check_mcp_connections() -> serde_json::Value {
         },
     };
 
-    match client.get(&url).send().await {
+    match with_cli_token(client.get(&url)).send().await {
         Ok(resp) if resp.status().is_success() => {
             if let Ok(data) = resp.json::<serde_json::Value>().await {
                 let empty_vec = vec![];
@@ -167,10 +214,6 @@ async fn start_foreground_mode(
     project_name: String,
     browser: bool,
 ) -> Result<()> {
-    use crate::dashboard::server::DashboardServer;
-
-    let server = DashboardServer::new(port, project_path, db_path).await?;
-
     println!("Dashboard starting for project: {}", project_name);
     println!("  Port: {}", port);
     println!("  URL: http://127.0.0.1:{}", port);
@@ -189,10 +232,8 @@ async fn start_foreground_mode(
         println!();
     }
 
-    // Run server (blocks until terminated)
-    server.run().await.map_err(IntentError::OtherError)?;
-
-    Ok(())
+    // Run server (blocks until terminated), restarting it after a crash
+    crate::dashboard::daemon::supervise(port, project_path, db_path).await
 }
 
 /// Start Dashboard in daemon (background) mode
@@ -258,18 +299,30 @@ async fn start_daemon_mode(
                 eprintln!("Failed to redirect stderr: {}", e);
             }
 
-            // Start server in child process
-            use crate::dashboard::server::DashboardServer;
-            let server = DashboardServer::new(port, project_path, db_path).await?;
-
             tracing::info!("Dashboard daemon started (PID: {})", std::process::id());
             tracing::info!("Port: {}", port);
             tracing::info!("Log file: {}", log_file_path.display());
 
-            // Run server (blocks until terminated)
-            server.run().await.map_err(IntentError::OtherError)?;
-
-            Ok(())
+            // The runtime's worker threads did not survive the fork, so
+            // nothing spawned on it would ever run; serve from a fresh one
+            let supervisor = std::thread::spawn(move || {
+                tokio::runtime::Runtime::new()?.block_on(crate::dashboard::daemon::supervise(
+                    port,
+                    project_path,
+                    db_path,
+                ))
+            });
+            let code = match supervisor.join() {
+                Ok(Ok(())) => 0,
+                Ok(Err(e)) => {
+                    tracing::error!(error = %e, "Dashboard daemon failed");
+                    1
+                },
+                Err(_) => 1,
+            };
+            // Shutting down the inherited runtime would wait on threads that
+            // do not exist in this process
+            std::process::exit(code)
         },
         Err(e) => Err(IntentError::OtherError(anyhow::anyhow!(
             "Failed to fork process: {}",
@@ -366,8 +419,15 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
                 .unwrap_or("unknown")
                 .to_string();
 
-            // Allocate port (always 11391, or custom if specified)
-            let allocated_port = port.unwrap_or(11391);
+            // Allocate port (--port, else the project's configured port)
+            let allocated_port = match port {
+                Some(port) => port,
+                None => {
+                    crate::config::ProjectConfig::for_project_root(&project_path)?
+                        .dashboard
+                        .port
+                },
+            };
 
             // Check if already running using HTTP health check
             if check_dashboard_health(allocated_port).await {
@@ -377,6 +437,22 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
                 return Ok(());
             }
 
+            // A process that holds the port without answering is not replaced
+            // silently; a PID file whose process is gone is dropped
+            match PidFile::inspect(allocated_port) {
+                PidState::Alive(record) if record.pid != std::process::id() => {
+                    return Err(IntentError::InvalidInput(format!(
+                        "Dashboard process {} is not responding on port {}; stop it with \
+                         'ie dashboard stop'",
+                        record.pid, allocated_port
+                    )));
+                },
+                PidState::Stale(record) => {
+                    tracing::info!(pid = record.pid, "Removed stale Dashboard PID file");
+                },
+                _ => {},
+            }
+
             // Check if port is available (use 0.0.0.0 to match actual server binding)
             if std::net::TcpListener::bind(("0.0.0.0", allocated_port)).is_err() {
                 return Err(IntentError::InvalidInput(format!(
@@ -400,7 +476,7 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
         },
 
         DashboardCommands::Stop { all } => {
-            let port = 11391;
+            let port = configured_port();
 
             if all {
                 println!("Note: Single Dashboard mode - checking port {}", port);
@@ -408,7 +484,22 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
 
             // Check if Dashboard is running via HTTP health check
             if !check_dashboard_health(port).await {
-                println!("Dashboard not running");
+                match PidFile::inspect(port) {
+                    PidState::Alive(record) => {
+                        crate::dashboard::daemon::terminate(record.pid)?;
+                        println!(
+                            "✓ Sent SIGTERM to unresponsive Dashboard process {}",
+                            record.pid
+                        );
+                    },
+                    PidState::Stale(record) => {
+                        println!(
+                            "Dashboard not running (removed stale PID file of process {})",
+                            record.pid
+                        );
+                    },
+                    PidState::Missing => println!("Dashboard not running"),
+                }
                 return Ok(());
             }
 
@@ -439,49 +530,146 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
             Ok(())
         },
 
-        DashboardCommands::Status { all } => {
-            let port = 11391;
+        DashboardCommands::Status { all, format, json } => {
+            let port = configured_port();
+            let json = json || crate::output::is_json(&format);
 
-            if all {
+            if all && !json {
                 println!("Note: Single Dashboard mode - checking port {}", port);
             }
 
             // Check if dashboard is running via HTTP health check
-            if check_dashboard_health(port).await {
+            let health = fetch_health(port).await;
+            let running = health.is_some();
+            // Without an answer, the PID file tells a hung process from a crashed one
+            let pid_state = if running {
+                PidState::Missing
+            } else {
+                PidFile::inspect(port)
+            };
+            let mut project = None;
+            if running {
                 // Dashboard is healthy - get project info via API
                 let url = format!("http://127.0.0.1:{}/api/info", port);
-                println!("Dashboard status:");
-                println!("  Status: ✓ Running");
-                println!("  Port: {}", port);
-                println!("  URL: http://127.0.0.1:{}", port);
-
-                if let Ok(response) = reqwest::get(&url).await {
+                let client = reqwest::Client::new();
+                if let Ok(response) = with_cli_token(client.get(&url)).send().await {
                     if response.status().is_success() {
                         #[derive(serde::Deserialize)]
                         struct InfoResponse {
                             data: serde_json::Value,
                         }
                         if let Ok(info) = response.json::<InfoResponse>().await {
-                            if let Some(project_name) = info.data.get("project_name") {
-                                println!("  Project: {}", project_name);
-                            }
-                            if let Some(project_path) = info.data.get("project_path") {
-                                println!("  Path: {}", project_path);
-                            }
+                            project = Some(info.data);
                         }
                     }
                 }
+            }
+
+            if json {
+                let mut warnings = Vec::new();
+                if all {
+                    warnings.push(format!(
+                        "Single Dashboard mode: only port {} was checked",
+                        port
+                    ));
+                }
+                if running && project.is_none() {
+                    warnings
+                        .push("Dashboard is running but project info was unavailable".to_string());
+                }
+                let state = match &pid_state {
+                    _ if running => "running",
+                    PidState::Alive(record) => {
+                        warnings.push(format!(
+                            "Dashboard process {} is not responding; stop it with 'ie dashboard stop'",
+                            record.pid
+                        ));
+                        "unresponsive"
+                    },
+                    PidState::Stale(record) => {
+                        warnings.push(format!(
+                            "Removed stale PID file of process {}, which exited without cleaning up",
+                            record.pid
+                        ));
+                        "stopped"
+                    },
+                    PidState::Missing => "stopped",
+                };
+                let field = |key: &str| project.as_ref().and_then(|p| p.get(key)).cloned();
+                let health_field = |key: &str| health.as_ref().and_then(|h| h.get(key)).cloned();
+                let pid = match &pid_state {
+                    PidState::Alive(record) => Some(serde_json::json!(record.pid)),
+                    _ => health_field("pid"),
+                };
+                let data = serde_json::json!({
+                    "running": running,
+                    "state": state,
+                    "port": port,
+                    "url": format!("http://127.0.0.1:{}", port),
+                    "pid": pid,
+                    "started_at": health_field("started_at"),
+                    "uptime_seconds": health_field("uptime_seconds"),
+                    "restarts": health_field("restarts"),
+                    "clients": health_field("clients"),
+                    "project_name": field("project_name"),
+                    "project_path": field("project_path"),
+                });
+                return crate::output::print_success(data, warnings);
+            }
+
+            println!("Dashboard status:");
+            if let Some(health) = &health {
+                let number = |key: &str| health.get(key).and_then(|v| v.as_u64());
+                println!("  Status: ✓ Running");
+                println!("  Port: {}", port);
+                println!("  URL: http://127.0.0.1:{}", port);
+                if let Some(pid) = number("pid") {
+                    println!("  PID: {}", pid);
+                }
+                if let Some(uptime) = number("uptime_seconds") {
+                    println!("  Uptime: {}", format_uptime(uptime));
+                }
+                if let Some(restarts) = number("restarts").filter(|r| *r > 0) {
+                    println!("  Restarts after crashes: {}", restarts);
+                }
+                if let Some(clients) = health.get("clients") {
+                    let count = |kind: &str| clients.get(kind).and_then(|v| v.as_u64());
+                    println!(
+                        "  Clients: {} UI, {} MCP",
+                        count("ui").unwrap_or(0),
+                        count("mcp").unwrap_or(0)
+                    );
+                }
+                if let Some(info) = &project {
+                    if let Some(project_name) = info.get("project_name") {
+                        println!("  Project: {}", project_name);
+                    }
+                    if let Some(project_path) = info.get("project_path") {
+                        println!("  Path: {}", project_path);
+                    }
+                }
+            } else if let PidState::Alive(record) = &pid_state {
+                println!("  Status: ⚠ Not responding");
+                println!("  Port: {}", port);
+                println!("  PID: {}", record.pid);
+                println!("  Stop it with: ie dashboard stop");
             } else {
-                println!("Dashboard status:");
                 println!("  Status: ✗ Not running");
                 println!("  Port: {}", port);
+                if let PidState::Stale(record) = &pid_state {
+                    println!(
+                        "  Removed stale PID file of process {} (started {})",
+                        record.pid,
+                        record.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    );
+                }
             }
 
             Ok(())
         },
 
         DashboardCommands::List => {
-            let port = 11391;
+            let port = configured_port();
 
             // Check if dashboard is running
             if !check_dashboard_health(port).await {
@@ -492,7 +680,8 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
 
             // Get project list via API
             let url = format!("http://127.0.0.1:{}/api/projects", port);
-            match reqwest::get(&url).await {
+            let client = reqwest::Client::new();
+            match with_cli_token(client.get(&url)).send().await {
                 Ok(response) if response.status().is_success() => {
                     #[derive(serde::Deserialize)]
                     struct ApiResponse {
@@ -556,7 +745,7 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
         },
 
         DashboardCommands::Open => {
-            let port = 11391;
+            let port = configured_port();
 
             // Check if dashboard is running via HTTP health check
             if !check_dashboard_health(port).await {
@@ -570,6 +759,12 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
             let url = format!("http://127.0.0.1:{}", port);
             println!("Opening dashboard: {}", url);
 
+            // The query token is exchanged for a session cookie on first load
+            let url = match crate::dashboard::auth::cli_token() {
+                Some(token) => format!("{}/?token={}", url, token),
+                None => url,
+            };
+
             if let Err(e) = open::that(&url) {
                 eprintln!("Failed to open browser: {}", e);
                 eprintln!("Please manually visit: {}", url);
@@ -577,6 +772,232 @@ pub async fn handle_dashboard_command(dashboard_cmd: DashboardCommands) -> Resul
 
             Ok(())
         },
+
+        DashboardCommands::InstallService { port, print, force } => {
+            use crate::dashboard::service::{self, ServiceManager, ServiceSpec};
+
+            let project_ctx = ProjectContext::load_or_init().await?;
+            let port = match port {
+                Some(port) => port,
+                None => {
+                    crate::config::ProjectConfig::for_project_root(&project_ctx.root)?
+                        .dashboard
+                        .port
+                },
+            };
+            let spec = ServiceSpec {
+                exe: std::env::current_exe()?,
+                project_root: project_ctx.root.clone(),
+                port,
+            };
+
+            if print {
+                print!("{}", spec.render(ServiceManager::current()));
+                return Ok(());
+            }
+
+            if check_dashboard_health(port).await {
+                println!(
+                    "Note: a Dashboard is already running on port {}; stop it with \
+                     'ie dashboard stop' so the service can take over",
+                    port
+                );
+            }
+            let location = service::install(&spec, force)?;
+            println!("✓ Dashboard service installed: {}", location);
+            println!("  Project: {}", spec.project_root.display());
+            println!("  URL: http://127.0.0.1:{}", port);
+            println!("  Remove with: ie dashboard uninstall-service");
+            Ok(())
+        },
+
+        DashboardCommands::UninstallService => {
+            let location = crate::dashboard::service::uninstall()?;
+            println!("✓ Dashboard service removed: {}", location);
+            Ok(())
+        },
+
+        DashboardCommands::RunService { port, project } => tokio::task::spawn_blocking(move || {
+            crate::dashboard::service::run_service(port, project.into())
+        })
+        .await
+        .map_err(|e| IntentError::OtherError(e.into()))?,
+
+        DashboardCommands::Token(token_cmd) => handle_token_command(token_cmd),
+
+        DashboardCommands::Schedule(schedule_cmd) => handle_schedule_command(schedule_cmd).await,
+    }
+}
+
+/// Manage scheduled reports
+async fn handle_schedule_command(schedule_cmd: ScheduleCommands) -> Result<()> {
+    use crate::dashboard::schedule::{ReportTarget, ScheduleStore, ScheduledReport};
+
+    let mut store = ScheduleStore::load();
+
+    match schedule_cmd {
+        ScheduleCommands::Add {
+            cron,
+            report,
+            since,
+            milestone,
+            dir,
+            webhook,
+            format,
+        } => {
+            let report = ScheduledReport::from_args(&report, since, milestone)?;
+            let target = match (dir, webhook) {
+                (_, Some(url)) => ReportTarget::Webhook(url),
+                // The Dashboard runs elsewhere; keep the directory absolute
+                (Some(dir), None) => ReportTarget::Directory(std::env::current_dir()?.join(dir)),
+                (None, None) => {
+                    return Err(IntentError::InvalidInput(
+                        "Give --dir or --webhook for the report".to_string(),
+                    ))
+                },
+            };
+            let ctx = ProjectContext::load_read_only().await?;
+            let job = store.add_job(&cron, ctx.root, ctx.db_path, report, target)?;
+            store.save()?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&job)?);
+            } else {
+                println!("Scheduled {} report {}", job.report.name(), job.id);
+                println!("  When: {}", job.cron);
+                println!("  To: {}", job.target);
+                if !check_dashboard_health(configured_port()).await {
+                    println!(
+                        "Reports run while the Dashboard is running: ie dashboard start --daemon"
+                    );
+                }
+            }
+            Ok(())
+        },
+
+        ScheduleCommands::List { format } => {
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&store.jobs)?);
+            } else if store.jobs.is_empty() {
+                println!("No scheduled reports");
+            } else {
+                println!(
+                    "{:<10} {:<16} {:<11} {:<18} TARGET",
+                    "ID", "CRON", "REPORT", "NEXT RUN"
+                );
+                let now = chrono::Local::now();
+                for job in &store.jobs {
+                    let next = job
+                        .schedule()
+                        .ok()
+                        .and_then(|s| s.next_after(now))
+                        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                    println!(
+                        "{:<10} {:<16} {:<11} {:<18} {}",
+                        job.id,
+                        job.cron,
+                        job.report.name(),
+                        next,
+                        job.target
+                    );
+                    println!("           {}", job.project_path.display());
+                }
+            }
+            Ok(())
+        },
+
+        ScheduleCommands::Remove { id, format } => {
+            if !store.remove_job(&id) {
+                return Err(IntentError::InvalidInput(format!(
+                    "Scheduled report '{}' not found",
+                    id
+                )));
+            }
+            store.save()?;
+
+            if format == "json" {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "removed": id }))?
+                );
+            } else {
+                println!("Removed scheduled report {}", id);
+            }
+            Ok(())
+        },
+    }
+}
+
+/// Manage Dashboard access tokens
+fn handle_token_command(token_cmd: TokenCommands) -> Result<()> {
+    let mut store = TokenStore::load()?;
+
+    match token_cmd {
+        TokenCommands::Create { name, format } => {
+            let (entry, token) = store.create_token(name);
+            store.save()?;
+
+            if format == "json" {
+                let output = serde_json::json!({
+                    "id": entry.id,
+                    "name": entry.name,
+                    "token": token,
+                    "created_at": entry.created_at,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("Created Dashboard token {}", entry.id);
+                println!("  Token: {}", token);
+                println!();
+                println!("Store it now - it cannot be shown again.");
+                println!("Use it as 'Authorization: Bearer <token>' or open the Dashboard with '?token=<token>'.");
+            }
+            Ok(())
+        },
+
+        TokenCommands::List { format } => {
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&store.tokens)?);
+            } else if store.tokens.is_empty() {
+                println!("No Dashboard tokens (authentication disabled)");
+            } else {
+                println!("{:<10} {:<24} CREATED", "ID", "NAME");
+                for entry in &store.tokens {
+                    println!(
+                        "{:<10} {:<24} {}",
+                        entry.id,
+                        entry.name.as_deref().unwrap_or("-"),
+                        entry.created_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+            }
+            Ok(())
+        },
+
+        TokenCommands::Revoke { id, format } => {
+            if !store.revoke_token(&id) {
+                return Err(IntentError::InvalidInput(format!(
+                    "Dashboard token '{}' not found",
+                    id
+                )));
+            }
+            store.save()?;
+
+            if format == "json" {
+                let output = serde_json::json!({
+                    "revoked": id,
+                    "auth_enabled": store.auth_enabled(),
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("Revoked Dashboard token {}", id);
+                if !store.auth_enabled() {
+                    println!("No tokens left - Dashboard authentication is now disabled");
+                }
+            }
+            Ok(())
+        },
     }
 }
 
@@ -0,0 +1,205 @@
+use crate::cli::DbCommands;
+use crate::compaction::{CompactionResult, LlmSummarizer, RuleSummarizer};
+use crate::db::encryption::{self, keychain};
+use crate::db::maintenance;
+use crate::error::{IntentError, Result};
+use crate::events::EventManager;
+use crate::links::ExtractReport;
+use crate::llm::LlmClient;
+use crate::output;
+use crate::project::ProjectContext;
+use crate::stale::StaleSweep;
+use crate::tasks::TaskManager;
+use serde::Serialize;
+
+/// JSON output of `ie db maintain`
+#[derive(Serialize)]
+struct MaintainOutput<'a> {
+    #[serde(flatten)]
+    report: &'a maintenance::MaintenanceReport,
+    stale: &'a StaleSweep,
+    links: &'a ExtractReport,
+}
+
+/// Handle `ie db` subcommands
+pub async fn handle_db(cmd: DbCommands) -> Result<()> {
+    match cmd {
+        DbCommands::Maintain { full, format } => {
+            let ctx = ProjectContext::load().await?;
+            let stale = TaskManager::new(&ctx.pool).sweep_stale(None, false).await?;
+            let links = crate::links::extract(&ctx.pool).await?;
+            let report = maintenance::maintain(&ctx.pool, full).await?;
+
+            if output::is_json(&format) {
+                let mut warnings = Vec::new();
+                if report.checkpoint.busy {
+                    warnings.push(
+                        "WAL checkpoint was blocked by another connection; run again later"
+                            .to_string(),
+                    );
+                }
+                let data = MaintainOutput {
+                    report: &report,
+                    stale: &stale,
+                    links: &links,
+                };
+                return output::print_success(&data, warnings);
+            }
+
+            println!("Analyze: statistics refreshed");
+            match &report.vacuum {
+                Some(vacuum) => println!(
+                    "Vacuum: {} -> {}",
+                    format_bytes(vacuum.bytes_before),
+                    format_bytes(vacuum.bytes_after)
+                ),
+                None => println!("Vacuum: skipped (little free space; use --full to force)"),
+            }
+            if report.checkpoint.busy {
+                println!("Checkpoint: blocked by another connection, run again later");
+            } else {
+                println!(
+                    "Checkpoint: {} WAL page(s) written back, WAL truncated",
+                    report.checkpoint.checkpointed_pages
+                );
+            }
+            if !stale.policy.is_off() {
+                println!(
+                    "Stale: {} doing task(s) idle for {} days or more, {} moved back to todo",
+                    stale.tasks.len(),
+                    stale.policy.days,
+                    stale.demoted()
+                );
+            }
+            println!("Links: {} new from URLs in specs and events", links.added);
+        },
+
+        DbCommands::Compact {
+            task_id,
+            keep,
+            llm,
+            format,
+        } => {
+            let ctx = ProjectContext::load().await?;
+            let event_mgr = EventManager::new(&ctx.pool);
+            let result = if llm {
+                let summarizer = LlmSummarizer::new(LlmClient::from_pool(&ctx.pool).await?);
+                event_mgr.compact_with(task_id, keep, &summarizer).await?
+            } else {
+                event_mgr
+                    .compact_with(task_id, keep, &RuleSummarizer)
+                    .await?
+            };
+            print_compaction(&result, &format)?;
+        },
+
+        DbCommands::Encrypt { format } => {
+            let (_, db_path) = ProjectContext::locate_database()?;
+            if !db_path.exists() {
+                return Err(IntentError::InvalidInput(
+                    "The project has no database yet; run any command first".to_string(),
+                ));
+            }
+            if encryption::is_encrypted(&db_path)? {
+                return Err(IntentError::InvalidInput(format!(
+                    "{} is already encrypted",
+                    db_path.display()
+                )));
+            }
+
+            // Store a generated key before the data depends on it
+            let (key, source) = match encryption::env_key() {
+                Some(key) => (key, encryption::ENV_KEY),
+                None => {
+                    let key = encryption::generate_key();
+                    keychain::set(&db_path, &key)?;
+                    (key, "keychain")
+                },
+            };
+            if let Err(e) = encryption::encrypt(&db_path, &key).await {
+                if source == "keychain" {
+                    keychain::delete(&db_path);
+                }
+                return Err(e);
+            }
+
+            let report = serde_json::json!({
+                "database": db_path.display().to_string(),
+                "encrypted": true,
+                "key_source": source,
+            });
+            if output::is_json(&format) {
+                return output::print_success(&report, Vec::new());
+            }
+            println!("Encrypted {}", db_path.display());
+            if source == "keychain" {
+                println!(
+                    "Key stored in the OS keychain (service '{}', account '{}')",
+                    keychain::SERVICE,
+                    keychain::account(&db_path)
+                );
+            } else {
+                println!(
+                    "Key taken from {}; keep it set for every ie process and the Dashboard",
+                    encryption::ENV_KEY
+                );
+            }
+        },
+
+        DbCommands::Decrypt { format } => {
+            let (_, db_path) = ProjectContext::locate_database()?;
+            let key = encryption::database_key(&db_path).ok_or_else(|| {
+                IntentError::InvalidInput(format!(
+                    "No key found for {}; set {}",
+                    db_path.display(),
+                    encryption::ENV_KEY
+                ))
+            })?;
+            encryption::decrypt(&db_path, &key).await?;
+            let key_removed = keychain::delete(&db_path);
+
+            let report = serde_json::json!({
+                "database": db_path.display().to_string(),
+                "encrypted": false,
+                "keychain_entry_removed": key_removed,
+            });
+            if output::is_json(&format) {
+                return output::print_success(&report, Vec::new());
+            }
+            println!("Decrypted {}", db_path.display());
+            if key_removed {
+                println!("Removed the key from the OS keychain");
+            }
+            if encryption::env_key().is_some() {
+                println!("Unset {} before the next command", encryption::ENV_KEY);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn print_compaction(result: &CompactionResult, format: &str) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(result)?);
+        return Ok(());
+    }
+    match result.digest_event_id {
+        Some(digest_id) => println!(
+            "Compacted {} event(s) of task #{} into digest event #{}",
+            result.archived, result.task_id, digest_id
+        ),
+        None => println!("Task #{}: too few old events to compact", result.task_id),
+    }
+    Ok(())
+}
+
+fn format_bytes(bytes: i64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KB * KB {
+        format!("{:.1} MB", bytes / (KB * KB))
+    } else {
+        format!("{:.1} KB", bytes / KB)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::cli::DepsCommands;
+use crate::db::models::SuggestedLink;
+use crate::dependencies::suggest_dependencies;
+use crate::error::Result;
+use crate::global_projects::{CrossProjectLink, CrossProjectLinks, ProjectTaskRef};
+use crate::project::ProjectContext;
+use serde_json::json;
+
+/// Handle `ie deps` subcommands
+pub async fn handle_deps_command(cmd: DepsCommands) -> Result<()> {
+    match cmd {
+        DepsCommands::Suggest {
+            task,
+            limit,
+            format,
+        } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let suggestions =
+                suggest_dependencies(&ctx.pool, task, crate::project::task_scope(), limit).await?;
+
+            if format == "json" {
+                let response = json!({ "task_id": task, "suggestions": suggestions });
+                println!("{}", serde_json::to_string_pretty(&response)?);
+                return Ok(());
+            }
+            if suggestions.is_empty() {
+                println!("No likely dependencies or duplicates of #{} found", task);
+                return Ok(());
+            }
+            println!("Suggestions for #{}:", task);
+            for suggestion in &suggestions {
+                let other = &suggestion.task;
+                println!(
+                    "  {:.2}  {:<10} #{} {}",
+                    suggestion.confidence,
+                    suggestion.link.as_str(),
+                    other.id,
+                    other.name
+                );
+                println!("        {}", suggestion.reasons.join("; "));
+                match suggestion.link {
+                    SuggestedLink::BlockedBy => println!(
+                        "        → ie task update {} --add-blocked-by {}",
+                        task, other.id
+                    ),
+                    SuggestedLink::Blocks => println!(
+                        "        → ie task update {} --add-blocks {}",
+                        task, other.id
+                    ),
+                    SuggestedLink::Duplicate => {},
+                }
+            }
+        },
+
+        DepsCommands::Link {
+            task,
+            blocked_by,
+            format,
+        } => {
+            let blocking: ProjectTaskRef = blocked_by.parse()?;
+            let ctx = ProjectContext::load_or_init().await?;
+            let link = CrossProjectLinks::new(&ctx.pool)
+                .add(task, &blocking)
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&link)?);
+            } else {
+                println!(
+                    "#{} {} now waits for {}",
+                    link.blocked_task_id,
+                    link.blocked_task_name,
+                    link.blocking_ref()
+                );
+                print_link_state(&link);
+            }
+        },
+
+        DepsCommands::Unlink { task, blocked_by } => {
+            let blocking: ProjectTaskRef = blocked_by.parse()?;
+            let ctx = ProjectContext::load_or_init().await?;
+            CrossProjectLinks::new(&ctx.pool)
+                .remove(task, &blocking)
+                .await?;
+            println!("#{} no longer waits for {}", task, blocked_by);
+        },
+
+        DepsCommands::Links { task, format } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let links = CrossProjectLinks::new(&ctx.pool).list(task).await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&links)?);
+                return Ok(());
+            }
+            if links.is_empty() {
+                println!("No dependencies on other projects");
+                return Ok(());
+            }
+            for link in &links {
+                println!(
+                    "#{} {} → {}",
+                    link.blocked_task_id,
+                    link.blocked_task_name,
+                    link.blocking_ref()
+                );
+                print_link_state(link);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn print_link_state(link: &CrossProjectLink) {
+    match (
+        &link.blocking_task_name,
+        &link.blocking_task_status,
+        &link.error,
+    ) {
+        (Some(name), Some(status), _) => println!(
+            "    {} [{}]{}",
+            name,
+            status,
+            if link.satisfied { "" } else { " — blocking" }
+        ),
+        (_, _, Some(error)) => println!("    unreadable — blocking: {}", error),
+        _ => {},
+    }
+}
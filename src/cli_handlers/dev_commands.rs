@@ -0,0 +1,33 @@
+use crate::cli::DevCommands;
+use crate::error::Result;
+use crate::output;
+use crate::project::ProjectContext;
+use crate::seed::{self, SeedProfile};
+
+/// Handle `ie dev` subcommands
+pub async fn handle_dev_command(cmd: DevCommands) -> Result<()> {
+    match cmd {
+        DevCommands::Seed {
+            profile,
+            seed,
+            format,
+        } => {
+            let profile: SeedProfile = profile.parse()?;
+            let ctx = ProjectContext::load_or_init().await?;
+            let summary = seed::seed(&ctx.pool, profile, seed).await?;
+
+            if output::is_json(&format) {
+                return output::print_success(&summary, Vec::new());
+            }
+            println!(
+                "Seeded '{}' profile (seed {}): {} tasks, {} dependencies, {} events",
+                summary.profile.as_str(),
+                summary.seed,
+                summary.tasks,
+                summary.dependencies,
+                summary.events
+            );
+        },
+    }
+    Ok(())
+}
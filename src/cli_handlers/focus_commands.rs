@@ -0,0 +1,158 @@
+use crate::cli::FocusCommands;
+use crate::error::Result;
+use crate::focus::{FocusSessionStatus, FocusStack, FocusTimer};
+use crate::hooks::HookEvent;
+use crate::project::ProjectContext;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+/// Handle `ie focus` subcommands
+pub async fn handle_focus_command(cmd: FocusCommands) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let stack = FocusStack::new(&ctx.pool);
+
+    match cmd {
+        FocusCommands::Push {
+            id,
+            over_wip,
+            format,
+        } => {
+            let pushed = stack.push(id, over_wip).await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&pushed)?);
+            } else {
+                println!("Started task #{} '{}'", pushed.task.id, pushed.task.name);
+                if let Some(suspended) = &pushed.suspended {
+                    println!(
+                        "  Suspended: #{} '{}' [{}] (stack depth {})",
+                        suspended.id, suspended.name, suspended.status, pushed.depth
+                    );
+                    println!("  Return with: ie focus pop");
+                }
+            }
+            ProjectContext::configured_hooks()?.fire(HookEvent::TaskStarted, &pushed.task)?;
+        },
+
+        FocusCommands::Pop { format } => {
+            let popped = stack.pop().await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&popped)?);
+            } else {
+                for id in &popped.skipped {
+                    println!("Skipped #{} (done or deleted)", id);
+                }
+                println!(
+                    "Back to task #{} '{}' [{}]",
+                    popped.task.id, popped.task.name, popped.task.status
+                );
+                if popped.depth > 0 {
+                    println!("  {} more on the focus stack", popped.depth);
+                }
+            }
+        },
+
+        FocusCommands::List { format } => {
+            let tasks = stack.tasks().await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&tasks)?);
+            } else if tasks.is_empty() {
+                println!("Focus stack is empty");
+            } else {
+                println!("Focus stack (top first):");
+                for task in &tasks {
+                    let icon = super::utils::status_icon(&task.status);
+                    println!("  {} #{} {} [{}]", icon, task.id, task.name, task.status);
+                }
+            }
+        },
+
+        FocusCommands::Start { minutes, format } => {
+            let started = FocusTimer::new(&ctx.pool).start(minutes).await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&started)?);
+            } else {
+                println!(
+                    "Focus session on #{} '{}' for {} min, until {}",
+                    started.task.id,
+                    started.task.name,
+                    minutes,
+                    started
+                        .ends_at
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M")
+                );
+                println!("  End it with: ie focus stop");
+            }
+        },
+
+        FocusCommands::Stop { note, format } => {
+            let summary = FocusTimer::new(&ctx.pool).stop(note.as_deref()).await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!(
+                    "Focus session ended: {} of {} min on #{}",
+                    summary.worked_minutes, summary.planned_minutes, summary.task_id
+                );
+                println!("  Logged as event #{}", summary.event.id);
+            }
+        },
+
+        FocusCommands::Status { format } => {
+            let status = FocusTimer::new(&ctx.pool).status().await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+            } else if let Some(status) = &status {
+                println!("{}", session_line(status));
+            } else {
+                println!("No focus session running");
+            }
+        },
+    }
+    Ok(())
+}
+
+fn session_line(status: &FocusSessionStatus) -> String {
+    let time = if status.minutes_left >= 0 {
+        format!("{} min left", status.minutes_left)
+    } else {
+        format!(
+            "time up {} min ago, end it with 'ie focus stop'",
+            -status.minutes_left
+        )
+    };
+    format!(
+        "⏱️  Focus session on #{} {} ({} of {} min, {})",
+        status.task.id,
+        status.task.name,
+        (Utc::now() - status.session.started_at).num_minutes(),
+        status.session.minutes,
+        time
+    )
+}
+
+/// Print the session's running timebox before `ie status` output, if any
+pub async fn print_focus_session(pool: &SqlitePool) -> Result<()> {
+    if let Some(status) = FocusTimer::new(pool).status().await? {
+        println!("{}", session_line(&status));
+        println!();
+    }
+    Ok(())
+}
+
+/// Print the session's focus stack before `ie status` output, if any
+pub async fn print_focus_stack(pool: &SqlitePool) -> Result<()> {
+    let tasks = FocusStack::new(pool).tasks().await?;
+    if tasks.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "📚 Focus stack ({} interrupted, return with 'ie focus pop')",
+        tasks.len()
+    );
+    for task in &tasks {
+        println!("   #{} {} [{}]", task.id, task.name, task.status);
+    }
+    println!();
+    Ok(())
+}
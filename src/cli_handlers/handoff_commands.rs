@@ -0,0 +1,89 @@
+use crate::cli::HandoffCommands;
+use crate::db::models::Handoff;
+use crate::error::Result;
+use crate::handoff::HandoffManager;
+use crate::project::ProjectContext;
+use crate::workspace::WorkspaceManager;
+use sqlx::SqlitePool;
+
+/// Handle `ie handoff` subcommands
+pub async fn handle_handoff_command(cmd: HandoffCommands) -> Result<()> {
+    match cmd {
+        HandoffCommands::Create {
+            summary,
+            next_steps,
+            task,
+            format,
+        } => {
+            let ctx = ProjectContext::load_or_init().await?;
+            let task_id = match task {
+                Some(id) => Some(id),
+                None => {
+                    WorkspaceManager::new(&ctx.pool)
+                        .get_current_task(None)
+                        .await?
+                        .current_task_id
+                },
+            };
+            let handoff = HandoffManager::new(&ctx.pool)
+                .create(task_id, &summary, next_steps.as_deref())
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&handoff)?);
+            } else {
+                println!("Recorded handoff #{}", handoff.id);
+            }
+        },
+
+        HandoffCommands::List {
+            task,
+            limit,
+            format,
+        } => {
+            let ctx = ProjectContext::load_or_init().await?;
+            let handoffs = HandoffManager::new(&ctx.pool).list(task, limit).await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&handoffs)?);
+            } else if handoffs.is_empty() {
+                println!("No handoffs.");
+            } else {
+                for (i, handoff) in handoffs.iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    print_handoff(handoff);
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Print the latest handoff, if any (text `ie status`)
+pub async fn print_latest_handoff(pool: &SqlitePool) -> Result<()> {
+    if let Some(handoff) = HandoffManager::new(pool).latest().await? {
+        print_handoff(&handoff);
+        println!();
+    }
+    Ok(())
+}
+
+fn print_handoff(handoff: &Handoff) {
+    let about = handoff
+        .task_id
+        .map(|id| format!(", task #{}", id))
+        .unwrap_or_default();
+    println!(
+        "📋 Handoff #{} ({}{})",
+        handoff.id,
+        handoff.created_at.format("%Y-%m-%d %H:%M"),
+        about
+    );
+    println!("   {}", handoff.summary);
+    if let Some(next_steps) = &handoff.next_steps {
+        println!("   Next: {}", next_steps);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::cli::HooksCommands;
+use crate::error::{IntentError, Result};
+use crate::hooks::{HookEvent, HookRun};
+use crate::project::ProjectContext;
+use serde_json::json;
+
+/// Handle `ie hooks` subcommands
+pub fn handle_hooks_command(cmd: HooksCommands) -> Result<()> {
+    let hooks = ProjectContext::configured_hooks()?;
+    let config = hooks.config();
+
+    match cmd {
+        HooksCommands::List { format } => {
+            if format == "json" {
+                let events: serde_json::Map<_, _> = HookEvent::ALL
+                    .into_iter()
+                    .map(|event| (event.as_str().to_string(), json!(config.commands(event))))
+                    .collect();
+                let response = json!({
+                    "hooks": events,
+                    "timeout_secs": config.timeout.as_secs(),
+                    "on_failure": config.on_failure,
+                });
+                println!("{}", serde_json::to_string_pretty(&response)?);
+            } else if config.commands.is_empty() {
+                println!(
+                    "No hooks configured. Add them under [hooks] in .intent-engine/config.toml"
+                );
+            } else {
+                for (event, commands) in &config.commands {
+                    println!("{}:", event.as_str());
+                    for command in commands {
+                        println!("  {}", command);
+                    }
+                }
+                println!(
+                    "Timeout: {}s, on failure: {}",
+                    config.timeout.as_secs(),
+                    config.on_failure.as_str()
+                );
+            }
+        },
+
+        HooksCommands::Test { event, format } => {
+            let event: HookEvent = event.parse()?;
+            let payload = hooks.payload(event, json!({ "test": true }));
+            let runs = hooks.run(event, &payload);
+
+            if format == "json" {
+                let response = json!({ "event": event.as_str(), "payload": payload, "runs": runs });
+                println!("{}", serde_json::to_string_pretty(&response)?);
+            } else if runs.is_empty() {
+                println!("No hooks configured for {}", event.as_str());
+            } else {
+                for run in &runs {
+                    print_run(run);
+                }
+            }
+
+            let failed = runs.iter().filter(|run| !run.succeeded()).count();
+            if failed > 0 {
+                return Err(IntentError::OtherError(anyhow::anyhow!(
+                    "{} of {} hooks for {} failed",
+                    failed,
+                    runs.len(),
+                    event.as_str()
+                )));
+            }
+        },
+    }
+    Ok(())
+}
+
+fn print_run(run: &HookRun) {
+    if run.succeeded() {
+        println!("✓ {} ({} ms)", run.command, run.duration_ms);
+    } else {
+        println!("✗ {}: {}", run.command, run.failure());
+    }
+    for (name, output) in [("stdout", &run.stdout), ("stderr", &run.stderr)] {
+        if !output.trim().is_empty() {
+            println!("  {}:", name);
+            for line in output.trim_end().lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+}
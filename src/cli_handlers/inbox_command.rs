@@ -0,0 +1,34 @@
+use crate::error::Result;
+use crate::inbox::InboxManager;
+use crate::project::ProjectContext;
+
+/// Handle `ie inbox`: tasks waiting on a human
+pub async fn handle_inbox(limit: i64, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load_read_only().await?;
+    let items = InboxManager::new(&ctx.pool).list(limit).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+    if items.is_empty() {
+        println!("Inbox is empty: nothing is waiting on a human.");
+        return Ok(());
+    }
+
+    println!("📥 Inbox ({} tasks)", items.len());
+    for item in &items {
+        let why = match (&item.needs_human, item.status.as_str()) {
+            (Some(reason), _) => format!("needs human: {}", reason),
+            (None, "review") => format!("awaiting review: ie task approve {}", item.task_id),
+            _ => format!("{} task, {}", item.owner, item.status),
+        };
+        println!("  #{} {}", item.task_id, item.name);
+        print!("     {}", why);
+        if item.blocking > 0 {
+            print!(" (blocks {})", item.blocking);
+        }
+        println!();
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::error::Result;
+use crate::insights::{InsightKind, InsightsManager};
+use crate::project::ProjectContext;
+
+/// Handle `ie insights`: project health score, or the tasks behind one insight
+pub async fn handle_insights(kind: Option<String>, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load_read_only().await?;
+    let insights = InsightsManager::new(&ctx.pool);
+
+    if let Some(kind) = kind {
+        let kind: InsightKind = kind.parse()?;
+        let tasks = insights.drill_down(kind).await?;
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&tasks)?);
+        } else if tasks.is_empty() {
+            println!("No tasks for {}", kind.as_str());
+        } else {
+            println!("{} ({} tasks)", kind.as_str(), tasks.len());
+            for task in &tasks {
+                println!("  #{} {} [{}]", task.id, task.name, task.status);
+                println!("     {}", task.detail);
+            }
+        }
+        return Ok(());
+    }
+
+    let report = insights.report().await?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "Project health: {}/100 ({})",
+        report.score,
+        report.verdict.as_str()
+    );
+    for insight in &report.insights {
+        println!(
+            "  {:>3}  {:<14} {}",
+            insight.score,
+            insight.kind.as_str(),
+            insight.summary
+        );
+    }
+    if let Some(worst) = report
+        .insights
+        .iter()
+        .filter(|i| i.score < 100)
+        .min_by_key(|i| i.score)
+    {
+        println!("\nStart with: {}", worst.drill_down);
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::cli::{Cli, ErrorsCommands};
+use crate::error::{Result, ERROR_CATALOG};
+use clap::{Arg, Command, CommandFactory};
+use serde_json::{json, Value};
+
+/// Version of the `ie introspect --json` layout; bumped on breaking changes
+const INTROSPECT_FORMAT: u32 = 1;
+
+/// Write a completion script for `bin_name` to stdout
+pub fn handle_completions(shell: clap_complete::Shell, bin_name: &str) {
+    let mut cmd = Cli::command();
+    clap_complete::generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
+}
+
+/// Handle `ie introspect`
+pub fn handle_introspect(json: bool, bin_name: &str) -> Result<()> {
+    let mut cmd = Cli::command().bin_name(bin_name);
+    cmd.build();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&describe_cli(&cmd))?);
+    } else {
+        print_command_list(&cmd, bin_name);
+    }
+    Ok(())
+}
+
+/// Handle `ie errors`
+pub fn handle_errors_command(cmd: ErrorsCommands) -> Result<()> {
+    match cmd {
+        ErrorsCommands::List { format } => {
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(ERROR_CATALOG)?);
+            } else {
+                for info in ERROR_CATALOG {
+                    println!("{}  {:<22} {}", info.code, info.name, info.description);
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Machine-readable description of the whole CLI
+pub fn describe_cli(cmd: &Command) -> Value {
+    json!({
+        "format": INTROSPECT_FORMAT,
+        "name": cmd.get_bin_name().unwrap_or(cmd.get_name()),
+        "version": env!("CARGO_PKG_VERSION"),
+        "global_args": cmd.get_arguments().filter(|a| is_documented(a)).map(describe_arg).collect::<Vec<_>>(),
+        "commands": cmd.get_subcommands().filter(|c| !c.is_hide_set()).map(|c| describe_command(c, &[])).collect::<Vec<_>>(),
+        "schemas": {
+            "plan": crate::plan::schema(),
+        },
+    })
+}
+
+fn describe_command(cmd: &Command, parents: &[&str]) -> Value {
+    let mut path = parents.to_vec();
+    path.push(cmd.get_name());
+
+    json!({
+        "name": cmd.get_name(),
+        "path": path.join(" "),
+        "about": cmd.get_about().map(|s| s.to_string()),
+        "aliases": cmd.get_visible_aliases().collect::<Vec<_>>(),
+        "args": cmd.get_arguments().filter(|a| is_documented(a)).map(describe_arg).collect::<Vec<_>>(),
+        "subcommands": cmd
+            .get_subcommands()
+            .filter(|c| !c.is_hide_set())
+            .map(|c| describe_command(c, &path))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn describe_arg(arg: &Arg) -> Value {
+    let takes_value = arg.get_num_args().is_some_and(|n| n.takes_values());
+    let possible_values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_string())
+        .collect();
+
+    json!({
+        "name": arg.get_id().as_str(),
+        "long": arg.get_long(),
+        "short": arg.get_short().map(String::from),
+        "positional": arg.is_positional(),
+        "help": arg.get_help().map(|s| s.to_string()),
+        "required": arg.is_required_set(),
+        "takes_value": takes_value,
+        "multiple": takes_value && matches!(arg.get_action(), clap::ArgAction::Append),
+        "default": arg
+            .get_default_values()
+            .iter()
+            .map(|v| v.to_string_lossy().into_owned())
+            .next(),
+        "possible_values": possible_values,
+    })
+}
+
+/// Clap's own --help/--version flags are not part of the interface
+fn is_documented(arg: &Arg) -> bool {
+    !arg.is_hide_set() && !matches!(arg.get_id().as_str(), "help" | "version")
+}
+
+fn print_command_list(cmd: &Command, prefix: &str) {
+    for sub in cmd.get_subcommands().filter(|c| !c.is_hide_set()) {
+        let path = format!("{} {}", prefix, sub.get_name());
+        match sub.get_about() {
+            Some(about) => println!("{:<32} {}", path, about),
+            None => println!("{}", path),
+        }
+        print_command_list(sub, &path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(commands: &'a Value, name: &str) -> &'a Value {
+        commands
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap_or_else(|| panic!("command {name} missing"))
+    }
+
+    #[test]
+    fn test_describe_cli_lists_nested_commands_and_args() {
+        let mut cmd = Cli::command().bin_name("ie");
+        cmd.build();
+        let description = describe_cli(&cmd);
+
+        assert_eq!(description["name"], "ie");
+        assert!(description["schemas"]["plan"]["$defs"]["TaskTree"].is_object());
+
+        let task = find(&description["commands"], "task");
+        let update = find(&task["subcommands"], "update");
+        assert_eq!(update["path"], "task update");
+
+        let args = update["args"].as_array().unwrap();
+        let id = args.iter().find(|a| a["name"] == "id").unwrap();
+        assert_eq!(
+            (id["positional"].as_bool(), id["required"].as_bool()),
+            (Some(true), Some(true))
+        );
+        let metadata = args.iter().find(|a| a["name"] == "metadata").unwrap();
+        assert_eq!(metadata["multiple"], true);
+        let format = args.iter().find(|a| a["name"] == "format").unwrap();
+        assert_eq!(format["default"], "text");
+        assert!(args.iter().all(|a| a["name"] != "help"));
+
+        let get = find(&task["subcommands"], "get");
+        assert_eq!(get["aliases"], json!(["show"]));
+    }
+
+    #[test]
+    fn test_completions_generate_for_every_shell() {
+        use clap::ValueEnum;
+
+        for shell in clap_complete::Shell::value_variants() {
+            let mut out = Vec::new();
+            clap_complete::generate(*shell, &mut Cli::command(), "ie", &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(
+                script.contains("introspect"),
+                "{shell} script lacks subcommands"
+            );
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::cli::LinksCommands;
+use crate::error::Result;
+use crate::links::{LinkChecker, LinkInput, LinkManager};
+use crate::project::ProjectContext;
+use std::time::Duration;
+
+/// Handle `ie links` subcommands
+pub async fn handle_links_command(cmd: LinksCommands) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let links = LinkManager::new(&ctx.pool);
+
+    match cmd {
+        LinksCommands::Add {
+            task_id,
+            url,
+            title,
+            kind,
+            format,
+        } => {
+            let link = links
+                .add(task_id, &LinkInput::Full { url, title, kind })
+                .await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&link)?);
+            } else {
+                println!(
+                    "Linked task #{}: {}",
+                    task_id,
+                    super::utils::link_line(&link)
+                );
+            }
+        },
+
+        LinksCommands::List { task, format } => {
+            let listed = match task {
+                Some(id) => links.list(id).await?,
+                None => links.list_all().await?,
+            };
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&listed)?);
+            } else if listed.is_empty() {
+                println!("No links");
+            } else {
+                for link in &listed {
+                    println!("  #{} {}", link.task_id, super::utils::link_line(link));
+                }
+            }
+        },
+
+        LinksCommands::Remove { task_id, url } => {
+            links.remove(task_id, &url).await?;
+            println!("Removed link from task #{}: {}", task_id, url.trim());
+        },
+
+        LinksCommands::Check {
+            task,
+            timeout,
+            format,
+        } => {
+            let checker = LinkChecker::new(Duration::from_secs(timeout), Some(ctx.root.clone()))?;
+            let report = checker.check(&ctx.pool, task).await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for link in &report.dead {
+                    println!("  #{} {}", link.task_id, super::utils::link_line(link));
+                }
+                println!(
+                    "Checked {} link(s): {} dead, {} skipped",
+                    report.checked,
+                    report.dead.len(),
+                    report.skipped
+                );
+            }
+        },
+    }
+
+    Ok(())
+}
@@ -1,42 +1,127 @@
 use crate::backend::{EventBackend, WorkspaceBackend};
-use crate::cli::LogEventType;
+use crate::db::models::{EventVisibility, NewEvent};
 use crate::error::{IntentError, Result};
+use crate::output;
+use crate::plan::{expand_file_directives, FileIncludeResult};
+use serde::Deserialize;
+
+use super::read_stdin;
+
+/// What `ie log` records
+pub enum LogInput {
+    /// One event from the command line
+    Single {
+        event_type: String,
+        message: String,
+        /// Backdate the event (RFC 3339 or a duration ago)
+        timestamp: Option<String>,
+    },
+    /// JSON array of `LogEntry` read from stdin
+    Batch(String),
+}
+
+impl LogInput {
+    /// Build from `ie log` arguments; `--stdin-json` reads the events from stdin
+    pub fn from_args(
+        event_type: Option<String>,
+        message: Option<String>,
+        timestamp: Option<String>,
+        stdin_json: bool,
+    ) -> Result<Self> {
+        if stdin_json {
+            return Ok(Self::Batch(read_stdin()?));
+        }
+        match (event_type, message) {
+            (Some(event_type), Some(message)) => Ok(Self::Single {
+                event_type,
+                message,
+                timestamp,
+            }),
+            _ => Err(IntentError::InvalidInput(
+                "ie log needs an event type and a message, or --stdin-json".to_string(),
+            )),
+        }
+    }
+}
+
+/// One element of the `ie log --stdin-json` array
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LogEntry {
+    #[serde(rename = "type")]
+    event_type: String,
+    message: String,
+    task: Option<i64>,
+    timestamp: Option<String>,
+    visibility: Option<String>,
+}
 
 /// Handle `ie log` command.
 pub async fn handle_log(
     event_mgr: &impl EventBackend,
     ws_mgr: &impl WorkspaceBackend,
-    event_type: LogEventType,
-    message: &str,
+    input: LogInput,
     task: Option<i64>,
+    visibility: EventVisibility,
     format: &str,
 ) -> Result<()> {
+    let (event_type, message, timestamp) = match input {
+        LogInput::Single {
+            event_type,
+            message,
+            timestamp,
+        } => (event_type, message, timestamp),
+        LogInput::Batch(json) => {
+            return handle_batch(event_mgr, ws_mgr, &json, task, visibility, format).await;
+        },
+    };
+
+    let event_type = event_type.to_ascii_lowercase();
+    event_mgr.event_types()?.check(&event_type)?;
+
     // Determine task_id: use --task flag, or fall back to current focused task
-    let target_task_id = if let Some(tid) = task {
-        tid
-    } else {
-        let current_response = ws_mgr.get_current_task(None).await?;
-        let current_task = current_response.task.ok_or_else(|| {
-            IntentError::ActionNotAllowed(
-                "No current task set. Use --task <ID> or start a task first.".to_string(),
-            )
-        })?;
-        current_task.id
+    let target_task_id = match task {
+        Some(tid) => tid,
+        None => focused_task_id(ws_mgr).await?,
     };
 
+    // Lines like @file(path) pull in prepared analysis files, as in plan
+    // specs, but `ie log` never deletes them
+    let mut includes = FileIncludeResult::default();
+    let message =
+        expand_file_directives(&message, &mut includes).map_err(IntentError::InvalidInput)?;
+    let message = message.as_str();
+
     let event_type_str = event_type.as_str();
 
-    let event = event_mgr
-        .add_event(target_task_id, event_type_str, message)
-        .await?;
+    let event = match timestamp {
+        Some(at) => {
+            let new = NewEvent {
+                task_id: target_task_id,
+                log_type: event_type_str.to_string(),
+                discussion_data: message.to_string(),
+                visibility,
+                timestamp: Some(crate::time_utils::parse_timestamp(&at)?),
+            };
+            event_mgr.add_events(&[new]).await?.remove(0)
+        },
+        None => {
+            event_mgr
+                .add_event_with_visibility(target_task_id, event_type_str, message, visibility)
+                .await?
+        },
+    };
 
-    if format == "json" {
-        println!("{}", serde_json::to_string_pretty(&event)?);
+    if output::is_json(format) {
+        output::print_success(&event, Vec::new())?;
     } else {
         println!("  Event recorded");
         println!("  ID: {}", event.id);
         println!("  Type: {}", event_type_str);
         println!("  Task: #{}", target_task_id);
+        if !visibility.is_normal() {
+            println!("  Visibility: {}", visibility.as_str());
+        }
         println!(
             "  Time: {}",
             event.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
@@ -46,3 +131,90 @@ pub async fn handle_log(
 
     Ok(())
 }
+
+/// `ie log --stdin-json`: validate every entry, then record them all at once
+async fn handle_batch(
+    event_mgr: &impl EventBackend,
+    ws_mgr: &impl WorkspaceBackend,
+    json: &str,
+    task: Option<i64>,
+    visibility: EventVisibility,
+    format: &str,
+) -> Result<()> {
+    let entries: Vec<LogEntry> = serde_json::from_str(json)
+        .map_err(|e| IntentError::InvalidInput(format!("Invalid log input: {}", e)))?;
+
+    let types = event_mgr.event_types()?;
+    // The focus is only looked up when some entry needs it
+    let mut default_task = task;
+    let mut includes = FileIncludeResult::default();
+    let mut events = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.into_iter().enumerate() {
+        let at = |e: IntentError| match e {
+            IntentError::InvalidInput(msg) => {
+                IntentError::InvalidInput(format!("[{}]: {}", i, msg))
+            },
+            other => other,
+        };
+        let invalid = |msg: String| at(IntentError::InvalidInput(msg));
+
+        let event_type = entry.event_type.to_ascii_lowercase();
+        types.check(&event_type).map_err(at)?;
+        let task_id = match entry.task.or(default_task) {
+            Some(tid) => tid,
+            None => {
+                let tid = focused_task_id(ws_mgr).await?;
+                default_task = Some(tid);
+                tid
+            },
+        };
+        let timestamp = entry
+            .timestamp
+            .as_deref()
+            .map(crate::time_utils::parse_timestamp)
+            .transpose()
+            .map_err(at)?;
+        let visibility = match entry.visibility {
+            Some(v) => v.parse().map_err(invalid)?,
+            None => visibility,
+        };
+        let message = expand_file_directives(&entry.message, &mut includes).map_err(&invalid)?;
+
+        events.push(NewEvent {
+            task_id,
+            log_type: event_type,
+            discussion_data: message,
+            visibility,
+            timestamp,
+        });
+    }
+
+    let recorded = event_mgr.add_events(&events).await?;
+
+    if output::is_json(format) {
+        output::print_success(&recorded, Vec::new())?;
+    } else {
+        println!("  {} event(s) recorded", recorded.len());
+        for event in &recorded {
+            println!(
+                "  #{} {} on task #{} at {}",
+                event.id,
+                event.log_type,
+                event.task_id,
+                event.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn focused_task_id(ws_mgr: &impl WorkspaceBackend) -> Result<i64> {
+    let current_response = ws_mgr.get_current_task(None).await?;
+    let current_task = current_response.task.ok_or_else(|| {
+        IntentError::ActionNotAllowed(
+            "No current task set. Use --task <ID> or start a task first.".to_string(),
+        )
+    })?;
+    Ok(current_task.id)
+}
@@ -4,32 +4,78 @@
 // Core: plan, log, search, status, task
 // System: init, dashboard, doctor
 
+pub mod activity_command;
+pub mod archive_commands;
+#[cfg(any(feature = "neo4j", feature = "postgres"))]
+pub mod backend_commands;
 pub mod config_commands;
 pub mod dashboard;
+pub mod db_command;
+pub mod deps_command;
+#[cfg(feature = "dev")]
+pub mod dev_commands;
+pub mod focus_commands;
+pub mod handoff_commands;
+pub mod hooks_commands;
+pub mod inbox_command;
+pub mod insights_command;
+pub mod introspect;
+pub mod links_commands;
 pub mod log_command;
 pub mod other;
 pub mod plan_command;
+pub mod prompt_segment;
+pub mod report_command;
 pub mod status_command;
 pub mod suggestions_commands;
+pub mod sync_commands;
 pub mod task_commands;
 pub mod utils;
+pub mod watch_command;
+pub mod workspace_commands;
 
 // Re-export commonly used functions
+pub use activity_command::handle_activity;
+pub use archive_commands::{handle_export_command, handle_import_command};
+#[cfg(feature = "neo4j")]
+pub use backend_commands::run_neo4j;
+#[cfg(feature = "postgres")]
+pub use backend_commands::run_postgres;
 pub use config_commands::handle_config_command;
 pub use dashboard::{check_dashboard_status, check_mcp_connections, handle_dashboard_command};
-pub use log_command::handle_log;
+pub use db_command::handle_db;
+pub use deps_command::handle_deps_command;
+#[cfg(feature = "dev")]
+pub use dev_commands::handle_dev_command;
+pub use focus_commands::{handle_focus_command, print_focus_session, print_focus_stack};
+pub use handoff_commands::{handle_handoff_command, print_latest_handoff};
+pub use hooks_commands::handle_hooks_command;
+pub use inbox_command::handle_inbox;
+pub use insights_command::handle_insights;
+pub use introspect::{handle_completions, handle_errors_command, handle_introspect};
+pub use links_commands::handle_links_command;
+pub use log_command::{handle_log, LogInput};
 pub use other::{
     handle_doctor_command,
     handle_init_command,
+    handle_search,
     handle_search_command,
     // Deprecated handlers (kept for potential MCP or Dashboard use):
     // handle_current_command, handle_event_command, handle_report_command,
     // handle_session_restore, handle_setup, handle_logs_command, check_session_start_hook
 };
-pub use plan_command::{execute_and_print as execute_plan_and_print, print_plan_result};
-pub use status_command::handle_status;
+pub use plan_command::{
+    execute_and_print as execute_plan_and_print, execute_under_focus, print_plan_result,
+    print_plan_schema, print_plan_validation,
+};
+pub use prompt_segment::handle_prompt_segment;
+pub use report_command::handle_report;
+pub use status_command::{handle_status, print_restore_bundle};
+pub use sync_commands::handle_sync_command;
 pub use task_commands::handle_task_command;
 pub use utils::{
     get_status_badge, merge_metadata, parse_metadata, print_events_summary, print_task_context,
     print_task_summary, print_task_tree, read_stdin, status_icon,
 };
+pub use watch_command::handle_watch;
+pub use workspace_commands::{handle_workspace_command, save_plan_workspace};
@@ -1,14 +1,18 @@
 // Note: CurrentAction and EventCommands removed in v0.10.1 CLI simplification
 // These functions are kept for potential Dashboard/MCP use but not exposed in CLI
 // use crate::cli::{CurrentAction, EventCommands};
+use crate::backend::{Backend, EventBackend, SearchBackend, TaskBackend};
 use crate::cli_handlers::read_stdin;
+use crate::db::models::PaginatedSearchResults;
+use crate::doctor;
 use crate::error::{IntentError, Result};
 use crate::events::EventManager;
+use crate::output;
 use crate::project::ProjectContext;
 use crate::report::ReportManager;
-use crate::tasks::TaskManager;
 use crate::time_utils::parse_date_filter;
 use crate::workspace::WorkspaceManager;
+use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 
 // Stub types for deprecated CLI commands (no longer in cli.rs)
@@ -194,6 +198,19 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
+type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Parse the `--since`/`--until` filters of a search
+fn parse_date_range(since: &Option<String>, until: &Option<String>) -> Result<DateRange> {
+    let parse = |date: &Option<String>| {
+        date.as_deref()
+            .map(parse_date_filter)
+            .transpose()
+            .map_err(IntentError::InvalidInput)
+    };
+    Ok((parse(since)?, parse(until)?))
+}
+
 /// Check if query is a status keyword combination (todo, doing, done)
 /// Returns Some(statuses) if it's a status query, None otherwise
 fn parse_status_keywords(query: &str) -> Option<Vec<String>> {
@@ -228,33 +245,81 @@ pub async fn handle_search_command(
     query: &str,
     include_tasks: bool,
     include_events: bool,
+    event_types: Vec<String>,
     limit: Option<i64>,
     offset: Option<i64>,
     since: Option<String>,
     until: Option<String>,
     format: &str,
+    semantic: bool,
 ) -> Result<()> {
-    use crate::search::SearchManager;
-    use chrono::{DateTime, Utc};
-
-    let ctx = ProjectContext::load_or_init().await?;
-
-    // Parse date filters
-    let since_dt: Option<DateTime<Utc>> = if let Some(ref s) = since {
-        Some(parse_date_filter(s).map_err(IntentError::InvalidInput)?)
+    // Search is read-only, except that semantic search keeps its index up to
+    // date; a fresh directory is still initialized as before
+    let ctx = if semantic {
+        ProjectContext::load_or_init().await?
     } else {
-        None
+        match ProjectContext::load_read_only().await {
+            Err(IntentError::NotAProject) => ProjectContext::load_or_init().await?,
+            result => result?,
+        }
     };
 
-    let until_dt: Option<DateTime<Utc>> = if let Some(ref u) = until {
-        Some(parse_date_filter(u).map_err(IntentError::InvalidInput)?)
-    } else {
-        None
-    };
+    if !semantic {
+        return handle_search(
+            &ctx,
+            query,
+            include_tasks,
+            include_events,
+            event_types,
+            limit,
+            offset,
+            since,
+            until,
+            format,
+        )
+        .await;
+    }
+
+    // Dates only filter status keyword queries, but bad ones are still errors
+    parse_date_range(&since, &until)?;
+    if since.is_some() || until.is_some() {
+        eprintln!("Warning: --since/--until are ignored for fulltext search (only apply to status keyword queries)");
+    }
+    let results = semantic_search(
+        &ctx.pool,
+        query,
+        include_tasks,
+        include_events,
+        limit,
+        offset,
+    )
+    .await?;
+    print_search_results(query, &results, format)
+}
+
+/// Search any storage backend: `#ID` lookup, then status keywords, then full text
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_search(
+    backend: &impl Backend,
+    query: &str,
+    include_tasks: bool,
+    include_events: bool,
+    event_types: Vec<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    since: Option<String>,
+    until: Option<String>,
+    format: &str,
+) -> Result<()> {
+    let (since_dt, until_dt) = parse_date_range(&since, &until)?;
+    let known_types = backend.events().event_types()?;
+    for event_type in &event_types {
+        known_types.check(event_type)?;
+    }
 
     // Check if query is a #ID format (e.g., "#123", "#1")
     if let Some(task_id) = parse_task_id_query(query) {
-        let task_mgr = TaskManager::new(&ctx.pool);
+        let task_mgr = backend.tasks();
         match task_mgr.get_task(task_id).await {
             Ok(task) => {
                 if format == "json" {
@@ -312,8 +377,7 @@ pub async fn handle_search_command(
 
     // Check if query is a status keyword combination
     if let Some(statuses) = parse_status_keywords(query) {
-        // Use TaskManager::find_tasks for status filtering
-        let task_mgr = TaskManager::new(&ctx.pool);
+        let task_mgr = backend.tasks();
 
         // Collect tasks for each status
         // When date filters are used, fetch more tasks initially
@@ -446,12 +510,16 @@ pub async fn handle_search_command(
     if since_dt.is_some() || until_dt.is_some() {
         eprintln!("Warning: --since/--until are ignored for fulltext search (only apply to status keyword queries)");
     }
-    let search_mgr = SearchManager::new(&ctx.pool);
-
-    let results = search_mgr
-        .search(query, include_tasks, include_events, limit, offset, false)
+    let results = backend
+        .search()
+        .filter_event_types(event_types)?
+        .search(query, include_tasks, include_events, limit, offset)
         .await?;
+    print_search_results(query, &results, format)
+}
 
+/// Print full-text or semantic search results
+fn print_search_results(query: &str, results: &PaginatedSearchResults, format: &str) -> Result<()> {
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&results)?);
     } else {
@@ -470,6 +538,7 @@ pub async fn handle_search_command(
                     task,
                     match_field,
                     match_snippet,
+                    ..
                 } => {
                     let status_icon = match task.status.as_str() {
                         "todo" => "○",
@@ -518,6 +587,7 @@ pub async fn handle_search_command(
                     event,
                     task_chain,
                     match_snippet,
+                    ..
                 } => {
                     let icon = match event.log_type.as_str() {
                         "decision" => "💡",
@@ -559,12 +629,96 @@ pub async fn handle_search_command(
     Ok(())
 }
 
-pub async fn handle_doctor_command() -> Result<()> {
+#[cfg(feature = "semantic")]
+async fn semantic_search(
+    pool: &sqlx::SqlitePool,
+    query: &str,
+    include_tasks: bool,
+    include_events: bool,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<crate::db::models::PaginatedSearchResults> {
+    use crate::search::semantic::{Embedder, SemanticSearch};
+
+    let search = SemanticSearch::new(pool, Embedder::from_pool(pool).await?);
+    // Under --read-only the index cannot be updated; search what is there
+    if !crate::project::is_read_only() {
+        search.sync().await?;
+    }
+    search
+        .search(query, include_tasks, include_events, limit, offset)
+        .await
+}
+
+#[cfg(not(feature = "semantic"))]
+async fn semantic_search(
+    _pool: &sqlx::SqlitePool,
+    _query: &str,
+    _include_tasks: bool,
+    _include_events: bool,
+    _limit: Option<i64>,
+    _offset: Option<i64>,
+) -> Result<crate::db::models::PaginatedSearchResults> {
+    Err(IntentError::InvalidInput(
+        "This build has no semantic search; rebuild with --features semantic".to_string(),
+    ))
+}
+
+pub async fn handle_doctor_command(fix: bool, format: &str) -> Result<()> {
     use crate::cli_handlers::dashboard::{check_dashboard_health, DASHBOARD_PORT};
 
     // Get database path info
     let db_path_info = ProjectContext::get_database_path_info();
 
+    let dirs_with_db: Vec<&String> = db_path_info
+        .directories_checked
+        .iter()
+        .filter(|d| d.has_intent_engine)
+        .map(|d| &d.path)
+        .collect();
+
+    let dashboard_health = check_dashboard_health(DASHBOARD_PORT).await;
+
+    // Opened without migrations, which would hide the damage (see doctor module)
+    let mut integrity = None;
+    if let Some(db_path) = &db_path_info.final_database_path {
+        let pool = crate::db::create_pool(std::path::Path::new(db_path)).await?;
+        let mut checks = doctor::run_checks(&pool).await?;
+        let mut repairs = Vec::new();
+        if fix && checks.iter().any(|c| !c.is_ok()) {
+            repairs = doctor::repair(&pool, &checks).await?;
+            checks = doctor::run_checks(&pool).await?;
+        }
+        integrity = Some((checks, repairs));
+    }
+
+    if output::is_json(format) {
+        let mut warnings = Vec::new();
+        if db_path_info.final_database_path.is_none() {
+            warnings.push("No database found; run 'ie init' to create one".to_string());
+        }
+        if let Some((checks, _)) = &integrity {
+            for check in checks {
+                for problem in &check.problems {
+                    warnings.push(format!("{}: {}", check.name, problem));
+                }
+            }
+        }
+        let data = serde_json::json!({
+            "database_path": db_path_info.final_database_path,
+            "ancestor_databases": dirs_with_db,
+            "dashboard": {
+                "running": dashboard_health,
+                "url": format!("http://127.0.0.1:{}", DASHBOARD_PORT),
+            },
+            "integrity": integrity.as_ref().map(|(checks, repairs)| serde_json::json!({
+                "checks": checks,
+                "repairs": repairs,
+            })),
+        });
+        return output::print_success(data, warnings);
+    }
+
     // Print database location
     println!("Database:");
     if let Some(db_path) = &db_path_info.final_database_path {
@@ -575,13 +729,6 @@ pub async fn handle_doctor_command() -> Result<()> {
     println!();
 
     // Print ancestor directories with databases
-    let dirs_with_db: Vec<&String> = db_path_info
-        .directories_checked
-        .iter()
-        .filter(|d| d.has_intent_engine)
-        .map(|d| &d.path)
-        .collect();
-
     if !dirs_with_db.is_empty() {
         println!("Ancestor directories with databases:");
         for dir in dirs_with_db {
@@ -594,17 +741,46 @@ pub async fn handle_doctor_command() -> Result<()> {
 
     // Check dashboard status
     print!("Dashboard: ");
-    let dashboard_health = check_dashboard_health(DASHBOARD_PORT).await;
     if dashboard_health {
         println!("Running (http://127.0.0.1:{})", DASHBOARD_PORT);
     } else {
         println!("Not running (start with 'ie dashboard start')");
     }
 
+    if let Some((checks, repairs)) = integrity {
+        println!();
+        if !repairs.is_empty() {
+            println!("Repaired:");
+            for repair in &repairs {
+                println!("  {}", repair);
+            }
+            println!();
+        }
+        println!("Integrity:");
+        for check in &checks {
+            if check.is_ok() {
+                println!("  ✓ {}", check.name);
+            }
+            for problem in &check.problems {
+                println!("  ✗ {}: {}", check.name, problem);
+            }
+        }
+        if !fix && checks.iter().any(|c| !c.is_ok()) {
+            println!();
+            println!("Run 'ie doctor --fix' to repair");
+        }
+    }
+
     Ok(())
 }
 
-pub async fn handle_init_command(at: Option<String>, force: bool) -> Result<()> {
+pub async fn handle_init_command(
+    at: Option<String>,
+    force: bool,
+    analyze: bool,
+    apply: bool,
+    format: &str,
+) -> Result<()> {
     use serde_json::json;
 
     // Determine target directory
@@ -631,7 +807,8 @@ pub async fn handle_init_command(at: Option<String>, force: bool) -> Result<()>
     let intent_dir = target_dir.join(".intent-engine");
 
     // Check if already exists
-    if intent_dir.exists() && !force {
+    let existed = intent_dir.exists();
+    if existed && !force {
         let error_msg = format!(
             ".intent-engine already exists at {}\nUse --force to re-initialize",
             intent_dir.display()
@@ -642,7 +819,26 @@ pub async fn handle_init_command(at: Option<String>, force: bool) -> Result<()>
     // Perform initialization
     let ctx = ProjectContext::initialize_project_at(target_dir).await?;
 
-    // Success output
+    if analyze {
+        return handle_init_analyze(&ctx, &intent_dir, existed, apply, format).await;
+    }
+
+    if output::is_json(format) {
+        let mut warnings = Vec::new();
+        if existed {
+            warnings.push(format!(
+                "Re-initialized existing .intent-engine at {}",
+                intent_dir.display()
+            ));
+        }
+        let data = json!({
+            "root": ctx.root.display().to_string(),
+            "database_path": ctx.db_path.display().to_string(),
+        });
+        return output::print_success(data, warnings);
+    }
+
+    // Text output has always been this JSON summary; scripts depend on it
     let result = json!({
         "success": true,
         "root": ctx.root.display().to_string(),
@@ -654,6 +850,108 @@ pub async fn handle_init_command(at: Option<String>, force: bool) -> Result<()>
     Ok(())
 }
 
+/// `ie init --analyze`: propose a plan from the repository, save it next to
+/// the database and, with `--apply`, execute it
+async fn handle_init_analyze(
+    ctx: &ProjectContext,
+    intent_dir: &std::path::Path,
+    existed: bool,
+    apply: bool,
+    format: &str,
+) -> Result<()> {
+    use crate::bootstrap;
+    use crate::plan::PlanExecutor;
+    use serde_json::json;
+
+    let analysis = bootstrap::analyze(&ctx.root)?;
+    let plan_path = intent_dir.join("bootstrap-plan.json");
+    std::fs::write(&plan_path, serde_json::to_string_pretty(&analysis.plan)?)?;
+    let task_count = crate::plan::flatten_task_tree(&analysis.plan.tasks).len();
+
+    let result = if apply {
+        let result = PlanExecutor::new(&ctx.pool).execute(&analysis.plan).await?;
+        if !result.success {
+            return Err(IntentError::InvalidInput(format!(
+                "Failed to apply the bootstrap plan: {}",
+                result.error.unwrap_or_default()
+            )));
+        }
+        Some(result)
+    } else {
+        None
+    };
+
+    if output::is_json(format) {
+        let mut warnings = Vec::new();
+        if existed {
+            warnings.push(format!(
+                "Re-initialized existing .intent-engine at {}",
+                intent_dir.display()
+            ));
+        }
+        let data = json!({
+            "root": ctx.root.display().to_string(),
+            "database_path": ctx.db_path.display().to_string(),
+            "analysis": analysis,
+            "plan_path": plan_path.display().to_string(),
+            "applied": result,
+        });
+        return output::print_success(data, warnings);
+    }
+
+    println!("Intent-Engine initialized at {}", ctx.root.display());
+    println!(
+        "Scanned {} files{}: {} open checklist items, {} TODO/FIXME comments",
+        analysis.files_scanned,
+        analysis
+            .readme
+            .as_ref()
+            .map(|r| format!(" (project described from {})", r))
+            .unwrap_or_default(),
+        analysis.checklist_items,
+        analysis.todo_comments
+    );
+    if analysis.omitted > 0 {
+        println!(
+            "{} findings left out of the plan (too many in one group)",
+            analysis.omitted
+        );
+    }
+    println!();
+    print_plan_outline(&analysis.plan.tasks, 0);
+    println!();
+    match result {
+        Some(result) => println!(
+            "Created {} tasks from the proposed plan ({})",
+            result.created_count,
+            plan_path.display()
+        ),
+        None => {
+            println!(
+                "Proposed plan with {} tasks saved to {}",
+                task_count,
+                plan_path.display()
+            );
+            println!("Edit it if needed, then create the tasks with:");
+            println!("  ie plan < {}", plan_path.display());
+        },
+    }
+    Ok(())
+}
+
+fn print_plan_outline(tasks: &[crate::plan::TaskTree], depth: usize) {
+    for task in tasks {
+        println!(
+            "{}- {}",
+            "  ".repeat(depth),
+            task.name.as_deref().unwrap_or_default()
+        );
+        if let Some(children) = &task.children {
+            print_plan_outline(children, depth + 1);
+        }
+    }
+}
+
 pub async fn handle_session_restore(
     include_events: usize,
     workspace: Option<String>,
@@ -679,7 +977,10 @@ pub async fn handle_session_restore(
                 parent_task: None,
                 siblings: None,
                 children: None,
+                subtree: None,
                 recent_events: None,
+                last_handoff: None,
+                focus_stack: None,
                 suggested_commands: Some(vec![
                     "ie workspace init".to_string(),
                     "ie help".to_string(),
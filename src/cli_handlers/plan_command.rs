@@ -1,6 +1,7 @@
-use crate::backend::PlanBackend;
+use crate::backend::{Backend, PlanBackend, WorkspaceBackend};
 use crate::error::Result;
-use crate::plan::PlanResult;
+use crate::plan::{PlanRequest, PlanResult, TaskOutcomeStatus};
+use crate::plan_validation::validate_request;
 
 /// Format and display the result of a plan execution.
 ///
@@ -33,6 +34,23 @@ pub fn print_plan_result(result: &PlanResult, format: &str) -> Result<()> {
             println!("  {} -> #{}", name, id);
         }
 
+        let failed: Vec<_> = result
+            .task_results
+            .iter()
+            .filter(|outcome| outcome.status == TaskOutcomeStatus::Failed)
+            .collect();
+        if !failed.is_empty() {
+            println!();
+            println!("Failed (not applied):");
+            for outcome in failed {
+                println!(
+                    "  - {}: {}",
+                    outcome.name.as_deref().unwrap_or("<unnamed>"),
+                    outcome.error.as_deref().unwrap_or_default()
+                );
+            }
+        }
+
         if !result.warnings.is_empty() {
             println!();
             println!("Warnings:");
@@ -107,3 +125,38 @@ pub async fn execute_and_print(
     print_plan_result(&result, format)?;
     Ok(result)
 }
+
+/// Execute a plan on any storage backend, auto-parenting root-level tasks
+/// to the focused task.
+pub async fn execute_under_focus(
+    backend: &impl Backend,
+    request: &PlanRequest,
+) -> Result<PlanResult> {
+    let current = backend.workspace().get_current_task(None).await?;
+    backend
+        .plans(current.current_task_id)
+        .execute(request)
+        .await
+}
+
+/// Print the JSON Schema of plan input (`ie plan --schema`).
+pub fn print_plan_schema() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&crate::plan::schema())?);
+    Ok(())
+}
+
+/// Check a plan without executing it (`ie plan --validate`).
+///
+/// Runs the checks that need no database; problems are returned as errors.
+pub fn print_plan_validation(request: &PlanRequest, format: &str) -> Result<()> {
+    let task_count = validate_request(request)?;
+
+    if format == "json" {
+        let output = serde_json::json!({ "valid": true, "tasks": task_count });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("Plan is valid ({} tasks)", task_count);
+    }
+
+    Ok(())
+}
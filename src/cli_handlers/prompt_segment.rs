@@ -0,0 +1,254 @@
+//! `ie prompt-segment` - compact workspace status for shell prompts
+//!
+//! Prompts render on every command, so the segment is cached per session in
+//! `.intent-engine/prompt-cache/` and only recomputed when the project database
+//! (or its WAL file) has been modified since the cache was written. A cache hit
+//! costs a few `stat` calls and one small file read.
+
+use crate::db::create_pool;
+use crate::error::Result;
+use crate::project::ProjectContext;
+use crate::workspace::{resolve_session_id, WorkspaceManager};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const CACHE_DIR: &str = "prompt-cache";
+
+/// Longest task name shown before truncating with "…"
+const MAX_NAME_CHARS: usize = 24;
+
+const BASH_INIT: &str = r#"# intent-engine prompt segment (add to ~/.bashrc: eval "$(ie prompt-segment --bash)")
+__ie_prompt_segment() {
+    local segment
+    segment="$(command ie prompt-segment 2>/dev/null)"
+    [ -n "$segment" ] && printf '%s ' "$segment"
+}
+PS1='$(__ie_prompt_segment)'"$PS1"
+"#;
+
+const ZSH_INIT: &str = r#"# intent-engine prompt segment (add to ~/.zshrc: eval "$(ie prompt-segment --zsh)")
+setopt PROMPT_SUBST
+__ie_prompt_segment() {
+    local segment
+    segment="$(command ie prompt-segment 2>/dev/null)"
+    [[ -n "$segment" ]] && print -rn -- "$segment "
+}
+PROMPT='$(__ie_prompt_segment)'"$PROMPT"
+"#;
+
+/// Data shown in the prompt segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptSegment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_name: Option<String>,
+    pub todo: i64,
+}
+
+impl PromptSegment {
+    /// Render as e.g. `⚒ #42 fix-login (3 todo)`; empty when there is nothing to show
+    pub fn render(&self) -> String {
+        let todo = if self.todo > 0 {
+            Some(format!("({} todo)", self.todo))
+        } else {
+            None
+        };
+
+        let focus = self.task_id.map(|id| match &self.task_name {
+            Some(name) => format!("#{} {}", id, truncate_name(name)),
+            None => format!("#{}", id),
+        });
+
+        let parts: Vec<String> = focus.into_iter().chain(todo).collect();
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!("⚒ {}", parts.join(" "))
+        }
+    }
+}
+
+fn truncate_name(name: &str) -> String {
+    let name = name.lines().next().unwrap_or_default().trim();
+    if name.chars().count() <= MAX_NAME_CHARS {
+        name.to_string()
+    } else {
+        let truncated: String = name.chars().take(MAX_NAME_CHARS - 1).collect();
+        format!("{}…", truncated.trim_end())
+    }
+}
+
+/// Handle `ie prompt-segment`
+pub async fn handle_prompt_segment(
+    zsh: bool,
+    bash: bool,
+    no_cache: bool,
+    format: &str,
+) -> Result<()> {
+    if zsh {
+        print!("{}", ZSH_INIT);
+        return Ok(());
+    }
+    if bash {
+        print!("{}", BASH_INIT);
+        return Ok(());
+    }
+
+    // Never initialize a project from a prompt: outside a project, print nothing
+    let Some(intent_dir) = ProjectContext::find_project_root()
+        .map(|root| root.join(".intent-engine"))
+        .filter(|dir| dir.join("project.db").exists())
+    else {
+        return Ok(());
+    };
+
+    let segment = load_segment(&intent_dir, no_cache).await?;
+
+    if format == "json" {
+        let mut output = serde_json::to_value(&segment)?;
+        output["segment"] = serde_json::Value::String(segment.render());
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        let rendered = segment.render();
+        if !rendered.is_empty() {
+            println!("{}", rendered);
+        }
+    }
+
+    Ok(())
+}
+
+/// Load the segment for the current session, using the cache when it is fresh
+pub async fn load_segment(intent_dir: &Path, no_cache: bool) -> Result<PromptSegment> {
+    let session_id = resolve_session_id(None);
+    let cache_path = cache_file(intent_dir, &session_id);
+
+    if !no_cache {
+        if let Some(segment) = read_fresh_cache(intent_dir, &cache_path) {
+            return Ok(segment);
+        }
+    }
+
+    let segment = compute_segment(&intent_dir.join("project.db")).await?;
+
+    // Best effort: an unwritable cache only costs speed
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string(&segment) {
+        let _ = std::fs::write(&cache_path, content);
+    }
+
+    Ok(segment)
+}
+
+async fn compute_segment(db_path: &Path) -> Result<PromptSegment> {
+    let pool = create_pool(db_path).await?;
+
+    let current = WorkspaceManager::new(&pool).get_current_task(None).await?;
+    let todo: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE status = 'todo'")
+        .fetch_one(&pool)
+        .await?;
+
+    // Close before writing the cache: the final checkpoint touches the WAL
+    // file, which would otherwise make the new cache look stale immediately
+    pool.close().await;
+
+    Ok(PromptSegment {
+        task_id: current.current_task_id,
+        task_name: current.task.map(|t| t.name),
+        todo,
+    })
+}
+
+fn cache_file(intent_dir: &Path, session_id: &str) -> PathBuf {
+    let safe_session: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    intent_dir
+        .join(CACHE_DIR)
+        .join(format!("{}.json", safe_session))
+}
+
+/// Return the cached segment if it was written after the last database change
+fn read_fresh_cache(intent_dir: &Path, cache_path: &Path) -> Option<PromptSegment> {
+    let cached_at = modified(cache_path)?;
+
+    let db_path = intent_dir.join("project.db");
+    let wal_path = intent_dir.join("project.db-wal");
+    let db_changed = [modified(&db_path), modified(&wal_path)]
+        .into_iter()
+        .flatten()
+        .max()?;
+
+    if cached_at <= db_changed {
+        return None;
+    }
+
+    let content = std::fs::read_to_string(cache_path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_with_focus() {
+        let segment = PromptSegment {
+            task_id: Some(42),
+            task_name: Some("fix-login".to_string()),
+            todo: 3,
+        };
+        assert_eq!(segment.render(), "⚒ #42 fix-login (3 todo)");
+    }
+
+    #[test]
+    fn test_render_without_focus() {
+        let segment = PromptSegment {
+            task_id: None,
+            task_name: None,
+            todo: 2,
+        };
+        assert_eq!(segment.render(), "⚒ (2 todo)");
+
+        let empty = PromptSegment {
+            task_id: None,
+            task_name: None,
+            todo: 0,
+        };
+        assert_eq!(empty.render(), "");
+    }
+
+    #[test]
+    fn test_truncate_name() {
+        assert_eq!(truncate_name("short"), "short");
+        assert_eq!(truncate_name("first line\nsecond"), "first line");
+
+        let long = truncate_name("Implement the authentication middleware");
+        assert_eq!(long.chars().count(), MAX_NAME_CHARS);
+        assert!(long.ends_with('…'));
+    }
+
+    #[test]
+    fn test_cache_file_sanitizes_session() {
+        let path = cache_file(Path::new("/p/.intent-engine"), "a/b c");
+        assert_eq!(
+            path,
+            Path::new("/p/.intent-engine/prompt-cache/a_b_c.json").to_path_buf()
+        );
+    }
+}
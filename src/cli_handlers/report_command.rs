@@ -0,0 +1,203 @@
+use crate::cli::ReportCommands;
+use crate::cli_handlers::utils::{format_minutes, status_icon};
+use crate::config::ProjectConfig;
+use crate::db::models::{AgingTask, CycleTimeReport, MilestoneReport};
+use crate::error::{IntentError, Result};
+use crate::project::ProjectContext;
+use crate::report::ReportManager;
+
+/// Handle `ie report` subcommands
+pub async fn handle_report(cmd: ReportCommands) -> Result<()> {
+    match cmd {
+        ReportCommands::Milestone { name, format } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let report = ReportManager::new(&ctx.pool)
+                .milestone_report(&name)
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_milestone_report(&report);
+            }
+        },
+        ReportCommands::Aging { format } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let tasks = ReportManager::new(&ctx.pool).aging_report().await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&tasks)?);
+            } else {
+                print_aging_report(&tasks);
+            }
+        },
+        ReportCommands::Pr { task, format } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let template = match &ProjectConfig::for_project_root(&ctx.root)?
+                .report
+                .pr_template
+            {
+                Some(path) => {
+                    let path = ctx.root.join(path);
+                    Some(std::fs::read_to_string(&path).map_err(|e| {
+                        IntentError::InvalidInput(format!(
+                            "Cannot read PR template {}: {}",
+                            path.display(),
+                            e
+                        ))
+                    })?)
+                },
+                None => None,
+            };
+            let report = ReportManager::new(&ctx.pool)
+                .pr_report(task, template.as_deref())
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.markdown);
+            }
+        },
+        ReportCommands::Standup {
+            since,
+            author,
+            format,
+        } => {
+            let since =
+                crate::time_utils::parse_date_filter(&since).map_err(IntentError::InvalidInput)?;
+            let ctx = ProjectContext::load_read_only().await?;
+            let report = ReportManager::new(&ctx.pool)
+                .standup_report(since, author.as_deref())
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{}", report.markdown);
+            }
+        },
+        ReportCommands::CycleTime {
+            since,
+            percentiles,
+            format,
+        } => {
+            let ctx = ProjectContext::load_read_only().await?;
+            let report = ReportManager::new(&ctx.pool)
+                .cycle_time_report(since.as_deref(), percentiles)
+                .await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_cycle_time_report(&report);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+fn print_milestone_report(report: &MilestoneReport) {
+    println!(
+        "Milestone: {} ({}/{} done, {:.0}%)",
+        report.milestone.name, report.done_tasks, report.total_tasks, report.completion_percent
+    );
+    let estimate = &report.estimate;
+    if estimate.total_minutes > 0 {
+        print!(
+            "Estimate: {} remaining of {}",
+            format_minutes(estimate.remaining_minutes),
+            format_minutes(estimate.total_minutes)
+        );
+        if estimate.unestimated > 0 {
+            print!(" ({} task(s) unestimated)", estimate.unestimated);
+        }
+        println!();
+    }
+
+    if !report.tasks.is_empty() {
+        println!("\nTasks:");
+        for task in &report.tasks {
+            println!(
+                "  {} #{}: {}",
+                status_icon(&task.status),
+                task.id,
+                task.name
+            );
+        }
+    }
+
+    if !report.blocked.is_empty() {
+        println!("\nBlocked:");
+        for blocked in &report.blocked {
+            println!("  #{}: {}", blocked.task.id, blocked.task.name);
+            for blocker in &blocked.blocking_chain {
+                println!(
+                    "    ← {} #{}: {}",
+                    status_icon(&blocker.status),
+                    blocker.id,
+                    blocker.name
+                );
+            }
+        }
+    }
+
+    if !report.at_risk.is_empty() {
+        println!("\nAt risk:");
+        for item in &report.at_risk {
+            println!(
+                "  #{}: {} ({})",
+                item.task.id,
+                item.task.name,
+                item.reasons.join("; ")
+            );
+        }
+    }
+}
+
+fn print_aging_report(tasks: &[AgingTask]) {
+    if tasks.is_empty() {
+        println!("No tasks in progress");
+        return;
+    }
+    println!("In progress, longest running first:");
+    for item in tasks {
+        println!(
+            "  #{}: {} ({}, doing for {})",
+            item.task.id,
+            item.task.name,
+            item.task.owner,
+            format_minutes(item.age_minutes)
+        );
+    }
+}
+
+fn print_cycle_time_report(report: &CycleTimeReport) {
+    if report.tasks.is_empty() {
+        println!("No completed tasks with a recorded start");
+        return;
+    }
+    println!("Cycle time, most recently completed first:");
+    for item in &report.tasks {
+        let active = match item.active_minutes {
+            Some(active) if active != item.cycle_minutes => {
+                format!(", {} active", format_minutes(active))
+            },
+            _ => String::new(),
+        };
+        println!(
+            "  #{}: {} ({}{})",
+            item.task.id,
+            item.task.name,
+            format_minutes(item.cycle_minutes),
+            active
+        );
+    }
+    if !report.percentiles.is_empty() {
+        println!("\nPercentiles over {} task(s):", report.tasks.len());
+        for p in &report.percentiles {
+            println!("  p{}: {}", p.percentile, format_minutes(p.minutes));
+        }
+    }
+}
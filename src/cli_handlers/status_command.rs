@@ -1,10 +1,12 @@
 use crate::backend::{TaskBackend, WorkspaceBackend};
 use crate::db::models::{NoFocusResponse, StatusResponse, TaskBrief};
 use crate::error::Result;
+use crate::session_restore::RestoreBundle;
 
 /// Handle `ie status` command.
 ///
 /// Returns `Ok(true)` if a focused task was displayed, `Ok(false)` if no focus.
+/// Runs the project's `session_restored` hooks afterwards (see `crate::hooks`).
 /// The caller can add backend-specific logic (e.g. LLM suggestions) after this.
 pub async fn handle_status(
     task_mgr: &impl TaskBackend,
@@ -21,7 +23,7 @@ pub async fn handle_status(
         current.current_task_id
     };
 
-    match target_task_id {
+    let focused = match target_task_id {
         Some(id) => {
             let status = task_mgr.get_status(id, with_events).await?;
 
@@ -30,7 +32,7 @@ pub async fn handle_status(
             } else {
                 print_status_text(&status);
             }
-            Ok(true)
+            true
         },
         None => {
             let root_tasks = task_mgr.get_root_tasks().await?;
@@ -54,9 +56,15 @@ pub async fn handle_status(
                     }
                 }
             }
-            Ok(false)
+            false
         },
-    }
+    };
+
+    crate::project::ProjectContext::configured_hooks()?.fire(
+        crate::hooks::HookEvent::SessionRestored,
+        &serde_json::json!({ "task_id": target_task_id }),
+    )?;
+    Ok(focused)
 }
 
 /// Print status in text format (shared between SQLite and Neo4j).
@@ -165,3 +173,30 @@ pub fn print_status_text(status: &StatusResponse) {
         }
     }
 }
+
+/// Print a `RestoreBundle` (`ie status --max-tokens/--max-chars`)
+pub fn print_restore_bundle(bundle: &RestoreBundle, format: &str) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(bundle)?);
+        return Ok(());
+    }
+
+    if bundle.focused_task_id.is_none() {
+        println!("  No focused task. Pending tasks:");
+    }
+    for item in &bundle.items {
+        let mut lines = item.text.lines();
+        println!("[{}] {}", item.kind.as_str(), lines.next().unwrap_or(""));
+        for line in lines {
+            println!("    {}", line);
+        }
+        if item.truncated {
+            println!("    (truncated to fit the budget)");
+        }
+    }
+    println!(
+        "\n{}/{} chars used, {} item(s) omitted",
+        bundle.used_chars, bundle.budget_chars, bundle.omitted
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use crate::cli::SyncCommands;
+use crate::config::ProjectConfig;
+use crate::error::{IntentError, Result};
+use crate::project::ProjectContext;
+use crate::sync::{ConflictPolicy, PullReport, Remote, SyncManager};
+
+/// Handle `ie sync` subcommands
+pub async fn handle_sync_command(cmd: SyncCommands) -> Result<()> {
+    match cmd {
+        SyncCommands::Push {
+            remote,
+            conflict,
+            format,
+        } => {
+            let ctx = ProjectContext::load().await?;
+            let (remote, policy) = resolve(&ctx, remote, conflict)?;
+            let report = SyncManager::new(&ctx.pool, remote, policy).push().await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            print_pull(&report.pull);
+            if report.pushed == 0 {
+                println!("Nothing to push");
+            } else {
+                println!("Pushed {} change(s)", report.pushed);
+            }
+        },
+
+        SyncCommands::Pull {
+            remote,
+            conflict,
+            format,
+        } => {
+            let ctx = ProjectContext::load().await?;
+            let (remote, policy) = resolve(&ctx, remote, conflict)?;
+            let report = SyncManager::new(&ctx.pool, remote, policy).pull().await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if report.applied == 0 && report.skipped == 0 {
+                println!("Already up to date");
+            }
+            print_pull(&report);
+        },
+
+        SyncCommands::Status { remote, format } => {
+            let ctx = ProjectContext::load().await?;
+            let (remote, policy) = resolve(&ctx, remote, None)?;
+            let status = SyncManager::new(&ctx.pool, remote, policy).status().await?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+                return Ok(());
+            }
+            println!("Replica: {}", status.replica_id);
+            println!("Hub: {}", status.remote);
+            println!("Conflict policy: {}", status.policy.as_str());
+            println!("Local changes to push: {}", status.pending);
+            match (status.incoming, &status.remote_error) {
+                (Some(incoming), _) => println!(
+                    "Hub changes to pull: {} ({} conflicting)",
+                    incoming,
+                    status.conflicts.unwrap_or(0)
+                ),
+                (None, Some(error)) => println!("Hub unreachable: {}", error),
+                (None, None) => {},
+            }
+            if let Some(at) = &status.last_push_at {
+                println!("Last push: {}", at);
+            }
+            if let Some(at) = &status.last_pull_at {
+                println!("Last pull: {}", at);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Hub and conflict policy from the flags, falling back to `[sync]`
+fn resolve(
+    ctx: &ProjectContext,
+    remote: Option<String>,
+    conflict: Option<String>,
+) -> Result<(Remote, ConflictPolicy)> {
+    let config = ProjectConfig::for_project_root(&ctx.root)?.sync;
+    let remote = remote.or(config.remote).ok_or_else(|| {
+        IntentError::InvalidInput(
+            "No sync hub configured. Set [sync] remote in .intent-engine/config.toml or pass --remote"
+                .to_string(),
+        )
+    })?;
+    let policy = match conflict {
+        Some(name) => name.parse()?,
+        None => config.conflict,
+    };
+    Ok((Remote::parse(&remote)?, policy))
+}
+
+fn print_pull(report: &PullReport) {
+    if report.applied > 0 {
+        println!(
+            "Pulled {} change(s) ({} deletion(s))",
+            report.applied, report.deleted
+        );
+    }
+    if report.skipped > 0 {
+        println!(
+            "Skipped {} change(s) referencing tasks not on the hub yet",
+            report.skipped
+        );
+    }
+    for conflict in &report.conflicts {
+        let row = match conflict.local_id {
+            Some(id) => format!("{} #{}", conflict.entity.as_str(), id),
+            None => format!("{} {}", conflict.entity.as_str(), conflict.uid),
+        };
+        println!("Conflict on {}: kept the {} version", row, conflict.kept);
+    }
+}
@@ -1,8 +1,12 @@
 use crate::backend::{TaskBackend, WorkspaceBackend};
 use crate::cli::TaskCommands;
-use crate::db::models::TaskSortBy;
+use crate::db::models::{CompletionOutcome, DoneTaskResponse, TaskSortBy, TaskVisibility};
 use crate::error::{IntentError, Result};
-use crate::tasks::TaskUpdate;
+use crate::hooks::HookEvent;
+use crate::project::ProjectContext;
+use crate::tasks::{CloneOptions, TaskUpdate};
+use crate::text_diff::{diff_lines, render as render_diff};
+use crate::workspace::resolve_session_id;
 use serde_json::json;
 
 use super::utils::{merge_metadata, parse_metadata};
@@ -24,6 +28,7 @@ pub async fn handle_task_command(
             metadata,
             blocked_by,
             blocks,
+            private,
             format,
         } => {
             handle_create(
@@ -38,6 +43,7 @@ pub async fn handle_task_command(
                 metadata,
                 blocked_by,
                 blocks,
+                private,
                 format,
             )
             .await
@@ -56,8 +62,12 @@ pub async fn handle_task_command(
             description,
             status,
             priority,
+            estimate_minutes,
             active_form,
             owner,
+            needs_human,
+            blocked,
+            visibility,
             parent,
             metadata,
             add_blocked_by,
@@ -73,8 +83,12 @@ pub async fn handle_task_command(
                 description,
                 status,
                 priority,
+                estimate_minutes,
                 active_form,
                 owner,
+                needs_human,
+                blocked,
+                visibility,
                 parent,
                 metadata,
                 add_blocked_by,
@@ -105,12 +119,87 @@ pub async fn handle_task_command(
         TaskCommands::Start {
             id,
             description,
+            over_wip,
             format,
-        } => handle_start(task_mgr, id, description, format).await,
+        } => handle_start(task_mgr, id, description, over_wip, format).await,
 
-        TaskCommands::Done { id, format } => handle_done(task_mgr, id, format).await,
+        TaskCommands::Done {
+            id,
+            outcome,
+            artifacts,
+            format,
+        } => {
+            let outcome = outcome.map(|summary| CompletionOutcome { summary, artifacts });
+            handle_done(task_mgr, id, outcome, format).await
+        },
+
+        TaskCommands::SpecDiff { id, rev, format } => {
+            handle_spec_diff(task_mgr, id, rev, format).await
+        },
+
+        TaskCommands::Children {
+            parent,
+            cursor,
+            limit,
+            format,
+        } => handle_children(task_mgr, parent, cursor, limit, format).await,
+
+        TaskCommands::Transitions { id, format } => handle_transitions(task_mgr, id, format).await,
+
+        TaskCommands::Approve { id, format } => handle_approve(task_mgr, id, format).await,
+
+        TaskCommands::Clone {
+            id,
+            name,
+            with_events,
+            keep_status,
+            format,
+        } => {
+            let options = CloneOptions {
+                name,
+                include_events: with_events,
+                keep_status,
+            };
+            handle_clone(task_mgr, id, options, format).await
+        },
+
+        TaskCommands::Check { id, item, format } => {
+            handle_checklist(task_mgr, id, item.map(|item| (item, true)), format).await
+        },
 
-        TaskCommands::Next { format } => handle_next(task_mgr, format).await,
+        TaskCommands::Uncheck { id, item, format } => {
+            handle_checklist(task_mgr, id, Some((item, false)), format).await
+        },
+
+        TaskCommands::Next { explain, format } => handle_next(task_mgr, explain, format).await,
+
+        TaskCommands::Claim {
+            session,
+            lease_minutes,
+            format,
+        } => handle_claim(task_mgr, session, lease_minutes, format).await,
+
+        TaskCommands::Renew {
+            id,
+            session,
+            lease_minutes,
+            format,
+        } => handle_renew(task_mgr, id, session, lease_minutes, format).await,
+
+        TaskCommands::Release {
+            id,
+            session,
+            format,
+        } => handle_release(task_mgr, id, session, format).await,
+        TaskCommands::Age { format } => handle_age(task_mgr, format).await,
+        TaskCommands::Stale {
+            days,
+            demote,
+            format,
+        } => handle_stale(task_mgr, days, demote, format).await,
+        TaskCommands::Estimate { id, apply, format } => {
+            handle_estimate(task_mgr, id, apply, format).await
+        },
     }
 }
 
@@ -127,10 +216,11 @@ pub async fn handle_create(
     parent: Option<i64>,
     status: String,
     priority: Option<i32>,
-    owner: String,
+    owner: Option<String>,
     metadata: Vec<String>,
     blocked_by: Vec<i64>,
     blocks: Vec<i64>,
+    private: bool,
     format: String,
 ) -> Result<()> {
     // Determine parent_id:
@@ -168,7 +258,7 @@ pub async fn handle_create(
             &name,
             description.as_deref(),
             parent_id,
-            Some(&owner),
+            owner.as_deref(),
             priority,
             merged_metadata.as_deref(),
         )
@@ -201,6 +291,19 @@ pub async fn handle_create(
         task_mgr.add_dependency(task.id, *blocked_id).await?;
     }
 
+    // Last, as outside the private scope the task is out of reach afterwards
+    if private {
+        task = task_mgr
+            .update_task(
+                task.id,
+                TaskUpdate {
+                    visibility: Some(TaskVisibility::Private),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+
     // Output
     if format == "json" {
         let mut response = serde_json::to_value(&task)?;
@@ -224,6 +327,9 @@ pub async fn handle_create(
             println!("  Spec: {}", spec);
         }
         println!("  Owner: {}", task.owner);
+        if !task.visibility.is_shared() {
+            println!("  Visibility: {}", task.visibility.as_str());
+        }
         if !blocked_by.is_empty() {
             println!("  Blocked by: {:?}", blocked_by);
         }
@@ -242,9 +348,23 @@ pub async fn handle_create(
         }
     }
 
+    let hooks = ProjectContext::configured_hooks()?;
+    hooks.fire(HookEvent::TaskCreated, &task)?;
+    if let Some(event) = status_hook(&status) {
+        hooks.fire(event, &task)?;
+    }
     Ok(())
 }
 
+/// Hook event of a task moved to `status` (see `crate::hooks`)
+fn status_hook(status: &str) -> Option<HookEvent> {
+    match status {
+        "doing" => Some(HookEvent::TaskStarted),
+        "done" => Some(HookEvent::TaskCompleted),
+        _ => None,
+    }
+}
+
 pub async fn handle_get(
     task_mgr: &impl TaskBackend,
     id: i64,
@@ -253,7 +373,7 @@ pub async fn handle_get(
     format: String,
 ) -> Result<()> {
     if with_context {
-        // Full context includes ancestors, siblings, children, dependencies
+        // Full context includes ancestors, siblings, children, dependencies, links
         let context = task_mgr.get_task_context(id).await?;
 
         if with_events {
@@ -265,13 +385,18 @@ pub async fn handle_get(
                 "siblings": context.siblings,
                 "children": context.children,
                 "dependencies": context.dependencies,
+                "links": context.links,
                 "events_summary": task_with_events.events_summary,
+                "outcome": task_with_events.outcome,
             });
 
             if format == "json" {
                 println!("{}", serde_json::to_string_pretty(&response)?);
             } else {
                 super::utils::print_task_context(&context);
+                if let Some(outcome) = &task_with_events.outcome {
+                    super::utils::print_outcome(outcome);
+                }
                 if let Some(summary) = &task_with_events.events_summary {
                     super::utils::print_events_summary(summary);
                 }
@@ -289,6 +414,9 @@ pub async fn handle_get(
         } else {
             let task = &task_with_events.task;
             super::utils::print_task_summary(task);
+            if let Some(outcome) = &task_with_events.outcome {
+                super::utils::print_outcome(outcome);
+            }
             if let Some(summary) = &task_with_events.events_summary {
                 super::utils::print_events_summary(summary);
             }
@@ -298,12 +426,25 @@ pub async fn handle_get(
 
         // Also fetch dependencies for display
         let context = task_mgr.get_task_context(id).await?;
+        // Parents also get a rollup of everything underneath them
+        let subtree = if context.children.is_empty() {
+            None
+        } else {
+            Some(task_mgr.get_subtree_stats(id).await?)
+        };
 
         if format == "json" {
+            // Parsed from the spec itself, so every backend has it
+            let spec_meta = task
+                .spec
+                .as_deref()
+                .and_then(|spec| crate::spec_meta::SpecMeta::parse(spec).ok().flatten());
             let response = json!({
                 "task": task,
+                "spec_meta": spec_meta,
                 "blocked_by": context.dependencies.blocking_tasks.iter().map(|t| t.id).collect::<Vec<_>>(),
                 "blocks": context.dependencies.blocked_by_tasks.iter().map(|t| t.id).collect::<Vec<_>>(),
+                "subtree": subtree,
             });
             println!("{}", serde_json::to_string_pretty(&response)?);
         } else {
@@ -326,6 +467,9 @@ pub async fn handle_get(
                     .collect();
                 println!("  Blocks: {}", ids.join(", "));
             }
+            if let Some(stats) = &subtree {
+                super::utils::print_subtree_stats(stats);
+            }
         }
     }
 
@@ -340,8 +484,12 @@ pub async fn handle_update(
     description: Option<String>,
     status: Option<String>,
     priority: Option<i32>,
+    estimate_minutes: Option<i64>,
     active_form: Option<String>,
     owner: Option<String>,
+    needs_human: Option<String>,
+    blocked: Option<String>,
+    visibility: Option<TaskVisibility>,
     parent: Option<i64>,
     metadata: Vec<String>,
     add_blocked_by: Vec<i64>,
@@ -382,6 +530,9 @@ pub async fn handle_update(
                 active_form: active_form.as_deref(),
                 owner: owner.as_deref(),
                 metadata: merged_metadata.as_deref(),
+                estimate_minutes,
+                needs_human: needs_human.as_deref(),
+                blocked_reason: blocked.as_deref(),
                 ..Default::default()
             },
         )
@@ -409,6 +560,19 @@ pub async fn handle_update(
         task_mgr.remove_dependency(id, *blocked_id).await?;
     }
 
+    // Last, as a task made private may be out of reach afterwards
+    if visibility.is_some() {
+        task = task_mgr
+            .update_task(
+                id,
+                TaskUpdate {
+                    visibility,
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+
     // Output
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&task)?);
@@ -417,9 +581,13 @@ pub async fn handle_update(
         super::utils::print_task_summary(&task);
     }
 
+    if let Some(event) = status.as_deref().and_then(status_hook) {
+        ProjectContext::configured_hooks()?.fire(event, &task)?;
+    }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_list(
     task_mgr: &impl TaskBackend,
     status: Option<String>,
@@ -486,9 +654,14 @@ pub async fn handle_list(
                 .priority
                 .map(|p| format!(" [P{}]", p))
                 .unwrap_or_default();
+            let blocked_info = task
+                .blocked_reason
+                .as_ref()
+                .map(|r| format!(" (blocked: {})", r))
+                .unwrap_or_default();
             println!(
-                "  {} #{} {}{}{}",
-                status_icon, task.id, task.name, parent_info, priority_info
+                "  {} #{} {}{}{}{}",
+                status_icon, task.id, task.name, parent_info, priority_info, blocked_info
             );
         }
         if result.has_more {
@@ -561,6 +734,7 @@ pub async fn handle_start(
     task_mgr: &impl TaskBackend,
     id: i64,
     description: Option<String>,
+    over_wip: bool,
     format: String,
 ) -> Result<()> {
     // Update description first if provided
@@ -577,7 +751,11 @@ pub async fn handle_start(
     }
 
     // Start the task (sets status to doing + sets as current focus)
-    let result = task_mgr.start_task(id, true).await?;
+    let result = if over_wip {
+        task_mgr.start_task_over_wip_limit(id, true).await?
+    } else {
+        task_mgr.start_task(id, true).await?
+    };
 
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&result)?);
@@ -595,26 +773,273 @@ pub async fn handle_start(
         }
     }
 
-    Ok(())
+    ProjectContext::configured_hooks()?.fire(HookEvent::TaskStarted, &result.task)
 }
 
 pub async fn handle_done(
     task_mgr: &impl TaskBackend,
     id: Option<i64>,
+    outcome: Option<CompletionOutcome>,
     format: String,
 ) -> Result<()> {
     // If ID given, complete by ID directly. If not, complete current focus.
     let result = if let Some(task_id) = id {
-        task_mgr.done_task_by_id(task_id, false).await?
+        task_mgr
+            .done_task_by_id_with_outcome(task_id, outcome.clone())
+            .await?
     } else {
-        task_mgr.done_task(false).await?
+        task_mgr.done_task_with_outcome(outcome.clone()).await?
+    };
+
+    print_done_result(&result, outcome.as_ref(), &format)?;
+    fire_completed(&result)
+}
+
+pub async fn handle_children(
+    task_mgr: &impl TaskBackend,
+    parent: Option<i64>,
+    cursor: Option<i64>,
+    limit: Option<i64>,
+    format: String,
+) -> Result<()> {
+    let page = task_mgr.find_children(parent, cursor, limit).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&page)?);
+        return Ok(());
+    }
+    match parent {
+        Some(id) => println!("Children of #{}: {} total", id, page.total_count),
+        None => println!("Root tasks: {} total", page.total_count),
+    }
+    for node in &page.tasks {
+        let task = &node.task;
+        print!(
+            "  {} #{}: {}",
+            super::utils::status_icon(&task.status),
+            task.id,
+            task.name
+        );
+        if node.child_count > 0 {
+            print!(" ({} children)", node.child_count);
+        }
+        println!();
+    }
+    if let Some(next) = page.next_cursor {
+        println!("\n  ... more children available (use --cursor {})", next);
+    }
+    Ok(())
+}
+
+pub async fn handle_transitions(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    format: String,
+) -> Result<()> {
+    let transitions = task_mgr.transitions(id).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&transitions)?);
+        return Ok(());
+    }
+    if transitions.is_empty() {
+        println!("Task #{} has no recorded status changes", id);
+        return Ok(());
+    }
+    println!("Task #{} status changes:", id);
+    for t in &transitions {
+        println!(
+            "  {}  {} -> {}  ({}{})",
+            t.changed_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            t.from_status,
+            t.to_status,
+            t.source.as_str(),
+            t.actor
+                .as_ref()
+                .map(|actor| format!(", {}", actor))
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_spec_diff(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    rev: Option<String>,
+    format: String,
+) -> Result<()> {
+    let history = task_mgr.spec_history(id).await?;
+    let (Some(first), Some(last)) = (history.first(), history.last()) else {
+        return Err(IntentError::InvalidInput(format!(
+            "Task #{} has no spec revisions yet (its spec has never been changed)",
+            id
+        )));
     };
 
+    let (from, to) = parse_rev_range(rev.as_deref(), first.revision, last.revision)?;
+    let find = |revision: i64| {
+        history
+            .iter()
+            .find(|r| r.revision == revision)
+            .ok_or_else(|| {
+                IntentError::InvalidInput(format!(
+                    "Task #{} has no spec revision {} (revisions {}..{})",
+                    id, revision, first.revision, last.revision
+                ))
+            })
+    };
+    let (old, new) = (find(from)?, find(to)?);
+    let diff = diff_lines(&old.spec, &new.spec);
+
+    if format == "json" {
+        let output = json!({
+            "task_id": id,
+            "from": old,
+            "to": new,
+            "diff": diff,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!(
+            "Task #{} spec: revision {} ({}) -> revision {} ({})",
+            id,
+            old.revision,
+            old.changed_at.format("%Y-%m-%d %H:%M"),
+            new.revision,
+            new.changed_at.format("%Y-%m-%d %H:%M")
+        );
+        println!("{}", render_diff(&diff));
+    }
+
+    Ok(())
+}
+
+/// Parse `A..B`, `A..`, `..B` or `N` (meaning `N-1..N`) into a revision pair
+fn parse_rev_range(rev: Option<&str>, first: i64, last: i64) -> Result<(i64, i64)> {
+    let parse = |s: &str, default: i64| {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(default);
+        }
+        s.parse::<i64>()
+            .map_err(|_| IntentError::InvalidInput(format!("Invalid revision '{}'", s)))
+    };
+
+    match rev {
+        None => Ok((first, last)),
+        Some(rev) => match rev.split_once("..") {
+            Some((from, to)) => Ok((parse(from, first)?, parse(to, last)?)),
+            None => {
+                let revision = parse(rev, last)?;
+                Ok(((revision - 1).max(first), revision))
+            },
+        },
+    }
+}
+
+pub async fn handle_approve(task_mgr: &impl TaskBackend, id: i64, format: String) -> Result<()> {
+    let result = task_mgr.approve_task(id).await?;
+    print_done_result(&result, None, &format)?;
+    fire_completed(&result)
+}
+
+/// Fire `task_completed` unless the task only went to review
+fn fire_completed(result: &DoneTaskResponse) -> Result<()> {
+    if result.completed_task.status != "done" {
+        return Ok(());
+    }
+    ProjectContext::configured_hooks()?.fire(HookEvent::TaskCompleted, result)
+}
+
+pub async fn handle_clone(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    options: CloneOptions,
+    format: String,
+) -> Result<()> {
+    let result = task_mgr.clone_subtree(id, options).await?;
+
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        let root = &result.root_task;
+        println!(
+            "Cloned task #{} as #{} '{}' ({} task(s), {} dependency(ies), {} event(s))",
+            id,
+            root.id,
+            root.name,
+            result.task_id_map.len(),
+            result.dependency_count,
+            result.event_count
+        );
+    }
+    Ok(())
+}
+
+/// Set a checklist item when `change` is given, then show the checklist
+pub async fn handle_checklist(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    change: Option<(i64, bool)>,
+    format: String,
+) -> Result<()> {
+    if let Some((position, checked)) = change {
+        task_mgr.set_checklist_item(id, position, checked).await?;
+    }
+    let items = task_mgr.checklist(id).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+    } else if items.is_empty() {
+        println!("Task #{} has no checklist", id);
+    } else {
+        let checked = items.iter().filter(|i| i.checked).count();
+        println!("Task #{} checklist ({}/{}):", id, checked, items.len());
+        for item in &items {
+            let mark = if item.checked { "x" } else { " " };
+            println!("  {}. [{}] {}", item.position, mark, item.text);
+        }
+    }
+    Ok(())
+}
+
+fn print_done_result(
+    result: &DoneTaskResponse,
+    outcome: Option<&CompletionOutcome>,
+    format: &str,
+) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(result)?);
     } else {
         let task = &result.completed_task;
-        println!("Completed task #{} '{}'", task.id, task.name);
+        if task.status == "review" {
+            println!(
+                "Task #{} '{}' is awaiting review (approve with: ie task approve {})",
+                task.id, task.name, task.id
+            );
+        } else {
+            println!(
+                "{}",
+                crate::i18n::t("done.completed", &[("id", &task.id), ("name", &task.name)])
+            );
+        }
+        if let Some(outcome) = outcome {
+            super::utils::print_outcome(outcome);
+        }
+        for warning in &result.warnings {
+            println!("  Warning: {}", warning);
+        }
+        // Nothing is completed or unblocked until a human approves
+        if task.status == "review" {
+            return Ok(());
+        }
+        for task in &result.newly_unblocked {
+            println!(
+                "  Unblocked: #{} '{}' is ready to start",
+                task.id, task.name
+            );
+        }
 
         // Show next step suggestion
         use crate::db::models::NextStepSuggestion;
@@ -624,7 +1049,13 @@ pub async fn handle_done(
                 parent_task_id,
                 ..
             } => {
-                println!("  Next: {} (ie task start {})", message, parent_task_id);
+                println!(
+                    "  {}",
+                    crate::i18n::t(
+                        "done.next_parent",
+                        &[("message", message), ("parent_id", parent_task_id)],
+                    )
+                );
             },
             NextStepSuggestion::SiblingTasksRemain {
                 message,
@@ -632,8 +1063,11 @@ pub async fn handle_done(
                 ..
             } => {
                 println!(
-                    "  Next: {} ({} siblings remaining)",
-                    message, remaining_siblings_count
+                    "  {}",
+                    crate::i18n::t(
+                        "done.next_siblings",
+                        &[("message", message), ("count", remaining_siblings_count)],
+                    )
                 );
             },
             NextStepSuggestion::TopLevelTaskCompleted { message, .. } => {
@@ -651,15 +1085,205 @@ pub async fn handle_done(
     Ok(())
 }
 
-pub async fn handle_next(task_mgr: &impl TaskBackend, format: String) -> Result<()> {
-    let result = task_mgr.pick_next().await?;
+pub async fn handle_next(task_mgr: &impl TaskBackend, explain: bool, format: String) -> Result<()> {
+    let result = if explain {
+        task_mgr.explain_next().await?
+    } else {
+        task_mgr.pick_next().await?
+    };
 
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else {
         println!("{}", result.format_as_text());
+        if explain {
+            println!("\n{}", result.format_candidates_as_text());
+        }
+    }
+
+    Ok(())
+}
+
+fn lease_duration(minutes: i64) -> Result<chrono::Duration> {
+    if minutes < 1 {
+        return Err(IntentError::InvalidInput(
+            "--lease-minutes must be at least 1".to_string(),
+        ));
     }
+    Ok(chrono::Duration::minutes(minutes))
+}
 
+pub async fn handle_claim(
+    task_mgr: &impl TaskBackend,
+    session: Option<String>,
+    lease_minutes: i64,
+    format: String,
+) -> Result<()> {
+    let lease = lease_duration(lease_minutes)?;
+    let session_id = resolve_session_id(session.as_deref());
+    let task = task_mgr.claim_next_for(&session_id, lease).await?;
+
+    if format == "json" {
+        let response = json!({ "session_id": session_id, "task": task });
+        println!("{}", serde_json::to_string_pretty(&response)?);
+    } else if let Some(task) = task {
+        println!(
+            "Session '{}' claimed task #{} '{}' for {} minutes",
+            session_id, task.id, task.name, lease_minutes
+        );
+    } else {
+        println!("No task available to claim");
+    }
+    Ok(())
+}
+
+pub async fn handle_renew(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    session: Option<String>,
+    lease_minutes: i64,
+    format: String,
+) -> Result<()> {
+    let lease = lease_duration(lease_minutes)?;
+    let session_id = resolve_session_id(session.as_deref());
+    let renewed = task_mgr.renew_lease(id, &session_id, lease).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&renewed)?);
+    } else {
+        println!(
+            "Lease on task #{} renewed until {}",
+            id,
+            renewed.lease_expires_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_release(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    session: Option<String>,
+    format: String,
+) -> Result<()> {
+    let session_id = resolve_session_id(session.as_deref());
+    let released = task_mgr.release_lease(id, &session_id).await?;
+
+    if format == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "task_id": id, "released": released }))?
+        );
+    } else if released {
+        println!("Released lease on task #{}", id);
+    } else {
+        println!("Session '{}' held no lease on task #{}", session_id, id);
+    }
+    Ok(())
+}
+
+pub async fn handle_age(task_mgr: &impl TaskBackend, format: String) -> Result<()> {
+    let result = task_mgr.age_priorities().await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if result.policy.curve == crate::priority::AgingCurve::Off {
+        println!("Priority aging is off (priority.aging.curve)");
+    } else {
+        println!(
+            "{} todo task(s) boosted, {} updated",
+            result.boosted, result.updated
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_stale(
+    task_mgr: &impl TaskBackend,
+    days: Option<f64>,
+    demote: bool,
+    format: String,
+) -> Result<()> {
+    if days.is_some_and(|d| !d.is_finite() || d < 0.0) {
+        return Err(IntentError::InvalidInput(
+            "--days must be a non-negative number".to_string(),
+        ));
+    }
+    let sweep = task_mgr.sweep_stale(days, demote).await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&sweep)?);
+        return Ok(());
+    }
+    if sweep.policy.is_off() {
+        println!("Stale task detection is off (stale.days)");
+        return Ok(());
+    }
+    if sweep.tasks.is_empty() {
+        println!(
+            "No doing task has been idle for {} days or more",
+            sweep.policy.days
+        );
+        return Ok(());
+    }
+    println!(
+        "{} stale task(s), idle for {} days or more:",
+        sweep.tasks.len(),
+        sweep.policy.days
+    );
+    for task in &sweep.tasks {
+        println!(
+            "  #{} {} ({}, idle {:.1} days){}",
+            task.id,
+            task.name,
+            task.owner,
+            task.idle_days,
+            if task.demoted {
+                " -> moved back to todo"
+            } else {
+                ""
+            }
+        );
+    }
+    Ok(())
+}
+
+pub async fn handle_estimate(
+    task_mgr: &impl TaskBackend,
+    id: i64,
+    apply: bool,
+    format: String,
+) -> Result<()> {
+    let estimate = task_mgr.estimate_complexity(id, apply).await?;
+
+    if format == "json" {
+        let response =
+            json!({ "task_id": id, "estimate": estimate, "applied": apply && estimate.is_some() });
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
+    let Some(estimate) = estimate else {
+        println!("No similar completed tasks to estimate #{} from", id);
+        return Ok(());
+    };
+    println!(
+        "Estimated complexity of #{}: {}/10 (confidence {:.2}){}",
+        id,
+        estimate.complexity,
+        estimate.confidence,
+        if apply { ", saved" } else { "" }
+    );
+    println!("Based on:");
+    for sample in &estimate.based_on {
+        let cycle = sample
+            .cycle_minutes
+            .map(|m| format!(", took {}", super::utils::format_minutes(m)))
+            .unwrap_or_default();
+        println!(
+            "  #{} {} (similarity {:.2}, complexity {}{})",
+            sample.task_id, sample.name, sample.similarity, sample.complexity, cycle
+        );
+    }
     Ok(())
 }
 
@@ -738,4 +1362,15 @@ mod tests {
         let result = merge_metadata(Some(existing), &new_meta);
         assert!(result.is_none()); // Empty map returns None
     }
+
+    #[test]
+    fn test_parse_rev_range() {
+        assert_eq!(parse_rev_range(None, 1, 5).unwrap(), (1, 5));
+        assert_eq!(parse_rev_range(Some("2..4"), 1, 5).unwrap(), (2, 4));
+        assert_eq!(parse_rev_range(Some("2.."), 1, 5).unwrap(), (2, 5));
+        assert_eq!(parse_rev_range(Some("..3"), 1, 5).unwrap(), (1, 3));
+        assert_eq!(parse_rev_range(Some("3"), 1, 5).unwrap(), (2, 3));
+        assert_eq!(parse_rev_range(Some("1"), 1, 5).unwrap(), (1, 1));
+        assert!(parse_rev_range(Some("a..b"), 1, 5).is_err());
+    }
 }
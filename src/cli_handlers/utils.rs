@@ -2,7 +2,7 @@
 //!
 //! Helper functions for reading stdin, formatting status badges, and printing task contexts.
 
-use crate::db::models::{EventsSummary, Task, TaskContext};
+use crate::db::models::{CompletionOutcome, EventsSummary, SubtreeStats, Task, TaskContext};
 use crate::error::{IntentError, Result};
 use std::io::{self, Read};
 
@@ -135,18 +135,33 @@ pub fn print_task_summary(task: &Task) {
     let icon = status_icon(&task.status);
     println!("  {} #{} {}", icon, task.id, task.name);
     println!("  Status: {}", task.status);
+    if let Some(reason) = &task.blocked_reason {
+        println!("  Blocked: {}", reason);
+    }
     if let Some(pid) = task.parent_id {
         println!("  Parent: #{}", pid);
     }
     if let Some(p) = task.priority {
         println!("  Priority: {}", p);
     }
+    if let Some(m) = task.estimate_minutes {
+        println!("  Estimate: {}", format_minutes(m));
+    }
+    if let Some(c) = &task.checklist {
+        println!("  Checklist: {}/{} ({}%)", c.checked, c.total, c.percent);
+    }
     if let Some(spec) = &task.spec {
         if !spec.is_empty() {
-            println!("  Spec: {}", spec);
+            println!("  Spec:");
+            for line in render_spec(spec) {
+                println!("    {}", line);
+            }
         }
     }
     println!("  Owner: {}", task.owner);
+    if !task.visibility.is_shared() {
+        println!("  Visibility: {}", task.visibility.as_str());
+    }
     if let Some(af) = &task.active_form {
         println!("  Active form: {}", af);
     }
@@ -155,19 +170,188 @@ pub fn print_task_summary(task: &Task) {
     }
 }
 
+/// Lines of a spec for the terminal: frontmatter fields, then the Markdown body
+pub fn render_spec(spec: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let (_, body) = crate::spec_meta::split_frontmatter(spec);
+    // Specs written before frontmatter was validated are shown as they are
+    match crate::spec_meta::SpecMeta::parse(spec) {
+        Ok(Some(meta)) => {
+            if !meta.acceptance_criteria.is_empty() {
+                lines.push("Acceptance criteria:".to_string());
+                lines.extend(
+                    meta.acceptance_criteria
+                        .iter()
+                        .map(|c| format!("  ☐ {}", c)),
+                );
+            }
+            if !meta.links.is_empty() {
+                lines.push("Links:".to_string());
+                lines.extend(meta.links.iter().map(|l| format!("  {}", l)));
+            }
+            if let Some(minutes) = meta.estimate_minutes {
+                lines.push(format!("Estimate: {}", format_minutes(minutes)));
+            }
+            for (key, value) in &meta.fields {
+                let value = value
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| value.to_string());
+                lines.push(format!("{}: {}", key, value));
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+        },
+        _ => return render_markdown(spec),
+    }
+    lines.extend(render_markdown(body.trim_start_matches(['\r', '\n'])));
+    lines
+}
+
+/// Render Markdown as plain text lines
+///
+/// Markers are dropped or replaced so the text reads naturally without a
+/// Markdown viewer: headings are underlined, list bullets become •, task
+/// list items ☐/☑, code blocks are indented and links show their URL.
+pub fn render_markdown(markdown: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(format!("    {}", line));
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let heading = render_inline(trimmed[level..].trim());
+            let width = heading.chars().count();
+            lines.push(heading);
+            match level {
+                1 => lines.push("=".repeat(width)),
+                2 => lines.push("-".repeat(width)),
+                _ => {},
+            }
+        } else if matches!(trimmed, "---" | "***" | "___") {
+            lines.push("─".repeat(40));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            lines.push(format!("{}│ {}", indent, render_inline(quote.trim_start())));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+        {
+            let (mark, text) = if let Some(text) = item.strip_prefix("[ ] ") {
+                ("☐", text)
+            } else if let Some(text) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                ("☑", text)
+            } else {
+                ("•", item)
+            };
+            lines.push(format!("{}{} {}", indent, mark, render_inline(text)));
+        } else {
+            lines.push(format!("{}{}", indent, render_inline(trimmed)));
+        }
+    }
+    lines
+}
+
+/// Drop inline emphasis and code markers; `[text](url)` becomes `text (url)`
+fn render_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let link = rest[start + 1..]
+            .split_once("](")
+            .and_then(|(label, after)| {
+                after
+                    .find(')')
+                    .map(|end| (label, &after[..end], &after[end + 1..]))
+            });
+        match link {
+            Some((label, url, after)) if !label.contains('[') => {
+                out.push_str(&rest[..start]);
+                out.push_str(&format!("{} ({})", label, url));
+                rest = after;
+            },
+            _ => {
+                out.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            },
+        }
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace("__", "").replace('`', "")
+}
+
+/// One line describing a task link: "[kind] title <url>" or "[kind] url",
+/// followed by "(dead: ...)" when the last check found it dead
+pub fn link_line(link: &crate::db::models::TaskLink) -> String {
+    let mut line = match &link.title {
+        Some(title) => format!("[{}] {} <{}>", link.kind, title, link.url),
+        None => format!("[{}] {}", link.kind, link.url),
+    };
+    if link.is_dead() {
+        let why = match (&link.last_error, link.last_status) {
+            (Some(error), _) => error.clone(),
+            (None, Some(status)) => status.to_string(),
+            (None, None) => String::new(),
+        };
+        line.push_str(&format!(" (dead: {})", why));
+    }
+    line
+}
+
+/// Format a minute count as e.g. "45m", "2h" or "1h 30m"
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
 /// Print task context in a human-friendly tree format
 pub fn print_task_context(ctx: &TaskContext) {
     let icon = status_icon(&ctx.task.status);
     println!("\n{} Task #{}: {}", icon, ctx.task.id, ctx.task.name);
     println!("Status: {}", ctx.task.status);
+    let estimate = &ctx.estimate;
+    if estimate.total_minutes > 0 {
+        print!(
+            "Estimate: {} remaining of {}",
+            format_minutes(estimate.remaining_minutes),
+            format_minutes(estimate.total_minutes)
+        );
+        if estimate.unestimated > 0 {
+            print!(" ({} task(s) unestimated)", estimate.unestimated);
+        }
+        println!();
+    }
 
     if let Some(spec) = &ctx.task.spec {
         println!("\nSpec:");
-        for line in spec.lines() {
+        for line in render_spec(spec) {
             println!("  {}", line);
         }
     }
 
+    if !ctx.links.is_empty() {
+        println!("\nLinks:");
+        for link in &ctx.links {
+            println!("  {}", link_line(link));
+        }
+    }
+
     // Print parent chain
     if !ctx.ancestors.is_empty() {
         println!("\nParent Chain:");
@@ -228,6 +412,39 @@ pub fn print_task_context(ctx: &TaskContext) {
     println!();
 }
 
+/// Print a subtree rollup under a task summary
+pub fn print_subtree_stats(stats: &SubtreeStats) {
+    print!(
+        "  Subtree: {}/{} done ({:.0}%)",
+        stats.done, stats.total_tasks, stats.completion_percent
+    );
+    if stats.doing > 0 {
+        print!(", {} doing", stats.doing);
+    }
+    if stats.review > 0 {
+        print!(", {} in review", stats.review);
+    }
+    println!();
+    if stats.estimate.total_minutes > 0 {
+        println!(
+            "  Subtree estimate: {} remaining of {}",
+            format_minutes(stats.estimate.remaining_minutes),
+            format_minutes(stats.estimate.total_minutes)
+        );
+    }
+    if stats.total_complexity > 0 {
+        println!("  Subtree complexity: {}", stats.total_complexity);
+    }
+    if !stats.blocking_chain.is_empty() {
+        let chain: Vec<String> = stats
+            .blocking_chain
+            .iter()
+            .map(|t| format!("#{}", t.id))
+            .collect();
+        println!("  Longest blocking chain: {}", chain.join(" ← "));
+    }
+}
+
 /// Print events summary (recent events with count)
 pub fn print_events_summary(summary: &EventsSummary) {
     println!("Events ({}):", summary.total_count);
@@ -241,6 +458,14 @@ pub fn print_events_summary(summary: &EventsSummary) {
     }
 }
 
+/// Print a completion outcome and its artifacts
+pub fn print_outcome(outcome: &CompletionOutcome) {
+    println!("  Outcome: {}", outcome.summary);
+    for artifact in &outcome.artifacts {
+        println!("    - {}", artifact);
+    }
+}
+
 /// Parse metadata key=value strings into a JSON object.
 /// "key=value" sets a key, "key=" deletes a key.
 pub fn parse_metadata(pairs: &[String]) -> Result<serde_json::Value> {
@@ -316,9 +541,55 @@ mod tests {
             active_form: None,
             owner: "human".to_string(),
             metadata: None,
+            sort_order: None,
+            estimate_minutes: None,
+            visibility: Default::default(),
+            blocked_reason: None,
+            checklist: None,
         }
     }
 
+    #[test]
+    fn test_format_minutes() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(120), "2h");
+        assert_eq!(format_minutes(90), "1h 30m");
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let lines = render_markdown(
+            "# Login\nUse **JWT**, see [RFC](https://x.dev/rfc).\n- [ ] expiry\n  - `refresh`\n```\n# not a heading\n```",
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "Login",
+                "=====",
+                "Use JWT, see RFC (https://x.dev/rfc).",
+                "☐ expiry",
+                "  • refresh",
+                "    # not a heading",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_spec_with_frontmatter() {
+        let lines = render_spec("---\nacceptance: [Works]\nestimate: 90\n---\n\nBody");
+        assert_eq!(
+            lines,
+            vec![
+                "Acceptance criteria:",
+                "  ☐ Works",
+                "Estimate: 1h 30m",
+                "",
+                "Body"
+            ]
+        );
+        assert_eq!(render_spec("Plain"), vec!["Plain"]);
+    }
+
     #[test]
     fn test_get_status_badge_done() {
         assert_eq!(get_status_badge("done"), "✓");
@@ -362,6 +633,8 @@ mod tests {
                 blocking_tasks: vec![],
                 blocked_by_tasks: vec![],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         // Should not panic and should execute all branches
@@ -382,6 +655,8 @@ mod tests {
                 blocking_tasks: vec![],
                 blocked_by_tasks: vec![],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         print_task_context(&ctx); // should not panic
@@ -402,6 +677,8 @@ mod tests {
                 blocking_tasks: vec![],
                 blocked_by_tasks: vec![],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         print_task_context(&ctx); // should not panic
@@ -421,6 +698,8 @@ mod tests {
                 blocking_tasks: vec![],
                 blocked_by_tasks: vec![],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         print_task_context(&ctx); // should not panic
@@ -441,6 +720,8 @@ mod tests {
                 blocking_tasks: vec![blocker],
                 blocked_by_tasks: vec![blocked],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         print_task_context(&ctx); // should not panic
@@ -460,6 +741,8 @@ mod tests {
                 blocking_tasks: vec![],
                 blocked_by_tasks: vec![],
             },
+            estimate: Default::default(),
+            links: Vec::new(),
         };
 
         print_task_context(&ctx); // should not panic
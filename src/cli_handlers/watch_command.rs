@@ -0,0 +1,68 @@
+use crate::error::Result;
+use crate::project::ProjectContext;
+use crate::watch::{Change, Watcher};
+use std::time::Duration;
+
+/// How often the database is checked for new writes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Handle `ie watch`: stream changes until interrupted
+pub async fn handle_watch(task: Option<i64>, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load().await?;
+    let mut watcher = Watcher::new(&ctx.pool, task).await?;
+
+    // data_version is per connection, so one connection is kept for polling
+    let mut conn = ctx.pool.acquire().await?;
+    let mut version = crate::db::data_version(&mut conn).await?;
+
+    if format != "json" {
+        match task {
+            Some(id) => eprintln!("Watching task #{} and its subtasks (Ctrl-C to stop)", id),
+            None => eprintln!("Watching all tasks and events (Ctrl-C to stop)"),
+        }
+    }
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let current = crate::db::data_version(&mut conn).await?;
+        if current == version {
+            continue;
+        }
+        version = current;
+
+        for change in watcher.poll().await? {
+            if format == "json" {
+                println!("{}", serde_json::to_string(&change)?);
+            } else {
+                print_change(&change);
+            }
+        }
+    }
+}
+
+fn print_change(change: &Change) {
+    let time = chrono::Local::now().format("%H:%M:%S");
+    match change {
+        Change::TaskCreated { task } => {
+            println!("{} task #{} created: {}", time, task.id, task.name)
+        },
+        Change::TaskUpdated { task, changed } => println!(
+            "{} task #{} updated ({}): {}",
+            time,
+            task.id,
+            changed.join(", "),
+            task.name
+        ),
+        Change::TaskDeleted { task_id, name } => {
+            println!("{} task #{} deleted: {}", time, task_id, name)
+        },
+        Change::EventAdded { event } => println!(
+            "{} event #{} on task #{} [{}]: {}",
+            time,
+            event.id,
+            event.task_id,
+            event.log_type,
+            event.discussion_data.lines().next().unwrap_or_default()
+        ),
+    }
+}
@@ -0,0 +1,135 @@
+use crate::cli::WorkspaceCommands;
+use crate::cli_handlers::{execute_under_focus, print_plan_result};
+use crate::error::Result;
+use crate::hooks::HookEvent;
+use crate::plan::PlanRequest;
+use crate::plan_workspace::{PlanWorkspace, PlanWorkspaceManager, WorkspaceDiff};
+use crate::project::ProjectContext;
+
+/// Handle `ie workspace` subcommands
+pub async fn handle_workspace_command(cmd: WorkspaceCommands) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let workspaces = PlanWorkspaceManager::new(&ctx.pool);
+
+    match cmd {
+        WorkspaceCommands::List { format } => {
+            let list = workspaces.list().await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&list)?);
+            } else if list.is_empty() {
+                println!("No workspaces. Save one with: ie plan --workspace <name> < plan.json");
+            } else {
+                for workspace in &list {
+                    print_workspace(workspace);
+                }
+            }
+        },
+
+        WorkspaceCommands::Show { name } => {
+            let workspace = workspaces.get(&name).await?;
+            println!("{}", serde_json::to_string_pretty(&workspace.plan)?);
+        },
+
+        WorkspaceCommands::Diff {
+            left,
+            right,
+            format,
+        } => {
+            let diff = workspaces.diff(&left, &right).await?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                print_diff(&diff);
+            }
+        },
+
+        WorkspaceCommands::Promote { name, format } => {
+            let workspace = workspaces.get(&name).await?;
+            let result = execute_under_focus(&ctx, &workspace.plan).await?;
+            if result.success {
+                workspaces.mark_promoted(&name).await?;
+            }
+            print_plan_result(&result, &format)?;
+            if result.success {
+                ProjectContext::configured_hooks()?.fire(HookEvent::PlanExecuted, &result)?;
+            }
+        },
+
+        WorkspaceCommands::Delete { name } => {
+            workspaces.delete(&name).await?;
+            println!("Deleted workspace '{}'", name);
+        },
+    }
+
+    Ok(())
+}
+
+/// `ie plan --workspace <name>`: keep the plan instead of executing it
+pub async fn save_plan_workspace(name: &str, request: &PlanRequest, format: &str) -> Result<()> {
+    let ctx = ProjectContext::load_or_init().await?;
+    let workspace = PlanWorkspaceManager::new(&ctx.pool)
+        .save(name, request)
+        .await?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&workspace)?);
+    } else {
+        println!(
+            "Saved {} tasks to workspace '{}' (task tree unchanged)",
+            workspace.task_count, workspace.name
+        );
+        println!("Compare with: ie workspace diff {}", workspace.name);
+        println!("Apply with:   ie workspace promote {}", workspace.name);
+    }
+    Ok(())
+}
+
+fn print_workspace(workspace: &PlanWorkspace) {
+    let promoted = workspace
+        .promoted_at
+        .map(|at| format!(", promoted {}", at.format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    println!(
+        "{}  {} tasks, updated {}{}",
+        workspace.name,
+        workspace.task_count,
+        workspace.updated_at.format("%Y-%m-%d %H:%M"),
+        promoted
+    );
+}
+
+fn print_diff(diff: &WorkspaceDiff) {
+    if diff.is_empty() {
+        println!(
+            "No differences between '{}' and '{}'",
+            diff.left, diff.right
+        );
+        return;
+    }
+    for (side, names) in [
+        (&diff.left, &diff.only_left),
+        (&diff.right, &diff.only_right),
+    ] {
+        if names.is_empty() {
+            continue;
+        }
+        println!("Only in {}:", side);
+        for name in names {
+            println!("  + {}", name);
+        }
+    }
+    if !diff.changed.is_empty() {
+        println!("Changed ({} → {}):", diff.left, diff.right);
+        for change in &diff.changed {
+            println!("  {}", change.name);
+            for field in &change.fields {
+                println!(
+                    "    {}: {} → {}",
+                    field.field,
+                    field.left.as_deref().unwrap_or("(unset)"),
+                    field.right.as_deref().unwrap_or("(unset)")
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Event compaction: roll old events of a task into a digest event
+//!
+//! Tasks with thousands of events overflow an agent's context on session
+//! restore. `EventManager::compact` keeps the most recent events as they are
+//! and replaces older ones with a single `digest` event, written by a
+//! `Summarizer`. The raw events move to `events_archive`, linked to their
+//! digest, so nothing is lost.
+//!
+//! Private events are never compacted, and digests are never compacted
+//! again; later compactions add another digest.
+
+use crate::db::models::{Event, EventVisibility};
+use crate::error::Result;
+use crate::llm::LlmClient;
+use serde::Serialize;
+use std::future::Future;
+
+/// Log type of the event that replaces compacted events
+pub const DIGEST_LOG_TYPE: &str = "digest";
+/// Events left untouched by `EventManager::compact`
+pub const DEFAULT_KEEP_RECENT: usize = 50;
+/// Fewer old events than this are not worth a digest
+pub const MIN_EVENTS_TO_COMPACT: usize = 20;
+
+/// Entries listed per event type in a rule-based digest
+const RULE_MAX_ITEMS: usize = 15;
+/// Characters kept of each listed event
+const RULE_ITEM_CHARS: usize = 160;
+/// Events (most recent) included in an LLM prompt
+const LLM_MAX_EVENTS: usize = 400;
+const LLM_EVENT_CHARS: usize = 400;
+
+/// Writes the text of a digest event
+pub trait Summarizer {
+    /// Summarize `events` (oldest first) of the task named `task_name`
+    fn summarize(
+        &self,
+        task_name: &str,
+        events: &[Event],
+    ) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// Outcome of `EventManager::compact`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CompactionResult {
+    pub task_id: i64,
+    /// Events moved to `events_archive`
+    pub archived: usize,
+    /// The new digest event; None when there was too little to compact
+    pub digest_event_id: Option<i64>,
+}
+
+/// Default summarizer: counts per type plus the latest decisions, blockers
+/// and milestones, which are what a resuming agent needs most
+pub struct RuleSummarizer;
+
+impl Summarizer for RuleSummarizer {
+    async fn summarize(&self, _task_name: &str, events: &[Event]) -> Result<String> {
+        Ok(rule_digest(events))
+    }
+}
+
+fn rule_digest(events: &[Event]) -> String {
+    let mut out = match (events.first(), events.last()) {
+        (Some(first), Some(last)) => format!(
+            "Digest of {} events ({} – {})\n",
+            events.len(),
+            first.timestamp.format("%Y-%m-%d %H:%M"),
+            last.timestamp.format("%Y-%m-%d %H:%M")
+        ),
+        _ => return "Digest of 0 events\n".to_string(),
+    };
+
+    for (log_type, title) in [
+        ("decision", "Decisions"),
+        ("blocker", "Blockers"),
+        ("milestone", "Milestones"),
+    ] {
+        let items: Vec<&Event> = events
+            .iter()
+            .filter(|e| e.log_type == log_type && e.visibility.is_normal())
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n{} ({}):\n", title, items.len()));
+        let skipped = items.len().saturating_sub(RULE_MAX_ITEMS);
+        if skipped > 0 {
+            out.push_str(&format!("- ... {} earlier\n", skipped));
+        }
+        for event in &items[skipped..] {
+            out.push_str(&format!("- {}\n", first_line(&event.discussion_data)));
+        }
+    }
+
+    let notes = events
+        .iter()
+        .filter(|e| e.log_type == "note" && e.visibility.is_normal())
+        .count();
+    let verbose = events
+        .iter()
+        .filter(|e| e.visibility == EventVisibility::Verbose)
+        .count();
+    let listed = events
+        .iter()
+        .filter(|e| {
+            e.visibility.is_normal()
+                && matches!(e.log_type.as_str(), "decision" | "blocker" | "milestone")
+        })
+        .count();
+    let other = events.len() - notes - verbose - listed;
+    if notes > 0 {
+        out.push_str(&format!("\nNotes: {}\n", notes));
+    }
+    if other > 0 {
+        out.push_str(&format!("Other events: {}\n", other));
+    }
+    if verbose > 0 {
+        out.push_str(&format!("Verbose events: {}\n", verbose));
+    }
+    out
+}
+
+fn first_line(text: &str) -> String {
+    let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    truncate(line.trim(), RULE_ITEM_CHARS)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars).collect();
+    cut.push('…');
+    cut
+}
+
+/// Summarizer backed by the configured LLM (see `crate::llm`)
+pub struct LlmSummarizer {
+    client: LlmClient,
+}
+
+impl LlmSummarizer {
+    pub fn new(client: LlmClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Summarizer for LlmSummarizer {
+    async fn summarize(&self, task_name: &str, events: &[Event]) -> Result<String> {
+        let skipped = events.len().saturating_sub(LLM_MAX_EVENTS);
+        let lines: Vec<String> = events[skipped..]
+            .iter()
+            .map(|e| {
+                format!(
+                    "[{}] {}: {}",
+                    e.timestamp.format("%Y-%m-%d %H:%M"),
+                    e.log_type,
+                    truncate(&e.discussion_data, LLM_EVENT_CHARS)
+                )
+            })
+            .collect();
+        let omitted = if skipped > 0 {
+            format!("({} older events omitted)\n", skipped)
+        } else {
+            String::new()
+        };
+        let prompt = format!(
+            "Summarize the history of the task \"{}\" for someone resuming the work.\n\
+             Keep every decision and its reason, open or resolved blockers, and milestones.\n\
+             Be concise; use markdown bullet points. Reply with the summary only.\n\n\
+             {}{}",
+            task_name,
+            omitted,
+            lines.join("\n")
+        );
+        let summary = self.client.chat(&prompt).await?;
+        Ok(format!(
+            "Digest of {} events (LLM summary)\n\n{}",
+            events.len(),
+            summary.trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn event(id: i64, log_type: &str, data: &str, visibility: EventVisibility) -> Event {
+        Event {
+            id,
+            task_id: 1,
+            timestamp: Utc::now(),
+            log_type: log_type.to_string(),
+            discussion_data: data.to_string(),
+            visibility,
+            actor: None,
+        }
+    }
+
+    #[test]
+    fn test_rule_digest() {
+        let mut events = vec![
+            event(
+                1,
+                "decision",
+                "Use SQLite\nbecause it is embedded",
+                EventVisibility::Normal,
+            ),
+            event(2, "blocker", "CI is red", EventVisibility::Normal),
+            event(3, "note", "tried X", EventVisibility::Normal),
+            event(4, "note", "scratch", EventVisibility::Verbose),
+        ];
+        for i in 0..20 {
+            events.push(event(
+                10 + i,
+                "decision",
+                &format!("d{}", i),
+                EventVisibility::Normal,
+            ));
+        }
+
+        let digest = rule_digest(&events);
+        assert!(digest.starts_with("Digest of 24 events"));
+        assert!(digest.contains("Decisions (21):\n- ... 6 earlier\n"));
+        assert!(!digest.contains("Use SQLite"));
+        assert!(digest.contains("- d19\n"));
+        assert!(digest.contains("Blockers (1):\n- CI is red\n"));
+        assert!(digest.contains("Notes: 1\n"));
+        assert!(digest.contains("Verbose events: 1\n"));
+        assert!(!digest.contains("Other events"));
+    }
+}
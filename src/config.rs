@@ -0,0 +1,908 @@
+//! Project configuration file (`.intent-engine/config.toml`)
+//!
+//! Per-project defaults that used to be hard-coded or tunable only through
+//! environment variables. Every key is optional; a missing file is the same
+//! as an empty one.
+//!
+//! ```toml
+//! [tasks]
+//! default_owner = "human"       # owner of `ie task create` without --owner
+//! default_sort = "focus_aware"  # id, priority, time or focus_aware
+//! capacity_limit = 3            # most tasks in doing across the project (see crate::wip)
+//! require_spec = true           # plans must give a spec to tasks they start
+//!
+//! [dashboard]
+//! port = 11391
+//!
+//! [notifications]
+//! webhooks = ["https://hooks.example.com/ie"]
+//!
+//! [events]                      # see crate::event_types
+//! types = ["experiment_result", "deploy"]
+//!
+//! [policy]                      # see crate::policy
+//! min_spec_chars = 50
+//! done_requires_event = ["milestone"]
+//! max_doing_per_parent = 3
+//!
+//! [wip.subtrees]                # see crate::wip
+//! 12 = 2                        # most tasks in doing below task #12
+//!
+//! [storage]
+//! backend = "sqlite"            # or "neo4j"/"postgres", in builds with that feature
+//!
+//! [sync]                        # see crate::sync
+//! remote = "s3://team-bucket/ie/web"
+//! conflict = "lamport"          # lamport, newest, local or remote
+//!
+//! [ui]                          # see crate::i18n
+//! locale = "zh"                 # en or zh; IE_LOCALE overrides it
+//!
+//! [hooks]                       # see crate::hooks
+//! task_completed = ["./scripts/on-done.sh"]
+//! timeout_secs = 10
+//! on_failure = "warn"           # warn, ignore or fail
+//!
+//! [report]
+//! pr_template = "docs/pr-template.md"  # layout of `ie report pr`, from the project root
+//! ```
+//!
+//! Keys may be written in any TOML form (`[hooks]` tables, dotted keys such
+//! as `hooks.task_created = [...]`, quoted keys). Unknown keys are errors,
+//! so a typo does not silently fall back to a default.
+//!
+//! The file sits next to the database, so any component holding a pool can
+//! find it with [`ProjectConfig::for_pool`].
+
+use crate::db::models::TaskSortBy;
+use crate::error::{IntentError, Result};
+use crate::event_types::{EventTypes, BUILTIN_EVENT_TYPES};
+use crate::hooks::{HookEvent, HookFailure};
+use crate::i18n::Locale;
+use crate::sync::{ConflictPolicy, Remote};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name inside `.intent-engine/`
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Default port of the Dashboard
+pub const DEFAULT_DASHBOARD_PORT: u16 = 11391;
+
+/// Typed contents of `config.toml`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProjectConfig {
+    pub tasks: TasksConfig,
+    pub dashboard: DashboardConfig,
+    pub notifications: NotificationsConfig,
+    pub events: EventsConfig,
+    pub policy: PolicyConfig,
+    pub storage: StorageConfig,
+    pub sync: SyncConfig,
+    pub ui: UiConfig,
+    pub hooks: HooksConfig,
+    pub report: ReportConfig,
+    pub wip: WipConfig,
+}
+
+/// `[tasks]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TasksConfig {
+    /// Owner of tasks created without an explicit owner
+    pub default_owner: String,
+    /// Order of task lists when no sort is requested
+    pub default_sort: TaskSortBy,
+    /// Most tasks in doing across the project, the WIP limit of `crate::wip`
+    pub capacity_limit: Option<usize>,
+    /// Whether plans must give a spec to tasks they start
+    pub require_spec: bool,
+}
+
+impl Default for TasksConfig {
+    fn default() -> Self {
+        Self {
+            default_owner: "human".to_string(),
+            default_sort: TaskSortBy::default(),
+            capacity_limit: None,
+            require_spec: true,
+        }
+    }
+}
+
+/// `[dashboard]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashboardConfig {
+    pub port: u16,
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_DASHBOARD_PORT,
+        }
+    }
+}
+
+/// `[notifications]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NotificationsConfig {
+    /// URLs that receive a JSON POST for every notification
+    pub webhooks: Vec<String>,
+}
+
+/// `[events]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EventsConfig {
+    /// Event types the project uses besides the built-in ones
+    pub types: Vec<String>,
+}
+
+/// `[policy]`, rules enforced on task transitions
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PolicyConfig {
+    /// Shortest spec a task may have when it starts
+    pub min_spec_chars: Option<usize>,
+    /// Event types of which a task needs at least one to be done
+    pub done_requires_event: Vec<String>,
+    /// Most subtasks of one parent in doing at a time
+    pub max_doing_per_parent: Option<usize>,
+}
+
+/// `[wip.subtrees]`, limits on tasks in doing below given tasks (see `crate::wip`)
+///
+/// The project-wide limit is `tasks.capacity_limit`, and the one on the
+/// children of any parent is `policy.max_doing_per_parent`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WipConfig {
+    /// Most tasks in doing below a task, by task ID
+    pub subtrees: BTreeMap<i64, usize>,
+}
+
+/// `[storage]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StorageConfig {
+    /// Where `ie` keeps tasks and events
+    pub backend: StorageBackend,
+}
+
+/// `[sync]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SyncConfig {
+    /// Hub of `ie sync` (directory, http(s):// URL or s3:// prefix)
+    pub remote: Option<String>,
+    /// Which side wins rows changed both locally and on the hub
+    pub conflict: ConflictPolicy,
+}
+
+/// `[ui]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UiConfig {
+    /// Language of CLI messages when `IE_LOCALE` is not set
+    pub locale: Option<Locale>,
+}
+
+/// `[hooks]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HooksConfig {
+    /// Shell commands per event, run in order
+    pub commands: BTreeMap<HookEvent, Vec<String>>,
+    /// Longest a hook may run before it is killed
+    pub timeout: Duration,
+    /// What a failing hook does to the command that triggered it
+    pub on_failure: HookFailure,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            commands: BTreeMap::new(),
+            timeout: crate::hooks::DEFAULT_TIMEOUT,
+            on_failure: HookFailure::default(),
+        }
+    }
+}
+
+impl HooksConfig {
+    /// Commands of `event`, empty when it has none
+    pub fn commands(&self, event: HookEvent) -> &[String] {
+        self.commands
+            .get(&event)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// `[report]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReportConfig {
+    /// Markdown template of `ie report pr`, relative to the project root
+    pub pr_template: Option<String>,
+}
+
+/// Storage backends `ie` can run against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    /// `.intent-engine/project.db`
+    #[default]
+    Sqlite,
+    /// The Neo4j server given by the `NEO4J_*` environment variables
+    Neo4j,
+    /// The shared database given by the `POSTGRES_*` environment variables
+    Postgres,
+}
+
+impl StorageBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StorageBackend::Sqlite => "sqlite",
+            StorageBackend::Neo4j => "neo4j",
+            StorageBackend::Postgres => "postgres",
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Path of the config file of the project whose `.intent-engine` dir is `intent_dir`
+    pub fn path_in(intent_dir: &Path) -> PathBuf {
+        intent_dir.join(CONFIG_FILE)
+    }
+
+    /// Path of the config file next to the database of `pool`; None for
+    /// in-memory databases
+    pub fn path_for_pool(pool: &SqlitePool) -> Option<PathBuf> {
+        let options = pool.connect_options();
+        let db_path = options.get_filename();
+        match db_path.parent() {
+            Some(dir) if db_path.is_absolute() => Some(Self::path_in(dir)),
+            _ => None,
+        }
+    }
+
+    /// Load the config next to the database of `pool`
+    ///
+    /// In-memory databases have no project directory and get the defaults.
+    pub fn for_pool(pool: &SqlitePool) -> Result<Self> {
+        match Self::path_for_pool(pool) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load the config of the project rooted at `root`
+    pub fn for_project_root(root: &Path) -> Result<Self> {
+        Self::load(&Self::path_in(&root.join(".intent-engine")))
+    }
+
+    /// Load a config file; a missing file gives the defaults
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| match e {
+                IntentError::InvalidInput(msg) => {
+                    IntentError::InvalidInput(format!("{}: {}", path.display(), msg))
+                },
+                other => other,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse the text of a config file
+    pub fn parse(text: &str) -> Result<Self> {
+        let document: toml::Table = text
+            .parse()
+            .map_err(|e: toml::de::Error| IntentError::InvalidInput(e.to_string()))?;
+        let mut entries = Vec::new();
+        flatten(String::new(), document, &mut entries);
+
+        let mut config = Self::default();
+        let mut wip_max_doing = None;
+        for (table, key, value) in entries {
+            let name = if table.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", table, key)
+            };
+            let at = |msg: String| IntentError::InvalidInput(format!("{}: {}", name, msg));
+            match (table.as_str(), key.as_str()) {
+                ("tasks", "default_owner") => {
+                    config.tasks.default_owner = string(value).map_err(at)?
+                },
+                ("tasks", "default_sort") => {
+                    config.tasks.default_sort =
+                        parse_sort(&string(value).map_err(at)?).ok_or_else(|| {
+                            at("must be id, priority, time or focus_aware".to_string())
+                        })?
+                },
+                ("tasks", "capacity_limit") => {
+                    let limit = integer(value).map_err(at)?;
+                    config.tasks.capacity_limit = Some(
+                        usize::try_from(limit)
+                            .ok()
+                            .filter(|l| *l > 0)
+                            .ok_or_else(|| at("must be at least 1".to_string()))?,
+                    );
+                },
+                ("tasks", "require_spec") => {
+                    config.tasks.require_spec = boolean(value).map_err(at)?
+                },
+                ("dashboard", "port") => {
+                    let port = integer(value).map_err(at)?;
+                    config.dashboard.port = u16::try_from(port)
+                        .ok()
+                        .filter(|p| *p > 0)
+                        .ok_or_else(|| at(format!("invalid port {}", port)))?;
+                },
+                ("notifications", "webhooks") => {
+                    let urls = strings(value).map_err(at)?;
+                    for url in &urls {
+                        match reqwest::Url::parse(url) {
+                            Ok(u) if matches!(u.scheme(), "http" | "https") => {},
+                            _ => return Err(at(format!("invalid webhook URL '{}'", url))),
+                        }
+                    }
+                    config.notifications.webhooks = urls;
+                },
+                ("policy", "min_spec_chars") => {
+                    let chars = integer(value).map_err(at)?;
+                    config.policy.min_spec_chars = Some(
+                        usize::try_from(chars)
+                            .map_err(|_| at("must not be negative".to_string()))?,
+                    );
+                },
+                ("events", "types") => {
+                    let types = strings(value).map_err(at)?;
+                    for (i, name) in types.iter().enumerate() {
+                        if !crate::event_types::is_valid_name(name) {
+                            return Err(at(format!(
+                                "invalid event type '{}': use lowercase letters, digits and _",
+                                name
+                            )));
+                        }
+                        if BUILTIN_EVENT_TYPES.contains(&name.as_str()) || types[..i].contains(name)
+                        {
+                            return Err(at(format!("event type '{}' is already defined", name)));
+                        }
+                    }
+                    config.events.types = types;
+                },
+                ("policy", "done_requires_event") => {
+                    // Checked against [events] types once the whole file is read
+                    config.policy.done_requires_event = strings(value).map_err(at)?;
+                },
+                ("policy", "max_doing_per_parent") => {
+                    let max = integer(value).map_err(at)?;
+                    config.policy.max_doing_per_parent = Some(
+                        usize::try_from(max)
+                            .ok()
+                            .filter(|m| *m > 0)
+                            .ok_or_else(|| at("must be at least 1".to_string()))?,
+                    );
+                },
+                // Deprecated spelling of tasks.capacity_limit
+                ("wip", "max_doing") => {
+                    let max = integer(value).map_err(at)?;
+                    wip_max_doing = Some(
+                        usize::try_from(max)
+                            .ok()
+                            .filter(|m| *m > 0)
+                            .ok_or_else(|| at("must be at least 1".to_string()))?,
+                    );
+                },
+                ("wip.subtrees", id) => {
+                    let id = id
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|id| *id > 0)
+                        .ok_or_else(|| at(format!("'{}' is not a task ID", key)))?;
+                    let max = integer(value).map_err(at)?;
+                    let max = usize::try_from(max)
+                        .ok()
+                        .filter(|m| *m > 0)
+                        .ok_or_else(|| at("must be at least 1".to_string()))?;
+                    config.wip.subtrees.insert(id, max);
+                },
+                ("storage", "backend") => {
+                    config.storage.backend = match string(value).map_err(at)?.as_str() {
+                        "sqlite" => StorageBackend::Sqlite,
+                        "neo4j" => StorageBackend::Neo4j,
+                        "postgres" => StorageBackend::Postgres,
+                        other => {
+                            return Err(at(format!(
+                                "unknown storage backend '{}', expected sqlite, neo4j or postgres",
+                                other
+                            )))
+                        },
+                    };
+                },
+                ("sync", "remote") => {
+                    let remote = string(value).map_err(at)?;
+                    Remote::parse(&remote).map_err(|e| at(e.to_string()))?;
+                    config.sync.remote = Some(remote);
+                },
+                ("sync", "conflict") => {
+                    config.sync.conflict = string(value)
+                        .map_err(at)?
+                        .parse()
+                        .map_err(|e: IntentError| at(e.to_string()))?;
+                },
+                ("ui", "locale") => {
+                    config.ui.locale = Some(
+                        string(value)
+                            .map_err(at)?
+                            .parse()
+                            .map_err(|e: IntentError| at(e.to_string()))?,
+                    );
+                },
+                ("report", "pr_template") => {
+                    let path = string(value).map_err(at)?;
+                    if path.trim().is_empty() {
+                        return Err(at("must not be empty".to_string()));
+                    }
+                    config.report.pr_template = Some(path);
+                },
+                ("hooks", "timeout_secs") => {
+                    let secs = integer(value).map_err(at)?;
+                    config.hooks.timeout = u64::try_from(secs)
+                        .ok()
+                        .filter(|s| *s > 0)
+                        .map(Duration::from_secs)
+                        .ok_or_else(|| at("must be at least 1".to_string()))?;
+                },
+                ("hooks", "on_failure") => {
+                    config.hooks.on_failure = string(value)
+                        .map_err(at)?
+                        .parse()
+                        .map_err(|e: IntentError| at(e.to_string()))?;
+                },
+                ("hooks", event) if event.parse::<HookEvent>().is_ok() => {
+                    let commands = strings(value).map_err(at)?;
+                    if commands.iter().any(|c| c.trim().is_empty()) {
+                        return Err(at("has an empty command".to_string()));
+                    }
+                    let event = event.parse::<HookEvent>().map_err(|e| at(e.to_string()))?;
+                    config.hooks.commands.insert(event, commands);
+                },
+                _ => {
+                    return Err(IntentError::InvalidInput(format!(
+                        "unknown setting '{}'",
+                        name
+                    )))
+                },
+            }
+        }
+
+        if let Some(max) = wip_max_doing {
+            tracing::warn!("wip.max_doing is deprecated; use tasks.capacity_limit");
+            match config.tasks.capacity_limit {
+                Some(limit) if limit != max => {
+                    return Err(IntentError::InvalidInput(format!(
+                        "wip.max_doing ({}) contradicts tasks.capacity_limit ({}); keep only tasks.capacity_limit",
+                        max, limit
+                    )))
+                },
+                _ => config.tasks.capacity_limit = Some(max),
+            }
+        }
+
+        let event_types = EventTypes::from_config(&config.events);
+        for log_type in &config.policy.done_requires_event {
+            if !event_types.contains(log_type) {
+                return Err(IntentError::InvalidInput(format!(
+                    "policy.done_requires_event: unknown event type '{}'",
+                    log_type
+                )));
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// A config file opened for editing; comments and layout survive a save
+pub struct ConfigEditor {
+    path: PathBuf,
+    document: toml_edit::DocumentMut,
+}
+
+impl ConfigEditor {
+    /// Open a config file; a missing file starts out empty
+    pub fn open(path: &Path) -> Result<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let document = text.parse().map_err(|e: toml_edit::TomlError| {
+            IntentError::InvalidInput(format!("{}: {}", path.display(), e))
+        })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            document,
+        })
+    }
+
+    /// Value of a dotted key such as `tasks.capacity_limit`, if set
+    pub fn get(&self, key: &str) -> Option<&toml_edit::Value> {
+        let value = self.lookup(key);
+        match key {
+            "tasks.capacity_limit" => value.or_else(|| self.lookup("wip.max_doing")),
+            _ => value,
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Option<&toml_edit::Value> {
+        let (table, key) = key.rsplit_once('.')?;
+        let mut item = self.document.as_item();
+        for part in table.split('.') {
+            item = item.get(part)?;
+        }
+        item.get(key)?.as_value()
+    }
+
+    /// Set a dotted key, or remove it with `None`
+    pub fn set(&mut self, key: &str, value: Option<toml_edit::Value>) -> Result<()> {
+        // The limit replaces its deprecated spelling
+        if key == "tasks.capacity_limit" {
+            self.set_key("wip.max_doing", None)?;
+        }
+        self.set_key(key, value)
+    }
+
+    fn set_key(&mut self, key: &str, value: Option<toml_edit::Value>) -> Result<()> {
+        let (table, name) = key
+            .rsplit_once('.')
+            .ok_or_else(|| IntentError::InvalidInput(format!("unknown setting '{}'", key)))?;
+        let mut item = self.document.as_item_mut();
+        for part in table.split('.') {
+            let parent = item.as_table_like_mut().ok_or_else(|| {
+                IntentError::InvalidInput(format!("{}: '{}' is not a table", key, part))
+            })?;
+            if parent.get(part).is_none() {
+                if value.is_none() {
+                    return Ok(());
+                }
+                parent.insert(part, toml_edit::table());
+            }
+            item = parent.get_mut(part).expect("inserted above");
+        }
+        let table = item.as_table_like_mut().ok_or_else(|| {
+            IntentError::InvalidInput(format!("{}: '{}' is not a table", key, table))
+        })?;
+        match value {
+            Some(value) => {
+                table.insert(name, toml_edit::Item::Value(value));
+            },
+            None => {
+                table.remove(name);
+            },
+        }
+        Ok(())
+    }
+
+    /// Validate the edited config and write it back
+    pub fn save(&self) -> Result<ProjectConfig> {
+        let text = self.document.to_string();
+        let config = ProjectConfig::parse(&text)?;
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut tmp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = self.path.with_file_name(tmp_name);
+        std::fs::write(&tmp, text)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(config)
+    }
+}
+
+/// Parse a sort name as accepted by `ie task list --sort`
+pub fn parse_sort(name: &str) -> Option<TaskSortBy> {
+    match name {
+        "id" => Some(TaskSortBy::Id),
+        "priority" => Some(TaskSortBy::Priority),
+        "time" => Some(TaskSortBy::Time),
+        "focus_aware" | "focus" => Some(TaskSortBy::FocusAware),
+        _ => None,
+    }
+}
+
+/// Collect the values of nested tables as (table, key, value), where `table`
+/// is the dotted path: `[wip.subtrees]` and `wip.subtrees.12 = 2` both give
+/// key `12` of table `wip.subtrees`
+fn flatten(table: String, values: toml::Table, entries: &mut Vec<(String, String, toml::Value)>) {
+    for (key, value) in values {
+        match value {
+            toml::Value::Table(inner) => {
+                let path = if table.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", table, key)
+                };
+                flatten(path, inner, entries);
+            },
+            value => entries.push((table.clone(), key, value)),
+        }
+    }
+}
+
+fn string(value: toml::Value) -> std::result::Result<String, String> {
+    match value {
+        toml::Value::String(s) => Ok(s),
+        _ => Err("must be a string".to_string()),
+    }
+}
+
+fn integer(value: toml::Value) -> std::result::Result<i64, String> {
+    match value {
+        toml::Value::Integer(n) => Ok(n),
+        _ => Err("must be an integer".to_string()),
+    }
+}
+
+fn boolean(value: toml::Value) -> std::result::Result<bool, String> {
+    match value {
+        toml::Value::Boolean(b) => Ok(b),
+        _ => Err("must be true or false".to_string()),
+    }
+}
+
+fn strings(value: toml::Value) -> std::result::Result<Vec<String>, String> {
+    let err = || "must be an array of strings".to_string();
+    match value {
+        toml::Value::Array(items) => items
+            .into_iter()
+            .map(|v| string(v).map_err(|_| err()))
+            .collect(),
+        _ => Err(err()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = ProjectConfig::parse(
+            r#"
+            # Project defaults
+            [tasks]
+            default_owner = "ai"   # agents create most tasks
+            default_sort = 'priority'
+            capacity_limit = 3
+            require_spec = false
+
+            [dashboard]
+            port = 12_000
+
+            [notifications]
+            webhooks = [
+                "https://hooks.example.com/a#frag",
+                "http://localhost:9000/ie",
+            ]
+
+            [events]
+            types = ["experiment_result", "deploy"]
+
+            [policy]
+            done_requires_event = ["deploy"]
+
+            [storage]
+            backend = "neo4j"
+
+            [sync]
+            remote = "s3://team-bucket/ie"
+            conflict = "newest"
+
+            [ui]
+            locale = "zh_CN"
+
+            [report]
+            pr_template = ".github/pr.md"
+
+            [hooks]
+            task_completed = ["./notify.sh", "make board"]
+            timeout_secs = 30
+            on_failure = "fail"
+
+            [wip.subtrees]
+            12 = 2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tasks.default_owner, "ai");
+        assert_eq!(config.tasks.default_sort, TaskSortBy::Priority);
+        assert_eq!(config.tasks.capacity_limit, Some(3));
+        assert!(!config.tasks.require_spec);
+        assert_eq!(config.dashboard.port, 12000);
+        assert_eq!(
+            config.notifications.webhooks,
+            vec![
+                "https://hooks.example.com/a#frag".to_string(),
+                "http://localhost:9000/ie".to_string()
+            ]
+        );
+        assert_eq!(config.events.types, vec!["experiment_result", "deploy"]);
+        assert_eq!(config.policy.done_requires_event, vec!["deploy"]);
+        assert_eq!(config.storage.backend, StorageBackend::Neo4j);
+        assert_eq!(config.sync.remote.as_deref(), Some("s3://team-bucket/ie"));
+        assert_eq!(config.sync.conflict, ConflictPolicy::Newest);
+        assert_eq!(config.ui.locale, Some(Locale::Zh));
+        assert_eq!(
+            config.hooks.commands(HookEvent::TaskCompleted),
+            ["./notify.sh", "make board"]
+        );
+        assert!(config.hooks.commands(HookEvent::TaskCreated).is_empty());
+        assert_eq!(config.hooks.timeout, Duration::from_secs(30));
+        assert_eq!(config.hooks.on_failure, HookFailure::Fail);
+        assert_eq!(config.report.pr_template.as_deref(), Some(".github/pr.md"));
+        assert_eq!(config.wip.subtrees, BTreeMap::from([(12, 2)]));
+    }
+
+    #[test]
+    fn test_parse_defaults_and_errors() {
+        assert_eq!(ProjectConfig::parse("").unwrap(), ProjectConfig::default());
+
+        for (text, expected) in [
+            (
+                "[tasks]\ndefault_sort = \"random\"",
+                "tasks.default_sort: must be",
+            ),
+            (
+                "[tasks]\ncapacity_limit = 0",
+                "tasks.capacity_limit: must be",
+            ),
+            (
+                "[task]\nrequire_spec = true",
+                "unknown setting 'task.require_spec'",
+            ),
+            ("[dashboard]\nport = \"80\"", "port: must be an integer"),
+            (
+                "[notifications]\nwebhooks = [\"ftp://x\"]",
+                "invalid webhook URL",
+            ),
+            (
+                "[tasks]\nrequire_spec = true\nrequire_spec = false",
+                "duplicate key",
+            ),
+            (
+                "[notifications]\nwebhooks = [\"a\"",
+                "TOML parse error at line 2",
+            ),
+            (
+                "[policy]\ndone_requires_event = [\"shipped\"]",
+                "unknown event type 'shipped'",
+            ),
+            ("[policy]\nmax_doing_per_parent = 0", "max_doing_per_parent"),
+            (
+                "[events]\ntypes = [\"Deploy\"]",
+                "invalid event type 'Deploy'",
+            ),
+            (
+                "[events]\ntypes = [\"note\"]",
+                "event type 'note' is already defined",
+            ),
+            (
+                "[storage]\nbackend = \"mysql\"",
+                "unknown storage backend 'mysql'",
+            ),
+            ("[sync]\nremote = \"ftp://hub\"", "Unsupported sync remote"),
+            (
+                "[sync]\nconflict = \"mine\"",
+                "Invalid conflict policy 'mine'",
+            ),
+            ("[ui]\nlocale = \"fr\"", "Invalid locale 'fr'"),
+            (
+                "[hooks]\ntask_deleted = [\"x\"]",
+                "unknown setting 'hooks.task_deleted'",
+            ),
+            ("[hooks]\ntask_created = \"x\"", "array of strings"),
+            ("[hooks]\ntimeout_secs = 0", "timeout_secs"),
+            ("[wip]\nmax_doing = 0", "wip.max_doing: must be at least 1"),
+            (
+                "[tasks]\ncapacity_limit = 3\n[wip]\nmax_doing = 5",
+                "keep only tasks.capacity_limit",
+            ),
+            ("[wip.subtrees]\nroot = 2", "'root' is not a task ID"),
+            (
+                "[wip.subtrees]\n12 = 0",
+                "wip.subtrees.12: must be at least 1",
+            ),
+            ("[wip..subtrees]\n12 = 1", "TOML parse error at line 1"),
+            (
+                "[hooks]\non_failure = \"abort\"",
+                "Invalid hook failure policy 'abort'",
+            ),
+        ] {
+            let err = ProjectConfig::parse(text).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", text, err);
+        }
+    }
+
+    #[test]
+    fn test_parse_any_toml_form() {
+        let config = ProjectConfig::parse(
+            r#"
+            hooks.task_created = ["./created.sh"]
+            hooks.timeout_secs = 5
+            wip = { max_doing = 4, subtrees = { "12" = 2 } }
+
+            [report]
+            pr_template = """
+docs/pr.md"""
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.hooks.commands(HookEvent::TaskCreated),
+            ["./created.sh"]
+        );
+        assert_eq!(config.hooks.timeout, Duration::from_secs(5));
+        // The deprecated spelling of the project-wide limit still works
+        assert_eq!(config.tasks.capacity_limit, Some(4));
+        assert_eq!(config.wip.subtrees, BTreeMap::from([(12, 2)]));
+        assert_eq!(config.report.pr_template.as_deref(), Some("docs/pr.md"));
+    }
+
+    #[tokio::test]
+    async fn test_config_drives_task_defaults() {
+        use crate::plan::{PlanExecutor, PlanRequest};
+        use crate::tasks::TaskManager;
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine").join(CONFIG_FILE),
+            "[tasks]\ndefault_owner = \"ai\"\ndefault_sort = \"id\"\ncapacity_limit = 1\nrequire_spec = false\n",
+        )
+        .unwrap();
+        assert_eq!(
+            ProjectConfig::for_pool(pool).unwrap().tasks.capacity_limit,
+            Some(1)
+        );
+
+        let tasks = TaskManager::new(pool);
+        let first = tasks
+            .add_task("First", None, None, None, Some(4), None)
+            .await
+            .unwrap();
+        assert_eq!(first.owner, "ai");
+        tasks
+            .add_task("Second", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        let listed = tasks
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(listed.tasks[0].id, first.id);
+
+        // No spec needed to start a task from a plan
+        let request: PlanRequest =
+            serde_json::from_str(r#"{"tasks": [{"name": "Started", "status": "doing"}]}"#).unwrap();
+        assert!(
+            PlanExecutor::new(pool)
+                .execute(&request)
+                .await
+                .unwrap()
+                .success
+        );
+
+        // One task is doing already, so claiming starts nothing new
+        assert!(tasks.claim_next("agent").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_missing_file_gives_defaults() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            ProjectConfig::for_project_root(dir.path()).unwrap(),
+            ProjectConfig::default()
+        );
+    }
+}
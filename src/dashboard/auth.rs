@@ -0,0 +1,510 @@
+//! Dashboard access tokens
+//!
+//! Tokens are managed with `ie dashboard token create/list/revoke` and stored
+//! (hashed) in `~/.intent-engine/dashboard-tokens.json`. Authentication is
+//! enforced as soon as at least one token exists; with no tokens the Dashboard
+//! stays open as before. A store file that cannot be read or parsed keeps the
+//! Dashboard from starting, and refuses every request if it breaks later.
+//!
+//! Clients present a token via `Authorization: Bearer <token>`, or via
+//! `?token=<token>` which also sets an HttpOnly cookie so the browser UI and
+//! its WebSocket keep working after the first visit.
+//!
+//! The store also holds a local CLI secret (readable only by the owning user)
+//! that the `ie` binary uses for its own calls to the Dashboard.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+const GLOBAL_DIR: &str = ".intent-engine";
+const TOKENS_FILE: &str = "dashboard-tokens.json";
+
+/// Cookie set after a successful `?token=` login
+pub const AUTH_COOKIE: &str = "ie_dashboard_token";
+
+/// Prefix for generated tokens (makes them easy to spot in logs and configs)
+const TOKEN_PREFIX: &str = "ie_";
+
+/// A named access token (only the hash is persisted)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenEntry {
+    /// Short identifier used for revocation
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// SHA-256 of the token, hex encoded
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Persistent token store
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TokenStore {
+    /// Secret used by the local `ie` CLI when talking to the Dashboard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_token: Option<String>,
+    #[serde(default)]
+    pub tokens: Vec<TokenEntry>,
+}
+
+impl TokenStore {
+    /// Default location of the token store
+    pub fn store_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(GLOBAL_DIR).join(TOKENS_FILE))
+    }
+
+    /// Load the store from the default location
+    pub fn load() -> std::io::Result<Self> {
+        match Self::store_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load the store from a specific file
+    ///
+    /// Only a missing file means no tokens. A file that cannot be read or
+    /// parsed is an error, so that a damaged store never turns auth off.
+    pub fn load_from(path: &Path) -> std::io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&content).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid Dashboard token store {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Save the store to the default location
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = Self::store_path() else {
+            return Ok(());
+        };
+        self.save_to(&path)
+    }
+
+    /// Save the store to a specific file, readable only by the current user
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+
+        // Write a temporary file that is private from the start, then move
+        // it into place, so the plaintext CLI token is never readable by
+        // other users, not even briefly
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+        if let Err(e) = std::fs::remove_file(&tmp) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(e);
+            }
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp)?;
+        std::io::Write::write_all(&mut file, content.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Whether the Dashboard should require a token
+    pub fn auth_enabled(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Create a new token and return `(entry, plaintext_token)`
+    ///
+    /// The plaintext token is only available here; it is never stored.
+    pub fn create_token(&mut self, name: Option<String>) -> (TokenEntry, String) {
+        let token = generate_token();
+        let entry = TokenEntry {
+            id: generate_id(),
+            name,
+            token_hash: hash_token(&token),
+            created_at: Utc::now(),
+        };
+        self.tokens.push(entry.clone());
+
+        if self.cli_token.is_none() {
+            self.cli_token = Some(generate_token());
+        }
+
+        (entry, token)
+    }
+
+    /// Revoke a token by id; returns whether it existed
+    pub fn revoke_token(&mut self, id: &str) -> bool {
+        let initial_len = self.tokens.len();
+        self.tokens.retain(|t| t.id != id);
+        let removed = self.tokens.len() < initial_len;
+
+        // Without user tokens auth is off, so the CLI secret is no longer needed
+        if self.tokens.is_empty() {
+            self.cli_token = None;
+        }
+
+        removed
+    }
+
+    /// Check a presented token against the store
+    pub fn validate(&self, token: &str) -> bool {
+        if let Some(cli) = &self.cli_token {
+            if constant_time_eq(cli.as_bytes(), token.as_bytes()) {
+                return true;
+            }
+        }
+
+        let presented = hash_token(token);
+        self.tokens
+            .iter()
+            .any(|t| constant_time_eq(t.token_hash.as_bytes(), presented.as_bytes()))
+    }
+}
+
+/// Token the local CLI should send to the Dashboard, if auth is enabled
+pub fn cli_token() -> Option<String> {
+    let store = match TokenStore::load() {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::warn!(error = %e, "Cannot read the Dashboard token store");
+            return None;
+        },
+    };
+    if store.auth_enabled() {
+        store.cli_token
+    } else {
+        None
+    }
+}
+
+/// Attach the CLI token to a request when Dashboard auth is enabled
+pub fn with_cli_token(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match cli_token() {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+fn generate_token() -> String {
+    let bytes: [u8; 24] = rand::random();
+    format!("{}{}", TOKEN_PREFIX, to_hex(&bytes))
+}
+
+fn generate_id() -> String {
+    let bytes: [u8; 4] = rand::random();
+    to_hex(&bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// Middleware
+// ============================================================================
+
+/// State for the auth middleware (token store location)
+#[derive(Clone, Debug, Default)]
+pub struct AuthState {
+    /// Token store file; `None` uses the default location
+    pub store_path: Option<PathBuf>,
+}
+
+impl AuthState {
+    fn load_store(&self) -> std::io::Result<TokenStore> {
+        match &self.store_path {
+            Some(path) => TokenStore::load_from(path),
+            None => TokenStore::load(),
+        }
+    }
+}
+
+/// Paths reachable without a token (liveness checks only)
+fn is_public_path(path: &str) -> bool {
+    path == "/api/health"
+}
+
+/// Extract the token from the Authorization header or the auth cookie
+fn token_from_headers(headers: &HeaderMap) -> Option<String> {
+    if let Some(value) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+    {
+        if let Some(token) = value.strip_prefix("Bearer ") {
+            return Some(token.trim().to_string());
+        }
+    }
+
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == AUTH_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Extract the token from a `token=` query parameter
+fn token_from_query(query: Option<&str>) -> Option<String> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == "token")
+        .map(|(_, value)| value.to_string())
+}
+
+/// Reject requests without a valid token (HTTP routes and WebSocket upgrades)
+pub async fn require_token(
+    State(auth): State<AuthState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_public_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+    // A store that cannot be read locks everyone out rather than nobody
+    let store = match auth.load_store() {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!(error = %e, "Rejecting Dashboard request");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "code": "AUTH_STORE_UNREADABLE",
+                    "error_code": "IE0110",
+                    "message": format!("Dashboard token store unreadable: {}", e),
+                })),
+            )
+                .into_response();
+        },
+    };
+    if !store.auth_enabled() {
+        return next.run(request).await;
+    }
+
+    if let Some(token) = token_from_headers(request.headers()) {
+        if store.validate(&token) {
+            return next.run(request).await;
+        }
+    }
+
+    // Query token: accept and remember it in a cookie for the browser UI
+    if let Some(token) = token_from_query(request.uri().query()) {
+        if store.validate(&token) {
+            let mut response = next.run(request).await;
+            let cookie = format!(
+                "{}={}; Path=/; HttpOnly; SameSite=Strict",
+                AUTH_COOKIE, token
+            );
+            if let Ok(value) = cookie.parse() {
+                response.headers_mut().append(header::SET_COOKIE, value);
+            }
+            return response;
+        }
+    }
+
+    tracing::debug!(path = %request.uri().path(), "Rejected unauthenticated Dashboard request");
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(serde_json::json!({
+            "code": "UNAUTHORIZED",
+            "error_code": "IE0108",
+            "message": "Dashboard access token required. Create one with 'ie dashboard token create' and pass it as 'Authorization: Bearer <token>' or '?token=<token>'",
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request as HttpRequest, routing::get, Router};
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_create_and_validate_token() {
+        let mut store = TokenStore::default();
+        assert!(!store.auth_enabled());
+
+        let (entry, token) = store.create_token(Some("laptop".to_string()));
+        assert!(store.auth_enabled());
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_ne!(entry.token_hash, token);
+        assert!(store.validate(&token));
+        assert!(!store.validate("ie_wrong"));
+
+        // CLI secret is created alongside the first token
+        let cli = store.cli_token.clone().unwrap();
+        assert!(store.validate(&cli));
+    }
+
+    #[test]
+    fn test_revoke_token() {
+        let mut store = TokenStore::default();
+        let (entry, token) = store.create_token(None);
+
+        assert!(!store.revoke_token("missing"));
+        assert!(store.revoke_token(&entry.id));
+        assert!(!store.validate(&token));
+        assert!(!store.auth_enabled());
+        assert!(store.cli_token.is_none());
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tokens.json");
+
+        let mut store = TokenStore::default();
+        let (_, token) = store.create_token(Some("ci".to_string()));
+        store.save_to(&path).unwrap();
+
+        let loaded = TokenStore::load_from(&path).unwrap();
+        assert_eq!(loaded.tokens.len(), 1);
+        assert!(loaded.validate(&token));
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(&token));
+
+        // Saving over a file others could read leaves a private one
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            store.save_to(&path).unwrap();
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            assert!(!dir.path().join("tokens.json.tmp").exists());
+        }
+    }
+
+    #[test]
+    fn test_token_extraction() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer abc".parse().unwrap());
+        assert_eq!(token_from_headers(&headers), Some("abc".to_string()));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            format!("theme=dark; {}=xyz", AUTH_COOKIE).parse().unwrap(),
+        );
+        assert_eq!(token_from_headers(&headers), Some("xyz".to_string()));
+
+        assert_eq!(
+            token_from_query(Some("a=1&token=t0k")),
+            Some("t0k".to_string())
+        );
+        assert_eq!(token_from_query(Some("a=1")), None);
+        assert_eq!(token_from_query(None), None);
+    }
+
+    fn test_router(store_path: PathBuf) -> Router {
+        Router::new()
+            .route("/api/health", get(|| async { "ok" }))
+            .route("/api/tasks", get(|| async { "tasks" }))
+            .layer(axum::middleware::from_fn_with_state(
+                AuthState {
+                    store_path: Some(store_path),
+                },
+                require_token,
+            ))
+    }
+
+    async fn status_of(router: Router, uri: &str, bearer: Option<&str>) -> Response {
+        let mut request = HttpRequest::builder().uri(uri);
+        if let Some(token) = bearer {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_open_without_tokens() {
+        let dir = TempDir::new().unwrap();
+        let router = test_router(dir.path().join("tokens.json"));
+
+        let response = status_of(router, "/api/tasks", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_store_fails_closed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tokens.json");
+        std::fs::write(&path, "{\"tokens\": [").unwrap();
+        assert!(TokenStore::load_from(&path).is_err());
+
+        let router = test_router(path);
+        let response = status_of(router.clone(), "/api/tasks", None).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let response = status_of(router, "/api/health", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_enforces_tokens() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tokens.json");
+        let mut store = TokenStore::default();
+        let (_, token) = store.create_token(None);
+        store.save_to(&path).unwrap();
+
+        let router = test_router(path);
+
+        let response = status_of(router.clone(), "/api/tasks", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = status_of(router.clone(), "/api/tasks", Some("ie_bad")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = status_of(router.clone(), "/api/tasks", Some(&token)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Health stays public for liveness checks
+        let response = status_of(router.clone(), "/api/health", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Query token sets the auth cookie
+        let response = status_of(router, &format!("/api/tasks?token={}", token), None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookie = response.headers().get(header::SET_COOKIE).unwrap();
+        assert!(cookie.to_str().unwrap().starts_with(AUTH_COOKIE));
+    }
+}
@@ -0,0 +1,271 @@
+//! In-process cache for the Dashboard's hot read paths
+//!
+//! Every refresh of the Dashboard asks for the same task details, contexts,
+//! subtree rollups and project overviews, and each request opens its own
+//! pool. Results are kept per project until something writes: any mutating
+//! API request (which includes CLI notifications) clears the cache, and
+//! entries also expire after [`CACHE_TTL`] as a safety net for writes the
+//! Dashboard never hears about.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{extract::Request, extract::State, http::Method, middleware::Next, response::Response};
+
+/// Longest time an entry is served without a write clearing it
+pub const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Entries kept before the cache starts over
+const MAX_ENTRIES: usize = 10_000;
+
+/// A cached read, scoped to one project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedQuery {
+    Task(i64),
+    TaskContext(i64),
+    SubtreeStats(i64),
+    Overview,
+}
+
+impl CachedQuery {
+    /// Label used on the `/metrics` counters
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Task(_) => "task",
+            Self::TaskContext(_) => "task_context",
+            Self::SubtreeStats(_) => "subtree_stats",
+            Self::Overview => "overview",
+        }
+    }
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<(PathBuf, CachedQuery), Entry>,
+    /// Bumped on every invalidation, so loads that raced a write are not stored
+    generation: u64,
+    /// (hits, misses) by query kind
+    counters: BTreeMap<&'static str, (u64, u64)>,
+}
+
+/// Hit and miss counts of one query kind
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheCounters {
+    pub kind: &'static str,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Query results shared by all Dashboard handlers
+#[derive(Clone, Default)]
+pub struct QueryCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached result of `query`, or run `load` and keep its result
+    ///
+    /// Errors are never cached.
+    pub async fn get_or_load<T, E, Fut>(
+        &self,
+        project: &Path,
+        query: CachedQuery,
+        load: impl FnOnce() -> Fut,
+    ) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let key = (project.to_path_buf(), query);
+        let generation = {
+            let mut inner = self.lock();
+            let hit = inner
+                .entries
+                .get(&key)
+                .filter(|entry| entry.stored_at.elapsed() < CACHE_TTL)
+                .and_then(|entry| entry.value.downcast_ref::<T>().cloned());
+            let counters = inner.counters.entry(query.kind()).or_default();
+            match hit {
+                Some(value) => {
+                    counters.0 += 1;
+                    return Ok(value);
+                },
+                None => counters.1 += 1,
+            }
+            inner.generation
+        };
+
+        let value = load().await?;
+
+        let mut inner = self.lock();
+        if inner.generation == generation {
+            if inner.entries.len() >= MAX_ENTRIES {
+                inner.entries.clear();
+            }
+            inner.entries.insert(
+                key,
+                Entry {
+                    value: Arc::new(value.clone()),
+                    stored_at: Instant::now(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Drop every cached result
+    pub fn invalidate_all(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.generation += 1;
+    }
+
+    /// Number of cached results (expired ones included until the next write)
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hits and misses so far, by query kind
+    pub fn counters(&self) -> Vec<CacheCounters> {
+        self.lock()
+            .counters
+            .iter()
+            .map(|(kind, (hits, misses))| CacheCounters {
+                kind,
+                hits: *hits,
+                misses: *misses,
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Clear the cache around every request that may have written something
+///
+/// Before, because a CLI notification reports a write that already happened
+/// and the UI refetches as soon as the handler broadcasts it; after, to drop
+/// anything read while the handler was still writing.
+pub async fn invalidate_on_write(
+    State(cache): State<QueryCache>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let writes = !matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    );
+    if writes {
+        cache.invalidate_all();
+    }
+    let response = next.run(request).await;
+    if writes {
+        cache.invalidate_all();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn load(calls: &Mutex<u32>, value: i64) -> Result<i64, String> {
+        *calls.lock().unwrap() += 1;
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn test_hits_until_invalidated() {
+        let cache = QueryCache::new();
+        let calls = Mutex::new(0);
+        let project = Path::new("/p");
+
+        for _ in 0..3 {
+            let value = cache
+                .get_or_load(project, CachedQuery::Task(1), || load(&calls, 7))
+                .await
+                .unwrap();
+            assert_eq!(value, 7);
+        }
+        assert_eq!(*calls.lock().unwrap(), 1);
+
+        // Other projects and queries are cached separately
+        cache
+            .get_or_load(Path::new("/q"), CachedQuery::Task(1), || load(&calls, 8))
+            .await
+            .unwrap();
+        cache
+            .get_or_load(project, CachedQuery::TaskContext(1), || load(&calls, 9))
+            .await
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), 3);
+        assert_eq!(cache.len(), 3);
+
+        cache.invalidate_all();
+        assert!(cache.is_empty());
+        cache
+            .get_or_load(project, CachedQuery::Task(1), || load(&calls, 10))
+            .await
+            .unwrap();
+        assert_eq!(*calls.lock().unwrap(), 4);
+
+        assert_eq!(
+            cache.counters(),
+            vec![
+                CacheCounters {
+                    kind: "task",
+                    hits: 2,
+                    misses: 3,
+                },
+                CacheCounters {
+                    kind: "task_context",
+                    hits: 0,
+                    misses: 1,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_racing_loads_are_not_stored() {
+        let cache = QueryCache::new();
+        let project = Path::new("/p");
+
+        let failed: Result<i64, String> = cache
+            .get_or_load(project, CachedQuery::Overview, || async {
+                Err("locked".to_string())
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(cache.is_empty());
+
+        // A write landing while the read runs makes its result stale
+        let result: Result<i64, String> = cache
+            .get_or_load(project, CachedQuery::Overview, || async {
+                cache.invalidate_all();
+                Ok(1)
+            })
+            .await;
+        assert_eq!(result, Ok(1));
+        assert!(cache.is_empty());
+    }
+}
@@ -32,6 +32,12 @@ pub enum NotificationMessage {
         /// Project path that sent this notification
         project_path: Option<String>,
     },
+    /// Tasks lost their last unfinished blocker
+    TasksUnblocked {
+        tasks: Vec<crate::db::models::UnblockedTask>,
+        /// Project path that sent this notification
+        project_path: Option<String>,
+    },
 }
 
 /// CLI Notifier for sending notifications to Dashboard
@@ -65,7 +71,11 @@ impl CliNotifier {
     }
 
     /// Send a notification to Dashboard (fire-and-forget, non-blocking)
-    pub async fn notify(&self, message: NotificationMessage) {
+    ///
+    /// Returns false when the Dashboard missed it (unreachable, or no UI
+    /// client for the project), so the caller can queue it. Disabled
+    /// notifications count as handled: nothing is owed to the Dashboard.
+    pub async fn notify(&self, message: NotificationMessage) -> bool {
         // Check: Environment variable to disable notifications
         if std::env::var("IE_DISABLE_DASHBOARD_NOTIFICATIONS")
             .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
@@ -74,16 +84,33 @@ impl CliNotifier {
             tracing::debug!(
                 "Dashboard notifications disabled via IE_DISABLE_DASHBOARD_NOTIFICATIONS"
             );
-            return; // Skip all notification logic
+            return true; // Skip all notification logic
         }
 
         let url = format!("{}/api/internal/cli-notify", self.base_url);
 
         // Send notification - short timeout to avoid blocking CLI for too long
-        if let Err(e) = self.client.post(&url).json(&message).send().await {
-            tracing::debug!("Failed to notify Dashboard: {}", e);
-            // Silently ignore errors - Dashboard might not be running
-        }
+        let request = super::auth::with_cli_token(self.client.post(&url));
+        let response = match request.json(&message).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::debug!("Dashboard rejected notification: {}", response.status());
+                return false;
+            },
+            Err(e) => {
+                tracing::debug!("Failed to notify Dashboard: {}", e);
+                // Silently ignore errors - Dashboard might not be running
+                return false;
+            },
+        };
+
+        // Dashboards from before the offline queue do not report delivery
+        response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body.get("delivered").and_then(|d| d.as_bool()))
+            .unwrap_or(true)
     }
 
     /// Notify about task change
@@ -92,13 +119,13 @@ impl CliNotifier {
         task_id: Option<i64>,
         operation: &str,
         project_path: Option<String>,
-    ) {
+    ) -> bool {
         self.notify(NotificationMessage::TaskChanged {
             task_id,
             operation: operation.to_string(),
             project_path,
         })
-        .await;
+        .await
     }
 
     /// Notify about event added
@@ -107,13 +134,26 @@ impl CliNotifier {
         task_id: i64,
         event_id: i64,
         project_path: Option<String>,
-    ) {
+    ) -> bool {
         self.notify(NotificationMessage::EventAdded {
             task_id,
             event_id,
             project_path,
         })
-        .await;
+        .await
+    }
+
+    /// Notify about tasks that became ready to start
+    pub async fn notify_tasks_unblocked(
+        &self,
+        tasks: Vec<crate::db::models::UnblockedTask>,
+        project_path: Option<String>,
+    ) -> bool {
+        self.notify(NotificationMessage::TasksUnblocked {
+            tasks,
+            project_path,
+        })
+        .await
     }
 
     /// Notify about workspace change
@@ -121,12 +161,12 @@ impl CliNotifier {
         &self,
         current_task_id: Option<i64>,
         project_path: Option<String>,
-    ) {
+    ) -> bool {
         self.notify(NotificationMessage::WorkspaceChanged {
             current_task_id,
             project_path,
         })
-        .await;
+        .await
     }
 }
 
@@ -156,11 +196,11 @@ mod tests {
     async fn test_notify_non_blocking() {
         // This should not panic even if Dashboard is not running
         let notifier = CliNotifier::with_port(65000); // Port not in use
-        notifier
+        let delivered = notifier
             .notify_task_changed(Some(42), "created", Some("/test/path".to_string()))
             .await;
 
-        // Should return immediately (fire-and-forget)
-        // No assertions needed - test passes if no panic
+        // Should return immediately (fire-and-forget), reporting the miss
+        assert!(!delivered);
     }
 }
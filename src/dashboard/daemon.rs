@@ -0,0 +1,250 @@
+//! Supervision of the Dashboard process
+//!
+//! A running Dashboard records itself in `~/.intent-engine/dashboard-<port>.pid`
+//! (JSON: pid, port, project, start time) and removes the file when it shuts
+//! down cleanly. A file left behind by a crashed or killed process is stale:
+//! `ie dashboard start/status/stop` notice that the process is gone and remove
+//! it, instead of trusting it.
+//!
+//! [`supervise`] runs the server and starts it again when it panics or fails,
+//! waiting 1s, 2s, 4s … up to a minute between attempts. A run that lasted
+//! [`STABLE_AFTER`] resets the delay. A clean shutdown (`ie dashboard stop`)
+//! ends supervision.
+
+use crate::error::{IntentError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+const GLOBAL_DIR: &str = ".intent-engine";
+
+/// First delay before restarting a failed server
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A server that ran this long before failing restarts after [`INITIAL_BACKOFF`] again
+pub const STABLE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// A first run failing sooner than this never came up at all
+const STARTUP_GRACE: Duration = Duration::from_secs(5);
+
+/// Times [`supervise`] restarted the server in this process
+static RESTARTS: AtomicU32 = AtomicU32::new(0);
+
+pub fn restart_count() -> u32 {
+    RESTARTS.load(Ordering::Relaxed)
+}
+
+/// Contents of the PID file of a running Dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PidFile {
+    pub pid: u32,
+    pub port: u16,
+    pub project_path: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// What the PID file of a port says about its Dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub enum PidState {
+    /// No PID file
+    Missing,
+    /// The recorded process exists
+    Alive(PidFile),
+    /// The recorded process is gone; the file was removed
+    Stale(PidFile),
+}
+
+impl PidFile {
+    /// PID file of the Dashboard on `port`
+    pub fn path(port: u16) -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(GLOBAL_DIR).join(format!("dashboard-{}.pid", port)))
+    }
+
+    /// Record this process as the Dashboard on `port`
+    pub fn write(port: u16, project_path: &std::path::Path) -> std::io::Result<()> {
+        let Some(path) = Self::path(port) else {
+            return Ok(());
+        };
+        let record = Self {
+            pid: std::process::id(),
+            port,
+            project_path: project_path.display().to_string(),
+            started_at: Utc::now(),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&record)?)
+    }
+
+    /// The PID file of `port`, if present and readable
+    pub fn read(port: u16) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::path(port)?).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Remove the PID file of `port` if it records this process
+    pub fn remove_own(port: u16) {
+        if Self::read(port).is_some_and(|record| record.pid == std::process::id()) {
+            Self::remove(port);
+        }
+    }
+
+    fn remove(port: u16) {
+        if let Some(path) = Self::path(port) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::debug!(path = %path.display(), error = %e, "No PID file removed");
+            }
+        }
+    }
+
+    /// Check the PID file of `port`, removing it when its process is gone
+    pub fn inspect(port: u16) -> PidState {
+        match Self::read(port) {
+            None => PidState::Missing,
+            Some(record) if process_exists(record.pid) => PidState::Alive(record),
+            Some(record) => {
+                Self::remove(port);
+                PidState::Stale(record)
+            },
+        }
+    }
+}
+
+#[cfg(unix)]
+fn process_exists(pid: u32) -> bool {
+    crate::plan_journal::process_alive(i64::from(pid))
+}
+
+#[cfg(windows)]
+fn process_exists(pid: u32) -> bool {
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetExitCodeProcess, OpenProcess};
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+
+    const STILL_ACTIVE: u32 = 259;
+    // SAFETY: the handle is checked for null and closed before returning
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return false;
+        }
+        let mut exit_code = 0;
+        let queried = GetExitCodeProcess(handle, &mut exit_code);
+        CloseHandle(handle);
+        queried != 0 && exit_code == STILL_ACTIVE
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_exists(_pid: u32) -> bool {
+    true
+}
+
+/// Ask the process of a Dashboard that does not answer HTTP to terminate
+#[cfg(unix)]
+pub fn terminate(pid: u32) -> Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    let pid = i32::try_from(pid)
+        .map_err(|_| IntentError::InvalidInput(format!("Invalid PID {}", pid)))?;
+    kill(Pid::from_raw(pid), Signal::SIGTERM).map_err(|e| {
+        IntentError::OtherError(anyhow::anyhow!("Failed to signal process {}: {}", pid, e))
+    })
+}
+
+#[cfg(not(unix))]
+pub fn terminate(pid: u32) -> Result<()> {
+    Err(IntentError::InvalidInput(format!(
+        "Stop process {} manually: taskkill /PID {} /F",
+        pid, pid
+    )))
+}
+
+/// Delay before the next restart, given the previous one
+fn next_backoff(previous: Duration) -> Duration {
+    (previous * 2).min(MAX_BACKOFF)
+}
+
+/// Run the Dashboard, restarting it whenever it panics or fails
+///
+/// Returns once the server shuts down cleanly. An error on the very first
+/// start (say, the port is taken) is returned rather than retried.
+pub async fn supervise(port: u16, project_path: PathBuf, db_path: PathBuf) -> Result<()> {
+    use super::server::DashboardServer;
+    use futures_util::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    let mut backoff = INITIAL_BACKOFF;
+    let mut first_run = true;
+    loop {
+        let server = DashboardServer::new(port, project_path.clone(), db_path.clone()).await?;
+        let started = Instant::now();
+
+        // Polled in place rather than spawned: a forked daemon has no
+        // runtime worker threads left to run a spawned task
+        match AssertUnwindSafe(server.run()).catch_unwind().await {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(e)) if first_run && started.elapsed() < STARTUP_GRACE => {
+                return Err(IntentError::OtherError(e))
+            },
+            Ok(Err(e)) => tracing::error!(error = %e, "Dashboard server failed"),
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                tracing::error!(error = %message, "Dashboard server panicked")
+            },
+        }
+        // The run ended without cleaning up after itself
+        PidFile::remove_own(port);
+
+        if started.elapsed() >= STABLE_AFTER {
+            backoff = INITIAL_BACKOFF;
+        }
+        tracing::warn!(
+            delay_secs = backoff.as_secs(),
+            "Restarting Dashboard server"
+        );
+        tokio::time::sleep(backoff).await;
+        backoff = next_backoff(backoff);
+        first_run = false;
+        RESTARTS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let mut delay = INITIAL_BACKOFF;
+        let mut delays = Vec::new();
+        for _ in 0..8 {
+            delays.push(delay.as_secs());
+            delay = next_backoff(delay);
+        }
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+    }
+
+    #[test]
+    fn test_pid_file_round_trip() {
+        let record = PidFile {
+            pid: 42,
+            port: 11391,
+            project_path: "/work/app".to_string(),
+            started_at: Utc::now(),
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(serde_json::from_str::<PidFile>(&json).unwrap(), record);
+    }
+}
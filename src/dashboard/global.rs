@@ -0,0 +1,219 @@
+//! Cross-project view: one Dashboard aggregating every registered project
+//!
+//! Project databases are opened on demand (like every other handler), read,
+//! and closed again. A project whose database cannot be read is still listed,
+//! with `error` set, so one broken project does not hide the others.
+//! Overviews are served from the query cache until something writes.
+
+use super::cache::CachedQuery;
+use super::metrics::ProjectSnapshot;
+use super::server::{AppState, ProjectInfo};
+use crate::db::models::Task;
+use crate::global_projects::{CrossProjectLink, CrossProjectLinks};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Upper bound for `GET /api/global/tasks?limit=`
+pub const MAX_GLOBAL_TASKS: i64 = 500;
+
+/// A session's focused task
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct FocusEntry {
+    pub session_id: String,
+    pub task_id: i64,
+    pub task_name: String,
+    pub task_status: String,
+}
+
+/// Summary of one project for the global view
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectOverview {
+    pub name: String,
+    pub path: String,
+    pub is_host: bool,
+    pub is_active: bool,
+    /// Task counts keyed by status
+    pub task_counts: BTreeMap<String, i64>,
+    /// Focused tasks, most recently active session first
+    pub focus: Vec<FocusEntry>,
+    /// Unix timestamp of the latest event or task transition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A task tagged with the project it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalTask {
+    pub project_name: String,
+    pub project_path: String,
+    #[serde(flatten)]
+    pub task: Task,
+}
+
+/// A dependency of a task on a task of another project, tagged with the
+/// project of the waiting task
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalLink {
+    pub from_project_name: String,
+    pub from_project_path: String,
+    #[serde(flatten)]
+    pub link: CrossProjectLink,
+}
+
+/// Known projects as (key, info), host first, then by name
+async fn projects_in_display_order(state: &AppState) -> Vec<(PathBuf, ProjectInfo)> {
+    state.sync_registry().await;
+
+    let host_key = AppState::project_key(std::path::Path::new(&state.host_project.path));
+    let mut projects: Vec<_> = state
+        .known_projects
+        .read()
+        .await
+        .iter()
+        .map(|(key, info)| (key.clone(), info.clone()))
+        .collect();
+    projects.sort_by(|(a_key, a), (b_key, b)| {
+        (*b_key == host_key)
+            .cmp(&(*a_key == host_key))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    projects
+}
+
+/// Collect an overview of every known project
+pub async fn collect_overview(state: &AppState) -> Vec<ProjectOverview> {
+    let host_key = AppState::project_key(std::path::Path::new(&state.host_project.path));
+    let active_key = state.active_project_path.read().await.clone();
+
+    let mut overviews = Vec::new();
+    for (key, info) in projects_in_display_order(state).await {
+        let mut overview = ProjectOverview {
+            name: info.name.clone(),
+            path: info.path.display().to_string(),
+            is_host: key == host_key,
+            is_active: key == active_key,
+            task_counts: BTreeMap::new(),
+            focus: Vec::new(),
+            last_activity: None,
+            error: None,
+        };
+
+        // Only the database part is cached; host and active flags stay live
+        let filled = state
+            .query_cache
+            .get_or_load(&key, CachedQuery::Overview, || async {
+                let pool = state.get_readonly_db_pool(&key).await?;
+                let mut filled = overview.clone();
+                let result = fill_overview(&mut filled, &pool).await;
+                pool.close().await;
+                result.map(|()| filled).map_err(|e| e.to_string())
+            })
+            .await;
+        match filled {
+            Ok(filled) => {
+                overview.task_counts = filled.task_counts;
+                overview.focus = filled.focus;
+                overview.last_activity = filled.last_activity;
+            },
+            Err(e) => overview.error = Some(e),
+        }
+
+        overviews.push(overview);
+    }
+
+    overviews
+}
+
+async fn fill_overview(
+    overview: &mut ProjectOverview,
+    pool: &sqlx::SqlitePool,
+) -> sqlx::Result<()> {
+    let snapshot = ProjectSnapshot::collect(overview.name.clone(), pool).await?;
+    overview.task_counts = snapshot.task_counts.into_iter().collect();
+    overview.last_activity = snapshot.last_activity;
+
+    overview.focus = sqlx::query_as(
+        r#"
+        SELECT s.session_id, t.id AS task_id, t.name AS task_name, t.status AS task_status
+        FROM sessions s
+        JOIN tasks t ON t.id = s.current_task_id
+        ORDER BY s.last_active_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Collect tasks from every known project, optionally filtered by status
+///
+/// `limit` applies per project, so one busy project cannot crowd out the rest.
+pub async fn collect_tasks(state: &AppState, status: Option<&str>, limit: i64) -> Vec<GlobalTask> {
+    let mut tasks = Vec::new();
+
+    for (key, info) in projects_in_display_order(state).await {
+        let Ok(pool) = state.get_readonly_db_pool(&key).await else {
+            continue;
+        };
+
+        let sql = format!(
+            "{} WHERE (? IS NULL OR status = ?) ORDER BY COALESCE(priority, 999), id LIMIT ?",
+            crate::sql_constants::SELECT_TASK_FULL
+        );
+        let result: sqlx::Result<Vec<Task>> = sqlx::query_as(&sql)
+            .bind(status)
+            .bind(status)
+            .bind(limit)
+            .fetch_all(&pool)
+            .await;
+        pool.close().await;
+
+        match result {
+            Ok(project_tasks) => {
+                let project_path = info.path.display().to_string();
+                tasks.extend(project_tasks.into_iter().map(|task| GlobalTask {
+                    project_name: info.name.clone(),
+                    project_path: project_path.clone(),
+                    task,
+                }))
+            },
+            Err(e) => tracing::warn!("Failed to read tasks for {}: {}", info.name, e),
+        }
+    }
+
+    tasks
+}
+
+/// Cross-project dependencies of every known project
+///
+/// Projects whose database predates cross-project dependencies or cannot be
+/// read are skipped.
+pub async fn collect_links(state: &AppState) -> Vec<GlobalLink> {
+    let mut links = Vec::new();
+
+    for (key, info) in projects_in_display_order(state).await {
+        let Ok(pool) = state.get_readonly_db_pool(&key).await else {
+            continue;
+        };
+        let result = CrossProjectLinks::new(&pool).list(None).await;
+        pool.close().await;
+
+        match result {
+            Ok(project_links) => {
+                let from_project_path = info.path.display().to_string();
+                links.extend(project_links.into_iter().map(|link| GlobalLink {
+                    from_project_name: info.name.clone(),
+                    from_project_path: from_project_path.clone(),
+                    link,
+                }))
+            },
+            Err(e) => tracing::debug!("No cross-project links for {}: {}", info.name, e),
+        }
+    }
+
+    links
+}
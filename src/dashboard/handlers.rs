@@ -5,12 +5,15 @@ use axum::{
 };
 use serde_json::json;
 
+use super::cache::CachedQuery;
 use super::models::*;
 use super::server::AppState;
 use crate::{
     db::models::TaskSortBy,
+    error::IntentError,
     events::EventManager,
     search::SearchManager,
+    settings::SettingsManager,
     tasks::{TaskManager, TaskUpdate},
     workspace::WorkspaceManager,
 };
@@ -20,7 +23,7 @@ pub async fn list_tasks(
     State(state): State<AppState>,
     Query(query): Query<TaskListQuery>,
 ) -> impl IntoResponse {
-    let db_pool = match state.get_active_db_pool().await {
+    let db_pool = match state.get_active_readonly_db_pool().await {
         Ok(pool) => pool,
         Err(e) => {
             return (
@@ -51,7 +54,7 @@ pub async fn list_tasks(
         Some("priority") => Some(TaskSortBy::Priority),
         Some("time") => Some(TaskSortBy::Time),
         Some("focus") => Some(TaskSortBy::FocusAware),
-        _ => Some(TaskSortBy::FocusAware), // Default to FocusAware
+        _ => None, // Project default (tasks.default_sort in config.toml)
     };
 
     match task_mgr
@@ -82,24 +85,28 @@ pub async fn list_tasks(
 
 /// Get a single task by ID
 pub async fn get_task(State(state): State<AppState>, Path(id): Path<i64>) -> impl IntoResponse {
-    let db_pool = match state.get_active_db_pool().await {
-        Ok(pool) => pool,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError {
-                    code: "DATABASE_ERROR".to_string(),
-                    message: e,
-                    details: None,
-                }),
-            )
-                .into_response()
-        },
-    };
-    let task_mgr = TaskManager::new(&db_pool);
+    let active_path = state.active_project_path.read().await.clone();
+    let detail = state
+        .query_cache
+        .get_or_load(&active_path, CachedQuery::Task(id), || async {
+            let db_pool = state
+                .get_readonly_db_pool(&active_path)
+                .await
+                .map_err(|e| IntentError::OtherError(anyhow::anyhow!(e)))?;
+            let task_mgr = TaskManager::new(&db_pool);
+            let task = task_mgr.get_task(id).await?;
+            let lease = task_mgr.get_lease(id).await?;
+            let spec_meta = crate::spec_meta::get(&db_pool, id).await?;
+            Ok::<_, IntentError>(TaskDetailResponse {
+                task,
+                lease,
+                spec_meta,
+            })
+        })
+        .await;
 
-    match task_mgr.get_task(id).await {
-        Ok(task) => (StatusCode::OK, Json(ApiResponse { data: task })).into_response(),
+    match detail {
+        Ok(detail) => (StatusCode::OK, Json(ApiResponse { data: detail })).into_response(),
         Err(e) if e.to_string().contains("not found") => (
             StatusCode::NOT_FOUND,
             Json(ApiError {
@@ -266,6 +273,7 @@ pub async fn update_task(
                 spec: req.spec.as_deref(),
                 status: req.status.as_deref(),
                 priority: req.priority,
+                blocked_reason: req.blocked_reason.as_deref(),
                 ..Default::default()
             },
         )
@@ -415,7 +423,7 @@ pub async fn done_task(State(state): State<AppState>) -> impl IntoResponse {
     );
 
     // Dashboard = human caller, no passphrase needed
-    match task_mgr.done_task(false).await {
+    match task_mgr.done_task().await {
         Ok(task) => (StatusCode::OK, Json(ApiResponse { data: task })).into_response(),
         Err(e) if e.to_string().contains("No current task") => (
             StatusCode::BAD_REQUEST,
@@ -490,6 +498,116 @@ pub async fn spawn_subtask(
     }
 }
 
+/// Move a task to another kanban column (status) and/or category (parent)
+pub async fn move_task(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(req): Json<MoveTaskRequest>,
+) -> impl IntoResponse {
+    let (db_pool, project_path) = match state.get_active_project_context().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    let task_mgr = TaskManager::with_websocket(
+        &db_pool,
+        std::sync::Arc::new(state.ws_state.clone()),
+        project_path,
+    );
+
+    match task_mgr
+        .update_task(
+            id,
+            TaskUpdate {
+                status: req.status.as_deref(),
+                parent_id: req.parent_id,
+                sort_order: req.sort_order,
+                ..Default::default()
+            },
+        )
+        .await
+    {
+        Ok(task) => (StatusCode::OK, Json(ApiResponse { data: task })).into_response(),
+        Err(crate::error::IntentError::TaskNotFound(missing)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "TASK_NOT_FOUND".to_string(),
+                message: format!("Task {} not found", missing),
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message: format!("Failed to move task: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Persist manual ordering of tasks (e.g. after a kanban drag-drop)
+pub async fn reorder_tasks(
+    State(state): State<AppState>,
+    Json(req): Json<ReorderTasksRequest>,
+) -> impl IntoResponse {
+    let (db_pool, project_path) = match state.get_active_project_context().await {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    let task_mgr = TaskManager::with_websocket(
+        &db_pool,
+        std::sync::Arc::new(state.ws_state.clone()),
+        project_path,
+    );
+
+    match task_mgr.reorder_tasks(&req.task_ids).await {
+        Ok(tasks) => (StatusCode::OK, Json(ApiResponse { data: tasks })).into_response(),
+        Err(crate::error::IntentError::TaskNotFound(missing)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "TASK_NOT_FOUND".to_string(),
+                message: format!("Task {} not found", missing),
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message: format!("Failed to reorder tasks: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
 /// Get current task
 pub async fn get_current_task(State(state): State<AppState>) -> impl IntoResponse {
     let db_pool = match state.get_active_db_pool().await {
@@ -535,6 +653,86 @@ pub async fn get_current_task(State(state): State<AppState>) -> impl IntoRespons
     }
 }
 
+/// Tasks interrupted with `ie focus push`, top first
+pub async fn get_focus_stack(State(state): State<AppState>) -> impl IntoResponse {
+    let db_pool = match state.get_active_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    match crate::focus::FocusStack::new(&db_pool).tasks().await {
+        Ok(tasks) => (StatusCode::OK, Json(ApiResponse { data: tasks })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to get focus stack: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Project health score, or the tasks behind one insight (`?kind=blocked`)
+pub async fn get_insights(
+    State(state): State<AppState>,
+    Query(query): Query<InsightsQuery>,
+) -> impl IntoResponse {
+    let db_pool = match state.get_active_readonly_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    let insights = crate::insights::InsightsManager::new(&db_pool);
+    let result = match query.kind.as_deref().map(str::parse) {
+        None => insights.report().await.map(|report| json!(report)),
+        Some(Ok(kind)) => insights.drill_down(kind).await.map(|tasks| json!(tasks)),
+        Some(Err(e)) => Err(e),
+    };
+    match result {
+        Ok(data) => (StatusCode::OK, Json(ApiResponse { data })).into_response(),
+        Err(IntentError::InvalidInput(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message,
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to compute insights: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
 /// Pick next task recommendation
 pub async fn pick_next_task(State(state): State<AppState>) -> impl IntoResponse {
     let db_pool = match state.get_active_db_pool().await {
@@ -567,13 +765,65 @@ pub async fn pick_next_task(State(state): State<AppState>) -> impl IntoResponse
     }
 }
 
-/// List events for a task
+/// Cross-task activity feed: events plus task creation/start/completion
+pub async fn list_activity(
+    State(state): State<AppState>,
+    Query(query): Query<ActivityQuery>,
+) -> impl IntoResponse {
+    let db_pool = match state.get_active_readonly_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+    let filter = crate::db::models::ActivityFilter {
+        since: query.since,
+        task_id: query.task_id,
+        event_type: query.event_type,
+    };
+
+    match EventManager::new(&db_pool)
+        .with_visibility(query.visibility)
+        .recent_activity(query.limit.unwrap_or(50).clamp(1, 500), &filter)
+        .await
+    {
+        Ok(entries) => (StatusCode::OK, Json(ApiResponse { data: entries })).into_response(),
+        Err(crate::error::IntentError::InvalidInput(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message,
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to load activity: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// List events for a task, newest first (paginated via `before` + `limit`)
 pub async fn list_events(
     State(state): State<AppState>,
     Path(task_id): Path<i64>,
     Query(query): Query<EventListQuery>,
 ) -> impl IntoResponse {
-    let db_pool = match state.get_active_db_pool().await {
+    let db_pool = match state.get_active_readonly_db_pool().await {
         Ok(pool) => pool,
         Err(e) => {
             return (
@@ -587,19 +837,37 @@ pub async fn list_events(
                 .into_response()
         },
     };
-    let event_mgr = EventManager::new(&db_pool);
+    let event_mgr = EventManager::new(&db_pool).with_visibility(query.visibility);
 
-    // Signature: list_events(task_id, limit, log_type, since)
     match event_mgr
-        .list_events(
+        .list_events_page(
             Some(task_id),
-            query.limit.map(|l| l as i64),
             query.event_type,
             query.since,
+            query.before,
+            query.limit.map(|l| l as i64),
         )
         .await
     {
-        Ok(events) => (StatusCode::OK, Json(ApiResponse { data: events })).into_response(),
+        Ok(page) => (StatusCode::OK, Json(ApiResponse { data: page })).into_response(),
+        Err(crate::error::IntentError::TaskNotFound(missing)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "TASK_NOT_FOUND".to_string(),
+                message: format!("Task {} not found", missing),
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(crate::error::IntentError::InvalidInput(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message,
+                details: None,
+            }),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -652,9 +920,8 @@ pub async fn create_event(
             .into_response();
     }
 
-    // add_event signature: (task_id, log_type, discussion_data)
     match event_mgr
-        .add_event(task_id, &req.event_type, &req.data)
+        .add_event_with_visibility(task_id, &req.event_type, &req.data, req.visibility)
         .await
     {
         Ok(event) => (StatusCode::CREATED, Json(ApiResponse { data: event })).into_response(),
@@ -831,7 +1098,7 @@ pub async fn search(
     State(state): State<AppState>,
     Query(query): Query<SearchQuery>,
 ) -> impl IntoResponse {
-    let db_pool = match state.get_active_db_pool().await {
+    let db_pool = match state.get_active_readonly_db_pool().await {
         Ok(pool) => pool,
         Err(e) => {
             return (
@@ -873,19 +1140,22 @@ pub async fn search(
 
 /// List all registered projects (from known_projects state loaded from global registry)
 pub async fn list_projects(State(state): State<AppState>) -> impl IntoResponse {
-    let host_path = state.host_project.path.clone();
+    let host_key = AppState::project_key(std::path::Path::new(&state.host_project.path));
 
-    // Read from known_projects (loaded from global registry at startup)
+    // Include projects registered since the Dashboard started
+    state.sync_registry().await;
+    let active_key = state.active_project_path.read().await.clone();
     let known_projects = state.known_projects.read().await;
 
     let projects: Vec<serde_json::Value> = known_projects
-        .values()
-        .map(|proj| {
-            let is_host = proj.path.to_string_lossy() == host_path;
+        .iter()
+        .map(|(key, proj)| {
+            let is_host = *key == host_key;
             json!({
                 "name": proj.name,
                 "path": proj.path.to_string_lossy(),
                 "is_online": is_host,  // Only host project is "online"
+                "is_active": *key == active_key,
                 "mcp_connected": false, // MCP removed, always false
             })
         })
@@ -894,6 +1164,222 @@ pub async fn list_projects(State(state): State<AppState>) -> impl IntoResponse {
     (StatusCode::OK, Json(ApiResponse { data: projects })).into_response()
 }
 
+/// Overview of every registered project: task counts, focus and activity
+pub async fn global_overview(State(state): State<AppState>) -> impl IntoResponse {
+    let projects = super::global::collect_overview(&state).await;
+    (StatusCode::OK, Json(ApiResponse { data: projects })).into_response()
+}
+
+/// Tasks across every registered project, tagged with their project
+pub async fn global_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<GlobalTaskQuery>,
+) -> impl IntoResponse {
+    use super::global::MAX_GLOBAL_TASKS;
+
+    let limit = query.limit.unwrap_or(100).clamp(1, MAX_GLOBAL_TASKS);
+    let tasks = super::global::collect_tasks(&state, query.status.as_deref(), limit).await;
+    (StatusCode::OK, Json(ApiResponse { data: tasks })).into_response()
+}
+
+/// Dependencies between tasks of different registered projects
+pub async fn global_links(State(state): State<AppState>) -> impl IntoResponse {
+    let links = super::global::collect_links(&state).await;
+    (StatusCode::OK, Json(ApiResponse { data: links })).into_response()
+}
+
+/// Project settings with schema and current values
+pub async fn list_settings(State(state): State<AppState>) -> impl IntoResponse {
+    let db_pool = match state.get_active_readonly_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    match SettingsManager::new(&db_pool).list().await {
+        Ok(settings) => (StatusCode::OK, Json(ApiResponse { data: settings })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to load settings: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Validate and apply settings changes, recording each in the audit log
+pub async fn update_settings(
+    State(state): State<AppState>,
+    Json(req): Json<UpdateSettingsRequest>,
+) -> impl IntoResponse {
+    let db_pool = match state.get_active_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+    if let Err(errors) = crate::settings::validate_changes(&req.values) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_SETTINGS".to_string(),
+                message: "Settings validation failed; nothing was saved".to_string(),
+                details: Some(json!({ "errors": errors })),
+            }),
+        )
+            .into_response();
+    }
+
+    let settings = SettingsManager::new(&db_pool);
+    let changes = match settings.apply(&req.values, "dashboard").await {
+        Ok(changes) => changes,
+        // Values that config.toml as a whole rejects, e.g. a capacity of 0
+        Err(crate::error::IntentError::InvalidInput(message)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiError {
+                    code: "INVALID_SETTINGS".to_string(),
+                    message: "Settings validation failed; nothing was saved".to_string(),
+                    details: Some(json!({ "errors": [message] })),
+                }),
+            )
+                .into_response();
+        },
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: format!("Failed to save settings: {}", e),
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    match settings.list().await {
+        Ok(current) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                data: json!({ "settings": current, "changes": changes }),
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to load settings: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Recent settings changes, newest first
+pub async fn settings_audit(
+    State(state): State<AppState>,
+    Query(query): Query<SettingsAuditQuery>,
+) -> impl IntoResponse {
+    let db_pool = match state.get_active_readonly_db_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    code: "DATABASE_ERROR".to_string(),
+                    message: e,
+                    details: None,
+                }),
+            )
+                .into_response()
+        },
+    };
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    match SettingsManager::new(&db_pool).history(limit).await {
+        Ok(changes) => (StatusCode::OK, Json(ApiResponse { data: changes })).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to load settings history: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Per-client WebSocket queue statistics (backpressure diagnostics)
+pub async fn ws_queue_stats(State(state): State<AppState>) -> impl IntoResponse {
+    let stats = state.ws_state.queue_stats().await;
+    (StatusCode::OK, Json(ApiResponse { data: stats })).into_response()
+}
+
+/// Prometheus metrics (task counts, WebSocket clients, request counters)
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    use super::metrics::{MetricsSnapshot, ProjectSnapshot};
+
+    let projects: Vec<_> = state
+        .known_projects
+        .read()
+        .await
+        .iter()
+        .map(|(key, info)| (key.clone(), info.name.clone()))
+        .collect();
+    let mut snapshot = MetricsSnapshot {
+        known_projects: projects.len(),
+        queues: state.ws_state.queue_stats().await,
+        cache: state.query_cache.counters(),
+        cache_entries: state.query_cache.len(),
+        ..Default::default()
+    };
+
+    for (key, name) in projects {
+        let Ok(pool) = state.get_readonly_db_pool(&key).await else {
+            continue;
+        };
+        match ProjectSnapshot::collect(name.clone(), &pool).await {
+            Ok(project_snapshot) => snapshot.projects.push(project_snapshot),
+            Err(e) => tracing::warn!("Failed to collect metrics for {}: {}", name, e),
+        }
+        pool.close().await;
+    }
+
+    (
+        StatusCode::OK,
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        state.metrics.render(&snapshot),
+    )
+        .into_response()
+}
+
 /// Switch to a different project database dynamically
 pub async fn switch_project(
     State(state): State<AppState>,
@@ -999,7 +1485,47 @@ pub async fn get_task_context(
     State(state): State<AppState>,
     Path(id): Path<i64>,
 ) -> impl IntoResponse {
-    let db_pool = match state.get_active_db_pool().await {
+    let active_path = state.active_project_path.read().await.clone();
+    let context = state
+        .query_cache
+        .get_or_load(&active_path, CachedQuery::TaskContext(id), || async {
+            let db_pool = state
+                .get_readonly_db_pool(&active_path)
+                .await
+                .map_err(|e| IntentError::OtherError(anyhow::anyhow!(e)))?;
+            TaskManager::new(&db_pool).get_task_context(id).await
+        })
+        .await;
+
+    match context {
+        Ok(context) => (StatusCode::OK, Json(ApiResponse { data: context })).into_response(),
+        Err(e) if e.to_string().contains("not found") => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "TASK_NOT_FOUND".to_string(),
+                message: format!("Task {} not found", id),
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to get task context: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// List one page of a task's children (root tasks without `parent`)
+pub async fn list_task_children(
+    State(state): State<AppState>,
+    Query(query): Query<TaskTreeQuery>,
+) -> impl IntoResponse {
+    let db_pool = match state.get_active_readonly_db_pool().await {
         Ok(pool) => pool,
         Err(e) => {
             return (
@@ -1015,9 +1541,61 @@ pub async fn get_task_context(
     };
     let task_mgr = TaskManager::new(&db_pool);
 
-    match task_mgr.get_task_context(id).await {
-        Ok(context) => (StatusCode::OK, Json(ApiResponse { data: context })).into_response(),
-        Err(e) if e.to_string().contains("not found") => (
+    match task_mgr
+        .find_children(query.parent, query.cursor, query.limit)
+        .await
+    {
+        Ok(page) => (StatusCode::OK, Json(ApiResponse { data: page })).into_response(),
+        Err(crate::error::IntentError::TaskNotFound(id)) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                code: "TASK_NOT_FOUND".to_string(),
+                message: format!("Task {} not found", id),
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(crate::error::IntentError::InvalidInput(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                code: "INVALID_REQUEST".to_string(),
+                message,
+                details: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to list children: {}", e),
+                details: None,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Get rollup stats for a task's subtree (tree badges)
+pub async fn get_subtree_stats(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    let active_path = state.active_project_path.read().await.clone();
+    let stats = state
+        .query_cache
+        .get_or_load(&active_path, CachedQuery::SubtreeStats(id), || async {
+            let db_pool = state
+                .get_readonly_db_pool(&active_path)
+                .await
+                .map_err(|e| IntentError::OtherError(anyhow::anyhow!(e)))?;
+            TaskManager::new(&db_pool).get_subtree_stats(id).await
+        })
+        .await;
+
+    match stats {
+        Ok(stats) => (StatusCode::OK, Json(ApiResponse { data: stats })).into_response(),
+        Err(crate::error::IntentError::TaskNotFound(_)) => (
             StatusCode::NOT_FOUND,
             Json(ApiError {
                 code: "TASK_NOT_FOUND".to_string(),
@@ -1030,7 +1608,7 @@ pub async fn get_task_context(
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
                 code: "DATABASE_ERROR".to_string(),
-                message: format!("Failed to get task context: {}", e),
+                message: format!("Failed to get subtree stats: {}", e),
                 details: None,
             }),
         )
@@ -1053,8 +1631,23 @@ pub async fn handle_cli_notification(
         NotificationMessage::TaskChanged { project_path, .. } => project_path.clone(),
         NotificationMessage::EventAdded { project_path, .. } => project_path.clone(),
         NotificationMessage::WorkspaceChanged { project_path, .. } => project_path.clone(),
+        NotificationMessage::TasksUnblocked { project_path, .. } => project_path.clone(),
     };
 
+    // Plan executions are reported as a single batch_update notification
+    let kind = match &message {
+        NotificationMessage::TaskChanged { operation, .. } => {
+            if operation == "batch_update" {
+                state.metrics.record_plan_execution();
+            }
+            "task_changed"
+        },
+        NotificationMessage::EventAdded { .. } => "event_added",
+        NotificationMessage::WorkspaceChanged { .. } => "workspace_changed",
+        NotificationMessage::TasksUnblocked { .. } => "tasks_unblocked",
+    };
+    state.metrics.record_cli_notification(kind);
+
     // If project_path is provided, register it as a known project
     if let Some(ref path_str) = project_path {
         let project_path = PathBuf::from(path_str);
@@ -1080,14 +1673,15 @@ pub async fn handle_cli_notification(
     }
 
     // Convert CLI notification to frontend-compatible format and broadcast
-    let ui_message = match &message {
+    use crate::dashboard::websocket::{task_events_topic, TASKS_TOPIC, WORKSPACE_TOPIC};
+    let (topic, ui_message) = match &message {
         NotificationMessage::TaskChanged {
             task_id,
             operation,
             project_path,
         } => {
             // Convert to db_operation format that frontend already handles
-            json!({
+            let message = json!({
                 "type": "db_operation",
                 "payload": {
                     "entity": "task",
@@ -1095,14 +1689,15 @@ pub async fn handle_cli_notification(
                     "affected_ids": task_id.map(|id| vec![id]).unwrap_or_default(),
                     "project_path": project_path
                 }
-            })
+            });
+            (TASKS_TOPIC.to_string(), message)
         },
         NotificationMessage::EventAdded {
             task_id,
             event_id,
             project_path,
         } => {
-            json!({
+            let message = json!({
                 "type": "db_operation",
                 "payload": {
                     "entity": "event",
@@ -1111,13 +1706,14 @@ pub async fn handle_cli_notification(
                     "task_id": task_id,
                     "project_path": project_path
                 }
-            })
+            });
+            (task_events_topic(*task_id), message)
         },
         NotificationMessage::WorkspaceChanged {
             current_task_id,
             project_path,
         } => {
-            json!({
+            let message = json!({
                 "type": "db_operation",
                 "payload": {
                     "entity": "workspace",
@@ -1125,14 +1721,53 @@ pub async fn handle_cli_notification(
                     "current_task_id": current_task_id,
                     "project_path": project_path
                 }
-            })
+            });
+            (WORKSPACE_TOPIC.to_string(), message)
+        },
+        NotificationMessage::TasksUnblocked {
+            tasks,
+            project_path,
+        } => {
+            // Agents hear about the newly available work directly
+            if let Some(path) = project_path {
+                state
+                    .ws_state
+                    .send_to_mcp(
+                        path,
+                        "tasks_unblocked",
+                        crate::dashboard::websocket::TasksUnblockedPayload {
+                            project_path: path.clone(),
+                            tasks: tasks.clone(),
+                        },
+                    )
+                    .await;
+            }
+            let message = json!({
+                "type": "db_operation",
+                "payload": {
+                    "entity": "task",
+                    "operation": "unblocked",
+                    "affected_ids": tasks.iter().map(|task| task.id).collect::<Vec<_>>(),
+                    "data": tasks,
+                    "project_path": project_path
+                }
+            });
+            (TASKS_TOPIC.to_string(), message)
         },
     };
 
     let notification_json = serde_json::to_string(&ui_message).unwrap_or_default();
-    state.ws_state.broadcast_to_ui(&notification_json).await;
+    let followers = state
+        .ws_state
+        .broadcast_to_topic(project_path.as_deref(), &topic, &notification_json)
+        .await;
 
-    (StatusCode::OK, Json(json!({"success": true}))).into_response()
+    // delivered = false tells the CLI to queue the change for the next UI client
+    (
+        StatusCode::OK,
+        Json(json!({"success": true, "delivered": followers > 0})),
+    )
+        .into_response()
 }
 
 /// Shutdown the Dashboard server gracefully
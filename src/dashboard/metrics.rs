@@ -0,0 +1,538 @@
+//! Dashboard metrics in Prometheus text exposition format
+//!
+//! Counters are kept in memory for the lifetime of the Dashboard process and
+//! rendered together with point-in-time gauges (task counts, WebSocket clients)
+//! on each scrape of `GET /metrics`.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::cache::CacheCounters;
+use super::websocket::ClientQueueStats;
+
+/// In-process counters updated by handlers, middleware and WebSocket loops
+#[derive(Debug)]
+pub struct DashboardMetrics {
+    started_at: Instant,
+    /// HTTP responses by status class (index 0 = 1xx … 4 = 5xx)
+    http_responses: [AtomicU64; 5],
+    /// MCP WebSocket messages received, by protocol message type
+    mcp_messages: Mutex<BTreeMap<String, u64>>,
+    /// CLI notifications received, by kind
+    cli_notifications: Mutex<BTreeMap<String, u64>>,
+    plan_executions: AtomicU64,
+    db_pool_opens: AtomicU64,
+    db_pool_open_errors: AtomicU64,
+}
+
+impl Default for DashboardMetrics {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            http_responses: Default::default(),
+            mcp_messages: Mutex::new(BTreeMap::new()),
+            cli_notifications: Mutex::new(BTreeMap::new()),
+            plan_executions: AtomicU64::new(0),
+            db_pool_opens: AtomicU64::new(0),
+            db_pool_open_errors: AtomicU64::new(0),
+        }
+    }
+}
+
+impl DashboardMetrics {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Time since this Dashboard server started
+    pub fn uptime(&self) -> std::time::Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn record_http_response(&self, status: u16) {
+        let class = (status / 100).clamp(1, 5) as usize - 1;
+        self.http_responses[class].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_mcp_message(&self, message_type: &str) {
+        increment(&self.mcp_messages, message_type);
+    }
+
+    pub fn record_cli_notification(&self, kind: &str) {
+        increment(&self.cli_notifications, kind);
+    }
+
+    pub fn record_plan_execution(&self) {
+        self.plan_executions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_db_pool_open(&self, success: bool) {
+        self.db_pool_opens.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.db_pool_open_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Render counters plus the given point-in-time snapshot
+    pub fn render(&self, snapshot: &MetricsSnapshot) -> String {
+        let mut out = PromWriter::default();
+
+        out.header(
+            "intent_engine_uptime_seconds",
+            "Seconds since the Dashboard started",
+            "gauge",
+        );
+        out.sample(
+            "intent_engine_uptime_seconds",
+            &[],
+            self.started_at.elapsed().as_secs_f64(),
+        );
+
+        out.header(
+            "intent_engine_tasks",
+            "Number of tasks by project and status",
+            "gauge",
+        );
+        for project in &snapshot.projects {
+            for (status, count) in &project.task_counts {
+                out.sample(
+                    "intent_engine_tasks",
+                    &[("project", &project.name), ("status", status)],
+                    *count as f64,
+                );
+            }
+        }
+
+        out.header(
+            "intent_engine_last_activity_timestamp_seconds",
+            "Unix time of the most recent event or task transition per project",
+            "gauge",
+        );
+        for project in &snapshot.projects {
+            if let Some(ts) = project.last_activity {
+                out.sample(
+                    "intent_engine_last_activity_timestamp_seconds",
+                    &[("project", &project.name)],
+                    ts as f64,
+                );
+            }
+        }
+
+        out.header(
+            "intent_engine_plan_executions_total",
+            "Plan executions reported by the CLI",
+            "counter",
+        );
+        out.sample(
+            "intent_engine_plan_executions_total",
+            &[],
+            self.plan_executions.load(Ordering::Relaxed) as f64,
+        );
+
+        out.header(
+            "intent_engine_cli_notifications_total",
+            "CLI notifications received, by kind",
+            "counter",
+        );
+        for (kind, count) in lock(&self.cli_notifications).iter() {
+            out.sample(
+                "intent_engine_cli_notifications_total",
+                &[("kind", kind)],
+                *count as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_mcp_requests_total",
+            "Messages received from MCP clients, by message type",
+            "counter",
+        );
+        for (message_type, count) in lock(&self.mcp_messages).iter() {
+            out.sample(
+                "intent_engine_mcp_requests_total",
+                &[("type", message_type)],
+                *count as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_http_responses_total",
+            "HTTP responses by status class",
+            "counter",
+        );
+        for (index, counter) in self.http_responses.iter().enumerate() {
+            let class = format!("{}xx", index + 1);
+            out.sample(
+                "intent_engine_http_responses_total",
+                &[("class", &class)],
+                counter.load(Ordering::Relaxed) as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_websocket_clients",
+            "Connected WebSocket clients by kind",
+            "gauge",
+        );
+        for kind in ["mcp", "ui"] {
+            let count = snapshot
+                .queues
+                .iter()
+                .filter(|q| q.client.as_str() == kind)
+                .count();
+            out.sample(
+                "intent_engine_websocket_clients",
+                &[("client", kind)],
+                count as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_websocket_queue_depth",
+            "Messages waiting in a client's outbound queue",
+            "gauge",
+        );
+        for queue in &snapshot.queues {
+            out.sample(
+                "intent_engine_websocket_queue_depth",
+                &queue_labels(queue),
+                queue.queued as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_websocket_messages_dropped_total",
+            "Messages dropped because a client's queue was full",
+            "counter",
+        );
+        for queue in &snapshot.queues {
+            out.sample(
+                "intent_engine_websocket_messages_dropped_total",
+                &queue_labels(queue),
+                queue.dropped as f64,
+            );
+        }
+
+        out.header(
+            "intent_engine_known_projects",
+            "Projects the Dashboard can serve",
+            "gauge",
+        );
+        out.sample(
+            "intent_engine_known_projects",
+            &[],
+            snapshot.known_projects as f64,
+        );
+
+        out.header(
+            "intent_engine_db_pool_opens_total",
+            "Database pools opened (one per request; SQLite opens on demand)",
+            "counter",
+        );
+        out.sample(
+            "intent_engine_db_pool_opens_total",
+            &[],
+            self.db_pool_opens.load(Ordering::Relaxed) as f64,
+        );
+
+        out.header(
+            "intent_engine_db_pool_open_errors_total",
+            "Database pools that failed to open",
+            "counter",
+        );
+        out.sample(
+            "intent_engine_db_pool_open_errors_total",
+            &[],
+            self.db_pool_open_errors.load(Ordering::Relaxed) as f64,
+        );
+
+        out.header(
+            "intent_engine_query_cache_requests_total",
+            "Cached reads served, by query and result (hit or miss)",
+            "counter",
+        );
+        for counters in &snapshot.cache {
+            for (result, count) in [("hit", counters.hits), ("miss", counters.misses)] {
+                out.sample(
+                    "intent_engine_query_cache_requests_total",
+                    &[("query", counters.kind), ("result", result)],
+                    count as f64,
+                );
+            }
+        }
+
+        out.header(
+            "intent_engine_query_cache_hit_ratio",
+            "Share of cached reads served without querying the database",
+            "gauge",
+        );
+        for counters in &snapshot.cache {
+            let total = counters.hits + counters.misses;
+            if total > 0 {
+                out.sample(
+                    "intent_engine_query_cache_hit_ratio",
+                    &[("query", counters.kind)],
+                    counters.hits as f64 / total as f64,
+                );
+            }
+        }
+
+        out.header(
+            "intent_engine_query_cache_entries",
+            "Results held by the query cache",
+            "gauge",
+        );
+        out.sample(
+            "intent_engine_query_cache_entries",
+            &[],
+            snapshot.cache_entries as f64,
+        );
+
+        out.header(
+            "intent_engine_db_pool_connections",
+            "Connections held by the pool used for this scrape",
+            "gauge",
+        );
+        for project in &snapshot.projects {
+            let idle = project.pool_idle as f64;
+            let active = (project.pool_size as f64 - idle).max(0.0);
+            for (state, value) in [("active", active), ("idle", idle)] {
+                out.sample(
+                    "intent_engine_db_pool_connections",
+                    &[("project", &project.name), ("state", state)],
+                    value,
+                );
+            }
+        }
+
+        out.finish()
+    }
+}
+
+/// Point-in-time values collected at scrape time
+#[derive(Debug, Default)]
+pub struct MetricsSnapshot {
+    pub projects: Vec<ProjectSnapshot>,
+    pub queues: Vec<ClientQueueStats>,
+    pub known_projects: usize,
+    /// Query cache hits and misses by query kind
+    pub cache: Vec<CacheCounters>,
+    pub cache_entries: usize,
+}
+
+/// Per-project values collected from the project database
+#[derive(Debug, Default)]
+pub struct ProjectSnapshot {
+    pub name: String,
+    pub task_counts: Vec<(String, i64)>,
+    /// Unix timestamp of the latest event or task transition
+    pub last_activity: Option<i64>,
+    pub pool_size: u32,
+    pub pool_idle: usize,
+}
+
+impl ProjectSnapshot {
+    /// Collect task counts and activity for a project database
+    pub async fn collect(name: String, pool: &sqlx::SqlitePool) -> sqlx::Result<Self> {
+        let task_counts: Vec<(String, i64)> =
+            sqlx::query_as("SELECT status, COUNT(*) FROM tasks GROUP BY status ORDER BY status")
+                .fetch_all(pool)
+                .await?;
+
+        let last_activity: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+            r#"
+            SELECT MAX(ts) FROM (
+                SELECT MAX(timestamp) AS ts FROM events
+                UNION ALL SELECT MAX(first_doing_at) FROM tasks
+                UNION ALL SELECT MAX(first_done_at) FROM tasks
+            )
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(Self {
+            name,
+            task_counts,
+            last_activity: last_activity.map(|ts| ts.timestamp()),
+            pool_size: pool.size(),
+            pool_idle: pool.num_idle(),
+        })
+    }
+}
+
+/// Count every HTTP response by status class
+pub async fn track_http(
+    State(metrics): State<Arc<DashboardMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    metrics.record_http_response(response.status().as_u16());
+    response
+}
+
+fn increment(map: &Mutex<BTreeMap<String, u64>>, key: &str) {
+    *lock(map).entry(key.to_string()).or_insert(0) += 1;
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn queue_labels(queue: &ClientQueueStats) -> Vec<(&str, &str)> {
+    let mut labels = vec![("client", queue.client.as_str())];
+    if let Some(path) = &queue.project_path {
+        labels.push(("project_path", path.as_str()));
+    }
+    labels
+}
+
+/// Minimal Prometheus text format writer
+#[derive(Default)]
+struct PromWriter {
+    out: String,
+}
+
+impl PromWriter {
+    fn header(&mut self, name: &str, help: &str, kind: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let rendered: Vec<String> = labels
+                .iter()
+                .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", rendered.join(","));
+        }
+        let _ = writeln!(self.out, " {}", value);
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counters() {
+        let metrics = DashboardMetrics::default();
+        metrics.record_http_response(200);
+        metrics.record_http_response(404);
+        metrics.record_http_response(500);
+        metrics.record_mcp_message("register");
+        metrics.record_mcp_message("register");
+        metrics.record_cli_notification("task_changed");
+        metrics.record_plan_execution();
+        metrics.record_db_pool_open(true);
+        metrics.record_db_pool_open(false);
+
+        let text = metrics.render(&MetricsSnapshot::default());
+
+        assert!(text.contains("# TYPE intent_engine_http_responses_total counter"));
+        assert!(text.contains("intent_engine_http_responses_total{class=\"2xx\"} 1"));
+        assert!(text.contains("intent_engine_http_responses_total{class=\"5xx\"} 1"));
+        assert!(text.contains("intent_engine_mcp_requests_total{type=\"register\"} 2"));
+        assert!(text.contains("intent_engine_cli_notifications_total{kind=\"task_changed\"} 1"));
+        assert!(text.contains("intent_engine_plan_executions_total 1"));
+        assert!(text.contains("intent_engine_db_pool_opens_total 2"));
+        assert!(text.contains("intent_engine_db_pool_open_errors_total 1"));
+        assert!(text.contains("intent_engine_websocket_clients{client=\"ui\"} 0"));
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        let metrics = DashboardMetrics::default();
+        let snapshot = MetricsSnapshot {
+            projects: vec![ProjectSnapshot {
+                name: "my \"app\"".to_string(),
+                task_counts: vec![("doing".to_string(), 1), ("todo".to_string(), 3)],
+                last_activity: Some(1_700_000_000),
+                pool_size: 1,
+                pool_idle: 1,
+            }],
+            queues: vec![ClientQueueStats {
+                client: "mcp".to_string(),
+                project_path: Some("/tmp/app".to_string()),
+                capacity: 256,
+                queued: 4,
+                sent: 10,
+                dropped: 2,
+            }],
+            known_projects: 1,
+            cache: vec![CacheCounters {
+                kind: "task",
+                hits: 3,
+                misses: 1,
+            }],
+            cache_entries: 1,
+        };
+
+        let text = metrics.render(&snapshot);
+
+        assert!(text.contains("intent_engine_tasks{project=\"my \\\"app\\\"\",status=\"todo\"} 3"));
+        assert!(text.contains(
+            "intent_engine_last_activity_timestamp_seconds{project=\"my \\\"app\\\"\"} 1700000000"
+        ));
+        assert!(text.contains("intent_engine_websocket_clients{client=\"mcp\"} 1"));
+        assert!(text.contains(
+            "intent_engine_websocket_queue_depth{client=\"mcp\",project_path=\"/tmp/app\"} 4"
+        ));
+        assert!(text.contains("intent_engine_known_projects 1"));
+        assert!(text
+            .contains("intent_engine_query_cache_requests_total{query=\"task\",result=\"hit\"} 3"));
+        assert!(text.contains("intent_engine_query_cache_hit_ratio{query=\"task\"} 0.75"));
+        assert!(text.contains("intent_engine_query_cache_entries 1"));
+    }
+
+    #[tokio::test]
+    async fn test_project_snapshot_collect() {
+        let ctx = crate::test_utils::test_helpers::TestContext::new().await;
+        let task_mgr = crate::tasks::TaskManager::new(ctx.pool());
+        let task = task_mgr
+            .add_task("A", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .add_task("B", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let snapshot = ProjectSnapshot::collect("p".to_string(), ctx.pool())
+            .await
+            .unwrap();
+        assert_eq!(snapshot.task_counts, vec![("todo".to_string(), 2)]);
+        assert!(snapshot.last_activity.is_none());
+
+        crate::events::EventManager::new(ctx.pool())
+            .add_event(task.id, "note", "progress")
+            .await
+            .unwrap();
+        let snapshot = ProjectSnapshot::collect("p".to_string(), ctx.pool())
+            .await
+            .unwrap();
+        assert!(snapshot.last_activity.is_some());
+    }
+}
@@ -1,6 +1,13 @@
+pub mod auth;
+pub mod cache;
 pub mod cli_notifier;
+pub mod daemon;
+pub mod global;
 pub mod handlers;
+pub mod metrics;
 pub mod models;
 pub mod routes;
+pub mod schedule;
 pub mod server;
+pub mod service;
 pub mod websocket;
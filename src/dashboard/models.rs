@@ -8,14 +8,44 @@ pub struct ApiResponse<T> {
 }
 
 /// API error response
-#[derive(Serialize)]
+///
+/// Serialized with `error_code`, the catalog code of `code` (`ie errors list`).
 pub struct ApiError {
     pub code: String,
     pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Value>,
 }
 
+impl Serialize for ApiError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ApiError", 4)?;
+        state.serialize_field("code", &self.code)?;
+        match crate::error::catalog_code(&self.code) {
+            Some(error_code) => state.serialize_field("error_code", error_code)?,
+            None => state.skip_field("error_code")?,
+        }
+        state.serialize_field("message", &self.message)?;
+        match &self.details {
+            Some(details) => state.serialize_field("details", details)?,
+            None => state.skip_field("details")?,
+        }
+        state.end()
+    }
+}
+
+/// A task with its live lease (`GET /api/tasks/:id`)
+#[derive(Clone, Serialize)]
+pub struct TaskDetailResponse {
+    #[serde(flatten)]
+    pub task: crate::db::models::Task,
+    /// Always present so clients merging updates clear a released lease
+    pub lease: Option<crate::db::models::TaskLease>,
+    /// Parsed spec frontmatter, if the spec has any
+    pub spec_meta: Option<crate::spec_meta::SpecMeta>,
+}
+
 /// Create task request
 #[derive(Deserialize)]
 pub struct CreateTaskRequest {
@@ -39,6 +69,30 @@ pub struct UpdateTaskRequest {
     pub priority: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
+    /// Why the task cannot move; "" unblocks it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+}
+
+/// Move task request (kanban drag-drop between columns/categories)
+#[derive(Deserialize)]
+pub struct MoveTaskRequest {
+    /// Target column (task status)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Target category (parent task); `null` moves the task to the root level
+    #[serde(default, deserialize_with = "crate::plan::deserialize_parent_id")]
+    pub parent_id: Option<Option<i64>>,
+    /// Position within the target column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i64>,
+}
+
+/// Reorder tasks request (manual ordering within a kanban column)
+#[derive(Deserialize)]
+pub struct ReorderTasksRequest {
+    /// Task IDs in their new display order
+    pub task_ids: Vec<i64>,
 }
 
 /// Create event request
@@ -47,6 +101,9 @@ pub struct CreateEventRequest {
     #[serde(alias = "type", alias = "event_type")]
     pub event_type: String,
     pub data: String,
+    /// normal (default), verbose or private
+    #[serde(default)]
+    pub visibility: crate::db::models::EventVisibility,
 }
 
 /// Update event request
@@ -85,15 +142,78 @@ pub struct TaskListQuery {
     pub offset: Option<i64>,
 }
 
+/// Query parameters for one page of the lazily loaded task tree
+#[derive(Deserialize)]
+pub struct TaskTreeQuery {
+    /// Parent task ID; root tasks when absent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<i64>,
+    /// ID of the last task of the previous page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+/// Query parameters for the cross-project task list
+#[derive(Deserialize)]
+pub struct GlobalTaskQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Maximum tasks per project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+/// Request to change project settings
+#[derive(Deserialize)]
+pub struct UpdateSettingsRequest {
+    /// Setting key → new value; `null` unsets the key
+    pub values: std::collections::BTreeMap<String, Option<String>>,
+}
+
+/// Query parameters for the settings audit log
+#[derive(Deserialize)]
+pub struct SettingsAuditQuery {
+    pub limit: Option<i64>,
+}
+
+/// Query parameters for project health
+#[derive(Deserialize)]
+pub struct InsightsQuery {
+    /// Insight to drill into; without it the whole health report is returned
+    pub kind: Option<String>,
+}
+
+/// Query parameters for the cross-task activity feed
+#[derive(Deserialize)]
+pub struct ActivityQuery {
+    /// Duration window, e.g. "7d"
+    pub since: Option<String>,
+    pub task_id: Option<i64>,
+    #[serde(alias = "type")]
+    pub event_type: Option<String>,
+    pub limit: Option<i64>,
+    /// Highest visibility to include: normal (default), verbose or all
+    #[serde(default)]
+    pub visibility: crate::db::models::EventVisibility,
+}
+
 /// Query parameters for event list
 #[derive(Deserialize)]
 pub struct EventListQuery {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "type", skip_serializing_if = "Option::is_none")]
     pub event_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub since: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
+    /// Event ID cursor: return events older than this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<i64>,
+    /// Highest visibility to include: normal (default), verbose or all
+    #[serde(default)]
+    pub visibility: crate::db::models::EventVisibility,
 }
 
 /// Switch project request
@@ -171,6 +291,22 @@ mod tests {
         let json = serde_json::to_string(&error).unwrap();
         assert!(json.contains("TEST_ERROR"));
         assert!(!json.contains("details"));
+        assert!(!json.contains("error_code"));
+
+        let error = ApiError {
+            code: "TASK_NOT_FOUND".to_string(),
+            message: "Task 7 not found".to_string(),
+            details: Some(serde_json::json!({"id": 7})),
+        };
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "TASK_NOT_FOUND",
+                "error_code": "IE0001",
+                "message": "Task 7 not found",
+                "details": {"id": 7}
+            })
+        );
     }
 
     #[test]
@@ -191,6 +327,27 @@ mod tests {
         assert_eq!(req.status, Some("done".to_string()));
     }
 
+    #[test]
+    fn test_move_task_request_deserialization() {
+        let json = r#"{"status":"doing","parent_id":null,"sort_order":2}"#;
+        let req: MoveTaskRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.status, Some("doing".to_string()));
+        assert_eq!(req.parent_id, Some(None));
+        assert_eq!(req.sort_order, Some(2));
+
+        let json = r#"{"sort_order":0}"#;
+        let req: MoveTaskRequest = serde_json::from_str(json).unwrap();
+        assert!(req.status.is_none());
+        assert!(req.parent_id.is_none());
+    }
+
+    #[test]
+    fn test_reorder_tasks_request_deserialization() {
+        let json = r#"{"task_ids":[3,1,2]}"#;
+        let req: ReorderTasksRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.task_ids, vec![3, 1, 2]);
+    }
+
     #[test]
     fn test_create_event_request_deserialization() {
         let json = r#"{"type":"decision","data":"Made a decision"}"#;
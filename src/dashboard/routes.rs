@@ -19,10 +19,16 @@ pub fn api_routes() -> Router<AppState> {
                 .delete(handlers::delete_task),
         )
         .route("/tasks/:id/start", post(handlers::start_task))
+        .route("/tasks/:id/move", post(handlers::move_task))
         .route("/tasks/:id/spawn-subtask", post(handlers::spawn_subtask))
         .route("/tasks/:id/context", get(handlers::get_task_context))
+        .route("/tasks/:id/stats", get(handlers::get_subtree_stats))
         // Task done is a global operation
         .route("/tasks/done", post(handlers::done_task))
+        // Lazily loaded tree, one level per request
+        .route("/tasks/tree", get(handlers::list_task_children))
+        // Kanban manual ordering
+        .route("/tasks/reorder", post(handlers::reorder_tasks))
         // Event routes
         .route(
             "/tasks/:id/events",
@@ -35,12 +41,26 @@ pub fn api_routes() -> Router<AppState> {
                 .delete(handlers::delete_event),
         )
         // Global routes
+        .route("/activity", get(handlers::list_activity))
         .route("/current-task", get(handlers::get_current_task))
+        .route("/focus-stack", get(handlers::get_focus_stack))
+        .route("/insights", get(handlers::get_insights))
         .route("/pick-next", get(handlers::pick_next_task))
         .route("/search", get(handlers::search))
         .route("/projects", get(handlers::list_projects))
         .route("/switch-project", post(handlers::switch_project))
         .route("/remove-project", post(handlers::remove_project))
+        .route("/ws-queues", get(handlers::ws_queue_stats))
+        // Project settings
+        .route(
+            "/settings",
+            get(handlers::list_settings).put(handlers::update_settings),
+        )
+        .route("/settings/audit", get(handlers::settings_audit))
+        // Cross-project view
+        .route("/global/overview", get(handlers::global_overview))
+        .route("/global/tasks", get(handlers::global_tasks))
+        .route("/global/links", get(handlers::global_links))
         // Internal routes (CLI → Dashboard communication)
         .route("/internal/cli-notify", post(handlers::handle_cli_notification))
         .route("/internal/shutdown", post(handlers::shutdown_handler))
@@ -0,0 +1,597 @@
+//! Scheduled reports
+//!
+//! Jobs are managed with `ie dashboard schedule add/list/remove` and stored in
+//! `~/.intent-engine/dashboard-schedules.json`. A running Dashboard checks the
+//! store once a minute, so new jobs apply without a restart.
+//!
+//! Each job has a five-field cron expression evaluated in local time
+//! (`minute hour day-of-month month day-of-week`, e.g. `0 9 * * 1-5` for
+//! weekday mornings), the report to generate and where it goes: a JSON file
+//! in a directory, or a JSON POST to a webhook.
+
+use crate::error::{IntentError, Result};
+use crate::report::ReportManager;
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const GLOBAL_DIR: &str = ".intent-engine";
+const SCHEDULES_FILE: &str = "dashboard-schedules.json";
+
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A five-field cron expression
+///
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `8-18/2`). Day of week runs from 0 (Sunday) to 7 (Sunday again).
+/// As in cron, when both day fields are restricted a day matching either runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl std::str::FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                s
+            ));
+        };
+        let field = |value: &str, name: &str, min: u32, max: u32| {
+            parse_field(value, min, max)
+                .map_err(|e| format!("Invalid {} field '{}' in '{}': {}", name, value, s, e))
+        };
+
+        let mut weekdays = field(weekday, "day-of-week", 0, 7)?;
+        // 7 is another name for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            minutes: field(minute, "minute", 0, 59)?,
+            hours: field(hour, "hour", 0, 23)?,
+            days: field(day, "day-of-month", 1, 31)?,
+            months: field(month, "month", 1, 12)?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+}
+
+/// Bit set of the values a cron field allows
+fn parse_field(field: &str, min: u32, max: u32) -> std::result::Result<u64, String> {
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .ok()
+            .filter(|n| (min..=max).contains(n))
+            .ok_or_else(|| format!("expected a number from {} to {}", min, max))
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid step '{}'", step))?;
+                (range, step)
+            },
+            None => (part, 1),
+        };
+        let (from, to) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((from, to)) => (number(from)?, number(to)?),
+                // `5/10` means from 5 to the end, every 10
+                None if step > 1 => (number(range)?, max),
+                None => {
+                    let n = number(range)?;
+                    (n, n)
+                },
+            },
+        };
+        if from > to {
+            return Err(format!("empty range '{}'", range));
+        }
+        for value in (from..=to).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    /// Whether the schedule fires in the minute of `at`
+    pub fn matches(&self, at: DateTime<Local>) -> bool {
+        let has = |bits: u64, value: u32| bits & (1 << value) != 0;
+        let day = has(self.days, at.day());
+        let weekday = has(self.weekdays, at.weekday().num_days_from_sunday());
+        let day_matches = if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        };
+        has(self.minutes, at.minute())
+            && has(self.hours, at.hour())
+            && has(self.months, at.month())
+            && day_matches
+    }
+
+    /// First minute after `after` the schedule fires in, within a year
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut at = start_of_minute(after) + chrono::Duration::minutes(1);
+        for _ in 0..366 * 24 * 60 {
+            if self.matches(at) {
+                return Some(at);
+            }
+            at += chrono::Duration::minutes(1);
+        }
+        None
+    }
+}
+
+fn start_of_minute(at: DateTime<Local>) -> DateTime<Local> {
+    at.with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(at)
+}
+
+/// What a job generates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum ScheduledReport {
+    /// Task and event summary of a recent window, as in `ReportManager::generate_report`
+    Summary { since: String },
+    /// Tasks in progress, longest running first
+    Aging,
+    /// Cycle times with percentiles
+    CycleTime {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        since: Option<String>,
+    },
+    /// Progress of one milestone
+    Milestone { name: String },
+}
+
+impl ScheduledReport {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Summary { .. } => "summary",
+            Self::Aging => "aging",
+            Self::CycleTime { .. } => "cycle-time",
+            Self::Milestone { .. } => "milestone",
+        }
+    }
+
+    /// Build from `ie dashboard schedule add` arguments
+    pub fn from_args(kind: &str, since: Option<String>, milestone: Option<String>) -> Result<Self> {
+        if let Some(since) = &since {
+            crate::time_utils::parse_duration(since)?;
+        }
+        match kind {
+            "summary" => Ok(Self::Summary {
+                since: since.unwrap_or_else(|| "1d".to_string()),
+            }),
+            "aging" => Ok(Self::Aging),
+            "cycle-time" => Ok(Self::CycleTime { since }),
+            "milestone" => milestone
+                .map(|name| Self::Milestone { name })
+                .ok_or_else(|| {
+                    IntentError::InvalidInput(
+                        "A milestone report needs --milestone <NAME>".to_string(),
+                    )
+                }),
+            other => Err(IntentError::InvalidInput(format!(
+                "Unknown report '{}'. Expected one of: summary, aging, cycle-time, milestone",
+                other
+            ))),
+        }
+    }
+
+    /// Generate the report from a project database
+    pub async fn generate(&self, pool: &sqlx::SqlitePool) -> Result<serde_json::Value> {
+        let reports = ReportManager::new(pool);
+        let value = match self {
+            Self::Summary { since } => serde_json::to_value(
+                reports
+                    .generate_report(Some(since.clone()), None, None, None, false)
+                    .await?,
+            )?,
+            Self::Aging => serde_json::to_value(reports.aging_report().await?)?,
+            Self::CycleTime { since } => {
+                serde_json::to_value(reports.cycle_time_report(since.as_deref(), true).await?)?
+            },
+            Self::Milestone { name } => {
+                serde_json::to_value(reports.milestone_report(name).await?)?
+            },
+        };
+        Ok(value)
+    }
+}
+
+/// Where a job's report goes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTarget {
+    /// A new JSON file per run in this directory
+    Directory(PathBuf),
+    /// A JSON POST per run
+    Webhook(String),
+}
+
+impl std::fmt::Display for ReportTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Directory(dir) => write!(f, "{}", dir.display()),
+            Self::Webhook(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// A scheduled report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Short identifier used for removal
+    pub id: String,
+    /// Five-field cron expression, local time
+    pub cron: String,
+    pub project_path: PathBuf,
+    pub db_path: PathBuf,
+    pub report: ScheduledReport,
+    pub target: ReportTarget,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+/// What a job delivers: the report and where it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledReportOutput {
+    pub job_id: String,
+    pub project_path: PathBuf,
+    pub report: String,
+    pub generated_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl ScheduledJob {
+    pub fn schedule(&self) -> std::result::Result<CronSchedule, String> {
+        self.cron.parse()
+    }
+
+    /// Generate the report and deliver it to the target
+    ///
+    /// Returns the file written for directory targets.
+    pub async fn run(&self) -> Result<Option<PathBuf>> {
+        let pool =
+            crate::db::create_pool_for(&self.db_path, crate::db::pool::PoolProfile::Dashboard)
+                .await?;
+        let data = self.report.generate(&pool).await;
+        pool.close().await;
+
+        let output = ScheduledReportOutput {
+            job_id: self.id.clone(),
+            project_path: self.project_path.clone(),
+            report: self.report.name().to_string(),
+            generated_at: Utc::now(),
+            data: data?,
+        };
+        match &self.target {
+            ReportTarget::Directory(dir) => {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join(format!(
+                    "{}-{}-{}.json",
+                    output.report,
+                    self.id,
+                    output
+                        .generated_at
+                        .with_timezone(&Local)
+                        .format("%Y%m%d-%H%M")
+                ));
+                std::fs::write(&path, serde_json::to_string_pretty(&output)?)?;
+                Ok(Some(path))
+            },
+            ReportTarget::Webhook(url) => {
+                let client = reqwest::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()
+                    .map_err(|e| IntentError::OtherError(e.into()))?;
+                client
+                    .post(url)
+                    .json(&output)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| IntentError::OtherError(e.into()))?;
+                Ok(None)
+            },
+        }
+    }
+}
+
+/// Persistent job store
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ScheduleStore {
+    #[serde(default)]
+    pub jobs: Vec<ScheduledJob>,
+}
+
+impl ScheduleStore {
+    /// Default location of the store
+    pub fn store_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(GLOBAL_DIR).join(SCHEDULES_FILE))
+    }
+
+    /// Load the store from the default location
+    pub fn load() -> Self {
+        Self::store_path()
+            .map(|p| Self::load_from(&p))
+            .unwrap_or_default()
+    }
+
+    /// Load the store from a specific file (missing or invalid file → empty store)
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the store to the default location
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = Self::store_path() else {
+            return Ok(());
+        };
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Add a job after checking its cron expression and target
+    pub fn add_job(
+        &mut self,
+        cron: &str,
+        project_path: PathBuf,
+        db_path: PathBuf,
+        report: ScheduledReport,
+        target: ReportTarget,
+    ) -> Result<ScheduledJob> {
+        let schedule: CronSchedule = cron.parse().map_err(IntentError::InvalidInput)?;
+        if let ReportTarget::Webhook(url) = &target {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(IntentError::InvalidInput(format!(
+                    "Webhook '{}' must be an http:// or https:// URL",
+                    url
+                )));
+            }
+        }
+        if schedule.next_after(Local::now()).is_none() {
+            return Err(IntentError::InvalidInput(format!(
+                "Cron expression '{}' never fires",
+                cron
+            )));
+        }
+
+        let job = ScheduledJob {
+            id: format!("{:08x}", rand::random::<u32>()),
+            cron: cron.split_whitespace().collect::<Vec<_>>().join(" "),
+            project_path,
+            db_path,
+            report,
+            target,
+            created_at: Utc::now(),
+            last_run_at: None,
+        };
+        self.jobs.push(job.clone());
+        Ok(job)
+    }
+
+    /// Remove a job by id; returns whether it existed
+    pub fn remove_job(&mut self, id: &str) -> bool {
+        let initial_len = self.jobs.len();
+        self.jobs.retain(|job| job.id != id);
+        self.jobs.len() < initial_len
+    }
+
+    /// Jobs due in the minute of `at` that have not run in it yet
+    pub fn due(&self, at: DateTime<Local>) -> Vec<ScheduledJob> {
+        let minute = start_of_minute(at);
+        self.jobs
+            .iter()
+            .filter(|job| job.schedule().is_ok_and(|s| s.matches(minute)))
+            .filter(|job| {
+                job.last_run_at
+                    .is_none_or(|last| last.with_timezone(&Local) < minute)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Run due jobs once a minute, for as long as the Dashboard runs
+///
+/// The store is re-read every minute so jobs added or removed from the CLI
+/// apply right away. Failures are logged and the job waits for its next slot.
+pub async fn run_scheduler() {
+    loop {
+        let now = Local::now();
+        let wait = 60 - u64::from(now.second());
+        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+
+        let now = Local::now();
+        for job in ScheduleStore::load().due(now) {
+            match job.run().await {
+                Ok(path) => tracing::info!(
+                    job = %job.id,
+                    report = job.report.name(),
+                    file = ?path,
+                    "Scheduled report delivered"
+                ),
+                Err(e) => tracing::warn!(
+                    job = %job.id,
+                    report = job.report.name(),
+                    error = %e,
+                    "Scheduled report failed"
+                ),
+            }
+
+            // Re-read so a job removed meanwhile is not written back
+            let mut store = ScheduleStore::load();
+            if let Some(stored) = store.jobs.iter_mut().find(|j| j.id == job.id) {
+                stored.last_run_at = Some(Utc::now());
+                if let Err(e) = store.save() {
+                    tracing::warn!(error = %e, "Failed to record scheduled report run");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_cron_weekday_mornings() {
+        let cron: CronSchedule = "0 9 * * 1-5".parse().unwrap();
+        // 2026-10-19 is a Monday, 2026-10-18 a Sunday
+        assert!(cron.matches(at(2026, 10, 19, 9, 0)));
+        assert!(!cron.matches(at(2026, 10, 19, 9, 1)));
+        assert!(!cron.matches(at(2026, 10, 18, 9, 0)));
+        assert_eq!(
+            cron.next_after(at(2026, 10, 16, 9, 0)),
+            Some(at(2026, 10, 19, 9, 0))
+        );
+    }
+
+    #[test]
+    fn test_cron_fields() {
+        let every_quarter: CronSchedule = "*/15 8-18/2 * * *".parse().unwrap();
+        assert!(every_quarter.matches(at(2026, 1, 1, 10, 45)));
+        assert!(!every_quarter.matches(at(2026, 1, 1, 11, 45)));
+
+        // Either day field may match when both are restricted; 7 is Sunday
+        let cron: CronSchedule = "30 8 1 * 7".parse().unwrap();
+        assert!(cron.matches(at(2026, 10, 1, 8, 30)));
+        assert!(cron.matches(at(2026, 10, 18, 8, 30)));
+        assert!(!cron.matches(at(2026, 10, 19, 8, 30)));
+
+        assert!("0 9 * *".parse::<CronSchedule>().is_err());
+        assert!("60 9 * * *".parse::<CronSchedule>().is_err());
+        assert!("0 9 * * 5-1".parse::<CronSchedule>().is_err());
+        assert!("0 9 31 2 *"
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(Local::now())
+            .is_none());
+    }
+
+    #[test]
+    fn test_due_runs_once_per_minute() {
+        let mut store = ScheduleStore::default();
+        let job = store
+            .add_job(
+                "0 9 * * *",
+                PathBuf::from("/p"),
+                PathBuf::from("/p/.intent-engine/project.db"),
+                ScheduledReport::Aging,
+                ReportTarget::Directory(PathBuf::from("/reports")),
+            )
+            .unwrap();
+
+        let nine = at(2026, 10, 19, 9, 0);
+        assert_eq!(store.due(nine + chrono::Duration::seconds(5)).len(), 1);
+        assert!(store.due(at(2026, 10, 19, 9, 1)).is_empty());
+
+        store.jobs[0].last_run_at = Some((nine + chrono::Duration::seconds(2)).with_timezone(&Utc));
+        assert!(store.due(nine + chrono::Duration::seconds(30)).is_empty());
+
+        assert!(store.remove_job(&job.id));
+        assert!(!store.remove_job(&job.id));
+    }
+
+    #[test]
+    fn test_add_job_validates_input() {
+        let mut store = ScheduleStore::default();
+        let add = |store: &mut ScheduleStore, cron: &str, target: ReportTarget| {
+            store.add_job(
+                cron,
+                PathBuf::from("/p"),
+                PathBuf::from("/p/db"),
+                ScheduledReport::Aging,
+                target,
+            )
+        };
+        assert!(add(
+            &mut store,
+            "0 25 * * *",
+            ReportTarget::Directory("/r".into())
+        )
+        .is_err());
+        assert!(add(
+            &mut store,
+            "0 9 * * *",
+            ReportTarget::Webhook("ftp://x".into())
+        )
+        .is_err());
+        assert!(store.jobs.is_empty());
+
+        assert!(ScheduledReport::from_args("milestone", None, None).is_err());
+        assert!(ScheduledReport::from_args("burndown", None, None).is_err());
+        assert_eq!(
+            ScheduledReport::from_args("summary", None, None).unwrap(),
+            ScheduledReport::Summary {
+                since: "1d".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_report_file() {
+        let ctx = crate::test_utils::test_helpers::TestContext::new().await;
+        let project = ctx._temp_dir.path().to_path_buf();
+        let out = project.join("reports");
+        let mut store = ScheduleStore::default();
+        let job = store
+            .add_job(
+                "0 9 * * *",
+                project.clone(),
+                project.join(".intent-engine/project.db"),
+                ScheduledReport::Summary {
+                    since: "1d".to_string(),
+                },
+                ReportTarget::Directory(out.clone()),
+            )
+            .unwrap();
+
+        let path = job.run().await.unwrap().unwrap();
+        assert!(path.starts_with(&out));
+        let written: ScheduledReportOutput =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(written.report, "summary");
+        assert_eq!(written.data["summary"]["total_tasks"], 0);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::db::pool::PoolProfile;
+use crate::db::{create_pool_for, create_readonly_pool_for};
 use anyhow::{Context, Result};
 use axum::{
     extract::{Path, State},
@@ -45,6 +47,10 @@ pub struct AppState {
     pub port: u16,
     /// WebSocket state for real-time connections
     pub ws_state: super::websocket::WebSocketState,
+    /// Counters exposed on `/metrics`
+    pub metrics: Arc<super::metrics::DashboardMetrics>,
+    /// Cached results of the hot read paths, cleared on every write
+    pub query_cache: super::cache::QueryCache,
     /// Shutdown signal sender (for graceful shutdown via HTTP)
     pub shutdown_tx: Arc<tokio::sync::Mutex<Option<tokio::sync::oneshot::Sender<()>>>>,
 }
@@ -52,21 +58,73 @@ pub struct AppState {
 impl AppState {
     /// Get database pool for a project (opens on demand - SQLite is fast)
     pub async fn get_db_pool(&self, project_path: &PathBuf) -> Result<SqlitePool, String> {
+        self.open_db_pool(project_path, false).await
+    }
+
+    /// Read-only pool for a project, for handlers that never write: a slow
+    /// read then cannot hold up the agents writing to the same database
+    pub async fn get_readonly_db_pool(&self, project_path: &PathBuf) -> Result<SqlitePool, String> {
+        self.open_db_pool(project_path, true).await
+    }
+
+    /// Get database pool for the active project
+    pub async fn get_active_db_pool(&self) -> Result<SqlitePool, String> {
+        let active_path = self.active_project_path.read().await.clone();
+        self.get_db_pool(&active_path).await
+    }
+
+    /// Read-only pool for the active project
+    pub async fn get_active_readonly_db_pool(&self) -> Result<SqlitePool, String> {
+        let active_path = self.active_project_path.read().await.clone();
+        self.get_readonly_db_pool(&active_path).await
+    }
+
+    async fn open_db_pool(
+        &self,
+        project_path: &PathBuf,
+        read_only: bool,
+    ) -> Result<SqlitePool, String> {
         let projects = self.known_projects.read().await;
         if let Some(info) = projects.get(project_path) {
-            let db_url = format!("sqlite://{}", info.db_path.display());
-            SqlitePool::connect(&db_url)
-                .await
-                .map_err(|e| format!("Failed to connect to database: {}", e))
+            // create_pool would create an empty file for a removed project
+            if !info.db_path.exists() {
+                self.metrics.record_db_pool_open(false);
+                return Err(format!("Database not found: {}", info.db_path.display()));
+            }
+            let result = if read_only {
+                create_readonly_pool_for(&info.db_path, PoolProfile::Dashboard).await
+            } else {
+                create_pool_for(&info.db_path, PoolProfile::Dashboard).await
+            };
+            self.metrics.record_db_pool_open(result.is_ok());
+            result.map_err(|e| format!("Failed to connect to database: {}", e))
         } else {
             Err(format!("Project not found: {}", project_path.display()))
         }
     }
 
-    /// Get database pool for the active project
-    pub async fn get_active_db_pool(&self) -> Result<SqlitePool, String> {
-        let active_path = self.active_project_path.read().await.clone();
-        self.get_db_pool(&active_path).await
+    /// Replay the offline notification queues of every known project to a UI
+    /// client (see [`crate::notifications::queue`])
+    pub async fn replay_offline_notifications(
+        &self,
+        conn: &super::websocket::UiConnection,
+    ) -> usize {
+        let keys: Vec<PathBuf> = self.known_projects.read().await.keys().cloned().collect();
+        let mut queues = Vec::new();
+        for key in keys {
+            match self.get_db_pool(&key).await {
+                Ok(pool) => queues.push((key.display().to_string(), pool)),
+                Err(e) => tracing::debug!("Skipping offline queue of {}: {}", key.display(), e),
+            }
+        }
+        conn.replay_offline_queues(&queues).await
+    }
+
+    /// Key used in `known_projects`: the canonical path, so that the same
+    /// project reached through different spellings (symlinks, Windows case)
+    /// is only listed once
+    pub fn project_key(path: &std::path::Path) -> PathBuf {
+        path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
     }
 
     /// Add a new project (or update existing)
@@ -80,21 +138,55 @@ impl AppState {
             return Err(format!("Database not found: {}", db_path.display()));
         }
 
+        let key = Self::project_key(&path);
+        let mut projects = self.known_projects.write().await;
+        if projects.contains_key(&key) {
+            return Ok(());
+        }
+
         let name = path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown")
             .to_string();
 
-        let info = ProjectInfo {
-            name,
-            path: path.clone(),
-            db_path,
-        };
+        projects.insert(
+            key,
+            ProjectInfo {
+                name,
+                path,
+                db_path,
+            },
+        );
+        Ok(())
+    }
 
+    /// Pick up projects registered in the global registry since startup
+    ///
+    /// Returns the number of newly added projects.
+    pub async fn sync_registry(&self) -> usize {
+        let registry = crate::global_projects::ProjectsRegistry::load();
         let mut projects = self.known_projects.write().await;
-        projects.insert(path, info);
-        Ok(())
+        let mut added = 0;
+
+        for entry in registry.valid_projects() {
+            let path = PathBuf::from(&entry.path);
+            let key = Self::project_key(&path);
+            if projects.contains_key(&key) {
+                continue;
+            }
+            projects.insert(
+                key,
+                ProjectInfo {
+                    name: entry.display_name(),
+                    db_path: path.join(".intent-engine").join("project.db"),
+                    path,
+                },
+            );
+            added += 1;
+        }
+
+        added
     }
 
     /// Get active project info
@@ -106,27 +198,29 @@ impl AppState {
 
     /// Switch active project
     pub async fn switch_active_project(&self, path: PathBuf) -> Result<(), String> {
+        let key = Self::project_key(&path);
         let projects = self.known_projects.read().await;
-        if !projects.contains_key(&path) {
+        if !projects.contains_key(&key) {
             return Err(format!("Project not registered: {}", path.display()));
         }
         drop(projects);
 
         let mut active = self.active_project_path.write().await;
-        *active = path;
+        *active = key;
         Ok(())
     }
 
     /// Remove a project from known projects and global registry
-    pub async fn remove_project(&self, path: &PathBuf) -> Result<(), String> {
+    pub async fn remove_project(&self, path: &std::path::Path) -> Result<(), String> {
         // Don't allow removing the host project
-        if path.as_path() == std::path::Path::new(&self.host_project.path) {
+        let key = Self::project_key(path);
+        if key == Self::project_key(std::path::Path::new(&self.host_project.path)) {
             return Err("Cannot remove the host project".to_string());
         }
 
         // Remove from known projects
         let mut projects = self.known_projects.write().await;
-        projects.remove(path);
+        projects.remove(&key);
 
         // Remove from global registry
         let path_str = path.to_string_lossy().to_string();
@@ -162,6 +256,20 @@ struct HealthResponse {
     status: String,
     service: String,
     version: String,
+    pid: u32,
+    port: u16,
+    started_at: chrono::DateTime<chrono::Utc>,
+    uptime_seconds: u64,
+    /// Times the server was restarted after a crash (see `daemon::supervise`)
+    restarts: u32,
+    clients: ClientCounts,
+}
+
+/// Connected WebSocket clients, by kind
+#[derive(Serialize)]
+struct ClientCounts {
+    ui: usize,
+    mcp: usize,
 }
 
 /// Project info response (for API)
@@ -202,52 +310,19 @@ impl DashboardServer {
 
     /// Run the Dashboard server
     pub async fn run(self) -> Result<()> {
-        // Initialize known projects with the host project
+        // Refuse to serve at all if the token store is damaged
+        super::auth::TokenStore::load().context("Cannot start the Dashboard")?;
+
+        // Initialize known projects with the host project; the rest of the
+        // global registry is loaded once the state exists (see sync_registry)
         let mut known_projects = HashMap::new();
         let host_info = ProjectInfo {
             name: self.project_name.clone(),
             path: self.project_path.clone(),
             db_path: self.db_path.clone(),
         };
-        // Use canonical path string as key for consistent comparison on Windows
-        let host_key = self
-            .project_path
-            .canonicalize()
-            .unwrap_or_else(|_| self.project_path.clone());
-        known_projects.insert(host_key, host_info);
-
-        // Load projects from global registry
-        let registry = crate::global_projects::ProjectsRegistry::load();
-        for entry in registry.projects {
-            let path = PathBuf::from(&entry.path);
-            // Use canonical path for comparison to handle Windows path normalization
-            let canonical_path = path.canonicalize().unwrap_or_else(|_| path.clone());
-            // Skip if already added (host project)
-            if known_projects.contains_key(&canonical_path) {
-                continue;
-            }
-            let db_path = path.join(".intent-engine").join("project.db");
-            if db_path.exists() {
-                let name = entry.name.unwrap_or_else(|| {
-                    path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
-                        .to_string()
-                });
-                known_projects.insert(
-                    canonical_path,
-                    ProjectInfo {
-                        name,
-                        path,
-                        db_path,
-                    },
-                );
-            }
-        }
-        tracing::info!(
-            "Loaded {} projects from global registry",
-            known_projects.len()
-        );
+        let host_key = AppState::project_key(&self.project_path);
+        known_projects.insert(host_key.clone(), host_info);
 
         // Create shared state
         let ws_state = websocket::WebSocketState::new();
@@ -266,13 +341,39 @@ impl DashboardServer {
 
         let state = AppState {
             known_projects: Arc::new(RwLock::new(known_projects)),
-            active_project_path: Arc::new(RwLock::new(self.project_path.clone())),
+            active_project_path: Arc::new(RwLock::new(host_key)),
             host_project: host_project_info,
             port: self.port,
             ws_state,
+            metrics: super::metrics::DashboardMetrics::new(),
+            query_cache: super::cache::QueryCache::new(),
             shutdown_tx: Arc::new(tokio::sync::Mutex::new(Some(shutdown_tx))),
         };
 
+        let added = state.sync_registry().await;
+        tracing::info!("Loaded {} projects from global registry", added + 1);
+
+        tokio::spawn(run_maintenance(state.clone()));
+        tokio::spawn(run_focus_reminders(state.clone()));
+        tokio::spawn(super::schedule::run_scheduler());
+
+        // A shared Postgres project also changes under other clients; relay
+        // their edits to the Dashboard
+        #[cfg(feature = "postgres")]
+        if crate::config::ProjectConfig::for_project_root(&self.project_path)
+            .is_ok_and(|config| config.storage.backend == crate::config::StorageBackend::Postgres)
+        {
+            let ws_state = state.ws_state.clone();
+            let project_path = self.project_path.display().to_string();
+            tokio::spawn(async move {
+                if let Err(e) =
+                    crate::postgres::listener::forward_to_dashboard(ws_state, project_path).await
+                {
+                    tracing::warn!(error = %e, "Postgres change listener stopped");
+                }
+            });
+        }
+
         // Build router
         let app = create_router(state);
 
@@ -284,6 +385,9 @@ impl DashboardServer {
             .with_context(|| format!("Failed to bind to {}", addr))?;
 
         tracing::info!(address = %addr, "Dashboard server listening");
+        if let Err(e) = super::daemon::PidFile::write(self.port, &self.project_path) {
+            tracing::warn!(error = %e, "Failed to write PID file");
+        }
         tracing::warn!(
             port = self.port,
             "⚠️  Dashboard is accessible from external IPs"
@@ -309,14 +413,137 @@ impl DashboardServer {
             .await
             .context("Server error")?;
 
+        super::daemon::PidFile::remove_own(self.port);
         tracing::info!("Dashboard server shut down successfully");
         Ok(())
     }
 }
 
+/// How often the Dashboard maintains the databases it serves
+const MAINTENANCE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Periodically checkpoint, analyze and (when worthwhile) vacuum every known
+/// project database, so a long-lived Dashboard does not grow huge WAL files,
+/// refresh the priority aging of waiting tasks, sweep stale doing tasks and
+/// link URLs mentioned in specs and events
+async fn run_maintenance(state: AppState) {
+    let mut interval = tokio::time::interval(MAINTENANCE_INTERVAL);
+    // The first tick fires immediately; skip it so startup stays quick
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        let db_paths: Vec<PathBuf> = state
+            .known_projects
+            .read()
+            .await
+            .values()
+            .map(|info| info.db_path.clone())
+            .collect();
+
+        for db_path in db_paths {
+            if !db_path.exists() {
+                continue;
+            }
+            let result = async {
+                let pool = create_pool_for(&db_path, PoolProfile::Dashboard).await?;
+                let report = crate::db::maintenance::maintain(&pool, false).await;
+                if let Err(e) = crate::priority::age_tasks(&pool).await {
+                    tracing::warn!(db_path = %db_path.display(), error = %e, "Priority aging failed");
+                }
+                let stale = crate::tasks::TaskManager::new(&pool)
+                    .with_source(crate::db::models::TransitionSource::Dashboard)
+                    .sweep_stale(None, false)
+                    .await;
+                if let Err(e) = stale {
+                    tracing::warn!(db_path = %db_path.display(), error = %e, "Stale task sweep failed");
+                }
+                if let Err(e) = crate::links::extract(&pool).await {
+                    tracing::warn!(db_path = %db_path.display(), error = %e, "Link extraction failed");
+                }
+                if let Err(e) = crate::otel::export_finished(&pool).await {
+                    tracing::warn!(db_path = %db_path.display(), error = %e, "Trace export failed");
+                }
+                state.query_cache.invalidate_all();
+                pool.close().await;
+                report
+            }
+            .await;
+            match result {
+                Ok(report) => tracing::debug!(
+                    db_path = %db_path.display(),
+                    vacuumed = report.vacuum.is_some(),
+                    checkpoint_busy = report.checkpoint.busy,
+                    "Database maintenance done"
+                ),
+                Err(e) => tracing::warn!(
+                    db_path = %db_path.display(),
+                    error = %e,
+                    "Database maintenance failed"
+                ),
+            }
+        }
+    }
+}
+
+/// How often the Dashboard looks for focus sessions whose time is up
+const FOCUS_REMINDER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Remind of focus sessions (`ie focus start`) whose time is up: a desktop
+/// notification (and webhooks) plus a `focus_reminder` message to the UI
+async fn run_focus_reminders(state: AppState) {
+    let mut interval = tokio::time::interval(FOCUS_REMINDER_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        let projects: Vec<ProjectInfo> = state
+            .known_projects
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+
+        for project in projects {
+            if !project.db_path.exists() {
+                continue;
+            }
+            let result = async {
+                let pool = create_pool_for(&project.db_path, PoolProfile::Dashboard).await?;
+                let due = crate::focus::due_reminders(&pool).await?;
+                let notifier = crate::notifications::desktop::DesktopNotifier::new(&pool);
+                let path = project.path.display().to_string();
+                for status in &due {
+                    notifier
+                        .focus_ended(status.task.id, &status.task.name, status.session.minutes)
+                        .await;
+                    if let Ok(message) =
+                        websocket::ProtocolMessage::new("focus_reminder", status).to_json()
+                    {
+                        state.ws_state.broadcast_to_project(&path, &message).await;
+                    }
+                }
+                pool.close().await;
+                anyhow::Ok(())
+            }
+            .await;
+            if let Err(e) = result {
+                tracing::warn!(
+                    db_path = %project.db_path.display(),
+                    error = %e,
+                    "Focus reminder check failed"
+                );
+            }
+        }
+    }
+}
+
 /// Create the Axum router with all routes and middleware
 fn create_router(state: AppState) -> Router {
-    use super::routes;
+    use super::{handlers, metrics, routes};
+
+    let metrics_state = state.metrics.clone();
+    let cache_state = state.query_cache.clone();
 
     // Combine basic API routes with full API routes
     let api_routes = Router::new()
@@ -334,6 +561,8 @@ fn create_router(state: AppState) -> Router {
         .route("/assets/*path", get(serve_assets))
         // API routes under /api prefix
         .nest("/api", api_routes)
+        // Prometheus scrape endpoint
+        .route("/metrics", get(handlers::metrics))
         // WebSocket routes (now use full AppState)
         .route("/ws/mcp", get(websocket::handle_mcp_websocket))
         .route("/ws/ui", get(websocket::handle_ui_websocket))
@@ -341,6 +570,21 @@ fn create_router(state: AppState) -> Router {
         .fallback(not_found_handler)
         // Add state
         .with_state(state)
+        // Token auth (no-op until a token is created); covers WebSocket upgrades too
+        .layer(axum::middleware::from_fn_with_state(
+            super::auth::AuthState::default(),
+            super::auth::require_token,
+        ))
+        // Writes (and CLI notifications of writes) invalidate cached reads
+        .layer(axum::middleware::from_fn_with_state(
+            cache_state,
+            super::cache::invalidate_on_write,
+        ))
+        // Count responses (including 401s from the auth layer)
+        .layer(axum::middleware::from_fn_with_state(
+            metrics_state,
+            metrics::track_http,
+        ))
         // Add middleware
         .layer(
             CorsLayer::new()
@@ -396,6 +640,7 @@ async fn serve_static(Path(path): Path<String>) -> impl IntoResponse {
             Json(serde_json::json!({
                 "error": "File not found",
                 "code": "NOT_FOUND",
+                "error_code": "IE0109",
                 "path": path
             })),
         )
@@ -430,6 +675,7 @@ async fn serve_assets(Path(path): Path<String>) -> impl IntoResponse {
             Json(serde_json::json!({
                 "error": "Asset not found",
                 "code": "NOT_FOUND",
+                "error_code": "IE0109",
                 "path": full_path
             })),
         )
@@ -438,11 +684,21 @@ async fn serve_assets(Path(path): Path<String>) -> impl IntoResponse {
 }
 
 /// Health check handler
-async fn health_handler() -> Json<HealthResponse> {
+async fn health_handler(State(state): State<AppState>) -> Json<HealthResponse> {
+    let uptime = state.metrics.uptime();
     Json(HealthResponse {
         status: "healthy".to_string(),
         service: "intent-engine-dashboard".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        pid: std::process::id(),
+        port: state.port,
+        started_at: chrono::Utc::now() - chrono::Duration::from_std(uptime).unwrap_or_default(),
+        uptime_seconds: uptime.as_secs(),
+        restarts: super::daemon::restart_count(),
+        clients: ClientCounts {
+            ui: state.ws_state.ui_connections.read().await.len(),
+            mcp: state.ws_state.mcp_connections.read().await.len(),
+        },
     })
 }
 
@@ -494,7 +750,8 @@ async fn not_found_handler() -> impl IntoResponse {
         StatusCode::NOT_FOUND,
         Json(serde_json::json!({
             "error": "Not found",
-            "code": "NOT_FOUND"
+            "code": "NOT_FOUND",
+            "error_code": "IE0109"
         })),
     )
 }
@@ -509,11 +766,19 @@ mod tests {
             status: "healthy".to_string(),
             service: "test".to_string(),
             version: "1.0.0".to_string(),
+            pid: 42,
+            port: 11391,
+            started_at: chrono::Utc::now(),
+            uptime_seconds: 90,
+            restarts: 0,
+            clients: ClientCounts { ui: 1, mcp: 0 },
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("healthy"));
         assert!(json.contains("test"));
+        assert!(json.contains(r#""uptime_seconds":90"#));
+        assert!(json.contains(r#""clients":{"ui":1,"mcp":0}"#));
     }
 
     #[test]
@@ -0,0 +1,576 @@
+//! Running the Dashboard as an OS service
+//!
+//! `ie dashboard install-service` registers the Dashboard of the current
+//! project with the service manager of the platform, so it starts at login
+//! (or boot on Windows) and no longer dies with the terminal that started it:
+//!
+//! - Linux: a systemd user unit, `~/.config/systemd/user/intent-engine-dashboard.service`
+//! - macOS: a launchd agent, `~/Library/LaunchAgents/com.intent-engine.dashboard.plist`
+//! - Windows: a service in the Service Control Manager, running
+//!   `ie dashboard run-service`
+//!
+//! All three restart the Dashboard when it crashes but not after
+//! `ie dashboard stop`, which exits cleanly. Stdout is never a terminal
+//! under a service manager, so the Dashboard logs to the daily rotated
+//! `~/.intent-engine/logs/dashboard.log` and prunes old files itself.
+
+use crate::error::{IntentError, Result};
+use std::path::{Path, PathBuf};
+
+/// Name of the systemd unit and of the Windows service
+pub const SERVICE_NAME: &str = "intent-engine-dashboard";
+
+/// Label of the launchd agent
+pub const LAUNCHD_LABEL: &str = "com.intent-engine.dashboard";
+
+/// Seconds the service manager waits before restarting a crashed Dashboard
+const RESTART_DELAY_SECS: u64 = 5;
+
+/// Service managers `install-service` knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceManager {
+    Systemd,
+    Launchd,
+    Windows,
+}
+
+impl ServiceManager {
+    /// The service manager of this platform
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            ServiceManager::Windows
+        } else if cfg!(target_os = "macos") {
+            ServiceManager::Launchd
+        } else {
+            ServiceManager::Systemd
+        }
+    }
+}
+
+/// What the service runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceSpec {
+    /// The `ie` binary
+    pub exe: PathBuf,
+    /// Project whose Dashboard the service starts
+    pub project_root: PathBuf,
+    pub port: u16,
+}
+
+impl ServiceSpec {
+    /// Arguments of `exe` the service is started with
+    fn args(&self, manager: ServiceManager) -> Vec<String> {
+        let port = self.port.to_string();
+        match manager {
+            ServiceManager::Windows => vec![
+                "dashboard".to_string(),
+                "run-service".to_string(),
+                "--port".to_string(),
+                port,
+                "--project".to_string(),
+                self.project_root.display().to_string(),
+            ],
+            _ => vec![
+                "dashboard".to_string(),
+                "start".to_string(),
+                "--port".to_string(),
+                port,
+            ],
+        }
+    }
+
+    /// Text of the unit file, plist or `sc.exe` commands that set up the service
+    pub fn render(&self, manager: ServiceManager) -> String {
+        match manager {
+            ServiceManager::Systemd => self.systemd_unit(),
+            ServiceManager::Launchd => self.launchd_plist(),
+            ServiceManager::Windows => self.sc_commands(),
+        }
+    }
+
+    fn systemd_unit(&self) -> String {
+        let exec_start: Vec<String> = std::iter::once(self.exe.display().to_string())
+            .chain(self.args(ServiceManager::Systemd))
+            .map(|arg| systemd_quote(&arg))
+            .collect();
+        format!(
+            "[Unit]\n\
+             Description=Intent-Engine Dashboard\n\
+             After=network.target\n\
+             \n\
+             [Service]\n\
+             Type=simple\n\
+             WorkingDirectory={}\n\
+             ExecStart={}\n\
+             Restart=on-failure\n\
+             RestartSec={}\n\
+             \n\
+             [Install]\n\
+             WantedBy=default.target\n",
+            self.project_root.display().to_string().replace('%', "%%"),
+            exec_start.join(" "),
+            RESTART_DELAY_SECS,
+        )
+    }
+
+    fn launchd_plist(&self) -> String {
+        let arguments: String = std::iter::once(self.exe.display().to_string())
+            .chain(self.args(ServiceManager::Launchd))
+            .map(|arg| format!("        <string>{}</string>\n", xml_escape(&arg)))
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{}    </array>
+    <key>WorkingDirectory</key>
+    <string>{}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ThrottleInterval</key>
+    <integer>{}</integer>
+</dict>
+</plist>
+"#,
+            LAUNCHD_LABEL,
+            arguments,
+            xml_escape(&self.project_root.display().to_string()),
+            RESTART_DELAY_SECS,
+        )
+    }
+
+    /// What `install` does on Windows, as the equivalent `sc.exe` commands
+    fn sc_commands(&self) -> String {
+        let bin_path: Vec<String> = std::iter::once(self.exe.display().to_string())
+            .chain(self.args(ServiceManager::Windows))
+            .map(|arg| windows_quote(&arg))
+            .collect();
+        let delay_ms = RESTART_DELAY_SECS * 1000;
+        format!(
+            "sc.exe create {name} binPath= \"{bin}\" start= auto DisplayName= \"Intent-Engine Dashboard\"\n\
+             sc.exe failure {name} reset= 86400 actions= restart/{delay}/restart/{delay}/restart/{delay}\n\
+             sc.exe start {name}\n",
+            name = SERVICE_NAME,
+            bin = bin_path.join(" ").replace('"', "\\\""),
+            delay = delay_ms,
+        )
+    }
+}
+
+/// Quote one word of a systemd `ExecStart=` line
+fn systemd_quote(arg: &str) -> String {
+    let arg = arg.replace('%', "%%");
+    if !arg.is_empty()
+        && !arg
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';'))
+    {
+        return arg;
+    }
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote one argument of a Windows command line
+fn windows_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.chars().any(|c| c.is_whitespace() || c == '"') {
+        return arg.to_string();
+    }
+    format!("\"{}\"", arg.replace('"', "\\\""))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Where the unit file or plist of `manager` goes (none on Windows)
+pub fn definition_path(manager: ServiceManager) -> Result<Option<PathBuf>> {
+    let home = || {
+        dirs::home_dir().ok_or_else(|| {
+            IntentError::InvalidInput("Could not determine home directory".to_string())
+        })
+    };
+    Ok(match manager {
+        ServiceManager::Systemd => Some(
+            dirs::config_dir()
+                .map(Ok)
+                .unwrap_or_else(|| home().map(|h| h.join(".config")))?
+                .join("systemd")
+                .join("user")
+                .join(format!("{}.service", SERVICE_NAME)),
+        ),
+        ServiceManager::Launchd => Some(
+            home()?
+                .join("Library")
+                .join("LaunchAgents")
+                .join(format!("{}.plist", LAUNCHD_LABEL)),
+        ),
+        ServiceManager::Windows => None,
+    })
+}
+
+/// Register and start the service; returns where it was registered
+///
+/// An existing registration is an error unless `replace` is set.
+pub fn install(spec: &ServiceSpec, replace: bool) -> Result<String> {
+    let manager = ServiceManager::current();
+    match definition_path(manager)? {
+        Some(path) => {
+            if path.exists() {
+                if !replace {
+                    return Err(IntentError::InvalidInput(format!(
+                        "{} already exists; use --force to replace it",
+                        path.display()
+                    )));
+                }
+                deactivate(manager, &path);
+            }
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, spec.render(manager))?;
+            activate(manager, &path).map_err(|e| {
+                IntentError::InvalidInput(format!(
+                    "Wrote {}, but could not start it: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            Ok(path.display().to_string())
+        },
+        None => {
+            scm_install(spec, replace)?;
+            Ok(format!("Windows service '{}'", SERVICE_NAME))
+        },
+    }
+}
+
+/// Stop the service and remove its registration
+pub fn uninstall() -> Result<String> {
+    let manager = ServiceManager::current();
+    match definition_path(manager)? {
+        Some(path) => {
+            if !path.exists() {
+                return Err(IntentError::InvalidInput(format!(
+                    "No Dashboard service is installed ({} does not exist)",
+                    path.display()
+                )));
+            }
+            deactivate(manager, &path);
+            std::fs::remove_file(&path)?;
+            if manager == ServiceManager::Systemd {
+                run(&["systemctl", "--user", "daemon-reload"]).ok();
+            }
+            Ok(path.display().to_string())
+        },
+        None => {
+            scm_uninstall()?;
+            Ok(format!("Windows service '{}'", SERVICE_NAME))
+        },
+    }
+}
+
+/// Load a freshly written definition and start the Dashboard
+fn activate(manager: ServiceManager, path: &Path) -> std::result::Result<(), String> {
+    let path = path.display().to_string();
+    match manager {
+        ServiceManager::Systemd => {
+            run(&["systemctl", "--user", "daemon-reload"])?;
+            run(&["systemctl", "--user", "enable", "--now", SERVICE_NAME])
+        },
+        ServiceManager::Launchd => run(&["launchctl", "load", "-w", &path]),
+        ServiceManager::Windows => Ok(()),
+    }
+}
+
+/// Stop the Dashboard and unload the definition, ignoring one that is not loaded
+fn deactivate(manager: ServiceManager, path: &Path) {
+    let path = path.display().to_string();
+    let result = match manager {
+        ServiceManager::Systemd => run(&["systemctl", "--user", "disable", "--now", SERVICE_NAME]),
+        ServiceManager::Launchd => run(&["launchctl", "unload", "-w", &path]),
+        ServiceManager::Windows => Ok(()),
+    };
+    if let Err(e) = result {
+        tracing::debug!(error = %e, "Service was not loaded");
+    }
+}
+
+fn run(command: &[&str]) -> std::result::Result<(), String> {
+    let output = std::process::Command::new(command[0])
+        .args(&command[1..])
+        .output()
+        .map_err(|e| format!("could not run '{}': {}", command.join(" "), e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' failed: {}",
+            command.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(windows)]
+pub use scm::run as run_service;
+#[cfg(windows)]
+use scm::{install as scm_install, uninstall as scm_uninstall};
+
+#[cfg(not(windows))]
+fn scm_install(_spec: &ServiceSpec, _replace: bool) -> Result<()> {
+    Err(windows_only())
+}
+
+#[cfg(not(windows))]
+fn scm_uninstall() -> Result<()> {
+    Err(windows_only())
+}
+
+/// Hand this process to the Service Control Manager (`ie dashboard run-service`)
+#[cfg(not(windows))]
+pub fn run_service(_port: u16, _project_root: PathBuf) -> Result<()> {
+    Err(windows_only())
+}
+
+#[cfg(not(windows))]
+fn windows_only() -> IntentError {
+    IntentError::InvalidInput("Windows services are only available on Windows".to_string())
+}
+
+/// The Service Control Manager side of the Windows service
+#[cfg(windows)]
+mod scm {
+    use super::{ServiceSpec, RESTART_DELAY_SECS, SERVICE_NAME};
+    use crate::error::{IntentError, Result};
+    use std::ffi::OsString;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+    use windows_service::service::{
+        ServiceAccess, ServiceAction, ServiceActionType, ServiceControl, ServiceControlAccept,
+        ServiceErrorControl, ServiceExitCode, ServiceFailureActions, ServiceFailureResetPeriod,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+
+    fn scm_err(action: &str, e: windows_service::Error) -> IntentError {
+        IntentError::OtherError(anyhow::anyhow!(
+            "Failed to {} (run from an elevated prompt): {}",
+            action,
+            e
+        ))
+    }
+
+    pub(super) fn install(spec: &ServiceSpec, replace: bool) -> Result<()> {
+        let manager = ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .map_err(|e| scm_err("open the Service Control Manager", e))?;
+
+        if replace {
+            // A missing service is fine here
+            uninstall().ok();
+        }
+
+        let info = ServiceInfo {
+            name: OsString::from(SERVICE_NAME),
+            display_name: OsString::from("Intent-Engine Dashboard"),
+            service_type: ServiceType::OWN_PROCESS,
+            start_type: ServiceStartType::AutoStart,
+            error_control: ServiceErrorControl::Normal,
+            executable_path: spec.exe.clone(),
+            launch_arguments: spec
+                .args(super::ServiceManager::Windows)
+                .into_iter()
+                .map(OsString::from)
+                .collect(),
+            dependencies: vec![],
+            account_name: None,
+            account_password: None,
+        };
+        let service = manager
+            .create_service(&info, ServiceAccess::CHANGE_CONFIG | ServiceAccess::START)
+            .map_err(|e| scm_err("create the service", e))?;
+        service
+            .set_description("Intent-Engine Dashboard web UI")
+            .map_err(|e| scm_err("describe the service", e))?;
+
+        let restart = || ServiceAction {
+            action_type: ServiceActionType::Restart,
+            delay: Duration::from_secs(RESTART_DELAY_SECS),
+        };
+        service
+            .update_failure_actions(ServiceFailureActions {
+                reset_period: ServiceFailureResetPeriod::After(Duration::from_secs(86400)),
+                reboot_msg: None,
+                command: None,
+                actions: Some(vec![restart(), restart(), restart()]),
+            })
+            .map_err(|e| scm_err("set the restart policy", e))?;
+        service
+            .start::<&str>(&[])
+            .map_err(|e| scm_err("start the service", e))?;
+        Ok(())
+    }
+
+    pub(super) fn uninstall() -> Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .map_err(|e| scm_err("open the Service Control Manager", e))?;
+        let service = manager
+            .open_service(
+                SERVICE_NAME,
+                ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+            )
+            .map_err(|e| scm_err("open the service", e))?;
+        let status = service
+            .query_status()
+            .map_err(|e| scm_err("query the service", e))?;
+        if status.current_state != ServiceState::Stopped {
+            service.stop().map_err(|e| scm_err("stop the service", e))?;
+        }
+        service
+            .delete()
+            .map_err(|e| scm_err("delete the service", e))?;
+        Ok(())
+    }
+
+    /// Port and project of the Dashboard the service runs, set before dispatching
+    static TARGET: OnceLock<(u16, std::path::PathBuf)> = OnceLock::new();
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    /// Hand this process to the Service Control Manager
+    ///
+    /// Blocks until the service stops. Only works when the SCM started the
+    /// process, i.e. as `ie dashboard run-service`.
+    pub fn run(port: u16, project_root: std::path::PathBuf) -> Result<()> {
+        let _ = TARGET.set((port, project_root));
+        service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+            .map_err(|e| scm_err("connect to the Service Control Manager", e))
+    }
+
+    fn service_main(_arguments: Vec<OsString>) {
+        if let Err(e) = run_service() {
+            tracing::error!(error = %e, "Dashboard service failed");
+        }
+    }
+
+    fn run_service() -> Result<()> {
+        let (port, project_root) = TARGET
+            .get()
+            .cloned()
+            .ok_or_else(|| IntentError::InvalidInput("Service target not set".to_string()))?;
+
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let stop_tx = std::sync::Mutex::new(Some(stop_tx));
+        let status_handle =
+            service_control_handler::register(SERVICE_NAME, move |control| match control {
+                ServiceControl::Stop | ServiceControl::Shutdown => {
+                    if let Some(tx) = stop_tx.lock().ok().and_then(|mut tx| tx.take()) {
+                        let _ = tx.send(());
+                    }
+                    ServiceControlHandlerResult::NoError
+                },
+                ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+                _ => ServiceControlHandlerResult::NotImplemented,
+            })
+            .map_err(|e| scm_err("register the control handler", e))?;
+
+        let set_state = |state: ServiceState, exit_code: u32| {
+            status_handle.set_service_status(ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted: if state == ServiceState::Running {
+                    ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN
+                } else {
+                    ServiceControlAccept::empty()
+                },
+                exit_code: ServiceExitCode::Win32(exit_code),
+                checkpoint: 0,
+                wait_hint: Duration::default(),
+                process_id: None,
+            })
+        };
+        set_state(ServiceState::Running, 0).map_err(|e| scm_err("report status", e))?;
+
+        let result = tokio::runtime::Runtime::new()?.block_on(async move {
+            std::env::set_current_dir(&project_root)?;
+            let ctx = crate::project::ProjectContext::load_or_init().await?;
+            let server =
+                crate::dashboard::server::DashboardServer::new(port, ctx.root, ctx.db_path).await?;
+            tokio::select! {
+                result = server.run() => result.map_err(IntentError::OtherError),
+                _ = stop_rx => Ok(()),
+            }
+        });
+
+        // A non-zero exit code makes the SCM apply the restart policy
+        let exit_code = if result.is_ok() { 0 } else { 1 };
+        set_state(ServiceState::Stopped, exit_code).map_err(|e| scm_err("report status", e))?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ServiceSpec {
+        ServiceSpec {
+            exe: PathBuf::from("/opt/my tools/ie"),
+            project_root: PathBuf::from("/home/dev/100% <done>"),
+            port: 11500,
+        }
+    }
+
+    #[test]
+    fn test_systemd_unit() {
+        let unit = spec().render(ServiceManager::Systemd);
+        assert!(unit.contains("ExecStart=\"/opt/my tools/ie\" dashboard start --port 11500\n"));
+        assert!(unit.contains("WorkingDirectory=/home/dev/100%% <done>\n"));
+        assert!(unit.contains("Restart=on-failure\n"));
+        assert!(unit.contains("WantedBy=default.target\n"));
+    }
+
+    #[test]
+    fn test_launchd_plist() {
+        let plist = spec().render(ServiceManager::Launchd);
+        assert!(plist.contains("<string>com.intent-engine.dashboard</string>"));
+        assert!(plist.contains("<string>/opt/my tools/ie</string>"));
+        assert!(plist.contains("<string>11500</string>"));
+        assert!(plist.contains("<string>/home/dev/100% &lt;done&gt;</string>"));
+        assert!(plist.contains("<key>SuccessfulExit</key>\n        <false/>"));
+    }
+
+    #[test]
+    fn test_sc_commands() {
+        let commands = spec().render(ServiceManager::Windows);
+        assert!(commands.starts_with(
+            "sc.exe create intent-engine-dashboard binPath= \"\\\"/opt/my tools/ie\\\" \
+             dashboard run-service --port 11500 --project \\\"/home/dev/100% <done>\\\"\""
+        ));
+        assert!(commands.contains("actions= restart/5000/restart/5000/restart/5000"));
+    }
+
+    #[test]
+    fn test_systemd_quote() {
+        assert_eq!(systemd_quote("plain"), "plain");
+        assert_eq!(systemd_quote(""), "\"\"");
+        assert_eq!(systemd_quote("a\"b"), "\"a\\\"b\"");
+        assert_eq!(systemd_quote("50%"), "50%%");
+    }
+}
@@ -13,9 +13,22 @@ use axum::{
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, Notify, RwLock};
+
+/// Capabilities announced to UI clients in `welcome`: topic subscriptions
+/// and `db_patch` deltas (protocol v2, opt-in through `subscribe`)
+pub const UI_CAPABILITIES: &[&str] = &["topics", "deltas"];
+
+/// Maximum number of outbound messages buffered per WebSocket client
+pub const CLIENT_QUEUE_CAPACITY: usize = 256;
+
+/// Consecutive dropped messages after which a client is treated as stalled
+/// and disconnected
+pub const SLOW_CONSUMER_DROP_LIMIT: u64 = 1024;
 
 /// Protocol message wrapper - wraps all WebSocket messages with version and timestamp
 #[derive(Debug, Serialize, Deserialize)]
@@ -89,19 +102,397 @@ pub struct ProjectInfo {
     pub is_online: bool,
 }
 
+// ============================================================================
+// Bounded Client Queues
+// ============================================================================
+
+/// Why a message could not be queued for a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueError {
+    /// The client's queue is full; the message was dropped
+    Full,
+    /// The client has disconnected
+    Closed,
+}
+
+/// Per-client queue counters
+#[derive(Debug, Default)]
+pub struct QueueMetrics {
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    consecutive_drops: AtomicU64,
+    /// Set when notifications were dropped; the client is told to resync
+    /// with a single aggregated message once its queue drains
+    resync_pending: AtomicBool,
+}
+
+/// Snapshot of a client's queue state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientQueueStats {
+    /// Client kind ("ui" or "mcp")
+    pub client: String,
+    /// Project path for MCP clients
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    pub capacity: usize,
+    pub queued: usize,
+    pub sent: u64,
+    pub dropped: u64,
+}
+
+/// Sending half of a bounded client queue
+///
+/// Sends never block: when the queue is full the message is dropped, the
+/// client is flagged for a resync, and after [`SLOW_CONSUMER_DROP_LIMIT`]
+/// consecutive drops the client is asked to disconnect.
+#[derive(Debug, Clone)]
+pub struct ClientSender {
+    tx: mpsc::Sender<Message>,
+    metrics: Arc<QueueMetrics>,
+    disconnect: Arc<Notify>,
+}
+
+impl ClientSender {
+    /// Create a bounded client queue with the given capacity
+    pub fn channel(capacity: usize) -> (Self, mpsc::Receiver<Message>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        let sender = Self {
+            tx,
+            metrics: Arc::new(QueueMetrics::default()),
+            disconnect: Arc::new(Notify::new()),
+        };
+        (sender, rx)
+    }
+
+    /// Queue a message without waiting
+    pub fn send(&self, msg: Message) -> Result<(), QueueError> {
+        if self.metrics.resync_pending.load(Ordering::Relaxed) {
+            self.try_send_resync();
+        }
+
+        match self.tx.try_send(msg) {
+            Ok(()) => {
+                self.metrics.sent.fetch_add(1, Ordering::Relaxed);
+                self.metrics.consecutive_drops.store(0, Ordering::Relaxed);
+                Ok(())
+            },
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.record_drop();
+                Err(QueueError::Full)
+            },
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(QueueError::Closed),
+        }
+    }
+
+    /// Whether the receiving side has gone away
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Whether both senders feed the same client queue
+    pub fn same_channel(&self, other: &ClientSender) -> bool {
+        self.tx.same_channel(&other.tx)
+    }
+
+    /// Resolves when this client has been flagged as a stalled consumer
+    pub async fn stalled(&self) {
+        self.disconnect.notified().await
+    }
+
+    /// Current queue snapshot
+    pub fn stats(&self, client: &str, project_path: Option<String>) -> ClientQueueStats {
+        let capacity = self.tx.max_capacity();
+        ClientQueueStats {
+            client: client.to_string(),
+            project_path,
+            capacity,
+            queued: capacity - self.tx.capacity(),
+            sent: self.metrics.sent.load(Ordering::Relaxed),
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    fn record_drop(&self) {
+        self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+        self.metrics.resync_pending.store(true, Ordering::Relaxed);
+        let consecutive = self
+            .metrics
+            .consecutive_drops
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        if consecutive == SLOW_CONSUMER_DROP_LIMIT {
+            tracing::warn!(
+                dropped = consecutive,
+                "WebSocket client is not draining its queue, disconnecting"
+            );
+            self.disconnect.notify_one();
+        }
+    }
+
+    /// Replace everything dropped so far with a single resync notice
+    fn try_send_resync(&self) {
+        let dropped = self.metrics.dropped.load(Ordering::Relaxed);
+        let json = match ProtocolMessage::new("resync", ResyncPayload { dropped }).to_json() {
+            Ok(json) => json,
+            Err(_) => return,
+        };
+        if self.tx.try_send(Message::Text(json)).is_ok() {
+            self.metrics.resync_pending.store(false, Ordering::Relaxed);
+            self.metrics.sent.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Forward queued messages to the socket until either side closes
+async fn forward_queue<S>(mut rx: mpsc::Receiver<Message>, mut sender: S)
+where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    while let Some(msg) = rx.recv().await {
+        if sender.send(msg).await.is_err() {
+            break;
+        }
+    }
+}
+
 /// MCP connection entry
 #[derive(Debug)]
 pub struct McpConnection {
-    pub tx: tokio::sync::mpsc::UnboundedSender<Message>,
+    pub tx: ClientSender,
     pub project: ProjectInfo,
     pub connected_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// UI connection entry
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UiConnection {
-    pub tx: tokio::sync::mpsc::UnboundedSender<Message>,
+    pub tx: ClientSender,
     pub connected_at: chrono::DateTime<chrono::Utc>,
+    /// Projects whose notifications this client receives
+    pub subscriptions: ProjectSubscriptions,
+    /// Entity topics this client follows (protocol v2)
+    pub topics: TopicSubscriptions,
+}
+
+impl UiConnection {
+    /// Send the offline queues of `projects` (path and pool) to this client,
+    /// oldest first
+    ///
+    /// Each queue is drained, so the notifications are replayed only once.
+    pub async fn replay_offline_queues(&self, projects: &[(String, SqlitePool)]) -> usize {
+        let mut replayed = 0;
+        for (project_path, pool) in projects {
+            if !self.subscriptions.matches(&project_topic(project_path)) {
+                continue;
+            }
+            let payloads = match crate::notifications::queue::OfflineQueue::new(pool)
+                .drain()
+                .await
+            {
+                Ok(payloads) => payloads,
+                Err(e) => {
+                    // Databases not yet migrated by a newer CLI have no queue
+                    tracing::debug!(project = %project_path, error = %e, "No offline notification queue");
+                    continue;
+                },
+            };
+            for payload in payloads {
+                let Ok(full) = ProtocolMessage::new("db_operation", &payload).to_json() else {
+                    continue;
+                };
+                if let Some(message) = self.topics.message_for(&payload, &full) {
+                    let _ = self.tx.send(Message::Text(message));
+                    replayed += 1;
+                }
+            }
+        }
+        replayed
+    }
+}
+
+// ============================================================================
+// Per-project Topics
+// ============================================================================
+
+/// Topic key for a project: its canonical path, so that subscriptions match
+/// notifications regardless of how the path was spelled
+pub fn project_topic(project_path: &str) -> String {
+    std::path::Path::new(project_path)
+        .canonicalize()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| project_path.to_string())
+}
+
+/// Projects a UI client is subscribed to
+///
+/// New clients receive notifications for every project, which keeps existing
+/// clients working unchanged. A `subscribe` message narrows this to a set of
+/// project topics; subscribing to `"*"` restores the default.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSubscriptions(Arc<std::sync::RwLock<Option<HashSet<String>>>>);
+
+impl ProjectSubscriptions {
+    /// Replace the subscription set (`None` = all projects)
+    pub fn set(&self, topics: Option<HashSet<String>>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = topics;
+    }
+
+    /// Whether notifications for `topic` should be delivered
+    pub fn matches(&self, topic: &str) -> bool {
+        match &*self.0.read().unwrap_or_else(|e| e.into_inner()) {
+            None => true,
+            Some(topics) => topics.contains(topic),
+        }
+    }
+
+    /// Current topics (`None` = all projects)
+    pub fn topics(&self) -> Option<Vec<String>> {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|topics| {
+                let mut topics: Vec<String> = topics.iter().cloned().collect();
+                topics.sort();
+                topics
+            })
+    }
+}
+
+// ============================================================================
+// Entity Topics (protocol v2)
+// ============================================================================
+
+/// Topic of task notifications
+pub const TASKS_TOPIC: &str = "tasks";
+
+/// Topic of workspace (focus) notifications
+pub const WORKSPACE_TOPIC: &str = "workspace";
+
+/// Topic of the events of one task
+pub fn task_events_topic(task_id: i64) -> String {
+    format!("events:task:{}", task_id)
+}
+
+/// Entity topics a UI client follows, and the objects it was last sent
+///
+/// Clients start on protocol v1: every notification of their projects, with
+/// full objects. Subscribing with `topics` switches a client to v2: only
+/// notifications of those topics are delivered, and an update of an object
+/// the client already received arrives as `db_patch`, a JSON Patch
+/// (RFC 6902) of the top-level fields that changed.
+#[derive(Debug, Clone, Default)]
+pub struct TopicSubscriptions(Arc<std::sync::Mutex<Option<TopicState>>>);
+
+#[derive(Debug, Default)]
+struct TopicState {
+    topics: HashSet<String>,
+    /// "entity:id" → the object as last sent
+    sent: HashMap<String, serde_json::Value>,
+}
+
+impl TopicSubscriptions {
+    /// Follow `topics` (v2), or everything with full objects (`None`, v1)
+    pub fn set(&self, topics: Option<HashSet<String>>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = topics.map(|topics| TopicState {
+            topics,
+            sent: HashMap::new(),
+        });
+    }
+
+    /// Current topics (`None` = v1, everything)
+    pub fn topics(&self) -> Option<Vec<String>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|state| {
+                let mut topics: Vec<String> = state.topics.iter().cloned().collect();
+                topics.sort();
+                topics
+            })
+    }
+
+    /// Whether notifications of `topic` should be delivered
+    pub fn follows(&self, topic: &str) -> bool {
+        match &*self.0.lock().unwrap_or_else(|e| e.into_inner()) {
+            None => true,
+            Some(state) => state.topics.contains(topic),
+        }
+    }
+
+    /// What to send this client for `payload`: `full` (the `db_operation`
+    /// message), a `db_patch` message, or nothing
+    pub fn message_for(&self, payload: &DatabaseOperationPayload, full: &str) -> Option<String> {
+        let mut guard = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(state) = guard.as_mut() else {
+            return Some(full.to_string());
+        };
+        if !state.topics.contains(&payload.topic()) {
+            return None;
+        }
+        let [id] = payload.affected_ids[..] else {
+            return Some(full.to_string());
+        };
+        let key = format!("{}:{}", payload.entity, id);
+        let data = match (&payload.data, payload.operation.as_str()) {
+            (Some(data), "create" | "update") => data,
+            _ => {
+                state.sent.remove(&key);
+                return Some(full.to_string());
+            },
+        };
+        let Some(previous) = state.sent.insert(key, data.clone()) else {
+            return Some(full.to_string());
+        };
+
+        let patch = json_patch(&previous, data);
+        if patch.is_empty() {
+            return None;
+        }
+        ProtocolMessage::new(
+            "db_patch",
+            DatabasePatchPayload {
+                operation: payload.operation.clone(),
+                entity: payload.entity.clone(),
+                affected_ids: payload.affected_ids.clone(),
+                patch,
+                project_path: payload.project_path.clone(),
+            },
+        )
+        .to_json()
+        .ok()
+    }
+}
+
+/// JSON Patch turning object `from` into `to`, one operation per top-level field
+///
+/// Whole fields are replaced, so applying the patch to a newer copy of the
+/// object is still correct for the fields it touches.
+pub fn json_patch(from: &serde_json::Value, to: &serde_json::Value) -> Vec<serde_json::Value> {
+    use serde_json::json;
+
+    let (Some(from), Some(to)) = (from.as_object(), to.as_object()) else {
+        return if from == to {
+            Vec::new()
+        } else {
+            vec![json!({"op": "replace", "path": "", "value": to})]
+        };
+    };
+    let pointer = |key: &str| format!("/{}", key.replace('~', "~0").replace('/', "~1"));
+
+    let mut patch = Vec::new();
+    for (key, value) in to {
+        match from.get(key) {
+            Some(old) if old == value => {},
+            Some(_) => patch.push(json!({"op": "replace", "path": pointer(key), "value": value})),
+            None => patch.push(json!({"op": "add", "path": pointer(key), "value": value})),
+        }
+    }
+    for key in from.keys().filter(|key| !to.contains_key(*key)) {
+        patch.push(json!({"op": "remove", "path": pointer(key)}));
+    }
+    patch
 }
 
 /// Shared WebSocket state
@@ -128,6 +519,8 @@ impl WebSocketState {
     }
 
     /// Broadcast message to all UI connections
+    ///
+    /// Never waits on a slow client: full queues drop the message (see [`ClientSender`]).
     pub async fn broadcast_to_ui(&self, message: &str) {
         let connections = self.ui_connections.read().await;
         for conn in connections.iter() {
@@ -135,6 +528,118 @@ impl WebSocketState {
         }
     }
 
+    /// Send a project notification to the UI clients subscribed to that project
+    pub async fn broadcast_to_project(&self, project_path: &str, message: &str) {
+        let topic = project_topic(project_path);
+        let connections = self.ui_connections.read().await;
+        for conn in connections
+            .iter()
+            .filter(|conn| conn.subscriptions.matches(&topic))
+        {
+            let _ = conn.tx.send(Message::Text(message.to_string()));
+        }
+    }
+
+    /// Send a database notification to the UI clients following its project and topic
+    ///
+    /// v1 clients get the full `db_operation` message, v2 clients a
+    /// `db_patch` when they already hold the object (see [`TopicSubscriptions`]).
+    /// Returns how many clients follow the project: 0 means nobody saw it.
+    pub async fn broadcast_db_operation(&self, payload: &DatabaseOperationPayload) -> usize {
+        let full = match ProtocolMessage::new("db_operation", payload).to_json() {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to serialize notification message");
+                return 0;
+            },
+        };
+        let project = project_topic(&payload.project_path);
+        let connections = self.ui_connections.read().await;
+        let mut followers = 0;
+        for conn in connections
+            .iter()
+            .filter(|conn| conn.subscriptions.matches(&project))
+        {
+            followers += 1;
+            if let Some(message) = conn.topics.message_for(payload, &full) {
+                let _ = conn.tx.send(Message::Text(message));
+            }
+        }
+        followers
+    }
+
+    /// Send a notification of `topic` to the UI clients following it and its
+    /// project (every project when `project_path` is None)
+    ///
+    /// Returns how many clients follow the project, whatever their topics.
+    pub async fn broadcast_to_topic(
+        &self,
+        project_path: Option<&str>,
+        topic: &str,
+        message: &str,
+    ) -> usize {
+        let project = project_path.map(project_topic);
+        let connections = self.ui_connections.read().await;
+        let mut followers = 0;
+        for conn in connections.iter().filter(|conn| {
+            project
+                .as_deref()
+                .is_none_or(|project| conn.subscriptions.matches(project))
+        }) {
+            followers += 1;
+            if conn.topics.follows(topic) {
+                let _ = conn.tx.send(Message::Text(message.to_string()));
+            }
+        }
+        followers
+    }
+
+    /// Send a protocol message to the MCP client registered for a project
+    ///
+    /// Returns whether such a client is connected and the message was queued.
+    pub async fn send_to_mcp<T: Serialize>(
+        &self,
+        project_path: &str,
+        message_type: &str,
+        payload: T,
+    ) -> bool {
+        let project = project_topic(project_path);
+        let connections = self.mcp_connections.read().await;
+        let Some(conn) = connections
+            .iter()
+            .find(|(path, _)| project_topic(path) == project)
+            .map(|(_, conn)| conn)
+        else {
+            return false;
+        };
+        match send_protocol_message(&conn.tx, message_type, payload) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::debug!(error = %e, project = %project_path, "MCP notification not sent");
+                false
+            },
+        }
+    }
+
+    /// Queue statistics for every connected client
+    pub async fn queue_stats(&self) -> Vec<ClientQueueStats> {
+        let mut stats: Vec<ClientQueueStats> = self
+            .ui_connections
+            .read()
+            .await
+            .iter()
+            .map(|conn| conn.tx.stats("ui", None))
+            .collect();
+        stats.extend(
+            self.mcp_connections
+                .read()
+                .await
+                .iter()
+                .map(|(path, conn)| conn.tx.stats("mcp", Some(path.clone()))),
+        );
+        stats
+    }
+
     /// Get list of all online projects from in-memory state
     pub async fn get_online_projects(&self) -> Vec<ProjectInfo> {
         // Read from in-memory MCP connections
@@ -272,6 +777,42 @@ pub struct WelcomePayload {
     pub session_id: String,
 }
 
+/// Payload for resync message (server → UI)
+///
+/// Sent in place of notifications that were dropped because the client's
+/// queue was full; the client should refetch its state.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResyncPayload {
+    /// Total messages dropped for this client so far
+    pub dropped: u64,
+}
+
+/// Payload for subscribe message (UI → server)
+///
+/// Omitted fields keep their current subscription.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscribePayload {
+    /// Project paths to follow; `"*"` follows every project
+    #[serde(default)]
+    pub projects: Option<Vec<String>>,
+    /// Entity topics to follow (`tasks`, `workspace`, `events:task:{id}`),
+    /// switching to protocol v2 deliveries; `"*"` goes back to v1
+    #[serde(default)]
+    pub topics: Option<Vec<String>>,
+}
+
+/// Payload for subscribed message (server → UI)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscribedPayload {
+    /// Normalized project topics (empty when `all` is set)
+    pub projects: Vec<String>,
+    /// Whether the client receives notifications for every project
+    pub all: bool,
+    /// Entity topics followed; absent for v1 clients, which receive everything
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topics: Option<Vec<String>>,
+}
+
 /// Payload for goodbye message
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GoodbyePayload {
@@ -292,6 +833,19 @@ pub struct ErrorPayload {
     pub details: Option<serde_json::Value>,
 }
 
+/// Payload for db_patch message (server → v2 UI clients)
+///
+/// Same fields as [`DatabaseOperationPayload`], with a JSON Patch against the
+/// object last sent to the client instead of the full object.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatabasePatchPayload {
+    pub operation: String,
+    pub entity: String,
+    pub affected_ids: Vec<i64>,
+    pub patch: Vec<serde_json::Value>,
+    pub project_path: String,
+}
+
 /// Standard error codes (Protocol v1.0 Section 4.5)
 pub mod error_codes {
     pub const UNSUPPORTED_VERSION: &str = "unsupported_version";
@@ -301,6 +855,16 @@ pub mod error_codes {
     pub const INTERNAL_ERROR: &str = "internal_error";
 }
 
+/// Payload for `tasks_unblocked`, sent to the project's MCP client
+///
+/// Lists tasks whose last unfinished blocker was just completed, so agents
+/// learn about newly available work without polling.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TasksUnblockedPayload {
+    pub project_path: String,
+    pub tasks: Vec<crate::db::models::UnblockedTask>,
+}
+
 /// Payload for database operation notifications
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DatabaseOperationPayload {
@@ -314,7 +878,7 @@ pub struct DatabaseOperationPayload {
     pub affected_ids: Vec<i64>,
 
     /// Full data for create/update operations
-    /// Empty for delete operations
+    /// Empty for delete operations, except `task_id` for events
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
 
@@ -411,8 +975,42 @@ impl DatabaseOperationPayload {
     }
 
     /// Helper: Create payload for event deleted
-    pub fn event_deleted(event_id: i64, project_path: impl Into<String>) -> Self {
-        Self::new("delete", "event", vec![event_id], None, project_path)
+    pub fn event_deleted(event_id: i64, task_id: i64, project_path: impl Into<String>) -> Self {
+        Self::new(
+            "delete",
+            "event",
+            vec![event_id],
+            Some(serde_json::json!({ "task_id": task_id })),
+            project_path,
+        )
+    }
+
+    /// Helper: Create payload for tasks that became ready to start
+    pub fn tasks_unblocked(
+        tasks: &[crate::db::models::UnblockedTask],
+        project_path: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            "unblocked",
+            "task",
+            tasks.iter().map(|task| task.id).collect(),
+            serde_json::to_value(tasks).ok(),
+            project_path,
+        )
+    }
+
+    /// Topic the notification belongs to
+    pub fn topic(&self) -> String {
+        let task_id = self
+            .data
+            .as_ref()
+            .and_then(|data| data.get("task_id"))
+            .and_then(|id| id.as_i64());
+        match (self.entity.as_str(), task_id) {
+            ("task", _) => TASKS_TOPIC.to_string(),
+            ("event", Some(task_id)) => task_events_topic(task_id),
+            (entity, _) => entity.to_string(),
+        }
     }
 }
 
@@ -422,7 +1020,7 @@ impl DatabaseOperationPayload {
 
 /// Send a protocol message through a channel
 fn send_protocol_message<T: Serialize>(
-    tx: &tokio::sync::mpsc::UnboundedSender<Message>,
+    tx: &ClientSender,
     message_type: &str,
     payload: T,
 ) -> Result<(), String> {
@@ -431,8 +1029,10 @@ fn send_protocol_message<T: Serialize>(
         .to_json()
         .map_err(|e| format!("Failed to serialize message: {}", e))?;
 
-    tx.send(Message::Text(json))
-        .map_err(|_| "Failed to send message: channel closed".to_string())
+    tx.send(Message::Text(json)).map_err(|e| match e {
+        QueueError::Full => "Failed to send message: queue full".to_string(),
+        QueueError::Closed => "Failed to send message: channel closed".to_string(),
+    })
 }
 
 /// Handle MCP WebSocket connections
@@ -440,21 +1040,20 @@ pub async fn handle_mcp_websocket(
     ws: WebSocketUpgrade,
     State(app_state): State<crate::dashboard::server::AppState>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_mcp_socket(socket, app_state.ws_state))
+    ws.on_upgrade(move |socket| handle_mcp_socket(socket, app_state.ws_state, app_state.metrics))
 }
 
-async fn handle_mcp_socket(socket: WebSocket, state: WebSocketState) {
-    let (mut sender, mut receiver) = socket.split();
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+async fn handle_mcp_socket(
+    socket: WebSocket,
+    state: WebSocketState,
+    metrics: Arc<super::metrics::DashboardMetrics>,
+) {
+    let (sender, mut receiver) = socket.split();
+    let (tx, rx) = ClientSender::channel(CLIENT_QUEUE_CAPACITY);
+    let stall_tx = tx.clone();
 
     // Spawn task to forward messages from channel to WebSocket
-    let mut send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if sender.send(msg).await.is_err() {
-                break;
-            }
-        }
-    });
+    let mut send_task = tokio::spawn(forward_queue(rx, sender));
 
     // Variables to track this connection
     let mut project_path: Option<String> = None;
@@ -474,11 +1073,12 @@ async fn handle_mcp_socket(socket: WebSocket, state: WebSocketState) {
 
         loop {
             interval.tick().await;
-            // Send ping to request heartbeat from client
-            if send_protocol_message(&heartbeat_tx, "ping", EmptyPayload {}).is_err() {
+            if heartbeat_tx.is_closed() {
                 // Connection closed
                 break;
             }
+            // Send ping to request heartbeat from client (skipped if the queue is full)
+            let _ = send_protocol_message(&heartbeat_tx, "ping", EmptyPayload {});
             tracing::trace!("Sent heartbeat ping to MCP client");
         }
     });
@@ -512,6 +1112,8 @@ async fn handle_mcp_socket(socket: WebSocket, state: WebSocketState) {
                         },
                     };
 
+                    metrics.record_mcp_message(&parsed_msg.message_type);
+
                     match parsed_msg.message_type.as_str() {
                         "hello" => {
                             // Parse hello payload
@@ -665,7 +1267,16 @@ async fn handle_mcp_socket(socket: WebSocket, state: WebSocketState) {
                             tracing::debug!(
                                 "Received db_operation from MCP, forwarding to UI clients"
                             );
-                            state_for_recv.broadcast_to_ui(&text).await;
+                            match parsed_msg
+                                .payload
+                                .get("project_path")
+                                .and_then(|p| p.as_str())
+                            {
+                                Some(path) => {
+                                    state_for_recv.broadcast_to_project(path, &text).await
+                                },
+                                None => state_for_recv.broadcast_to_ui(&text).await,
+                            }
                         },
                         _ => {
                             tracing::warn!("Unknown message type: {}", parsed_msg.message_type);
@@ -689,6 +1300,17 @@ async fn handle_mcp_socket(socket: WebSocket, state: WebSocketState) {
             recv_task.abort();
             heartbeat_task.abort();
         }
+        _ = stall_tx.stalled() => {
+            tracing::warn!("Disconnecting stalled MCP client");
+            send_task.abort();
+            heartbeat_task.abort();
+            recv_task.abort();
+            state
+                .mcp_connections
+                .write()
+                .await
+                .retain(|_, conn| !conn.tx.same_channel(&stall_tx));
+        }
         project_path_result = (&mut recv_task) => {
             send_task.abort();
             heartbeat_task.abort();
@@ -728,32 +1350,28 @@ pub async fn handle_ui_websocket(
 }
 
 async fn handle_ui_socket(socket: WebSocket, app_state: crate::dashboard::server::AppState) {
-    let (mut sender, mut receiver) = socket.split();
-    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let (sender, mut receiver) = socket.split();
+    let (tx, rx) = ClientSender::channel(CLIENT_QUEUE_CAPACITY);
+    let stall_tx = tx.clone();
 
     // Spawn task to forward messages from channel to WebSocket
-    let mut send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if sender.send(msg).await.is_err() {
-                break;
-            }
-        }
-    });
+    let mut send_task = tokio::spawn(forward_queue(rx, sender));
 
     // Protocol v1.0 Compliance: Wait for client to send "hello" first
     // The "init" message will be sent after receiving "hello" and sending "welcome"
     // This is handled in the message loop below
 
     // Register this UI connection
+    let subscriptions = ProjectSubscriptions::default();
+    let topics = TopicSubscriptions::default();
     let conn = UiConnection {
         tx: tx.clone(),
         connected_at: chrono::Utc::now(),
+        subscriptions: subscriptions.clone(),
+        topics: topics.clone(),
     };
-    let conn_index = {
-        let mut connections = app_state.ws_state.ui_connections.write().await;
-        connections.push(conn);
-        connections.len() - 1
-    };
+    let replay_conn = conn.clone();
+    app_state.ws_state.ui_connections.write().await.push(conn);
 
     tracing::info!("UI client connected");
 
@@ -771,10 +1389,11 @@ async fn handle_ui_socket(socket: WebSocket, app_state: crate::dashboard::server
 
         loop {
             interval.tick().await;
-            if send_protocol_message(&heartbeat_tx, "ping", EmptyPayload {}).is_err() {
+            if heartbeat_tx.is_closed() {
                 // Connection closed
                 break;
             }
+            let _ = send_protocol_message(&heartbeat_tx, "ping", EmptyPayload {});
             tracing::trace!("Sent heartbeat ping to UI client");
         }
     });
@@ -809,7 +1428,10 @@ async fn handle_ui_socket(socket: WebSocket, app_state: crate::dashboard::server
                                     // Send welcome response
                                     let welcome_payload = WelcomePayload {
                                         session_id,
-                                        capabilities: vec![],
+                                        capabilities: UI_CAPABILITIES
+                                            .iter()
+                                            .map(|c| c.to_string())
+                                            .collect(),
                                     };
 
                                     let _ = send_protocol_message(&tx, "welcome", welcome_payload);
@@ -844,11 +1466,70 @@ async fn handle_ui_socket(socket: WebSocket, app_state: crate::dashboard::server
                                             projects: current_projects,
                                         },
                                     );
+
+                                    // Then whatever changed while no Dashboard was listening
+                                    let replayed = app_state_for_recv
+                                        .replay_offline_notifications(&replay_conn)
+                                        .await;
+                                    if replayed > 0 {
+                                        tracing::info!(
+                                            "Replayed {} queued notifications to UI",
+                                            replayed
+                                        );
+                                    }
                                 }
                             },
                             "pong" => {
                                 tracing::trace!("Received pong from UI");
                             },
+                            "subscribe" => {
+                                match serde_json::from_value::<SubscribePayload>(parsed_msg.payload)
+                                {
+                                    Ok(subscribe) => {
+                                        if let Some(projects) = subscribe.projects {
+                                            subscriptions.set(
+                                                (!projects.iter().any(|p| p == "*")).then(|| {
+                                                    projects
+                                                        .iter()
+                                                        .map(|p| project_topic(p))
+                                                        .collect()
+                                                }),
+                                            );
+                                        }
+                                        if let Some(entity_topics) = subscribe.topics {
+                                            topics.set(
+                                                (!entity_topics.iter().any(|t| t == "*"))
+                                                    .then(|| entity_topics.into_iter().collect()),
+                                            );
+                                        }
+
+                                        let current = subscriptions.topics();
+                                        let _ = send_protocol_message(
+                                            &tx,
+                                            "subscribed",
+                                            SubscribedPayload {
+                                                all: current.is_none(),
+                                                projects: current.unwrap_or_default(),
+                                                topics: topics.topics(),
+                                            },
+                                        );
+                                    },
+                                    Err(e) => {
+                                        let _ = send_protocol_message(
+                                            &tx,
+                                            "error",
+                                            ErrorPayload {
+                                                code: error_codes::INVALID_MESSAGE.to_string(),
+                                                message: format!(
+                                                    "Invalid subscribe payload: {}",
+                                                    e
+                                                ),
+                                                details: None,
+                                            },
+                                        );
+                                    },
+                                }
+                            },
                             "goodbye" => {
                                 // UI client closing gracefully
                                 if let Ok(goodbye_payload) =
@@ -900,14 +1581,278 @@ async fn handle_ui_socket(socket: WebSocket, app_state: crate::dashboard::server
             send_task.abort();
             recv_task.abort();
         }
+        _ = stall_tx.stalled() => {
+            tracing::warn!("Disconnecting stalled UI client");
+            send_task.abort();
+            recv_task.abort();
+            heartbeat_task.abort();
+        }
     }
 
-    // Clean up UI connection
+    // Clean up UI connection (matched by channel: indices shift as clients leave)
     app_state
         .ws_state
         .ui_connections
         .write()
         .await
-        .swap_remove(conn_index);
+        .retain(|conn| !conn.tx.same_channel(&stall_tx));
     tracing::info!("UI client disconnected");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_sender_drops_when_full() {
+        let (tx, _rx) = ClientSender::channel(2);
+        assert!(tx.send(Message::Text("a".into())).is_ok());
+        assert!(tx.send(Message::Text("b".into())).is_ok());
+        assert_eq!(tx.send(Message::Text("c".into())), Err(QueueError::Full));
+
+        let stats = tx.stats("ui", None);
+        assert_eq!(stats.capacity, 2);
+        assert_eq!(stats.queued, 2);
+        assert_eq!(stats.sent, 2);
+        assert_eq!(stats.dropped, 1);
+    }
+
+    #[test]
+    fn test_client_sender_reports_closed() {
+        let (tx, rx) = ClientSender::channel(2);
+        drop(rx);
+        assert!(tx.is_closed());
+        assert_eq!(tx.send(Message::Text("a".into())), Err(QueueError::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_messages_aggregate_into_resync() {
+        let (tx, mut rx) = ClientSender::channel(1);
+        tx.send(Message::Text("first".into())).unwrap();
+        for _ in 0..5 {
+            let _ = tx.send(Message::Text("lost".into()));
+        }
+
+        // Drain, then the next send is preceded by a single resync notice
+        assert_eq!(rx.recv().await, Some(Message::Text("first".into())));
+        let _ = tx.send(Message::Text("next".into()));
+
+        let Some(Message::Text(resync)) = rx.recv().await else {
+            panic!("expected resync message");
+        };
+        let msg = ProtocolMessage::<ResyncPayload>::from_json(&resync).unwrap();
+        assert_eq!(msg.message_type, "resync");
+        assert_eq!(msg.payload.dropped, 5);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_client_is_flagged() {
+        let (tx, _rx) = ClientSender::channel(1);
+        for _ in 0..=SLOW_CONSUMER_DROP_LIMIT {
+            let _ = tx.send(Message::Text("x".into()));
+        }
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), tx.stalled())
+            .await
+            .expect("stalled client should be flagged");
+    }
+
+    #[tokio::test]
+    async fn test_queue_stats_lists_ui_clients() {
+        let state = WebSocketState::new();
+        let (tx, _rx) = ClientSender::channel(CLIENT_QUEUE_CAPACITY);
+        state.ui_connections.write().await.push(UiConnection {
+            tx,
+            connected_at: chrono::Utc::now(),
+            subscriptions: ProjectSubscriptions::default(),
+            topics: TopicSubscriptions::default(),
+        });
+
+        state.broadcast_to_ui("hello").await;
+
+        let stats = state.queue_stats().await;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].client, "ui");
+        assert_eq!(stats[0].sent, 1);
+        assert_eq!(stats[0].capacity, CLIENT_QUEUE_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn test_send_to_mcp_reaches_only_that_project() {
+        let state = WebSocketState::new();
+        let (tx, mut rx) = ClientSender::channel(8);
+        state.mcp_connections.write().await.insert(
+            "/projects/a".to_string(),
+            McpConnection {
+                tx,
+                project: ProjectInfo {
+                    path: "/projects/a".to_string(),
+                    name: "a".to_string(),
+                    db_path: "/projects/a/.intent-engine/project.db".to_string(),
+                    agent: None,
+                    mcp_connected: true,
+                    is_online: true,
+                },
+                connected_at: chrono::Utc::now(),
+            },
+        );
+
+        let payload = |path: &str| TasksUnblockedPayload {
+            project_path: path.to_string(),
+            tasks: Vec::new(),
+        };
+        assert!(
+            !state
+                .send_to_mcp("/projects/b", "tasks_unblocked", payload("/projects/b"))
+                .await
+        );
+        assert!(
+            state
+                .send_to_mcp("/projects/a", "tasks_unblocked", payload("/projects/a"))
+                .await
+        );
+
+        let Some(Message::Text(json)) = rx.recv().await else {
+            panic!("expected a message for project a");
+        };
+        let msg = ProtocolMessage::<TasksUnblockedPayload>::from_json(&json).unwrap();
+        assert_eq!(msg.message_type, "tasks_unblocked");
+        assert_eq!(msg.payload.project_path, "/projects/a");
+    }
+
+    #[tokio::test]
+    async fn test_project_broadcast_respects_subscriptions() {
+        let state = WebSocketState::new();
+        let (all_tx, mut all_rx) = ClientSender::channel(8);
+        let (one_tx, mut one_rx) = ClientSender::channel(8);
+        let one_subs = ProjectSubscriptions::default();
+        one_subs.set(Some(HashSet::from([project_topic("/projects/a")])));
+
+        {
+            let mut connections = state.ui_connections.write().await;
+            connections.push(UiConnection {
+                tx: all_tx,
+                connected_at: chrono::Utc::now(),
+                subscriptions: ProjectSubscriptions::default(),
+                topics: TopicSubscriptions::default(),
+            });
+            connections.push(UiConnection {
+                tx: one_tx,
+                connected_at: chrono::Utc::now(),
+                subscriptions: one_subs.clone(),
+                topics: TopicSubscriptions::default(),
+            });
+        }
+
+        state.broadcast_to_project("/projects/b", "b").await;
+        state.broadcast_to_project("/projects/a", "a").await;
+
+        assert_eq!(all_rx.recv().await, Some(Message::Text("b".into())));
+        assert_eq!(all_rx.recv().await, Some(Message::Text("a".into())));
+        assert_eq!(one_rx.recv().await, Some(Message::Text("a".into())));
+        assert!(one_rx.try_recv().is_err());
+
+        assert_eq!(one_subs.topics(), Some(vec!["/projects/a".to_string()]));
+        one_subs.set(None);
+        assert!(one_subs.matches("/projects/b"));
+    }
+
+    #[tokio::test]
+    async fn test_topic_subscribers_receive_patches() {
+        let state = WebSocketState::new();
+        let (v1_tx, mut v1_rx) = ClientSender::channel(8);
+        let (v2_tx, mut v2_rx) = ClientSender::channel(8);
+        let topics = TopicSubscriptions::default();
+        topics.set(Some(HashSet::from([TASKS_TOPIC.to_string()])));
+        {
+            let mut connections = state.ui_connections.write().await;
+            for (tx, topics) in [(v1_tx, TopicSubscriptions::default()), (v2_tx, topics)] {
+                connections.push(UiConnection {
+                    tx,
+                    connected_at: chrono::Utc::now(),
+                    subscriptions: ProjectSubscriptions::default(),
+                    topics,
+                });
+            }
+        }
+        let text = |msg: Option<Message>| match msg {
+            Some(Message::Text(text)) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            other => panic!("unexpected {:?}", other),
+        };
+
+        let task = serde_json::json!({"id": 1, "name": "Login", "status": "todo"});
+        let done = serde_json::json!({"id": 1, "name": "Login", "status": "done"});
+        for payload in [
+            DatabaseOperationPayload::task_created(1, task.clone(), "/p"),
+            DatabaseOperationPayload::task_updated(1, done.clone(), "/p"),
+            DatabaseOperationPayload::task_updated(1, done, "/p"),
+            DatabaseOperationPayload::event_deleted(7, 1, "/p"),
+        ] {
+            state.broadcast_db_operation(&payload).await;
+        }
+
+        // v1: everything, full objects
+        for _ in 0..4 {
+            assert_eq!(text(v1_rx.recv().await)["type"], "db_operation");
+        }
+        // v2: full object first, then only what changed; no-op updates and
+        // events of unfollowed tasks are skipped
+        assert_eq!(text(v2_rx.recv().await)["payload"]["data"], task);
+        let patch = text(v2_rx.recv().await);
+        assert_eq!(patch["type"], "db_patch");
+        assert_eq!(
+            patch["payload"]["patch"],
+            serde_json::json!([{"op": "replace", "path": "/status", "value": "done"}])
+        );
+        assert!(v2_rx.try_recv().is_err());
+
+        state
+            .broadcast_to_topic(Some("/p"), &task_events_topic(1), "event")
+            .await;
+        assert_eq!(v1_rx.recv().await, Some(Message::Text("event".into())));
+        assert!(v2_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_offline_queues_drains_once() {
+        let ctx = crate::test_utils::test_helpers::TestContext::new().await;
+        let queue = crate::notifications::queue::OfflineQueue::new(ctx.pool());
+        queue
+            .enqueue(&DatabaseOperationPayload::task_deleted(3, "/p"))
+            .await
+            .unwrap();
+
+        let (tx, mut rx) = ClientSender::channel(8);
+        let conn = UiConnection {
+            tx,
+            connected_at: chrono::Utc::now(),
+            subscriptions: ProjectSubscriptions::default(),
+            topics: TopicSubscriptions::default(),
+        };
+        let projects = vec![("/p".to_string(), ctx.pool().clone())];
+
+        assert_eq!(conn.replay_offline_queues(&projects).await, 1);
+        let Some(Message::Text(text)) = rx.recv().await else {
+            panic!("expected replayed notification");
+        };
+        let msg = ProtocolMessage::<DatabaseOperationPayload>::from_json(&text).unwrap();
+        assert_eq!(msg.message_type, "db_operation");
+        assert_eq!(msg.payload.affected_ids, vec![3]);
+
+        assert_eq!(conn.replay_offline_queues(&projects).await, 0);
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let from = serde_json::json!({"a": 1, "b/c": 2, "gone": true});
+        let to = serde_json::json!({"a": 1, "b/c": 3, "new": null});
+        assert_eq!(
+            json_patch(&from, &to),
+            vec![
+                serde_json::json!({"op": "replace", "path": "/b~1c", "value": 3}),
+                serde_json::json!({"op": "add", "path": "/new", "value": null}),
+                serde_json::json!({"op": "remove", "path": "/gone"}),
+            ]
+        );
+    }
+}
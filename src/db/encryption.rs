@@ -0,0 +1,417 @@
+//! Encryption at rest with SQLCipher
+//!
+//! Builds with the `encryption` feature link SQLCipher instead of SQLite. A
+//! database is then opened with the key from `IE_DB_KEY` or, failing that,
+//! from the OS keychain entry `ie db encrypt` stored for it (service
+//! `intent-engine`, account: the database path). Without a key, databases
+//! stay plaintext as before.
+//!
+//! Encrypted files have no `SQLite format 3` header. They start with a
+//! 16-byte salt and are made of whole SQLCipher pages, which is how a missing
+//! key is told apart from a corrupt file before SQLite ever sees it.
+
+use crate::error::{IntentError, Result};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the database key
+pub const ENV_KEY: &str = "IE_DB_KEY";
+
+/// First bytes of every plaintext SQLite database
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Page size of databases `ie db encrypt` writes (SQLCipher 4 default)
+const CIPHER_PAGE_SIZE: u64 = 4096;
+
+/// Whether `path` holds an encrypted database (false if missing or empty)
+///
+/// A file that is neither plaintext SQLite nor plausibly SQLCipher (a full
+/// salt, whole pages) is reported as corrupt.
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    let mut header = Vec::with_capacity(SQLITE_HEADER.len());
+    file.take(SQLITE_HEADER.len() as u64)
+        .read_to_end(&mut header)?;
+    if header.is_empty() || header == SQLITE_HEADER {
+        return Ok(false);
+    }
+    if header.len() < SQLITE_HEADER.len() || len % CIPHER_PAGE_SIZE != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "{} is neither an SQLite nor an SQLCipher database ({} bytes); \
+                 the file looks corrupt",
+                path.display(),
+                len
+            ),
+        )
+        .into());
+    }
+    Ok(true)
+}
+
+/// `IE_DB_KEY`, if set
+pub fn env_key() -> Option<String> {
+    std::env::var(ENV_KEY).ok().filter(|key| !key.is_empty())
+}
+
+/// Key for the database at `db_path`: `IE_DB_KEY`, else its keychain entry
+pub fn database_key(db_path: &Path) -> Option<String> {
+    env_key().or_else(|| keychain::get(db_path))
+}
+
+/// Add the key pragma to the options for `db_path`
+///
+/// New databases are created encrypted when `IE_DB_KEY` is set. Fails with
+/// an explanation instead of SQLite's "file is not a database" when the key
+/// is missing, or when `IE_DB_KEY` is set for a plaintext database.
+pub(crate) fn keyed(options: SqliteConnectOptions, db_path: &Path) -> Result<SqliteConnectOptions> {
+    let encrypted = is_encrypted(db_path)?;
+    let plaintext = !encrypted && std::fs::metadata(db_path).is_ok_and(|m| m.len() > 0);
+
+    if !cfg!(feature = "encryption") {
+        if encrypted || env_key().is_some() {
+            return Err(IntentError::InvalidInput(format!(
+                "{} is encrypted or {} is set, but this build of ie has no encryption support \
+                 (rebuild with --features encryption)",
+                db_path.display(),
+                ENV_KEY
+            )));
+        }
+        return Ok(options);
+    }
+
+    if plaintext {
+        if env_key().is_some() {
+            return Err(IntentError::InvalidInput(format!(
+                "{} is set but {} is not encrypted. Run 'ie db encrypt' to encrypt it, \
+                 or unset {}",
+                ENV_KEY,
+                db_path.display(),
+                ENV_KEY
+            )));
+        }
+        return Ok(options);
+    }
+
+    // The keychain is only asked about files that need it
+    let key = if encrypted {
+        database_key(db_path)
+    } else {
+        env_key()
+    };
+    match key {
+        Some(key) => Ok(options.pragma("key", quote(&key))),
+        None if encrypted => Err(IntentError::InvalidInput(format!(
+            "{} is encrypted and no key was found. Set {} or store the key in the OS keychain \
+             (service '{}', account '{}')",
+            db_path.display(),
+            ENV_KEY,
+            keychain::SERVICE,
+            keychain::account(db_path)
+        ))),
+        None => Ok(options),
+    }
+}
+
+/// A random 256-bit key, hex-encoded
+pub fn generate_key() -> String {
+    use rand::RngCore;
+
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Rewrite a plaintext database encrypted with `key`
+///
+/// Nothing else may have the database open; the Dashboard must be stopped.
+pub async fn encrypt(db_path: &Path, key: &str) -> Result<()> {
+    if is_encrypted(db_path)? {
+        return Err(IntentError::InvalidInput(format!(
+            "{} is already encrypted",
+            db_path.display()
+        )));
+    }
+    export(db_path, None, key).await
+}
+
+/// Rewrite a database encrypted with `key` as plaintext
+pub async fn decrypt(db_path: &Path, key: &str) -> Result<()> {
+    if !is_encrypted(db_path)? {
+        return Err(IntentError::InvalidInput(format!(
+            "{} is not encrypted",
+            db_path.display()
+        )));
+    }
+    export(db_path, Some(key), "").await
+}
+
+/// Copy the database into a new file with `to_key` ("" for plaintext) and
+/// swap it in
+async fn export(db_path: &Path, from_key: Option<&str>, to_key: &str) -> Result<()> {
+    if !cfg!(feature = "encryption") {
+        return Err(IntentError::InvalidInput(
+            "This build of ie has no encryption support (rebuild with --features encryption)"
+                .to_string(),
+        ));
+    }
+    if !db_path.exists() {
+        return Err(IntentError::InvalidInput(format!(
+            "{} does not exist",
+            db_path.display()
+        )));
+    }
+
+    let target = sibling(db_path, "export");
+    let _ = std::fs::remove_file(&target);
+
+    // ATTACH inherits the open flags, and must be allowed to create the target
+    let mut options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(true);
+    if let Some(key) = from_key {
+        options = options.pragma("key", quote(key));
+    }
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+    let result = async {
+        // Pending WAL frames would be lost when the file is replaced
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&pool)
+            .await?;
+        let user_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&pool)
+            .await?;
+        sqlx::query("ATTACH DATABASE ? AS target KEY ?")
+            .bind(target.to_string_lossy().to_string())
+            .bind(to_key)
+            .execute(&pool)
+            .await?;
+        sqlx::query("SELECT sqlcipher_export('target')")
+            .execute(&pool)
+            .await?;
+        sqlx::query(&format!("PRAGMA target.user_version = {}", user_version))
+            .execute(&pool)
+            .await?;
+        sqlx::query("DETACH DATABASE target").execute(&pool).await?;
+        Ok::<_, IntentError>(())
+    }
+    .await;
+    pool.close().await;
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&target);
+        return Err(e);
+    }
+
+    // Check the copy opens with its key before the original goes away
+    let mut options = SqliteConnectOptions::new()
+        .filename(&target)
+        .journal_mode(SqliteJournalMode::Delete);
+    if !to_key.is_empty() {
+        options = options.pragma("key", quote(to_key));
+    }
+    let copy = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+    let check: String = sqlx::query_scalar("PRAGMA quick_check")
+        .fetch_one(&copy)
+        .await?;
+    copy.close().await;
+    if check != "ok" {
+        let _ = std::fs::remove_file(&target);
+        return Err(IntentError::OtherError(anyhow::anyhow!(
+            "The rewritten database failed its integrity check: {}",
+            check
+        )));
+    }
+
+    for suffix in ["wal", "shm"] {
+        let _ = std::fs::remove_file(sibling(db_path, suffix));
+    }
+    std::fs::rename(&target, db_path)?;
+    Ok(())
+}
+
+/// `project.db` -> `project.db-<suffix>`
+fn sibling(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(format!("-{}", suffix));
+    PathBuf::from(name)
+}
+
+/// Key as an SQL string literal for `PRAGMA key`
+fn quote(key: &str) -> String {
+    format!("'{}'", key.replace('\'', "''"))
+}
+
+/// OS keychain entries holding database keys
+pub mod keychain {
+    use crate::error::{IntentError, Result};
+    use std::path::Path;
+
+    pub const SERVICE: &str = "intent-engine";
+
+    /// Account name of the entry for `db_path`
+    pub fn account(db_path: &Path) -> String {
+        db_path
+            .canonicalize()
+            .unwrap_or_else(|_| db_path.to_path_buf())
+            .display()
+            .to_string()
+    }
+
+    /// Stored key; None if there is none or the keychain is unavailable
+    pub fn get(db_path: &Path) -> Option<String> {
+        #[cfg(feature = "encryption")]
+        {
+            match keyring::Entry::new(SERVICE, &account(db_path)).and_then(|e| e.get_password()) {
+                Ok(key) => return Some(key),
+                Err(keyring::Error::NoEntry) => {},
+                Err(e) => tracing::debug!(error = %e, "OS keychain unavailable"),
+            }
+        }
+        let _ = db_path;
+        None
+    }
+
+    pub fn set(db_path: &Path, key: &str) -> Result<()> {
+        #[cfg(feature = "encryption")]
+        {
+            keyring::Entry::new(SERVICE, &account(db_path))
+                .and_then(|e| e.set_password(key))
+                .map_err(|e| {
+                    IntentError::OtherError(anyhow::anyhow!(
+                        "Could not store the key in the OS keychain: {}. Set {} instead",
+                        e,
+                        super::ENV_KEY
+                    ))
+                })
+        }
+        #[cfg(not(feature = "encryption"))]
+        {
+            let _ = (db_path, key);
+            Err(IntentError::InvalidInput(
+                "This build of ie has no keychain support".to_string(),
+            ))
+        }
+    }
+
+    /// Remove the stored key; true if there was one
+    pub fn delete(db_path: &Path) -> bool {
+        #[cfg(feature = "encryption")]
+        {
+            if let Ok(entry) = keyring::Entry::new(SERVICE, &account(db_path)) {
+                return entry.delete_credential().is_ok();
+            }
+        }
+        let _ = db_path;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_is_encrypted_reads_header() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("project.db");
+        assert!(!is_encrypted(&path).unwrap());
+
+        let pool = crate::db::create_pool(&path).await.unwrap();
+        crate::db::run_migrations(&pool).await.unwrap();
+        pool.close().await;
+        assert!(!is_encrypted(&path).unwrap());
+
+        // A separate file: SQLite may still be checkpointing the one above
+        let path = dir.path().join("other.db");
+        std::fs::write(&path, [0x5a; 4096]).unwrap();
+        assert!(is_encrypted(&path).unwrap());
+
+        // Too short for a salt, or not whole pages
+        for len in [8, 64, 4100] {
+            std::fs::write(&path, vec![0x5a; len]).unwrap();
+            let err = is_encrypted(&path).unwrap_err();
+            assert!(err.to_string().contains("looks corrupt"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_quote_and_generate_key() {
+        assert_eq!(quote("it's"), "'it''s'");
+        let key = generate_key();
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, generate_key());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test]
+    async fn test_encrypt_decrypt_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("project.db");
+        let pool = crate::db::create_pool(&path).await.unwrap();
+        crate::db::run_migrations(&pool).await.unwrap();
+        crate::tasks::TaskManager::new(&pool)
+            .add_task("Secret design", Some("proprietary"), None, None, None, None)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        encrypt(&path, "hunter2").await.unwrap();
+        assert!(is_encrypted(&path).unwrap());
+        assert!(!std::fs::read(&path)
+            .unwrap()
+            .windows(11)
+            .any(|w| w == b"proprietary"));
+        assert!(encrypt(&path, "hunter2").await.is_err());
+
+        let open = |key: &str| {
+            SqliteConnectOptions::new()
+                .filename(&path)
+                .pragma("key", quote(key))
+        };
+        let wrong = SqlitePoolOptions::new().connect_with(open("nope")).await;
+        let wrong_read = match wrong {
+            Ok(pool) => sqlx::query("SELECT COUNT(*) FROM tasks")
+                .fetch_one(&pool)
+                .await
+                .is_err(),
+            Err(_) => true,
+        };
+        assert!(wrong_read);
+
+        let pool = SqlitePoolOptions::new()
+            .connect_with(open("hunter2"))
+            .await
+            .unwrap();
+        // Full-text search and triggers survive the export
+        let hits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM tasks_fts WHERE tasks_fts MATCH 'secret'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(hits, 1);
+        pool.close().await;
+
+        decrypt(&path, "hunter2").await.unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+        let pool = crate::db::create_pool(&path).await.unwrap();
+        let name: String = sqlx::query_scalar("SELECT name FROM tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(name, "Secret design");
+    }
+}
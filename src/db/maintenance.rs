@@ -0,0 +1,173 @@
+//! Database maintenance: WAL checkpoints, ANALYZE and VACUUM
+//!
+//! In WAL mode SQLite only shrinks the `-wal` file when a checkpoint can run
+//! with no readers in the way, which a long-lived dashboard rarely allows on
+//! its own. Query planner statistics go stale as the events table grows, and
+//! deleted rows leave free pages behind. `maintain` runs all three in the
+//! order that leaves the smallest files: statistics, compaction, then the
+//! checkpoint that writes everything back and truncates the log.
+
+use crate::error::Result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// VACUUM only pays off once a good share of the file is free pages
+const VACUUM_MIN_FREE_RATIO: f64 = 0.25;
+/// ...and the file is big enough for the rewrite to matter (pages)
+const VACUUM_MIN_FREE_PAGES: i64 = 256;
+/// Rows sampled per index by `analyze`, keeping it cheap on large tables
+const ANALYSIS_LIMIT: i64 = 1000;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CheckpointResult {
+    /// Another connection prevented a full checkpoint; try again later
+    pub busy: bool,
+    /// Pages in the WAL before the checkpoint
+    pub wal_pages: i64,
+    /// Pages written back to the database file
+    pub checkpointed_pages: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct VacuumResult {
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+}
+
+/// Size and free space of the database file
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DbStats {
+    pub page_size: i64,
+    pub page_count: i64,
+    pub freelist_count: i64,
+}
+
+impl DbStats {
+    pub fn size_bytes(&self) -> i64 {
+        self.page_size * self.page_count
+    }
+
+    /// Whether enough of the file is free pages for VACUUM to be worth it
+    pub fn needs_vacuum(&self) -> bool {
+        self.freelist_count >= VACUUM_MIN_FREE_PAGES
+            && self.freelist_count as f64 >= self.page_count as f64 * VACUUM_MIN_FREE_RATIO
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MaintenanceReport {
+    /// None when VACUUM was skipped
+    pub vacuum: Option<VacuumResult>,
+    pub checkpoint: CheckpointResult,
+}
+
+pub async fn stats(pool: &SqlitePool) -> Result<DbStats> {
+    let pragma = |name: &'static str| async move {
+        sqlx::query_scalar::<_, i64>(&format!("PRAGMA {}", name))
+            .fetch_one(pool)
+            .await
+    };
+    Ok(DbStats {
+        page_size: pragma("page_size").await?,
+        page_count: pragma("page_count").await?,
+        freelist_count: pragma("freelist_count").await?,
+    })
+}
+
+/// Write the WAL back into the database file and truncate it
+pub async fn checkpoint(pool: &SqlitePool) -> Result<CheckpointResult> {
+    let (busy, wal_pages, checkpointed_pages): (i64, i64, i64) =
+        sqlx::query_as("PRAGMA wal_checkpoint(TRUNCATE)")
+            .fetch_one(pool)
+            .await?;
+    Ok(CheckpointResult {
+        busy: busy != 0,
+        wal_pages,
+        checkpointed_pages,
+    })
+}
+
+/// Refresh query planner statistics
+///
+/// Sampling is bounded by `ANALYSIS_LIMIT`, so this stays fast however large
+/// the events table gets.
+pub async fn analyze(pool: &SqlitePool) -> Result<()> {
+    // analysis_limit is per connection, so set and reset it on the same one
+    let mut conn = pool.acquire().await?;
+    sqlx::query(&format!("PRAGMA analysis_limit = {}", ANALYSIS_LIMIT))
+        .execute(&mut *conn)
+        .await?;
+    let analyzed = sqlx::query("ANALYZE").execute(&mut *conn).await;
+    sqlx::query("PRAGMA analysis_limit = 0")
+        .execute(&mut *conn)
+        .await?;
+    analyzed?;
+    Ok(())
+}
+
+/// Rebuild the database file without free pages
+pub async fn vacuum(pool: &SqlitePool) -> Result<VacuumResult> {
+    let bytes_before = stats(pool).await?.size_bytes();
+    sqlx::query("VACUUM").execute(pool).await?;
+    let bytes_after = stats(pool).await?.size_bytes();
+    Ok(VacuumResult {
+        bytes_before,
+        bytes_after,
+    })
+}
+
+/// Run all maintenance steps; VACUUM only when `DbStats::needs_vacuum` or
+/// `force_vacuum` says so
+pub async fn maintain(pool: &SqlitePool, force_vacuum: bool) -> Result<MaintenanceReport> {
+    analyze(pool).await?;
+    let vacuum = if force_vacuum || stats(pool).await?.needs_vacuum() {
+        Some(vacuum(pool).await?)
+    } else {
+        None
+    };
+    // Last, so the pages written by ANALYZE and VACUUM leave the WAL too
+    let checkpoint = checkpoint(pool).await?;
+    Ok(MaintenanceReport { vacuum, checkpoint })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{create_pool, run_migrations};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_maintain_truncates_wal_and_compacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let pool = create_pool(&db_path).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let payload = "x".repeat(4000);
+        for _ in 0..400 {
+            sqlx::query("INSERT INTO tasks (name, spec) VALUES ('bulk', ?)")
+                .bind(&payload)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM tasks")
+            .execute(&pool)
+            .await
+            .unwrap();
+        checkpoint(&pool).await.unwrap();
+        assert!(stats(&pool).await.unwrap().needs_vacuum());
+
+        let report = maintain(&pool, false).await.unwrap();
+        let vacuum = report.vacuum.expect("free pages should trigger VACUUM");
+        assert!(vacuum.bytes_after < vacuum.bytes_before);
+        assert!(!report.checkpoint.busy);
+
+        let wal = temp_dir.path().join("test.db-wal");
+        assert_eq!(std::fs::metadata(wal).map(|m| m.len()).unwrap_or(0), 0);
+
+        // Nothing left to reclaim
+        assert!(!stats(&pool).await.unwrap().needs_vacuum());
+        assert!(maintain(&pool, false).await.unwrap().vacuum.is_none());
+    }
+}
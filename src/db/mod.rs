@@ -1,24 +1,84 @@
+pub mod encryption;
+pub mod maintenance;
 pub mod models;
+pub mod pool;
 
 use crate::error::Result;
+use pool::{PoolConfig, PoolProfile};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Connection;
 use std::path::Path;
 
+/// Schema version recorded in `workspace_state` by `run_migrations`
+pub const SCHEMA_VERSION: &str = "0.25.0";
+
+/// Open a pool tuned for one-shot CLI commands
 pub async fn create_pool(db_path: &Path) -> Result<SqlitePool> {
+    create_pool_for(db_path, PoolProfile::Cli).await
+}
+
+/// Open a pool with the configured settings of `profile` (see `pool`)
+pub async fn create_pool_for(db_path: &Path, profile: PoolProfile) -> Result<SqlitePool> {
+    create_pool_with(db_path, &PoolConfig::load(profile)?).await
+}
+
+pub async fn create_pool_with(db_path: &Path, config: &PoolConfig) -> Result<SqlitePool> {
     let options = SqliteConnectOptions::new()
         .filename(db_path)
         .create_if_missing(true)
         .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-        .busy_timeout(std::time::Duration::from_millis(5000));
+        .busy_timeout(config.busy_timeout)
+        .synchronous(config.synchronous);
+    let options = encryption::keyed(options, db_path)?;
 
     let pool = SqlitePoolOptions::new()
-        .max_connections(5)
+        .max_connections(config.max_connections)
         .connect_with(options)
         .await?;
 
     Ok(pool)
 }
 
+/// Open a pool that cannot write, for reports, search and other read paths
+///
+/// Connections are opened read-only with `query_only` set, so any mutation
+/// fails instead of taking the write lock. The file is not opened `immutable`:
+/// that flag tells SQLite nobody else writes either, and it would then read
+/// torn data while a CLI command commits. In WAL mode readers never block
+/// writers, so read-only is enough. The database must already exist and be
+/// migrated.
+pub async fn create_readonly_pool(db_path: &Path) -> Result<SqlitePool> {
+    create_readonly_pool_for(db_path, PoolProfile::Cli).await
+}
+
+/// Read-only pool with the configured settings of `profile`
+pub async fn create_readonly_pool_for(db_path: &Path, profile: PoolProfile) -> Result<SqlitePool> {
+    let config = PoolConfig::load(profile)?;
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .busy_timeout(config.busy_timeout)
+        .pragma("query_only", "ON");
+    let options = encryption::keyed(options, db_path)?;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(config.max_connections)
+        .connect_with(options)
+        .await?;
+
+    Ok(pool)
+}
+
+/// SQLite's `data_version` for this connection
+///
+/// The value changes whenever another connection (in any process) commits a
+/// write, so polling it on one dedicated connection is a cheap change check.
+pub async fn data_version(conn: &mut sqlx::SqliteConnection) -> Result<i64> {
+    Ok(sqlx::query_scalar::<_, i64>("PRAGMA data_version")
+        .fetch_one(conn)
+        .await?)
+}
+
 pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     // Enable FTS5
     sqlx::query("PRAGMA journal_mode=WAL;")
@@ -43,7 +103,7 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
             owner TEXT NOT NULL DEFAULT 'human',
             metadata TEXT DEFAULT '{}',
             FOREIGN KEY (parent_id) REFERENCES tasks(id) ON DELETE CASCADE,
-            CHECK (status IN ('todo', 'doing', 'done')),
+            CHECK (status IN ('todo', 'doing', 'review', 'done')),
             CHECK (owner IS NOT NULL AND owner != '')
         )
         "#,
@@ -51,6 +111,10 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Older databases only allow todo/doing/done; must run before the FTS
+    // triggers and task indexes below, which the table rebuild drops
+    allow_review_status(pool).await?;
+
     // Add active_form column if it doesn't exist (migration for existing databases)
     // This column stores the present progressive form of task description for UI display
     let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN active_form TEXT")
@@ -88,10 +152,16 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // A plain DELETE on an external-content index cannot find the terms to
+    // remove once the content row is gone, so use the 'delete' command
+    let _ = sqlx::query("DROP TRIGGER IF EXISTS tasks_ad")
+        .execute(pool)
+        .await; // Ignore error if trigger doesn't exist
+
     sqlx::query(
         r#"
         CREATE TRIGGER IF NOT EXISTS tasks_ad AFTER DELETE ON tasks BEGIN
-            DELETE FROM tasks_fts WHERE rowid = old.id;
+            INSERT INTO tasks_fts(tasks_fts, rowid, name, spec) VALUES('delete', old.id, old.name, old.spec);
         END
         "#,
     )
@@ -101,13 +171,15 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     // Recreate trigger with correct FTS5 syntax (drop and create for migration from buggy version)
     // Note: We always drop first because SQLite doesn't support CREATE OR REPLACE TRIGGER,
     // and we need to update existing databases that have the buggy trigger.
+    // Only name and spec are indexed; other updates (e.g. tasks_inherit_visibility,
+    // which can run before tasks_ai has indexed the row) must not touch the index.
     let _ = sqlx::query("DROP TRIGGER IF EXISTS tasks_au")
         .execute(pool)
         .await; // Ignore error if trigger doesn't exist
 
     sqlx::query(
         r#"
-        CREATE TRIGGER IF NOT EXISTS tasks_au AFTER UPDATE ON tasks BEGIN
+        CREATE TRIGGER IF NOT EXISTS tasks_au AFTER UPDATE OF name, spec ON tasks BEGIN
             INSERT INTO tasks_fts(tasks_fts, rowid, name, spec) VALUES('delete', old.id, old.name, old.spec);
             INSERT INTO tasks_fts(rowid, name, spec) VALUES (new.id, new.name, new.spec);
         END
@@ -175,10 +247,15 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Same 'delete' command fix as tasks_ad
+    let _ = sqlx::query("DROP TRIGGER IF EXISTS events_ad")
+        .execute(pool)
+        .await; // Ignore error if trigger doesn't exist
+
     sqlx::query(
         r#"
         CREATE TRIGGER IF NOT EXISTS events_ad AFTER DELETE ON events BEGIN
-            DELETE FROM events_fts WHERE rowid = old.id;
+            INSERT INTO events_fts(events_fts, rowid, discussion_data) VALUES('delete', old.id, old.discussion_data);
         END
         "#,
     )
@@ -267,6 +344,151 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Create settings_audit table: one row per config change (Dashboard settings, ie config)
+    // Secret values are stored masked
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS settings_audit (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL,
+            old_value TEXT,
+            new_value TEXT,
+            source TEXT NOT NULL,
+            changed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Spec history: every change of a task's spec, numbered per task
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS spec_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            revision INTEGER NOT NULL,
+            spec TEXT NOT NULL,
+            changed_at DATETIME NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            UNIQUE (task_id, revision)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Status history: one row per status change (first_*_at only keep the first)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_transitions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            from_status TEXT NOT NULL,
+            to_status TEXT NOT NULL,
+            source TEXT NOT NULL,
+            actor TEXT,
+            changed_at DATETIME NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_task_transitions_task_id ON task_transitions(task_id, id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Spec frontmatter as columns, rewritten whenever the spec is; see spec_meta
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS spec_meta (
+            task_id INTEGER PRIMARY KEY,
+            acceptance_criteria TEXT NOT NULL DEFAULT '[]',
+            links TEXT NOT NULL DEFAULT '[]',
+            estimate_minutes INTEGER,
+            fields TEXT NOT NULL DEFAULT '{}',
+            updated_at DATETIME NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Checklist steps of a task, numbered from 1; see checklist
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS checklist_items (
+            task_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            checked BOOLEAN NOT NULL DEFAULT 0,
+            checked_at DATETIME,
+            PRIMARY KEY (task_id, position),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Milestones group tasks across subtrees; a task belongs to at most one
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS milestones (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            created_at DATETIME NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS milestone_tasks (
+            task_id INTEGER PRIMARY KEY,
+            milestone_id INTEGER NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+            FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_milestone_tasks_milestone ON milestone_tasks(milestone_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Plan application journal for crash recovery (see plan_journal.rs)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS plan_journal (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            request_hash TEXT NOT NULL,
+            phase TEXT NOT NULL DEFAULT 'started',
+            session_id TEXT NOT NULL,
+            pid INTEGER NOT NULL,
+            task_id_map TEXT,
+            focus_task_id INTEGER,
+            note TEXT,
+            started_at DATETIME NOT NULL,
+            updated_at DATETIME NOT NULL,
+            CHECK (phase IN ('started', 'committed', 'finished', 'failed', 'interrupted', 'recovered'))
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     // Migrate existing current_task_id from workspace_state to default session (v0.11.0)
     // This ensures backward compatibility - existing focus is preserved in session "-1"
     sqlx::query(
@@ -372,25 +594,460 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
         .execute(pool)
         .await; // Ignore error if column already exists
 
-    // Update schema version to 0.12.0
+    // Add sort_order column to tasks table (Dashboard kanban ordering)
+    // Manual position within a board column; NULL means "not manually ordered"
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN sort_order INTEGER")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add estimate_minutes column to tasks table (capacity planning)
+    // Effort of the task itself; parents roll up their subtree on read
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add priority_boost column to tasks table (priority aging)
+    // Priority levels a waiting todo task has gained; see priority::age_tasks
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN priority_boost REAL")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add needs_human column to tasks table (human inbox)
+    // Why an agent is waiting on a human for this task; NULL when it is not
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN needs_human TEXT")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add blocked_reason column to tasks table (blocked tasks)
+    // Why the task cannot move; NULL when it is not blocked
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN blocked_reason TEXT")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add visibility column to events table (normal, verbose, private)
+    // Only 'normal' events are shown by default; see EventVisibility
+    let _ = sqlx::query("ALTER TABLE events ADD COLUMN visibility TEXT NOT NULL DEFAULT 'normal'")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add actor column to events table: JSON of who logged the event (see Actor)
+    let _ = sqlx::query("ALTER TABLE events ADD COLUMN actor TEXT")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Add visibility column to tasks table (shared, private)
+    // Private tasks are hidden unless the caller runs with --scope private
+    let _ = sqlx::query("ALTER TABLE tasks ADD COLUMN visibility TEXT NOT NULL DEFAULT 'shared'")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // A private parent keeps its whole subtree private, so new children inherit it
+    sqlx::query(
+        r#"
+        CREATE TRIGGER IF NOT EXISTS tasks_inherit_visibility AFTER INSERT ON tasks
+        WHEN NEW.visibility = 'shared'
+            AND NEW.parent_id IS NOT NULL
+            AND (SELECT visibility FROM tasks WHERE id = NEW.parent_id) = 'private'
+        BEGIN
+            UPDATE tasks SET visibility = 'private' WHERE id = NEW.id;
+        END
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_tasks_status_sort_order
+        ON tasks(status, sort_order)
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Raw events replaced by a digest event (see compaction)
+    // `id` keeps the original event ID
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS events_archive (
+            id INTEGER PRIMARY KEY,
+            task_id INTEGER NOT NULL,
+            timestamp DATETIME NOT NULL,
+            log_type TEXT NOT NULL,
+            discussion_data TEXT NOT NULL,
+            visibility TEXT NOT NULL DEFAULT 'normal',
+            actor TEXT,
+            digest_event_id INTEGER NOT NULL,
+            archived_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_archive_task_id ON events_archive(task_id)")
+        .execute(pool)
+        .await?;
+    let _ = sqlx::query("ALTER TABLE events_archive ADD COLUMN actor TEXT")
+        .execute(pool)
+        .await; // Ignore error if column already exists
+
+    // Embedding vectors for semantic search (see search::semantic)
+    // Created regardless of the `semantic` feature so builds can share a database
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS embeddings (
+            kind TEXT NOT NULL CHECK (kind IN ('task', 'event')),
+            item_id INTEGER NOT NULL,
+            model TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            vector BLOB NOT NULL,
+            PRIMARY KEY (kind, item_id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Session handoff notes (see handoff)
+    // Handoffs outlive their task: the note is still worth reading
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS handoffs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER,
+            summary TEXT NOT NULL,
+            next_steps TEXT,
+            created_at DATETIME NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_handoffs_task_id ON handoffs(task_id)")
+        .execute(pool)
+        .await?;
+
+    // Task leases for multi-agent claiming (see TaskManager::claim_next)
+    // A row whose lease_expires_at has passed is free to be claimed again
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_leases (
+            task_id INTEGER PRIMARY KEY,
+            assignee TEXT NOT NULL,
+            claimed_at DATETIME NOT NULL,
+            lease_expires_at DATETIME NOT NULL,
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Dashboard notifications nobody received, replayed on reconnect
+    // (see notifications::queue); one row per entity
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS notification_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entity TEXT NOT NULL,
+            entity_key TEXT NOT NULL,
+            operation TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            queued_at DATETIME NOT NULL,
+            UNIQUE(entity, entity_key)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Alternative plans kept beside the task tree until one is promoted
+    // (see plan_workspace)
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS plan_workspaces (
+            name TEXT PRIMARY KEY,
+            plan TEXT NOT NULL,
+            created_at DATETIME NOT NULL,
+            updated_at DATETIME NOT NULL,
+            promoted_at DATETIME
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Dependencies on tasks of other projects (see global_projects); the
+    // blocking task lives in the database under project_path
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS cross_project_dependencies (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            blocked_task_id INTEGER NOT NULL,
+            project_path TEXT NOT NULL,
+            blocking_task_id INTEGER NOT NULL,
+            created_at DATETIME NOT NULL,
+            UNIQUE (blocked_task_id, project_path, blocking_task_id),
+            FOREIGN KEY (blocked_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // URLs and documents tasks refer to (see links); one row per task and URL
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS task_links (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id INTEGER NOT NULL,
+            url TEXT NOT NULL,
+            title TEXT,
+            kind TEXT NOT NULL DEFAULT 'other',
+            created_at DATETIME NOT NULL,
+            UNIQUE (task_id, url),
+            FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Outcome of the last `ie links check`: HTTP status or why it failed
+    for column in [
+        "last_checked_at DATETIME",
+        "last_status INTEGER",
+        "last_error TEXT",
+    ] {
+        let _ = sqlx::query(&format!("ALTER TABLE task_links ADD COLUMN {}", column))
+            .execute(pool)
+            .await; // Ignore error if column already exists
+    }
+
+    // Change tracking for `ie sync` (see sync): every task/event/dependency
+    // row gets a stable uid and the Lamport clock value of its last change
+    create_sync_tracking(pool).await?;
+
+    // Update schema version
     sqlx::query(
         r#"
         INSERT INTO workspace_state (key, value)
-        VALUES ('schema_version', '0.12.0')
-        ON CONFLICT(key) DO UPDATE SET value = '0.12.0'
+        VALUES ('schema_version', ?)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value
         "#,
     )
+    .bind(SCHEMA_VERSION)
     .execute(pool)
     .await?;
 
     Ok(())
 }
 
+/// Widen the tasks status CHECK constraint to include 'review'
+///
+/// SQLite cannot alter a CHECK constraint, so the table is rebuilt from its
+/// own stored definition. Foreign keys are switched off on the connection
+/// doing the rebuild; otherwise dropping the old table would cascade into
+/// events and dependencies.
+async fn allow_review_status(pool: &SqlitePool) -> Result<()> {
+    const OLD_CHECK: &str = "CHECK (status IN ('todo', 'doing', 'done'))";
+    const NEW_CHECK: &str = "CHECK (status IN ('todo', 'doing', 'review', 'done'))";
+
+    let sql: Option<String> =
+        sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'tasks'")
+            .fetch_optional(pool)
+            .await?;
+    let Some(sql) = sql.filter(|sql| sql.contains(OLD_CHECK)) else {
+        return Ok(());
+    };
+    let Some(columns_start) = sql.find('(') else {
+        return Ok(());
+    };
+    let create_new = format!(
+        "CREATE TABLE tasks_new {}",
+        sql[columns_start..].replace(OLD_CHECK, NEW_CHECK)
+    );
+
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await?;
+
+    let rebuilt = async {
+        let mut tx = conn.begin().await?;
+        let next_id: Option<i64> =
+            sqlx::query_scalar("SELECT seq FROM sqlite_sequence WHERE name = 'tasks'")
+                .fetch_optional(&mut *tx)
+                .await?;
+        sqlx::query(&create_new).execute(&mut *tx).await?;
+        sqlx::query("INSERT INTO tasks_new SELECT * FROM tasks")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DROP TABLE tasks").execute(&mut *tx).await?;
+        sqlx::query("ALTER TABLE tasks_new RENAME TO tasks")
+            .execute(&mut *tx)
+            .await?;
+        // Keep AUTOINCREMENT from reusing ids of tasks deleted before the rebuild
+        if let Some(seq) = next_id {
+            sqlx::query("UPDATE sqlite_sequence SET seq = MAX(seq, ?) WHERE name = 'tasks'")
+                .bind(seq)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+    .await;
+
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await?;
+    rebuilt?;
+    Ok(())
+}
+
+/// Create the `sync_state`/`sync_rows` tables and the triggers filling them
+///
+/// The triggers stand down while `sync_state` holds an `applying` key, which
+/// the sync engine sets while it writes changes pulled from the hub. The
+/// first run gives the database its replica ID and records the rows that
+/// already exist, so they are pushed like new ones.
+async fn create_sync_tracking(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_state (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_rows (
+            uid TEXT PRIMARY KEY,
+            entity TEXT NOT NULL,
+            local_id INTEGER,
+            lamport INTEGER NOT NULL,
+            origin TEXT NOT NULL,
+            updated_at DATETIME NOT NULL,
+            deleted INTEGER NOT NULL DEFAULT 0,
+            synced_lamport INTEGER,
+            UNIQUE (entity, local_id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    const CLOCK: &str = "(SELECT CAST(value AS INTEGER) FROM sync_state WHERE key = 'clock')";
+    const REPLICA: &str = "(SELECT value FROM sync_state WHERE key = 'replica_id')";
+    const NOW: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+    const TICK: &str =
+        "UPDATE sync_state SET value = CAST(value AS INTEGER) + 1 WHERE key = 'clock';";
+
+    for entity in crate::sync::Entity::ALL {
+        let (name, table) = (entity.as_str(), entity.table());
+        let upsert = format!(
+            "INSERT INTO sync_rows (uid, entity, local_id, lamport, origin, updated_at) \
+             VALUES ({REPLICA} || '-{name}-' || NEW.id, '{name}', NEW.id, {CLOCK}, {REPLICA}, {NOW}) \
+             ON CONFLICT(entity, local_id) DO UPDATE SET lamport = excluded.lamport, \
+                 origin = excluded.origin, updated_at = excluded.updated_at, deleted = 0;"
+        );
+        for (suffix, event, body) in [
+            ("ai", "INSERT", upsert.clone()),
+            ("au", "UPDATE", upsert),
+            (
+                "ad",
+                "DELETE",
+                format!(
+                    "UPDATE sync_rows SET deleted = 1, lamport = {CLOCK}, origin = {REPLICA}, \
+                         updated_at = {NOW} \
+                     WHERE entity = '{name}' AND local_id = OLD.id;"
+                ),
+            ),
+        ] {
+            sqlx::query(&format!(
+                "CREATE TRIGGER IF NOT EXISTS sync_{table}_{suffix} AFTER {event} ON {table} \
+                 WHEN NOT EXISTS (SELECT 1 FROM sync_state WHERE key = 'applying') \
+                 BEGIN {TICK} {body} END"
+            ))
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    let has_replica: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM sync_state WHERE key = 'replica_id')")
+            .fetch_one(pool)
+            .await?;
+    if !has_replica {
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO sync_state (key, value) \
+             VALUES ('replica_id', lower(hex(randomblob(8)))), ('clock', '0')",
+        )
+        .execute(&mut *tx)
+        .await?;
+        for entity in crate::sync::Entity::ALL {
+            let (name, table) = (entity.as_str(), entity.table());
+            sqlx::query(&format!(
+                "INSERT OR IGNORE INTO sync_rows (uid, entity, local_id, lamport, origin, updated_at) \
+                 SELECT {REPLICA} || '-{name}-' || id, '{name}', id, 0, {REPLICA}, {NOW} FROM {table}"
+            ))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn test_readonly_pool_reads_but_cannot_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let pool = create_pool(&db_path).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        sqlx::query("INSERT INTO tasks (name) VALUES ('Existing')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let readonly = create_readonly_pool(&db_path).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(&readonly)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(sqlx::query("INSERT INTO tasks (name) VALUES ('Nope')")
+            .execute(&readonly)
+            .await
+            .is_err());
+
+        // Writers are not blocked while the read-only pool is open
+        sqlx::query("INSERT INTO tasks (name) VALUES ('Later')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Never creates a missing database
+        assert!(create_readonly_pool(&temp_dir.path().join("missing.db"))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_create_pool_success() {
         let temp_dir = TempDir::new().unwrap();
@@ -516,6 +1173,42 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[tokio::test]
+    async fn test_fts_stays_in_sync_after_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let pool = create_pool(&db_path).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let task_id =
+            sqlx::query("INSERT INTO tasks (name, spec, status) VALUES ('Doomed', 'spec', 'todo')")
+                .execute(&pool)
+                .await
+                .unwrap()
+                .last_insert_rowid();
+        sqlx::query("INSERT INTO events (task_id, log_type, discussion_data) VALUES (?, 'note', 'gone soon')")
+            .bind(task_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Cascades to the event, so both delete triggers fire
+        sqlx::query("DELETE FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for fts in ["tasks_fts", "events_fts"] {
+            sqlx::query(&format!(
+                "INSERT INTO {fts}({fts}, rank) VALUES ('integrity-check', 1)"
+            ))
+            .execute(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("{} out of sync: {}", fts, e));
+        }
+    }
+
     #[tokio::test]
     async fn test_workspace_state_table_structure() {
         let temp_dir = TempDir::new().unwrap();
@@ -558,6 +1251,108 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_review_status_migration_keeps_related_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let pool = create_pool(&db_path).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        let parent = sqlx::query("INSERT INTO tasks (name) VALUES ('Parent')")
+            .execute(&pool)
+            .await
+            .unwrap()
+            .last_insert_rowid();
+        sqlx::query("INSERT INTO tasks (name, parent_id) VALUES ('Child', ?)")
+            .bind(parent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO events (task_id, log_type, discussion_data) VALUES (?, 'note', 'kept')",
+        )
+        .bind(parent)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Simulate a database created before the review status existed
+        let sql: String =
+            sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type='table' AND name='tasks'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let old_sql = sql.replace(
+            "CHECK (status IN ('todo', 'doing', 'review', 'done'))",
+            "CHECK (status IN ('todo', 'doing', 'done'))",
+        );
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query(&old_sql.replacen("CREATE TABLE tasks", "CREATE TABLE tasks_old", 1))
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tasks_old SELECT * FROM tasks")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("DROP TABLE tasks")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("ALTER TABLE tasks_old RENAME TO tasks")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        drop(conn);
+        assert!(
+            sqlx::query("UPDATE tasks SET status = 'review' WHERE id = ?")
+                .bind(parent)
+                .execute(&pool)
+                .await
+                .is_err()
+        );
+
+        run_migrations(&pool).await.unwrap();
+
+        sqlx::query("UPDATE tasks SET status = 'review' WHERE id = ?")
+            .bind(parent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let events: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events WHERE task_id = ?")
+            .bind(parent)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(events, 1);
+        let children: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE parent_id = ?")
+            .bind(parent)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(children, 1);
+
+        // FTS triggers are back in place after the rebuild
+        sqlx::query("INSERT INTO tasks (name) VALUES ('Searchable rebuild')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let hits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM tasks_fts WHERE tasks_fts MATCH 'rebuild'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(hits, 1);
+    }
+
     // v0.2.0 Migration Tests
 
     #[tokio::test]
@@ -723,7 +1518,7 @@ mod tests {
                 .await
                 .unwrap();
 
-        assert_eq!(version, "0.12.0");
+        assert_eq!(version, "0.25.0");
     }
 
     #[tokio::test]
@@ -754,7 +1549,7 @@ mod tests {
                 .await
                 .unwrap();
 
-        assert_eq!(version, "0.12.0");
+        assert_eq!(version, "0.25.0");
     }
 
     #[tokio::test]
@@ -784,4 +1579,35 @@ mod tests {
 
         assert!(indices.contains(&"idx_sessions_last_active".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_sort_order_column_added() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let pool = create_pool(&db_path).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO tasks (name, status, sort_order) VALUES (?, ?, ?)")
+            .bind("Ordered")
+            .bind("todo")
+            .bind(3)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let sort_order: Option<i64> =
+            sqlx::query_scalar("SELECT sort_order FROM tasks WHERE name = 'Ordered'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(sort_order, Some(3));
+
+        let indices: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type='index' AND name='idx_tasks_status_sort_order'",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(indices.len(), 1);
+    }
 }
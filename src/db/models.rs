@@ -76,6 +76,39 @@ pub struct Dependency {
     pub created_at: DateTime<Utc>,
 }
 
+/// How a suggested task relates to the task suggestions were made for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestedLink {
+    /// Likely the same work
+    Duplicate,
+    /// The task likely depends on the suggested one
+    BlockedBy,
+    /// The suggested task likely depends on the task
+    Blocks,
+}
+
+impl SuggestedLink {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::BlockedBy => "blocked by",
+            Self::Blocks => "blocks",
+        }
+    }
+}
+
+/// A likely dependency or duplicate, from `ie deps suggest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencySuggestion {
+    pub task: Task,
+    pub link: SuggestedLink,
+    /// 0 to 1; higher is more likely
+    pub confidence: f64,
+    /// Why the task was suggested, for whoever confirms it
+    pub reasons: Vec<String>,
+}
+
 /// Task approval record for human task authorization
 /// AI must provide the passphrase to complete a human-owned task
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -129,6 +162,98 @@ pub struct Task {
     /// Free-form metadata JSON string for extensibility
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<String>,
+    /// Manual ordering position (set by drag-drop reordering in the Dashboard)
+    /// Tasks without a position sort after positioned ones in Priority mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub sort_order: Option<i64>,
+    /// Estimated effort in minutes for this task alone (not its subtasks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub estimate_minutes: Option<i64>,
+    #[serde(default, skip_serializing_if = "TaskVisibility::is_shared")]
+    #[sqlx(default)]
+    pub visibility: TaskVisibility,
+    /// Why the task cannot move; set by hand or when starting it fails on
+    /// unfinished dependencies, cleared when those dependencies are done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub blocked_reason: Option<String>,
+    /// Checklist completion, when the task has checklist items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(skip)]
+    pub checklist: Option<ChecklistProgress>,
+}
+
+/// One step of a task's checklist
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ChecklistItem {
+    pub task_id: i64,
+    /// 1-based position in the checklist
+    pub position: i64,
+    pub text: String,
+    pub checked: bool,
+    #[serde(with = "option_datetime_format")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// A URL or document a task refers to; see `links`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskLink {
+    pub id: i64,
+    pub task_id: i64,
+    /// Absolute URL, or a path for documents in the repository
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// doc, issue, pr, design or other
+    pub kind: String,
+    #[serde(with = "datetime_format")]
+    pub created_at: DateTime<Utc>,
+    /// When `ie links check` last checked the link
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_datetime_format"
+    )]
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_status: Option<i64>,
+    /// Why the last check failed (no response, missing file, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl TaskLink {
+    /// Whether the last check found the link dead
+    ///
+    /// 401 and 403 only mean the checker was not let in, so they do not count.
+    pub fn is_dead(&self) -> bool {
+        self.last_error.is_some()
+            || self
+                .last_status
+                .is_some_and(|s| s >= 400 && s != 401 && s != 403)
+    }
+}
+
+/// How much of a task's checklist is checked
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecklistProgress {
+    pub checked: i64,
+    pub total: i64,
+    /// Rounded down, so 100 means every item is checked
+    pub percent: i64,
+}
+
+impl ChecklistProgress {
+    pub fn new(checked: i64, total: i64) -> Self {
+        Self {
+            checked,
+            total,
+            percent: if total > 0 { checked * 100 / total } else { 0 },
+        }
+    }
 }
 
 fn default_owner() -> String {
@@ -141,6 +266,9 @@ pub struct TaskWithEvents {
     pub task: Task,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub events_summary: Option<EventsSummary>,
+    /// What the task produced, from its latest completion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CompletionOutcome>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -149,6 +277,346 @@ pub struct EventsSummary {
     pub recent_events: Vec<Event>,
 }
 
+/// One recorded version of a task's spec
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct SpecRevision {
+    pub task_id: i64,
+    /// 1-based, per task
+    pub revision: i64,
+    pub spec: String,
+    #[serde(with = "datetime_format")]
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Whether a change was made by a person or an agent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActorKind {
+    #[default]
+    Human,
+    Ai,
+}
+
+/// Who made a change; recorded on events and status transitions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    pub kind: ActorKind,
+    /// Agent session (`IE_SESSION_ID`), if one was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Program acting for the user, e.g. `dashboard` or an agent's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+}
+
+impl Actor {
+    pub fn human() -> Self {
+        Self::default()
+    }
+
+    pub fn ai() -> Self {
+        Self {
+            kind: ActorKind::Ai,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_client(mut self, client: impl Into<String>) -> Self {
+        self.client = Some(client.into());
+        self
+    }
+
+    pub fn is_ai(&self) -> bool {
+        self.kind == ActorKind::Ai
+    }
+
+    /// The actor of this process: `IE_ACTOR` (`ai`, or `human` by default),
+    /// `IE_SESSION_ID` and `IE_CLIENT`
+    pub fn from_env() -> Self {
+        let is_ai = std::env::var("IE_ACTOR").is_ok_and(|kind| kind.eq_ignore_ascii_case("ai"));
+        let mut actor = if is_ai { Self::ai() } else { Self::human() };
+        let session_id = crate::workspace::resolve_session_id(None);
+        if session_id != crate::workspace::DEFAULT_SESSION_ID {
+            actor.session_id = Some(session_id);
+        }
+        actor.client = std::env::var("IE_CLIENT").ok().filter(|c| !c.is_empty());
+        actor
+    }
+}
+
+impl std::fmt::Display for Actor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self.kind {
+            ActorKind::Human => "human",
+            ActorKind::Ai => "ai",
+        })?;
+        if let Some(session_id) = &self.session_id {
+            write!(f, " session {}", session_id)?;
+        }
+        if let Some(client) = &self.client {
+            write!(f, " via {}", client)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a status change came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum TransitionSource {
+    #[default]
+    Cli,
+    Dashboard,
+    Plan,
+}
+
+impl TransitionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cli => "cli",
+            Self::Dashboard => "dashboard",
+            Self::Plan => "plan",
+        }
+    }
+}
+
+/// One recorded status change of a task
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskTransition {
+    pub id: i64,
+    pub task_id: i64,
+    pub from_status: String,
+    pub to_status: String,
+    pub source: TransitionSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(json(nullable))]
+    pub actor: Option<Actor>,
+    #[serde(with = "datetime_format")]
+    pub changed_at: DateTime<Utc>,
+}
+
+/// A named goal grouping tasks from anywhere in the tree
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Milestone {
+    pub id: i64,
+    pub name: String,
+    #[serde(with = "datetime_format")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A time-limited claim of a task by one agent session
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct TaskLease {
+    pub task_id: i64,
+    /// Session ID of the claiming agent
+    pub assignee: String,
+    #[serde(with = "datetime_format")]
+    pub claimed_at: DateTime<Utc>,
+    #[serde(with = "datetime_format")]
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+/// State-of-the-world note left when an agent session ends
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct Handoff {
+    pub id: i64,
+    /// Task the session was working on, if any
+    pub task_id: Option<i64>,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_steps: Option<String>,
+    #[serde(with = "datetime_format")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A task waiting on a human, from `ie inbox`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct InboxItem {
+    pub task_id: i64,
+    pub name: String,
+    pub status: String,
+    pub owner: String,
+    /// Why an agent flagged the task as needing a human
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub needs_human: Option<String>,
+    pub effective_priority: f64,
+    /// Unfinished tasks that depend on this one
+    pub blocking: i64,
+}
+
+/// Progress of one milestone, from `ie report milestone <name>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MilestoneReport {
+    pub milestone: Milestone,
+    pub total_tasks: i64,
+    pub done_tasks: i64,
+    /// Share of milestone tasks that are done, 0-100
+    pub completion_percent: f64,
+    pub estimate: EstimateRollup,
+    pub tasks: Vec<Task>,
+    /// Unfinished milestone tasks waiting on incomplete dependencies
+    pub blocked: Vec<BlockedTask>,
+    pub at_risk: Vec<AtRiskTask>,
+}
+
+/// Material for a pull request description, from `ie report pr --task <id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrReport {
+    pub task: Task,
+    /// Completed tasks below `task`, in id order
+    pub changes: Vec<CompletedTask>,
+    /// `decision` events anywhere in the subtree, oldest first
+    pub decisions: Vec<Event>,
+    /// Outcome recorded when `task` itself was completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CompletionOutcome>,
+    /// Tasks below `task` that are not done yet
+    pub remaining: Vec<Task>,
+    /// The description, rendered from the above
+    pub markdown: String,
+}
+
+/// A completed task and the outcome recorded with it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedTask {
+    pub task: Task,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CompletionOutcome>,
+}
+
+/// What happened since the last standup, from `ie report standup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandupReport {
+    #[serde(with = "datetime_format")]
+    pub since: DateTime<Utc>,
+    /// Tasks completed since then, oldest first
+    pub completed: Vec<CompletedTask>,
+    /// Tasks in doing, longest running first
+    pub in_progress: Vec<InProgressTask>,
+    /// Unfinished tasks that are blocked or reported a blocker since then
+    pub blockers: Vec<StandupBlocker>,
+    /// The summary, rendered from the above
+    pub markdown: String,
+}
+
+/// A task in progress and the last thing logged on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InProgressTask {
+    pub task: Task,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_event: Option<Event>,
+}
+
+/// Why an unfinished task is stuck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandupBlocker {
+    pub task: Task,
+    /// The task's blocked reason, else its latest `blocker` event
+    pub reason: String,
+}
+
+/// A task together with every incomplete task it transitively depends on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedTask {
+    pub task: Task,
+    /// Nearest blockers first
+    pub blocking_chain: Vec<Task>,
+}
+
+/// An unfinished milestone task that is likely to hold the milestone up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtRiskTask {
+    pub task: Task,
+    pub reasons: Vec<String>,
+}
+
+/// A task in progress, from `ie report aging`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgingTask {
+    pub task: Task,
+    /// Minutes since the task was first started
+    pub age_minutes: i64,
+}
+
+/// How long finished tasks took, from `ie report cycle-time`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleTimeReport {
+    /// Most recently completed first
+    pub tasks: Vec<TaskCycleTime>,
+    /// Only filled in when asked for with `--percentiles`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub percentiles: Vec<CycleTimePercentile>,
+}
+
+/// A done task and the minutes from its first start to its first completion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCycleTime {
+    pub task: Task,
+    pub cycle_minutes: i64,
+    /// Minutes actually spent in `doing`, across every start and stop;
+    /// unknown for tasks started before status history was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleTimePercentile {
+    pub percentile: u8,
+    pub minutes: i64,
+}
+
+/// What an activity feed entry records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ActivityKind {
+    /// A task was created
+    Created,
+    /// A task was started for the first time
+    Started,
+    /// A task was completed for the first time
+    Completed,
+    /// An event was logged
+    Event,
+}
+
+/// One entry of the cross-task activity feed, see `EventManager::recent_activity`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
+pub struct ActivityEntry {
+    pub timestamp: DateTime<Utc>,
+    pub kind: ActivityKind,
+    pub task_id: i64,
+    pub task_name: String,
+    /// Status the task has now
+    pub task_status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Filters of the activity feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityFilter {
+    /// Only entries within this window (e.g. "1d")
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Only entries about this task
+    #[serde(default)]
+    pub task_id: Option<i64>,
+    /// Only events of this type (leaves out status transitions)
+    #[serde(default)]
+    pub event_type: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct Event {
     pub id: i64,
@@ -157,6 +625,196 @@ pub struct Event {
     pub timestamp: DateTime<Utc>,
     pub log_type: String,
     pub discussion_data: String,
+    /// Who the event is meant for; omitted from JSON when `normal`
+    #[serde(default, skip_serializing_if = "EventVisibility::is_normal")]
+    #[sqlx(default)]
+    pub visibility: EventVisibility,
+    /// Who logged it; unknown for events recorded before actors were
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default, json(nullable))]
+    pub actor: Option<Actor>,
+}
+
+/// An event to record, see `EventManager::add_events`
+#[derive(Debug, Clone)]
+pub struct NewEvent {
+    pub task_id: i64,
+    pub log_type: String,
+    pub discussion_data: String,
+    pub visibility: EventVisibility,
+    /// When it happened; `None` means now
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// What a task produced, given when it is completed
+///
+/// Stored as an `outcome` event whose text is the summary followed by an
+/// `Artifacts:` list, so it reads naturally wherever events are shown.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CompletionOutcome {
+    pub summary: String,
+    /// Files, URLs or other things the task produced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+}
+
+impl CompletionOutcome {
+    /// `log_type` of outcome events
+    pub const LOG_TYPE: &'static str = "outcome";
+
+    const ARTIFACTS_HEADER: &'static str = "\n\nArtifacts:\n";
+
+    /// Event text for this outcome
+    pub fn to_event_text(&self) -> String {
+        let mut text = self.summary.clone();
+        if !self.artifacts.is_empty() {
+            text.push_str(Self::ARTIFACTS_HEADER);
+            let lines: Vec<String> = self.artifacts.iter().map(|a| format!("- {}", a)).collect();
+            text.push_str(&lines.join("\n"));
+        }
+        text
+    }
+
+    /// Read an outcome back from its event text
+    pub fn from_event_text(text: &str) -> Self {
+        match text.rsplit_once(Self::ARTIFACTS_HEADER) {
+            Some((summary, list)) => Self {
+                summary: summary.to_string(),
+                artifacts: list
+                    .lines()
+                    .map(|line| line.strip_prefix("- ").unwrap_or(line).to_string())
+                    .collect(),
+            },
+            None => Self {
+                summary: text.to_string(),
+                artifacts: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Event visibility level
+///
+/// Levels are ordered: reading at a level includes every level below it.
+/// Only `normal` events are shown by default (digests, Dashboard, task
+/// details); the others must be asked for explicitly.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum EventVisibility {
+    /// Shared history
+    #[default]
+    Normal,
+    /// Detailed scratch reasoning, debug output
+    #[serde(alias = "debug")]
+    Verbose,
+    /// Agent-private notes
+    #[serde(alias = "all")]
+    Private,
+}
+
+impl EventVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Verbose => "verbose",
+            Self::Private => "private",
+        }
+    }
+
+    pub fn is_normal(&self) -> bool {
+        *self == Self::Normal
+    }
+
+    /// SQL condition selecting events visible at this level
+    pub fn sql_filter(&self) -> &'static str {
+        match self {
+            Self::Normal => "visibility = 'normal'",
+            Self::Verbose => "visibility IN ('normal', 'verbose')",
+            Self::Private => "1=1",
+        }
+    }
+}
+
+impl std::str::FromStr for EventVisibility {
+    type Err = String;
+
+    /// Parse a level; `all` is accepted as an alias for `private`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(Self::Normal),
+            "verbose" | "debug" => Ok(Self::Verbose),
+            "private" | "all" => Ok(Self::Private),
+            other => Err(format!(
+                "Invalid visibility '{}'. Expected normal, verbose or private",
+                other
+            )),
+        }
+    }
+}
+
+/// Who may see a task
+///
+/// Private tasks (and everything under them) are left out of listings,
+/// search, recommendations and exports unless the caller runs with
+/// `ie --scope private`; see `TaskManager::with_scope`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum TaskVisibility {
+    /// Visible to every caller
+    #[default]
+    Shared,
+    /// Personal; only visible in the private scope
+    Private,
+}
+
+impl TaskVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Shared => "shared",
+            Self::Private => "private",
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        *self == Self::Shared
+    }
+
+    /// SQL condition selecting tasks visible to a caller with this scope
+    pub fn sql_filter(&self) -> &'static str {
+        match self {
+            Self::Shared => "visibility = 'shared'",
+            Self::Private => "1=1",
+        }
+    }
+
+    /// SQL condition on a `task_id` column: its task is visible to this scope
+    pub fn sql_task_filter(&self) -> &'static str {
+        match self {
+            Self::Shared => "task_id IN (SELECT id FROM tasks WHERE visibility = 'shared')",
+            Self::Private => "1=1",
+        }
+    }
+}
+
+impl std::str::FromStr for TaskVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shared" => Ok(Self::Shared),
+            "private" => Ok(Self::Private),
+            other => Err(format!(
+                "Invalid visibility '{}'. Expected shared or private",
+                other
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -172,6 +830,20 @@ pub struct Report {
     pub tasks: Option<Vec<Task>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub events: Option<Vec<Event>>,
+    /// Outcomes recorded for the reported tasks, newest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<ReportedOutcome>,
+}
+
+/// A completion outcome in a report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportedOutcome {
+    pub task_id: i64,
+    pub task_name: String,
+    #[serde(with = "datetime_format")]
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub outcome: CompletionOutcome,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -180,12 +852,17 @@ pub struct ReportSummary {
     pub tasks_by_status: StatusBreakdown,
     pub total_events: i64,
     pub date_range: Option<DateRange>,
+    /// Estimate rollup over the reported tasks
+    #[serde(default)]
+    pub estimate: EstimateRollup,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusBreakdown {
     pub todo: i64,
     pub doing: i64,
+    #[serde(default)]
+    pub review: i64,
     pub done: i64,
 }
 
@@ -202,6 +879,22 @@ pub struct DoneTaskResponse {
     pub completed_task: Task,
     pub workspace_status: WorkspaceStatus,
     pub next_step_suggestion: NextStepSuggestion,
+    /// Things worth a look that did not stop completion, e.g. unchecked checklist items
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Tasks this completion removed the last unfinished blocker of
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub newly_unblocked: Vec<UnblockedTask>,
+}
+
+/// A task that became ready to start because its last unfinished blocker was completed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct UnblockedTask {
+    pub id: i64,
+    pub name: String,
+    pub owner: String,
+    #[serde(default, skip_serializing_if = "TaskVisibility::is_shared")]
+    pub visibility: TaskVisibility,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -254,6 +947,8 @@ pub struct TaskSearchResult {
 /// Unified search result that can represent either a task or event match
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "result_type")]
+// Results are built once per query and serialized; boxing Task would only add noise
+#[allow(clippy::large_enum_variant)]
 pub enum SearchResult {
     #[serde(rename = "task")]
     Task {
@@ -261,12 +956,17 @@ pub enum SearchResult {
         task: Task,
         match_snippet: String,
         match_field: String, // "name" or "spec"
+        /// Relevance, higher is better (negated FTS5 bm25; 0 for substring matches)
+        #[serde(default)]
+        score: f64,
     },
     #[serde(rename = "event")]
     Event {
         event: Event,
         task_chain: Vec<Task>, // Ancestry: [immediate task, parent, grandparent, ...]
         match_snippet: String,
+        #[serde(default)]
+        score: f64,
     },
 }
 
@@ -281,6 +981,17 @@ pub struct PaginatedSearchResults {
     pub offset: i64,
 }
 
+/// Response for `ie task clone`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneSubtreeResponse {
+    /// The copy of the root task
+    pub root_task: Task,
+    /// Original task ID → ID of its copy
+    pub task_id_map: std::collections::BTreeMap<i64, i64>,
+    pub dependency_count: usize,
+    pub event_count: usize,
+}
+
 /// Response for spawn-subtask command - includes subtask and parent info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnSubtaskResponse {
@@ -321,15 +1032,50 @@ pub struct TaskContext {
     pub siblings: Vec<Task>,
     pub children: Vec<Task>,
     pub dependencies: TaskDependencies,
+    /// Estimate rollup over the task and all of its descendants
+    #[serde(default)]
+    pub estimate: EstimateRollup,
+    /// URLs and documents the task refers to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<TaskLink>,
+}
+
+/// Effort estimates summed over a set of tasks (usually a subtree)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EstimateRollup {
+    /// Sum of all estimates, done or not
+    pub total_minutes: i64,
+    /// Sum of estimates for tasks that are not done yet
+    pub remaining_minutes: i64,
+    /// Number of tasks without an estimate; totals undercount when non-zero
+    pub unestimated: i64,
+}
+
+impl EstimateRollup {
+    pub fn from_tasks<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        let mut rollup = Self::default();
+        for task in tasks {
+            match task.estimate_minutes {
+                Some(minutes) => {
+                    rollup.total_minutes += minutes;
+                    if task.status != "done" {
+                        rollup.remaining_minutes += minutes;
+                    }
+                },
+                None => rollup.unestimated += 1,
+            }
+        }
+        rollup
+    }
 }
 
 /// Sort order for task queries
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TaskSortBy {
     /// Legacy: ORDER BY id ASC (backward compatible)
     Id,
-    /// ORDER BY priority ASC, complexity ASC, id ASC
+    /// ORDER BY sort_order ASC NULLS LAST, effective priority ASC, complexity ASC, id ASC
     Priority,
     /// ORDER BY first_doing_at DESC NULLS LAST, first_todo_at DESC NULLS LAST, id ASC
     Time,
@@ -348,6 +1094,93 @@ pub struct PaginatedTasks {
     pub offset: i64,
 }
 
+/// Paginated event timeline (newest first, keyset pagination)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedEvents {
+    pub events: Vec<Event>,
+    /// Events matching the filters, ignoring the `before` cursor
+    pub total_count: i64,
+    pub has_more: bool,
+    pub limit: i64,
+    /// Cursor for the next (older) page: pass as `before`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_before: Option<i64>,
+}
+
+/// A task plus how many children it has, so trees can offer to expand it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskTreeNode {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub task: Task,
+    pub child_count: i64,
+}
+
+/// One page of a task's children (or of root tasks), keyset paginated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildrenPage {
+    /// None for root tasks
+    pub parent_id: Option<i64>,
+    pub tasks: Vec<TaskTreeNode>,
+    /// Children of the parent, ignoring the cursor
+    pub total_count: i64,
+    pub has_more: bool,
+    pub limit: i64,
+    /// Cursor for the next page: pass as `cursor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<i64>,
+}
+
+/// Rollup of a task and all of its descendants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtreeStats {
+    pub root_id: i64,
+    /// Tasks in the subtree, the root included
+    pub total_tasks: i64,
+    pub todo: i64,
+    pub doing: i64,
+    pub review: i64,
+    pub done: i64,
+    /// Share of subtree tasks that are done, 0-100
+    pub completion_percent: f64,
+    pub estimate: EstimateRollup,
+    /// Sum of complexity over the tasks that have one
+    pub total_complexity: i64,
+    /// Longest run of incomplete dependencies holding up an unfinished subtree
+    /// task: that task first, then each task the previous one waits on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocking_chain: Vec<Task>,
+}
+
+impl SubtreeStats {
+    /// Count `tasks` (the root and its descendants); leaves the blocking chain empty
+    pub fn from_tasks(root_id: i64, tasks: &[Task]) -> Self {
+        let count = |status: &str| tasks.iter().filter(|t| t.status == status).count() as i64;
+        let total_tasks = tasks.len() as i64;
+        let done = count("done");
+        Self {
+            root_id,
+            total_tasks,
+            todo: count("todo"),
+            doing: count("doing"),
+            review: count("review"),
+            done,
+            completion_percent: if total_tasks == 0 {
+                0.0
+            } else {
+                done as f64 * 100.0 / total_tasks as f64
+            },
+            estimate: EstimateRollup::from_tasks(tasks),
+            total_complexity: tasks
+                .iter()
+                .filter_map(|t| t.complexity)
+                .map(i64::from)
+                .sum(),
+            blocking_chain: Vec::new(),
+        }
+    }
+}
+
 /// Workspace statistics (aggregated counts without loading tasks)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceStats {
@@ -366,6 +1199,25 @@ pub struct PickNextResponse {
     pub reason_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// Every open task scored the way `pick_next` ranks them, best first;
+    /// only filled in by `explain_next`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<PickNextCandidate>,
+}
+
+/// An open task as seen by `pick_next`, with why it ranks where it does
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PickNextCandidate {
+    pub task_id: i64,
+    pub name: String,
+    pub status: String,
+    /// Higher is better; 0 when `pick_next` would never suggest the task
+    pub score: f64,
+    /// Priority after aging (1 = critical, 5 = unset)
+    pub effective_priority: f64,
+    /// Whether this is the suggested task
+    pub selected: bool,
+    pub reasons: Vec<String>,
 }
 
 impl PickNextResponse {
@@ -376,6 +1228,7 @@ impl PickNextResponse {
             task: Some(task),
             reason_code: None,
             message: None,
+            candidates: Vec::new(),
         }
     }
 
@@ -386,6 +1239,7 @@ impl PickNextResponse {
             task: Some(task),
             reason_code: None,
             message: None,
+            candidates: Vec::new(),
         }
     }
 
@@ -398,6 +1252,7 @@ impl PickNextResponse {
             message: Some(
                 "No tasks found in this project. Your intent backlog is empty.".to_string(),
             ),
+            candidates: Vec::new(),
         }
     }
 
@@ -408,6 +1263,7 @@ impl PickNextResponse {
             task: None,
             reason_code: Some("ALL_TASKS_COMPLETED".to_string()),
             message: Some("Project Complete! All intents have been realized.".to_string()),
+            candidates: Vec::new(),
         }
     }
 
@@ -418,7 +1274,33 @@ impl PickNextResponse {
             task: None,
             reason_code: Some("NO_AVAILABLE_TODOS".to_string()),
             message: Some("No immediate next task found based on the current context.".to_string()),
+            candidates: Vec::new(),
+        }
+    }
+
+    /// Format the scored candidates as human-readable text
+    pub fn format_candidates_as_text(&self) -> String {
+        if self.candidates.is_empty() {
+            return "No open tasks to rank.".to_string();
+        }
+        let mut out = String::from("Candidates (best first):\n");
+        for c in &self.candidates {
+            let score = if c.score > 0.0 {
+                format!("{:>5.1}", c.score)
+            } else {
+                "    -".to_string()
+            };
+            out.push_str(&format!(
+                "{} {} #{} {} [{}]\n      {}\n",
+                if c.selected { "→" } else { " " },
+                score,
+                c.task_id,
+                c.name,
+                c.status,
+                c.reasons.join("; ")
+            ));
         }
+        out
     }
 
     /// Format response as human-readable text
@@ -566,6 +1448,11 @@ mod tests {
             active_form: None,
             owner: "human".to_string(),
             metadata: None,
+            sort_order: None,
+            estimate_minutes: None,
+            visibility: Default::default(),
+            blocked_reason: None,
+            checklist: None,
         }
     }
 
@@ -713,6 +1600,7 @@ mod tests {
         let task_with_events = TaskWithEvents {
             task,
             events_summary: None,
+            outcome: None,
         };
 
         let json = serde_json::to_string(&task_with_events).unwrap();
@@ -722,6 +1610,24 @@ mod tests {
         assert!(!json.contains("events_summary"));
     }
 
+    #[test]
+    fn test_completion_outcome_event_text_round_trip() {
+        let outcome = CompletionOutcome {
+            summary: "Login works with JWT".to_string(),
+            artifacts: vec!["src/auth.rs".to_string(), "docs/auth.md".to_string()],
+        };
+        let text = outcome.to_event_text();
+        assert_eq!(
+            text,
+            "Login works with JWT\n\nArtifacts:\n- src/auth.rs\n- docs/auth.md"
+        );
+        assert_eq!(CompletionOutcome::from_event_text(&text), outcome);
+
+        let plain = CompletionOutcome::from_event_text("Logged by hand");
+        assert_eq!(plain.summary, "Logged by hand");
+        assert!(plain.artifacts.is_empty());
+    }
+
     #[test]
     fn test_report_summary_with_date_range() {
         let from = Utc::now() - chrono::Duration::days(7);
@@ -732,10 +1638,12 @@ mod tests {
             tasks_by_status: StatusBreakdown {
                 todo: 5,
                 doing: 3,
+                review: 0,
                 done: 2,
             },
             total_events: 20,
             date_range: Some(DateRange { from, to }),
+            estimate: EstimateRollup::default(),
         };
 
         let json = serde_json::to_string(&summary).unwrap();
@@ -0,0 +1,216 @@
+//! Connection pool tuning per kind of process
+//!
+//! One-shot CLI commands and the long-running Dashboard want different
+//! trade-offs: several agents running `ie` in parallel should queue on the
+//! write lock rather than fail with "database is locked", while the Dashboard
+//! serves many small reads and should not hang a request for long.
+//!
+//! Each `PoolProfile` has tuned defaults that can be overridden per profile in
+//! `~/.intent-engine/db.json`:
+//!
+//! ```json
+//! { "cli": { "busy_timeout_ms": 60000 }, "dashboard": { "max_connections": 16 } }
+//! ```
+//!
+//! and, for every profile at once, by the `IE_DB_MAX_CONNECTIONS`,
+//! `IE_DB_BUSY_TIMEOUT_MS` and `IE_DB_SYNCHRONOUS` environment variables.
+
+use crate::error::{IntentError, Result};
+use serde::Deserialize;
+use sqlx::sqlite::SqliteSynchronous;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "db.json";
+
+pub const ENV_MAX_CONNECTIONS: &str = "IE_DB_MAX_CONNECTIONS";
+pub const ENV_BUSY_TIMEOUT_MS: &str = "IE_DB_BUSY_TIMEOUT_MS";
+pub const ENV_SYNCHRONOUS: &str = "IE_DB_SYNCHRONOUS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolProfile {
+    /// Short-lived `ie` commands, often several in parallel
+    Cli,
+    /// The Dashboard daemon
+    Dashboard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub busy_timeout: Duration,
+    pub synchronous: SqliteSynchronous,
+}
+
+impl PoolProfile {
+    pub fn defaults(self) -> PoolConfig {
+        match self {
+            // Few connections and a long wait: a command that queues for a
+            // few seconds beats one that fails
+            PoolProfile::Cli => PoolConfig {
+                max_connections: 4,
+                busy_timeout: Duration::from_secs(30),
+                synchronous: SqliteSynchronous::Full,
+            },
+            // NORMAL is still corruption-safe in WAL mode and saves an fsync
+            // per commit
+            PoolProfile::Dashboard => PoolConfig {
+                max_connections: 8,
+                busy_timeout: Duration::from_secs(5),
+                synchronous: SqliteSynchronous::Normal,
+            },
+        }
+    }
+}
+
+/// Overrides for one profile; unset fields keep the profile default
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolOverrides {
+    pub max_connections: Option<u32>,
+    pub busy_timeout_ms: Option<u64>,
+    pub synchronous: Option<String>,
+}
+
+/// Contents of `~/.intent-engine/db.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolConfigFile {
+    #[serde(default)]
+    pub cli: PoolOverrides,
+    #[serde(default)]
+    pub dashboard: PoolOverrides,
+}
+
+impl PoolConfigFile {
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|h| h.join(".intent-engine").join(CONFIG_FILE))
+    }
+
+    /// Read the config file; a missing file means no overrides
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| IntentError::InvalidInput(format!("{}: {}", path.display(), e)))
+    }
+}
+
+impl PoolConfig {
+    /// Effective settings for `profile` from the config file and environment
+    pub fn load(profile: PoolProfile) -> Result<Self> {
+        Self::resolve(profile, &PoolConfigFile::load()?, |name| {
+            std::env::var(name).ok()
+        })
+    }
+
+    /// Profile defaults, then the file section, then environment variables
+    pub fn resolve(
+        profile: PoolProfile,
+        file: &PoolConfigFile,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let mut config = profile.defaults();
+        let section = match profile {
+            PoolProfile::Cli => &file.cli,
+            PoolProfile::Dashboard => &file.dashboard,
+        };
+
+        let max_connections = match env(ENV_MAX_CONNECTIONS) {
+            Some(value) => Some(parse_env(ENV_MAX_CONNECTIONS, &value)?),
+            None => section.max_connections,
+        };
+        if let Some(max) = max_connections {
+            if max == 0 {
+                return Err(IntentError::InvalidInput(
+                    "max_connections must be at least 1".to_string(),
+                ));
+            }
+            config.max_connections = max;
+        }
+
+        let busy_timeout_ms = match env(ENV_BUSY_TIMEOUT_MS) {
+            Some(value) => Some(parse_env(ENV_BUSY_TIMEOUT_MS, &value)?),
+            None => section.busy_timeout_ms,
+        };
+        if let Some(ms) = busy_timeout_ms {
+            config.busy_timeout = Duration::from_millis(ms);
+        }
+
+        if let Some(value) = env(ENV_SYNCHRONOUS).or_else(|| section.synchronous.clone()) {
+            config.synchronous = SqliteSynchronous::from_str(&value).map_err(|_| {
+                IntentError::InvalidInput(format!(
+                    "synchronous must be off, normal, full or extra, got '{}'",
+                    value
+                ))
+            })?;
+        }
+
+        Ok(config)
+    }
+}
+
+fn parse_env<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value.trim().parse().map_err(|_| {
+        IntentError::InvalidInput(format!("{} is not a valid number: '{}'", name, value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_profiles_have_distinct_defaults() {
+        let cli = PoolConfig::resolve(PoolProfile::Cli, &Default::default(), no_env).unwrap();
+        let dashboard =
+            PoolConfig::resolve(PoolProfile::Dashboard, &Default::default(), no_env).unwrap();
+        assert_eq!(cli, PoolProfile::Cli.defaults());
+        assert!(cli.busy_timeout > dashboard.busy_timeout);
+        assert!(dashboard.max_connections > cli.max_connections);
+    }
+
+    #[test]
+    fn test_file_then_env_overrides() {
+        let file: PoolConfigFile = serde_json::from_str(
+            r#"{"cli": {"max_connections": 2, "busy_timeout_ms": 1000, "synchronous": "normal"}}"#,
+        )
+        .unwrap();
+
+        let cli = PoolConfig::resolve(PoolProfile::Cli, &file, no_env).unwrap();
+        assert_eq!(cli.max_connections, 2);
+        assert_eq!(cli.busy_timeout, Duration::from_secs(1));
+        assert_eq!(cli.synchronous, SqliteSynchronous::Normal);
+
+        // The dashboard section is untouched by the cli one
+        let dashboard = PoolConfig::resolve(PoolProfile::Dashboard, &file, no_env).unwrap();
+        assert_eq!(dashboard, PoolProfile::Dashboard.defaults());
+
+        let env = |name: &str| (name == ENV_BUSY_TIMEOUT_MS).then(|| "250".to_string());
+        let cli = PoolConfig::resolve(PoolProfile::Cli, &file, env).unwrap();
+        assert_eq!(cli.busy_timeout, Duration::from_millis(250));
+        assert_eq!(cli.max_connections, 2);
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        let bad_env = |name: &str| (name == ENV_MAX_CONNECTIONS).then(|| "many".to_string());
+        assert!(PoolConfig::resolve(PoolProfile::Cli, &Default::default(), bad_env).is_err());
+
+        let zero = |name: &str| (name == ENV_MAX_CONNECTIONS).then(|| "0".to_string());
+        assert!(PoolConfig::resolve(PoolProfile::Cli, &Default::default(), zero).is_err());
+
+        let file: PoolConfigFile =
+            serde_json::from_str(r#"{"dashboard": {"synchronous": "sometimes"}}"#).unwrap();
+        assert!(PoolConfig::resolve(PoolProfile::Dashboard, &file, no_env).is_err());
+
+        assert!(serde_json::from_str::<PoolConfigFile>(r#"{"cli": {"pool": 3}}"#).is_err());
+    }
+}
@@ -1,7 +1,17 @@
-use crate::db::models::Dependency;
+use crate::db::models::{
+    Dependency, DependencySuggestion, SuggestedLink, Task, TaskVisibility, UnblockedTask,
+};
 use crate::error::{IntentError, Result};
+use crate::similarity::{name_similarity, TermVector};
 use sqlx::SqlitePool;
 
+/// Name or text similarity from which two tasks look like duplicates
+const DUPLICATE_SIMILARITY: f64 = 0.8;
+/// Text similarity from which two tasks without a mention are suggested
+const RELATED_SIMILARITY: f64 = 0.25;
+/// Confidence added when one task's spec names the other
+const MENTION_BONUS: f64 = 0.5;
+
 /// Check if adding a dependency would create a circular dependency.
 ///
 /// This function implements a depth-first search using SQLite's recursive CTE
@@ -32,7 +42,7 @@ use sqlx::SqlitePool;
 ///
 /// # Arguments
 ///
-/// * `pool` - Database connection pool
+/// * `pool` - Database connection pool or open transaction
 /// * `blocking_task_id` - ID of the task that must be completed first
 /// * `blocked_task_id` - ID of the task that depends on the blocking task
 ///
@@ -41,11 +51,14 @@ use sqlx::SqlitePool;
 /// - `Ok(true)` if adding this dependency would create a cycle
 /// - `Ok(false)` if the dependency is safe to add
 /// - `Err` if database query fails
-pub async fn check_circular_dependency(
-    pool: &SqlitePool,
+pub async fn check_circular_dependency<'e, E>(
+    pool: E,
     blocking_task_id: i64,
     blocked_task_id: i64,
-) -> Result<bool> {
+) -> Result<bool>
+where
+    E: sqlx::SqliteExecutor<'e>,
+{
     // Self-dependency is always circular (but should be prevented by DB constraint)
     if blocking_task_id == blocked_task_id {
         return Ok(true);
@@ -194,6 +207,183 @@ pub async fn get_incomplete_blocking_tasks(
     }
 }
 
+/// Unfinished tasks that `task_id` blocks and that have no other unfinished blocker
+///
+/// Called right after `task_id` is completed, this lists the tasks its
+/// completion made ready to start. Only tasks visible to `scope` are returned.
+pub async fn newly_unblocked<'e, E>(
+    pool: E,
+    task_id: i64,
+    scope: TaskVisibility,
+) -> Result<Vec<UnblockedTask>>
+where
+    E: sqlx::SqliteExecutor<'e>,
+{
+    let unblocked = sqlx::query_as::<_, UnblockedTask>(&format!(
+        r#"
+        SELECT t.id, t.name, t.owner, t.visibility
+        FROM dependencies d
+        JOIN tasks t ON t.id = d.blocked_task_id
+        WHERE d.blocking_task_id = ?
+          AND t.status != 'done'
+          AND {}
+          AND NOT EXISTS (
+              SELECT 1 FROM dependencies d2
+              JOIN tasks b ON b.id = d2.blocking_task_id
+              WHERE d2.blocked_task_id = t.id AND b.status != 'done'
+          )
+        ORDER BY t.id
+        "#,
+        scope.sql_filter()
+    ))
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(unblocked)
+}
+
+/// Open tasks that `task_id` likely depends on, blocks or duplicates, most
+/// likely first
+///
+/// Candidates are unfinished tasks visible to `scope` that are not already
+/// linked to the task by a dependency and are not its ancestors or
+/// descendants. Each is scored by the similarity of names and specs
+/// (see `crate::similarity`) and by whether one spec names the other, by
+/// `#id` or by full name. A spec naming another task suggests depending on
+/// it; otherwise similar tasks are assumed to run in creation order, the
+/// older one blocking the newer. Suggestions are only proposals: nothing is
+/// linked until confirmed with `ie task update`.
+pub async fn suggest_dependencies(
+    pool: &SqlitePool,
+    task_id: i64,
+    scope: TaskVisibility,
+    limit: usize,
+) -> Result<Vec<DependencySuggestion>> {
+    let task = sqlx::query_as::<_, Task>(&format!(
+        "SELECT {} FROM tasks WHERE id = ? AND {}",
+        crate::sql_constants::TASK_COLUMNS,
+        scope.sql_filter()
+    ))
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or(IntentError::TaskNotFound(task_id))?;
+
+    let candidates = sqlx::query_as::<_, Task>(&format!(
+        r#"
+        WITH RECURSIVE
+            ancestors(id) AS (
+                SELECT parent_id FROM tasks WHERE id = ?1 AND parent_id IS NOT NULL
+                UNION
+                SELECT t.parent_id FROM tasks t JOIN ancestors a ON t.id = a.id
+                WHERE t.parent_id IS NOT NULL
+            ),
+            descendants(id) AS (
+                SELECT id FROM tasks WHERE parent_id = ?1
+                UNION
+                SELECT t.id FROM tasks t JOIN descendants d ON t.parent_id = d.id
+            )
+        SELECT {}
+        FROM tasks
+        WHERE id != ?1
+          AND status != 'done'
+          AND {}
+          AND id NOT IN ancestors
+          AND id NOT IN descendants
+          AND id NOT IN (SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ?1)
+          AND id NOT IN (SELECT blocked_task_id FROM dependencies WHERE blocking_task_id = ?1)
+        ORDER BY id
+        "#,
+        crate::sql_constants::TASK_COLUMNS,
+        scope.sql_filter()
+    ))
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+
+    let vector = TermVector::for_task(&task.name, task.spec.as_deref());
+    let mut suggestions: Vec<DependencySuggestion> = candidates
+        .into_iter()
+        .filter_map(|candidate| score_candidate(&task, &vector, candidate))
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(a.task.id.cmp(&b.task.id))
+    });
+    suggestions.truncate(limit);
+    Ok(suggestions)
+}
+
+/// How `candidate` relates to `task`, or None when it looks unrelated
+fn score_candidate(
+    task: &Task,
+    vector: &TermVector,
+    candidate: Task,
+) -> Option<DependencySuggestion> {
+    let candidate_vector = TermVector::for_task(&candidate.name, candidate.spec.as_deref());
+    let similarity = vector.cosine(&candidate_vector);
+    let names = name_similarity(&task.name, &candidate.name);
+
+    let mut reasons = Vec::new();
+    let (link, confidence) = if names.max(similarity) >= DUPLICATE_SIMILARITY {
+        reasons.push(if names >= DUPLICATE_SIMILARITY {
+            "names are nearly identical".to_string()
+        } else {
+            "names and specs are nearly identical".to_string()
+        });
+        (SuggestedLink::Duplicate, names.max(similarity))
+    } else if mentions(task.spec.as_deref(), &candidate) {
+        reasons.push(format!("spec of #{} names it", task.id));
+        (SuggestedLink::BlockedBy, similarity + MENTION_BONUS)
+    } else if mentions(candidate.spec.as_deref(), task) {
+        reasons.push(format!("its spec names #{}", task.id));
+        (SuggestedLink::Blocks, similarity + MENTION_BONUS)
+    } else if similarity >= RELATED_SIMILARITY {
+        let link = if candidate.id < task.id {
+            reasons.push("created earlier".to_string());
+            SuggestedLink::BlockedBy
+        } else {
+            reasons.push("created later".to_string());
+            SuggestedLink::Blocks
+        };
+        (link, similarity)
+    } else {
+        return None;
+    };
+
+    let shared = vector.shared_terms(&candidate_vector);
+    if !shared.is_empty() {
+        let shown: Vec<&str> = shared.iter().take(5).map(String::as_str).collect();
+        reasons.push(format!("shares terms: {}", shown.join(", ")));
+    }
+
+    Some(DependencySuggestion {
+        task: candidate,
+        link,
+        confidence: (confidence.min(1.0) * 100.0).round() / 100.0,
+        reasons,
+    })
+}
+
+/// Whether `spec` refers to `task` by `#id` or by its full name
+fn mentions(spec: Option<&str>, task: &Task) -> bool {
+    let Some(spec) = spec else {
+        return false;
+    };
+    let id_ref = format!("#{}", task.id);
+    let by_id = spec.match_indices(&id_ref).any(|(at, _)| {
+        !spec[at + id_ref.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit())
+    });
+    // Very short names ("API", "UI") match too much prose to count
+    let name = task.name.trim().to_lowercase();
+    by_id || (name.chars().count() >= 6 && spec.to_lowercase().contains(&name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,4 +607,67 @@ mod tests {
         let incomplete = get_incomplete_blocking_tasks(&pool, task_a).await.unwrap();
         assert!(incomplete.is_none());
     }
+
+    #[tokio::test]
+    async fn test_suggest_dependencies() {
+        let (_temp, pool) = setup_test_db().await;
+        let insert = |name: &'static str, spec: String, parent: Option<i64>| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query(
+                    "INSERT INTO tasks (name, spec, parent_id, status) VALUES (?, ?, ?, 'todo')",
+                )
+                .bind(name)
+                .bind(spec)
+                .bind(parent)
+                .execute(&pool)
+                .await
+                .unwrap()
+                .last_insert_rowid()
+            }
+        };
+        let cart = insert("Cart API", "REST endpoints for cart items".into(), None).await;
+        let checkout = insert(
+            "Checkout page",
+            format!("Lists cart items from #{} and takes payment", cart),
+            None,
+        )
+        .await;
+        let duplicate = insert("Checkout pages", String::new(), None).await;
+        insert("Billing export", "CSV of invoices".into(), None).await;
+        insert(
+            "Checkout page form",
+            "Checkout page fields".into(),
+            Some(checkout),
+        )
+        .await;
+
+        let suggestions = suggest_dependencies(&pool, checkout, TaskVisibility::Private, 10)
+            .await
+            .unwrap();
+        let found: Vec<(i64, SuggestedLink)> =
+            suggestions.iter().map(|s| (s.task.id, s.link)).collect();
+        // The unrelated task and the subtask are left out
+        assert_eq!(
+            found,
+            [
+                (duplicate, SuggestedLink::Duplicate),
+                (cart, SuggestedLink::BlockedBy)
+            ]
+        );
+        assert_eq!(suggestions[0].confidence, 1.0);
+        assert!(suggestions[1].reasons[0].contains("names it"));
+
+        // Linked tasks are no longer suggested
+        add_dependency(&pool, cart, checkout).await.unwrap();
+        let suggestions = suggest_dependencies(&pool, checkout, TaskVisibility::Private, 10)
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+
+        assert!(matches!(
+            suggest_dependencies(&pool, 999, TaskVisibility::Private, 10).await,
+            Err(IntentError::TaskNotFound(999))
+        ));
+    }
 }
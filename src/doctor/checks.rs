@@ -0,0 +1,264 @@
+use super::{CheckResult, DANGLING_DEPENDENCIES, FOCUS, FTS_INDEX, ORPHANED_EVENTS, SCHEMA};
+use crate::db::SCHEMA_VERSION;
+use crate::error::Result;
+use sqlx::SqlitePool;
+
+/// Tables created by `run_migrations`
+const TABLES: &[&str] = &[
+    "tasks",
+    "events",
+    "workspace_state",
+    "sessions",
+    "suggestions",
+    "settings_audit",
+    "spec_revisions",
+    "task_transitions",
+    "spec_meta",
+    "checklist_items",
+    "task_links",
+    "milestones",
+    "milestone_tasks",
+    "plan_journal",
+    "dependencies",
+    "embeddings",
+    "events_archive",
+    "handoffs",
+    "task_leases",
+    "notification_queue",
+    "sync_state",
+    "sync_rows",
+    "tasks_fts",
+    "events_fts",
+];
+
+/// Triggers that keep the FTS indexes in sync with their content tables
+const TRIGGERS: &[&str] = &[
+    "tasks_ai",
+    "tasks_ad",
+    "tasks_au",
+    "events_ai",
+    "events_ad",
+    "events_au",
+];
+
+const EVENT_COLUMNS: &[&str] = &[
+    "id",
+    "task_id",
+    "timestamp",
+    "log_type",
+    "discussion_data",
+    "visibility",
+    "actor",
+];
+
+/// FTS index and the content table it mirrors
+pub(super) const FTS_TABLES: &[(&str, &str)] = &[("tasks_fts", "tasks"), ("events_fts", "events")];
+
+/// Run every check, in a fixed order
+pub async fn run_checks(pool: &SqlitePool) -> Result<Vec<CheckResult>> {
+    Ok(vec![
+        check_schema(pool).await?,
+        check_fts(pool).await?,
+        check_orphaned_events(pool).await?,
+        check_dangling_dependencies(pool).await?,
+        check_focus(pool).await?,
+    ])
+}
+
+async fn check_schema(pool: &SqlitePool) -> Result<CheckResult> {
+    let mut problems = Vec::new();
+
+    for (kind, expected) in [("table", TABLES), ("trigger", TRIGGERS)] {
+        for name in expected.iter() {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = ? AND name = ?",
+            )
+            .bind(kind)
+            .bind(name)
+            .fetch_one(pool)
+            .await?;
+            if !exists {
+                problems.push(format!("missing {} {}", kind, name));
+            }
+        }
+    }
+
+    let task_columns: Vec<&str> = crate::sql_constants::TASK_COLUMNS.split(", ").collect();
+    for (table, expected) in [
+        ("tasks", task_columns.as_slice()),
+        ("events", EVENT_COLUMNS),
+    ] {
+        let actual: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+        if actual.is_empty() {
+            // Already reported as a missing table
+            continue;
+        }
+        for column in expected {
+            if !actual.iter().any(|c| c == column) {
+                problems.push(format!("missing column {}.{}", table, column));
+            }
+        }
+    }
+
+    if table_exists(pool, "workspace_state").await? {
+        let version: Option<String> =
+            sqlx::query_scalar("SELECT value FROM workspace_state WHERE key = 'schema_version'")
+                .fetch_optional(pool)
+                .await?;
+        if version.as_deref() != Some(SCHEMA_VERSION) {
+            problems.push(format!(
+                "schema_version is {}, expected {}",
+                version.as_deref().unwrap_or("unset"),
+                SCHEMA_VERSION
+            ));
+        }
+    }
+
+    Ok(CheckResult {
+        name: SCHEMA,
+        problems,
+    })
+}
+
+async fn check_fts(pool: &SqlitePool) -> Result<CheckResult> {
+    let mut problems = Vec::new();
+    for (fts, content) in FTS_TABLES {
+        if !tables_exist(pool, &[fts, content]).await? {
+            // Reported by the schema check
+            continue;
+        }
+        // A non-zero rank also compares the index against the content table
+        let check = format!(
+            "INSERT INTO {fts}({fts}, rank) VALUES ('integrity-check', 1)",
+            fts = fts
+        );
+        if sqlx::query(&check).execute(pool).await.is_err() {
+            problems.push(format!("{} is out of sync with {}", fts, content));
+        }
+    }
+    Ok(CheckResult {
+        name: FTS_INDEX,
+        problems,
+    })
+}
+
+async fn check_orphaned_events(pool: &SqlitePool) -> Result<CheckResult> {
+    if !tables_exist(pool, &["tasks", "events"]).await? {
+        return Ok(skipped(ORPHANED_EVENTS));
+    }
+    let orphans: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM events WHERE task_id NOT IN (SELECT id FROM tasks)",
+    )
+    .fetch_one(pool)
+    .await?;
+    let mut problems = Vec::new();
+    if orphans > 0 {
+        problems.push(format!("{} event(s) belong to missing tasks", orphans));
+    }
+    Ok(CheckResult {
+        name: ORPHANED_EVENTS,
+        problems,
+    })
+}
+
+async fn check_dangling_dependencies(pool: &SqlitePool) -> Result<CheckResult> {
+    if !tables_exist(pool, &["tasks", "dependencies"]).await? {
+        return Ok(skipped(DANGLING_DEPENDENCIES));
+    }
+    let dangling: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM dependencies
+        WHERE blocking_task_id NOT IN (SELECT id FROM tasks)
+           OR blocked_task_id NOT IN (SELECT id FROM tasks)
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    let mut problems = Vec::new();
+    if dangling > 0 {
+        problems.push(format!(
+            "{} dependency(ies) reference missing tasks",
+            dangling
+        ));
+    }
+    Ok(CheckResult {
+        name: DANGLING_DEPENDENCIES,
+        problems,
+    })
+}
+
+async fn check_focus(pool: &SqlitePool) -> Result<CheckResult> {
+    if !tables_exist(pool, &["tasks", "sessions", "workspace_state"]).await? {
+        return Ok(skipped(FOCUS));
+    }
+    let mut problems = Vec::new();
+
+    let sessions: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT session_id, current_task_id FROM sessions
+        WHERE current_task_id IS NOT NULL
+          AND current_task_id NOT IN (SELECT id FROM tasks)
+        ORDER BY session_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    for (session_id, task_id) in sessions {
+        problems.push(format!(
+            "session {} is focused on missing task #{}",
+            session_id, task_id
+        ));
+    }
+
+    // Pre-session databases kept the focus in workspace_state
+    let legacy: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT value FROM workspace_state
+        WHERE key = 'current_task_id' AND value != ''
+          AND CAST(value AS INTEGER) NOT IN (SELECT id FROM tasks)
+        "#,
+    )
+    .fetch_optional(pool)
+    .await?;
+    if let Some(value) = legacy {
+        problems.push(format!(
+            "workspace_state current_task_id points at missing task #{}",
+            value
+        ));
+    }
+
+    Ok(CheckResult {
+        name: FOCUS,
+        problems,
+    })
+}
+
+/// A check that cannot run because its tables are missing; the schema
+/// check already reports those
+fn skipped(name: &'static str) -> CheckResult {
+    CheckResult {
+        name,
+        problems: Vec::new(),
+    }
+}
+
+async fn tables_exist(pool: &SqlitePool, names: &[&str]) -> Result<bool> {
+    for name in names {
+        if !table_exists(pool, name).await? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+async fn table_exists(pool: &SqlitePool, name: &str) -> Result<bool> {
+    Ok(sqlx::query_scalar(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?",
+    )
+    .bind(name)
+    .fetch_one(pool)
+    .await?)
+}
@@ -0,0 +1,192 @@
+//! Database integrity checks and repair for `ie doctor`
+//!
+//! Every normal command runs `run_migrations` on open, which quietly rebuilds
+//! the task search index and recreates missing schema objects. The doctor
+//! therefore opens the database without migrating, so it sees the file as a
+//! crash left it. `repair` fixes whatever the checks report.
+
+mod checks;
+mod repair;
+
+pub use checks::run_checks;
+pub use repair::repair;
+
+use serde::Serialize;
+
+/// Check names, in the order they run
+pub const SCHEMA: &str = "schema";
+pub const FTS_INDEX: &str = "fts_index";
+pub const ORPHANED_EVENTS: &str = "orphaned_events";
+pub const DANGLING_DEPENDENCIES: &str = "dangling_dependencies";
+pub const FOCUS: &str = "focus";
+
+/// Outcome of one integrity check
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    /// Human-readable problems; empty when the check passed
+    pub problems: Vec<String>,
+}
+
+impl CheckResult {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::add_dependency;
+    use crate::events::EventManager;
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+    use crate::workspace::WorkspaceManager;
+    use sqlx::SqlitePool;
+
+    fn failed(results: &[CheckResult]) -> Vec<&'static str> {
+        results
+            .iter()
+            .filter(|r| !r.is_ok())
+            .map(|r| r.name)
+            .collect()
+    }
+
+    /// Delete a task behind the back of the foreign keys, as a crash or an
+    /// older build without enforcement could
+    async fn delete_unchecked(pool: &SqlitePool, task_id: i64) {
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_healthy_database_passes() {
+        let ctx = TestContext::new().await;
+        let task = TaskManager::new(ctx.pool())
+            .add_task("Task", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(task.id, "note", "hello")
+            .await
+            .unwrap();
+
+        let results = run_checks(ctx.pool()).await.unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.name).collect::<Vec<_>>(),
+            vec![
+                SCHEMA,
+                FTS_INDEX,
+                ORPHANED_EVENTS,
+                DANGLING_DEPENDENCIES,
+                FOCUS
+            ]
+        );
+        assert!(failed(&results).is_empty(), "{:?}", results);
+        assert!(repair(ctx.pool(), &results).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detects_and_repairs_dangling_references() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let task_mgr = TaskManager::new(pool);
+        let kept = task_mgr
+            .add_task("Kept", None, None, None, None, None)
+            .await
+            .unwrap();
+        let lost = task_mgr
+            .add_task("Lost", None, None, None, None, None)
+            .await
+            .unwrap();
+        add_dependency(pool, kept.id, lost.id).await.unwrap();
+        EventManager::new(pool)
+            .add_event(lost.id, "note", "orphan")
+            .await
+            .unwrap();
+        WorkspaceManager::new(pool)
+            .set_current_task(lost.id, Some("doctor"))
+            .await
+            .unwrap();
+
+        delete_unchecked(pool, lost.id).await;
+
+        let results = run_checks(pool).await.unwrap();
+        assert_eq!(
+            failed(&results),
+            vec![ORPHANED_EVENTS, DANGLING_DEPENDENCIES, FOCUS]
+        );
+
+        let repairs = repair(pool, &results).await.unwrap();
+        assert_eq!(repairs.len(), 3);
+        assert!(failed(&run_checks(pool).await.unwrap()).is_empty());
+
+        // The surviving task is untouched
+        assert_eq!(task_mgr.get_task(kept.id).await.unwrap().name, "Kept");
+    }
+
+    #[tokio::test]
+    async fn test_detects_and_rebuilds_fts_desync() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let task = TaskManager::new(pool)
+            .add_task("Searchable", Some("needle"), None, None, None, None)
+            .await
+            .unwrap();
+
+        // Drop the index entry without touching the content row
+        sqlx::query(
+            "INSERT INTO tasks_fts(tasks_fts, rowid, name, spec) VALUES ('delete', ?, 'Searchable', 'needle')",
+        )
+        .bind(task.id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        let results = run_checks(pool).await.unwrap();
+        assert_eq!(failed(&results), vec![FTS_INDEX]);
+
+        repair(pool, &results).await.unwrap();
+        assert!(failed(&run_checks(pool).await.unwrap()).is_empty());
+        let hits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM tasks_fts WHERE tasks_fts MATCH 'needle'")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_detects_and_repairs_schema_drift() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        sqlx::query("DROP TRIGGER events_ai")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE workspace_state SET value = '0.1.0' WHERE key = 'schema_version'")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let results = run_checks(pool).await.unwrap();
+        assert_eq!(failed(&results), vec![SCHEMA]);
+        let schema = &results[0];
+        assert_eq!(schema.problems.len(), 2, "{:?}", schema.problems);
+
+        repair(pool, &results).await.unwrap();
+        assert!(failed(&run_checks(pool).await.unwrap()).is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+use super::checks::FTS_TABLES;
+use super::{CheckResult, DANGLING_DEPENDENCIES, FOCUS, FTS_INDEX, ORPHANED_EVENTS, SCHEMA};
+use crate::error::Result;
+use sqlx::SqlitePool;
+
+/// Fix the problems reported in `results`, returning what was done
+///
+/// Schema comes first so the FTS triggers exist again before references are
+/// cleaned up, and the indexes are rebuilt last from the final content.
+pub async fn repair(pool: &SqlitePool, results: &[CheckResult]) -> Result<Vec<String>> {
+    let failed = |name: &str| results.iter().any(|r| r.name == name && !r.is_ok());
+    let mut repairs = Vec::new();
+
+    if failed(SCHEMA) {
+        crate::db::run_migrations(pool).await?;
+        repairs.push("Re-ran schema migrations".to_string());
+    }
+
+    let mut tx = pool.begin().await?;
+    if failed(ORPHANED_EVENTS) {
+        let deleted = sqlx::query("DELETE FROM events WHERE task_id NOT IN (SELECT id FROM tasks)")
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        repairs.push(format!("Deleted {} orphaned event(s)", deleted));
+    }
+    if failed(DANGLING_DEPENDENCIES) {
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM dependencies
+            WHERE blocking_task_id NOT IN (SELECT id FROM tasks)
+               OR blocked_task_id NOT IN (SELECT id FROM tasks)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        repairs.push(format!("Deleted {} dangling dependency(ies)", deleted));
+    }
+    if failed(FOCUS) {
+        let cleared = sqlx::query(
+            r#"
+            UPDATE sessions SET current_task_id = NULL
+            WHERE current_task_id IS NOT NULL
+              AND current_task_id NOT IN (SELECT id FROM tasks)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        sqlx::query(
+            r#"
+            DELETE FROM workspace_state
+            WHERE key = 'current_task_id'
+              AND CAST(value AS INTEGER) NOT IN (SELECT id FROM tasks)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+        repairs.push(format!("Cleared focus of {} session(s)", cleared));
+    }
+    tx.commit().await?;
+
+    if failed(SCHEMA) || failed(FTS_INDEX) {
+        for (fts, _) in FTS_TABLES {
+            sqlx::query(&format!(
+                "INSERT INTO {fts}({fts}) VALUES ('rebuild')",
+                fts = fts
+            ))
+            .execute(pool)
+            .await?;
+        }
+        repairs.push("Rebuilt search indexes".to_string());
+    }
+
+    Ok(repairs)
+}
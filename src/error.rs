@@ -27,6 +27,38 @@ pub enum IntentError {
         blocking_task_ids: Vec<i64>,
     },
 
+    #[error("Task {task_id} is blocked by incomplete tasks in other projects: {}", blockers.join(", "))]
+    CrossProjectBlocked {
+        task_id: i64,
+        /// Blocking tasks as `<project>#<task id>`
+        blockers: Vec<String>,
+    },
+
+    #[error("Task {task_id} is claimed by session '{assignee}' until {expires_at}")]
+    TaskLeased {
+        task_id: i64,
+        assignee: String,
+        expires_at: String,
+    },
+
+    #[error("Policy '{rule}' forbids moving task {task_id} to {status}: {message}")]
+    PolicyViolation {
+        rule: String,
+        task_id: i64,
+        status: String,
+        message: String,
+    },
+
+    #[error("Starting task {task_id} would exceed the WIP limit of {}: {doing} tasks already in doing (limit {limit})", wip_scope(*root_id))]
+    WipLimitExceeded {
+        task_id: i64,
+        /// Task whose subtree is limited; None for the project-wide limit
+        root_id: Option<i64>,
+        limit: usize,
+        /// Tasks in doing within the limit, not counting `task_id`
+        doing: usize,
+    },
+
     #[error("Action not allowed: {0}")]
     ActionNotAllowed(String),
 
@@ -46,10 +78,173 @@ pub enum IntentError {
     OtherError(#[from] anyhow::Error),
 }
 
+fn wip_scope(root_id: Option<i64>) -> String {
+    match root_id {
+        Some(id) => format!("subtree #{}", id),
+        None => "the project".to_string(),
+    }
+}
+
 #[derive(Serialize)]
 pub struct ErrorResponse {
     pub error: String,
+    /// Symbolic name, e.g. `TASK_NOT_FOUND`
     pub code: String,
+    /// Stable catalog code, e.g. `IE0001` (see `ie errors list`)
+    pub error_code: String,
+}
+
+/// An entry of the error catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ErrorCodeInfo {
+    /// Stable code; never renumbered or reused
+    pub code: &'static str,
+    /// Symbolic name, as in the `code` field of error responses
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+const fn entry(code: &'static str, name: &'static str, description: &'static str) -> ErrorCodeInfo {
+    ErrorCodeInfo {
+        code,
+        name,
+        description,
+    }
+}
+
+/// Every error code the CLI and the Dashboard API can return
+///
+/// IE00xx are [`IntentError`] variants; IE01xx only come from the Dashboard
+/// API. Messages may change between releases, codes do not: match on these.
+pub const ERROR_CATALOG: &[ErrorCodeInfo] = &[
+    entry(
+        "IE0001",
+        "TASK_NOT_FOUND",
+        "No task with this ID, or it is outside the current scope",
+    ),
+    entry(
+        "IE0002",
+        "INVALID_INPUT",
+        "An argument or request body was rejected",
+    ),
+    entry(
+        "IE0003",
+        "CIRCULAR_DEPENDENCY",
+        "The dependency would create a cycle",
+    ),
+    entry(
+        "IE0004",
+        "TASK_BLOCKED",
+        "The task depends on tasks that are not done",
+    ),
+    entry(
+        "IE0005",
+        "TASK_LEASED",
+        "Another session holds the task's lease",
+    ),
+    entry(
+        "IE0006",
+        "POLICY_VIOLATION",
+        "A project policy forbids the status change",
+    ),
+    entry(
+        "IE0007",
+        "ACTION_NOT_ALLOWED",
+        "The action is not allowed in the current state",
+    ),
+    entry(
+        "IE0008",
+        "UNCOMPLETED_CHILDREN",
+        "The task still has children that are not done",
+    ),
+    entry(
+        "IE0009",
+        "NOT_A_PROJECT",
+        "No Intent-Engine project was found",
+    ),
+    entry(
+        "IE0010",
+        "HUMAN_TASK_PROTECTED",
+        "Only a human may complete this human-owned task",
+    ),
+    entry("IE0011", "DATABASE_ERROR", "The database query failed"),
+    entry(
+        "IE0012",
+        "INTERNAL_ERROR",
+        "Reading or writing a file failed",
+    ),
+    entry(
+        "IE0013",
+        "INTERNAL_ERROR",
+        "JSON could not be serialized or parsed",
+    ),
+    entry("IE0014", "INTERNAL_ERROR", "Any other unexpected failure"),
+    entry(
+        "IE0015",
+        "WIP_LIMIT_EXCEEDED",
+        "Starting the task would put more tasks in doing than a WIP limit allows",
+    ),
+    entry(
+        "IE0016",
+        "CROSS_PROJECT_BLOCKED",
+        "The task depends on unfinished (or unreadable) tasks of other projects",
+    ),
+    entry(
+        "IE0101",
+        "INVALID_REQUEST",
+        "The Dashboard API request was malformed",
+    ),
+    entry("IE0102", "EVENT_NOT_FOUND", "No event with this ID"),
+    entry(
+        "IE0103",
+        "INVALID_SETTINGS",
+        "The settings update was rejected",
+    ),
+    entry(
+        "IE0104",
+        "NO_CURRENT_TASK",
+        "The session has no focused task",
+    ),
+    entry(
+        "IE0105",
+        "PROJECT_NOT_FOUND",
+        "The Dashboard does not know this project",
+    ),
+    entry(
+        "IE0106",
+        "REMOVE_FAILED",
+        "The project could not be removed from the Dashboard",
+    ),
+    entry(
+        "IE0107",
+        "SWITCH_ERROR",
+        "The Dashboard could not switch to the project",
+    ),
+    entry(
+        "IE0108",
+        "UNAUTHORIZED",
+        "A Dashboard access token is required",
+    ),
+    entry(
+        "IE0109",
+        "NOT_FOUND",
+        "No Dashboard route or asset at this path",
+    ),
+    entry(
+        "IE0110",
+        "AUTH_STORE_UNREADABLE",
+        "The Dashboard token store cannot be read, so every request is refused",
+    ),
+];
+
+/// Catalog code of a symbolic name, for errors that only carry the name
+///
+/// Names shared by several entries (`INTERNAL_ERROR`) resolve to the first.
+pub fn catalog_code(name: &str) -> Option<&'static str> {
+    ERROR_CATALOG
+        .iter()
+        .find(|info| info.name == name)
+        .map(|info| info.code)
 }
 
 impl IntentError {
@@ -60,6 +255,10 @@ impl IntentError {
             IntentError::InvalidInput(_) => "INVALID_INPUT",
             IntentError::CircularDependency { .. } => "CIRCULAR_DEPENDENCY",
             IntentError::TaskBlocked { .. } => "TASK_BLOCKED",
+            IntentError::TaskLeased { .. } => "TASK_LEASED",
+            IntentError::PolicyViolation { .. } => "POLICY_VIOLATION",
+            IntentError::WipLimitExceeded { .. } => "WIP_LIMIT_EXCEEDED",
+            IntentError::CrossProjectBlocked { .. } => "CROSS_PROJECT_BLOCKED",
             IntentError::ActionNotAllowed(_) => "ACTION_NOT_ALLOWED",
             IntentError::UncompletedChildren => "UNCOMPLETED_CHILDREN",
             IntentError::NotAProject => "NOT_A_PROJECT",
@@ -68,10 +267,33 @@ impl IntentError {
         }
     }
 
+    /// Stable catalog code of this error (`IE0001`-style)
+    pub fn catalog_code(&self) -> &'static str {
+        match self {
+            IntentError::TaskNotFound(_) => "IE0001",
+            IntentError::InvalidInput(_) => "IE0002",
+            IntentError::CircularDependency { .. } => "IE0003",
+            IntentError::TaskBlocked { .. } => "IE0004",
+            IntentError::TaskLeased { .. } => "IE0005",
+            IntentError::PolicyViolation { .. } => "IE0006",
+            IntentError::ActionNotAllowed(_) => "IE0007",
+            IntentError::UncompletedChildren => "IE0008",
+            IntentError::NotAProject => "IE0009",
+            IntentError::HumanTaskCannotBeCompletedByAI { .. } => "IE0010",
+            IntentError::DatabaseError(_) => "IE0011",
+            IntentError::IoError(_) => "IE0012",
+            IntentError::JsonError(_) => "IE0013",
+            IntentError::OtherError(_) => "IE0014",
+            IntentError::WipLimitExceeded { .. } => "IE0015",
+            IntentError::CrossProjectBlocked { .. } => "IE0016",
+        }
+    }
+
     pub fn to_error_response(&self) -> ErrorResponse {
         ErrorResponse {
             error: self.to_string(),
             code: self.to_error_code().to_string(),
+            error_code: self.catalog_code().to_string(),
         }
     }
 }
@@ -115,6 +337,51 @@ mod tests {
         assert_eq!(error.to_error_code(), "ACTION_NOT_ALLOWED");
     }
 
+    #[test]
+    fn test_policy_violation_error() {
+        let error = IntentError::PolicyViolation {
+            rule: "min_spec_chars".to_string(),
+            task_id: 7,
+            status: "doing".to_string(),
+            message: "spec too short".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Policy 'min_spec_chars' forbids moving task 7 to doing: spec too short"
+        );
+        assert_eq!(error.to_error_code(), "POLICY_VIOLATION");
+    }
+
+    #[test]
+    fn test_wip_limit_exceeded_error() {
+        let error = IntentError::WipLimitExceeded {
+            task_id: 9,
+            root_id: Some(3),
+            limit: 2,
+            doing: 2,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Starting task 9 would exceed the WIP limit of subtree #3: 2 tasks already in doing (limit 2)"
+        );
+        assert_eq!(error.to_error_code(), "WIP_LIMIT_EXCEEDED");
+        assert_eq!(error.catalog_code(), "IE0015");
+    }
+
+    #[test]
+    fn test_cross_project_blocked_error() {
+        let error = IntentError::CrossProjectBlocked {
+            task_id: 4,
+            blockers: vec!["api#12".to_string(), "web#3".to_string()],
+        };
+        assert_eq!(
+            error.to_string(),
+            "Task 4 is blocked by incomplete tasks in other projects: api#12, web#3"
+        );
+        assert_eq!(error.to_error_code(), "CROSS_PROJECT_BLOCKED");
+        assert_eq!(error.catalog_code(), "IE0016");
+    }
+
     #[test]
     fn test_uncompleted_children_error() {
         let error = IntentError::UncompletedChildren;
@@ -153,9 +420,48 @@ mod tests {
         let response = error.to_error_response();
 
         assert_eq!(response.code, "TASK_NOT_FOUND");
+        assert_eq!(response.error_code, "IE0001");
         assert_eq!(response.error, "Task not found: 456");
     }
 
+    #[test]
+    fn test_catalog_matches_variants() {
+        let errors = [
+            IntentError::TaskNotFound(1),
+            IntentError::InvalidInput(String::new()),
+            IntentError::CircularDependency {
+                blocking_task_id: 1,
+                blocked_task_id: 2,
+            },
+            IntentError::UncompletedChildren,
+            IntentError::NotAProject,
+            IntentError::WipLimitExceeded {
+                task_id: 1,
+                root_id: None,
+                limit: 1,
+                doing: 1,
+            },
+            IntentError::IoError(std::io::Error::other("x")),
+            IntentError::OtherError(anyhow::anyhow!("x")),
+        ];
+        for error in errors {
+            let info = ERROR_CATALOG
+                .iter()
+                .find(|info| info.code == error.catalog_code())
+                .unwrap();
+            assert_eq!(info.name, error.to_error_code());
+        }
+
+        // Codes are unique and the names of Dashboard-only codes too
+        let mut codes: Vec<_> = ERROR_CATALOG.iter().map(|info| info.code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ERROR_CATALOG.len());
+        assert_eq!(catalog_code("TASK_NOT_FOUND"), Some("IE0001"));
+        assert_eq!(catalog_code("INVALID_REQUEST"), Some("IE0101"));
+        assert_eq!(catalog_code("NOPE"), None);
+    }
+
     #[test]
     fn test_error_response_serialization() {
         let error = IntentError::InvalidInput("Test".to_string());
@@ -163,6 +469,7 @@ mod tests {
         let json = serde_json::to_string(&response).unwrap();
 
         assert!(json.contains("\"code\":\"INVALID_INPUT\""));
+        assert!(json.contains("\"error_code\":\"IE0002\""));
         assert!(json.contains("\"error\":\"Invalid input: Test\""));
     }
 
@@ -0,0 +1,104 @@
+//! Event types a project may log
+//!
+//! Four types are built in. A project adds its own in the `[events]` table of
+//! its config.toml:
+//!
+//! ```toml
+//! [events]
+//! types = ["experiment_result", "deploy"]
+//! ```
+//!
+//! `ie log`, `EventManager` and the `--event-type` filters of search all
+//! check names against the same registry, so a typo is an error instead of a
+//! new type nobody filters on. Names are lowercase letters, digits and `_`.
+
+use crate::config::{EventsConfig, ProjectConfig};
+use crate::error::{IntentError, Result};
+use sqlx::SqlitePool;
+
+/// Types every project has
+pub const BUILTIN_EVENT_TYPES: &[&str] = &["decision", "blocker", "milestone", "note"];
+
+/// Whether `name` can be used as a custom event type
+pub fn is_valid_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Built-in plus project-defined event types
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EventTypes {
+    custom: Vec<String>,
+}
+
+impl EventTypes {
+    /// Only the built-in types
+    pub fn builtin() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(config: &EventsConfig) -> Self {
+        Self {
+            custom: config.types.clone(),
+        }
+    }
+
+    /// Types of the project whose database is `pool`
+    pub fn for_pool(pool: &SqlitePool) -> Result<Self> {
+        Ok(Self::from_config(&ProjectConfig::for_pool(pool)?.events))
+    }
+
+    /// All names, built-in ones first
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        BUILTIN_EVENT_TYPES
+            .iter()
+            .copied()
+            .chain(self.custom.iter().map(String::as_str))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names().any(|n| n == name)
+    }
+
+    /// Fail with the list of known types unless `name` is one of them
+    pub fn check(&self, name: &str) -> Result<()> {
+        if self.contains(name) {
+            return Ok(());
+        }
+        Err(IntentError::InvalidInput(format!(
+            "Unknown event type '{}'. Expected one of: {} (add more under [events] types in .intent-engine/config.toml)",
+            name,
+            self.names().collect::<Vec<_>>().join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_accepts_builtin_and_custom_types() {
+        let types = EventTypes::from_config(&EventsConfig {
+            types: vec!["deploy".to_string()],
+        });
+        assert!(types.check("decision").is_ok());
+        assert!(types.check("deploy").is_ok());
+
+        let err = types.check("deploys").unwrap_err().to_string();
+        assert!(err.contains("decision, blocker, milestone, note, deploy"));
+        assert!(EventTypes::builtin().check("deploy").is_err());
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("experiment_result"));
+        assert!(is_valid_name("v2_rollout"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("2fast"));
+        assert!(!is_valid_name("Deploy"));
+        assert!(!is_valid_name("deploy-prod"));
+    }
+}
@@ -1,14 +1,32 @@
-use crate::db::models::Event;
+use crate::compaction::{
+    CompactionResult, RuleSummarizer, Summarizer, DEFAULT_KEEP_RECENT, DIGEST_LOG_TYPE,
+    MIN_EVENTS_TO_COMPACT,
+};
+use crate::db::models::{
+    ActivityEntry, ActivityFilter, Actor, Event, EventVisibility, NewEvent, PaginatedEvents,
+    TaskVisibility,
+};
 use crate::error::{IntentError, Result};
+use crate::event_types::EventTypes;
 use chrono::Utc;
-use sqlx::{Row, SqlitePool};
+use sqlx::types::Json;
+use sqlx::{FromRow, Row, SqlitePool};
 use std::sync::Arc;
 
+/// Upper bound for a single page of [`EventManager::list_events_page`]
+pub const MAX_EVENT_PAGE_SIZE: i64 = 500;
+
 pub struct EventManager<'a> {
     pool: &'a SqlitePool,
     notifier: crate::notifications::NotificationSender,
     cli_notifier: Option<crate::dashboard::cli_notifier::CliNotifier>,
     project_path: Option<String>,
+    /// Highest visibility level returned by list queries
+    visibility: EventVisibility,
+    /// Whether events of private tasks are visible
+    scope: TaskVisibility,
+    /// Recorded on every event this manager logs
+    actor: Actor,
 }
 
 impl<'a> EventManager<'a> {
@@ -18,6 +36,9 @@ impl<'a> EventManager<'a> {
             notifier: crate::notifications::NotificationSender::new(None),
             cli_notifier: Some(crate::dashboard::cli_notifier::CliNotifier::new()),
             project_path: None,
+            visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            actor: Actor::from_env(),
         }
     }
 
@@ -25,9 +46,13 @@ impl<'a> EventManager<'a> {
     pub fn with_project_path(pool: &'a SqlitePool, project_path: String) -> Self {
         Self {
             pool,
-            notifier: crate::notifications::NotificationSender::new(None),
+            notifier: crate::notifications::NotificationSender::new(None)
+                .with_offline_queue(pool.clone()),
             cli_notifier: Some(crate::dashboard::cli_notifier::CliNotifier::new()),
             project_path: Some(project_path),
+            visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            actor: Actor::from_env(),
         }
     }
 
@@ -39,17 +64,56 @@ impl<'a> EventManager<'a> {
     ) -> Self {
         Self {
             pool,
-            notifier: crate::notifications::NotificationSender::new(Some(ws_state)),
+            notifier: crate::notifications::NotificationSender::new(Some(ws_state))
+                .with_offline_queue(pool.clone()),
             cli_notifier: None, // Dashboard context doesn't need CLI notifier
             project_path: Some(project_path),
+            visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            actor: Actor::human().with_client("dashboard"),
         }
     }
 
+    /// Also return `verbose` (and, at `Private`, `private`) events from list queries
+    pub fn with_visibility(mut self, visibility: EventVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Use an explicit task scope instead of the process-wide `--scope`
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Attribute logged events to `actor` instead of the one from the environment
+    pub fn with_actor(mut self, actor: Actor) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// Fail with `TaskNotFound` unless the task exists within the scope
+    async fn check_task_exists(&self, task_id: i64) -> Result<()> {
+        let task_exists: bool = sqlx::query_scalar::<_, bool>(&format!(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND {})",
+            self.scope.sql_filter()
+        ))
+        .bind(task_id)
+        .fetch_one(self.pool)
+        .await?;
+
+        if !task_exists {
+            return Err(IntentError::TaskNotFound(task_id));
+        }
+        Ok(())
+    }
+
     /// Internal helper: Notify UI about event creation
     async fn notify_event_created(&self, event: &Event) {
         use crate::dashboard::websocket::DatabaseOperationPayload;
 
         // WebSocket notification (Dashboard context)
+        let mut payload = None;
         if let Some(project_path) = &self.project_path {
             let event_json = match serde_json::to_value(event) {
                 Ok(json) => json,
@@ -59,16 +123,20 @@ impl<'a> EventManager<'a> {
                 },
             };
 
-            let payload =
+            let created =
                 DatabaseOperationPayload::event_created(event.id, event_json, project_path.clone());
-            self.notifier.send(payload).await;
+            self.notifier.send(created.clone()).await;
+            payload = Some(created);
         }
 
-        // CLI → Dashboard HTTP notification (CLI context)
+        // CLI → Dashboard HTTP notification (CLI context), queued if missed
         if let Some(cli_notifier) = &self.cli_notifier {
-            cli_notifier
+            let delivered = cli_notifier
                 .notify_event_added(event.task_id, event.id, self.project_path.clone())
                 .await;
+            if let (false, Some(payload)) = (delivered, payload) {
+                self.notifier.queue(payload).await;
+            }
         }
     }
 
@@ -94,14 +162,15 @@ impl<'a> EventManager<'a> {
     }
 
     /// Internal helper: Notify UI about event deletion
-    async fn notify_event_deleted(&self, event_id: i64) {
+    async fn notify_event_deleted(&self, event_id: i64, task_id: i64) {
         use crate::dashboard::websocket::DatabaseOperationPayload;
 
         let Some(project_path) = &self.project_path else {
             return;
         };
 
-        let payload = DatabaseOperationPayload::event_deleted(event_id, project_path.clone());
+        let payload =
+            DatabaseOperationPayload::event_deleted(event_id, task_id, project_path.clone());
         self.notifier.send(payload).await;
     }
 
@@ -112,29 +181,37 @@ impl<'a> EventManager<'a> {
         log_type: &str,
         discussion_data: &str,
     ) -> Result<Event> {
-        // Check if task exists
-        let task_exists: bool =
-            sqlx::query_scalar::<_, bool>(crate::sql_constants::CHECK_TASK_EXISTS)
-                .bind(task_id)
-                .fetch_one(self.pool)
-                .await?;
+        self.add_event_with_visibility(task_id, log_type, discussion_data, EventVisibility::Normal)
+            .await
+    }
 
-        if !task_exists {
-            return Err(IntentError::TaskNotFound(task_id));
-        }
+    /// Add a new event at the given visibility level
+    #[tracing::instrument(skip(self, discussion_data))]
+    pub async fn add_event_with_visibility(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+        visibility: EventVisibility,
+    ) -> Result<Event> {
+        // Check if task exists
+        self.check_task_exists(task_id).await?;
+        EventTypes::for_pool(self.pool)?.check(log_type)?;
 
         let now = Utc::now();
 
         let result = sqlx::query(
             r#"
-            INSERT INTO events (task_id, log_type, discussion_data, timestamp)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO events (task_id, log_type, discussion_data, timestamp, visibility, actor)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(task_id)
         .bind(log_type)
         .bind(discussion_data)
         .bind(now)
+        .bind(visibility)
+        .bind(Json(&self.actor))
         .execute(self.pool)
         .await?;
 
@@ -146,6 +223,8 @@ impl<'a> EventManager<'a> {
             timestamp: now,
             log_type: log_type.to_string(),
             discussion_data: discussion_data.to_string(),
+            visibility,
+            actor: Some(self.actor.clone()),
         };
 
         // Notify WebSocket clients about the new event
@@ -154,6 +233,63 @@ impl<'a> EventManager<'a> {
         Ok(event)
     }
 
+    /// Add several events in one transaction, in the given order
+    ///
+    /// Every task is checked first, so either all events are recorded or none.
+    /// Events may be backdated, but not dated in the future.
+    #[tracing::instrument(skip(self, events), fields(rows = events.len()))]
+    pub async fn add_events(&self, events: &[NewEvent]) -> Result<Vec<Event>> {
+        let now = Utc::now();
+        let types = EventTypes::for_pool(self.pool)?;
+        for new in events {
+            self.check_task_exists(new.task_id).await?;
+            types.check(&new.log_type)?;
+            if new.timestamp.is_some_and(|at| at > now) {
+                return Err(IntentError::InvalidInput(format!(
+                    "Event timestamp {} is in the future",
+                    new.timestamp.unwrap_or(now).to_rfc3339()
+                )));
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut recorded = Vec::with_capacity(events.len());
+        for new in events {
+            let timestamp = new.timestamp.unwrap_or(now);
+            let result = sqlx::query(
+                r#"
+                INSERT INTO events (task_id, log_type, discussion_data, timestamp, visibility, actor)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(new.task_id)
+            .bind(&new.log_type)
+            .bind(&new.discussion_data)
+            .bind(timestamp)
+            .bind(new.visibility)
+            .bind(Json(&self.actor))
+            .execute(&mut *tx)
+            .await?;
+
+            recorded.push(Event {
+                id: result.last_insert_rowid(),
+                task_id: new.task_id,
+                timestamp,
+                log_type: new.log_type.clone(),
+                discussion_data: new.discussion_data.clone(),
+                visibility: new.visibility,
+                actor: Some(self.actor.clone()),
+            });
+        }
+        tx.commit().await?;
+
+        for event in &recorded {
+            self.notify_event_created(event).await;
+        }
+
+        Ok(recorded)
+    }
+
     /// Update an existing event
     pub async fn update_event(
         &self,
@@ -173,6 +309,10 @@ impl<'a> EventManager<'a> {
             event_id
         )))?;
 
+        if let Some(log_type) = log_type {
+            EventTypes::for_pool(self.pool)?.check(log_type)?;
+        }
+
         // Update only the fields that are provided
         let new_log_type = log_type.unwrap_or(&existing_event.log_type);
         let new_discussion_data = discussion_data.unwrap_or(&existing_event.discussion_data);
@@ -196,6 +336,8 @@ impl<'a> EventManager<'a> {
             timestamp: existing_event.timestamp,
             log_type: new_log_type.to_string(),
             discussion_data: new_discussion_data.to_string(),
+            visibility: existing_event.visibility,
+            actor: existing_event.actor,
         };
 
         // Notify WebSocket clients about the update
@@ -212,30 +354,147 @@ impl<'a> EventManager<'a> {
             .fetch_optional(self.pool)
             .await?;
 
-        let _event = event.ok_or(IntentError::InvalidInput(format!(
+        let event = event.ok_or(IntentError::InvalidInput(format!(
             "Event {} not found",
             event_id
         )))?;
 
-        // Delete from FTS index first (if it exists)
-        let _ = sqlx::query("DELETE FROM events_fts WHERE rowid = ?")
-            .bind(event_id)
-            .execute(self.pool)
-            .await;
-
-        // Delete the event
+        // Delete the event (the events_ad trigger updates the FTS index)
         sqlx::query("DELETE FROM events WHERE id = ?")
             .bind(event_id)
             .execute(self.pool)
             .await?;
 
         // Notify WebSocket clients about the deletion
-        self.notify_event_deleted(event_id).await;
+        self.notify_event_deleted(event_id, event.task_id).await;
 
         Ok(())
     }
 
+    /// Roll all but the latest `DEFAULT_KEEP_RECENT` events of a task into a
+    /// rule-based digest event (see `crate::compaction`)
+    pub async fn compact(&self, task_id: i64) -> Result<CompactionResult> {
+        self.compact_with(task_id, DEFAULT_KEEP_RECENT, &RuleSummarizer)
+            .await
+    }
+
+    /// Compact keeping `keep_recent` events, with any summarizer
+    pub async fn compact_with(
+        &self,
+        task_id: i64,
+        keep_recent: usize,
+        summarizer: &impl Summarizer,
+    ) -> Result<CompactionResult> {
+        let task_name: String = sqlx::query_scalar("SELECT name FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(self.pool)
+            .await?
+            .ok_or(IntentError::TaskNotFound(task_id))?;
+
+        let events: Vec<Event> = sqlx::query_as(&format!(
+            r#"
+            SELECT {} FROM events
+            WHERE task_id = ? AND log_type != ? AND visibility != 'private'
+            ORDER BY timestamp ASC, id ASC
+            "#,
+            crate::sql_constants::EVENT_COLUMNS
+        ))
+        .bind(task_id)
+        .bind(DIGEST_LOG_TYPE)
+        .fetch_all(self.pool)
+        .await?;
+
+        let old_count = events.len().saturating_sub(keep_recent);
+        if old_count < MIN_EVENTS_TO_COMPACT {
+            return Ok(CompactionResult {
+                task_id,
+                archived: 0,
+                digest_event_id: None,
+            });
+        }
+        let old = &events[..old_count];
+        let summary = summarizer.summarize(&task_name, old).await?;
+
+        // The digest takes the place of the events it replaces in the timeline
+        let timestamp = old[old_count - 1].timestamp;
+        let mut tx = self.pool.begin().await?;
+        let digest_id = sqlx::query(
+            r#"
+            INSERT INTO events (task_id, log_type, discussion_data, timestamp, visibility, actor)
+            VALUES (?, ?, ?, ?, 'normal', ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(DIGEST_LOG_TYPE)
+        .bind(&summary)
+        .bind(timestamp)
+        .bind(Json(&self.actor))
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        for chunk in old.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let archive = format!(
+                r#"
+                INSERT INTO events_archive
+                    (id, task_id, timestamp, log_type, discussion_data, visibility, actor, digest_event_id)
+                SELECT id, task_id, timestamp, log_type, discussion_data, visibility, actor, ?
+                FROM events WHERE id IN ({})
+                "#,
+                placeholders
+            );
+            let mut query = sqlx::query(&archive).bind(digest_id);
+            for event in chunk {
+                query = query.bind(event.id);
+            }
+            query.execute(&mut *tx).await?;
+
+            // The events_ad trigger keeps the FTS index in sync
+            let delete = format!("DELETE FROM events WHERE id IN ({})", placeholders);
+            let mut query = sqlx::query(&delete);
+            for event in chunk {
+                query = query.bind(event.id);
+            }
+            query.execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        let digest = Event {
+            id: digest_id,
+            task_id,
+            timestamp,
+            log_type: DIGEST_LOG_TYPE.to_string(),
+            discussion_data: summary,
+            visibility: EventVisibility::Normal,
+            actor: Some(self.actor.clone()),
+        };
+        self.notify_event_created(&digest).await;
+
+        Ok(CompactionResult {
+            task_id,
+            archived: old_count,
+            digest_event_id: Some(digest_id),
+        })
+    }
+
+    /// Raw events that a digest event replaced, oldest first
+    pub async fn archived_events(&self, digest_event_id: i64) -> Result<Vec<Event>> {
+        Ok(sqlx::query_as(&format!(
+            r#"
+            SELECT {} FROM events_archive
+            WHERE digest_event_id = ?
+            ORDER BY timestamp ASC, id ASC
+            "#,
+            crate::sql_constants::EVENT_COLUMNS
+        ))
+        .bind(digest_event_id)
+        .fetch_all(self.pool)
+        .await?)
+    }
+
     /// List events for a task (or globally if task_id is None)
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn list_events(
         &self,
         task_id: Option<i64>,
@@ -245,15 +504,7 @@ impl<'a> EventManager<'a> {
     ) -> Result<Vec<Event>> {
         // Check if task exists (only if task_id provided)
         if let Some(tid) = task_id {
-            let task_exists: bool =
-                sqlx::query_scalar::<_, bool>(crate::sql_constants::CHECK_TASK_EXISTS)
-                    .bind(tid)
-                    .fetch_one(self.pool)
-                    .await?;
-
-            if !task_exists {
-                return Err(IntentError::TaskNotFound(tid));
-            }
+            self.check_task_exists(tid).await?;
         }
 
         let limit = limit.unwrap_or(50);
@@ -267,7 +518,7 @@ impl<'a> EventManager<'a> {
 
         // Build dynamic query based on filters
         let mut query = String::from(crate::sql_constants::SELECT_EVENT_BASE);
-        let mut conditions = Vec::new();
+        let mut conditions = vec![self.visibility.sql_filter(), self.scope.sql_task_filter()];
 
         if task_id.is_some() {
             conditions.push("task_id = ?");
@@ -306,11 +557,188 @@ impl<'a> EventManager<'a> {
         sql_query = sql_query.bind(limit);
 
         let events = sql_query.fetch_all(self.pool).await?;
+        tracing::Span::current().record("rows", events.len());
 
         Ok(events)
     }
 
+    /// List one page of events, newest first
+    ///
+    /// `before` is an event ID cursor: only events older than it are returned
+    /// (ties on timestamp are broken by ID, so pages never overlap or skip).
+    /// Use the returned `next_before` to fetch the following page.
+    #[tracing::instrument(skip(self), fields(rows))]
+    pub async fn list_events_page(
+        &self,
+        task_id: Option<i64>,
+        log_type: Option<String>,
+        since: Option<String>,
+        before: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<PaginatedEvents> {
+        if let Some(tid) = task_id {
+            self.check_task_exists(tid).await?;
+        }
+
+        let limit = limit.unwrap_or(50).clamp(1, MAX_EVENT_PAGE_SIZE);
+
+        let since_timestamp = match since {
+            Some(duration_str) => Some(crate::time_utils::parse_duration(&duration_str)?),
+            None => None,
+        };
+
+        let cursor = match before {
+            Some(event_id) => {
+                let timestamp: Option<chrono::DateTime<Utc>> =
+                    sqlx::query_scalar("SELECT timestamp FROM events WHERE id = ?")
+                        .bind(event_id)
+                        .fetch_optional(self.pool)
+                        .await?;
+                let timestamp = timestamp.ok_or_else(|| {
+                    IntentError::InvalidInput(format!("Event {} not found", event_id))
+                })?;
+                Some((timestamp, event_id))
+            },
+            None => None,
+        };
+
+        let mut conditions = vec![self.visibility.sql_filter(), self.scope.sql_task_filter()];
+        if task_id.is_some() {
+            conditions.push("task_id = ?");
+        }
+        if log_type.is_some() {
+            conditions.push("log_type = ?");
+        }
+        if since_timestamp.is_some() {
+            conditions.push("timestamp >= ?");
+        }
+        let filter = conditions
+            .iter()
+            .map(|c| format!(" AND {}", c))
+            .collect::<String>();
+
+        // Total ignores the cursor so the UI can show "n of total"
+        let count_sql = format!("SELECT COUNT(*) FROM events WHERE 1=1{}", filter);
+        let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+        if let Some(tid) = task_id {
+            count_query = count_query.bind(tid);
+        }
+        if let Some(ref typ) = log_type {
+            count_query = count_query.bind(typ);
+        }
+        if let Some(ts) = since_timestamp {
+            count_query = count_query.bind(ts);
+        }
+        let total_count = count_query.fetch_one(self.pool).await?;
+
+        let mut page_sql = format!("{}{}", crate::sql_constants::SELECT_EVENT_BASE, filter);
+        if cursor.is_some() {
+            page_sql.push_str(" AND (timestamp < ? OR (timestamp = ? AND id < ?))");
+        }
+        page_sql.push_str(" ORDER BY timestamp DESC, id DESC LIMIT ?");
+
+        let mut page_query = sqlx::query_as::<_, Event>(&page_sql);
+        if let Some(tid) = task_id {
+            page_query = page_query.bind(tid);
+        }
+        if let Some(ref typ) = log_type {
+            page_query = page_query.bind(typ);
+        }
+        if let Some(ts) = since_timestamp {
+            page_query = page_query.bind(ts);
+        }
+        if let Some((ts, id)) = cursor {
+            page_query = page_query.bind(ts).bind(ts).bind(id);
+        }
+        // Fetch one extra row to know whether another page exists
+        let mut events = page_query.bind(limit + 1).fetch_all(self.pool).await?;
+        tracing::Span::current().record("rows", events.len());
+
+        let has_more = events.len() as i64 > limit;
+        events.truncate(limit as usize);
+        let next_before = if has_more {
+            events.last().map(|e| e.id)
+        } else {
+            None
+        };
+
+        Ok(PaginatedEvents {
+            events,
+            total_count,
+            has_more,
+            limit,
+            next_before,
+        })
+    }
+
+    /// Events and task status transitions of every task, newest first
+    ///
+    /// Transitions come from the tasks' `first_todo_at`, `first_doing_at`
+    /// and `first_done_at`, so each task contributes at most one created,
+    /// started and completed entry. Events follow this manager's visibility.
+    pub async fn recent_activity(
+        &self,
+        limit: i64,
+        filter: &ActivityFilter,
+    ) -> Result<Vec<ActivityEntry>> {
+        let since = filter
+            .since
+            .as_deref()
+            .map(crate::time_utils::parse_duration)
+            .transpose()?;
+        if let Some(event_type) = &filter.event_type {
+            EventTypes::for_pool(self.pool)?.check(event_type)?;
+        }
+
+        let transition = |kind: &str, column: &str| {
+            format!(
+                "SELECT t.{column} AS timestamp, '{kind}' AS kind, t.id AS task_id, \
+                 t.name AS task_name, t.status AS task_status, NULL AS event_id, \
+                 NULL AS log_type, NULL AS message \
+                 FROM (SELECT * FROM tasks WHERE {scope}) t WHERE t.{column} IS NOT NULL",
+                scope = self.scope.sql_filter()
+            )
+        };
+        let mut query = format!(
+            "SELECT * FROM (\
+             SELECT e.timestamp AS timestamp, 'event' AS kind, t.id AS task_id, \
+             t.name AS task_name, t.status AS task_status, e.id AS event_id, \
+             e.log_type AS log_type, e.discussion_data AS message \
+             FROM (SELECT * FROM events WHERE {} AND {}) e JOIN tasks t ON t.id = e.task_id \
+             UNION ALL {} UNION ALL {} UNION ALL {}) WHERE 1=1",
+            self.visibility.sql_filter(),
+            self.scope.sql_task_filter(),
+            transition("created", "first_todo_at"),
+            transition("started", "first_doing_at"),
+            transition("completed", "first_done_at"),
+        );
+        // Timestamps are stored in more than one text format; compare them as dates
+        if since.is_some() {
+            query.push_str(" AND julianday(timestamp) >= julianday(?)");
+        }
+        if filter.task_id.is_some() {
+            query.push_str(" AND task_id = ?");
+        }
+        if filter.event_type.is_some() {
+            query.push_str(" AND kind = 'event' AND log_type = ?");
+        }
+        query.push_str(" ORDER BY julianday(timestamp) DESC, event_id DESC LIMIT ?");
+
+        let mut q = sqlx::query_as::<_, ActivityEntry>(&query);
+        if let Some(since) = since {
+            q = q.bind(since);
+        }
+        if let Some(task_id) = filter.task_id {
+            q = q.bind(task_id);
+        }
+        if let Some(event_type) = &filter.event_type {
+            q = q.bind(event_type);
+        }
+        Ok(q.bind(limit).fetch_all(self.pool).await?)
+    }
+
     /// Search events using FTS5
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn search_events_fts5(
         &self,
         query: &str,
@@ -319,7 +747,7 @@ impl<'a> EventManager<'a> {
         let limit = limit.unwrap_or(20);
 
         // Use FTS5 to search events and get snippets
-        let results = sqlx::query(
+        let sql = format!(
             r#"
             SELECT
                 e.id,
@@ -327,28 +755,27 @@ impl<'a> EventManager<'a> {
                 e.timestamp,
                 e.log_type,
                 e.discussion_data,
+                e.visibility,
+                e.actor,
                 snippet(events_fts, 0, '**', '**', '...', 15) as match_snippet
             FROM events_fts
             INNER JOIN events e ON events_fts.rowid = e.id
-            WHERE events_fts MATCH ?
+            WHERE events_fts MATCH ? AND ({})
             ORDER BY rank
             LIMIT ?
             "#,
-        )
-        .bind(query)
-        .bind(limit)
-        .fetch_all(self.pool)
-        .await?;
+            self.visibility.sql_filter()
+        );
+        let results = sqlx::query(&sql)
+            .bind(query)
+            .bind(limit)
+            .fetch_all(self.pool)
+            .await?;
+        tracing::Span::current().record("rows", results.len());
 
         let mut search_results = Vec::new();
         for row in results {
-            let event = Event {
-                id: row.get("id"),
-                task_id: row.get("task_id"),
-                timestamp: row.get("timestamp"),
-                log_type: row.get("log_type"),
-                discussion_data: row.get("discussion_data"),
-            };
+            let event = Event::from_row(&row)?;
             let match_snippet: String = row.get("match_snippet");
 
             search_results.push(EventSearchResult {
@@ -378,6 +805,23 @@ impl crate::backend::EventBackend for EventManager<'_> {
         self.add_event(task_id, log_type, discussion_data)
     }
 
+    fn add_event_with_visibility(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+        visibility: EventVisibility,
+    ) -> impl std::future::Future<Output = Result<Event>> + Send {
+        self.add_event_with_visibility(task_id, log_type, discussion_data, visibility)
+    }
+
+    fn add_events(
+        &self,
+        events: &[NewEvent],
+    ) -> impl std::future::Future<Output = Result<Vec<Event>>> + Send {
+        self.add_events(events)
+    }
+
     fn list_events(
         &self,
         task_id: Option<i64>,
@@ -387,6 +831,10 @@ impl crate::backend::EventBackend for EventManager<'_> {
     ) -> impl std::future::Future<Output = Result<Vec<Event>>> + Send {
         self.list_events(task_id, limit, log_type, since)
     }
+
+    fn event_types(&self) -> Result<EventTypes> {
+        EventTypes::for_pool(self.pool)
+    }
 }
 
 #[cfg(test)]
@@ -415,6 +863,30 @@ mod tests {
         assert_eq!(event.discussion_data, "Test decision");
     }
 
+    #[tokio::test]
+    async fn test_events_record_their_actor() {
+        let ctx = TestContext::new().await;
+        let task = TaskManager::new(ctx.pool())
+            .add_task("Attributed", None, None, None, None, None)
+            .await
+            .unwrap();
+        let agent = Actor::ai().with_session("s-1").with_client("claude");
+
+        let logged = EventManager::new(ctx.pool())
+            .with_actor(agent.clone())
+            .add_event(task.id, "decision", "Use JWT")
+            .await
+            .unwrap();
+        assert_eq!(logged.actor.as_ref(), Some(&agent));
+
+        let stored = EventManager::new(ctx.pool())
+            .list_events(Some(task.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(stored[0].actor.as_ref(), Some(&agent));
+        assert_eq!(agent.to_string(), "ai session s-1 via claude");
+    }
+
     #[tokio::test]
     async fn test_add_event_nonexistent_task() {
         let ctx = TestContext::new().await;
@@ -424,6 +896,93 @@ mod tests {
         assert!(matches!(result, Err(IntentError::TaskNotFound(999))));
     }
 
+    #[tokio::test]
+    async fn test_add_events_batch() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Test task", None, None, None, None, None)
+            .await
+            .unwrap();
+        let new = |log_type: &str, timestamp| NewEvent {
+            task_id: task.id,
+            log_type: log_type.to_string(),
+            discussion_data: format!("{} entry", log_type),
+            visibility: EventVisibility::Normal,
+            timestamp,
+        };
+
+        let earlier = Utc::now() - chrono::Duration::hours(2);
+        let events = event_mgr
+            .add_events(&[new("decision", Some(earlier)), new("note", None)])
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].log_type, "decision");
+        assert_eq!(events[0].timestamp, earlier);
+        assert!(events[1].timestamp > earlier);
+
+        // One bad entry rejects the whole batch
+        let mut missing = new("blocker", None);
+        missing.task_id = 999;
+        let result = event_mgr.add_events(&[new("note", None), missing]).await;
+        assert!(matches!(result, Err(IntentError::TaskNotFound(999))));
+
+        let future = Utc::now() + chrono::Duration::hours(1);
+        let result = event_mgr.add_events(&[new("note", Some(future))]).await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+
+        let stored = event_mgr
+            .list_events(Some(task.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_event_types_from_config() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Test task", None, None, None, None, None)
+            .await
+            .unwrap();
+        let result = event_mgr
+            .add_event(task.id, "deploy", "v1 to staging")
+            .await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine/config.toml"),
+            "[events]\ntypes = [\"deploy\"]\n",
+        )
+        .unwrap();
+        let event = event_mgr
+            .add_event(task.id, "deploy", "v1 to staging")
+            .await
+            .unwrap();
+        assert_eq!(event.log_type, "deploy");
+
+        let result = event_mgr
+            .update_event(event.id, Some("deployed"), None)
+            .await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+        let result = event_mgr
+            .add_events(&[NewEvent {
+                task_id: task.id,
+                log_type: "rollback".to_string(),
+                discussion_data: "v1".to_string(),
+                visibility: EventVisibility::Normal,
+                timestamp: None,
+            }])
+            .await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+    }
+
     #[tokio::test]
     async fn test_list_events() {
         let ctx = TestContext::new().await;
@@ -475,7 +1034,7 @@ mod tests {
         // Add 5 events
         for i in 0..5 {
             event_mgr
-                .add_event(task.id, "test", &format!("Event {}", i))
+                .add_event(task.id, "note", &format!("Event {}", i))
                 .await
                 .unwrap();
         }
@@ -685,4 +1244,352 @@ mod tests {
         assert_eq!(task1_events.len(), 1);
         assert_eq!(task2_events.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_list_events_page_walks_history() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Long history", None, None, None, None, None)
+            .await
+            .unwrap();
+        for i in 0..5 {
+            let log_type = if i % 2 == 0 { "note" } else { "decision" };
+            event_mgr
+                .add_event(task.id, log_type, &format!("Event {}", i))
+                .await
+                .unwrap();
+        }
+
+        let page1 = event_mgr
+            .list_events_page(Some(task.id), None, None, None, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(page1.total_count, 5);
+        assert!(page1.has_more);
+        assert_eq!(
+            page1
+                .events
+                .iter()
+                .map(|e| e.discussion_data.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Event 4", "Event 3"]
+        );
+
+        let page2 = event_mgr
+            .list_events_page(Some(task.id), None, None, page1.next_before, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(page2.events[0].discussion_data, "Event 2");
+        assert!(page2.has_more);
+
+        let page3 = event_mgr
+            .list_events_page(Some(task.id), None, None, page2.next_before, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(page3.events.len(), 1);
+        assert_eq!(page3.events[0].discussion_data, "Event 0");
+        assert!(!page3.has_more);
+        assert!(page3.next_before.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_events_page_filters_and_errors() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Task", None, None, None, None, None)
+            .await
+            .unwrap();
+        event_mgr.add_event(task.id, "note", "n").await.unwrap();
+        event_mgr.add_event(task.id, "blocker", "b").await.unwrap();
+
+        let blockers = event_mgr
+            .list_events_page(Some(task.id), Some("blocker".to_string()), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(blockers.total_count, 1);
+        assert_eq!(blockers.events[0].log_type, "blocker");
+        assert_eq!(blockers.limit, 50);
+
+        let result = event_mgr
+            .list_events_page(Some(task.id), None, None, Some(9999), None)
+            .await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+
+        let result = event_mgr
+            .list_events_page(Some(9999), None, None, None, None)
+            .await;
+        assert!(matches!(result, Err(IntentError::TaskNotFound(9999))));
+    }
+
+    #[tokio::test]
+    async fn test_event_visibility_hidden_by_default() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Task", None, None, None, None, None)
+            .await
+            .unwrap();
+        event_mgr
+            .add_event(task.id, "note", "shared")
+            .await
+            .unwrap();
+        let verbose = event_mgr
+            .add_event_with_visibility(task.id, "note", "scratch", EventVisibility::Verbose)
+            .await
+            .unwrap();
+        assert_eq!(verbose.visibility, EventVisibility::Verbose);
+        event_mgr
+            .add_event_with_visibility(task.id, "note", "secret", EventVisibility::Private)
+            .await
+            .unwrap();
+
+        let events = event_mgr
+            .list_events(Some(task.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].discussion_data, "shared");
+
+        let page = EventManager::new(ctx.pool())
+            .with_visibility(EventVisibility::Verbose)
+            .list_events_page(Some(task.id), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(page.total_count, 2);
+
+        let all = EventManager::new(ctx.pool())
+            .with_visibility(EventVisibility::Private)
+            .list_events(Some(task.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+
+        let summary = task_mgr.get_task_with_events(task.id).await.unwrap();
+        assert_eq!(summary.events_summary.unwrap().total_count, 1);
+    }
+
+    #[test]
+    fn test_event_visibility_from_str() {
+        assert_eq!("normal".parse(), Ok(EventVisibility::Normal));
+        assert_eq!("debug".parse(), Ok(EventVisibility::Verbose));
+        assert_eq!("all".parse(), Ok(EventVisibility::Private));
+        assert!("loud".parse::<EventVisibility>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_events_of_private_tasks_hidden() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool()).with_scope(TaskVisibility::Private);
+        let public = task_mgr
+            .add_task("Public", None, None, None, None, None)
+            .await
+            .unwrap();
+        let secret = task_mgr
+            .add_task("Secret", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .update_task(
+                secret.id,
+                crate::tasks::TaskUpdate {
+                    visibility: Some(TaskVisibility::Private),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let private = EventManager::new(ctx.pool()).with_scope(TaskVisibility::Private);
+        private.add_event(public.id, "note", "open").await.unwrap();
+        private
+            .add_event(secret.id, "note", "hidden")
+            .await
+            .unwrap();
+
+        let shared = EventManager::new(ctx.pool()).with_scope(TaskVisibility::Shared);
+        let events = shared.list_events(None, None, None, None).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].task_id, public.id);
+        assert!(matches!(
+            shared.list_events(Some(secret.id), None, None, None).await,
+            Err(IntentError::TaskNotFound(_))
+        ));
+        assert_eq!(
+            private
+                .list_events(None, None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!("private".parse(), Ok(TaskVisibility::Private));
+        assert!("secret".parse::<TaskVisibility>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compact_archives_old_events() {
+        let ctx = TestContext::new().await;
+        let task = TaskManager::new(ctx.pool())
+            .add_task("Long task", None, None, None, None, None)
+            .await
+            .unwrap();
+        let event_mgr = EventManager::new(ctx.pool());
+        event_mgr
+            .add_event(task.id, "decision", "Chose the archive table")
+            .await
+            .unwrap();
+        for i in 0..30 {
+            event_mgr
+                .add_event(task.id, "note", &format!("step {}", i))
+                .await
+                .unwrap();
+        }
+        event_mgr
+            .add_event_with_visibility(task.id, "note", "secret", EventVisibility::Private)
+            .await
+            .unwrap();
+
+        // Too few old events for a digest
+        let result = event_mgr
+            .compact_with(task.id, 25, &RuleSummarizer)
+            .await
+            .unwrap();
+        assert_eq!(result.archived, 0);
+
+        let result = event_mgr
+            .compact_with(task.id, 5, &RuleSummarizer)
+            .await
+            .unwrap();
+        assert_eq!(result.archived, 26);
+        let digest_id = result.digest_event_id.unwrap();
+
+        let remaining = event_mgr
+            .with_visibility(EventVisibility::Private)
+            .list_events(Some(task.id), None, None, None)
+            .await
+            .unwrap();
+        // 5 kept, the private event and the digest
+        assert_eq!(remaining.len(), 7);
+        let digest = remaining.iter().find(|e| e.id == digest_id).unwrap();
+        assert_eq!(digest.log_type, DIGEST_LOG_TYPE);
+        assert!(digest.discussion_data.contains("Chose the archive table"));
+
+        let event_mgr = EventManager::new(ctx.pool());
+        let archived = event_mgr.archived_events(digest_id).await.unwrap();
+        assert_eq!(archived.len(), 26);
+        assert_eq!(archived[0].discussion_data, "Chose the archive table");
+
+        // Digests are not compacted again
+        let result = event_mgr
+            .compact_with(task.id, 0, &RuleSummarizer)
+            .await
+            .unwrap();
+        assert_eq!(result.archived, 0);
+    }
+
+    #[tokio::test]
+    async fn test_recent_activity_merges_events_and_transitions() {
+        use crate::db::models::ActivityKind;
+
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+        let api = task_mgr
+            .add_task("API", None, None, None, None, None)
+            .await
+            .unwrap();
+        let docs = task_mgr
+            .add_task("Docs", None, None, None, None, None)
+            .await
+            .unwrap();
+        let event = event_mgr
+            .add_event(api.id, "decision", "Use REST")
+            .await
+            .unwrap();
+
+        // Spread the timeline out: the API was created two days ago
+        let ago = |hours: i64| (Utc::now() - chrono::Duration::hours(hours)).to_rfc3339();
+        sqlx::query(
+            "UPDATE tasks SET first_todo_at = ?, first_doing_at = ?, first_done_at = ?, \
+             status = 'done' WHERE id = ?",
+        )
+        .bind(ago(48))
+        .bind(ago(30))
+        .bind(ago(1))
+        .bind(api.id)
+        .execute(ctx.pool())
+        .await
+        .unwrap();
+        sqlx::query("UPDATE tasks SET first_todo_at = ? WHERE id = ?")
+            .bind(ago(40))
+            .bind(docs.id)
+            .execute(ctx.pool())
+            .await
+            .unwrap();
+        sqlx::query("UPDATE events SET timestamp = ? WHERE id = ?")
+            .bind(ago(20))
+            .bind(event.id)
+            .execute(ctx.pool())
+            .await
+            .unwrap();
+
+        let feed = event_mgr
+            .recent_activity(10, &ActivityFilter::default())
+            .await
+            .unwrap();
+        let timeline: Vec<(ActivityKind, i64)> = feed.iter().map(|e| (e.kind, e.task_id)).collect();
+        assert_eq!(
+            timeline,
+            vec![
+                (ActivityKind::Completed, api.id),
+                (ActivityKind::Event, api.id),
+                (ActivityKind::Started, api.id),
+                (ActivityKind::Created, docs.id),
+                (ActivityKind::Created, api.id),
+            ]
+        );
+        assert_eq!(feed[1].task_name, "API");
+        assert_eq!(feed[1].message.as_deref(), Some("Use REST"));
+        assert_eq!(feed[0].task_status, "done");
+
+        let filter = ActivityFilter {
+            since: Some("1d".to_string()),
+            ..Default::default()
+        };
+        let recent = event_mgr.recent_activity(10, &filter).await.unwrap();
+        assert_eq!(recent.len(), 2);
+
+        let filter = ActivityFilter {
+            event_type: Some("decision".to_string()),
+            ..Default::default()
+        };
+        let decisions = event_mgr.recent_activity(10, &filter).await.unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].event_id, Some(event.id));
+
+        let filter = ActivityFilter {
+            task_id: Some(docs.id),
+            ..Default::default()
+        };
+        assert_eq!(
+            event_mgr.recent_activity(10, &filter).await.unwrap().len(),
+            1
+        );
+        assert_eq!(
+            event_mgr
+                .recent_activity(2, &ActivityFilter::default())
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
 }
@@ -0,0 +1,526 @@
+//! Focus stack
+//!
+//! Starting a task replaces the session's focus, so jumping to an urgent bug
+//! loses track of what was being worked on. `ie focus push <id>` starts the
+//! urgent task and remembers the previous focus on a stack; `ie focus pop`
+//! returns to it. Each session has its own stack, a JSON list of task IDs
+//! (bottom first) in `workspace_state` under `focus_stack.<session>`. Tasks
+//! keep their status while they wait on the stack.
+//!
+//! `ie focus start --minutes 45` timeboxes work on the current task. The
+//! running session is kept under `focus_session.<session>`; a running
+//! Dashboard reminds (desktop notification, webhooks and the UI) when the time
+//! is up, and `ie focus stop` logs the session as a `note` event on the task.
+
+use crate::cli_handlers::config_commands::{config_delete, config_get, config_set};
+use crate::db::models::{Event, Task};
+use crate::error::{IntentError, Result};
+use crate::events::EventManager;
+use crate::tasks::TaskManager;
+use crate::workspace::{resolve_session_id, WorkspaceManager};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// `workspace_state` key prefix of the per-session stacks
+pub const FOCUS_STACK_KEY_PREFIX: &str = "focus_stack.";
+
+/// `workspace_state` key prefix of the per-session timeboxes
+pub const FOCUS_SESSION_KEY_PREFIX: &str = "focus_session.";
+
+/// Longest timebox `ie focus start` accepts
+pub const MAX_SESSION_MINUTES: u32 = 8 * 60;
+
+/// Result of `FocusStack::push`
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusPush {
+    /// The newly focused task
+    pub task: Task,
+    /// The previous focus, now on top of the stack
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspended: Option<Task>,
+    /// Tasks on the stack afterwards
+    pub depth: usize,
+}
+
+/// Result of `FocusStack::pop`
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusPop {
+    /// The task focused again
+    pub task: Task,
+    /// Stacked tasks passed over because they were done or deleted meanwhile
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<i64>,
+    /// Tasks still on the stack
+    pub depth: usize,
+}
+
+/// The focus stack of one session
+pub struct FocusStack<'a> {
+    pool: &'a SqlitePool,
+    key: String,
+}
+
+impl<'a> FocusStack<'a> {
+    /// Stack of the current session (`IE_SESSION_ID`, else the default session)
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self {
+            pool,
+            key: format!("{}{}", FOCUS_STACK_KEY_PREFIX, resolve_session_id(None)),
+        }
+    }
+
+    /// Stacked task IDs, bottom first
+    pub async fn ids(&self) -> Result<Vec<i64>> {
+        Ok(config_get(self.pool, &self.key)
+            .await?
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    /// Stacked tasks, top first; deleted tasks are left out
+    pub async fn tasks(&self) -> Result<Vec<Task>> {
+        let task_mgr = TaskManager::new(self.pool);
+        let mut tasks = Vec::new();
+        for id in self.ids().await?.into_iter().rev() {
+            match task_mgr.get_task(id).await {
+                Ok(task) => tasks.push(task),
+                Err(IntentError::TaskNotFound(_)) => {},
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(tasks)
+    }
+
+    async fn save(&self, ids: &[i64]) -> Result<()> {
+        if ids.is_empty() {
+            config_delete(self.pool, &self.key).await?;
+        } else {
+            config_set(self.pool, &self.key, &serde_json::to_string(ids)?).await?;
+        }
+        Ok(())
+    }
+
+    /// Start `task_id` and put the previous focus, if any, on the stack
+    pub async fn push(&self, task_id: i64, over_wip: bool) -> Result<FocusPush> {
+        let task_mgr = TaskManager::new(self.pool);
+        let previous = WorkspaceManager::new(self.pool)
+            .get_current_task(None)
+            .await?
+            .task;
+        if previous.as_ref().is_some_and(|t| t.id == task_id) {
+            return Err(IntentError::InvalidInput(format!(
+                "Task #{} is already the focus",
+                task_id
+            )));
+        }
+
+        let started = if over_wip {
+            task_mgr.start_task_over_wip_limit(task_id, false).await?
+        } else {
+            task_mgr.start_task(task_id, false).await?
+        };
+
+        // A task jumped to from deeper in the stack leaves its old place
+        let mut ids = self.ids().await?;
+        ids.retain(|&id| id != task_id);
+        if let Some(previous) = &previous {
+            ids.retain(|&id| id != previous.id);
+            ids.push(previous.id);
+        }
+        self.save(&ids).await?;
+
+        Ok(FocusPush {
+            task: started.task,
+            suspended: previous,
+            depth: ids.len(),
+        })
+    }
+
+    /// Focus the task on top of the stack again
+    ///
+    /// A task still in doing only gets the focus back; one moved back to todo
+    /// is started. Tasks done or deleted since they were pushed are skipped.
+    pub async fn pop(&self) -> Result<FocusPop> {
+        let task_mgr = TaskManager::new(self.pool);
+        let mut ids = self.ids().await?;
+        let mut skipped = Vec::new();
+
+        while let Some(id) = ids.pop() {
+            let task = match task_mgr.get_task(id).await {
+                Ok(task) if task.status != "done" => task,
+                Ok(_) | Err(IntentError::TaskNotFound(_)) => {
+                    skipped.push(id);
+                    continue;
+                },
+                Err(e) => return Err(e),
+            };
+
+            let task = if task.status == "todo" {
+                task_mgr.start_task(id, false).await?.task
+            } else {
+                WorkspaceManager::new(self.pool)
+                    .set_current_task(id, None)
+                    .await?;
+                task
+            };
+            self.save(&ids).await?;
+            return Ok(FocusPop {
+                task,
+                skipped,
+                depth: ids.len(),
+            });
+        }
+
+        self.save(&ids).await?;
+        Err(IntentError::InvalidInput(if skipped.is_empty() {
+            "The focus stack is empty; 'ie focus push <id>' puts the current focus on it"
+                .to_string()
+        } else {
+            format!(
+                "Nothing to return to: the stacked task(s) {} are done or deleted",
+                skipped
+                    .iter()
+                    .map(|id| format!("#{}", id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }))
+    }
+}
+
+/// A timebox running on a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub task_id: i64,
+    pub started_at: DateTime<Utc>,
+    /// Planned length
+    pub minutes: u32,
+    /// Whether the reminder for the end of the timebox went out
+    #[serde(default)]
+    pub reminded: bool,
+}
+
+impl FocusSession {
+    pub fn ends_at(&self) -> DateTime<Utc> {
+        self.started_at + Duration::minutes(i64::from(self.minutes))
+    }
+}
+
+/// A running timebox with its task, as shown by `ie focus status`
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusSessionStatus {
+    pub task: Task,
+    #[serde(flatten)]
+    pub session: FocusSession,
+    pub ends_at: DateTime<Utc>,
+    /// Whole minutes left; negative once the time is up
+    pub minutes_left: i64,
+}
+
+impl FocusSessionStatus {
+    fn new(task: Task, session: FocusSession, now: DateTime<Utc>) -> Self {
+        let ends_at = session.ends_at();
+        Self {
+            task,
+            session,
+            ends_at,
+            minutes_left: (ends_at - now).num_minutes(),
+        }
+    }
+}
+
+/// Result of `FocusTimer::stop`
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusSummary {
+    pub task_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub planned_minutes: u32,
+    pub worked_minutes: i64,
+    /// The `note` event recording the session on the task
+    pub event: Event,
+}
+
+/// The timebox of one session
+pub struct FocusTimer<'a> {
+    pool: &'a SqlitePool,
+    key: String,
+}
+
+impl<'a> FocusTimer<'a> {
+    /// Timebox of the current session (`IE_SESSION_ID`, else the default session)
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self {
+            pool,
+            key: format!("{}{}", FOCUS_SESSION_KEY_PREFIX, resolve_session_id(None)),
+        }
+    }
+
+    /// The running timebox, if any
+    pub async fn current(&self) -> Result<Option<FocusSession>> {
+        Ok(config_get(self.pool, &self.key)
+            .await?
+            .and_then(|value| serde_json::from_str(&value).ok()))
+    }
+
+    /// The running timebox with its task; a session whose task was deleted
+    /// counts as none
+    pub async fn status(&self) -> Result<Option<FocusSessionStatus>> {
+        let Some(session) = self.current().await? else {
+            return Ok(None);
+        };
+        match TaskManager::new(self.pool).get_task(session.task_id).await {
+            Ok(task) => Ok(Some(FocusSessionStatus::new(task, session, Utc::now()))),
+            Err(IntentError::TaskNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Start a timebox of `minutes` on the current task
+    pub async fn start(&self, minutes: u32) -> Result<FocusSessionStatus> {
+        if !(1..=MAX_SESSION_MINUTES).contains(&minutes) {
+            return Err(IntentError::InvalidInput(format!(
+                "A focus session lasts 1 to {} minutes, not {}",
+                MAX_SESSION_MINUTES, minutes
+            )));
+        }
+        if let Some(running) = self.status().await? {
+            return Err(IntentError::InvalidInput(format!(
+                "A focus session on #{} is already running since {}; end it with 'ie focus stop'",
+                running.task.id,
+                running.session.started_at.format("%H:%M")
+            )));
+        }
+        let task = WorkspaceManager::new(self.pool)
+            .get_current_task(None)
+            .await?
+            .task
+            .ok_or_else(|| {
+                IntentError::InvalidInput(
+                    "No current task; start one with 'ie task start <id>' first".to_string(),
+                )
+            })?;
+
+        let now = Utc::now();
+        let session = FocusSession {
+            task_id: task.id,
+            started_at: now,
+            minutes,
+            reminded: false,
+        };
+        config_set(self.pool, &self.key, &serde_json::to_string(&session)?).await?;
+        Ok(FocusSessionStatus::new(task, session, now))
+    }
+
+    /// End the running timebox and log it on its task, with `note` appended
+    pub async fn stop(&self, note: Option<&str>) -> Result<FocusSummary> {
+        let session = self.current().await?.ok_or_else(|| {
+            IntentError::InvalidInput(
+                "No focus session is running; start one with 'ie focus start'".to_string(),
+            )
+        })?;
+        // Cleared first: a session whose task is gone must not stay stuck
+        config_delete(self.pool, &self.key).await?;
+
+        let ended_at = Utc::now();
+        let worked_minutes = (ended_at - session.started_at).num_minutes().max(0);
+        let planned = i64::from(session.minutes);
+        let mut text = format!(
+            "Focus session: worked {} of {} min",
+            worked_minutes, session.minutes
+        );
+        if worked_minutes < planned {
+            text.push_str(&format!(
+                " (stopped {} min early)",
+                planned - worked_minutes
+            ));
+        } else if worked_minutes > planned {
+            text.push_str(&format!(" (ran {} min over)", worked_minutes - planned));
+        }
+        if let Some(note) = note.map(str::trim).filter(|n| !n.is_empty()) {
+            text.push_str("\n\n");
+            text.push_str(note);
+        }
+        let event = EventManager::new(self.pool)
+            .add_event(session.task_id, "note", &text)
+            .await?;
+
+        Ok(FocusSummary {
+            task_id: session.task_id,
+            started_at: session.started_at,
+            ended_at,
+            planned_minutes: session.minutes,
+            worked_minutes,
+            event,
+        })
+    }
+}
+
+/// Timeboxes of any session whose time is up and that got no reminder yet
+///
+/// Marks them reminded, so each one is returned once even when several
+/// Dashboards poll the same database.
+pub async fn due_reminders(pool: &SqlitePool) -> Result<Vec<FocusSessionStatus>> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT key, value FROM workspace_state WHERE substr(key, 1, length(?1)) = ?1",
+    )
+    .bind(FOCUS_SESSION_KEY_PREFIX)
+    .fetch_all(pool)
+    .await?;
+
+    let now = Utc::now();
+    let task_mgr = TaskManager::new(pool);
+    let mut due = Vec::new();
+    for (key, value) in rows {
+        let Ok(mut session) = serde_json::from_str::<FocusSession>(&value) else {
+            continue;
+        };
+        if session.reminded || session.ends_at() > now {
+            continue;
+        }
+        session.reminded = true;
+        // Only if the session was not stopped or replaced meanwhile
+        let updated =
+            sqlx::query("UPDATE workspace_state SET value = ? WHERE key = ? AND value = ?")
+                .bind(serde_json::to_string(&session)?)
+                .bind(&key)
+                .bind(&value)
+                .execute(pool)
+                .await?;
+        if updated.rows_affected() == 0 {
+            continue;
+        }
+        match task_mgr.get_task(session.task_id).await {
+            Ok(task) => due.push(FocusSessionStatus::new(task, session, now)),
+            Err(IntentError::TaskNotFound(_)) => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_push_and_pop_return_to_previous_focus() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let feature = task_mgr
+            .add_task("Feature", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let bug = task_mgr
+            .add_task("Urgent bug", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let stack = FocusStack::new(ctx.pool());
+
+        // Nothing focused yet: push starts the task without stacking anything
+        let pushed = stack.push(feature.id, false).await.unwrap();
+        assert!(pushed.suspended.is_none());
+        assert_eq!(pushed.depth, 0);
+
+        let pushed = stack.push(bug.id, true).await.unwrap();
+        assert_eq!(pushed.task.id, bug.id);
+        assert_eq!(pushed.suspended.map(|t| t.id), Some(feature.id));
+        assert_eq!(stack.ids().await.unwrap(), vec![feature.id]);
+
+        task_mgr.done_task().await.unwrap();
+        let popped = stack.pop().await.unwrap();
+        assert_eq!(popped.task.id, feature.id);
+        assert_eq!(popped.task.status, "doing");
+        assert_eq!(popped.depth, 0);
+        let current = WorkspaceManager::new(ctx.pool())
+            .get_current_task(None)
+            .await
+            .unwrap();
+        assert_eq!(current.current_task_id, Some(feature.id));
+
+        let err = stack.pop().await.unwrap_err();
+        assert!(err.to_string().contains("empty"));
+    }
+
+    #[tokio::test]
+    async fn test_pop_skips_finished_tasks() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let mut ids = Vec::new();
+        for name in ["A", "B", "C"] {
+            let task = task_mgr
+                .add_task(name, Some("spec"), None, None, None, None)
+                .await
+                .unwrap();
+            ids.push(task.id);
+        }
+        let stack = FocusStack::new(ctx.pool());
+        for &id in &ids {
+            stack.push(id, true).await.unwrap();
+        }
+        assert_eq!(stack.ids().await.unwrap(), vec![ids[0], ids[1]]);
+
+        task_mgr.done_task_by_id(ids[1]).await.unwrap();
+        let popped = stack.pop().await.unwrap();
+        assert_eq!(popped.task.id, ids[0]);
+        assert_eq!(popped.skipped, vec![ids[1]]);
+        assert!(stack.ids().await.unwrap().is_empty());
+
+        let err = stack.push(ids[0], false).await.unwrap_err();
+        assert!(err.to_string().contains("already the focus"));
+    }
+
+    #[tokio::test]
+    async fn test_timebox_reminds_once_and_logs_summary() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let task = task_mgr
+            .add_task("Parser", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let timer = FocusTimer::new(ctx.pool());
+
+        let err = timer.start(45).await.unwrap_err();
+        assert!(err.to_string().contains("No current task"));
+        task_mgr.start_task(task.id, false).await.unwrap();
+        assert!(timer.start(0).await.is_err());
+
+        let started = timer.start(45).await.unwrap();
+        assert_eq!(started.task.id, task.id);
+        assert!(started.minutes_left >= 44);
+        let err = timer.start(25).await.unwrap_err();
+        assert!(err.to_string().contains("already running"));
+        assert!(due_reminders(ctx.pool()).await.unwrap().is_empty());
+
+        // Let the timebox run out
+        let session = FocusSession {
+            started_at: Utc::now() - Duration::minutes(50),
+            ..started.session
+        };
+        config_set(
+            ctx.pool(),
+            &timer.key,
+            &serde_json::to_string(&session).unwrap(),
+        )
+        .await
+        .unwrap();
+        let due = due_reminders(ctx.pool()).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].task.id, task.id);
+        assert!(due[0].minutes_left < 0);
+        assert!(due_reminders(ctx.pool()).await.unwrap().is_empty());
+
+        let summary = timer.stop(Some("Tests next")).await.unwrap();
+        assert_eq!(summary.worked_minutes, 50);
+        assert_eq!(summary.event.task_id, task.id);
+        assert_eq!(summary.event.log_type, "note");
+        assert_eq!(
+            summary.event.discussion_data,
+            "Focus session: worked 50 of 45 min (ran 5 min over)\n\nTests next"
+        );
+        assert!(timer.current().await.unwrap().is_none());
+        assert!(timer.stop(None).await.is_err());
+    }
+}
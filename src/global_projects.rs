@@ -2,9 +2,18 @@
 //!
 //! Manages a global list of all projects that have used Intent-Engine.
 //! This allows the Dashboard to show all known projects even when CLI is not running.
+//!
+//! Tasks can also depend on tasks of other projects (`ie deps link --task 5
+//! --blocked-by api-server#12`). Such a dependency is stored in the blocked
+//! task's database with the other project's root path; the other database is
+//! opened read-only whenever the dependency is checked, so `start_task`
+//! refuses to start a task until its blockers elsewhere are finished.
 
+use crate::error::{IntentError, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 const GLOBAL_DIR: &str = ".intent-engine";
@@ -98,7 +107,7 @@ impl ProjectsRegistry {
     /// Get all registered projects sorted by last_accessed (most recent first)
     pub fn get_projects(&self) -> Vec<&ProjectEntry> {
         let mut projects: Vec<_> = self.projects.iter().collect();
-        projects.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_accessed));
         projects
     }
 
@@ -108,6 +117,35 @@ impl ProjectsRegistry {
         let db_path = project_path.join(".intent-engine").join("project.db");
         db_path.exists()
     }
+
+    /// Registered projects that still have a database, most recent first
+    pub fn valid_projects(&self) -> Vec<&ProjectEntry> {
+        self.get_projects()
+            .into_iter()
+            .filter(|p| Self::validate_project(&p.path))
+            .collect()
+    }
+
+    /// The project `project` refers to: its root path or its display name
+    pub fn resolve(&self, project: &str) -> Option<&ProjectEntry> {
+        self.projects
+            .iter()
+            .find(|p| p.path == project)
+            .or_else(|| self.projects.iter().find(|p| p.display_name() == project))
+    }
+}
+
+impl ProjectEntry {
+    /// Display name: the configured name, or the directory name
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            Path::new(&self.path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string()
+        })
+    }
 }
 
 /// Register a project in the global registry (convenience function)
@@ -131,6 +169,315 @@ pub fn remove_project(project_path: &str) -> bool {
     removed
 }
 
+/// A task of another project: `<project>#<task id>`, the project given by
+/// root path or registered name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectTaskRef {
+    pub project: String,
+    pub task_id: i64,
+}
+
+impl std::str::FromStr for ProjectTaskRef {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parsed = s.rsplit_once('#').and_then(|(project, id)| {
+            let task_id = id.trim().parse().ok()?;
+            Some((project.trim(), task_id))
+        });
+        match parsed {
+            Some((project, task_id)) if !project.is_empty() => Ok(Self {
+                project: project.to_string(),
+                task_id,
+            }),
+            _ => Err(IntentError::InvalidInput(format!(
+                "Invalid task reference '{}': expected <project>#<task id>, e.g. api-server#12",
+                s
+            ))),
+        }
+    }
+}
+
+/// A dependency of a task on a task of another project, with the state of
+/// the other task as last read
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CrossProjectLink {
+    pub id: i64,
+    pub blocked_task_id: i64,
+    pub blocked_task_name: String,
+    /// Root path of the project holding the blocking task
+    pub project_path: String,
+    pub project_name: String,
+    pub blocking_task_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocking_task_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocking_task_status: Option<String>,
+    /// Whether the blocking task is done, as for local dependencies
+    pub satisfied: bool,
+    /// Why the blocking task could not be read; such a link is unsatisfied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CrossProjectLink {
+    /// `<project name>#<task id>`
+    pub fn blocking_ref(&self) -> String {
+        format!("{}#{}", self.project_name, self.blocking_task_id)
+    }
+}
+
+/// Cross-project dependencies of the tasks in one project database
+pub struct CrossProjectLinks<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> CrossProjectLinks<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Make `blocked_task_id` wait for `blocking`
+    ///
+    /// The other project must have a database holding the task, and must
+    /// not be this project (use a plain dependency there).
+    pub async fn add(
+        &self,
+        blocked_task_id: i64,
+        blocking: &ProjectTaskRef,
+    ) -> Result<CrossProjectLink> {
+        self.check_local_task(blocked_task_id).await?;
+        let project_path = resolve_project_path(&blocking.project)?;
+        if Some(Path::new(&project_path)) == self.project_root().as_deref() {
+            return Err(IntentError::InvalidInput(format!(
+                "{} is this project; use 'ie task update --add-blocked-by {}' instead",
+                blocking.project, blocking.task_id
+            )));
+        }
+        let remote = read_remote_tasks(&project_path, &[blocking.task_id]).await?;
+        if !remote.contains_key(&blocking.task_id) {
+            return Err(IntentError::InvalidInput(format!(
+                "Task #{} not found in project {}",
+                blocking.task_id, project_path
+            )));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO cross_project_dependencies (blocked_task_id, project_path, blocking_task_id, created_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(blocked_task_id)
+        .bind(&project_path)
+        .bind(blocking.task_id)
+        .bind(Utc::now())
+        .execute(self.pool)
+        .await?;
+
+        self.list(Some(blocked_task_id))
+            .await?
+            .into_iter()
+            .find(|l| l.project_path == project_path && l.blocking_task_id == blocking.task_id)
+            .ok_or_else(|| IntentError::InvalidInput("Link was not recorded".to_string()))
+    }
+
+    /// Drop the dependency of `blocked_task_id` on `blocking`
+    pub async fn remove(&self, blocked_task_id: i64, blocking: &ProjectTaskRef) -> Result<()> {
+        let project_path = resolve_project_path(&blocking.project)?;
+        let removed = sqlx::query(
+            "DELETE FROM cross_project_dependencies WHERE blocked_task_id = ? AND project_path = ? AND blocking_task_id = ?",
+        )
+        .bind(blocked_task_id)
+        .bind(&project_path)
+        .bind(blocking.task_id)
+        .execute(self.pool)
+        .await?
+        .rows_affected();
+        if removed == 0 {
+            return Err(IntentError::InvalidInput(format!(
+                "Task #{} does not depend on {}#{}",
+                blocked_task_id, blocking.project, blocking.task_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Links of `task_id`, or of every task, with the blocking tasks' state
+    pub async fn list(&self, task_id: Option<i64>) -> Result<Vec<CrossProjectLink>> {
+        let rows: Vec<(i64, i64, String, String, i64, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT d.id, d.blocked_task_id, t.name, d.project_path, d.blocking_task_id, d.created_at
+            FROM cross_project_dependencies d
+            JOIN tasks t ON t.id = d.blocked_task_id
+            WHERE (? IS NULL OR d.blocked_task_id = ?)
+            ORDER BY d.blocked_task_id, d.id
+            "#,
+        )
+        .bind(task_id)
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        // One read per other project
+        let mut wanted: HashMap<&str, Vec<i64>> = HashMap::new();
+        for row in &rows {
+            wanted.entry(row.3.as_str()).or_default().push(row.4);
+        }
+        let mut remote = HashMap::new();
+        for (project_path, ids) in wanted {
+            remote.insert(
+                project_path.to_string(),
+                read_remote_tasks(project_path, &ids)
+                    .await
+                    .map_err(|e| e.to_string()),
+            );
+        }
+
+        let registry = ProjectsRegistry::load();
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    id,
+                    blocked_task_id,
+                    blocked_task_name,
+                    project_path,
+                    blocking_task_id,
+                    created_at,
+                )| {
+                    let (task, error) = match &remote[&project_path] {
+                        Ok(tasks) => match tasks.get(&blocking_task_id) {
+                            Some(task) => (Some(task.clone()), None),
+                            None => (
+                                None,
+                                Some(format!("Task #{} no longer exists", blocking_task_id)),
+                            ),
+                        },
+                        Err(e) => (None, Some(e.clone())),
+                    };
+                    let project_name = registry
+                        .resolve(&project_path)
+                        .map(ProjectEntry::display_name)
+                        .unwrap_or_else(|| display_name_of(&project_path));
+                    CrossProjectLink {
+                        id,
+                        blocked_task_id,
+                        blocked_task_name,
+                        project_name,
+                        project_path,
+                        blocking_task_id,
+                        satisfied: task.as_ref().is_some_and(|(_, status)| status == "done"),
+                        blocking_task_name: task.as_ref().map(|(name, _)| name.clone()),
+                        blocking_task_status: task.map(|(_, status)| status),
+                        error,
+                        created_at,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Links holding `task_id` back: blocking task unfinished or unreadable
+    pub async fn incomplete_blockers(&self, task_id: i64) -> Result<Vec<CrossProjectLink>> {
+        let has_links: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM cross_project_dependencies WHERE blocked_task_id = ?)",
+        )
+        .bind(task_id)
+        .fetch_one(self.pool)
+        .await?;
+        if !has_links {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .list(Some(task_id))
+            .await?
+            .into_iter()
+            .filter(|l| !l.satisfied)
+            .collect())
+    }
+
+    async fn check_local_task(&self, task_id: i64) -> Result<()> {
+        let exists: bool = sqlx::query_scalar(crate::sql_constants::CHECK_TASK_EXISTS)
+            .bind(task_id)
+            .fetch_one(self.pool)
+            .await?;
+        if !exists {
+            return Err(IntentError::TaskNotFound(task_id));
+        }
+        Ok(())
+    }
+
+    /// Root of the project this database belongs to
+    fn project_root(&self) -> Option<PathBuf> {
+        let options = self.pool.connect_options();
+        let root = options.get_filename().parent()?.parent()?;
+        root.canonicalize().ok()
+    }
+}
+
+/// Root path of the project `project` names: a registered name, or a
+/// directory holding an Intent-Engine database
+fn resolve_project_path(project: &str) -> Result<String> {
+    let path = Path::new(project);
+    if ProjectsRegistry::validate_project(project) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        return Ok(path.to_string_lossy().to_string());
+    }
+    let registry = ProjectsRegistry::load();
+    match registry.resolve(project) {
+        Some(entry) if ProjectsRegistry::validate_project(&entry.path) => Ok(entry.path.clone()),
+        Some(entry) => Err(IntentError::InvalidInput(format!(
+            "Project '{}' at {} has no Intent-Engine database",
+            project, entry.path
+        ))),
+        None => Err(IntentError::InvalidInput(format!(
+            "Unknown project '{}': give its root path or the name of a registered project",
+            project
+        ))),
+    }
+}
+
+fn display_name_of(project_path: &str) -> String {
+    Path::new(project_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(project_path)
+        .to_string()
+}
+
+/// (name, status) of the tasks `ids` in another project's database
+async fn read_remote_tasks(
+    project_path: &str,
+    ids: &[i64],
+) -> Result<HashMap<i64, (String, String)>> {
+    let db_path = Path::new(project_path)
+        .join(".intent-engine")
+        .join("project.db");
+    if !db_path.exists() {
+        return Err(IntentError::InvalidInput(format!(
+            "No Intent-Engine database at {}",
+            project_path
+        )));
+    }
+    let pool = crate::db::create_readonly_pool(&db_path).await?;
+    let mut tasks = HashMap::new();
+    for &id in ids {
+        let row: Option<(String, String)> =
+            sqlx::query_as("SELECT name, status FROM tasks WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&pool)
+                .await?;
+        if let Some(row) = row {
+            tasks.insert(id, row);
+        }
+    }
+    pool.close().await;
+    Ok(tasks)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,4 +535,114 @@ mod tests {
         assert_eq!(projects[0].path, "/new");
         assert_eq!(projects[1].path, "/old");
     }
+
+    #[test]
+    fn test_valid_projects_skips_missing_databases() {
+        let with_db = TempDir::new().unwrap();
+        std::fs::create_dir_all(with_db.path().join(".intent-engine")).unwrap();
+        std::fs::write(with_db.path().join(".intent-engine/project.db"), b"").unwrap();
+        let without_db = TempDir::new().unwrap();
+
+        let mut registry = ProjectsRegistry::default();
+        registry.register_project(with_db.path());
+        registry.register_project(without_db.path());
+
+        let valid = registry.valid_projects();
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].path, with_db.path().to_string_lossy());
+        assert_eq!(
+            valid[0].display_name(),
+            with_db.path().file_name().unwrap().to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn test_project_task_ref_parse_and_resolve() {
+        let parsed: ProjectTaskRef = "api-server#12".parse().unwrap();
+        assert_eq!(parsed.project, "api-server");
+        assert_eq!(parsed.task_id, 12);
+        assert!("api-server".parse::<ProjectTaskRef>().is_err());
+        assert!("#12".parse::<ProjectTaskRef>().is_err());
+        assert!("api#x".parse::<ProjectTaskRef>().is_err());
+
+        let mut registry = ProjectsRegistry::default();
+        registry.projects.push(ProjectEntry {
+            path: "/src/api".to_string(),
+            last_accessed: Utc::now(),
+            name: Some("api-server".to_string()),
+        });
+        assert_eq!(registry.resolve("api-server").unwrap().path, "/src/api");
+        assert_eq!(registry.resolve("/src/api").unwrap().path, "/src/api");
+        assert!(registry.resolve("web").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cross_project_dependency_blocks_start() {
+        use crate::tasks::TaskManager;
+        use crate::test_utils::test_helpers::TestContext;
+
+        let api = TestContext::new().await;
+        let web = TestContext::new().await;
+        let endpoint = TaskManager::new(api.pool())
+            .add_task("Ship login endpoint", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let web_tasks = TaskManager::new(web.pool());
+        let client = web_tasks
+            .add_task("Use login API", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+
+        let links = CrossProjectLinks::new(web.pool());
+        let api_root = api.project_root().display().to_string();
+        let blocking: ProjectTaskRef = format!("{}#{}", api_root, endpoint.id).parse().unwrap();
+        let link = links.add(client.id, &blocking).await.unwrap();
+        assert_eq!(
+            link.blocking_task_name.as_deref(),
+            Some("Ship login endpoint")
+        );
+        assert!(!link.satisfied);
+        // Adding it again changes nothing
+        links.add(client.id, &blocking).await.unwrap();
+        assert_eq!(links.list(None).await.unwrap().len(), 1);
+
+        let missing: ProjectTaskRef = format!("{}#999", api_root).parse().unwrap();
+        assert!(links.add(client.id, &missing).await.is_err());
+        let own = format!("{}#{}", web.project_root().display(), client.id)
+            .parse()
+            .unwrap();
+        assert!(links.add(client.id, &own).await.is_err());
+
+        match web_tasks.start_task(client.id, false).await.unwrap_err() {
+            IntentError::CrossProjectBlocked { task_id, blockers } => {
+                assert_eq!(task_id, client.id);
+                assert_eq!(blockers, vec![link.blocking_ref()]);
+            },
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        // A blocker awaiting review still blocks, as it does within a project
+        sqlx::query("UPDATE tasks SET status = 'review' WHERE id = ?")
+            .bind(endpoint.id)
+            .execute(api.pool())
+            .await
+            .unwrap();
+        assert_eq!(links.incomplete_blockers(client.id).await.unwrap().len(), 1);
+        assert!(web_tasks.start_task(client.id, false).await.is_err());
+
+        sqlx::query("UPDATE tasks SET status = 'done' WHERE id = ?")
+            .bind(endpoint.id)
+            .execute(api.pool())
+            .await
+            .unwrap();
+        assert!(links
+            .incomplete_blockers(client.id)
+            .await
+            .unwrap()
+            .is_empty());
+        web_tasks.start_task(client.id, false).await.unwrap();
+
+        links.remove(client.id, &blocking).await.unwrap();
+        assert!(links.remove(client.id, &blocking).await.is_err());
+    }
 }
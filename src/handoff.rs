@@ -0,0 +1,138 @@
+//! Handoff notes between agent sessions
+//!
+//! When a session ends, the agent leaves a handoff: what state the work is
+//! in and what to do next. Unlike an event it is not tied to one task's
+//! history, and `ie status` and session restore show the latest one first
+//! instead of burying it among the task's events.
+
+use crate::db::models::Handoff;
+use crate::error::{IntentError, Result};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+const HANDOFF_COLUMNS: &str = "id, task_id, summary, next_steps, created_at";
+
+pub struct HandoffManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> HandoffManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a handoff, optionally about a task
+    pub async fn create(
+        &self,
+        task_id: Option<i64>,
+        summary: &str,
+        next_steps: Option<&str>,
+    ) -> Result<Handoff> {
+        if summary.trim().is_empty() {
+            return Err(IntentError::InvalidInput(
+                "Handoff summary cannot be empty".to_string(),
+            ));
+        }
+        if let Some(id) = task_id {
+            let exists: bool = sqlx::query_scalar(crate::sql_constants::CHECK_TASK_EXISTS)
+                .bind(id)
+                .fetch_one(self.pool)
+                .await?;
+            if !exists {
+                return Err(IntentError::TaskNotFound(id));
+            }
+        }
+        let next_steps = next_steps.filter(|s| !s.trim().is_empty());
+
+        let id = sqlx::query(
+            "INSERT INTO handoffs (task_id, summary, next_steps, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(summary)
+        .bind(next_steps)
+        .bind(Utc::now())
+        .execute(self.pool)
+        .await?
+        .last_insert_rowid();
+
+        self.get(id).await
+    }
+
+    pub async fn get(&self, id: i64) -> Result<Handoff> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM handoffs WHERE id = ?",
+            HANDOFF_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or_else(|| IntentError::InvalidInput(format!("Handoff #{} not found", id)))
+    }
+
+    /// Most recent handoffs first, optionally only those about `task_id`
+    pub async fn list(&self, task_id: Option<i64>, limit: i64) -> Result<Vec<Handoff>> {
+        let filter = if task_id.is_some() {
+            "WHERE task_id = ?"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "SELECT {} FROM handoffs {} ORDER BY created_at DESC, id DESC LIMIT ?",
+            HANDOFF_COLUMNS, filter
+        );
+        let mut query = sqlx::query_as(&sql);
+        if let Some(id) = task_id {
+            query = query.bind(id);
+        }
+        Ok(query.bind(limit).fetch_all(self.pool).await?)
+    }
+
+    /// The latest handoff of the project
+    pub async fn latest(&self) -> Result<Option<Handoff>> {
+        Ok(self.list(None, 1).await?.pop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_create_and_list_handoffs() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let task = TaskManager::new(pool)
+            .add_task("Auth", None, None, None, None, None)
+            .await
+            .unwrap();
+        let handoffs = HandoffManager::new(pool);
+
+        assert!(handoffs.latest().await.unwrap().is_none());
+        assert!(handoffs.create(None, "  ", None).await.is_err());
+        assert!(matches!(
+            handoffs.create(Some(999), "x", None).await,
+            Err(IntentError::TaskNotFound(999))
+        ));
+
+        let first = handoffs
+            .create(Some(task.id), "Login works", Some("Add refresh tokens"))
+            .await
+            .unwrap();
+        assert_eq!(first.next_steps.as_deref(), Some("Add refresh tokens"));
+        let second = handoffs
+            .create(None, "Cleaned up CI", Some(""))
+            .await
+            .unwrap();
+        assert_eq!(second.next_steps, None);
+
+        assert_eq!(handoffs.latest().await.unwrap().unwrap().id, second.id);
+        let for_task = handoffs.list(Some(task.id), 10).await.unwrap();
+        assert_eq!(for_task, vec![first.clone()]);
+
+        // Deleting the task keeps the note
+        TaskManager::new(pool).delete_task(task.id).await.unwrap();
+        assert_eq!(handoffs.get(first.id).await.unwrap().task_id, None);
+    }
+}
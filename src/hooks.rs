@@ -0,0 +1,459 @@
+//! User-defined commands run on lifecycle events
+//!
+//! The `[hooks]` table of the project's config.toml (see `crate::config`)
+//! lists shell commands per event:
+//!
+//! ```toml
+//! [hooks]
+//! task_completed = ["./scripts/notify-slack.sh"]
+//! plan_executed = ["make sync-board"]
+//! timeout_secs = 10      # a command still running after this is killed
+//! on_failure = "warn"    # warn, ignore or fail
+//! ```
+//!
+//! Each command runs through the shell (`sh -c`, `cmd /C` on Windows) in the
+//! project root, after the change it reports on has been made. It gets a JSON
+//! payload on stdin (`event`, `project_path`, `timestamp`, `data`) and the
+//! `IE_HOOK_EVENT` and `IE_PROJECT_ROOT` environment variables. A command
+//! fails when it exits non-zero, cannot be started or runs out of time;
+//! `on_failure` decides whether that is a warning on stderr, only a log
+//! line, or an error of the `ie` command (the change itself stays).
+//!
+//! Hooks fire for `ie` commands on any storage backend; changes made through
+//! the Dashboard do not run them.
+
+use crate::config::ProjectConfig;
+use crate::error::{IntentError, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Default of `[hooks] timeout_secs`
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often a running hook is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long output of a killed hook is still collected
+const KILL_GRACE: Duration = Duration::from_millis(200);
+
+/// Lifecycle events hooks can be attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    /// `ie task create`
+    TaskCreated,
+    /// `ie task start`, or an update to doing
+    TaskStarted,
+    /// `ie task done` or `ie task approve` completed a task, or an update to done
+    TaskCompleted,
+    /// `ie plan` applied a plan
+    PlanExecuted,
+    /// `ie status` restored the session context
+    SessionRestored,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 5] = [
+        HookEvent::TaskCreated,
+        HookEvent::TaskStarted,
+        HookEvent::TaskCompleted,
+        HookEvent::PlanExecuted,
+        HookEvent::SessionRestored,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::TaskCreated => "task_created",
+            HookEvent::TaskStarted => "task_started",
+            HookEvent::TaskCompleted => "task_completed",
+            HookEvent::PlanExecuted => "plan_executed",
+            HookEvent::SessionRestored => "session_restored",
+        }
+    }
+}
+
+impl std::str::FromStr for HookEvent {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == s)
+            .ok_or_else(|| {
+                IntentError::InvalidInput(format!(
+                    "Invalid hook event '{}'. Expected task_created, task_started, \
+                     task_completed, plan_executed or session_restored",
+                    s
+                ))
+            })
+    }
+}
+
+/// What a failing hook does to the `ie` command that triggered it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFailure {
+    /// Print a warning on stderr
+    #[default]
+    Warn,
+    /// Only log it
+    Ignore,
+    /// Make the command fail
+    Fail,
+}
+
+impl HookFailure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookFailure::Warn => "warn",
+            HookFailure::Ignore => "ignore",
+            HookFailure::Fail => "fail",
+        }
+    }
+}
+
+impl std::str::FromStr for HookFailure {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "warn" => Ok(HookFailure::Warn),
+            "ignore" => Ok(HookFailure::Ignore),
+            "fail" => Ok(HookFailure::Fail),
+            _ => Err(IntentError::InvalidInput(format!(
+                "Invalid hook failure policy '{}'. Expected warn, ignore or fail",
+                s
+            ))),
+        }
+    }
+}
+
+/// How one hook command went
+#[derive(Debug, Clone, Serialize)]
+pub struct HookRun {
+    pub command: String,
+    /// Exit code, if the command exited on its own
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration_ms: u64,
+    pub stdout: String,
+    pub stderr: String,
+    /// Why the command could not be started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HookRun {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// One-line reason of a failure
+    pub fn failure(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("could not start: {}", error);
+        }
+        if self.timed_out {
+            return format!("timed out after {} ms", self.duration_ms);
+        }
+        let status = match self.exit_code {
+            Some(code) => format!("exited with {}", code),
+            None => "killed by a signal".to_string(),
+        };
+        match self.stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(line) => format!("{}: {}", status, line.trim()),
+            None => status,
+        }
+    }
+}
+
+/// The hooks of one project
+pub struct Hooks {
+    root: PathBuf,
+    config: crate::config::HooksConfig,
+}
+
+impl Hooks {
+    pub fn new(root: PathBuf, config: crate::config::HooksConfig) -> Self {
+        Self { root, config }
+    }
+
+    /// Hooks of the project rooted at `root`
+    pub fn for_project_root(root: &Path) -> Result<Self> {
+        let config = ProjectConfig::for_project_root(root)?.hooks;
+        Ok(Self::new(root.to_path_buf(), config))
+    }
+
+    pub fn config(&self) -> &crate::config::HooksConfig {
+        &self.config
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Payload sent on stdin for `event`
+    pub fn payload(&self, event: HookEvent, data: Value) -> Value {
+        serde_json::json!({
+            "event": event.as_str(),
+            "project_path": self.root.display().to_string(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "data": data,
+        })
+    }
+
+    /// Run every command of `event` with `payload` on stdin, in order
+    pub fn run(&self, event: HookEvent, payload: &Value) -> Vec<HookRun> {
+        let input = payload.to_string();
+        self.config
+            .commands(event)
+            .iter()
+            .map(|command| self.run_one(event, command, &input))
+            .collect()
+    }
+
+    /// Run the hooks of `event` and apply the failure policy
+    ///
+    /// `data` is only serialized when the event has hooks, so events nobody
+    /// listens to cost nothing.
+    pub fn fire<T: Serialize>(&self, event: HookEvent, data: &T) -> Result<()> {
+        if self.config.commands(event).is_empty() {
+            return Ok(());
+        }
+        let payload = self.payload(event, serde_json::to_value(data)?);
+        for run in self.run(event, &payload) {
+            if run.succeeded() {
+                tracing::debug!(event = event.as_str(), command = %run.command, "Hook ran");
+                continue;
+            }
+            let message = format!(
+                "Hook '{}' for {} failed: {}",
+                run.command,
+                event.as_str(),
+                run.failure()
+            );
+            match self.config.on_failure {
+                HookFailure::Warn => eprintln!("⚠ {}", message),
+                HookFailure::Ignore => tracing::info!("{}", message),
+                HookFailure::Fail => return Err(IntentError::OtherError(anyhow::anyhow!(message))),
+            }
+        }
+        Ok(())
+    }
+
+    fn run_one(&self, event: HookEvent, command: &str, input: &str) -> HookRun {
+        let started = Instant::now();
+        let mut run = HookRun {
+            command: command.to_string(),
+            exit_code: None,
+            timed_out: false,
+            duration_ms: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+        };
+
+        let mut cmd = shell(command);
+        if !self.root.as_os_str().is_empty() {
+            cmd.current_dir(&self.root);
+        }
+        let child = cmd
+            .env("IE_HOOK_EVENT", event.as_str())
+            .env("IE_PROJECT_ROOT", &self.root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                run.error = Some(e.to_string());
+                return run;
+            },
+        };
+
+        // Feed and drain the pipes on their own threads, so a hook that
+        // ignores stdin or prints a lot cannot stall us
+        let stdin = child.stdin.take().map(|mut pipe| {
+            let input = input.to_string();
+            std::thread::spawn(move || {
+                // A hook that exits without reading its input is fine
+                let _ = pipe.write_all(input.as_bytes());
+            })
+        });
+        let stdout = child.stdout.take().map(read_all);
+        let stderr = child.stderr.take().map(read_all);
+
+        match wait_with_timeout(&mut child, self.config.timeout) {
+            Ok(Some(status)) => run.exit_code = status.code(),
+            Ok(None) => run.timed_out = true,
+            Err(e) => run.error = Some(e.to_string()),
+        }
+        run.duration_ms = started.elapsed().as_millis() as u64;
+
+        // After a kill, a process that escaped the group may still hold the
+        // pipes; give the readers a moment, then leave them behind
+        let grace = if run.timed_out {
+            Some(Instant::now() + KILL_GRACE)
+        } else {
+            None
+        };
+        if let Some(handle) = stdin {
+            join_until(handle, grace);
+        }
+        run.stdout = stdout
+            .and_then(|h| join_until(h, grace))
+            .unwrap_or_default();
+        run.stderr = stderr
+            .and_then(|h| join_until(h, grace))
+            .unwrap_or_default();
+        run
+    }
+}
+
+/// The platform shell running `command`
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        // Own process group, so a timeout kills what the hook started too
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+        cmd
+    }
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// Join `handle`, giving up at `deadline` if there is one
+fn join_until<T>(handle: std::thread::JoinHandle<T>, deadline: Option<Instant>) -> Option<T> {
+    if let Some(deadline) = deadline {
+        while !handle.is_finished() {
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+    handle.join().ok()
+}
+
+/// Kill `child` and every process in its group
+fn kill_tree(child: &mut Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{killpg, Signal};
+        use nix::unistd::Pid;
+        if killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL).is_ok() {
+            return Ok(());
+        }
+    }
+    child.kill()
+}
+
+/// Wait for `child`, killing it after `timeout`; `None` means it was killed
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            kill_tree(child)?;
+            child.wait()?;
+            return Ok(None);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config::HooksConfig;
+
+    fn hooks(dir: &Path, commands: &[&str], on_failure: HookFailure) -> Hooks {
+        let mut config = HooksConfig {
+            timeout: Duration::from_millis(500),
+            on_failure,
+            ..Default::default()
+        };
+        config.commands.insert(
+            HookEvent::TaskCompleted,
+            commands.iter().map(|c| c.to_string()).collect(),
+        );
+        Hooks::new(dir.to_path_buf(), config)
+    }
+
+    #[test]
+    fn test_hook_gets_payload_and_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = hooks(
+            dir.path(),
+            &["cat > payload.json; echo \"$IE_HOOK_EVENT\" > event.txt"],
+            HookFailure::Fail,
+        );
+        hooks
+            .fire(HookEvent::TaskCompleted, &serde_json::json!({"id": 7}))
+            .unwrap();
+
+        let payload: Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join("payload.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(payload["event"], "task_completed");
+        assert_eq!(payload["data"]["id"], 7);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("event.txt")).unwrap(),
+            "task_completed\n"
+        );
+    }
+
+    #[test]
+    fn test_hook_failures_follow_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = serde_json::json!({});
+
+        let failing = hooks(dir.path(), &["echo boom >&2; exit 3"], HookFailure::Fail);
+        let err = failing
+            .fire(HookEvent::TaskCompleted, &data)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exited with 3: boom"), "{}", err);
+
+        let warning = hooks(dir.path(), &["exit 1"], HookFailure::Warn);
+        assert!(warning.fire(HookEvent::TaskCompleted, &data).is_ok());
+
+        // Other events have no hooks
+        assert!(failing.fire(HookEvent::TaskCreated, &data).is_ok());
+    }
+
+    #[test]
+    fn test_hook_timeout_kills_command() {
+        let dir = tempfile::tempdir().unwrap();
+        // The background sleep holds the pipes after the shell is killed
+        let hooks = hooks(dir.path(), &["sleep 5 & sleep 5"], HookFailure::Fail);
+        let started = Instant::now();
+        let runs = hooks.run(HookEvent::TaskCompleted, &Value::Null);
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(runs[0].timed_out);
+        assert!(runs[0].failure().starts_with("timed out"));
+    }
+}
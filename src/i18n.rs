@@ -0,0 +1,295 @@
+//! Localized user-facing messages
+//!
+//! A small catalog of the messages people read most: CLI errors raised by
+//! `main`, plan validation errors and the next-step suggestion after
+//! `ie task done`. Each entry has an English and a Chinese text with `{name}`
+//! placeholders.
+//!
+//! The locale is picked once per process by [`Locale::resolve`]: the
+//! `IE_LOCALE` environment variable, then `[ui] locale` in the project config,
+//! then the usual `LC_ALL` / `LC_MESSAGES` / `LANG`. Anything that is not
+//! Chinese falls back to English.
+
+use crate::error::{IntentError, Result};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Languages the catalog has texts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Locale {
+    /// Language part of a POSIX locale name such as `zh_CN.UTF-8` or `en-US`
+    fn from_posix(name: &str) -> Option<Self> {
+        let language = name
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "zh" => Some(Locale::Zh),
+            _ => None,
+        }
+    }
+
+    /// Locale of this process, `configured` being the project's `[ui] locale`
+    pub fn resolve(configured: Option<Locale>) -> Self {
+        if let Ok(name) = std::env::var("IE_LOCALE") {
+            match name.parse() {
+                Ok(locale) => return locale,
+                Err(e) => tracing::warn!("Ignoring IE_LOCALE: {}", e),
+            }
+        }
+        if let Some(locale) = configured {
+            return locale;
+        }
+        // The first one set wins, as in setlocale(3)
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_posix(&value))
+            .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_posix(s).ok_or_else(|| {
+            IntentError::InvalidInput(format!("Invalid locale '{}'. Expected en or zh", s))
+        })
+    }
+}
+
+/// Set by `main` from [`Locale::resolve`]; see `set_locale`
+static LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Make every later [`t`] use `locale`
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Zh,
+        _ => Locale::En,
+    }
+}
+
+/// (key, English, Chinese)
+const CATALOG: &[(&str, &str, &str)] = &[
+    (
+        "main.console_utf8_failed",
+        "Warning: Failed to setup Windows console UTF-8: {error}",
+        "警告：无法将 Windows 控制台设置为 UTF-8：{error}",
+    ),
+    (
+        "main.console_utf8_hint",
+        "Chinese characters may not display correctly. Consider running 'chcp 65001' first.",
+        "中文字符可能无法正确显示，建议先运行 'chcp 65001'。",
+    ),
+    (
+        "main.logging_failed",
+        "Failed to initialize logging: {error}",
+        "日志系统初始化失败：{error}",
+    ),
+    (
+        "main.read_only",
+        "This command cannot run with --read-only",
+        "该命令无法在 --read-only 模式下运行",
+    ),
+    (
+        "main.backend_not_built",
+        "This project's config sets [storage] backend = \"{backend}\", but this build has no \
+         {product} support; rebuild with --features {backend}",
+        "项目配置设置了 [storage] backend = \"{backend}\"，但当前构建不支持 {product}；\
+         请使用 --features {backend} 重新构建",
+    ),
+    (
+        "plan.duplicate_names",
+        "Duplicate task names in request: {names}",
+        "请求中存在重复的任务名称：{names}",
+    ),
+    (
+        "plan.missing_name",
+        "Task {index} of the plan (in tree order) has neither a name nor an id",
+        "计划中第 {index} 个任务（按树的顺序）既没有名称也没有 id",
+    ),
+    (
+        "plan.unknown_dependency",
+        "Task '{task}' depends on '{dependency}', but '{dependency}' is not in the plan",
+        "任务 '{task}' 依赖 '{dependency}'，但 '{dependency}' 不在计划中",
+    ),
+    (
+        "plan.multiple_doing",
+        "Batch single doing constraint violated: only one task per batch can have \
+         status='doing'. Found: {names}",
+        "违反单个 doing 约束：每批计划只能有一个任务的 status='doing'。发现：{names}",
+    ),
+    (
+        "plan.self_dependency",
+        "Circular dependency detected: task '{task}' depends on itself",
+        "检测到循环依赖：任务 '{task}' 依赖自身",
+    ),
+    (
+        "plan.dependency_cycle",
+        "Circular dependency detected: {cycle}",
+        "检测到循环依赖：{cycle}",
+    ),
+    (
+        "next.parent_ready",
+        "All sub-tasks of parent #{parent_id} '{parent}' are now complete. The parent task \
+         is ready for your attention.",
+        "父任务 #{parent_id} '{parent}' 的所有子任务均已完成，可以处理父任务了。",
+    ),
+    (
+        "next.siblings_remain",
+        "Task #{id} completed. Parent task #{parent_id} '{parent}' has other sub-tasks remaining.",
+        "任务 #{id} 已完成。父任务 #{parent_id} '{parent}' 还有其他未完成的子任务。",
+    ),
+    (
+        "next.top_level_done",
+        "Top-level task #{id} '{name}' has been completed. Well done!",
+        "顶层任务 #{id} '{name}' 已完成，干得好！",
+    ),
+    (
+        "next.workspace_clear",
+        "Project complete! Task #{id} was the last remaining task. There are no more 'todo' \
+         or 'doing' tasks.",
+        "项目完成！任务 #{id} 是最后一个剩余任务，已没有 'todo' 或 'doing' 状态的任务。",
+    ),
+    (
+        "next.task_done",
+        "Task #{id} '{name}' has been completed.",
+        "任务 #{id} '{name}' 已完成。",
+    ),
+    (
+        "done.completed",
+        "Completed task #{id} '{name}'",
+        "已完成任务 #{id} '{name}'",
+    ),
+    (
+        "done.next_parent",
+        "Next: {message} (ie task start {parent_id})",
+        "下一步：{message}（ie task start {parent_id}）",
+    ),
+    (
+        "done.next_siblings",
+        "Next: {message} ({count} siblings remaining)",
+        "下一步：{message}（还剩 {count} 个同级任务）",
+    ),
+];
+
+/// Text of `key` in the process locale, with `{name}` placeholders filled from `args`
+pub fn t(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    t_in(locale(), key, args)
+}
+
+/// Text of `key` in `locale`
+///
+/// A key missing from the catalog comes back as itself, so a typo shows up
+/// in the output rather than as an empty message.
+pub fn t_in(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    match CATALOG.iter().find(|(k, _, _)| *k == key) {
+        Some((_, en, zh)) => fill(
+            match locale {
+                Locale::En => en,
+                Locale::Zh => zh,
+            },
+            args,
+        ),
+        None => key.to_string(),
+    }
+}
+
+/// Replace the `{name}` placeholders of `template` with `args`
+///
+/// Placeholders without an argument are left as they are. One pass, so a
+/// value containing `{...}` is never expanded again.
+pub fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (end, value))
+        });
+        match arg {
+            Some((end, value)) => {
+                out.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            },
+            None => {
+                out.push('{');
+                rest = after;
+            },
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalog_entries_are_consistent() {
+        let mut keys = BTreeSet::new();
+        for (key, en, zh) in CATALOG {
+            assert!(keys.insert(*key), "duplicate key {}", key);
+            assert!(!en.is_empty() && !zh.is_empty(), "{}", key);
+            assert_eq!(placeholders(en), placeholders(zh), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_t_in_fills_placeholders() {
+        let args: &[(&str, &dyn Display)] = &[("id", &7), ("name", &"Write {id} docs")];
+        assert_eq!(
+            t_in(Locale::En, "next.task_done", args),
+            "Task #7 'Write {id} docs' has been completed."
+        );
+        assert_eq!(
+            t_in(Locale::Zh, "next.task_done", args),
+            "任务 #7 'Write {id} docs' 已完成。"
+        );
+        assert_eq!(t_in(Locale::Zh, "no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn test_locale_names() {
+        for (name, expected) in [
+            ("zh", Locale::Zh),
+            ("zh_CN.UTF-8", Locale::Zh),
+            ("zh-TW", Locale::Zh),
+            ("en", Locale::En),
+            ("en_US.UTF-8", Locale::En),
+            ("C", Locale::En),
+        ] {
+            assert_eq!(name.parse::<Locale>().unwrap(), expected, "{}", name);
+        }
+        let err = "fr_FR".parse::<Locale>().unwrap_err().to_string();
+        assert!(err.contains("Invalid locale 'fr_FR'"), "{}", err);
+        assert!("".parse::<Locale>().is_err());
+    }
+}
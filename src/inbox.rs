@@ -0,0 +1,116 @@
+//! The human inbox: tasks waiting on a person
+//!
+//! Agents flag a task with `needs_human` (in plan input or with
+//! `ie task update --needs-human`) when they cannot go on without a decision,
+//! access or review. `ie inbox` lists those together with tasks awaiting
+//! review and unfinished human-owned tasks, most urgent first: flagged tasks
+//! (an agent is blocked), then tasks in review, then by how many unfinished
+//! tasks depend on them and by effective priority.
+
+use crate::db::models::InboxItem;
+use crate::error::Result;
+use crate::priority::EFFECTIVE_PRIORITY_SQL;
+use sqlx::SqlitePool;
+
+pub struct InboxManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> InboxManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Tasks waiting on a human, most urgent first
+    pub async fn list(&self, limit: i64) -> Result<Vec<InboxItem>> {
+        let sql = format!(
+            r#"
+            SELECT t.id AS task_id, t.name, t.status, t.owner, t.needs_human,
+                   {} AS effective_priority,
+                   (SELECT COUNT(*) FROM dependencies d
+                    JOIN tasks b ON b.id = d.blocked_task_id
+                    WHERE d.blocking_task_id = t.id AND b.status != 'done') AS blocking
+            FROM tasks t
+            WHERE t.status != 'done'
+              AND (t.needs_human IS NOT NULL OR t.status = 'review' OR t.owner = 'human')
+            ORDER BY t.needs_human IS NULL, t.status != 'review', blocking DESC,
+                     effective_priority, t.id
+            LIMIT ?
+            "#,
+            EFFECTIVE_PRIORITY_SQL
+        );
+        Ok(sqlx::query_as(&sql)
+            .bind(limit)
+            .fetch_all(self.pool)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::add_dependency;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_inbox_orders_by_urgency() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let ai = Some("ai");
+
+        let human = tasks
+            .add_task("Human chore", None, None, None, Some(4), None)
+            .await
+            .unwrap();
+        let gate = tasks
+            .add_task("Approve schema", None, None, None, Some(4), None)
+            .await
+            .unwrap();
+        let waiting = tasks
+            .add_task("Migrate data", None, None, ai, None, None)
+            .await
+            .unwrap();
+        add_dependency(pool, gate.id, waiting.id).await.unwrap();
+        let flagged = tasks
+            .add_task("Pick OAuth provider", None, None, ai, None, None)
+            .await
+            .unwrap();
+        tasks
+            .update_task(
+                flagged.id,
+                TaskUpdate {
+                    needs_human: Some("Google or GitHub?"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let finished = tasks
+            .add_task("Done already", None, None, None, None, None)
+            .await
+            .unwrap();
+        tasks.done_task_by_id(finished.id).await.unwrap();
+
+        let items = InboxManager::new(pool).list(10).await.unwrap();
+        let ids: Vec<i64> = items.iter().map(|i| i.task_id).collect();
+        assert_eq!(ids, vec![flagged.id, gate.id, human.id]);
+        assert_eq!(items[0].needs_human.as_deref(), Some("Google or GitHub?"));
+        assert_eq!(items[1].blocking, 1);
+
+        // An empty reason clears the flag
+        tasks
+            .update_task(
+                flagged.id,
+                TaskUpdate {
+                    needs_human: Some(""),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let items = InboxManager::new(pool).list(10).await.unwrap();
+        assert!(items.iter().all(|i| i.task_id != flagged.id));
+    }
+}
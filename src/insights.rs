@@ -0,0 +1,593 @@
+//! Project health: is this project on track?
+//!
+//! `ie insights` scores five aspects of the task tree from 0 (bad) to 100
+//! (healthy) and combines them into one weighted health score:
+//!
+//! - **blocked**: share of unfinished tasks with a blocked reason or an
+//!   unfinished blocker; half of them blocked scores 0
+//! - **stale_wip**: share of doing tasks idle for `stale.days` or more
+//!   (see [`crate::stale`])
+//! - **bottlenecks**: unfinished tasks that each hold up
+//!   [`BOTTLENECK_MIN_BLOCKED`] or more unfinished tasks; 25 points each
+//! - **missing_specs**: share of todo and doing tasks without a spec
+//! - **velocity**: tasks first completed in the last [`VELOCITY_WINDOW_DAYS`]
+//!   days against the window before
+//!
+//! Every insight names the command listing the tasks behind it
+//! (`ie insights <kind>`, [`InsightsManager::drill_down`]).
+
+use crate::db::models::TaskVisibility;
+use crate::error::{IntentError, Result};
+use crate::stale::StalePolicy;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// Unfinished tasks a task must hold up to count as a bottleneck
+pub const BOTTLENECK_MIN_BLOCKED: i64 = 3;
+
+/// Length of each of the two windows velocity compares
+pub const VELOCITY_WINDOW_DAYS: i64 = 28;
+
+/// One aspect of project health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsightKind {
+    Blocked,
+    StaleWip,
+    Bottlenecks,
+    MissingSpecs,
+    Velocity,
+}
+
+impl InsightKind {
+    pub const ALL: [InsightKind; 5] = [
+        InsightKind::Blocked,
+        InsightKind::StaleWip,
+        InsightKind::Bottlenecks,
+        InsightKind::MissingSpecs,
+        InsightKind::Velocity,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InsightKind::Blocked => "blocked",
+            InsightKind::StaleWip => "stale_wip",
+            InsightKind::Bottlenecks => "bottlenecks",
+            InsightKind::MissingSpecs => "missing_specs",
+            InsightKind::Velocity => "velocity",
+        }
+    }
+
+    /// Share of the health score, in percent
+    fn weight(self) -> u32 {
+        match self {
+            InsightKind::Blocked | InsightKind::Velocity => 25,
+            InsightKind::StaleWip => 20,
+            InsightKind::Bottlenecks | InsightKind::MissingSpecs => 15,
+        }
+    }
+}
+
+impl std::str::FromStr for InsightKind {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        InsightKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s.replace('-', "_"))
+            .ok_or_else(|| {
+                IntentError::InvalidInput(format!(
+                    "Invalid insight '{}'. Expected blocked, stale_wip, bottlenecks, missing_specs or velocity",
+                    s
+                ))
+            })
+    }
+}
+
+/// Overall verdict derived from the health score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthVerdict {
+    /// Score of 75 or more
+    OnTrack,
+    /// Score of 50 to 74
+    AtRisk,
+    /// Score below 50
+    OffTrack,
+}
+
+impl HealthVerdict {
+    fn from_score(score: u8) -> Self {
+        match score {
+            75.. => HealthVerdict::OnTrack,
+            50..=74 => HealthVerdict::AtRisk,
+            _ => HealthVerdict::OffTrack,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HealthVerdict::OnTrack => "on track",
+            HealthVerdict::AtRisk => "at risk",
+            HealthVerdict::OffTrack => "off track",
+        }
+    }
+}
+
+/// Score and figures of one insight
+#[derive(Debug, Clone, Serialize)]
+pub struct Insight {
+    pub kind: InsightKind,
+    /// 0 (bad) to 100 (healthy)
+    pub score: u8,
+    /// Tasks the insight counts (velocity: completions in the last window)
+    pub count: i64,
+    /// What `count` is measured against: unfinished tasks (blocked), doing
+    /// tasks (stale_wip), unfinished tasks (bottlenecks), todo and doing
+    /// tasks (missing_specs), completions in the window before (velocity)
+    pub out_of: i64,
+    pub summary: String,
+    /// Command listing the tasks behind the insight
+    pub drill_down: String,
+}
+
+/// Health of the project as a whole
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Weighted mean of the insight scores, 0 to 100
+    pub score: u8,
+    pub verdict: HealthVerdict,
+    pub generated_at: DateTime<Utc>,
+    pub insights: Vec<Insight>,
+}
+
+/// A task behind an insight
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow)]
+pub struct InsightTask {
+    pub id: i64,
+    pub name: String,
+    pub status: String,
+    pub owner: String,
+    /// Why the task is listed: blocker, idle time, completion date, ...
+    pub detail: String,
+}
+
+pub struct InsightsManager<'a> {
+    pool: &'a SqlitePool,
+    /// Whether private tasks count
+    scope: TaskVisibility,
+}
+
+impl<'a> InsightsManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self {
+            pool,
+            scope: crate::project::task_scope(),
+        }
+    }
+
+    /// Use an explicit task scope instead of the process-wide `--scope`
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Score every insight as of now
+    pub async fn report(&self) -> Result<HealthReport> {
+        self.report_at(Utc::now()).await
+    }
+
+    /// Score every insight as of `now`
+    pub async fn report_at(&self, now: DateTime<Utc>) -> Result<HealthReport> {
+        let (unfinished, open, doing): (i64, i64, i64) = sqlx::query_as(&format!(
+            r#"
+            SELECT COUNT(*),
+                   COALESCE(SUM(status IN ('todo', 'doing')), 0),
+                   COALESCE(SUM(status = 'doing'), 0)
+            FROM tasks WHERE status != 'done' AND {}
+            "#,
+            self.scope.sql_filter()
+        ))
+        .fetch_one(self.pool)
+        .await?;
+
+        let mut insights = Vec::new();
+        for kind in InsightKind::ALL {
+            let count = self.drill_down_at(kind, now).await?.len() as i64;
+            let drill_down = format!("ie insights {}", kind.as_str());
+            let insight = match kind {
+                InsightKind::Blocked => Insight {
+                    kind,
+                    score: share_score(count, unfinished, 2.0),
+                    count,
+                    out_of: unfinished,
+                    summary: format!("{} of {} unfinished tasks are blocked", count, unfinished),
+                    drill_down,
+                },
+                InsightKind::StaleWip => {
+                    let policy = StalePolicy::load(self.pool).await?;
+                    let summary = if policy.is_off() {
+                        "Stale detection is off (stale.days = 0)".to_string()
+                    } else {
+                        format!(
+                            "{} of {} doing tasks idle for {} days or more",
+                            count, doing, policy.days
+                        )
+                    };
+                    Insight {
+                        kind,
+                        score: share_score(count, doing, 1.0),
+                        count,
+                        out_of: doing,
+                        summary,
+                        drill_down,
+                    }
+                },
+                InsightKind::Bottlenecks => Insight {
+                    kind,
+                    score: 100u8.saturating_sub((count.min(4) * 25) as u8),
+                    count,
+                    out_of: unfinished,
+                    summary: format!(
+                        "{} tasks each hold up {} or more unfinished tasks",
+                        count, BOTTLENECK_MIN_BLOCKED
+                    ),
+                    drill_down,
+                },
+                InsightKind::MissingSpecs => Insight {
+                    kind,
+                    score: share_score(count, open, 1.0),
+                    count,
+                    out_of: open,
+                    summary: format!("{} of {} todo and doing tasks have no spec", count, open),
+                    drill_down,
+                },
+                InsightKind::Velocity => {
+                    let previous = self.completed_between(now, 2).await?;
+                    let score = match (count, previous) {
+                        (_, 0) if count > 0 || unfinished == 0 => 100,
+                        // Nothing finished for two windows with work left
+                        (_, 0) => 50,
+                        _ => (100.0 * (count as f64 / previous as f64).min(1.0)).round() as u8,
+                    };
+                    Insight {
+                        kind,
+                        score,
+                        count,
+                        out_of: previous,
+                        summary: format!(
+                            "{} done in the last {} days, {} in the {} days before",
+                            count, VELOCITY_WINDOW_DAYS, previous, VELOCITY_WINDOW_DAYS
+                        ),
+                        drill_down,
+                    }
+                },
+            };
+            insights.push(insight);
+        }
+
+        let score = (insights
+            .iter()
+            .map(|i| u32::from(i.score) * i.kind.weight())
+            .sum::<u32>() as f64
+            / 100.0)
+            .round() as u8;
+        Ok(HealthReport {
+            score,
+            verdict: HealthVerdict::from_score(score),
+            generated_at: now,
+            insights,
+        })
+    }
+
+    /// Tasks behind an insight, worst first
+    pub async fn drill_down(&self, kind: InsightKind) -> Result<Vec<InsightTask>> {
+        self.drill_down_at(kind, Utc::now()).await
+    }
+
+    /// Tasks behind an insight as of `now`, worst first
+    pub async fn drill_down_at(
+        &self,
+        kind: InsightKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<InsightTask>> {
+        let filter = self.scope.sql_filter();
+        let tasks = match kind {
+            InsightKind::Blocked => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT id, name, status, owner,
+                           COALESCE('blocked: ' || blocked_reason, 'waiting on ' || blockers) AS detail
+                    FROM (
+                        SELECT t.id, t.name, t.status, t.owner, t.blocked_reason,
+                               (SELECT GROUP_CONCAT('#' || d.blocking_task_id, ', ')
+                                FROM dependencies d JOIN tasks b ON b.id = d.blocking_task_id
+                                WHERE d.blocked_task_id = t.id AND b.status != 'done') AS blockers
+                        FROM tasks t
+                        WHERE t.status != 'done' AND {}
+                    )
+                    WHERE blocked_reason IS NOT NULL OR blockers IS NOT NULL
+                    ORDER BY id
+                    "#,
+                    filter
+                ))
+                .fetch_all(self.pool)
+                .await?
+            },
+            InsightKind::StaleWip => self.stale_wip(now).await?,
+            InsightKind::Bottlenecks => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT id, name, status, owner,
+                           'holds up ' || held_up || ' unfinished tasks' AS detail
+                    FROM (
+                        SELECT t.id, t.name, t.status, t.owner,
+                               (SELECT COUNT(*) FROM dependencies d
+                                JOIN tasks b ON b.id = d.blocked_task_id
+                                WHERE d.blocking_task_id = t.id AND b.status != 'done') AS held_up
+                        FROM tasks t
+                        WHERE t.status != 'done' AND {}
+                    )
+                    WHERE held_up >= ?
+                    ORDER BY held_up DESC, id
+                    "#,
+                    filter
+                ))
+                .bind(BOTTLENECK_MIN_BLOCKED)
+                .fetch_all(self.pool)
+                .await?
+            },
+            InsightKind::MissingSpecs => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT id, name, status, owner, 'no spec' AS detail
+                    FROM tasks
+                    WHERE status IN ('todo', 'doing') AND TRIM(COALESCE(spec, '')) = ''
+                      AND {}
+                    ORDER BY status = 'todo', id
+                    "#,
+                    filter
+                ))
+                .fetch_all(self.pool)
+                .await?
+            },
+            InsightKind::Velocity => {
+                sqlx::query_as(&format!(
+                    r#"
+                    SELECT id, name, status, owner,
+                           'done ' || strftime('%Y-%m-%d', first_done_at) AS detail
+                    FROM tasks
+                    WHERE first_done_at > ? AND first_done_at <= ? AND {}
+                    ORDER BY first_done_at DESC, id
+                    "#,
+                    filter
+                ))
+                .bind(now - Duration::days(VELOCITY_WINDOW_DAYS))
+                .bind(now)
+                .fetch_all(self.pool)
+                .await?
+            },
+        };
+        Ok(tasks)
+    }
+
+    /// Tasks first completed in the `window`-th window back from `now`
+    /// (1 = the last [`VELOCITY_WINDOW_DAYS`] days)
+    async fn completed_between(&self, now: DateTime<Utc>, window: i64) -> Result<i64> {
+        Ok(sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM tasks WHERE first_done_at > ? AND first_done_at <= ? AND {}",
+            self.scope.sql_filter()
+        ))
+        .bind(now - Duration::days(VELOCITY_WINDOW_DAYS * window))
+        .bind(now - Duration::days(VELOCITY_WINDOW_DAYS * (window - 1)))
+        .fetch_one(self.pool)
+        .await?)
+    }
+
+    /// Doing tasks idle for `stale.days` or more, longest idle first
+    ///
+    /// Same rule as the stale sweep, without flagging anything.
+    async fn stale_wip(&self, now: DateTime<Utc>) -> Result<Vec<InsightTask>> {
+        let policy = StalePolicy::load(self.pool).await?;
+        if policy.is_off() {
+            return Ok(Vec::new());
+        }
+
+        type Row = (
+            i64,
+            String,
+            String,
+            String,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+        );
+        let rows: Vec<Row> = sqlx::query_as(&format!(
+            r#"
+            SELECT t.id, t.name, t.status, t.owner, t.first_doing_at,
+                (SELECT MAX(e.timestamp) FROM events e
+                 WHERE e.task_id = t.id AND e.log_type != ?),
+                (SELECT MAX(tr.changed_at) FROM task_transitions tr
+                 WHERE tr.task_id = t.id AND tr.to_status = 'doing')
+            FROM tasks t
+            WHERE t.status = 'doing' AND {}
+            "#,
+            self.scope.sql_filter()
+        ))
+        .bind(crate::stale::STALE_LOG_TYPE)
+        .fetch_all(self.pool)
+        .await?;
+
+        let threshold = Duration::seconds((policy.days * 86_400.0) as i64);
+        let mut stale: Vec<(DateTime<Utc>, InsightTask)> = rows
+            .into_iter()
+            .filter_map(
+                |(id, name, status, owner, first_doing, last_event, last_doing)| {
+                    let last_activity = [first_doing, last_event, last_doing]
+                        .into_iter()
+                        .flatten()
+                        .max()?;
+                    let idle = now - last_activity;
+                    (idle >= threshold).then(|| {
+                        let days = idle.num_seconds() as f64 / 86_400.0;
+                        let detail = format!("idle {:.1} days", days);
+                        let task = InsightTask {
+                            id,
+                            name,
+                            status,
+                            owner,
+                            detail,
+                        };
+                        (last_activity, task)
+                    })
+                },
+            )
+            .collect();
+        stale.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.id.cmp(&b.1.id)));
+        Ok(stale.into_iter().map(|(_, task)| task).collect())
+    }
+}
+
+/// 100 when `count` is none of `total`, falling linearly to 0 when it is
+/// `1 / severity` of it
+fn share_score(count: i64, total: i64, severity: f64) -> u8 {
+    if total == 0 {
+        return 100;
+    }
+    let share = (count as f64 / total as f64 * severity).min(1.0);
+    (100.0 * (1.0 - share)).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::add_dependency;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[test]
+    fn test_scores() {
+        assert_eq!(share_score(0, 0, 2.0), 100);
+        assert_eq!(share_score(1, 4, 2.0), 50);
+        assert_eq!(share_score(3, 4, 2.0), 0);
+        assert_eq!(share_score(1, 4, 1.0), 75);
+        assert_eq!(HealthVerdict::from_score(75), HealthVerdict::OnTrack);
+        assert_eq!(HealthVerdict::from_score(74), HealthVerdict::AtRisk);
+        assert_eq!(HealthVerdict::from_score(49), HealthVerdict::OffTrack);
+        assert_eq!(
+            "stale-wip".parse::<InsightKind>().unwrap(),
+            InsightKind::StaleWip
+        );
+        assert!("speed".parse::<InsightKind>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_empty_project_is_on_track() {
+        let ctx = TestContext::new().await;
+        let report = InsightsManager::new(ctx.pool()).report().await.unwrap();
+        assert_eq!(report.score, 100);
+        assert_eq!(report.verdict, HealthVerdict::OnTrack);
+        assert_eq!(report.insights.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_insights_and_drill_down() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let spec = Some("spec");
+
+        let schema = tasks
+            .add_task("Schema", spec, None, None, None, None)
+            .await
+            .unwrap();
+        let mut waiting = Vec::new();
+        for name in ["API", "Import", "Reports"] {
+            let task = tasks
+                .add_task(name, spec, None, None, None, None)
+                .await
+                .unwrap();
+            add_dependency(pool, schema.id, task.id).await.unwrap();
+            waiting.push(task.id);
+        }
+        let vendor = tasks
+            .add_task("Vendor access", None, None, None, None, None)
+            .await
+            .unwrap();
+        tasks
+            .update_task(
+                vendor.id,
+                TaskUpdate {
+                    blocked_reason: Some("Waiting on legal"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        tasks.start_task(schema.id, false).await.unwrap();
+
+        // Two tasks finished 5 and 40 days ago, one 50 days ago
+        let now = Utc::now() + Duration::days(30);
+        for (name, days_ago) in [("Setup", 5), ("CI", 40), ("Repo", 50)] {
+            let task = tasks
+                .add_task(name, spec, None, None, None, None)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE tasks SET status = 'done', first_done_at = ? WHERE id = ?")
+                .bind(now - Duration::days(days_ago))
+                .bind(task.id)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+
+        let insights = InsightsManager::new(pool);
+        let report = insights.report_at(now).await.unwrap();
+        let get = |kind| report.insights.iter().find(|i| i.kind == kind).unwrap();
+
+        // API, Import, Reports wait on Schema; Vendor access is flagged
+        let blocked = get(InsightKind::Blocked);
+        assert_eq!((blocked.count, blocked.out_of), (4, 5));
+        assert_eq!(blocked.score, 0);
+        // Schema has been doing for 30 days without activity
+        assert_eq!(get(InsightKind::StaleWip).count, 1);
+        assert_eq!(get(InsightKind::StaleWip).score, 0);
+        assert_eq!(get(InsightKind::Bottlenecks).score, 75);
+        let specs = get(InsightKind::MissingSpecs);
+        assert_eq!((specs.count, specs.out_of, specs.score), (1, 5, 80));
+        let velocity = get(InsightKind::Velocity);
+        assert_eq!(
+            (velocity.count, velocity.out_of, velocity.score),
+            (1, 2, 50)
+        );
+        assert_eq!(velocity.drill_down, "ie insights velocity");
+        // 0*25 + 0*20 + 75*15 + 80*15 + 50*25 over 100
+        assert_eq!(report.score, 36);
+        assert_eq!(report.verdict, HealthVerdict::OffTrack);
+
+        let blocked = insights
+            .drill_down_at(InsightKind::Blocked, now)
+            .await
+            .unwrap();
+        assert_eq!(blocked[0].detail, format!("waiting on #{}", schema.id));
+        assert_eq!(blocked[3].detail, "blocked: Waiting on legal");
+        let bottlenecks = insights
+            .drill_down_at(InsightKind::Bottlenecks, now)
+            .await
+            .unwrap();
+        assert_eq!(bottlenecks[0].id, schema.id);
+        assert_eq!(bottlenecks[0].detail, "holds up 3 unfinished tasks");
+        let stale = insights
+            .drill_down_at(InsightKind::StaleWip, now)
+            .await
+            .unwrap();
+        assert!(stale[0].detail.starts_with("idle 30.0 days"));
+        let done = insights
+            .drill_down_at(InsightKind::Velocity, now)
+            .await
+            .unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].name, "Setup");
+    }
+}
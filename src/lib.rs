@@ -1,31 +1,65 @@
+pub mod anonymize;
+pub mod archive;
 pub mod backend;
+pub mod bootstrap;
+pub mod checklist;
 pub mod cli;
 pub mod cli_handlers;
+pub mod compaction;
+pub mod config;
 pub mod dashboard;
 pub mod db;
 pub mod dependencies;
+pub mod doctor;
 pub mod error;
+pub mod event_types;
 pub mod events;
+pub mod focus;
 pub mod global_projects;
+pub mod handoff;
+pub mod hooks;
+pub mod i18n;
+pub mod inbox;
+pub mod insights;
+pub mod links;
 pub mod llm;
 pub mod logging;
 pub mod logs;
 pub mod notifications;
+pub mod otel;
+pub mod output;
 pub mod plan;
+pub mod plan_journal;
 pub mod plan_validation;
+pub mod plan_workspace;
+pub mod policy;
 pub mod priority;
 pub mod project;
 pub mod report;
 pub mod search;
+pub mod seed;
 pub mod session_restore;
+pub mod settings;
+pub mod similarity;
+pub mod spec_meta;
 pub mod sql_constants;
+pub mod stale;
+pub mod sync;
 pub mod tasks;
+pub mod text_diff;
 pub mod time_utils;
+pub mod timing;
+pub mod tui;
+pub mod watch;
 pub mod windows_console;
+pub mod wip;
 pub mod workspace;
 
 #[cfg(feature = "neo4j")]
 pub mod neo4j;
 
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
 #[cfg(test)]
 pub mod test_utils;
@@ -0,0 +1,808 @@
+//! Task links: URLs and documents a task refers to
+//!
+//! Specs used to collect raw URLs that could be neither listed nor checked.
+//! Links live in `task_links`, one row per task and URL with an optional title
+//! and a kind (`doc`, `issue`, `pr`, `design` or `other`; guessed from the URL
+//! when not given). A plan sets a task's links with `"links"`, given as URLs
+//! or `{"url", "title", "kind"}` objects; a restated list replaces the
+//! previous one and `[]` removes them all. `ie links add/remove/list` edit
+//! them one at a time. Task context (`ie task get --with-context`) and
+//! archives include them.
+//!
+//! Two passes keep links useful in long-lived projects. [`extract`] (run by
+//! `ie db maintain` and the Dashboard's periodic maintenance) links the URLs
+//! that specs and events mention to their tasks. [`LinkChecker`] (`ie links
+//! check`) requests every URL, looks up every repository path, and records
+//! the result on the link so that dead references stand out.
+
+use crate::db::models::TaskLink;
+use crate::error::{IntentError, Result};
+use chrono::Utc;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const LINK_COLUMNS: &str =
+    "id, task_id, url, title, kind, created_at, last_checked_at, last_status, last_error";
+
+/// Highest event ID already scanned by [`extract`]
+const EXTRACTED_EVENT_KEY: &str = "links.extracted_event_id";
+
+/// Links checked at the same time by [`LinkChecker`]
+const CHECK_CONCURRENCY: usize = 8;
+
+/// What a link points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    /// Documentation, specs, design docs in the repository
+    Doc,
+    /// Issue or ticket
+    Issue,
+    /// Pull or merge request
+    Pr,
+    /// Mockups and other design files
+    Design,
+    Other,
+}
+
+impl LinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LinkKind::Doc => "doc",
+            LinkKind::Issue => "issue",
+            LinkKind::Pr => "pr",
+            LinkKind::Design => "design",
+            LinkKind::Other => "other",
+        }
+    }
+
+    /// Kind suggested by the shape of a URL or path
+    pub fn guess(url: &str) -> Self {
+        let lower = url.to_lowercase();
+        if ["/pull/", "/merge_requests/", "/pull-requests/"]
+            .iter()
+            .any(|p| lower.contains(p))
+        {
+            LinkKind::Pr
+        } else if ["/issues/", "/browse/"].iter().any(|p| lower.contains(p)) {
+            LinkKind::Issue
+        } else if ["figma.com/", "miro.com/", "excalidraw.com/"]
+            .iter()
+            .any(|p| lower.contains(p))
+        {
+            LinkKind::Design
+        } else if !has_scheme(&lower)
+            || [".md", ".pdf", ".txt", ".adoc", ".rst"]
+                .iter()
+                .any(|ext| lower.ends_with(ext))
+            || ["docs.google.com/", "notion.so/", "/wiki/"]
+                .iter()
+                .any(|p| lower.contains(p))
+        {
+            LinkKind::Doc
+        } else {
+            LinkKind::Other
+        }
+    }
+}
+
+impl std::str::FromStr for LinkKind {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "doc" => Ok(Self::Doc),
+            "issue" => Ok(Self::Issue),
+            "pr" => Ok(Self::Pr),
+            "design" => Ok(Self::Design),
+            "other" => Ok(Self::Other),
+            other => Err(IntentError::InvalidInput(format!(
+                "Invalid link kind '{}'. Expected doc, issue, pr, design or other",
+                other
+            ))),
+        }
+    }
+}
+
+/// A link as a plan gives it: a bare URL or an object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LinkInput {
+    Url(String),
+    Full {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        kind: Option<String>,
+    },
+}
+
+impl LinkInput {
+    pub fn url(&self) -> &str {
+        match self {
+            LinkInput::Url(url) | LinkInput::Full { url, .. } => url.trim(),
+        }
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            LinkInput::Url(_) => None,
+            LinkInput::Full { title, .. } => {
+                title.as_deref().map(str::trim).filter(|t| !t.is_empty())
+            },
+        }
+    }
+
+    /// The given kind, else the one guessed from the URL
+    pub fn kind(&self) -> Result<LinkKind> {
+        match self {
+            LinkInput::Full {
+                kind: Some(kind), ..
+            } => kind.parse(),
+            _ => Ok(LinkKind::guess(self.url())),
+        }
+    }
+
+    /// Why the link cannot be stored, if it cannot
+    pub fn error(&self) -> Option<String> {
+        if let Err(e) = check_url(self.url()) {
+            return Some(e.to_string());
+        }
+        self.kind().err().map(|e| e.to_string())
+    }
+}
+
+/// Whether `url` starts with a URL scheme (`https:`, `mailto:`, ...) rather
+/// than being a path; a single letter is a Windows drive, not a scheme
+fn has_scheme(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// Reject targets that are not a single URL or path
+pub fn check_url(url: &str) -> Result<()> {
+    let invalid = |why: &str| {
+        Err(IntentError::InvalidInput(format!(
+            "Invalid link '{}': {}",
+            url, why
+        )))
+    };
+    if url.is_empty() {
+        return invalid("empty");
+    }
+    if url.chars().any(char::is_whitespace) {
+        return invalid("contains whitespace");
+    }
+    if let Some((scheme, rest)) = url.split_once("://") {
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return invalid("malformed scheme");
+        }
+        if rest.is_empty() {
+            return invalid("nothing after the scheme");
+        }
+    }
+    Ok(())
+}
+
+pub struct LinkManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> LinkManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Links of a task, oldest first
+    pub async fn list(&self, task_id: i64) -> Result<Vec<TaskLink>> {
+        let mut conn = self.pool.acquire().await?;
+        ensure_task(&mut conn, task_id).await?;
+        list_in_tx(&mut conn, task_id).await
+    }
+
+    /// Links of every task, by task
+    pub async fn list_all(&self) -> Result<Vec<TaskLink>> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {} FROM task_links ORDER BY task_id, id",
+            LINK_COLUMNS
+        ))
+        .fetch_all(self.pool)
+        .await?)
+    }
+
+    /// Link a task to a URL; linking the same URL again updates title and kind
+    pub async fn add(&self, task_id: i64, link: &LinkInput) -> Result<TaskLink> {
+        let mut conn = self.pool.acquire().await?;
+        ensure_task(&mut conn, task_id).await?;
+        upsert(&mut conn, task_id, link).await?;
+        Ok(sqlx::query_as(&format!(
+            "SELECT {} FROM task_links WHERE task_id = ? AND url = ?",
+            LINK_COLUMNS
+        ))
+        .bind(task_id)
+        .bind(link.url())
+        .fetch_one(&mut *conn)
+        .await?)
+    }
+
+    /// Remove a task's link to `url`
+    pub async fn remove(&self, task_id: i64, url: &str) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        ensure_task(&mut conn, task_id).await?;
+        let result = sqlx::query("DELETE FROM task_links WHERE task_id = ? AND url = ?")
+            .bind(task_id)
+            .bind(url.trim())
+            .execute(&mut *conn)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(IntentError::InvalidInput(format!(
+                "Task #{} has no link to '{}'",
+                task_id,
+                url.trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+async fn ensure_task(conn: &mut SqliteConnection, task_id: i64) -> Result<()> {
+    let exists: bool = sqlx::query_scalar(crate::sql_constants::CHECK_TASK_EXISTS)
+        .bind(task_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    if !exists {
+        return Err(IntentError::TaskNotFound(task_id));
+    }
+    Ok(())
+}
+
+async fn upsert(conn: &mut SqliteConnection, task_id: i64, link: &LinkInput) -> Result<()> {
+    check_url(link.url())?;
+    let kind = link.kind()?;
+    sqlx::query(
+        r#"
+        INSERT INTO task_links (task_id, url, title, kind, created_at) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (task_id, url) DO UPDATE SET title = excluded.title, kind = excluded.kind
+        "#,
+    )
+    .bind(task_id)
+    .bind(link.url())
+    .bind(link.title())
+    .bind(kind.as_str())
+    .bind(Utc::now())
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Links of a task, oldest first, within a transaction
+pub async fn list_in_tx(conn: &mut SqliteConnection, task_id: i64) -> Result<Vec<TaskLink>> {
+    Ok(sqlx::query_as(&format!(
+        "SELECT {} FROM task_links WHERE task_id = ? ORDER BY id",
+        LINK_COLUMNS
+    ))
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+/// Replace a task's links within a transaction
+///
+/// URLs already linked keep their row (and creation time); an empty list
+/// removes every link.
+pub async fn set_in_tx(
+    conn: &mut SqliteConnection,
+    task_id: i64,
+    links: &[LinkInput],
+) -> Result<()> {
+    let keep: Vec<&str> = links.iter().map(LinkInput::url).collect();
+    let existing: Vec<String> = sqlx::query_scalar("SELECT url FROM task_links WHERE task_id = ?")
+        .bind(task_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    for url in existing.iter().filter(|url| !keep.contains(&url.as_str())) {
+        sqlx::query("DELETE FROM task_links WHERE task_id = ? AND url = ?")
+            .bind(task_id)
+            .bind(url)
+            .execute(&mut *conn)
+            .await?;
+    }
+    for link in links {
+        upsert(conn, task_id, link).await?;
+    }
+    Ok(())
+}
+
+/// Absolute http(s) URLs in free text, in order of appearance, without duplicates
+///
+/// A URL ends at whitespace, quotes or angle brackets. Trailing punctuation
+/// and closing brackets without an opening one inside the URL are left out,
+/// so Markdown links and sentences ending in a URL give the bare URL.
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`' | '|'))
+            .unwrap_or(candidate.len());
+        let mut url = &candidate[..end];
+        loop {
+            let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '*']);
+            let trimmed = match trimmed.chars().last() {
+                Some(close @ (')' | ']')) => {
+                    let open = if close == ')' { '(' } else { '[' };
+                    if trimmed.matches(open).count() < trimmed.matches(close).count() {
+                        &trimmed[..trimmed.len() - 1]
+                    } else {
+                        trimmed
+                    }
+                },
+                _ => trimmed,
+            };
+            if trimmed.len() == url.len() {
+                break;
+            }
+            url = trimmed;
+        }
+        if !url.ends_with("://") && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+        rest = &candidate[end..];
+    }
+    urls
+}
+
+/// What one [`extract`] run found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ExtractReport {
+    /// Specs that mention a URL
+    pub specs_scanned: usize,
+    /// Events scanned since the previous run that mention a URL
+    pub events_scanned: usize,
+    /// Links created
+    pub added: usize,
+}
+
+/// Link the URLs that task specs and events mention to their tasks
+///
+/// Specs are scanned in full; events only past the highest ID scanned by the
+/// previous run, and private events never. URLs a task already links keep
+/// their title and kind, so a link removed with `ie links remove` comes back
+/// as long as the spec still mentions it.
+pub async fn extract(pool: &SqlitePool) -> Result<ExtractReport> {
+    let mut tx = pool.begin().await?;
+    let mut report = ExtractReport::default();
+
+    let specs: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, spec FROM tasks WHERE spec LIKE '%http%' ORDER BY id")
+            .fetch_all(&mut *tx)
+            .await?;
+    report.specs_scanned = specs.len();
+
+    let since: i64 =
+        sqlx::query_scalar::<_, String>("SELECT value FROM workspace_state WHERE key = ?")
+            .bind(EXTRACTED_EVENT_KEY)
+            .fetch_optional(&mut *tx)
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+    let last_event: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(id), 0) FROM events")
+        .fetch_one(&mut *tx)
+        .await?;
+    let events: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT task_id, discussion_data FROM events
+        WHERE id > ? AND visibility != 'private' AND discussion_data LIKE '%http%'
+        ORDER BY id
+        "#,
+    )
+    .bind(since)
+    .fetch_all(&mut *tx)
+    .await?;
+    report.events_scanned = events.len();
+
+    for (task_id, text) in specs.iter().chain(&events) {
+        for url in find_urls(text) {
+            let inserted = sqlx::query(
+                "INSERT OR IGNORE INTO task_links (task_id, url, kind, created_at) VALUES (?, ?, ?, ?)",
+            )
+            .bind(task_id)
+            .bind(&url)
+            .bind(LinkKind::guess(&url).as_str())
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+            report.added += inserted.rows_affected() as usize;
+        }
+    }
+
+    sqlx::query(
+        "INSERT INTO workspace_state (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )
+    .bind(EXTRACTED_EVENT_KEY)
+    .bind(last_event.to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(report)
+}
+
+/// Outcome of checking one link
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Probe {
+    /// An HTTP response came back
+    Status(u16),
+    /// The repository path exists
+    Found,
+    /// No response, or no such file
+    Failed(String),
+}
+
+/// Outcome of an `ie links check` run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckReport {
+    /// Links requested or looked up
+    pub checked: usize,
+    /// Links that cannot be checked (other schemes, paths outside a project)
+    pub skipped: usize,
+    /// Checked links found dead, with the recorded result
+    pub dead: Vec<TaskLink>,
+}
+
+/// Checks that links still lead somewhere and records the result
+pub struct LinkChecker {
+    client: reqwest::Client,
+    /// Directory relative paths are resolved against; None skips them
+    root: Option<PathBuf>,
+}
+
+impl LinkChecker {
+    pub fn new(timeout: Duration, root: Option<PathBuf>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("intent-engine/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| IntentError::OtherError(e.into()))?;
+        Ok(Self { client, root })
+    }
+
+    /// Check the links of one task, or of every task
+    ///
+    /// http(s) URLs are requested with HEAD (GET when HEAD is not allowed),
+    /// following redirects; paths are looked up under the project root. Each
+    /// checked link gets `last_checked_at`, `last_status` and `last_error`.
+    pub async fn check(&self, pool: &SqlitePool, task_id: Option<i64>) -> Result<CheckReport> {
+        let manager = LinkManager::new(pool);
+        let links = match task_id {
+            Some(id) => manager.list(id).await?,
+            None => manager.list_all().await?,
+        };
+
+        let probes: Vec<(TaskLink, Option<Probe>)> = futures_util::stream::iter(links)
+            .map(|link| async move {
+                let probe = self.probe(&link.url).await;
+                (link, probe)
+            })
+            .buffered(CHECK_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut report = CheckReport::default();
+        let checked_at = Utc::now();
+        for (mut link, probe) in probes {
+            let Some(probe) = probe else {
+                report.skipped += 1;
+                continue;
+            };
+            (link.last_status, link.last_error) = match probe {
+                Probe::Status(status) => (Some(i64::from(status)), None),
+                Probe::Found => (None, None),
+                Probe::Failed(error) => (None, Some(error)),
+            };
+            link.last_checked_at = Some(checked_at);
+            sqlx::query(
+                "UPDATE task_links SET last_checked_at = ?, last_status = ?, last_error = ? WHERE id = ?",
+            )
+            .bind(checked_at)
+            .bind(link.last_status)
+            .bind(&link.last_error)
+            .bind(link.id)
+            .execute(pool)
+            .await?;
+            report.checked += 1;
+            if link.is_dead() {
+                report.dead.push(link);
+            }
+        }
+        Ok(report)
+    }
+
+    /// None when the link cannot be checked
+    async fn probe(&self, url: &str) -> Option<Probe> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Some(self.request(url).await);
+        }
+        if has_scheme(url) {
+            return None;
+        }
+        let path = url.split(['#', '?']).next().unwrap_or(url);
+        let path = match &self.root {
+            _ if std::path::Path::new(path).is_absolute() => PathBuf::from(path),
+            Some(root) => root.join(path),
+            None => return None,
+        };
+        Some(if path.exists() {
+            Probe::Found
+        } else {
+            Probe::Failed(format!("No such file: {}", path.display()))
+        })
+    }
+
+    async fn request(&self, url: &str) -> Probe {
+        let status = match self.client.head(url).send().await {
+            Ok(response)
+                if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
+                    || response.status() == reqwest::StatusCode::NOT_IMPLEMENTED =>
+            {
+                self.client.get(url).send().await.map(|r| r.status())
+            },
+            other => other.map(|r| r.status()),
+        };
+        match status {
+            Ok(status) => Probe::Status(status.as_u16()),
+            Err(e) if e.is_timeout() => Probe::Failed("Timed out".to_string()),
+            Err(e) if e.is_connect() => Probe::Failed("Could not connect".to_string()),
+            Err(e) => Probe::Failed(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::{PlanExecutor, PlanRequest};
+    use crate::test_utils::test_helpers::TestContext;
+
+    fn plan(json: &str) -> PlanRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_guess_kind() {
+        let cases = [
+            ("https://github.com/org/repo/issues/12", LinkKind::Issue),
+            ("https://jira.example.com/browse/OPS-7", LinkKind::Issue),
+            ("https://github.com/org/repo/pull/3", LinkKind::Pr),
+            ("https://www.figma.com/file/abc", LinkKind::Design),
+            ("docs/design.md", LinkKind::Doc),
+            ("https://example.com/spec.pdf", LinkKind::Doc),
+            ("https://example.com", LinkKind::Other),
+            ("mailto:team@example.com", LinkKind::Other),
+            ("C:\\notes\\plan.txt", LinkKind::Doc),
+        ];
+        for (url, kind) in cases {
+            assert_eq!(LinkKind::guess(url), kind, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_check_url() {
+        assert!(check_url("https://example.com/a?b=c").is_ok());
+        assert!(check_url("docs/adr/0001.md").is_ok());
+        assert!(check_url("").is_err());
+        assert!(check_url("see https://example.com").is_err());
+        assert!(check_url("://example.com").is_err());
+        assert!(check_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_find_urls() {
+        let text = "See [the RFC](https://example.com/rfc(1)) and https://example.com/a.\n\
+                    Also <http://x.org/b?c=d>, \"https://example.com/a\" and https://.";
+        assert_eq!(
+            find_urls(text),
+            vec![
+                "https://example.com/rfc(1)",
+                "https://example.com/a",
+                "http://x.org/b?c=d"
+            ]
+        );
+        assert!(find_urls("no links here").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extract_links_from_specs_and_events() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let task_mgr = crate::tasks::TaskManager::new(pool);
+        let task = task_mgr
+            .add_task(
+                "Auth",
+                Some("Follow https://github.com/org/repo/issues/12."),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let events = crate::events::EventManager::new(pool);
+        events
+            .add_event(
+                task.id,
+                "note",
+                "Reviewed in https://github.com/org/repo/pull/3",
+            )
+            .await
+            .unwrap();
+
+        let report = extract(pool).await.unwrap();
+        assert_eq!(report.added, 2);
+        let links = LinkManager::new(pool).list(task.id).await.unwrap();
+        assert_eq!(links[0].url, "https://github.com/org/repo/issues/12");
+        assert_eq!(links[0].kind, "issue");
+        assert_eq!(links[1].kind, "pr");
+
+        // Events already scanned are not scanned again
+        events
+            .add_event(task.id, "note", "Design: https://www.figma.com/file/x")
+            .await
+            .unwrap();
+        let report = extract(pool).await.unwrap();
+        assert_eq!((report.events_scanned, report.added), (1, 1));
+        assert_eq!(extract(pool).await.unwrap().added, 0);
+    }
+
+    #[tokio::test]
+    async fn test_check_records_dead_links() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let root = ctx._temp_dir.path().to_path_buf();
+        std::fs::write(root.join("README.md"), "# Readme").unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request);
+                let status = if request.starts_with("HEAD /gone") {
+                    "404 Not Found"
+                } else if request.starts_with("HEAD /get-only") {
+                    "405 Method Not Allowed"
+                } else {
+                    "200 OK"
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let task = crate::tasks::TaskManager::new(pool)
+            .add_task("Docs", None, None, None, None, None)
+            .await
+            .unwrap();
+        let links = LinkManager::new(pool);
+        for url in [
+            format!("{}/ok", base),
+            format!("{}/gone", base),
+            format!("{}/get-only", base),
+            "README.md#usage".to_string(),
+            "docs/missing.md".to_string(),
+            "mailto:team@example.com".to_string(),
+        ] {
+            links.add(task.id, &LinkInput::Url(url)).await.unwrap();
+        }
+
+        let checker = LinkChecker::new(Duration::from_secs(5), Some(root)).unwrap();
+        let report = checker.check(pool, Some(task.id)).await.unwrap();
+        assert_eq!((report.checked, report.skipped), (5, 1));
+        let dead: Vec<&str> = report.dead.iter().map(|l| l.url.as_str()).collect();
+        assert_eq!(
+            dead,
+            vec![format!("{}/gone", base).as_str(), "docs/missing.md"]
+        );
+
+        let listed = links.list(task.id).await.unwrap();
+        assert_eq!(listed[0].last_status, Some(200));
+        assert_eq!(listed[1].last_status, Some(404));
+        assert_eq!(listed[2].last_status, Some(200));
+        assert!(listed[3].last_checked_at.is_some() && !listed[3].is_dead());
+        assert!(listed[4]
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("No such file"));
+        assert!(listed[5].last_checked_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_links_from_plan_and_cli() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let result = PlanExecutor::new(pool)
+            .execute(&plan(
+                r#"{"tasks": [{"name": "Auth", "links": [
+                    "https://github.com/org/repo/issues/12",
+                    {"url": "docs/auth.md", "title": "Design", "kind": "design"}
+                ]}]}"#,
+            ))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let id = result.task_id_map["Auth"];
+
+        let links = LinkManager::new(pool);
+        let listed = links.list(id).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].kind, "issue");
+        assert_eq!(listed[1].title.as_deref(), Some("Design"));
+        assert_eq!(listed[1].kind, "design");
+
+        // Restating keeps rows of URLs still listed and drops the others
+        PlanExecutor::new(pool)
+            .execute(&plan(
+                r#"{"tasks": [{"name": "Auth", "links": ["docs/auth.md", "https://example.com"]}]}"#,
+            ))
+            .await
+            .unwrap();
+        let restated = links.list(id).await.unwrap();
+        assert_eq!(restated.len(), 2);
+        assert_eq!(restated[0].id, listed[1].id);
+        assert_eq!(restated[0].title, None);
+        assert_eq!(restated[0].kind, "doc");
+
+        links.remove(id, "https://example.com").await.unwrap();
+        assert!(links.remove(id, "https://example.com").await.is_err());
+        let added = links
+            .add(
+                id,
+                &LinkInput::Url("https://github.com/org/repo/pull/9".into()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(added.kind, "pr");
+        assert_eq!(links.list_all().await.unwrap().len(), 2);
+
+        // Invalid links fail the plan before anything is written
+        let result = PlanExecutor::new(pool)
+            .execute(&plan(
+                r#"{"tasks": [{"name": "Auth", "links": [{"url": "x", "kind": "video"}]}]}"#,
+            ))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid link kind"));
+        assert_eq!(links.list(id).await.unwrap().len(), 2);
+    }
+}
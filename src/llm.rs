@@ -398,7 +398,7 @@ async fn analyze_and_store_suggestions(pool: &SqlitePool) -> Result<()> {
     // Get all tasks
     let tasks: Vec<crate::db::models::Task> = sqlx::query_as(
         "SELECT id, parent_id, name, spec, status, complexity, priority, \
-         first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata \
+         first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason \
          FROM tasks ORDER BY id",
     )
     .fetch_all(pool)
@@ -729,7 +729,9 @@ mod tests {
             task_id: 1,
             log_type: "decision".to_string(),
             discussion_data: "Test decision".to_string(),
+            visibility: Default::default(),
             timestamp: Utc::now(),
+            actor: None,
         };
 
         // Should return None when LLM not configured
@@ -749,20 +751,24 @@ mod tests {
         // This test verifies the prompt structure without calling actual LLM
         use chrono::Utc;
 
-        let events = vec![
+        let events = [
             crate::db::models::Event {
                 id: 1,
                 task_id: 1,
                 log_type: "decision".to_string(),
                 discussion_data: "Chose approach A".to_string(),
+                visibility: Default::default(),
                 timestamp: Utc::now(),
+                actor: None,
             },
             crate::db::models::Event {
                 id: 2,
                 task_id: 1,
                 log_type: "milestone".to_string(),
                 discussion_data: "Completed phase 1".to_string(),
+                visibility: Default::default(),
                 timestamp: Utc::now(),
+                actor: None,
             },
         ];
 
@@ -803,12 +809,14 @@ mod tests {
         use chrono::Utc;
 
         let original_spec: Option<&str> = None;
-        let events = vec![crate::db::models::Event {
+        let events = [crate::db::models::Event {
             id: 1,
             task_id: 1,
             log_type: "note".to_string(),
             discussion_data: "Some work done".to_string(),
+            visibility: Default::default(),
             timestamp: Utc::now(),
+            actor: None,
         }];
 
         // Should handle missing original spec
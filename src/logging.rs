@@ -29,6 +29,8 @@ pub struct LoggingConfig {
     pub enable_spans: bool,
     /// Output to file instead of stdout (for daemon mode)
     pub file_output: Option<std::path::PathBuf>,
+    /// Collect database operation timings for `timing::print_summary`
+    pub timing: bool,
 }
 
 impl Default for LoggingConfig {
@@ -41,6 +43,7 @@ impl Default for LoggingConfig {
             json_format: false,
             enable_spans: false,
             file_output: None,
+            timing: false,
         }
     }
 }
@@ -57,6 +60,7 @@ impl LoggingConfig {
                 json_format: true,   // Machine-readable for MCP
                 enable_spans: false, // Avoid noise in JSON-RPC
                 file_output: None,
+                timing: false,
             },
             ApplicationMode::Dashboard => Self {
                 level: Level::INFO,
@@ -66,6 +70,7 @@ impl LoggingConfig {
                 json_format: false,
                 enable_spans: true, // Good for debugging dashboard
                 file_output: None,
+                timing: false,
             },
             ApplicationMode::Cli => Self {
                 level: Level::INFO,
@@ -75,6 +80,7 @@ impl LoggingConfig {
                 json_format: false,
                 enable_spans: false,
                 file_output: None,
+                timing: false,
             },
             ApplicationMode::Test => Self {
                 level: Level::DEBUG,
@@ -84,6 +90,7 @@ impl LoggingConfig {
                 json_format: false,
                 enable_spans: true,
                 file_output: None,
+                timing: false,
             },
         }
     }
@@ -106,6 +113,7 @@ impl LoggingConfig {
             json_format: json,
             enable_spans: verbose,
             file_output: None,
+            timing: verbose,
         }
     }
 }
@@ -132,7 +140,9 @@ pub fn init_logging(config: LoggingConfig) -> io::Result<()> {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(format!("intent_engine={}", config.level)));
 
-    let registry = Registry::default().with(env_filter);
+    let registry = Registry::default()
+        .with(env_filter)
+        .with(config.timing.then(crate::timing::TimingLayer::install));
 
     if let Some(log_file) = config.file_output {
         let log_dir = log_file
@@ -277,6 +287,21 @@ pub fn cleanup_old_logs(log_dir: &std::path::Path, retention_days: u32) -> io::R
     Ok(())
 }
 
+/// Run [`cleanup_old_logs`] now and then once a day, for long-running processes
+///
+/// Must be called inside a Tokio runtime.
+pub fn spawn_log_cleanup(log_dir: std::path::PathBuf, retention_days: u32) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = cleanup_old_logs(&log_dir, retention_days) {
+                tracing::warn!(error = %e, "Log cleanup failed");
+            }
+        }
+    });
+}
+
 /// Get log file path for a given application mode
 pub fn log_file_path(mode: ApplicationMode) -> std::path::PathBuf {
     let home = dirs::home_dir().expect("Failed to get home directory");
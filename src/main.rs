@@ -1,31 +1,41 @@
 use clap::Parser;
+use intent_engine::backend::Backend;
 use intent_engine::cli::{Cli, Commands, DashboardCommands};
 use intent_engine::cli_handlers::{
-    handle_config_command, handle_dashboard_command, handle_doctor_command, handle_init_command,
-    handle_log, handle_search_command, handle_status, handle_task_command, print_plan_result,
-    read_stdin,
+    execute_under_focus, handle_activity, handle_completions, handle_config_command,
+    handle_dashboard_command, handle_db, handle_deps_command, handle_doctor_command,
+    handle_errors_command, handle_export_command, handle_focus_command, handle_handoff_command,
+    handle_hooks_command, handle_import_command, handle_inbox, handle_init_command,
+    handle_insights, handle_introspect, handle_links_command, handle_log, handle_prompt_segment,
+    handle_report, handle_search_command, handle_status, handle_sync_command, handle_task_command,
+    handle_watch, handle_workspace_command, print_focus_session, print_focus_stack,
+    print_latest_handoff, print_plan_result, print_plan_schema, print_plan_validation,
+    print_restore_bundle, read_stdin, save_plan_workspace, LogInput,
 };
+use intent_engine::config::StorageBackend;
 use intent_engine::error::{IntentError, Result};
-use intent_engine::events::EventManager;
+use intent_engine::hooks::HookEvent;
+use intent_engine::i18n::{t, Locale};
 use intent_engine::logging::LoggingConfig;
-use intent_engine::plan::{
-    cleanup_included_files, process_file_includes, PlanExecutor, PlanRequest,
-};
+use intent_engine::plan::{cleanup_included_files, parse_request, process_file_includes};
+use intent_engine::plan_journal::PlanJournal;
 use intent_engine::project::ProjectContext;
+use intent_engine::session_restore::{RestoreBudget, SessionRestoreManager};
 use intent_engine::tasks::TaskManager;
 use intent_engine::workspace::WorkspaceManager;
 use std::io::IsTerminal;
 
 #[tokio::main]
 async fn main() {
+    // Pick the message language before anything is printed
+    intent_engine::i18n::set_locale(Locale::resolve(ProjectContext::configured_locale()));
+
     // Setup Windows console for UTF-8 output
     // This ensures Chinese and other non-ASCII characters display correctly
     #[cfg(windows)]
     if let Err(e) = intent_engine::windows_console::setup_windows_console() {
-        eprintln!("Warning: Failed to setup Windows console UTF-8: {}", e);
-        eprintln!(
-            "Chinese characters may not display correctly. Consider running 'chcp 65001' first."
-        );
+        eprintln!("{}", t("main.console_utf8_failed", &[("error", &e)]));
+        eprintln!("{}", t("main.console_utf8_hint", &[]));
     }
 
     // Parse CLI arguments first to get logging configuration
@@ -38,7 +48,7 @@ async fn main() {
     // Also support IE_DASHBOARD_LOG_FILE env var for testing
     if matches!(
         cli.command,
-        Commands::Dashboard(DashboardCommands::Start { .. })
+        Commands::Dashboard(DashboardCommands::Start { .. } | DashboardCommands::RunService { .. })
     ) {
         // Force enable file logging if env var is set (for testing)
         let force_file_log = std::env::var("IE_DASHBOARD_LOG_FILE").is_ok();
@@ -52,16 +62,17 @@ async fn main() {
     }
 
     if let Err(e) = intent_engine::logging::init_logging(log_config) {
-        eprintln!("Failed to initialize logging: {}", e);
+        eprintln!("{}", t("main.logging_failed", &[("error", &e)]));
         std::process::exit(1);
     }
 
-    // Clean up old log files for Dashboard mode (after logging init)
+    // Clean up old log files for Dashboard mode (after logging init), daily
+    // while it runs since a service may run for weeks
     if matches!(
         cli.command,
-        Commands::Dashboard(DashboardCommands::Start { .. })
+        Commands::Dashboard(DashboardCommands::Start { .. } | DashboardCommands::RunService { .. })
     ) {
-        use intent_engine::logging::cleanup_old_logs;
+        use intent_engine::logging::spawn_log_cleanup;
         let log_dir = dirs::home_dir().map(|h| h.join(".intent-engine").join("logs"));
 
         if let Some(dir) = log_dir {
@@ -71,7 +82,7 @@ async fn main() {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(7);
 
-            cleanup_old_logs(&dir, retention_days).ok();
+            spawn_log_cleanup(dir, retention_days);
         }
     }
 
@@ -79,7 +90,16 @@ async fn main() {
     // Users should manually start Dashboard with: ie dashboard start
 
     // Continue with main application logic
-    if let Err(e) = run(&cli).await {
+    let started = std::time::Instant::now();
+    let result = run(&cli).await;
+    if cli.verbose > 0 {
+        intent_engine::timing::print_summary(started.elapsed());
+    }
+    if let Err(e) = result {
+        if cli.command.uses_output_envelope() {
+            let _ = intent_engine::output::print(&intent_engine::output::Envelope::failure(&e));
+            std::process::exit(1);
+        }
         let error_response = e.to_error_response();
         eprintln!("{}", serde_json::to_string_pretty(&error_response).unwrap());
         std::process::exit(1);
@@ -87,34 +107,56 @@ async fn main() {
 }
 
 async fn run(cli: &Cli) -> Result<()> {
+    intent_engine::project::set_task_scope(cli.scope);
+    if cli.read_only {
+        if cli.command.always_writes() {
+            return Err(IntentError::InvalidInput(t("main.read_only", &[])));
+        }
+        intent_engine::project::set_read_only(true);
+    }
+
+    // Data commands go to the backend the project config selects
+    if uses_storage_backend(&cli.command) {
+        match ProjectContext::configured_storage()? {
+            StorageBackend::Sqlite => {},
+            StorageBackend::Neo4j => return run_on_neo4j(cli).await,
+            StorageBackend::Postgres => return run_on_postgres(cli).await,
+        }
+    }
+
     match cli.command.clone() {
-        Commands::Plan { format } => {
+        Commands::Plan {
+            format,
+            validate,
+            schema,
+            workspace,
+        } => {
+            if schema {
+                return print_plan_schema();
+            }
+
             // Read JSON from stdin
             let json_input = read_stdin()?;
 
             // Parse JSON into PlanRequest
-            let mut request: PlanRequest = serde_json::from_str(&json_input)
-                .map_err(|e| IntentError::InvalidInput(format!("Invalid JSON: {}", e)))?;
+            let mut request = parse_request(&json_input)?;
 
             // Process @file directives - replace @file(path) with file contents
             let file_include_result =
                 process_file_includes(&mut request).map_err(IntentError::InvalidInput)?;
 
-            // Execute the plan
-            let ctx = ProjectContext::load_or_init().await?;
-            let project_path = ctx.root.to_string_lossy().to_string();
-
-            // Get current focused task for auto-parenting
-            let workspace_mgr = WorkspaceManager::new(&ctx.pool);
-            let current = workspace_mgr.get_current_task(None).await?;
+            if validate {
+                return print_plan_validation(&request, &format);
+            }
 
-            // Create executor with project path, then optionally set default parent
-            let mut executor = PlanExecutor::with_project_path(&ctx.pool, project_path);
-            if let Some(current_task_id) = current.current_task_id {
-                executor = executor.with_default_parent(current_task_id);
+            // Keep the plan as an alternative instead of merging it
+            if let Some(name) = workspace {
+                return save_plan_workspace(&name, &request, &format).await;
             }
 
-            let result = executor.execute(&request).await?;
+            // Execute the plan, auto-parenting to the focused task
+            let ctx = ProjectContext::load_or_init().await?;
+            let result = execute_under_focus(&ctx, &request).await?;
 
             // Clean up included files after successful execution
             if result.success && !file_include_result.files_to_delete.is_empty() {
@@ -122,24 +164,28 @@ async fn run(cli: &Cli) -> Result<()> {
             }
 
             print_plan_result(&result, &format)?;
+            if result.success {
+                ProjectContext::configured_hooks()?.fire(HookEvent::PlanExecuted, &result)?;
+            }
         },
 
         Commands::Log {
             event_type,
             message,
             task,
+            visibility,
+            timestamp,
+            stdin_json,
             format,
         } => {
             let ctx = ProjectContext::load_or_init().await?;
-            let project_path = ctx.root.to_string_lossy().to_string();
-            let event_mgr = EventManager::with_project_path(&ctx.pool, project_path);
-            let workspace_mgr = WorkspaceManager::new(&ctx.pool);
+            let input = LogInput::from_args(event_type, message, timestamp, stdin_json)?;
             handle_log(
-                &event_mgr,
-                &workspace_mgr,
-                event_type,
-                &message,
+                &ctx.events(),
+                &ctx.workspace(),
+                input,
                 task,
+                visibility,
                 &format,
             )
             .await?;
@@ -153,24 +199,54 @@ async fn run(cli: &Cli) -> Result<()> {
             offset,
             since,
             until,
+            event_types,
             format,
+            semantic,
         } => {
-            handle_search_command(&query, tasks, events, limit, offset, since, until, &format)
-                .await?
+            handle_search_command(
+                &query,
+                tasks,
+                events,
+                event_types,
+                limit,
+                offset,
+                since,
+                until,
+                &format,
+                semantic,
+            )
+            .await?
         },
 
-        Commands::Init { at, force } => handle_init_command(at, force).await?,
+        Commands::Init {
+            at,
+            force,
+            analyze,
+            apply,
+            format,
+        } => handle_init_command(at, force, analyze, apply, &format).await?,
 
         Commands::Dashboard(dashboard_cmd) => handle_dashboard_command(dashboard_cmd).await?,
 
-        Commands::Doctor => handle_doctor_command().await?,
+        Commands::Doctor { fix, format } => handle_doctor_command(fix, &format).await?,
 
-        Commands::Task(task_cmd) => {
+        Commands::Watch { task, format } => handle_watch(task, &format).await?,
+
+        Commands::Completions { shell } => handle_completions(shell, "ie"),
+
+        Commands::Introspect { json } => handle_introspect(json, "ie")?,
+
+        Commands::Errors(errors_cmd) => handle_errors_command(errors_cmd)?,
+
+        Commands::Tui => {
             let ctx = ProjectContext::load_or_init().await?;
             let project_path = ctx.root.to_string_lossy().to_string();
-            let task_mgr = TaskManager::with_project_path(&ctx.pool, project_path);
-            let workspace_mgr = WorkspaceManager::new(&ctx.pool);
-            handle_task_command(&task_mgr, &workspace_mgr, task_cmd).await?
+            intent_engine::tui::run(&ctx.pool, project_path).await?
+        },
+
+        Commands::Task(task_cmd) => {
+            let ctx = ProjectContext::load_or_init().await?;
+            handle_task_command(&ctx.tasks(), &ctx.workspace(), task_cmd).await?
         },
 
         Commands::Suggestions(suggestions_cmd) => {
@@ -192,18 +268,117 @@ async fn run(cli: &Cli) -> Result<()> {
 
         Commands::Config(config_cmd) => handle_config_command(config_cmd).await?,
 
+        Commands::Hooks(hooks_cmd) => handle_hooks_command(hooks_cmd)?,
+
+        Commands::Deps(deps_cmd) => handle_deps_command(deps_cmd).await?,
+
+        Commands::PromptSegment {
+            zsh,
+            bash,
+            no_cache,
+            format,
+        } => handle_prompt_segment(zsh, bash, no_cache, &format).await?,
+
+        Commands::Export(export_cmd) => handle_export_command(export_cmd).await?,
+
+        Commands::Report(report_cmd) => handle_report(report_cmd).await?,
+
+        Commands::Handoff(handoff_cmd) => handle_handoff_command(handoff_cmd).await?,
+
+        Commands::Workspace(workspace_cmd) => handle_workspace_command(workspace_cmd).await?,
+
+        Commands::Focus(focus_cmd) => handle_focus_command(focus_cmd).await?,
+
+        Commands::Links(links_cmd) => handle_links_command(links_cmd).await?,
+
+        Commands::Inbox { limit, format } => handle_inbox(limit, &format).await?,
+
+        Commands::Insights { kind, format } => handle_insights(kind, &format).await?,
+
+        Commands::Activity {
+            since,
+            task,
+            event_type,
+            limit,
+            format,
+        } => {
+            let filter = intent_engine::db::models::ActivityFilter {
+                since,
+                task_id: task,
+                event_type,
+            };
+            handle_activity(limit, filter, &format).await?
+        },
+
+        Commands::Db(db_cmd) => handle_db(db_cmd).await?,
+
+        Commands::Import {
+            file,
+            on_conflict,
+            resolve,
+            parent,
+            dry_run,
+            format,
+        } => {
+            handle_import_command(
+                &file,
+                on_conflict.as_deref(),
+                &resolve,
+                parent,
+                dry_run,
+                &format,
+            )
+            .await?
+        },
+
+        Commands::Sync(sync_cmd) => handle_sync_command(sync_cmd).await?,
+
+        #[cfg(feature = "dev")]
+        Commands::Dev(dev_cmd) => intent_engine::cli_handlers::handle_dev_command(dev_cmd).await?,
+
         Commands::Status {
             task_id,
             with_events,
+            max_tokens,
+            max_chars,
+            event_visibility,
             format,
         } => {
             let ctx = ProjectContext::load_or_init().await?;
-            let task_mgr = TaskManager::new(&ctx.pool);
+            let task_mgr = TaskManager::new(&ctx.pool).with_event_visibility(event_visibility);
             let workspace_mgr = WorkspaceManager::new(&ctx.pool);
 
+            // Settle plans a crashed process left half-applied before showing state
+            for entry in PlanJournal::new(&ctx.pool).recover(&task_mgr).await? {
+                eprintln!("{}", entry.message());
+            }
+
+            let budget = match (max_tokens, max_chars) {
+                (Some(tokens), _) => Some(RestoreBudget::Tokens(tokens)),
+                (None, Some(chars)) => Some(RestoreBudget::Chars(chars)),
+                (None, None) => None,
+            };
+            if let Some(budget) = budget {
+                let bundle = SessionRestoreManager::new(&ctx.pool)
+                    .restore_bundle(task_id, budget)
+                    .await?;
+                print_restore_bundle(&bundle, &format)?;
+                return ProjectContext::configured_hooks()?.fire(
+                    HookEvent::SessionRestored,
+                    &serde_json::json!({ "task_id": bundle.focused_task_id }),
+                );
+            }
+
             // Trigger background task structure analysis (async, non-blocking)
             intent_engine::llm::analyze_task_structure_background(ctx.pool.clone());
 
+            // The last session's handoff comes before anything else
+            if format != "json" {
+                print_latest_handoff(&ctx.pool).await?;
+                print_focus_session(&ctx.pool).await?;
+                print_focus_stack(&ctx.pool).await?;
+            }
+
             // Use shared status handler
             handle_status(&task_mgr, &workspace_mgr, task_id, with_events, &format).await?;
 
@@ -216,3 +391,41 @@ async fn run(cli: &Cli) -> Result<()> {
 
     Ok(())
 }
+
+/// Commands served by whichever storage backend the project config selects
+fn uses_storage_backend(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Plan { .. }
+            | Commands::Log { .. }
+            | Commands::Search { .. }
+            | Commands::Task(_)
+            | Commands::Status { .. }
+    )
+}
+
+#[cfg(feature = "neo4j")]
+async fn run_on_neo4j(cli: &Cli) -> Result<()> {
+    intent_engine::cli_handlers::run_neo4j(cli, "ie").await
+}
+
+#[cfg(not(feature = "neo4j"))]
+async fn run_on_neo4j(_cli: &Cli) -> Result<()> {
+    Err(IntentError::InvalidInput(t(
+        "main.backend_not_built",
+        &[("backend", &"neo4j"), ("product", &"Neo4j")],
+    )))
+}
+
+#[cfg(feature = "postgres")]
+async fn run_on_postgres(cli: &Cli) -> Result<()> {
+    intent_engine::cli_handlers::run_postgres(cli, "ie").await
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn run_on_postgres(_cli: &Cli) -> Result<()> {
+    Err(IntentError::InvalidInput(t(
+        "main.backend_not_built",
+        &[("backend", &"postgres"), ("product", &"Postgres")],
+    )))
+}
@@ -1,4 +1,4 @@
-use crate::db::models::Event;
+use crate::db::models::{Event, EventVisibility, NewEvent};
 use crate::error::{IntentError, Result};
 use chrono::Utc;
 use neo4rs::{query, Graph};
@@ -85,6 +85,8 @@ impl Neo4jEventManager {
                 timestamp: now,
                 log_type: log_type.to_string(),
                 discussion_data: discussion_data.to_string(),
+                visibility: Default::default(),
+                actor: None,
             }),
             None => Err(IntentError::OtherError(anyhow::anyhow!(
                 "add_event: CREATE did not return a node"
@@ -268,6 +270,28 @@ impl crate::backend::EventBackend for Neo4jEventManager {
     ) -> impl std::future::Future<Output = Result<Vec<Event>>> + Send {
         self.list_events(task_id, limit, log_type, since)
     }
+
+    async fn add_event_with_visibility(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+        visibility: EventVisibility,
+    ) -> Result<Event> {
+        if !visibility.is_normal() {
+            return Err(IntentError::InvalidInput(format!(
+                "Event visibility '{}' is not supported by the Neo4j backend",
+                visibility.as_str()
+            )));
+        }
+        self.add_event(task_id, log_type, discussion_data).await
+    }
+
+    async fn add_events(&self, _events: &[NewEvent]) -> Result<Vec<Event>> {
+        Err(IntentError::InvalidInput(
+            "Batch logging is not supported by the Neo4j backend".to_string(),
+        ))
+    }
 }
 
 /// Convert a Neo4j Event node to an Event struct.
@@ -302,5 +326,7 @@ pub(crate) fn node_to_event(node: &neo4rs::Node) -> Result<Event> {
         timestamp,
         log_type,
         discussion_data,
+        visibility: Default::default(),
+        actor: None,
     })
 }
@@ -4,7 +4,8 @@
 //! the same `Task`, `Event`, and CLI types from the main crate, but stores
 //! everything in Neo4j instead of SQLite.
 //!
-//! Activated by the `neo4j` feature flag; used exclusively by the `ie-neo4j` binary.
+//! Activated by the `neo4j` feature flag; used by the `ie-neo4j` binary, and by
+//! `ie` in projects whose config sets `[storage] backend = "neo4j"`.
 
 pub mod config;
 pub mod event_manager;
@@ -112,3 +113,39 @@ impl Neo4jContext {
         Neo4jSearchManager::new(self.graph.clone(), self.project_id.clone())
     }
 }
+
+impl crate::backend::Backend for Neo4jContext {
+    type Tasks<'a> = Neo4jTaskManager;
+    type Workspace<'a> = Neo4jWorkspaceManager;
+    type Events<'a> = Neo4jEventManager;
+    type Plans<'a> = Neo4jPlanExecutor;
+    type Search<'a> = Neo4jSearchManager;
+
+    fn name(&self) -> &'static str {
+        crate::config::StorageBackend::Neo4j.as_str()
+    }
+
+    fn tasks(&self) -> Self::Tasks<'_> {
+        self.task_manager()
+    }
+
+    fn workspace(&self) -> Self::Workspace<'_> {
+        self.workspace_manager()
+    }
+
+    fn events(&self) -> Self::Events<'_> {
+        self.event_manager()
+    }
+
+    fn plans(&self, default_parent: Option<i64>) -> Self::Plans<'_> {
+        let executor = self.plan_executor();
+        match default_parent {
+            Some(parent_id) => executor.with_default_parent(parent_id),
+            None => executor,
+        }
+    }
+
+    fn search(&self) -> Self::Search<'_> {
+        self.search_manager()
+    }
+}
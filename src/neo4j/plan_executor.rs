@@ -49,6 +49,73 @@ impl Neo4jPlanExecutor {
     pub async fn execute(&self, request: &PlanRequest) -> Result<PlanResult> {
         let task_mgr = super::Neo4jTaskManager::new(self.graph.clone(), self.project_id.clone());
 
+        if !request.on_error.is_abort() {
+            return Ok(PlanResult::error(format!(
+                "on_error '{}' is not supported by the Neo4j backend; plans are applied atomically",
+                request.on_error.as_str()
+            )));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.milestone.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Milestones are not supported by the Neo4j backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.needs_human.is_some())
+        {
+            return Ok(PlanResult::error(
+                "needs_human is not supported by the Neo4j backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.blocked_reason.is_some())
+        {
+            return Ok(PlanResult::error(
+                "blocked_reason is not supported by the Neo4j backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.checklist.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Checklists are not supported by the Neo4j backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.links.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Links are not supported by the Neo4j backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.outcome.is_some() || t.artifacts.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Completion outcomes are not supported by the Neo4j backend",
+            ));
+        }
+
+        if !request.scope.is_project() {
+            return Ok(PlanResult::error(
+                "Plan scopes are not supported by the Neo4j backend; names are matched project-wide",
+            ));
+        }
+
         // ── 1. Validate: duplicate names ──
         let duplicates = find_duplicate_names(&request.tasks);
         if !duplicates.is_empty() {
@@ -208,6 +275,7 @@ impl Neo4jPlanExecutor {
                     priority: task.priority.as_ref().map(|p| p.to_int()),
                     status: status_for_update,
                     active_form: task.active_form.as_deref(),
+                    estimate_minutes: task.estimate_minutes.map(i64::from),
                     ..Default::default()
                 };
 
@@ -215,7 +283,7 @@ impl Neo4jPlanExecutor {
 
                 // Handle done transition
                 if is_becoming_done {
-                    if let Err(e) = task_mgr.done_task_by_id(existing_info.id, false).await {
+                    if let Err(e) = task_mgr.done_task_by_id(existing_info.id).await {
                         return Ok(PlanResult::error(format!(
                             "Cannot complete task '{}': {}\n\n\
                             Please complete all subtasks before marking the parent as done.",
@@ -257,18 +325,19 @@ impl Neo4jPlanExecutor {
                     )
                     .await?;
 
-                // Set status and active_form in a single update if needed
+                // Set status, active_form and estimate in a single update if needed
                 let needs_status = initial_status
                     .as_ref()
                     .map(|s| *s != "todo")
                     .unwrap_or(false);
-                if needs_status || task.active_form.is_some() {
+                if needs_status || task.active_form.is_some() || task.estimate_minutes.is_some() {
                     task_mgr
                         .update_task(
                             new_task.id,
                             TaskUpdate {
                                 status: if needs_status { initial_status } else { None },
                                 active_form: task.active_form.as_deref(),
+                                estimate_minutes: task.estimate_minutes.map(i64::from),
                                 ..Default::default()
                             },
                         )
@@ -379,7 +448,11 @@ impl Neo4jPlanExecutor {
         }
 
         // ── 12. Build BLOCKED_BY dependency relationships ──
-        let dep_count = self.build_dependencies(&flat_tasks, &task_id_map).await?;
+        let dep_count = match self.build_dependencies(&flat_tasks, &task_id_map).await {
+            Ok(count) => count,
+            Err(IntentError::InvalidInput(message)) => return Ok(PlanResult::error(message)),
+            Err(e) => return Err(e),
+        };
 
         // ── 13. Auto-focus the doing task ──
         let doing_task = normal_tasks
@@ -419,13 +492,18 @@ impl Neo4jPlanExecutor {
     /// For each task with depends_on entries, creates:
     ///   (blocked_task)-[:BLOCKED_BY]->(blocking_task)
     /// where blocking_task is the task that must complete first.
+    ///
+    /// Same rules as the SQLite executor: new edges are checked against the
+    /// stored ones before any is written (a cycle fails with its full path),
+    /// and edges that already exist are skipped, so re-running a plan is
+    /// idempotent and the count only covers new edges.
     async fn build_dependencies(
         &self,
         flat_tasks: &[FlatTask],
         task_id_map: &HashMap<String, i64>,
     ) -> Result<usize> {
-        let mut count: usize = 0;
-
+        // (blocked, blocking) pairs
+        let mut edges = Vec::new();
         for task in flat_tasks {
             if task.delete || task.depends_on.is_empty() {
                 continue;
@@ -447,79 +525,131 @@ impl Neo4jPlanExecutor {
                         dep_name, task_name
                     ))
                 })?;
+                edges.push((blocked_id, *blocking_id));
+            }
+        }
+        if edges.is_empty() {
+            return Ok(0);
+        }
 
-                // Check for circular dependency via Neo4j path query:
-                // Would creating blocked->BLOCKED_BY->blocking create a cycle?
-                if self.would_create_cycle(blocked_id, *blocking_id).await? {
-                    return Err(IntentError::CircularDependency {
-                        blocking_task_id: *blocking_id,
-                        blocked_task_id: blocked_id,
-                    });
-                }
-
-                // Create BLOCKED_BY relationship (idempotent via MERGE)
-                let mut stream = self
-                    .graph
-                    .execute(
-                        query(
-                            "MATCH (blocked:Task {project_id: $pid, id: $blocked_id}) \
-                             MATCH (blocking:Task {project_id: $pid, id: $blocking_id}) \
-                             MERGE (blocked)-[:BLOCKED_BY]->(blocking)",
+        let mut depends_on = self.load_dependency_graph(&edges).await?;
+        for &(blocked_id, blocking_id) in &edges {
+            if let Some(cycle) =
+                plan_validation::find_dependency_cycle(&depends_on, blocked_id, blocking_id)
+            {
+                let names = self.find_task_names(&cycle).await?;
+                let path: Vec<String> = cycle
+                    .iter()
+                    .map(|id| {
+                        format!(
+                            "{} (#{})",
+                            names.get(id).map(String::as_str).unwrap_or("?"),
+                            id
                         )
-                        .param("pid", self.project_id.clone())
-                        .param("blocked_id", blocked_id)
-                        .param("blocking_id", *blocking_id),
+                    })
+                    .collect();
+                return Err(IntentError::InvalidInput(format!(
+                    "Circular dependency detected with existing dependencies: {}",
+                    path.join(" → ")
+                )));
+            }
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
+        }
+
+        let mut count: usize = 0;
+        for &(blocked_id, blocking_id) in &edges {
+            let mut result = self
+                .graph
+                .execute(
+                    query(
+                        "MATCH (blocked:Task {project_id: $pid, id: $blocked_id}) \
+                         MATCH (blocking:Task {project_id: $pid, id: $blocking_id}) \
+                         WHERE NOT (blocked)-[:BLOCKED_BY]->(blocking) \
+                         CREATE (blocked)-[:BLOCKED_BY]->(blocking) \
+                         RETURN count(*) AS created",
                     )
-                    .await
-                    .map_err(|e| neo4j_err("build_dependencies", e))?;
-                // Consume stream to ensure query executes
-                while stream
-                    .next()
-                    .await
-                    .map_err(|e| neo4j_err("build_dependencies consume", e))?
-                    .is_some()
-                {}
-
-                count += 1;
+                    .param("pid", self.project_id.clone())
+                    .param("blocked_id", blocked_id)
+                    .param("blocking_id", blocking_id),
+                )
+                .await
+                .map_err(|e| neo4j_err("build_dependencies", e))?;
+
+            if let Some(row) = result
+                .next()
+                .await
+                .map_err(|e| neo4j_err("build_dependencies fetch", e))?
+            {
+                count += row.get::<i64>("created").unwrap_or(0) as usize;
             }
         }
 
         Ok(count)
     }
 
-    /// Check if adding a BLOCKED_BY edge from blocked_id to blocking_id
-    /// would create a cycle (i.e., blocking_id already transitively depends on blocked_id).
-    async fn would_create_cycle(&self, blocked_id: i64, blocking_id: i64) -> Result<bool> {
-        if blocked_id == blocking_id {
-            return Ok(true);
+    /// Stored BLOCKED_BY edges reachable from the tasks of `edges`, as
+    /// task → dependencies (the input of `find_dependency_cycle`)
+    async fn load_dependency_graph(&self, edges: &[(i64, i64)]) -> Result<HashMap<i64, Vec<i64>>> {
+        let seeds: Vec<i64> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+        let mut result = self
+            .graph
+            .execute(
+                query(
+                    "UNWIND $ids AS seed_id \
+                     MATCH (seed:Task {project_id: $pid, id: seed_id})\
+                     -[:BLOCKED_BY*0..]->(t:Task)-[:BLOCKED_BY]->(dep:Task) \
+                     RETURN DISTINCT t.id AS blocked_id, dep.id AS blocking_id",
+                )
+                .param("pid", self.project_id.clone())
+                .param("ids", seeds),
+            )
+            .await
+            .map_err(|e| neo4j_err("load_dependency_graph", e))?;
+
+        let mut depends_on: HashMap<i64, Vec<i64>> = HashMap::new();
+        while let Some(row) = result
+            .next()
+            .await
+            .map_err(|e| neo4j_err("load_dependency_graph iterate", e))?
+        {
+            let blocked_id: i64 = row
+                .get("blocked_id")
+                .map_err(|e| neo4j_err("load_dependency_graph blocked_id", e))?;
+            let blocking_id: i64 = row
+                .get("blocking_id")
+                .map_err(|e| neo4j_err("load_dependency_graph blocking_id", e))?;
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
         }
+        Ok(depends_on)
+    }
 
-        // Check: is there already a path from blocking_id to blocked_id via BLOCKED_BY?
-        // If so, adding blocked_id->blocking_id would complete a cycle.
+    /// Names of the given tasks, by ID
+    async fn find_task_names(&self, ids: &[i64]) -> Result<HashMap<i64, String>> {
         let mut result = self
             .graph
             .execute(
                 query(
-                    "MATCH path = (start:Task {project_id: $pid, id: $blocking_id})\
-                     -[:BLOCKED_BY*1..50]->\
-                     (end:Task {project_id: $pid, id: $blocked_id}) \
-                     RETURN count(path) > 0 AS has_cycle LIMIT 1",
+                    "UNWIND $ids AS task_id \
+                     MATCH (t:Task {project_id: $pid, id: task_id}) \
+                     RETURN t.id AS id, t.name AS name",
                 )
                 .param("pid", self.project_id.clone())
-                .param("blocking_id", blocking_id)
-                .param("blocked_id", blocked_id),
+                .param("ids", ids.to_vec()),
             )
             .await
-            .map_err(|e| neo4j_err("would_create_cycle", e))?;
+            .map_err(|e| neo4j_err("find_task_names", e))?;
 
-        match result
+        let mut names = HashMap::new();
+        while let Some(row) = result
             .next()
             .await
-            .map_err(|e| neo4j_err("would_create_cycle fetch", e))?
+            .map_err(|e| neo4j_err("find_task_names iterate", e))?
         {
-            Some(row) => Ok(row.get::<bool>("has_cycle").unwrap_or(false)),
-            None => Ok(false),
+            if let (Ok(id), Ok(name)) = (row.get::<i64>("id"), row.get::<String>("name")) {
+                names.insert(id, name);
+            }
         }
+        Ok(names)
     }
 
     /// Find existing tasks by names using batch Cypher query.
@@ -537,7 +667,8 @@ impl Neo4jPlanExecutor {
                 query(
                     "UNWIND $names AS name \
                      MATCH (t:Task {project_id: $pid, name: name}) \
-                     RETURN t.name AS name, t.id AS id, t.status AS status, t.spec AS spec",
+                     RETURN t.name AS name, t.id AS id, t.status AS status, t.spec AS spec, \
+                     t.owner AS owner",
                 )
                 .param("pid", self.project_id.clone())
                 .param(
@@ -564,8 +695,17 @@ impl Neo4jPlanExecutor {
                 .get("status")
                 .map_err(|e| neo4j_err("find_tasks_by_names status", e))?;
             let spec: Option<String> = row.get("spec").ok();
-
-            map.insert(name, ExistingTaskInfo { id, status, spec });
+            let owner: String = row.get("owner").unwrap_or_else(|_| "human".to_string());
+
+            map.insert(
+                name,
+                ExistingTaskInfo {
+                    id,
+                    status,
+                    spec,
+                    owner,
+                },
+            );
         }
 
         Ok(map)
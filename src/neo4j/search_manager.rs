@@ -5,8 +5,8 @@
 //! queries (1-2 chars) that Lucene's StandardAnalyzer may not tokenize well.
 
 use crate::db::models::{Event, PaginatedSearchResults, SearchResult, Task};
-use crate::error::Result;
-use crate::search::{is_cjk_char, needs_like_fallback};
+use crate::error::{IntentError, Result};
+use crate::search::{build_context_snippet, is_cjk_char, needs_like_fallback};
 use neo4rs::{query, Graph};
 
 use super::event_manager::node_to_event;
@@ -168,6 +168,7 @@ impl Neo4jSearchManager {
                     task,
                     match_snippet,
                     match_field,
+                    score,
                 },
                 score,
             ));
@@ -240,6 +241,7 @@ impl Neo4jSearchManager {
                     event,
                     task_chain,
                     match_snippet,
+                    score,
                 },
                 score,
             ));
@@ -293,6 +295,7 @@ impl Neo4jSearchManager {
                     task,
                     match_snippet,
                     match_field,
+                    score: 0.0,
                 },
                 1.0,
             ));
@@ -359,6 +362,7 @@ impl Neo4jSearchManager {
                     event,
                     task_chain,
                     match_snippet,
+                    score,
                 },
                 score,
             ));
@@ -368,6 +372,28 @@ impl Neo4jSearchManager {
     }
 }
 
+impl crate::backend::SearchBackend for Neo4jSearchManager {
+    fn search(
+        &self,
+        query: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<PaginatedSearchResults>> + Send {
+        self.search(query, include_tasks, include_events, limit, offset)
+    }
+
+    fn filter_event_types(self, event_types: Vec<String>) -> Result<Self> {
+        if event_types.is_empty() {
+            return Ok(self);
+        }
+        Err(IntentError::InvalidInput(
+            "Filtering events by type is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+}
+
 // ── Lucene helpers ───────────────────────────────────────────────
 
 /// Escape special characters for Lucene query syntax and wrap in quotes
@@ -449,38 +475,6 @@ fn build_event_snippet(event: &Event, query_str: &str) -> String {
     build_context_snippet(&event.discussion_data, query_str)
 }
 
-/// Extract a context window around the first occurrence of `needle` in `haystack`.
-/// Returns up to ~120 chars of context centered on the match.
-fn build_context_snippet(haystack: &str, needle: &str) -> String {
-    let needle_lower = needle.to_lowercase();
-    let haystack_lower = haystack.to_lowercase();
-
-    if let Some(pos) = haystack_lower.find(&needle_lower) {
-        let chars: Vec<char> = haystack.chars().collect();
-        let char_pos = haystack[..pos].chars().count();
-        let context_chars = 60;
-
-        let start = char_pos.saturating_sub(context_chars);
-        let end = (char_pos + needle.chars().count() + context_chars).min(chars.len());
-
-        let snippet: String = chars[start..end].iter().collect();
-
-        let prefix = if start > 0 { "..." } else { "" };
-        let suffix = if end < chars.len() { "..." } else { "" };
-
-        format!("{}{}{}", prefix, snippet, suffix)
-    } else {
-        // No match found — return truncated haystack
-        let chars: Vec<char> = haystack.chars().collect();
-        if chars.len() > 120 {
-            let truncated: String = chars[..117].iter().collect();
-            format!("{}...", truncated)
-        } else {
-            haystack.to_string()
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -529,6 +523,11 @@ mod tests {
             active_form: None,
             owner: "human".to_string(),
             metadata: None,
+            sort_order: None,
+            estimate_minutes: None,
+            visibility: Default::default(),
+            blocked_reason: None,
+            checklist: None,
         };
         let (field, snippet) = build_task_snippet(&task, "authentication");
         assert_eq!(field, "name");
@@ -551,6 +550,11 @@ mod tests {
             active_form: None,
             owner: "human".to_string(),
             metadata: None,
+            sort_order: None,
+            estimate_minutes: None,
+            visibility: Default::default(),
+            blocked_reason: None,
+            checklist: None,
         };
         let (field, _snippet) = build_task_snippet(&task, "JWT");
         assert_eq!(field, "spec");
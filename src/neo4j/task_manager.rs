@@ -1,12 +1,18 @@
 use crate::db::models::{
-    DoneTaskResponse, NextStepSuggestion, PaginatedTasks, PickNextResponse, Task, TaskSortBy,
-    TaskWithEvents, WorkspaceStatus,
+    ChecklistItem, ChildrenPage, CloneSubtreeResponse, CompletionOutcome, DoneTaskResponse,
+    NextStepSuggestion, PaginatedTasks, PickNextResponse, SpecRevision, Task, TaskLease,
+    TaskSortBy, TaskTransition, TaskWithEvents, WorkspaceStatus,
 };
 use crate::error::{IntentError, Result};
 use crate::tasks::TaskUpdate;
 use chrono::{DateTime, Utc};
 use neo4rs::{query, Graph};
 
+/// Cypher predicate: task `t` has no unfinished `BLOCKED_BY` dependency.
+/// Same rule as the SQLite `pick_next` (any blocking task not yet `done`).
+const UNBLOCKED: &str =
+    "NOT EXISTS { MATCH (t)-[:BLOCKED_BY]->(blocking:Task) WHERE blocking.status <> 'done' }";
+
 /// Task management backed by Neo4j.
 ///
 /// Design notes:
@@ -44,7 +50,8 @@ impl Neo4jTaskManager {
                        CASE t.status \
                          WHEN 'doing' THEN 0 \
                          WHEN 'todo'  THEN 1 \
-                         WHEN 'done'  THEN 2 \
+                         WHEN 'review' THEN 2 \
+                         WHEN 'done'  THEN 3 \
                        END, \
                        t.priority ASC, \
                        t.id ASC",
@@ -371,11 +378,35 @@ impl Neo4jTaskManager {
 
         // Validate status
         if let Some(s) = update.status {
-            if !["todo", "doing", "done"].contains(&s) {
+            if !["todo", "doing", "review", "done"].contains(&s) {
                 return Err(IntentError::InvalidInput(format!("Invalid status: {}", s)));
             }
         }
 
+        if update.estimate_minutes.is_some_and(|m| m < 0) {
+            return Err(IntentError::InvalidInput(
+                "estimate_minutes cannot be negative".to_string(),
+            ));
+        }
+
+        if update.needs_human.is_some() {
+            return Err(IntentError::InvalidInput(
+                "needs_human is not supported by the Neo4j backend".to_string(),
+            ));
+        }
+
+        if update.blocked_reason.is_some() {
+            return Err(IntentError::InvalidInput(
+                "blocked_reason is not supported by the Neo4j backend".to_string(),
+            ));
+        }
+
+        if update.visibility.is_some() {
+            return Err(IntentError::InvalidInput(
+                "visibility is not supported by the Neo4j backend".to_string(),
+            ));
+        }
+
         // Validate owner early
         if let Some(o) = update.owner {
             if o.is_empty() {
@@ -427,6 +458,12 @@ impl Neo4jTaskManager {
         if update.metadata.is_some() {
             set_parts.push("t.metadata = $new_metadata");
         }
+        if update.sort_order.is_some() {
+            set_parts.push("t.sort_order = $new_sort_order");
+        }
+        if update.estimate_minutes.is_some() {
+            set_parts.push("t.estimate_minutes = $new_estimate_minutes");
+        }
         if let Some(s) = update.status {
             set_parts.push("t.status = $new_status");
             match s {
@@ -484,6 +521,12 @@ impl Neo4jTaskManager {
         if let Some(m) = update.metadata {
             q = q.param("new_metadata", m.to_string());
         }
+        if let Some(so) = update.sort_order {
+            q = q.param("new_sort_order", so);
+        }
+        if let Some(em) = update.estimate_minutes {
+            q = q.param("new_estimate_minutes", em);
+        }
         if let Some(s) = update.status {
             q = q.param("new_status", s.to_string());
             let needs_ts = match s {
@@ -738,6 +781,7 @@ impl Neo4jTaskManager {
             TaskSortBy::Time => "ORDER BY \
                  CASE t.status \
                    WHEN 'doing' THEN t.first_doing_at \
+                   WHEN 'review' THEN t.first_doing_at \
                    WHEN 'todo' THEN t.first_todo_at \
                    WHEN 'done' THEN t.first_done_at \
                  END ASC, t.id ASC"
@@ -850,7 +894,7 @@ impl Neo4jTaskManager {
     pub async fn start_task(&self, id: i64, with_events: bool) -> Result<TaskWithEvents> {
         self.check_task_exists(id).await?;
 
-        // Check blocking dependencies (no-op when BLOCKED_BY relationships don't exist yet)
+        // Check blocking dependencies
         let blocking_ids = self.get_blocking_task_ids(id).await?;
         if !blocking_ids.is_empty() {
             return Err(IntentError::TaskBlocked {
@@ -909,6 +953,7 @@ impl Neo4jTaskManager {
             Ok(TaskWithEvents {
                 task,
                 events_summary: None,
+                outcome: None,
             })
         }
     }
@@ -917,8 +962,7 @@ impl Neo4jTaskManager {
     ///
     /// State changes (status + focus) run in a single transaction.
     /// Returns a DoneTaskResponse with next-step suggestion.
-    pub async fn done_task_by_id(&self, id: i64, is_ai_caller: bool) -> Result<DoneTaskResponse> {
-        let _ = is_ai_caller; // Signature aligned with SQLite; Neo4j does not do LLM synthesis yet
+    pub async fn done_task_by_id(&self, id: i64) -> Result<DoneTaskResponse> {
         let task = self.get_task(id).await?;
 
         // Check incomplete children
@@ -1002,11 +1046,13 @@ impl Neo4jTaskManager {
                 current_task_id: actual_current_task_id,
             },
             next_step_suggestion,
+            warnings: Vec::new(),
+            newly_unblocked: Vec::new(),
         })
     }
 
     /// Complete the current focused task.
-    pub async fn done_task(&self, is_ai_caller: bool) -> Result<DoneTaskResponse> {
+    pub async fn done_task(&self) -> Result<DoneTaskResponse> {
         let session_id = crate::workspace::resolve_session_id(None);
 
         let mut result = self
@@ -1033,7 +1079,7 @@ impl Neo4jTaskManager {
                 .to_string(),
         ))?;
 
-        self.done_task_by_id(id, is_ai_caller).await
+        self.done_task_by_id(id).await
     }
 
     /// Suggest the next task to work on based on context.
@@ -1043,6 +1089,8 @@ impl Neo4jTaskManager {
     /// 2. Todo subtasks of current focus
     /// 3. Top-level doing tasks
     /// 4. Top-level todo tasks
+    ///
+    /// Tasks blocked by unfinished dependencies are skipped at every level.
     pub async fn pick_next(&self) -> Result<PickNextResponse> {
         let session_id = crate::workspace::resolve_session_id(None);
 
@@ -1118,7 +1166,7 @@ impl Neo4jTaskManager {
             .graph
             .execute(
                 query(
-                    "MATCH (t:Task {project_id: $pid}) WHERE t.status <> 'done' \
+                    "MATCH (t:Task {project_id: $pid}) WHERE t.status IN ['todo', 'doing'] \
                      RETURN count(t) AS cnt",
                 )
                 .param("pid", self.project_id.clone()),
@@ -1196,18 +1244,22 @@ impl Neo4jTaskManager {
 
     /// Get full task context: task + ancestors + siblings + children + dependencies.
     ///
-    /// Fetches task first (needed for parent_id), then fires the remaining 5
+    /// Fetches task first (needed for parent_id), then fires the remaining 6
     /// queries in parallel via `tokio::try_join!` — 2 round-trips total.
     pub async fn get_task_context(&self, id: i64) -> Result<crate::db::models::TaskContext> {
         let task = self.get_task(id).await?;
 
-        let (ancestors, siblings, children, blocking_tasks, blocked_by_tasks) = tokio::try_join!(
+        let (ancestors, siblings, children, blocking_tasks, blocked_by_tasks, descendants) = tokio::try_join!(
             self.get_task_ancestry(id),
             self.get_siblings(id, task.parent_id),
             self.get_children(id),
             self.get_blocking_tasks(id),
             self.get_blocked_by_tasks(id),
+            self.get_descendants(id),
         )?;
+        let estimate = crate::db::models::EstimateRollup::from_tasks(
+            std::iter::once(&task).chain(&descendants),
+        );
 
         Ok(crate::db::models::TaskContext {
             task,
@@ -1218,6 +1270,8 @@ impl Neo4jTaskManager {
                 blocking_tasks,
                 blocked_by_tasks,
             },
+            estimate,
+            links: Vec::new(),
         })
     }
 
@@ -1236,14 +1290,26 @@ impl Neo4jTaskManager {
 
         let event_mgr = super::Neo4jEventManager::new(self.graph.clone(), self.project_id.clone());
 
-        let (ancestors, siblings, children, blocking_tasks, blocked_by_tasks, events_summary) = tokio::try_join!(
+        let (
+            ancestors,
+            siblings,
+            children,
+            blocking_tasks,
+            blocked_by_tasks,
+            descendants,
+            events_summary,
+        ) = tokio::try_join!(
             self.get_task_ancestry(id),
             self.get_siblings(id, task.parent_id),
             self.get_children(id),
             self.get_blocking_tasks(id),
             self.get_blocked_by_tasks(id),
+            self.get_descendants(id),
             event_mgr.get_events_summary(id),
         )?;
+        let estimate = crate::db::models::EstimateRollup::from_tasks(
+            std::iter::once(&task).chain(&descendants),
+        );
 
         let context = crate::db::models::TaskContext {
             task,
@@ -1254,6 +1320,8 @@ impl Neo4jTaskManager {
                 blocking_tasks,
                 blocked_by_tasks,
             },
+            estimate,
+            links: Vec::new(),
         };
 
         Ok((context, events_summary))
@@ -1268,6 +1336,7 @@ impl Neo4jTaskManager {
         Ok(TaskWithEvents {
             task,
             events_summary: Some(events_summary),
+            outcome: None,
         })
     }
 
@@ -1318,8 +1387,8 @@ impl Neo4jTaskManager {
 
     /// Get IDs of incomplete tasks that block the given task via BLOCKED_BY relationships.
     ///
-    /// Returns empty vec when no BLOCKED_BY relationships exist (current state).
-    /// Will automatically work when dependency support is added to the Neo4j schema.
+    /// Mirrors `dependencies::get_incomplete_blocking_tasks`: only `todo` and
+    /// `doing` blockers prevent a start.
     async fn get_blocking_task_ids(&self, task_id: i64) -> Result<Vec<i64>> {
         let mut result = self
             .graph
@@ -1456,13 +1525,14 @@ impl Neo4jTaskManager {
         let mut result = self
             .graph
             .execute(
-                query(
-                    "MATCH (child:Task {project_id: $pid})-[:CHILD_OF]->(parent:Task {project_id: $pid, id: $parent_id}) \
-                     WHERE child.status = $status \
-                     RETURN child \
-                     ORDER BY COALESCE(child.priority, 999) ASC, child.id ASC \
+                query(&format!(
+                    "MATCH (t:Task {{project_id: $pid}})-[:CHILD_OF]->(parent:Task {{project_id: $pid, id: $parent_id}}) \
+                     WHERE t.status = $status AND {} \
+                     RETURN t AS child \
+                     ORDER BY COALESCE(t.priority, 999) ASC, t.id ASC \
                      LIMIT 1",
-                )
+                    UNBLOCKED
+                ))
                 .param("pid", self.project_id.clone())
                 .param("parent_id", parent_id)
                 .param("status", status.to_string()),
@@ -1486,21 +1556,21 @@ impl Neo4jTaskManager {
         status: &str,
         exclude_id: Option<i64>,
     ) -> Result<Option<Task>> {
-        let cypher = if exclude_id.is_some() {
-            "MATCH (t:Task {project_id: $pid}) \
-             WHERE NOT (t)-[:CHILD_OF]->() AND t.status = $status AND t.id <> $exclude_id \
-             RETURN t \
-             ORDER BY COALESCE(t.priority, 999) ASC, t.id ASC \
-             LIMIT 1"
+        let exclude = if exclude_id.is_some() {
+            "AND t.id <> $exclude_id"
         } else {
-            "MATCH (t:Task {project_id: $pid}) \
-             WHERE NOT (t)-[:CHILD_OF]->() AND t.status = $status \
+            ""
+        };
+        let cypher = format!(
+            "MATCH (t:Task {{project_id: $pid}}) \
+             WHERE NOT (t)-[:CHILD_OF]->() AND t.status = $status {} AND {} \
              RETURN t \
              ORDER BY COALESCE(t.priority, 999) ASC, t.id ASC \
-             LIMIT 1"
-        };
+             LIMIT 1",
+            exclude, UNBLOCKED
+        );
 
-        let mut q = query(cypher)
+        let mut q = query(&cypher)
             .param("pid", self.project_id.clone())
             .param("status", status.to_string());
 
@@ -1560,18 +1630,22 @@ impl Neo4jTaskManager {
 
             if remaining_siblings == 0 {
                 Ok(NextStepSuggestion::ParentIsReady {
-                    message: format!(
-                        "All sub-tasks of parent #{} '{}' are now complete. The parent task is ready for your attention.",
-                        parent_task_id, parent_name
+                    message: crate::i18n::t(
+                        "next.parent_ready",
+                        &[("parent_id", &parent_task_id), ("parent", &parent_name)],
                     ),
                     parent_task_id,
                     parent_task_name: parent_name,
                 })
             } else {
                 Ok(NextStepSuggestion::SiblingTasksRemain {
-                    message: format!(
-                        "Task #{} completed. Parent task #{} '{}' has other sub-tasks remaining.",
-                        id, parent_task_id, parent_name
+                    message: crate::i18n::t(
+                        "next.siblings_remain",
+                        &[
+                            ("id", &id),
+                            ("parent_id", &parent_task_id),
+                            ("parent", &parent_name),
+                        ],
                     ),
                     parent_task_id,
                     parent_task_name: parent_name,
@@ -1583,9 +1657,9 @@ impl Neo4jTaskManager {
 
             if child_count > 0 {
                 Ok(NextStepSuggestion::TopLevelTaskCompleted {
-                    message: format!(
-                        "Top-level task #{} '{}' has been completed. Well done!",
-                        id, task_name
+                    message: crate::i18n::t(
+                        "next.top_level_done",
+                        &[("id", &id), ("name", &task_name)],
                     ),
                     completed_task_id: id,
                     completed_task_name: task_name.to_string(),
@@ -1615,15 +1689,15 @@ impl Neo4jTaskManager {
 
                 if remaining_tasks == 0 {
                     Ok(NextStepSuggestion::WorkspaceIsClear {
-                        message: format!(
-                            "Project complete! Task #{} was the last remaining task. There are no more 'todo' or 'doing' tasks.",
-                            id
-                        ),
+                        message: crate::i18n::t("next.workspace_clear", &[("id", &id)]),
                         completed_task_id: id,
                     })
                 } else {
                     Ok(NextStepSuggestion::NoParentContext {
-                        message: format!("Task #{} '{}' has been completed.", id, task_name),
+                        message: crate::i18n::t(
+                            "next.task_done",
+                            &[("id", &id), ("name", &task_name)],
+                        ),
                         completed_task_id: id,
                         completed_task_name: task_name.to_string(),
                     })
@@ -1663,6 +1737,8 @@ pub(crate) fn node_to_task(node: &neo4rs::Node) -> Result<Task> {
     let priority: Option<i32> = node.get("priority").ok();
     let active_form: Option<String> = node.get("active_form").ok();
     let metadata: Option<String> = node.get("metadata").ok();
+    let sort_order: Option<i64> = node.get("sort_order").ok();
+    let estimate_minutes: Option<i64> = node.get("estimate_minutes").ok();
 
     let status: String = node.get("status").unwrap_or_else(|_| "todo".into());
     let owner: String = node.get("owner").unwrap_or_else(|_| "human".into());
@@ -1685,6 +1761,11 @@ pub(crate) fn node_to_task(node: &neo4rs::Node) -> Result<Task> {
         active_form,
         owner,
         metadata,
+        sort_order,
+        estimate_minutes,
+        visibility: Default::default(),
+        blocked_reason: None,
+        checklist: None,
     })
 }
 
@@ -1847,24 +1928,154 @@ impl crate::backend::TaskBackend for Neo4jTaskManager {
         self.start_task(id, with_events)
     }
 
-    fn done_task(
-        &self,
-        is_ai_caller: bool,
-    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
-        self.done_task(is_ai_caller)
+    fn done_task(&self) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task()
     }
 
     fn done_task_by_id(
         &self,
         id: i64,
-        is_ai_caller: bool,
     ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
-        self.done_task_by_id(id, is_ai_caller)
+        self.done_task_by_id(id)
     }
 
     fn pick_next(&self) -> impl std::future::Future<Output = Result<PickNextResponse>> + Send {
         self.pick_next()
     }
+
+    async fn spec_history(&self, _id: i64) -> Result<Vec<SpecRevision>> {
+        Err(IntentError::InvalidInput(
+            "Spec history is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn find_children(
+        &self,
+        _parent_id: Option<i64>,
+        _cursor: Option<i64>,
+        _limit: Option<i64>,
+    ) -> Result<ChildrenPage> {
+        Err(IntentError::InvalidInput(
+            "Paginated children are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn transitions(&self, _id: i64) -> Result<Vec<TaskTransition>> {
+        Err(IntentError::InvalidInput(
+            "Status history is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn done_task_with_outcome(
+        &self,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
+        if outcome.is_some() {
+            return Err(IntentError::InvalidInput(
+                "Completion outcomes are not supported by the Neo4j backend".to_string(),
+            ));
+        }
+        self.done_task().await
+    }
+
+    async fn done_task_by_id_with_outcome(
+        &self,
+        id: i64,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
+        if outcome.is_some() {
+            return Err(IntentError::InvalidInput(
+                "Completion outcomes are not supported by the Neo4j backend".to_string(),
+            ));
+        }
+        self.done_task_by_id(id).await
+    }
+
+    async fn claim_next_for(
+        &self,
+        _session_id: &str,
+        _lease: chrono::Duration,
+    ) -> Result<Option<Task>> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn renew_lease(
+        &self,
+        _task_id: i64,
+        _session_id: &str,
+        _lease: chrono::Duration,
+    ) -> Result<TaskLease> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn release_lease(&self, _task_id: i64, _session_id: &str) -> Result<bool> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn explain_next(&self) -> Result<PickNextResponse> {
+        Err(IntentError::InvalidInput(
+            "Explaining the next task is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn age_priorities(&self) -> Result<crate::priority::AgingResult> {
+        Err(IntentError::InvalidInput(
+            "Priority aging is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn sweep_stale(
+        &self,
+        _days: Option<f64>,
+        _demote: bool,
+    ) -> Result<crate::stale::StaleSweep> {
+        Err(IntentError::InvalidInput(
+            "Stale task detection is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn estimate_complexity(
+        &self,
+        _id: i64,
+        _apply: bool,
+    ) -> Result<Option<crate::priority::ComplexityEstimate>> {
+        Err(IntentError::InvalidInput(
+            "Complexity estimation is not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn checklist(&self, _task_id: i64) -> Result<Vec<ChecklistItem>> {
+        Err(IntentError::InvalidInput(
+            "Checklists are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn set_checklist_item(
+        &self,
+        _task_id: i64,
+        _position: i64,
+        _checked: bool,
+    ) -> Result<ChecklistItem> {
+        Err(IntentError::InvalidInput(
+            "Checklists are not supported by the Neo4j backend".to_string(),
+        ))
+    }
+
+    async fn clone_subtree(
+        &self,
+        _id: i64,
+        _options: crate::tasks::CloneOptions,
+    ) -> Result<CloneSubtreeResponse> {
+        Err(IntentError::InvalidInput(
+            "Cloning tasks is not supported by the Neo4j backend".to_string(),
+        ))
+    }
 }
 
 // ── Tests ───────────────────────────────────────────────────────
@@ -0,0 +1,395 @@
+//! Desktop notifications for transitions a human should hear about
+//!
+//! The WebSocket and MCP channels only reach software. This module pops an OS
+//! notification when a human-owned task becomes unblocked, when an AI asks a
+//! human to complete (review) a task, when a plan fails, or when the time of a
+//! focus session (`ie focus start`) is up.
+//!
+//! Disabled by default. Enable per project with `ie config set notify.desktop true`;
+//! individual event types can then be turned off, e.g.
+//! `ie config set notify.desktop.plan_failed false`.
+//!
+//! The same notifications are also POSTed as JSON to every URL listed under
+//! `notifications.webhooks` in the project's config.toml (see `crate::config`),
+//! whatever the desktop switches say. Webhooks hear about every unblocked task,
+//! AI-owned ones included.
+
+use crate::cli_handlers::config_commands::config_get;
+use crate::db::models::UnblockedTask;
+use crate::settings::parse_bool;
+use sqlx::SqlitePool;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+/// Master switch config key
+pub const CONFIG_KEY: &str = "notify.desktop";
+
+const APP_NAME: &str = "Intent-Engine";
+
+/// Webhooks must not hold up the command that triggered them for long
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Transitions that can trigger a desktop notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopEventKind {
+    /// A human-owned task lost its last incomplete blocker
+    Unblocked,
+    /// An AI tried to complete a human-owned task and needs the human to sign off
+    ReviewRequested,
+    /// A plan was rejected or failed to apply
+    PlanFailed,
+    /// The timebox of a focus session ran out
+    FocusEnded,
+}
+
+impl DesktopEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DesktopEventKind::Unblocked => "unblocked",
+            DesktopEventKind::ReviewRequested => "review_requested",
+            DesktopEventKind::PlanFailed => "plan_failed",
+            DesktopEventKind::FocusEnded => "focus_ended",
+        }
+    }
+
+    /// Per-type config key, e.g. `notify.desktop.unblocked`
+    pub fn config_key(&self) -> String {
+        format!("{}.{}", CONFIG_KEY, self.as_str())
+    }
+}
+
+/// A notification ready to be shown
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DesktopNotification {
+    pub kind: DesktopEventKind,
+    pub title: String,
+    pub body: String,
+}
+
+/// Something that can put a notification on the user's screen
+pub trait DesktopBackend: Send + Sync {
+    fn show(&self, notification: &DesktopNotification) -> std::io::Result<()>;
+}
+
+/// Default backend: the platform's own notification command
+///
+/// `notify-send` on Linux/BSD, `osascript` on macOS and PowerShell on Windows.
+/// Title and body are passed through the environment so they never need quoting.
+pub struct SystemBackend;
+
+impl SystemBackend {
+    fn command() -> Command {
+        if cfg!(target_os = "macos") {
+            let mut cmd = Command::new("osascript");
+            cmd.args([
+                "-e",
+                r#"display notification (system attribute "IE_NOTIFY_BODY") with title (system attribute "IE_NOTIFY_TITLE")"#,
+            ]);
+            cmd
+        } else if cfg!(windows) {
+            let mut cmd = Command::new("powershell");
+            cmd.args([
+                "-NoProfile",
+                "-Command",
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 $n = New-Object System.Windows.Forms.NotifyIcon; \
+                 $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+                 $n.ShowBalloonTip(5000, $env:IE_NOTIFY_TITLE, $env:IE_NOTIFY_BODY, 'Info'); \
+                 Start-Sleep -Seconds 6; $n.Dispose()",
+            ]);
+            cmd
+        } else {
+            let mut cmd = Command::new("sh");
+            cmd.args([
+                "-c",
+                r#"exec notify-send --app-name="$IE_NOTIFY_APP" "$IE_NOTIFY_TITLE" "$IE_NOTIFY_BODY""#,
+            ]);
+            cmd
+        }
+    }
+}
+
+impl DesktopBackend for SystemBackend {
+    fn show(&self, notification: &DesktopNotification) -> std::io::Result<()> {
+        let mut child = Self::command()
+            .env("IE_NOTIFY_APP", APP_NAME)
+            .env("IE_NOTIFY_TITLE", &notification.title)
+            .env("IE_NOTIFY_BODY", &notification.body)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        // Reap in the background so a long-running Dashboard leaves no zombies
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+/// Sends desktop notifications according to the project's config
+pub struct DesktopNotifier<'a> {
+    pool: &'a SqlitePool,
+    backend: Arc<dyn DesktopBackend>,
+}
+
+impl<'a> DesktopNotifier<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self::with_backend(pool, Arc::new(SystemBackend))
+    }
+
+    pub fn with_backend(pool: &'a SqlitePool, backend: Arc<dyn DesktopBackend>) -> Self {
+        Self { pool, backend }
+    }
+
+    /// Whether notifications of this kind are switched on for the project
+    pub async fn is_enabled(&self, kind: DesktopEventKind) -> bool {
+        let master = config_get(self.pool, CONFIG_KEY).await.ok().flatten();
+        if !master.as_deref().and_then(parse_bool).unwrap_or(false) {
+            return false;
+        }
+
+        match config_get(self.pool, &kind.config_key()).await {
+            Ok(Some(value)) => parse_bool(&value).unwrap_or(false),
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Webhook URLs from the project's config.toml
+    fn webhooks(&self) -> Vec<String> {
+        match crate::config::ProjectConfig::for_pool(self.pool) {
+            Ok(config) => config.notifications.webhooks,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring invalid project config for webhooks");
+                Vec::new()
+            },
+        }
+    }
+
+    /// Show a notification if its kind is enabled and send it to the
+    /// configured webhooks; failures are only logged
+    pub async fn notify(&self, notification: DesktopNotification) {
+        if self.is_enabled(notification.kind).await {
+            if let Err(e) = self.backend.show(&notification) {
+                tracing::debug!(error = %e, kind = notification.kind.as_str(), "Desktop notification failed");
+            }
+        }
+        self.post_webhooks(&notification).await;
+    }
+
+    /// POST a notification to the configured webhooks
+    async fn post_webhooks(&self, notification: &DesktopNotification) {
+        let webhooks = self.webhooks();
+        if webhooks.is_empty() {
+            return;
+        }
+        let client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::debug!(error = %e, "Failed to create webhook client");
+                return;
+            },
+        };
+        for url in webhooks {
+            let result = client
+                .post(&url)
+                .json(notification)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status());
+            if let Err(e) = result {
+                tracing::warn!(error = %e, url = %url, "Webhook notification failed");
+            }
+        }
+    }
+
+    /// Call with the tasks a completion made ready to start; only
+    /// human-owned ones pop up on the desktop
+    pub async fn tasks_unblocked(&self, tasks: &[UnblockedTask]) {
+        for task in tasks {
+            let notification = DesktopNotification {
+                kind: DesktopEventKind::Unblocked,
+                title: "Task unblocked".to_string(),
+                body: format!("#{} {} is ready to start", task.id, task.name),
+            };
+            if task.owner == "human" {
+                self.notify(notification).await;
+            } else {
+                self.post_webhooks(&notification).await;
+            }
+        }
+    }
+
+    /// An AI wants a human to complete or approve a task
+    pub async fn review_requested(&self, task_id: i64, task_name: &str) {
+        self.notify(DesktopNotification {
+            kind: DesktopEventKind::ReviewRequested,
+            title: "Review requested".to_string(),
+            body: format!(
+                "AI finished #{} {} and is waiting for your review",
+                task_id, task_name
+            ),
+        })
+        .await;
+    }
+
+    /// A plan could not be applied
+    pub async fn plan_failed(&self, error: &str) {
+        let first_line = error.lines().next().unwrap_or(error);
+        self.notify(DesktopNotification {
+            kind: DesktopEventKind::PlanFailed,
+            title: "Plan failed".to_string(),
+            body: first_line.to_string(),
+        })
+        .await;
+    }
+
+    /// A focus session's time is up
+    pub async fn focus_ended(&self, task_id: i64, task_name: &str, minutes: u32) {
+        self.notify(DesktopNotification {
+            kind: DesktopEventKind::FocusEnded,
+            title: "Focus session over".to_string(),
+            body: format!(
+                "{} min on #{} {} are up; wrap up with 'ie focus stop'",
+                minutes, task_id, task_name
+            ),
+        })
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_handlers::config_commands::config_set;
+    use crate::dependencies::add_dependency;
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingBackend {
+        shown: Mutex<Vec<DesktopNotification>>,
+    }
+
+    impl DesktopBackend for RecordingBackend {
+        fn show(&self, notification: &DesktopNotification) -> std::io::Result<()> {
+            self.shown.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let ctx = TestContext::new().await;
+        let backend = Arc::new(RecordingBackend::default());
+        let notifier = DesktopNotifier::with_backend(ctx.pool(), backend.clone());
+
+        notifier.plan_failed("boom").await;
+        assert!(backend.shown.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_per_kind_switch() {
+        let ctx = TestContext::new().await;
+        config_set(ctx.pool(), CONFIG_KEY, "true").await.unwrap();
+        config_set(
+            ctx.pool(),
+            &DesktopEventKind::PlanFailed.config_key(),
+            "false",
+        )
+        .await
+        .unwrap();
+
+        let backend = Arc::new(RecordingBackend::default());
+        let notifier = DesktopNotifier::with_backend(ctx.pool(), backend.clone());
+        notifier.plan_failed("boom").await;
+        notifier.review_requested(3, "Ship it").await;
+
+        let shown = backend.shown.lock().unwrap();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].kind, DesktopEventKind::ReviewRequested);
+        assert!(shown[0].body.contains("#3 Ship it"));
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_receive_notifications() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let ctx = TestContext::new().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine/config.toml"),
+            format!("[notifications]\nwebhooks = [\"{}\"]\n", url),
+        )
+        .unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !String::from_utf8_lossy(&request).contains("Ship it") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        // Desktop notifications are off; the webhook still fires
+        let backend = Arc::new(RecordingBackend::default());
+        let notifier = DesktopNotifier::with_backend(ctx.pool(), backend.clone());
+        notifier.review_requested(3, "Ship it").await;
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert!(request.contains(r#""kind":"review_requested""#));
+        assert!(backend.shown.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unblocked_only_after_last_blocker() {
+        let ctx = TestContext::new().await;
+        config_set(ctx.pool(), CONFIG_KEY, "on").await.unwrap();
+        let task_mgr = TaskManager::new(ctx.pool());
+
+        let a = task_mgr
+            .add_task("A", None, None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        let b = task_mgr
+            .add_task("B", None, None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        let human = task_mgr
+            .add_task("Deploy", None, None, None, None, None)
+            .await
+            .unwrap();
+        add_dependency(ctx.pool(), a.id, human.id).await.unwrap();
+        add_dependency(ctx.pool(), b.id, human.id).await.unwrap();
+
+        let backend = Arc::new(RecordingBackend::default());
+        let notifier = DesktopNotifier::with_backend(ctx.pool(), backend.clone());
+
+        let done = task_mgr.done_task_by_id(a.id).await.unwrap();
+        notifier.tasks_unblocked(&done.newly_unblocked).await;
+        assert!(backend.shown.lock().unwrap().is_empty());
+
+        let done = task_mgr.done_task_by_id(b.id).await.unwrap();
+        notifier.tasks_unblocked(&done.newly_unblocked).await;
+        let shown = backend.shown.lock().unwrap();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].kind, DesktopEventKind::Unblocked);
+        assert_eq!(
+            shown[0].body,
+            format!("#{} Deploy is ready to start", human.id)
+        );
+    }
+}
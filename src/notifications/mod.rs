@@ -0,0 +1,130 @@
+/// Unified notification infrastructure for Dashboard WebSocket communication
+///
+/// This module provides a centralized NotificationSender that handles the common
+/// pattern of sending database operation notifications via WebSocket.
+use crate::dashboard::websocket::{DatabaseOperationPayload, WebSocketState};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+pub mod desktop;
+pub mod queue;
+
+/// Centralized notification sender for database operations
+///
+/// Routes database operation payloads to the Dashboard UI clients that
+/// follow them (see `WebSocketState::broadcast_db_operation`). With an
+/// offline queue, payloads nobody received are kept for the next Dashboard
+/// connection (see [`queue::OfflineQueue`]).
+pub struct NotificationSender {
+    ws_state: Option<Arc<WebSocketState>>,
+    offline_queue: Option<SqlitePool>,
+}
+
+impl NotificationSender {
+    /// Create a new NotificationSender
+    ///
+    /// # Arguments
+    /// * `ws_state` - Optional WebSocket state for Dashboard UI notifications
+    pub fn new(ws_state: Option<Arc<WebSocketState>>) -> Self {
+        Self {
+            ws_state,
+            offline_queue: None,
+        }
+    }
+
+    /// Keep undelivered notifications in this project database's queue
+    pub fn with_offline_queue(mut self, pool: SqlitePool) -> Self {
+        self.offline_queue = Some(pool);
+        self
+    }
+
+    /// Send a database operation notification via all available channels
+    ///
+    /// Broadcasts to the Dashboard WebSocket (if connected), which wraps the
+    /// payload in a ProtocolMessage per client: the full `db_operation`, or a
+    /// `db_patch` for clients on topic subscriptions. When no UI client
+    /// follows the project, the payload goes to the offline queue.
+    ///
+    /// Without a WebSocket state (CLI context) nothing is sent: the CLI
+    /// reports through `CliNotifier` and calls [`Self::queue`] when that fails.
+    ///
+    /// # Arguments
+    /// * `payload` - The database operation payload to send
+    pub async fn send(&self, payload: DatabaseOperationPayload) {
+        if let Some(ws) = &self.ws_state {
+            if ws.broadcast_db_operation(&payload).await == 0 {
+                self.queue(payload).await;
+            }
+        }
+    }
+
+    /// Send a protocol message to the MCP client connected for a project
+    ///
+    /// Does nothing outside the Dashboard or when no such client is connected;
+    /// agents are not sent missed messages later.
+    pub async fn send_to_mcp<T: serde::Serialize>(
+        &self,
+        project_path: &str,
+        message_type: &str,
+        payload: T,
+    ) {
+        if let Some(ws) = &self.ws_state {
+            ws.send_to_mcp(project_path, message_type, payload).await;
+        }
+    }
+
+    /// Store a notification no Dashboard received, to be replayed on reconnect
+    ///
+    /// Does nothing without an offline queue. Failures are logged at debug
+    /// level, never returned or printed: a missed notification must not fail
+    /// or clutter the operation itself.
+    pub async fn queue(&self, payload: DatabaseOperationPayload) {
+        let Some(pool) = &self.offline_queue else {
+            return;
+        };
+        if let Err(e) = queue::OfflineQueue::new(pool).enqueue(&payload).await {
+            tracing::debug!(error = %e, "Failed to queue notification for the Dashboard");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_sender_new() {
+        let sender = NotificationSender::new(None);
+        assert!(sender.ws_state.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_send_with_no_channels() {
+        // Should not panic when no channels are configured
+        let sender = NotificationSender::new(None);
+        let payload = DatabaseOperationPayload {
+            operation: "create".to_string(),
+            entity: "task".to_string(),
+            affected_ids: vec![1],
+            data: None,
+            project_path: "/test".to_string(),
+        };
+
+        sender.send(payload).await; // Should complete without error
+    }
+
+    #[tokio::test]
+    async fn test_send_queues_when_no_ui_client() {
+        let ctx = crate::test_utils::test_helpers::TestContext::new().await;
+        let sender = NotificationSender::new(Some(Arc::new(WebSocketState::new())))
+            .with_offline_queue(ctx.pool().clone());
+
+        sender
+            .send(DatabaseOperationPayload::task_deleted(1, "/test"))
+            .await;
+
+        let queued = queue::OfflineQueue::new(ctx.pool()).drain().await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].operation, "delete");
+    }
+}
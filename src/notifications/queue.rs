@@ -0,0 +1,223 @@
+/// Offline notification queue
+///
+/// Database operations that no Dashboard received (the daemon was down, or
+/// no UI client was connected) are stored in the project's
+/// `notification_queue` table and replayed when a UI client connects.
+/// The queue keeps one row per entity: a newer notification about the same
+/// task or event replaces the older one.
+use crate::dashboard::websocket::DatabaseOperationPayload;
+use crate::error::Result;
+use sqlx::SqlitePool;
+
+/// Oldest rows beyond this are dropped, so that projects never opened in
+/// the Dashboard do not grow the queue without bound
+pub const MAX_QUEUED_NOTIFICATIONS: i64 = 1000;
+
+pub struct OfflineQueue<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> OfflineQueue<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a notification, merging it with a queued one for the same entity
+    ///
+    /// A queued `create` stays a `create` (with the newer data) when the
+    /// entity is updated, and disappears when the entity is deleted: the
+    /// Dashboard never saw it.
+    pub async fn enqueue(&self, payload: &DatabaseOperationPayload) -> Result<()> {
+        let entity_key = entity_key(&payload.affected_ids);
+        // Take the write lock up front: a deferred transaction that reads
+        // first fails at once with "database is locked" when another process
+        // writes in between, instead of waiting out the busy timeout
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let queued: Option<(i64, String)> = sqlx::query_as(
+            "SELECT id, operation FROM notification_queue WHERE entity = ? AND entity_key = ?",
+        )
+        .bind(&payload.entity)
+        .bind(&entity_key)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        match (queued, payload.operation.as_str()) {
+            (Some((id, queued_op)), "delete") if queued_op == "create" => {
+                sqlx::query("DELETE FROM notification_queue WHERE id = ?")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            },
+            (Some((id, queued_op)), "update") if queued_op == "create" => {
+                let mut merged = payload.clone();
+                merged.operation = queued_op;
+                sqlx::query("UPDATE notification_queue SET payload = ? WHERE id = ?")
+                    .bind(serde_json::to_string(&merged)?)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            },
+            (queued, _) => {
+                if let Some((id, _)) = queued {
+                    sqlx::query("DELETE FROM notification_queue WHERE id = ?")
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT INTO notification_queue (entity, entity_key, operation, payload, queued_at)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&payload.entity)
+                .bind(&entity_key)
+                .bind(&payload.operation)
+                .bind(serde_json::to_string(payload)?)
+                .bind(chrono::Utc::now())
+                .execute(&mut *tx)
+                .await?;
+            },
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM notification_queue
+            WHERE id NOT IN (SELECT id FROM notification_queue ORDER BY id DESC LIMIT ?)
+            "#,
+        )
+        .bind(MAX_QUEUED_NOTIFICATIONS)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Remove and return every queued notification, oldest first
+    pub async fn drain(&self) -> Result<Vec<DatabaseOperationPayload>> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        let rows: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, payload FROM notification_queue ORDER BY id")
+                .fetch_all(&mut *tx)
+                .await?;
+        let Some((last_id, _)) = rows.last() else {
+            return Ok(Vec::new());
+        };
+        sqlx::query("DELETE FROM notification_queue WHERE id <= ?")
+            .bind(last_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, payload)| match serde_json::from_str(&payload) {
+                Ok(payload) => Some(payload),
+                Err(e) => {
+                    tracing::warn!(id, error = %e, "Dropping unreadable queued notification");
+                    None
+                },
+            })
+            .collect())
+    }
+
+    /// Number of queued notifications
+    pub async fn len(&self) -> Result<i64> {
+        let count = sqlx::query_scalar("SELECT COUNT(*) FROM notification_queue")
+            .fetch_one(self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    pub async fn is_empty(&self) -> Result<bool> {
+        Ok(self.len().await? == 0)
+    }
+}
+
+/// Deduplication key: the affected IDs, or `*` for notifications without
+/// any (a plan's `batch_update`)
+fn entity_key(affected_ids: &[i64]) -> String {
+    if affected_ids.is_empty() {
+        return "*".to_string();
+    }
+    affected_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_helpers::TestContext;
+
+    fn task(operation: &str, id: i64, name: &str) -> DatabaseOperationPayload {
+        let data = (operation != "delete").then(|| serde_json::json!({"id": id, "name": name}));
+        DatabaseOperationPayload::new(operation, "task", vec![id], data, "/p")
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_merges_per_entity() {
+        let ctx = TestContext::new().await;
+        let queue = OfflineQueue::new(ctx.pool());
+
+        queue.enqueue(&task("create", 1, "a")).await.unwrap();
+        queue.enqueue(&task("update", 1, "b")).await.unwrap();
+        queue.enqueue(&task("update", 2, "x")).await.unwrap();
+        queue.enqueue(&task("update", 2, "y")).await.unwrap();
+        queue.enqueue(&task("create", 3, "gone")).await.unwrap();
+        queue.enqueue(&task("delete", 3, "")).await.unwrap();
+
+        let drained = queue.drain().await.unwrap();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[0].operation, "create");
+        assert_eq!(drained[0].data.as_ref().unwrap()["name"], "b");
+        assert_eq!(drained[1].operation, "update");
+        assert_eq!(drained[1].data.as_ref().unwrap()["name"], "y");
+
+        assert!(queue.is_empty().await.unwrap());
+        assert!(queue.drain().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_keeps_newest_rows() {
+        let ctx = TestContext::new().await;
+        let queue = OfflineQueue::new(ctx.pool());
+
+        for id in 0..MAX_QUEUED_NOTIFICATIONS + 5 {
+            queue.enqueue(&task("update", id, "t")).await.unwrap();
+        }
+
+        assert_eq!(queue.len().await.unwrap(), MAX_QUEUED_NOTIFICATIONS);
+        assert_eq!(queue.drain().await.unwrap()[0].affected_ids, vec![5]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_processes_all_enqueue() {
+        let ctx = TestContext::new().await;
+        let db_path = ctx.project_root().join(".intent-engine").join("project.db");
+
+        // One pool per simulated CLI process, all writing at once
+        let mut handles = Vec::new();
+        for process in 0..4 {
+            let pool = crate::db::create_pool(&db_path).await.unwrap();
+            handles.push(tokio::spawn(async move {
+                for n in 0..25 {
+                    OfflineQueue::new(&pool)
+                        .enqueue(&task("update", process * 100 + n, "t"))
+                        .await
+                        .unwrap();
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(OfflineQueue::new(ctx.pool()).len().await.unwrap(), 100);
+    }
+}
@@ -0,0 +1,667 @@
+//! Task timeline export to OpenTelemetry traces
+//!
+//! Application logs go through `logging`; this module exports the domain
+//! timeline instead, so agent work shows up in Jaeger or Tempo beside the
+//! traces of the systems it touches:
+//!
+//! - every task is a span, from its first todo (or doing) to its first done
+//!   time; open tasks end at the time of the export
+//! - a subtask's span is a child of its parent's span, and each root task
+//!   with its descendants is one trace
+//! - events and status transitions are span events
+//!
+//! Trace and span IDs are derived from the service name and the task IDs, so
+//! exporting a task twice produces the same IDs. Only shared tasks and
+//! normal events are exported.
+//!
+//! The payload is OTLP/HTTP JSON. `ie export traces` sends it to
+//! `otel.endpoint` (or `OTEL_EXPORTER_OTLP_ENDPOINT`) or writes it to a file;
+//! with an endpoint configured, the Dashboard's periodic maintenance also
+//! exports tasks as they are completed (`export_finished`).
+
+use crate::cli_handlers::config_commands::{config_get, config_set};
+use crate::error::{IntentError, Result};
+use crate::settings::SettingsManager;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Timeout of one export request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Standard OTLP environment variable for extra request headers (`k=v,k2=v2`)
+pub const HEADERS_ENV: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+
+/// `workspace_state` key: completion time of the last task sent by
+/// `export_finished`
+const EXPORTED_UNTIL_KEY: &str = "otel.exported_until";
+
+/// Longest event message kept in a span event attribute
+const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Where and as whom traces are exported
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtelConfig {
+    /// OTLP/HTTP base URL; None when export is not configured
+    pub endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl OtelConfig {
+    /// Load from project settings (`otel.endpoint`, `otel.service_name`);
+    /// the standard `OTEL_*` environment variables take priority
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let settings = SettingsManager::new(pool);
+        let endpoint = match env_value("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            Some(endpoint) => Some(endpoint),
+            None => settings.get_text("otel.endpoint").await?,
+        };
+        let service_name = match env_value("OTEL_SERVICE_NAME") {
+            Some(name) => name,
+            None => settings
+                .get_text("otel.service_name")
+                .await?
+                .unwrap_or_else(|| "intent-engine".to_string()),
+        };
+        Ok(Self {
+            endpoint: endpoint.filter(|e| !e.trim().is_empty()),
+            service_name,
+        })
+    }
+}
+
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|v| !v.is_empty())
+}
+
+/// Spans ready to be sent
+#[derive(Debug, Clone)]
+pub struct TraceBatch {
+    service_name: String,
+    spans: Vec<Span>,
+    /// Latest completion time among the exported tasks
+    latest_done_at: Option<DateTime<Utc>>,
+}
+
+/// What an export sent or wrote
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ExportSummary {
+    pub spans: usize,
+    pub span_events: usize,
+    /// Traces endpoint the spans were sent to; None when written to a file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+impl TraceBatch {
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn summary(&self, endpoint: Option<String>) -> ExportSummary {
+        ExportSummary {
+            spans: self.spans.len(),
+            span_events: self.spans.iter().map(|span| span.events.len()).sum(),
+            endpoint,
+        }
+    }
+
+    /// The OTLP/HTTP JSON request body (`ExportTraceServiceRequest`)
+    pub fn to_otlp_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [string_attribute("service.name", &self.service_name)],
+                },
+                "scopeSpans": [{
+                    "scope": {
+                        "name": "intent-engine",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                    "spans": self.spans,
+                }],
+            }],
+        })
+    }
+
+    /// POST the batch to the traces endpoint of an OTLP/HTTP collector
+    pub async fn send(&self, endpoint: &str) -> Result<String> {
+        let url = traces_url(endpoint)?;
+        let mut request = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| IntentError::OtherError(e.into()))?
+            .post(url.clone())
+            .json(&self.to_otlp_json());
+        for (name, value) in env_headers() {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            IntentError::OtherError(anyhow::anyhow!("OTLP export to {} failed: {}", url, e))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(IntentError::OtherError(anyhow::anyhow!(
+                "OTLP collector at {} answered {}: {}",
+                url,
+                status,
+                body.trim()
+            )));
+        }
+        Ok(url.to_string())
+    }
+}
+
+/// `{endpoint}/v1/traces`, unless the endpoint already names the traces path
+fn traces_url(endpoint: &str) -> Result<reqwest::Url> {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    let full = if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    };
+    reqwest::Url::parse(&full).map_err(|e| {
+        IntentError::InvalidInput(format!("Invalid OTLP endpoint '{}': {}", endpoint, e))
+    })
+}
+
+/// Headers from `OTEL_EXPORTER_OTLP_HEADERS`, e.g. for collector auth
+fn env_headers() -> Vec<(String, String)> {
+    env_value(HEADERS_ENV)
+        .map(|raw| parse_headers(&raw))
+        .unwrap_or_default()
+}
+
+fn parse_headers(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// Builds trace batches from the task tree
+pub struct TraceExporter<'a> {
+    pool: &'a SqlitePool,
+    service_name: String,
+}
+
+impl<'a> TraceExporter<'a> {
+    pub fn new(pool: &'a SqlitePool, service_name: impl Into<String>) -> Self {
+        Self {
+            pool,
+            service_name: service_name.into(),
+        }
+    }
+
+    /// Spans of all tasks, or of one task and its descendants
+    pub async fn collect(&self, task_id: Option<i64>) -> Result<TraceBatch> {
+        let tasks = self.load_tasks().await?;
+        if let Some(id) = task_id {
+            if !tasks.contains_key(&id) {
+                return Err(IntentError::TaskNotFound(id));
+            }
+        }
+        let selected: Vec<i64> = tasks
+            .keys()
+            .copied()
+            .filter(|&id| task_id.is_none_or(|root| is_within(&tasks, id, root)))
+            .collect();
+        self.build(&tasks, selected).await
+    }
+
+    /// Spans of the tasks completed after `since` (all completed tasks if None)
+    pub async fn collect_finished(&self, since: Option<DateTime<Utc>>) -> Result<TraceBatch> {
+        let tasks = self.load_tasks().await?;
+        let selected: Vec<i64> = tasks
+            .values()
+            .filter(|task| {
+                task.first_done_at
+                    .is_some_and(|done| since.is_none_or(|since| done > since))
+            })
+            .map(|task| task.id)
+            .collect();
+        self.build(&tasks, selected).await
+    }
+
+    async fn load_tasks(&self) -> Result<HashMap<i64, TaskRow>> {
+        let rows: Vec<TaskRow> = sqlx::query_as(
+            r#"
+            SELECT id, parent_id, name, status, priority, owner,
+                   first_todo_at, first_doing_at, first_done_at
+            FROM tasks
+            WHERE visibility = 'shared'
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|row| (row.id, row)).collect())
+    }
+
+    async fn build(&self, tasks: &HashMap<i64, TaskRow>, mut ids: Vec<i64>) -> Result<TraceBatch> {
+        ids.sort_unstable();
+        let mut events = self.load_span_events(&ids).await?;
+        let now = Utc::now();
+
+        let spans = ids
+            .iter()
+            .map(|id| {
+                let task = &tasks[id];
+                let end = task.first_done_at.unwrap_or(now);
+                let start = task
+                    .first_todo_at
+                    .or(task.first_doing_at)
+                    .unwrap_or(end)
+                    .min(end);
+                // A parent that is private (not loaded) ends the trace there
+                let parent = task.parent_id.filter(|p| tasks.contains_key(p));
+                Span {
+                    trace_id: self.hex_id("trace", root_of(tasks, *id), 16),
+                    span_id: self.hex_id("span", *id, 8),
+                    parent_span_id: parent.map(|p| self.hex_id("span", p, 8)),
+                    name: task.name.clone(),
+                    kind: SPAN_KIND_INTERNAL,
+                    start_time_unix_nano: unix_nanos(start),
+                    end_time_unix_nano: unix_nanos(end),
+                    attributes: task.attributes(),
+                    events: events.remove(id).unwrap_or_default(),
+                    status: SpanStatus {
+                        code: if task.status == "done" {
+                            STATUS_CODE_OK
+                        } else {
+                            STATUS_CODE_UNSET
+                        },
+                    },
+                }
+            })
+            .collect();
+
+        Ok(TraceBatch {
+            service_name: self.service_name.clone(),
+            spans,
+            latest_done_at: ids.iter().filter_map(|id| tasks[id].first_done_at).max(),
+        })
+    }
+
+    /// Events and status transitions of the given tasks, per task, in time order
+    async fn load_span_events(&self, ids: &[i64]) -> Result<HashMap<i64, Vec<SpanEvent>>> {
+        let mut by_task: HashMap<i64, Vec<(DateTime<Utc>, SpanEvent)>> = HashMap::new();
+        // Bound the IN lists; SQLite limits the number of parameters
+        for chunk in ids.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+
+            let query = format!(
+                "SELECT id, task_id, timestamp, log_type, discussion_data FROM events \
+                 WHERE visibility = 'normal' AND task_id IN ({}) ORDER BY timestamp, id",
+                placeholders
+            );
+            let mut rows = sqlx::query_as::<_, EventRow>(&query);
+            for id in chunk {
+                rows = rows.bind(id);
+            }
+            for row in rows.fetch_all(self.pool).await? {
+                let mut message = row.discussion_data;
+                truncate_at_char_boundary(&mut message, MAX_MESSAGE_LEN);
+                by_task.entry(row.task_id).or_default().push((
+                    row.timestamp,
+                    SpanEvent {
+                        time_unix_nano: unix_nanos(row.timestamp),
+                        name: row.log_type,
+                        attributes: vec![
+                            int_attribute("ie.event.id", row.id),
+                            string_attribute("ie.event.message", &message),
+                        ],
+                    },
+                ));
+            }
+
+            let query = format!(
+                "SELECT task_id, from_status, to_status, source, changed_at FROM task_transitions \
+                 WHERE task_id IN ({}) ORDER BY changed_at, id",
+                placeholders
+            );
+            let mut rows = sqlx::query_as::<_, TransitionRow>(&query);
+            for id in chunk {
+                rows = rows.bind(id);
+            }
+            for row in rows.fetch_all(self.pool).await? {
+                by_task.entry(row.task_id).or_default().push((
+                    row.changed_at,
+                    SpanEvent {
+                        time_unix_nano: unix_nanos(row.changed_at),
+                        name: format!("status {} → {}", row.from_status, row.to_status),
+                        attributes: vec![
+                            string_attribute("ie.transition.from", &row.from_status),
+                            string_attribute("ie.transition.to", &row.to_status),
+                            string_attribute("ie.transition.source", &row.source),
+                        ],
+                    },
+                ));
+            }
+        }
+
+        Ok(by_task
+            .into_iter()
+            .map(|(task_id, mut events)| {
+                events.sort_by_key(|(at, _)| *at);
+                (task_id, events.into_iter().map(|(_, e)| e).collect())
+            })
+            .collect())
+    }
+
+    /// Stable hex ID of `bytes` bytes for a trace or span
+    fn hex_id(&self, kind: &str, task_id: i64, bytes: usize) -> String {
+        let digest = Sha256::digest(format!("{}:{}:{}", self.service_name, kind, task_id));
+        digest[..bytes]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// Send the tasks completed since the last call to the configured endpoint
+///
+/// Returns None when no endpoint is configured. Tasks are sent once, when
+/// they are done; `ie export traces` covers open tasks and re-exports.
+pub async fn export_finished(pool: &SqlitePool) -> Result<Option<ExportSummary>> {
+    let config = OtelConfig::load(pool).await?;
+    let Some(endpoint) = config.endpoint else {
+        return Ok(None);
+    };
+
+    let since = config_get(pool, EXPORTED_UNTIL_KEY)
+        .await?
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|at| at.with_timezone(&Utc));
+    let batch = TraceExporter::new(pool, config.service_name)
+        .collect_finished(since)
+        .await?;
+    if batch.is_empty() {
+        return Ok(Some(batch.summary(None)));
+    }
+
+    let url = batch.send(&endpoint).await?;
+    if let Some(latest) = batch.latest_done_at {
+        config_set(pool, EXPORTED_UNTIL_KEY, &latest.to_rfc3339()).await?;
+    }
+    Ok(Some(batch.summary(Some(url))))
+}
+
+/// Whether `id` is `root` or one of its descendants
+fn is_within(tasks: &HashMap<i64, TaskRow>, id: i64, root: i64) -> bool {
+    let mut current = Some(id);
+    let mut steps = 0;
+    while let Some(task_id) = current {
+        if task_id == root {
+            return true;
+        }
+        // Guard against a corrupted, cyclic parent chain
+        steps += 1;
+        if steps > tasks.len() {
+            return false;
+        }
+        current = tasks.get(&task_id).and_then(|t| t.parent_id);
+    }
+    false
+}
+
+/// Topmost exported ancestor of a task
+fn root_of(tasks: &HashMap<i64, TaskRow>, id: i64) -> i64 {
+    let mut root = id;
+    for _ in 0..tasks.len() {
+        match tasks.get(&root).and_then(|t| t.parent_id) {
+            Some(parent) if tasks.contains_key(&parent) => root = parent,
+            _ => break,
+        }
+    }
+    root
+}
+
+fn unix_nanos(at: DateTime<Utc>) -> String {
+    at.timestamp_nanos_opt()
+        .unwrap_or_else(|| at.timestamp_micros().saturating_mul(1000))
+        .to_string()
+}
+
+fn truncate_at_char_boundary(text: &mut String, max: usize) {
+    if text.len() > max {
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TaskRow {
+    id: i64,
+    parent_id: Option<i64>,
+    name: String,
+    status: String,
+    priority: Option<i64>,
+    owner: String,
+    first_todo_at: Option<DateTime<Utc>>,
+    first_doing_at: Option<DateTime<Utc>>,
+    first_done_at: Option<DateTime<Utc>>,
+}
+
+impl TaskRow {
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![
+            int_attribute("ie.task.id", self.id),
+            string_attribute("ie.task.status", &self.status),
+            string_attribute("ie.task.owner", &self.owner),
+        ];
+        if let Some(parent_id) = self.parent_id {
+            attributes.push(int_attribute("ie.task.parent_id", parent_id));
+        }
+        if let Some(priority) = self
+            .priority
+            .and_then(|p| crate::plan::PriorityValue::from_int(p as i32))
+        {
+            attributes.push(string_attribute("ie.task.priority", priority.as_str()));
+        }
+        attributes
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EventRow {
+    id: i64,
+    task_id: i64,
+    timestamp: DateTime<Utc>,
+    log_type: String,
+    discussion_data: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct TransitionRow {
+    task_id: i64,
+    from_status: String,
+    to_status: String,
+    source: String,
+    changed_at: DateTime<Utc>,
+}
+
+const SPAN_KIND_INTERNAL: u8 = 1;
+const STATUS_CODE_UNSET: u8 = 0;
+const STATUS_CODE_OK: u8 = 1;
+
+/// An OTLP span in the protobuf JSON mapping (IDs in hex, times as strings)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Span {
+    trace_id: String,
+    span_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<String>,
+    name: String,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    attributes: Vec<Attribute>,
+    events: Vec<SpanEvent>,
+    status: SpanStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpanEvent {
+    time_unix_nano: String,
+    name: String,
+    attributes: Vec<Attribute>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SpanStatus {
+    code: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Attribute {
+    key: &'static str,
+    value: serde_json::Value,
+}
+
+fn string_attribute(key: &'static str, value: &str) -> Attribute {
+    Attribute {
+        key,
+        value: serde_json::json!({ "stringValue": value }),
+    }
+}
+
+fn int_attribute(key: &'static str, value: i64) -> Attribute {
+    // OTLP JSON encodes 64-bit integers as strings
+    Attribute {
+        key,
+        value: serde_json::json!({ "intValue": value.to_string() }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_task_tree_maps_to_one_trace_with_child_spans() {
+        let ctx = TestContext::new().await;
+        let tasks = TaskManager::new(ctx.pool());
+        let root = tasks
+            .add_task("Ship login", None, None, None, None, None)
+            .await
+            .unwrap();
+        let child = tasks
+            .add_task("Write handler", None, Some(root.id), None, None, None)
+            .await
+            .unwrap();
+        let other = tasks
+            .add_task("Unrelated", None, None, None, None, None)
+            .await
+            .unwrap();
+        tasks.start_task(child.id, false).await.unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(child.id, "decision", "Use JWT")
+            .await
+            .unwrap();
+        tasks.done_task_by_id(child.id).await.unwrap();
+
+        let exporter = TraceExporter::new(ctx.pool(), "agents");
+        let json = exporter
+            .collect(Some(root.id))
+            .await
+            .unwrap()
+            .to_otlp_json();
+        let resource = &json["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "agents"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 2, "subtree export leaves out other trees");
+
+        let (parent, sub) = (&spans[0], &spans[1]);
+        assert_eq!(parent["name"], "Ship login");
+        assert_eq!(parent["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(parent["spanId"].as_str().unwrap().len(), 16);
+        assert!(parent.get("parentSpanId").is_none());
+        assert_eq!(sub["traceId"], parent["traceId"]);
+        assert_eq!(sub["parentSpanId"], parent["spanId"]);
+        assert_eq!(sub["status"]["code"], STATUS_CODE_OK);
+        assert_eq!(parent["status"]["code"], STATUS_CODE_UNSET);
+
+        let names: Vec<&str> = sub["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"decision"));
+        assert!(names.contains(&"status todo → doing"));
+        assert!(names.contains(&"status doing → done"));
+
+        // IDs are stable across exports and differ per tree
+        let again = exporter.collect(None).await.unwrap().to_otlp_json();
+        let all = again["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        let unrelated = all.iter().find(|s| s["name"] == other.name).unwrap();
+        assert_ne!(unrelated["traceId"], parent["traceId"]);
+        assert!(all.iter().any(|s| s["spanId"] == sub["spanId"]));
+    }
+
+    #[tokio::test]
+    async fn test_collect_finished_only_takes_tasks_done_after_watermark() {
+        let ctx = TestContext::new().await;
+        let tasks = TaskManager::new(ctx.pool());
+        let first = tasks
+            .add_task("First", None, None, None, None, None)
+            .await
+            .unwrap();
+        tasks
+            .add_task("Open", None, None, None, None, None)
+            .await
+            .unwrap();
+        tasks.start_task(first.id, false).await.unwrap();
+        tasks.done_task_by_id(first.id).await.unwrap();
+
+        let exporter = TraceExporter::new(ctx.pool(), "intent-engine");
+        let batch = exporter.collect_finished(None).await.unwrap();
+        assert_eq!(batch.summary(None).spans, 1);
+        let latest = batch.latest_done_at.unwrap();
+        assert!(exporter
+            .collect_finished(Some(latest))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_traces_url_and_headers() {
+        assert_eq!(
+            traces_url("http://localhost:4318").unwrap().as_str(),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://collector/v1/traces/").unwrap().as_str(),
+            "http://collector/v1/traces"
+        );
+        assert!(traces_url("not a url").is_err());
+        assert_eq!(
+            parse_headers("authorization=Basic abc, x-scope-orgid = team,broken"),
+            vec![
+                ("authorization".to_string(), "Basic abc".to_string()),
+                ("x-scope-orgid".to_string(), "team".to_string()),
+            ]
+        );
+    }
+}
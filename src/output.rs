@@ -0,0 +1,96 @@
+//! Structured output contract for `--format json`
+//!
+//! Commands that adopt the contract print exactly one [`Envelope`] on stdout,
+//! whether they succeed or fail, so scripts never have to scrape human text:
+//!
+//! ```json
+//! { "ok": true, "data": { ... }, "error": null, "warnings": [] }
+//! ```
+//!
+//! On failure `data` is null and `error` carries the same `{error, code}`
+//! object that is otherwise printed on stderr.
+
+use crate::error::{ErrorResponse, IntentError, Result};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Envelope<T: Serialize> {
+    pub ok: bool,
+    pub data: Option<T>,
+    pub error: Option<ErrorResponse>,
+    /// Non-fatal problems worth surfacing to automation
+    pub warnings: Vec<String>,
+}
+
+impl<T: Serialize> Envelope<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            ok: true,
+            data: Some(data),
+            error: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+}
+
+impl Envelope<()> {
+    pub fn failure(error: &IntentError) -> Self {
+        Self {
+            ok: false,
+            data: None,
+            error: Some(error.to_error_response()),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Whether a `--format` value selects the JSON envelope
+pub fn is_json(format: &str) -> bool {
+    format == "json"
+}
+
+/// Print an envelope on stdout
+pub fn print<T: Serialize>(envelope: &Envelope<T>) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(envelope)?);
+    Ok(())
+}
+
+/// Print a successful envelope with `data` and `warnings`
+pub fn print_success<T: Serialize>(data: T, warnings: Vec<String>) -> Result<()> {
+    print(&Envelope::success(data).with_warnings(warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_envelope_shapes() {
+        let ok = Envelope::success(json!({"id": 1})).with_warnings(vec!["careful".to_string()]);
+        assert_eq!(
+            serde_json::to_value(&ok).unwrap(),
+            json!({"ok": true, "data": {"id": 1}, "error": null, "warnings": ["careful"]})
+        );
+
+        let failed = Envelope::failure(&IntentError::TaskNotFound(7));
+        assert_eq!(
+            serde_json::to_value(&failed).unwrap(),
+            json!({
+                "ok": false,
+                "data": null,
+                "error": {
+                    "error": "Task not found: 7",
+                    "code": "TASK_NOT_FOUND",
+                    "error_code": "IE0001"
+                },
+                "warnings": []
+            })
+        );
+    }
+}
@@ -5,15 +5,109 @@
 //! single atomic calls.
 
 use serde::{Deserialize, Serialize};
-use sqlx::Row;
+use sqlx::{Acquire, Row};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Request for creating/updating task structure declaratively
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
 pub struct PlanRequest {
     /// Task tree to create or update
     pub tasks: Vec<TaskTree>,
+
+    /// What to do when individual tasks fail (default: abort)
+    #[serde(default, skip_serializing_if = "OnError::is_abort")]
+    pub on_error: OnError,
+
+    /// Which existing tasks plan names are matched against (default: whole project)
+    #[serde(default, skip_serializing_if = "PlanScope::is_project")]
+    pub scope: PlanScope,
+
+    /// Start tasks even past the project's WIP limits (see `crate::wip`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_wip_limits: bool,
+}
+
+/// Where plan task names are looked up
+///
+/// JSON: `"project"`, `"focused"` or `{"parent_id": N}`. With a subtree scope a
+/// name that only exists elsewhere in the project creates a new task, and new
+/// top-level plan tasks are created under the scope's root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(try_from = "serde_json::Value", into = "serde_json::Value")]
+pub enum PlanScope {
+    /// The whole project
+    #[default]
+    Project,
+    /// The focused task and its descendants
+    Focused,
+    /// Task `parent_id` and its descendants
+    Subtree { parent_id: i64 },
+}
+
+impl PlanScope {
+    pub fn is_project(&self) -> bool {
+        *self == PlanScope::Project
+    }
+}
+
+impl TryFrom<serde_json::Value> for PlanScope {
+    type Error = String;
+
+    fn try_from(value: serde_json::Value) -> std::result::Result<Self, Self::Error> {
+        let parent_id = value
+            .as_object()
+            .filter(|object| object.len() == 1)
+            .and_then(|object| object.get("parent_id"))
+            .and_then(|id| id.as_i64());
+
+        match (value.as_str(), parent_id) {
+            (Some("project"), _) => Ok(PlanScope::Project),
+            (Some("focused"), _) => Ok(PlanScope::Focused),
+            (_, Some(parent_id)) => Ok(PlanScope::Subtree { parent_id }),
+            _ => Err(format!(
+                "invalid scope {}, expected \"project\", \"focused\" or {{\"parent_id\": <id>}}",
+                value
+            )),
+        }
+    }
+}
+
+impl From<PlanScope> for serde_json::Value {
+    fn from(scope: PlanScope) -> Self {
+        match scope {
+            PlanScope::Project => "project".into(),
+            PlanScope::Focused => "focused".into(),
+            PlanScope::Subtree { parent_id } => serde_json::json!({ "parent_id": parent_id }),
+        }
+    }
+}
+
+/// How a plan handles tasks that fail validation
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    /// Stop at the first failing task and apply nothing
+    #[default]
+    Abort,
+    /// Apply every task that can be applied; report the ones that cannot
+    Skip,
+    /// Apply nothing, but report every failing task instead of only the first
+    Collect,
+}
+
+impl OnError {
+    pub fn is_abort(&self) -> bool {
+        *self == OnError::Abort
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnError::Abort => "abort",
+            OnError::Skip => "skip",
+            OnError::Collect => "collect",
+        }
+    }
 }
 
 /// Hierarchical task definition with nested children
@@ -54,6 +148,38 @@ pub struct TaskTree {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub active_form: Option<String>,
 
+    /// Optional effort estimate in minutes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+
+    /// Optional milestone name; created on first use, "" removes the task from its milestone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+
+    /// Why the task is waiting on a human (listed by `ie inbox`); "" clears the flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub needs_human: Option<String>,
+
+    /// Why the task cannot move (listed by `ie task list --status blocked`); "" unblocks it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+
+    /// Checklist items, checked off with `ie task check`; [] removes the checklist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checklist: Option<Vec<String>>,
+
+    /// What the task produced; only with status "done"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+
+    /// Files, URLs or other things the task produced; requires `outcome`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifacts: Option<Vec<String>>,
+
+    /// URLs or documents the task refers to, as URLs or {"url", "title", "kind"}; [] removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<crate::links::LinkInput>>,
+
     /// Explicit parent task ID
     /// - None: use default behavior (auto-parent to focused task for new root tasks)
     /// - Some(None): explicitly create as root task (no parent)
@@ -75,7 +201,7 @@ pub struct TaskTree {
 /// - Field absent → None (handled by #[serde(default)])
 /// - Field is null → Some(None) (explicit root task)
 /// - Field is number → Some(Some(id)) (explicit parent)
-fn deserialize_parent_id<'de, D>(
+pub(crate) fn deserialize_parent_id<'de, D>(
     deserializer: D,
 ) -> std::result::Result<Option<Option<i64>>, D::Error>
 where
@@ -97,6 +223,8 @@ where
 pub enum TaskStatus {
     Todo,
     Doing,
+    /// Finished by the agent, waiting for a human to approve
+    Review,
     Done,
 }
 
@@ -106,6 +234,7 @@ impl TaskStatus {
         match self {
             TaskStatus::Todo => "todo",
             TaskStatus::Doing => "doing",
+            TaskStatus::Review => "review",
             TaskStatus::Done => "done",
         }
     }
@@ -115,6 +244,7 @@ impl TaskStatus {
         match s {
             "todo" => Some(TaskStatus::Todo),
             "doing" => Some(TaskStatus::Doing),
+            "review" => Some(TaskStatus::Review),
             "done" => Some(TaskStatus::Done),
             _ => None,
         }
@@ -125,6 +255,7 @@ impl TaskStatus {
         match self {
             TaskStatus::Todo => "todo",
             TaskStatus::Doing => "doing",
+            TaskStatus::Review => "review",
             TaskStatus::Done => "done",
         }
     }
@@ -179,6 +310,7 @@ pub struct ExistingTaskInfo {
     pub id: i64,
     pub status: String,
     pub spec: Option<String>,
+    pub owner: String,
 }
 
 /// Result of plan execution
@@ -219,6 +351,52 @@ pub struct PlanResult {
     /// Warning messages (non-fatal hints)
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub warnings: Vec<String>,
+
+    /// Per-task outcomes; filled when `on_error` is `skip` or `collect`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub task_results: Vec<TaskOutcome>,
+}
+
+/// What happened to one task of a plan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskOutcome {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub status: TaskOutcomeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskOutcomeStatus {
+    Created,
+    Updated,
+    Deleted,
+    /// Not applied; `error` says why
+    Failed,
+}
+
+impl TaskOutcome {
+    fn applied(name: Option<&String>, id: i64, status: TaskOutcomeStatus) -> Self {
+        Self {
+            name: name.cloned(),
+            id: Some(id),
+            status,
+            error: None,
+        }
+    }
+
+    fn failed(task: &FlatTask, error: String) -> Self {
+        Self {
+            name: task.name.clone(),
+            id: task.id,
+            status: TaskOutcomeStatus::Failed,
+            error: Some(error),
+        }
+    }
 }
 
 fn is_zero(n: &usize) -> bool {
@@ -250,6 +428,7 @@ impl PlanResult {
             focused_task,
             error: None,
             warnings: Vec::new(),
+            task_results: Vec::new(),
         }
     }
 
@@ -276,6 +455,7 @@ impl PlanResult {
             focused_task,
             error: None,
             warnings,
+            task_results: Vec::new(),
         }
     }
 
@@ -292,10 +472,187 @@ impl PlanResult {
             focused_task: None,
             error: Some(message.into()),
             warnings: Vec::new(),
+            task_results: Vec::new(),
         }
     }
 }
 
+// ============================================================================
+// Parsing and Schema
+// ============================================================================
+
+/// Parse a plan from JSON, reporting where in the document an error occurred
+///
+/// Errors name the offending field, e.g. `tasks[2].children[0].priority`,
+/// instead of only a line and column.
+pub fn parse_request(json: &str) -> crate::error::Result<PlanRequest> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        let inner = e.into_inner();
+        if inner.is_syntax() || inner.is_eof() || path == "." {
+            crate::error::IntentError::InvalidInput(format!("Invalid JSON: {}", inner))
+        } else {
+            crate::error::IntentError::InvalidInput(format!("Invalid plan: {}: {}", path, inner))
+        }
+    })
+}
+
+/// JSON Schema (draft 2020-12) describing a [`PlanRequest`]
+///
+/// Printed by `ie plan --schema` so tools can check plans before sending them.
+pub fn schema() -> serde_json::Value {
+    use serde_json::json;
+
+    let statuses: Vec<&str> = [
+        TaskStatus::Todo,
+        TaskStatus::Doing,
+        TaskStatus::Review,
+        TaskStatus::Done,
+    ]
+    .iter()
+    .map(TaskStatus::as_str)
+    .collect();
+    let priorities: Vec<&str> = [
+        PriorityValue::Critical,
+        PriorityValue::High,
+        PriorityValue::Medium,
+        PriorityValue::Low,
+    ]
+    .iter()
+    .map(PriorityValue::as_str)
+    .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "PlanRequest",
+        "description": "Input of `ie plan`: tasks are matched by name, created if missing and updated otherwise",
+        "type": "object",
+        "required": ["tasks"],
+        "properties": {
+            "tasks": {
+                "type": "array",
+                "items": { "$ref": "#/$defs/TaskTree" }
+            },
+            "scope": {
+                "description": "Match task names only within a subtree: \"project\" (default), \"focused\" or {\"parent_id\": N}",
+                "oneOf": [
+                    { "enum": ["project", "focused"] },
+                    {
+                        "type": "object",
+                        "required": ["parent_id"],
+                        "properties": { "parent_id": { "type": "integer" } },
+                        "additionalProperties": false
+                    }
+                ]
+            },
+            "on_error": {
+                "enum": ["abort", "skip", "collect"],
+                "default": "abort",
+                "description": "abort: apply nothing on the first failure; skip: apply the valid tasks; collect: apply nothing but report every failure"
+            },
+            "ignore_wip_limits": {
+                "type": "boolean",
+                "default": false,
+                "description": "Start tasks even past the WIP limits of .intent-engine/config.toml"
+            }
+        },
+        "$defs": {
+            "TaskTree": {
+                "type": "object",
+                "description": "A task; `name` may only be omitted when `id` is given",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Task name, used to match existing tasks"
+                    },
+                    "spec": {
+                        "type": "string",
+                        "description": "Goal and approach; lines like @file(path) are replaced by file content"
+                    },
+                    "priority": { "enum": priorities },
+                    "status": { "enum": statuses },
+                    "active_form": {
+                        "type": "string",
+                        "description": "Present-tense label shown while the task is doing"
+                    },
+                    "estimate_minutes": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Effort estimate in minutes; rolled up into parents and reports"
+                    },
+                    "milestone": {
+                        "type": "string",
+                        "description": "Milestone to put the task in (created on first use); empty string removes it"
+                    },
+                    "needs_human": {
+                        "type": "string",
+                        "description": "Why the task is waiting on a human, listed by 'ie inbox'; empty string clears it"
+                    },
+                    "blocked_reason": {
+                        "type": "string",
+                        "description": "Why the task cannot move, listed by 'ie task list --status blocked'; empty string unblocks it"
+                    },
+                    "checklist": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Checklist items inside the task; checked items keep their state when restated, empty array removes the checklist"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "description": "What the task produced, recorded when it becomes done"
+                    },
+                    "artifacts": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Files, URLs or other things the task produced; requires outcome"
+                    },
+                    "links": {
+                        "type": "array",
+                        "items": {
+                            "oneOf": [
+                                { "type": "string" },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "url": { "type": "string" },
+                                        "title": { "type": "string" },
+                                        "kind": { "type": "string", "enum": ["doc", "issue", "pr", "design", "other"] }
+                                    },
+                                    "required": ["url"]
+                                }
+                            ]
+                        },
+                        "description": "URLs or documents the task refers to; a restated list replaces the previous one, empty array removes them"
+                    },
+                    "children": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/TaskTree" }
+                    },
+                    "depends_on": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Names of tasks in the same plan that must finish first"
+                    },
+                    "id": {
+                        "type": "integer",
+                        "description": "Existing task ID (alias: task_id), for forced updates or delete"
+                    },
+                    "task_id": { "type": "integer" },
+                    "parent_id": {
+                        "type": ["integer", "null"],
+                        "description": "Explicit parent; null makes a root task"
+                    },
+                    "delete": {
+                        "type": "boolean",
+                        "description": "Delete the task given by `id`"
+                    }
+                }
+            }
+        }
+    })
+}
+
 // ============================================================================
 // Name Extraction and Classification Logic
 // ============================================================================
@@ -332,6 +689,14 @@ pub struct FlatTask {
     pub id: Option<i64>,
     pub status: Option<TaskStatus>,
     pub active_form: Option<String>,
+    pub estimate_minutes: Option<u32>,
+    pub milestone: Option<String>,
+    pub needs_human: Option<String>,
+    pub blocked_reason: Option<String>,
+    pub checklist: Option<Vec<String>>,
+    pub outcome: Option<String>,
+    pub artifacts: Option<Vec<String>>,
+    pub links: Option<Vec<crate::links::LinkInput>>,
     /// Explicit parent_id from JSON
     /// - None: use default behavior (auto-parent to focused task for new root tasks)
     /// - Some(None): explicitly create as root task (no parent)
@@ -341,6 +706,16 @@ pub struct FlatTask {
     pub delete: bool,
 }
 
+impl FlatTask {
+    /// The outcome to record when this task is completed
+    pub fn completion_outcome(&self) -> Option<CompletionOutcome> {
+        self.outcome.as_ref().map(|summary| CompletionOutcome {
+            summary: summary.clone(),
+            artifacts: self.artifacts.clone().unwrap_or_default(),
+        })
+    }
+}
+
 pub fn flatten_task_tree(tasks: &[TaskTree]) -> Vec<FlatTask> {
     flatten_task_tree_recursive(tasks, None)
 }
@@ -358,6 +733,14 @@ fn flatten_task_tree_recursive(tasks: &[TaskTree], parent_name: Option<String>)
             id: task.id,
             status: task.status.clone(),
             active_form: task.active_form.clone(),
+            estimate_minutes: task.estimate_minutes,
+            milestone: task.milestone.clone(),
+            needs_human: task.needs_human.clone(),
+            blocked_reason: task.blocked_reason.clone(),
+            checklist: task.checklist.clone(),
+            outcome: task.outcome.clone(),
+            artifacts: task.artifacts.clone(),
+            links: task.links.clone(),
             explicit_parent_id: task.parent_id,
             delete: task.delete.unwrap_or(false),
         };
@@ -458,7 +841,13 @@ pub fn find_duplicate_names(tasks: &[TaskTree]) -> Vec<String> {
 // Database Operations (Plan Executor)
 // ============================================================================
 
+use crate::db::models::{CompletionOutcome, UnblockedTask};
 use crate::error::{IntentError, Result};
+use crate::notifications::desktop::DesktopNotifier;
+use crate::plan_journal::PlanJournal;
+use crate::policy::PolicyEngine;
+use crate::tasks::{NewTask, BATCH_ROWS};
+use crate::wip::WipLimits;
 use sqlx::SqlitePool;
 
 /// Plan executor for creating/updating task structures
@@ -467,6 +856,8 @@ pub struct PlanExecutor<'a> {
     project_path: Option<String>,
     /// Default parent ID for root-level tasks (auto-parenting to focused task)
     default_parent_id: Option<i64>,
+    /// Who the plan's changes are attributed to
+    actor: crate::db::models::Actor,
 }
 
 impl<'a> PlanExecutor<'a> {
@@ -476,6 +867,7 @@ impl<'a> PlanExecutor<'a> {
             pool,
             project_path: None,
             default_parent_id: None,
+            actor: crate::db::models::Actor::from_env(),
         }
     }
 
@@ -485,6 +877,7 @@ impl<'a> PlanExecutor<'a> {
             pool,
             project_path: Some(project_path),
             default_parent_id: None,
+            actor: crate::db::models::Actor::from_env(),
         }
     }
 
@@ -495,17 +888,63 @@ impl<'a> PlanExecutor<'a> {
         self
     }
 
+    /// Attribute the plan's changes to `actor` instead of the one from the environment
+    pub fn with_actor(mut self, actor: crate::db::models::Actor) -> Self {
+        self.actor = actor;
+        self
+    }
+
     /// Get TaskManager configured for this executor
     fn get_task_manager(&self) -> crate::tasks::TaskManager<'a> {
-        match &self.project_path {
+        let task_mgr = match &self.project_path {
             Some(path) => crate::tasks::TaskManager::with_project_path(self.pool, path.clone()),
             None => crate::tasks::TaskManager::new(self.pool),
-        }
+        };
+        task_mgr
+            .with_source(crate::db::models::TransitionSource::Plan)
+            .with_actor(self.actor.clone())
     }
 
     /// Execute a plan request (Phase 2: create + update mode)
-    #[tracing::instrument(skip(self, request), fields(task_count = request.tasks.len()))]
+    ///
+    /// Each execution is journaled (see `plan_journal`); plans left behind by a
+    /// crashed process are settled first and reported as warnings.
+    /// A failed plan also raises a desktop notification (if enabled).
+    #[tracing::instrument(skip(self, request))]
     pub async fn execute(&self, request: &PlanRequest) -> Result<PlanResult> {
+        let journal = PlanJournal::new(self.pool);
+        let recovered = journal
+            .recover(&self.get_task_manager())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Plan journal recovery failed");
+                Vec::new()
+            });
+
+        let journal_id = journal.start(request).await?;
+        let mut result = self.execute_plan(request, journal_id).await;
+        if let Err(e) = journal.finish(journal_id, &result).await {
+            tracing::warn!(error = %e, journal_id, "Failed to settle plan journal entry");
+        }
+        if let Ok(plan) = &mut result {
+            plan.warnings
+                .extend(recovered.iter().map(|entry| entry.message()));
+        }
+
+        let failure = match &result {
+            Ok(plan) if !plan.success => plan.error.clone(),
+            Err(e) => Some(e.to_string()),
+            Ok(_) => None,
+        };
+        if let Some(error) = failure {
+            DesktopNotifier::new(self.pool).plan_failed(&error).await;
+        }
+
+        result
+    }
+
+    #[tracing::instrument(skip(self, request), fields(task_count = request.tasks.len(), rows))]
+    async fn execute_plan(&self, request: &PlanRequest, journal_id: i64) -> Result<PlanResult> {
         // 1. Check for duplicate names in the request
         let duplicates = find_duplicate_names(&request.tasks);
         if !duplicates.is_empty() {
@@ -518,15 +957,40 @@ impl<'a> PlanExecutor<'a> {
         // 2. Extract all task names
         let all_names = extract_all_names(&request.tasks);
 
-        // 3. Find existing tasks by name
-        let existing = self.find_tasks_by_names(&all_names).await?;
+        // 3. Find existing tasks by name, within the scope's subtree if any
+        let scope_root = match request.scope {
+            PlanScope::Project => None,
+            PlanScope::Focused => match self.default_parent_id {
+                Some(id) => Some(id),
+                None => {
+                    return Ok(PlanResult::error(
+                        "scope \"focused\" needs a focused task. Start one, or use {\"parent_id\": <id>}",
+                    ))
+                },
+            },
+            PlanScope::Subtree { parent_id } => Some(parent_id),
+        };
+        if let Some(root) = scope_root {
+            let found: Option<i64> = sqlx::query_scalar("SELECT id FROM tasks WHERE id = ?")
+                .bind(root)
+                .fetch_optional(self.pool)
+                .await?;
+            if found.is_none() {
+                return Ok(PlanResult::error(format!("Scope task #{} not found", root)));
+            }
+        }
+        let existing = self.find_tasks_by_names(&all_names, scope_root).await?;
 
         // 4. Flatten the task tree
         let flat_tasks = flatten_task_tree(&request.tasks);
-
-        // 5. Validate dependencies exist in the plan
-        if let Err(e) = self.validate_dependencies(&flat_tasks) {
-            return Ok(PlanResult::error(e.to_string()));
+        let on_error = request.on_error;
+
+        // 5. Validate dependencies exist in the plan. This and the other
+        // per-task checks below fail the whole plan only with on_error=abort;
+        // otherwise failures are collected as (flat index, message)
+        let mut failures = crate::plan_validation::dependency_errors(&flat_tasks);
+        if let (OnError::Abort, Some((_, message))) = (on_error, failures.first()) {
+            return Ok(PlanResult::error(message.clone()));
         }
 
         // 6. Detect circular dependencies
@@ -541,8 +1005,10 @@ impl<'a> PlanExecutor<'a> {
 
         // 8. Get TaskManager for transaction operations
         let task_mgr = self.get_task_manager();
+        let review_required = task_mgr.review_required().await?;
 
         // 9. Execute in transaction
+        let transaction_started = std::time::Instant::now();
         let mut tx = self.pool.begin().await?;
 
         // 10. Create or update tasks based on existence
@@ -553,6 +1019,10 @@ impl<'a> PlanExecutor<'a> {
         let mut newly_created_names: std::collections::HashSet<String> =
             std::collections::HashSet::new();
         let mut deleted_count = 0;
+        let mut completed_ids: Vec<i64> = Vec::new();
+        let mut unblocked: Vec<UnblockedTask> = Vec::new();
+        let mut review_tasks: Vec<(i64, String)> = Vec::new();
+        let mut outcomes: Vec<TaskOutcome> = Vec::new();
 
         // ============================================================================
         // Delete Operations (processed first, before create/update)
@@ -563,20 +1033,6 @@ impl<'a> PlanExecutor<'a> {
         // 3. Cascade deletes (due to ON DELETE CASCADE on parent_id) are tracked
         // 4. Deleting focused task should warn the user
 
-        // Separate delete operations from normal operations
-        let (delete_tasks, normal_tasks): (Vec<_>, Vec<_>) =
-            flat_tasks.iter().partition(|t| t.delete);
-
-        // Validate delete operations: each must have an id
-        for task in &delete_tasks {
-            if task.id.is_none() {
-                return Ok(PlanResult::error(
-                    "Delete operation requires 'id' field. Use {\"id\": <task_id>, \"delete\": true}",
-                ));
-            }
-        }
-
-        // Process delete operations first
         // ============================================================================
         // Focus Protection Check (BEFORE any deletions)
         // ============================================================================
@@ -588,29 +1044,80 @@ impl<'a> PlanExecutor<'a> {
         //
         // Rationale: Focus represents "commitment to complete". Deleting it
         // without explicitly switching focus is semantically incomplete.
-        for task in &delete_tasks {
-            if let Some(id) = task.id {
+        for (index, task) in flat_tasks.iter().enumerate().filter(|(_, t)| t.delete) {
+            let error = match task.id {
+                // Validate delete operations: each must have an id
+                None => Some(
+                    "Delete operation requires 'id' field. Use {\"id\": <task_id>, \"delete\": true}"
+                        .to_string(),
+                ),
                 // Check entire subtree (task + all descendants) for focus in ANY session
-                if let Some((focused_id, session_id)) =
-                    task_mgr.find_focused_in_subtree_in_tx(&mut tx, id).await?
-                {
-                    if focused_id == id {
-                        // Direct deletion of focused task
-                        return Ok(PlanResult::error(format!(
-                            "Task #{} is the current focus of session '{}'. That session must switch focus first.",
-                            id, session_id
-                        )));
-                    } else {
-                        // Cascade would delete focused task
-                        return Ok(PlanResult::error(format!(
-                            "Task #{} is the current focus of session '{}' and would be deleted by cascade (descendant of #{}). That session must switch focus first.",
-                            focused_id, session_id, id
-                        )));
-                    }
+                Some(id) => match task_mgr.find_focused_in_subtree_in_tx(&mut tx, id).await? {
+                    // Direct deletion of focused task
+                    Some((focused_id, session_id)) if focused_id == id => Some(format!(
+                        "Task #{} is the current focus of session '{}'. That session must switch focus first.",
+                        id, session_id
+                    )),
+                    // Cascade would delete focused task
+                    Some((focused_id, session_id)) => Some(format!(
+                        "Task #{} is the current focus of session '{}' and would be deleted by cascade (descendant of #{}). That session must switch focus first.",
+                        focused_id, session_id, id
+                    )),
+                    None => None,
+                },
+            };
+            if let Some(error) = error {
+                if on_error.is_abort() {
+                    return Ok(PlanResult::error(error));
+                }
+                failures.push((index, error));
+            }
+        }
+
+        // Starting a task requires a spec, unless the project opts out (config.toml)
+        let require_spec = crate::config::ProjectConfig::for_pool(self.pool)?
+            .tasks
+            .require_spec;
+        for (index, task) in flat_tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| require_spec && !t.delete)
+        {
+            let Some(name) = &task.name else { continue };
+            if let Some(error) = missing_spec_error(task, name, existing.get(name)) {
+                if on_error.is_abort() {
+                    return Ok(PlanResult::error(error));
+                }
+                failures.push((index, error));
+            }
+        }
+
+        for (index, task) in flat_tasks.iter().enumerate().filter(|(_, t)| !t.delete) {
+            if let Some(error) = outcome_error(task).or_else(|| links_error(task)) {
+                if on_error.is_abort() {
+                    return Ok(PlanResult::error(error));
                 }
+                failures.push((index, error));
             }
         }
 
+        // Without its parent or the tasks it depends on, a task cannot be applied either
+        propagate_failures(&flat_tasks, &mut failures);
+        failures.sort_by_key(|(index, _)| *index);
+        outcomes.extend(
+            failures
+                .iter()
+                .map(|(index, error)| TaskOutcome::failed(&flat_tasks[*index], error.clone())),
+        );
+
+        // Separate delete operations from normal operations
+        let (delete_tasks, mut normal_tasks): (Vec<_>, Vec<_>) = flat_tasks
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !failures.iter().any(|(failed, _)| failed == index))
+            .map(|(_, task)| task)
+            .partition(|t| t.delete);
+
         // ============================================================================
         // Execute Deletions (focus protection already verified)
         // ============================================================================
@@ -628,6 +1135,11 @@ impl<'a> PlanExecutor<'a> {
                     ));
                 } else {
                     deleted_count += 1;
+                    outcomes.push(TaskOutcome::applied(
+                        task.name.as_ref(),
+                        id,
+                        TaskOutcomeStatus::Deleted,
+                    ));
 
                     // Track cascade-deleted descendants (due to ON DELETE CASCADE)
                     if delete_result.descendant_count > 0 {
@@ -645,6 +1157,67 @@ impl<'a> PlanExecutor<'a> {
         // Create/Update Operations
         // ============================================================================
 
+        // New tasks are inserted up front, a chunk of rows per statement and
+        // one tree level at a time, so that every row is written with its
+        // parent already set; the loop below then only fills in what the plan
+        // sets on each of them.
+        // Parent priority: children nesting > explicit parent_id > auto-parent
+        // to the scope's root, or else to default_parent_id (focused task)
+        let default_parent = scope_root.or(self.default_parent_id);
+        let mut new_ids: HashMap<&str, i64> = HashMap::new();
+        let mut pending: Vec<&FlatTask> = normal_tasks
+            .iter()
+            .copied()
+            .filter(|task| matches!(&task.name, Some(name) if !existing.contains_key(name)))
+            .collect();
+        while !pending.is_empty() {
+            let mut rows = Vec::new();
+            // Children of tasks in this level
+            let mut waiting = Vec::new();
+            for task in pending {
+                let parent_id = match (&task.parent_name, &task.explicit_parent_id) {
+                    (Some(parent), _) => {
+                        let id = existing
+                            .get(parent)
+                            .map(|info| info.id)
+                            .or_else(|| new_ids.get(parent.as_str()).copied());
+                        if id.is_none() {
+                            waiting.push(task);
+                            continue;
+                        }
+                        id
+                    },
+                    // parent_id: null → root task; parent_id: N → task N
+                    // (checked by the foreign key; may be in this batch or in DB)
+                    (None, Some(explicit)) => *explicit,
+                    (None, None) => default_parent,
+                };
+                rows.push(NewTask {
+                    name: task.name.as_deref().unwrap_or_default(),
+                    spec: task.spec.as_deref(),
+                    priority: task.priority.as_ref().map(|p| p.to_int()),
+                    status: created_status(task, review_required).map(|s| s.as_db_str()),
+                    active_form: task.active_form.as_deref(),
+                    parent_id,
+                });
+            }
+            if let (true, Some(task)) = (rows.is_empty(), waiting.first()) {
+                return Err(IntentError::InvalidInput(format!(
+                    "Parent task not found: {}",
+                    task.parent_name.as_deref().unwrap_or_default()
+                )));
+            }
+            // Plan-created tasks are AI-owned
+            let ids = task_mgr.create_tasks_in_tx(&mut tx, &rows, "ai").await?;
+            new_ids.extend(rows.iter().map(|row| row.name).zip(ids));
+            pending = waiting;
+        }
+
+        // Tasks whose update had to be rolled back (on_error != abort)
+        let mut rolled_back: std::collections::HashSet<String> = std::collections::HashSet::new();
+        // Tasks moved to doing or done, for the policy pass below
+        let mut transitioned: Vec<(String, i64)> = Vec::new();
+
         // Process normal operations (create/update)
         for task in &normal_tasks {
             // Skip tasks without name (shouldn't happen for normal operations)
@@ -653,8 +1226,6 @@ impl<'a> PlanExecutor<'a> {
                 None => continue, // Skip invalid entries
             };
 
-            // Check if task is transitioning to 'doing' status and validate spec
-            let is_becoming_doing = task.status.as_ref() == Some(&TaskStatus::Doing);
             let has_spec = task
                 .spec
                 .as_ref()
@@ -664,36 +1235,21 @@ impl<'a> PlanExecutor<'a> {
             if let Some(existing_info) = existing.get(task_name) {
                 // Task exists -> UPDATE
 
-                // Validation: if transitioning to 'doing' and no spec provided,
-                // check if existing spec is also empty
-                if is_becoming_doing && !has_spec {
-                    let existing_is_doing = existing_info.status == "doing";
-                    let existing_has_spec = existing_info
-                        .spec
-                        .as_ref()
-                        .map(|s| !s.trim().is_empty())
-                        .unwrap_or(false);
-
-                    // Only error if: transitioning TO doing (wasn't doing before) AND no spec anywhere
-                    if !existing_is_doing && !existing_has_spec {
-                        return Ok(PlanResult::error(format!(
-                            "Task '{}': spec (description) is required when starting a task (status: doing).\n\n\
-                            Before starting a task, please describe:\n  \
-                            • What is the goal of this task\n  \
-                            • How do you plan to approach it\n\n\
-                            Tip: Use @file(path) to include content from a file",
-                            task_name
-                        )));
-                    }
-                }
-
-                // Check if transitioning to 'done'
+                // Check if transitioning to 'done'; AI-owned tasks may have to
+                // stop at 'review' for a human to approve
                 let is_becoming_done = task.status.as_ref() == Some(&TaskStatus::Done);
+                let goes_to_review = is_becoming_done
+                    && review_required
+                    && existing_info.owner == "ai"
+                    && existing_info.status != "done";
+
+                // A savepoint, so a failed completion only undoes this task
+                let mut savepoint = tx.begin().await?;
 
                 // Update non-status fields first
                 task_mgr
                     .update_task_in_tx(
-                        &mut tx,
+                        &mut savepoint,
                         existing_info.id,
                         task.spec.as_deref(),
                         task.priority.as_ref().map(|p| p.to_int()),
@@ -706,53 +1262,153 @@ impl<'a> PlanExecutor<'a> {
                         task.active_form.as_deref(),
                     )
                     .await?;
+                if let Some(minutes) = task.estimate_minutes {
+                    task_mgr
+                        .set_estimate_in_tx(&mut savepoint, existing_info.id, minutes.into())
+                        .await?;
+                }
+                if let Some(milestone) = &task.milestone {
+                    task_mgr
+                        .assign_milestone_in_tx(&mut savepoint, existing_info.id, milestone)
+                        .await?;
+                }
+                if let Some(reason) = &task.needs_human {
+                    task_mgr
+                        .set_needs_human_in_tx(&mut savepoint, existing_info.id, reason)
+                        .await?;
+                }
+                if let Some(reason) = &task.blocked_reason {
+                    task_mgr
+                        .set_blocked_reason_in_tx(&mut savepoint, existing_info.id, reason)
+                        .await?;
+                }
+                if let Some(items) = &task.checklist {
+                    crate::checklist::set_in_tx(&mut savepoint, existing_info.id, items).await?;
+                }
+                if let Some(links) = &task.links {
+                    crate::links::set_in_tx(&mut savepoint, existing_info.id, links).await?;
+                }
 
                 // If becoming done, use complete_task_in_tx for business logic
                 if is_becoming_done {
-                    if let Err(e) = task_mgr
-                        .complete_task_in_tx(&mut tx, existing_info.id)
-                        .await
-                    {
-                        // Convert IntentError to user-friendly message
-                        return Ok(PlanResult::error(format!(
-                            "Cannot complete task '{}': {}\n\n\
+                    let outcome = task.completion_outcome();
+                    let completion = if goes_to_review {
+                        task_mgr
+                            .submit_for_review_in_tx(
+                                &mut savepoint,
+                                existing_info.id,
+                                outcome.as_ref(),
+                            )
+                            .await
+                            .map(|()| Vec::new())
+                    } else {
+                        task_mgr
+                            .complete_task_in_tx(&mut savepoint, existing_info.id, outcome.as_ref())
+                            .await
+                    };
+                    let newly_unblocked = match completion {
+                        Ok(newly_unblocked) => newly_unblocked,
+                        Err(e) => {
+                            // Convert IntentError to user-friendly message
+                            let error = format!(
+                                "Cannot complete task '{}': {}\n\n\
                             Please complete all subtasks before marking the parent as done.",
-                            task_name, e
-                        )));
+                                task_name, e
+                            );
+                            if on_error.is_abort() {
+                                return Ok(PlanResult::error(error));
+                            }
+                            savepoint.rollback().await?;
+                            outcomes.push(TaskOutcome::failed(task, error));
+                            rolled_back.insert(task_name.clone());
+                            // The task itself still exists for children and dependents
+                            task_id_map.insert(task_name.clone(), existing_info.id);
+                            continue;
+                        },
+                    };
+                    if goes_to_review {
+                        if existing_info.status != "review" {
+                            warnings.push(format!(
+                                "Task '{}' is awaiting review. A human can approve it with: ie task approve {}",
+                                task_name, existing_info.id
+                            ));
+                            review_tasks.push((existing_info.id, task_name.clone()));
+                        }
+                    } else if existing_info.status != "done" {
+                        completed_ids.push(existing_info.id);
+                        unblocked.extend(newly_unblocked);
+                        warnings.extend(
+                            crate::checklist::unchecked_warning(&mut savepoint, existing_info.id)
+                                .await?,
+                        );
                     }
                 }
+                savepoint.commit().await?;
 
+                let target = task.status.as_ref().map(|s| s.as_db_str());
+                if matches!(target, Some("doing" | "done"))
+                    && !goes_to_review
+                    && target != Some(existing_info.status.as_str())
+                {
+                    transitioned.push((task_name.clone(), existing_info.id));
+                }
                 task_id_map.insert(task_name.clone(), existing_info.id);
                 updated_count += 1;
+                outcomes.push(TaskOutcome::applied(
+                    Some(task_name),
+                    existing_info.id,
+                    TaskOutcomeStatus::Updated,
+                ));
             } else {
-                // Task doesn't exist -> CREATE
-
-                // Validation: new task with status=doing must have spec
-                if is_becoming_doing && !has_spec {
-                    return Ok(PlanResult::error(format!(
-                        "Task '{}': spec (description) is required when starting a task (status: doing).\n\n\
-                        Before starting a task, please describe:\n  \
-                        • What is the goal of this task\n  \
-                        • How do you plan to approach it\n\n\
-                        Tip: Use @file(path) to include content from a file",
-                        task_name
-                    )));
+                // Task doesn't exist -> CREATE (inserted above)
+                let is_becoming_doing = task.status.as_ref() == Some(&TaskStatus::Doing);
+                let status = created_status(task, review_required);
+                let id = new_ids[task_name.as_str()];
+                if let Some(minutes) = task.estimate_minutes {
+                    task_mgr
+                        .set_estimate_in_tx(&mut tx, id, minutes.into())
+                        .await?;
+                }
+                if let Some(milestone) = &task.milestone {
+                    task_mgr
+                        .assign_milestone_in_tx(&mut tx, id, milestone)
+                        .await?;
+                }
+                if let Some(reason) = &task.needs_human {
+                    task_mgr.set_needs_human_in_tx(&mut tx, id, reason).await?;
+                }
+                if let Some(reason) = &task.blocked_reason {
+                    task_mgr
+                        .set_blocked_reason_in_tx(&mut tx, id, reason)
+                        .await?;
+                }
+                if let Some(items) = &task.checklist {
+                    crate::checklist::set_in_tx(&mut tx, id, items).await?;
+                }
+                if let Some(links) = &task.links {
+                    crate::links::set_in_tx(&mut tx, id, links).await?;
+                }
+                if let Some(outcome) = task.completion_outcome() {
+                    task_mgr.record_outcome_in_tx(&mut tx, id, &outcome).await?;
+                }
+                if status == Some(TaskStatus::Review) {
+                    warnings.push(format!(
+                        "Task '{}' is awaiting review. A human can approve it with: ie task approve {}",
+                        task_name, id
+                    ));
+                    review_tasks.push((id, task_name.clone()));
+                }
+                if matches!(status, Some(TaskStatus::Doing | TaskStatus::Done)) {
+                    transitioned.push((task_name.clone(), id));
                 }
-
-                let id = task_mgr
-                    .create_task_in_tx(
-                        &mut tx,
-                        task_name,
-                        task.spec.as_deref(),
-                        task.priority.as_ref().map(|p| p.to_int()),
-                        task.status.as_ref().map(|s| s.as_db_str()),
-                        task.active_form.as_deref(),
-                        "ai", // Plan-created tasks are AI-owned
-                    )
-                    .await?;
                 task_id_map.insert(task_name.clone(), id);
                 newly_created_names.insert(task_name.clone());
                 created_count += 1;
+                outcomes.push(TaskOutcome::applied(
+                    Some(task_name),
+                    id,
+                    TaskOutcomeStatus::Created,
+                ));
 
                 // Warning: new task without spec (non-doing tasks only, doing already validated)
                 if !has_spec && !is_becoming_doing {
@@ -764,113 +1420,175 @@ impl<'a> PlanExecutor<'a> {
             }
         }
 
-        // 11. Build parent-child relationships via TaskManager (only for normal tasks)
+        let failed: Vec<TaskOutcome> = outcomes
+            .iter()
+            .filter(|outcome| outcome.status == TaskOutcomeStatus::Failed)
+            .cloned()
+            .collect();
+        if on_error == OnError::Collect && !failed.is_empty() {
+            // Dropping the transaction rolls everything back
+            let mut result = PlanResult::error(format!(
+                "{} task(s) failed; nothing was applied:\n{}",
+                failed.len(),
+                failed
+                    .iter()
+                    .map(|outcome| format!(
+                        "  - {}: {}",
+                        outcome.name.as_deref().unwrap_or("<unnamed>"),
+                        outcome.error.as_deref().unwrap_or_default()
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+            result.task_results = failed;
+            return Ok(result);
+        }
+        if !failed.is_empty() {
+            warnings.push(format!(
+                "{} task(s) failed and were not applied (on_error: skip); see task_results",
+                failed.len()
+            ));
+        }
+        normal_tasks.retain(|task| !matches!(&task.name, Some(name) if rolled_back.contains(name)));
+
+        // 11. Move updated tasks to their parents via TaskManager (new tasks
+        // were created under theirs), collected as (task, parent) and written
+        // in one go. Priority: children nesting > explicit parent_id
+        let mut parents: Vec<(i64, Option<i64>)> = Vec::new();
         for task in &normal_tasks {
-            if let Some(parent_name) = &task.parent_name {
-                if let Some(task_name) = &task.name {
-                    let task_id = task_id_map.get(task_name).ok_or_else(|| {
-                        IntentError::InvalidInput(format!("Task not found: {}", task_name))
-                    })?;
+            let task_name = match &task.name {
+                Some(name) if !newly_created_names.contains(name) => name,
+                _ => continue,
+            };
+            let parent_id = match (&task.parent_name, &task.explicit_parent_id) {
+                (Some(parent_name), _) => {
                     let parent_id = task_id_map.get(parent_name).ok_or_else(|| {
                         IntentError::InvalidInput(format!("Parent task not found: {}", parent_name))
                     })?;
-                    task_mgr
-                        .set_parent_in_tx(&mut tx, *task_id, *parent_id)
-                        .await?;
+                    Some(*parent_id)
+                },
+                // parent_id: null → explicitly set as root task (clear parent)
+                // parent_id: N → set parent to task N (validated by the
+                // foreign key; it may be in this batch or already in DB)
+                (None, Some(explicit_parent)) => *explicit_parent,
+                (None, None) => continue,
+            };
+            let task_id = task_id_map.get(task_name).ok_or_else(|| {
+                IntentError::InvalidInput(format!("Task not found: {}", task_name))
+            })?;
+            parents.push((*task_id, parent_id));
+        }
+        task_mgr.set_parents_in_tx(&mut tx, &parents).await?;
+
+        // 12. Build dependencies
+        let dep_count = match self
+            .build_dependencies(&mut tx, &normal_tasks, &task_id_map)
+            .await
+        {
+            Ok(count) => count,
+            Err(IntentError::InvalidInput(message)) => return Ok(PlanResult::error(message)),
+            Err(e) => return Err(e),
+        };
+
+        // 12b. Project policies and WIP limits, once parents and events are in place
+        let policy = PolicyEngine::for_pool(self.pool)?;
+        let wip = if request.ignore_wip_limits {
+            WipLimits::default()
+        } else {
+            WipLimits::for_pool(self.pool)?
+        };
+        for (task_name, id) in &transitioned {
+            if let Err(e) = policy.check_in_tx(&mut tx, *id).await {
+                return Ok(PlanResult::error(format!("Task '{}': {}", task_name, e)));
+            }
+            let starts: bool =
+                sqlx::query_scalar("SELECT status = 'doing' FROM tasks WHERE id = ?")
+                    .bind(id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            if starts {
+                if let Err(e) = wip.check_in_tx(&mut tx, *id).await {
+                    return Ok(PlanResult::error(format!("Task '{}': {}", task_name, e)));
                 }
             }
         }
 
-        // 11b. Handle explicit parent_id (takes precedence over auto-parenting)
-        // Priority: children nesting > explicit parent_id > auto-parent
-        for task in &normal_tasks {
-            // Skip if parent was set via children nesting
-            if task.parent_name.is_some() {
-                continue;
-            }
+        // 13. Commit transaction, together with the journal's record of what
+        // the post-commit steps below still have to do
+        let focus_task_id = normal_tasks
+            .iter()
+            .find(|task| matches!(task.status, Some(TaskStatus::Doing)))
+            .and_then(|task| task.name.as_ref())
+            .and_then(|name| task_id_map.get(name))
+            .copied();
+        PlanJournal::new(self.pool)
+            .mark_committed_in_tx(&mut tx, journal_id, &task_id_map, focus_task_id)
+            .await?;
+        tx.commit().await?;
+        let rows = (created_count + updated_count + deleted_count) as u64;
+        tracing::Span::current().record("rows", rows);
+        crate::timing::record(
+            "plan::transaction",
+            transaction_started.elapsed(),
+            Some(rows),
+        );
 
-            // Handle explicit parent_id
-            if let Some(explicit_parent) = &task.explicit_parent_id {
-                if let Some(task_name) = &task.name {
-                    let task_id = task_id_map.get(task_name).ok_or_else(|| {
-                        IntentError::InvalidInput(format!("Task not found: {}", task_name))
-                    })?;
+        // 14. Notify Dashboard about the batch change (via TaskManager)
+        task_mgr.notify_batch_changed().await;
 
-                    match explicit_parent {
-                        None => {
-                            // parent_id: null → explicitly set as root task (clear parent)
-                            task_mgr.clear_parent_in_tx(&mut tx, *task_id).await?;
-                        },
-                        Some(parent_id) => {
-                            // parent_id: N → set parent to task N (validate exists)
-                            // Note: parent task may be in this batch or already in DB
-                            task_mgr
-                                .set_parent_in_tx(&mut tx, *task_id, *parent_id)
-                                .await?;
-                        },
-                    }
-                }
-            }
+        // Tasks the plan completed itself are not news
+        unblocked.retain(|task| !completed_ids.contains(&task.id));
+        task_mgr.notify_unblocked(&unblocked).await;
+
+        let desktop = DesktopNotifier::new(self.pool);
+        for (id, name) in &review_tasks {
+            desktop.review_requested(*id, name).await;
         }
 
-        // 11c. Auto-parent newly created root tasks to default_parent_id (focused task)
-        if let Some(default_parent) = self.default_parent_id {
-            for task in &normal_tasks {
-                // Only auto-parent if:
-                // 1. Task was newly created (not updated)
-                // 2. Task has no explicit parent in the plan (children nesting)
-                // 3. Task has no explicit parent_id in JSON
-                if let Some(task_name) = &task.name {
-                    if newly_created_names.contains(task_name)
-                        && task.parent_name.is_none()
-                        && task.explicit_parent_id.is_none()
-                    {
-                        if let Some(&task_id) = task_id_map.get(task_name) {
-                            task_mgr
-                                .set_parent_in_tx(&mut tx, task_id, default_parent)
-                                .await?;
-                        }
-                    }
+        // 14b. Suggest splitting new leaf tasks that similar completed tasks
+        // say are complex
+        if !newly_created_names.is_empty() {
+            let history = crate::priority::ComplexityHistory::load(self.pool).await?;
+            let mut created: Vec<i64> = newly_created_names
+                .iter()
+                .filter_map(|name| task_id_map.get(name).copied())
+                .collect();
+            created.sort_unstable();
+            for id in created {
+                let task = task_mgr.get_task(id).await?;
+                let Some(estimate) = history.estimate(&task) else {
+                    continue;
+                };
+                let has_children: bool =
+                    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM tasks WHERE parent_id = ?)")
+                        .bind(id)
+                        .fetch_one(self.pool)
+                        .await?;
+                if estimate.complexity >= crate::priority::HIGH_COMPLEXITY && !has_children {
+                    let similar: Vec<String> = estimate
+                        .based_on
+                        .iter()
+                        .map(|s| format!("#{} {}", s.task_id, s.name))
+                        .collect();
+                    warnings.push(format!(
+                        "Task '{}' looks complex: estimated {}/10 from similar completed tasks ({}); consider splitting it",
+                        task.name,
+                        estimate.complexity,
+                        similar.join(", ")
+                    ));
                 }
             }
         }
 
-        // 12. Build dependencies
-        let dep_count = self
-            .build_dependencies(&mut tx, &flat_tasks, &task_id_map)
-            .await?;
-
-        // 13. Commit transaction
-        tx.commit().await?;
-
-        // 14. Notify Dashboard about the batch change (via TaskManager)
-        task_mgr.notify_batch_changed().await;
-
         // 15. Auto-focus the doing task if present and return full context
-        // Find the doing task in the batch (only from normal tasks, not deletes)
-        let doing_task = normal_tasks
-            .iter()
-            .find(|task| matches!(task.status, Some(TaskStatus::Doing)));
-
-        let focused_task_response = if let Some(doing_task) = doing_task {
-            // Get the task ID from the map
-            if let Some(task_name) = &doing_task.name {
-                if let Some(&task_id) = task_id_map.get(task_name) {
-                    // Call task_start with events to get full context
-                    let response = task_mgr.start_task(task_id, true).await?;
-                    Some(response)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
+        let focused_task_response = match focus_task_id {
+            // Call task_start with events to get full context
+            Some(task_id) => Some(task_mgr.start_task(task_id, true).await?),
+            None => None,
         };
 
         // 16. Return success result with focused task and warnings
-        Ok(PlanResult::success_with_warnings(
+        let mut result = PlanResult::success_with_warnings(
             task_id_map,
             created_count,
             updated_count,
@@ -879,13 +1597,22 @@ impl<'a> PlanExecutor<'a> {
             dep_count,
             focused_task_response,
             warnings,
-        ))
+        );
+        if !on_error.is_abort() {
+            result.task_results = outcomes;
+        }
+        Ok(result)
     }
 
     /// Find tasks by names (returns full info for validation)
+    ///
+    /// With `scope_root`, only that task and its descendants are considered.
+    /// When several tasks share a name the oldest one is used, so copies made
+    /// by `ie task clone` do not take over their originals.
     async fn find_tasks_by_names(
         &self,
         names: &[String],
+        scope_root: Option<i64>,
     ) -> Result<HashMap<String, ExistingTaskInfo>> {
         if names.is_empty() {
             return Ok(HashMap::new());
@@ -896,12 +1623,30 @@ impl<'a> PlanExecutor<'a> {
         // Query all names at once using IN clause
         // Build placeholders: ?, ?, ?...
         let placeholders = names.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-        let query = format!(
-            "SELECT id, name, status, spec FROM tasks WHERE name IN ({})",
-            placeholders
-        );
+        let query = match scope_root {
+            None => format!(
+                "SELECT id, name, status, spec, owner FROM tasks WHERE name IN ({}) ORDER BY id DESC",
+                placeholders
+            ),
+            Some(_) => format!(
+                r#"
+                WITH RECURSIVE subtree(id) AS (
+                    SELECT id FROM tasks WHERE id = ?
+                    UNION ALL
+                    SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+                )
+                SELECT id, name, status, spec, owner FROM tasks
+                WHERE name IN ({}) AND id IN (SELECT id FROM subtree)
+                ORDER BY id DESC
+                "#,
+                placeholders
+            ),
+        };
 
         let mut query_builder = sqlx::query(&query);
+        if let Some(root) = scope_root {
+            query_builder = query_builder.bind(root);
+        }
         for name in names {
             query_builder = query_builder.bind(name);
         }
@@ -913,21 +1658,34 @@ impl<'a> PlanExecutor<'a> {
             let name: String = row.get("name");
             let status: String = row.get("status");
             let spec: Option<String> = row.get("spec");
-            map.insert(name, ExistingTaskInfo { id, status, spec });
+            let owner: String = row.get("owner");
+            map.insert(
+                name,
+                ExistingTaskInfo {
+                    id,
+                    status,
+                    spec,
+                    owner,
+                },
+            );
         }
 
         Ok(map)
     }
 
     /// Build dependency relationships
+    ///
+    /// Plans only see their own edges at validation time, so before inserting
+    /// anything the new edges are checked against the stored ones: a cycle
+    /// running through the database fails with its full path.
     async fn build_dependencies(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
-        flat_tasks: &[FlatTask],
+        flat_tasks: &[&FlatTask],
         task_id_map: &HashMap<String, i64>,
     ) -> Result<usize> {
-        let mut count = 0;
-
+        // (blocked, blocking) pairs
+        let mut edges = Vec::new();
         for task in flat_tasks {
             // Skip delete operations and tasks without names
             if task.delete {
@@ -950,26 +1708,80 @@ impl<'a> PlanExecutor<'a> {
                             dep_name, task_name
                         ))
                     })?;
+                    edges.push((*blocked_id, *blocking_id));
+                }
+            }
+        }
+        if edges.is_empty() {
+            return Ok(0);
+        }
 
-                    sqlx::query(
-                        "INSERT INTO dependencies (blocking_task_id, blocked_task_id) VALUES (?, ?)",
-                    )
-                    .bind(blocking_id)
-                    .bind(blocked_id)
-                    .execute(&mut **tx)
-                    .await?;
-
-                    count += 1;
+        let mut depends_on = Self::load_dependency_graph(tx, &edges).await?;
+        for &(blocked_id, blocking_id) in &edges {
+            if let Some(cycle) =
+                crate::plan_validation::find_dependency_cycle(&depends_on, blocked_id, blocking_id)
+            {
+                let mut names = Vec::with_capacity(cycle.len());
+                for id in cycle {
+                    let name: String = sqlx::query_scalar("SELECT name FROM tasks WHERE id = ?")
+                        .bind(id)
+                        .fetch_one(&mut **tx)
+                        .await?;
+                    names.push(format!("{} (#{})", name, id));
                 }
+                return Err(IntentError::InvalidInput(format!(
+                    "Circular dependency detected with existing dependencies: {}",
+                    names.join(" → ")
+                )));
             }
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
+        }
+
+        // Edges already stored (or listed twice) are kept as they are, so
+        // re-running a plan is idempotent and the count only covers new edges
+        let mut count = 0;
+        for chunk in edges.chunks(BATCH_ROWS) {
+            let sql = format!(
+                "INSERT OR IGNORE INTO dependencies (blocking_task_id, blocked_task_id) VALUES {}",
+                vec!["(?, ?)"; chunk.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql);
+            for &(blocked_id, blocking_id) in chunk {
+                query = query.bind(blocking_id).bind(blocked_id);
+            }
+            count += query.execute(&mut **tx).await?.rows_affected() as usize;
         }
 
         Ok(count)
     }
 
-    /// Validate that all dependencies exist in the plan
-    fn validate_dependencies(&self, flat_tasks: &[FlatTask]) -> Result<()> {
-        crate::plan_validation::validate_dependencies(flat_tasks)
+    /// Stored dependencies reachable from the tasks of `edges`, as task → dependencies
+    async fn load_dependency_graph(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        edges: &[(i64, i64)],
+    ) -> Result<HashMap<i64, Vec<i64>>> {
+        let seeds: Vec<i64> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE reach(id) AS (
+                SELECT value FROM json_each(?)
+                UNION
+                SELECT d.blocking_task_id FROM dependencies d
+                JOIN reach ON d.blocked_task_id = reach.id
+            )
+            SELECT d.blocked_task_id, d.blocking_task_id FROM dependencies d
+            JOIN reach ON d.blocked_task_id = reach.id
+            "#,
+        )
+        .bind(serde_json::to_string(&seeds)?)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let mut depends_on: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (blocked_id, blocking_id) in rows {
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
+        }
+        Ok(depends_on)
     }
 
     fn validate_batch_single_doing(&self, flat_tasks: &[FlatTask]) -> Result<()> {
@@ -981,6 +1793,102 @@ impl<'a> PlanExecutor<'a> {
     }
 }
 
+/// Status a new (AI-owned) task is created with: done becomes review under the review gate
+fn created_status(task: &FlatTask, review_required: bool) -> Option<TaskStatus> {
+    match &task.status {
+        Some(TaskStatus::Done) if review_required => Some(TaskStatus::Review),
+        other => other.clone(),
+    }
+}
+
+/// Error for the first link that cannot be stored
+fn links_error(task: &FlatTask) -> Option<String> {
+    let name = task.name.as_deref().unwrap_or("(unnamed)");
+    task.links
+        .iter()
+        .flatten()
+        .find_map(|link| link.error())
+        .map(|error| format!("Task '{}': {}", name, error))
+}
+
+/// Error for an outcome on a task that is not being completed, or artifacts without one
+fn outcome_error(task: &FlatTask) -> Option<String> {
+    let name = task.name.as_deref().unwrap_or("(unnamed)");
+    if task.artifacts.is_some() && task.outcome.is_none() {
+        return Some(format!("Task '{}': artifacts require an outcome", name));
+    }
+    if task.outcome.is_some() && task.status != Some(TaskStatus::Done) {
+        return Some(format!(
+            "Task '{}': outcome can only be given with status \"done\"",
+            name
+        ));
+    }
+    None
+}
+
+/// Error for a task that would become `doing` without any spec, in the plan or stored
+fn missing_spec_error(
+    task: &FlatTask,
+    name: &str,
+    existing: Option<&ExistingTaskInfo>,
+) -> Option<String> {
+    let is_blank = |spec: Option<&String>| spec.map(|s| s.trim().is_empty()).unwrap_or(true);
+
+    if task.status != Some(TaskStatus::Doing) || !is_blank(task.spec.as_ref()) {
+        return None;
+    }
+    // Only error if: transitioning TO doing (wasn't doing before) AND no spec anywhere
+    if let Some(info) = existing {
+        if info.status == "doing" || !is_blank(info.spec.as_ref()) {
+            return None;
+        }
+    }
+
+    Some(format!(
+        "Task '{}': spec (description) is required when starting a task (status: doing).\n\n\
+        Before starting a task, please describe:\n  \
+        • What is the goal of this task\n  \
+        • How do you plan to approach it\n\n\
+        Tip: Use @file(path) to include content from a file",
+        name
+    ))
+}
+
+/// Fail every task whose parent or dependency failed, until nothing changes
+fn propagate_failures(flat_tasks: &[FlatTask], failures: &mut Vec<(usize, String)>) {
+    loop {
+        let failed_names: std::collections::HashSet<&str> = failures
+            .iter()
+            .filter_map(|(index, _)| flat_tasks[*index].name.as_deref())
+            .collect();
+
+        let mut added = Vec::new();
+        for (index, task) in flat_tasks.iter().enumerate() {
+            if task.delete || failures.iter().any(|(failed, _)| *failed == index) {
+                continue;
+            }
+            let reason = match &task.parent_name {
+                Some(parent) if failed_names.contains(parent.as_str()) => {
+                    Some(format!("Parent task '{}' failed", parent))
+                },
+                _ => task
+                    .depends_on
+                    .iter()
+                    .find(|dep| failed_names.contains(dep.as_str()))
+                    .map(|dep| format!("Depends on '{}', which failed", dep)),
+            };
+            if let Some(reason) = reason {
+                added.push((index, reason));
+            }
+        }
+
+        if added.is_empty() {
+            return;
+        }
+        failures.extend(added);
+    }
+}
+
 /// Result of processing @file directives in a PlanRequest
 #[derive(Debug, Default)]
 pub struct FileIncludeResult {
@@ -988,56 +1896,222 @@ pub struct FileIncludeResult {
     pub files_to_delete: Vec<PathBuf>,
 }
 
-/// Parse @file directive from a string value
-///
-/// Syntax: `@file(path)` or `@file(path, keep)`
+/// Upper bound on the text a single value may pull in through includes
+pub const MAX_INCLUDE_BYTES: usize = 1024 * 1024;
+
+/// A parsed include directive
+#[derive(Debug, PartialEq)]
+enum FileDirective {
+    /// `@file(path)` / `@file(path, keep)`
+    File { path: PathBuf, delete: bool },
+    /// `@files(dir/*.md)` - every match, in name order; never deleted
+    Glob(PathBuf),
+}
+
+/// Parse an include directive from a string value
 ///
-/// Returns: (file_path, should_delete)
-fn parse_file_directive(value: &str) -> Option<(PathBuf, bool)> {
+/// Syntax: `@file(path)`, `@file(path, keep)` or `@files(pattern)`
+fn parse_file_directive(value: &str) -> Option<FileDirective> {
     let trimmed = value.trim();
 
-    // Must start with @file( and end with )
-    if !trimmed.starts_with("@file(") || !trimmed.ends_with(')') {
-        return None;
+    if let Some(pattern) = trimmed
+        .strip_prefix("@files(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return Some(FileDirective::Glob(PathBuf::from(pattern.trim())));
     }
 
-    // Extract content between @file( and )
-    let inner = &trimmed[6..trimmed.len() - 1];
+    // Must start with @file( and end with )
+    let inner = trimmed
+        .strip_prefix("@file(")
+        .and_then(|rest| rest.strip_suffix(')'))?;
 
     // Check for ", keep" suffix
-    if let Some(path_str) = inner.strip_suffix(", keep") {
-        Some((PathBuf::from(path_str.trim()), false)) // keep = don't delete
+    let (path_str, delete) = if let Some(path_str) = inner.strip_suffix(", keep") {
+        (path_str, false) // keep = don't delete
     } else if let Some(path_str) = inner.strip_suffix(",keep") {
-        Some((PathBuf::from(path_str.trim()), false))
+        (path_str, false)
     } else {
-        Some((PathBuf::from(inner.trim()), true)) // default = delete
+        (inner, true) // default = delete
+    };
+
+    Some(FileDirective::File {
+        path: PathBuf::from(path_str.trim()),
+        delete,
+    })
+}
+
+/// Match a file name against a pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| wildcard_match(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
+
+/// Expand an `@files(...)` pattern; wildcards are allowed in the file name only
+fn expand_glob(pattern: &Path) -> std::result::Result<Vec<PathBuf>, String> {
+    let file_pattern: Vec<char> = pattern
+        .file_name()
+        .map(|n| n.to_string_lossy().chars().collect())
+        .unwrap_or_default();
+    let dir = match pattern.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read @files({}): {}", pattern.display(), e))?;
+
+    let mut matches: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .filter(|entry| {
+            let name: Vec<char> = entry.file_name().to_string_lossy().chars().collect();
+            wildcard_match(&file_pattern, &name)
+        })
+        .map(|entry| dir.join(entry.file_name()))
+        .collect();
+    matches.sort();
+
+    if matches.is_empty() {
+        return Err(format!("@files({}) matched no files", pattern.display()));
+    }
+    Ok(matches)
+}
+
+/// Include state for one top-level value
+struct IncludeExpander<'a> {
+    files_to_delete: &'a mut Vec<PathBuf>,
+    /// Canonical paths of the files currently being expanded (cycle detection)
+    stack: Vec<PathBuf>,
+    bytes: usize,
+}
+
+impl IncludeExpander<'_> {
+    fn expand(&mut self, text: &str, base: Option<&Path>) -> std::result::Result<String, String> {
+        if !text.contains("@file") {
+            return Ok(text.to_string());
+        }
+
+        let mut output = String::with_capacity(text.len());
+        for line in text.split_inclusive('\n') {
+            let Some(directive) = parse_file_directive(line) else {
+                output.push_str(line);
+                continue;
+            };
+
+            let resolve = |path: PathBuf| match base {
+                Some(base) if path.is_relative() => base.join(path),
+                _ => path,
+            };
+            let included = match directive {
+                FileDirective::File { path, delete } => {
+                    let path = resolve(path);
+                    let content = self.include_file(&path)?;
+                    // Only a directive written in the value itself deletes;
+                    // ones inside included files never do
+                    if delete && self.stack.is_empty() && !self.files_to_delete.contains(&path) {
+                        self.files_to_delete.push(path);
+                    }
+                    content
+                },
+                FileDirective::Glob(pattern) => {
+                    let mut parts = Vec::new();
+                    for path in expand_glob(&resolve(pattern))? {
+                        let content = self.include_file(&path)?;
+                        parts.push(content.trim_end_matches('\n').to_string());
+                    }
+                    parts.join("\n\n")
+                },
+            };
+
+            output.push_str(&included);
+            // Keep the directive line's own line break
+            if line.ends_with('\n') && !included.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Read a file and expand the directives inside it, relative to its directory
+    fn include_file(&mut self, path: &Path) -> std::result::Result<String, String> {
+        let read_error =
+            |e: std::io::Error| format!("Failed to read @file({}): {}", path.display(), e);
+
+        let canonical = path.canonicalize().map_err(read_error)?;
+        if self.stack.contains(&canonical) {
+            let chain: Vec<String> = self
+                .stack
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(format!("Circular @file include: {}", chain.join(" -> ")));
+        }
+
+        let size = std::fs::metadata(&canonical).map_err(read_error)?.len() as usize;
+        self.bytes += size;
+        if self.bytes > MAX_INCLUDE_BYTES {
+            return Err(format!(
+                "@file includes exceed {} KB (at {}); reference the file instead of inlining it",
+                MAX_INCLUDE_BYTES / 1024,
+                path.display()
+            ));
+        }
+
+        let content = std::fs::read_to_string(&canonical).map_err(read_error)?;
+
+        self.stack.push(canonical.clone());
+        let expanded = self.expand(&content, canonical.parent());
+        self.stack.pop();
+        expanded
+    }
+}
+
+/// Expand include directives in free text (task specs, `ie log` messages)
+///
+/// Any line consisting only of a directive is replaced by the file content:
+///
+/// - `@file(path)` - include the file, delete it after success
+/// - `@file(path, keep)` - include the file, keep it
+/// - `@files(dir/*.md)` - include every match (`*`/`?` in the file name),
+///   in name order, separated by blank lines; matched files are kept
+///
+/// Included files may contain directives themselves; relative paths there are
+/// resolved against the including file's directory, and the files they name
+/// are always kept. Include cycles and more than [`MAX_INCLUDE_BYTES`] of
+/// included text are errors. Files to delete are appended to `result`.
+pub fn expand_file_directives(
+    text: &str,
+    result: &mut FileIncludeResult,
+) -> std::result::Result<String, String> {
+    IncludeExpander {
+        files_to_delete: &mut result.files_to_delete,
+        stack: Vec::new(),
+        bytes: 0,
     }
+    .expand(text, None)
 }
 
 /// Process @file directives in a TaskTree recursively
 fn process_task_tree_includes(
     task: &mut TaskTree,
-    files_to_delete: &mut Vec<PathBuf>,
+    result: &mut FileIncludeResult,
 ) -> std::result::Result<(), String> {
     // Process spec field
     if let Some(ref spec_value) = task.spec {
-        if let Some((file_path, should_delete)) = parse_file_directive(spec_value) {
-            // Read file content
-            let content = std::fs::read_to_string(&file_path)
-                .map_err(|e| format!("Failed to read @file({}): {}", file_path.display(), e))?;
-
-            task.spec = Some(content);
-
-            if should_delete {
-                files_to_delete.push(file_path);
-            }
-        }
+        task.spec = Some(expand_file_directives(spec_value, result)?);
     }
 
     // Process children recursively
     if let Some(ref mut children) = task.children {
         for child in children.iter_mut() {
-            process_task_tree_includes(child, files_to_delete)?;
+            process_task_tree_includes(child, result)?;
         }
     }
 
@@ -1048,12 +2122,7 @@ fn process_task_tree_includes(
 ///
 /// This function scans all task specs for @file(path) syntax and replaces
 /// them with the file contents. Files are tracked for deletion after
-/// successful plan execution.
-///
-/// # Syntax
-///
-/// - `@file(/path/to/file.md)` - Include file content, delete after success
-/// - `@file(/path/to/file.md, keep)` - Include file content, keep the file
+/// successful plan execution. See [`expand_file_directives`] for the syntax.
 ///
 /// # Example
 ///
@@ -1071,7 +2140,7 @@ pub fn process_file_includes(
     let mut result = FileIncludeResult::default();
 
     for task in request.tasks.iter_mut() {
-        process_task_tree_includes(task, &mut result.files_to_delete)?;
+        process_task_tree_includes(task, &mut result)?;
     }
 
     Ok(result)
@@ -1188,6 +2257,7 @@ mod tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -1944,6 +3014,7 @@ mod tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         // Execute the plan
@@ -2050,6 +3121,7 @@ mod tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2131,6 +3203,7 @@ mod tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result3 = executor.execute(&modified_request).await.unwrap();
@@ -2216,6 +3289,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2225,84 +3299,330 @@ mod tests {
         assert_eq!(result.created_count, 4, "Should create 4 tasks");
         assert_eq!(result.dependency_count, 4, "Should create 4 dependencies");
 
-        // Get task IDs
-        let foundation_id = *result.task_id_map.get("Foundation").unwrap();
-        let layer1_id = *result.task_id_map.get("Layer 1").unwrap();
-        let layer2_id = *result.task_id_map.get("Layer 2").unwrap();
-        let integration_id = *result.task_id_map.get("Integration").unwrap();
+        // Get task IDs
+        let foundation_id = *result.task_id_map.get("Foundation").unwrap();
+        let layer1_id = *result.task_id_map.get("Layer 1").unwrap();
+        let layer2_id = *result.task_id_map.get("Layer 2").unwrap();
+        let integration_id = *result.task_id_map.get("Integration").unwrap();
+
+        // Verify dependency: Layer 1 -> Foundation
+        let deps1: Vec<(i64,)> =
+            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ?")
+                .bind(layer1_id)
+                .fetch_all(&ctx.pool)
+                .await
+                .unwrap();
+
+        assert_eq!(deps1.len(), 1);
+        assert_eq!(deps1[0].0, foundation_id);
+
+        // Verify dependency: Layer 2 -> Layer 1
+        let deps2: Vec<(i64,)> =
+            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ?")
+                .bind(layer2_id)
+                .fetch_all(&ctx.pool)
+                .await
+                .unwrap();
+
+        assert_eq!(deps2.len(), 1);
+        assert_eq!(deps2[0].0, layer1_id);
+
+        // Verify dependencies: Integration -> Foundation, Layer 2
+        let deps3: Vec<(i64,)> =
+            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ? ORDER BY blocking_task_id")
+                .bind(integration_id)
+                .fetch_all(&ctx.pool)
+                .await
+                .unwrap();
+
+        assert_eq!(deps3.len(), 2);
+        let mut blocking_ids: Vec<i64> = deps3.iter().map(|d| d.0).collect();
+        blocking_ids.sort();
+
+        let mut expected_ids = vec![foundation_id, layer2_id];
+        expected_ids.sort();
+
+        assert_eq!(blocking_ids, expected_ids);
+    }
+
+    #[tokio::test]
+    async fn test_plan_executor_invalid_dependency() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+
+        // Create a plan with an invalid dependency
+        let request = PlanRequest {
+            tasks: vec![TaskTree {
+                name: Some("Task A".to_string()),
+                spec: Some("Depends on non-existent task".to_string()),
+                priority: None,
+                children: None,
+                depends_on: Some(vec!["NonExistent".to_string()]),
+                id: None,
+                status: None,
+                active_form: None,
+                parent_id: None,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let executor = PlanExecutor::new(&ctx.pool);
+        let result = executor.execute(&request).await.unwrap();
+
+        assert!(!result.success, "Plan execution should fail");
+        assert!(result.error.is_some(), "Should have error message");
+        let error = result.error.unwrap();
+        assert!(
+            error.contains("NonExistent"),
+            "Error should mention the missing dependency: {}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_on_error_skip_applies_valid_tasks() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let executor = PlanExecutor::new(&ctx.pool);
+
+        // An existing parent with an unfinished child cannot be completed
+        let setup = parse_request(
+            r#"{"tasks":[{"name":"Parent","spec":"p","children":[{"name":"Open child"}]}]}"#,
+        )
+        .unwrap();
+        assert!(executor.execute(&setup).await.unwrap().success);
+
+        let request = parse_request(
+            r#"{"on_error":"skip","tasks":[
+                {"name":"Good","spec":"ok"},
+                {"name":"Bad dep","depends_on":["Missing"],"children":[{"name":"Orphan"}]},
+                {"name":"Needs Bad","depends_on":["Bad dep"]},
+                {"name":"No spec","status":"doing"},
+                {"name":"Parent","status":"done","spec":"changed"},
+                {"name":"Uses Parent","depends_on":["Parent"]}
+            ]}"#,
+        )
+        .unwrap();
+        let result = executor.execute(&request).await.unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.created_count, 2);
+        let failed: Vec<(&str, &str)> = result
+            .task_results
+            .iter()
+            .filter(|r| r.status == TaskOutcomeStatus::Failed)
+            .map(|r| (r.name.as_deref().unwrap(), r.error.as_deref().unwrap()))
+            .collect();
+        assert_eq!(failed.len(), 5, "{:?}", failed);
+        assert!(failed[0].1.contains("'Missing' is not in the plan"));
+        assert_eq!(failed[1], ("Orphan", "Parent task 'Bad dep' failed"));
+        assert_eq!(
+            failed[2],
+            ("Needs Bad", "Depends on 'Bad dep', which failed")
+        );
+        assert!(failed[3].1.contains("spec (description) is required"));
+        assert!(failed[4].1.contains("Cannot complete task 'Parent'"));
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("5 task(s) failed")));
+
+        let task_mgr = crate::tasks::TaskManager::new(&ctx.pool);
+        let tasks = task_mgr
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap()
+            .tasks;
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert!(names.contains(&"Good") && names.contains(&"Uses Parent"));
+        assert!(!names.contains(&"Orphan") && !names.contains(&"No spec"));
+
+        // The failed completion left the parent untouched
+        let parent_id = tasks.iter().find(|t| t.name == "Parent").unwrap().id;
+        let parent = task_mgr.get_task(parent_id).await.unwrap();
+        assert_eq!(parent.status, "todo");
+        assert_eq!(parent.spec.as_deref(), Some("p"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_warns_about_complex_new_tasks() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        sqlx::query(
+            "INSERT INTO tasks (name, status, complexity) VALUES ('Payment gateway migration', 'done', 9)",
+        )
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+        let request = parse_request(
+            r#"{"tasks":[
+                {"name":"Billing gateway migration"},
+                {"name":"Search gateway migration","children":[{"name":"Index"}]},
+                {"name":"Fix typo"}
+            ]}"#,
+        )
+        .unwrap();
+        let result = PlanExecutor::new(&ctx.pool)
+            .execute(&request)
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        // The parent is already split; the unrelated task has no estimate
+        let complex: Vec<&String> = result
+            .warnings
+            .iter()
+            .filter(|w| w.contains("looks complex"))
+            .collect();
+        assert_eq!(complex.len(), 1, "{:?}", result.warnings);
+        assert!(complex[0]
+            .starts_with("Task 'Billing gateway migration' looks complex: estimated 9/10"));
+    }
+
+    #[tokio::test]
+    async fn test_plan_on_error_collect_reports_all_and_applies_nothing() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let request = parse_request(
+            r#"{"on_error":"collect","tasks":[
+                {"name":"Good"},
+                {"name":"A","depends_on":["X"]},
+                {"name":"B","status":"doing"}
+            ]}"#,
+        )
+        .unwrap();
+        let result = PlanExecutor::new(&ctx.pool)
+            .execute(&request)
+            .await
+            .unwrap();
 
-        // Verify dependency: Layer 1 -> Foundation
-        let deps1: Vec<(i64,)> =
-            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ?")
-                .bind(layer1_id)
-                .fetch_all(&ctx.pool)
-                .await
-                .unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(
+            error.starts_with("2 task(s) failed; nothing was applied"),
+            "{error}"
+        );
+        assert_eq!(result.task_results.len(), 2);
 
-        assert_eq!(deps1.len(), 1);
-        assert_eq!(deps1[0].0, foundation_id);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
 
-        // Verify dependency: Layer 2 -> Layer 1
-        let deps2: Vec<(i64,)> =
-            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ?")
-                .bind(layer2_id)
-                .fetch_all(&ctx.pool)
-                .await
-                .unwrap();
+        // The default stops at the first failure, without per-task results
+        let mut request = request;
+        request.on_error = OnError::Abort;
+        let result = PlanExecutor::new(&ctx.pool)
+            .execute(&request)
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("'X' is not in the plan"));
+        assert!(result.task_results.is_empty());
+    }
 
-        assert_eq!(deps2.len(), 1);
-        assert_eq!(deps2[0].0, layer1_id);
+    #[tokio::test]
+    async fn test_plan_scope_matches_names_within_subtree() {
+        use crate::test_utils::test_helpers::TestContext;
 
-        // Verify dependencies: Integration -> Foundation, Layer 2
-        let deps3: Vec<(i64,)> =
-            sqlx::query_as("SELECT blocking_task_id FROM dependencies WHERE blocked_task_id = ? ORDER BY blocking_task_id")
-                .bind(integration_id)
-                .fetch_all(&ctx.pool)
-                .await
-                .unwrap();
+        let ctx = TestContext::new().await;
+        let executor = PlanExecutor::new(&ctx.pool);
+        let setup = parse_request(
+            r#"{"tasks":[
+                {"name":"Feature A","children":[{"name":"Write tests"}]},
+                {"name":"Feature B"}
+            ]}"#,
+        )
+        .unwrap();
+        let ids = executor.execute(&setup).await.unwrap().task_id_map;
+
+        let request = |scope: &str| {
+            parse_request(&format!(
+                r#"{{"scope":{},"tasks":[{{"name":"Write tests","priority":"high"}}]}}"#,
+                scope
+            ))
+            .unwrap()
+        };
 
-        assert_eq!(deps3.len(), 2);
-        let mut blocking_ids: Vec<i64> = deps3.iter().map(|d| d.0).collect();
-        blocking_ids.sort();
+        // Inside Feature B the name is new: a subtask is created there
+        let result = executor
+            .execute(&request(&format!(
+                r#"{{"parent_id":{}}}"#,
+                ids["Feature B"]
+            )))
+            .await
+            .unwrap();
+        assert_eq!((result.created_count, result.updated_count), (1, 0));
+        let created = result.task_id_map["Write tests"];
+        assert_ne!(created, ids["Write tests"]);
+        let task_mgr = crate::tasks::TaskManager::new(&ctx.pool);
+        let task = task_mgr.get_task(created).await.unwrap();
+        assert_eq!(task.parent_id, Some(ids["Feature B"]));
+
+        // Inside Feature A the existing task is updated
+        let result = executor
+            .execute(&request(&format!(
+                r#"{{"parent_id":{}}}"#,
+                ids["Feature A"]
+            )))
+            .await
+            .unwrap();
+        assert_eq!(result.task_id_map["Write tests"], ids["Write tests"]);
 
-        let mut expected_ids = vec![foundation_id, layer2_id];
-        expected_ids.sort();
+        // "focused" uses the executor's focused task
+        let focused = PlanExecutor::new(&ctx.pool).with_default_parent(ids["Feature A"]);
+        let result = focused.execute(&request(r#""focused""#)).await.unwrap();
+        assert_eq!(result.task_id_map["Write tests"], ids["Write tests"]);
 
-        assert_eq!(blocking_ids, expected_ids);
+        let result = executor.execute(&request(r#""focused""#)).await.unwrap();
+        assert!(result.error.unwrap().contains("needs a focused task"));
+        let result = executor
+            .execute(&request(r#"{"parent_id":9999}"#))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("#9999 not found"));
+
+        let err = parse_request(r#"{"scope":"everywhere","tasks":[]}"#).unwrap_err();
+        assert!(err.to_string().contains("scope: invalid scope"), "{err}");
     }
 
     #[tokio::test]
-    async fn test_plan_executor_invalid_dependency() {
+    async fn test_plan_estimates_roll_up_to_parent() {
         use crate::test_utils::test_helpers::TestContext;
 
         let ctx = TestContext::new().await;
-
-        // Create a plan with an invalid dependency
-        let request = PlanRequest {
-            tasks: vec![TaskTree {
-                name: Some("Task A".to_string()),
-                spec: Some("Depends on non-existent task".to_string()),
-                priority: None,
-                children: None,
-                depends_on: Some(vec!["NonExistent".to_string()]),
-                id: None,
-                status: None,
-                active_form: None,
-                parent_id: None,
-                ..Default::default()
-            }],
-        };
-
         let executor = PlanExecutor::new(&ctx.pool);
-        let result = executor.execute(&request).await.unwrap();
-
-        assert!(!result.success, "Plan execution should fail");
-        assert!(result.error.is_some(), "Should have error message");
-        let error = result.error.unwrap();
+        let request = parse_request(
+            r#"{"tasks":[{"name":"Release","estimate_minutes":30,"children":[
+                {"name":"Docs","estimate_minutes":60},
+                {"name":"Changelog"}
+            ]}]}"#,
+        )
+        .unwrap();
+        let ids = executor.execute(&request).await.unwrap().task_id_map;
+
+        let task_mgr = crate::tasks::TaskManager::new(&ctx.pool);
+        let context = task_mgr.get_task_context(ids["Release"]).await.unwrap();
+        assert_eq!(context.task.estimate_minutes, Some(30));
+        assert_eq!(context.estimate.total_minutes, 90);
+        assert_eq!(context.estimate.unestimated, 1);
+
+        // Updates change the estimate; negative estimates are rejected at parse time
+        let update = parse_request(r#"{"tasks":[{"name":"Docs","estimate_minutes":15}]}"#).unwrap();
+        executor.execute(&update).await.unwrap();
+        let docs = task_mgr.get_task(ids["Docs"]).await.unwrap();
+        assert_eq!(docs.estimate_minutes, Some(15));
+
+        let err =
+            parse_request(r#"{"tasks":[{"name":"Docs","estimate_minutes":-5}]}"#).unwrap_err();
         assert!(
-            error.contains("NonExistent"),
-            "Error should mention the missing dependency: {}",
-            error
+            err.to_string().contains("tasks[0].estimate_minutes"),
+            "{err}"
         );
     }
 
@@ -2340,6 +3660,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2406,6 +3727,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2489,6 +3811,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2502,6 +3825,74 @@ mod tests {
         assert_eq!(result.dependency_count, 4, "Should create 4 dependencies");
     }
 
+    #[tokio::test]
+    async fn test_plan_executor_cycle_through_existing_dependencies() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let executor = PlanExecutor::new(&ctx.pool);
+        let result = executor
+            .execute(
+                &parse_request(
+                    r#"{"tasks":[
+                        {"name":"A"},
+                        {"name":"B","depends_on":["A"]},
+                        {"name":"C","depends_on":["B"]}
+                    ]}"#,
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        // Only A → C is new; C → B → A is stored
+        let result = executor
+            .execute(
+                &parse_request(r#"{"tasks":[{"name":"A","depends_on":["C"]},{"name":"C"}]}"#)
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.error.unwrap(),
+            "Circular dependency detected with existing dependencies: A (#1) → C (#3) → B (#2) → A (#1)"
+        );
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dependencies")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 2);
+    }
+
+    #[tokio::test]
+    async fn test_plan_rerun_keeps_dependencies_idempotent() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let executor = PlanExecutor::new(&ctx.pool);
+        let request = parse_request(
+            r#"{"tasks":[{"name":"A"},{"name":"B","depends_on":["A","A"]},{"name":"C","depends_on":["A","B"]}]}"#,
+        )
+        .unwrap();
+
+        let first = executor.execute(&request).await.unwrap();
+        assert!(first.success, "{:?}", first.error);
+        assert_eq!(first.dependency_count, 3);
+
+        let second = executor.execute(&request).await.unwrap();
+        assert!(second.success, "{:?}", second.error);
+        assert_eq!(second.dependency_count, 0);
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dependencies")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 3);
+    }
+
     #[tokio::test]
     async fn test_plan_executor_self_dependency() {
         use crate::test_utils::test_helpers::TestContext;
@@ -2522,6 +3913,7 @@ mod tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2548,7 +3940,7 @@ mod tests {
         let ctx = TestContext::new().await;
         let executor = PlanExecutor::new(&ctx.pool);
 
-        let result = executor.find_tasks_by_names(&[]).await.unwrap();
+        let result = executor.find_tasks_by_names(&[], None).await.unwrap();
         assert!(result.is_empty(), "Empty input should return empty map");
     }
 
@@ -2587,6 +3979,7 @@ mod tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
         executor.execute(&request).await.unwrap();
 
@@ -2596,7 +3989,7 @@ mod tests {
             "Task B".to_string(),
             "Task C".to_string(),
         ];
-        let result = executor.find_tasks_by_names(&names).await.unwrap();
+        let result = executor.find_tasks_by_names(&names, None).await.unwrap();
 
         assert_eq!(result.len(), 2, "Should find 2 out of 3 tasks");
         assert!(result.contains_key("Task A"));
@@ -2629,7 +4022,10 @@ mod tests {
             });
         }
 
-        let request = PlanRequest { tasks };
+        let request = PlanRequest {
+            tasks,
+            ..Default::default()
+        };
 
         let start = std::time::Instant::now();
         let result = executor.execute(&request).await.unwrap();
@@ -2646,6 +4042,71 @@ mod tests {
         println!("✅ Created 1000 tasks in {:?}", duration);
     }
 
+    #[tokio::test]
+    async fn test_plan_batches_span_several_statements() {
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let focus = crate::tasks::TaskManager::new(&ctx.pool)
+            .add_task("Focus", None, None, None, None, None)
+            .await
+            .unwrap();
+        let executor = PlanExecutor::new(&ctx.pool).with_default_parent(focus.id);
+
+        // More children than one multi-row INSERT takes, one of them nested
+        let mut children: Vec<TaskTree> = (0..BATCH_ROWS + 100)
+            .map(|i| TaskTree {
+                name: Some(format!("Child {}", i)),
+                ..Default::default()
+            })
+            .collect();
+        children[BATCH_ROWS].children = Some(vec![TaskTree {
+            name: Some("Grandchild".to_string()),
+            ..Default::default()
+        }]);
+        let request = PlanRequest {
+            tasks: vec![
+                TaskTree {
+                    name: Some("Big".to_string()),
+                    children: Some(children),
+                    ..Default::default()
+                },
+                TaskTree {
+                    name: Some("Root".to_string()),
+                    parent_id: Some(None),
+                    depends_on: Some(vec!["Grandchild".to_string()]),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let result = executor.execute(&request).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.created_count, BATCH_ROWS + 103);
+        assert_eq!(result.dependency_count, 1);
+
+        let rows: Vec<(i64, String, Option<i64>)> =
+            sqlx::query_as("SELECT id, name, parent_id FROM tasks WHERE id != ?")
+                .bind(focus.id)
+                .fetch_all(&ctx.pool)
+                .await
+                .unwrap();
+        assert_eq!(rows.len(), BATCH_ROWS + 103);
+        let big = result.task_id_map["Big"];
+        let last = result.task_id_map[&format!("Child {}", BATCH_ROWS)];
+        for (id, name, parent_id) in rows {
+            assert_eq!(result.task_id_map[&name], id, "{}", name);
+            let expected = match name.as_str() {
+                "Big" => Some(focus.id),
+                "Root" => None,
+                "Grandchild" => Some(last),
+                _ => Some(big),
+            };
+            assert_eq!(parent_id, expected, "{}", name);
+        }
+    }
+
     #[tokio::test]
     async fn test_plan_deep_nesting_20_levels() {
         use crate::test_utils::test_helpers::TestContext;
@@ -2675,6 +4136,7 @@ mod tests {
 
         let request = PlanRequest {
             tasks: vec![build_deep_tree(20, 1)],
+            ..Default::default()
         };
 
         let start = std::time::Instant::now();
@@ -2720,6 +4182,153 @@ mod tests {
         assert_eq!(task.depends_on, vec!["Dep1", "Dep2"]);
         assert_eq!(task.id, Some(42));
     }
+
+    #[test]
+    fn test_parse_request_reports_field_path() {
+        let json = r#"{"tasks":[{"name":"A"},{"name":"B","children":[{"name":"C","priority":"urgent"}]}]}"#;
+        let err = parse_request(json).unwrap_err().to_string();
+        assert!(
+            err.contains("tasks[1].children[0].priority: unknown variant `urgent`"),
+            "{err}"
+        );
+        assert!(err.contains("expected one of"), "{err}");
+
+        let err = parse_request(r#"{"tasks":[{"name":"A"}"#).unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON"), "{err}");
+
+        let err = parse_request(r#"{"task":[]}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `tasks`"), "{err}");
+
+        assert_eq!(
+            parse_request(r#"{"tasks":[{"name":"A","task_id":3}]}"#)
+                .unwrap()
+                .tasks[0]
+                .id,
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_schema_enums_match_deserialization() {
+        let schema = schema();
+        let properties = &schema["$defs"]["TaskTree"]["properties"];
+
+        for field in ["status", "priority"] {
+            let values = properties[field]["enum"].as_array().unwrap();
+            assert!(!values.is_empty());
+            for value in values {
+                let json = serde_json::json!({ "tasks": [{ "name": "A", field: value }] });
+                parse_request(&json.to_string()).unwrap();
+            }
+        }
+
+        // Every documented field is one TaskTree understands
+        let full = serde_json::to_value(TaskTree {
+            name: Some("A".into()),
+            spec: Some("s".into()),
+            priority: Some(PriorityValue::High),
+            children: Some(vec![]),
+            depends_on: Some(vec![]),
+            id: Some(1),
+            status: Some(TaskStatus::Todo),
+            active_form: Some("Doing A".into()),
+            estimate_minutes: Some(30),
+            milestone: Some("v1".into()),
+            needs_human: None,
+            blocked_reason: None,
+            outcome: None,
+            artifacts: None,
+            links: None,
+            checklist: None,
+            parent_id: Some(Some(2)),
+            delete: Some(false),
+        })
+        .unwrap();
+        for key in full.as_object().unwrap().keys() {
+            assert!(properties.get(key).is_some(), "schema lacks {key}");
+        }
+    }
+
+    #[test]
+    fn test_parse_file_directive() {
+        assert_eq!(
+            parse_file_directive(" @file(/tmp/a.md) "),
+            Some(FileDirective::File {
+                path: PathBuf::from("/tmp/a.md"),
+                delete: true
+            })
+        );
+        assert_eq!(
+            parse_file_directive("@file(/tmp/a.md, keep)"),
+            Some(FileDirective::File {
+                path: PathBuf::from("/tmp/a.md"),
+                delete: false
+            })
+        );
+        assert_eq!(
+            parse_file_directive("@files(docs/*.md)"),
+            Some(FileDirective::Glob(PathBuf::from("docs/*.md")))
+        );
+        assert_eq!(parse_file_directive("see @file(a.md)"), None);
+    }
+
+    #[test]
+    fn test_expand_file_directives_nested_and_glob() {
+        let dir = tempfile::tempdir().unwrap();
+        let docs = dir.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        std::fs::write(docs.join("b.md"), "B").unwrap();
+        std::fs::write(docs.join("a.md"), "A\n@file(b.md, keep)\n").unwrap();
+        std::fs::write(docs.join("notes.txt"), "ignored").unwrap();
+        let analysis = dir.path().join("analysis.md");
+        std::fs::write(&analysis, "Findings").unwrap();
+        // A nested plain @file, reached through @files, is never deleted
+        std::fs::write(dir.path().join("shared.md"), "Shared").unwrap();
+        std::fs::write(docs.join("c.md"), "@file(../shared.md)\n").unwrap();
+
+        let text = format!(
+            "Summary\n@file({})\n@files({}/*.md)\nEnd",
+            analysis.display(),
+            docs.display()
+        );
+        let mut result = FileIncludeResult::default();
+        let expanded = expand_file_directives(&text, &mut result).unwrap();
+
+        assert_eq!(expanded, "Summary\nFindings\nA\nB\n\nB\n\nShared\nEnd");
+        // Only the top-level plain @file is deleted; @files, keep and
+        // directives inside included files are not
+        assert_eq!(result.files_to_delete, vec![analysis]);
+
+        // Text without directives is untouched
+        let mut result = FileIncludeResult::default();
+        assert_eq!(
+            expand_file_directives("mentions @file inline", &mut result).unwrap(),
+            "mentions @file inline"
+        );
+    }
+
+    #[test]
+    fn test_expand_file_directives_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.md"), "@file(b.md, keep)").unwrap();
+        std::fs::write(dir.path().join("b.md"), "@file(a.md, keep)").unwrap();
+        let big = dir.path().join("big.md");
+        std::fs::write(&big, "x".repeat(MAX_INCLUDE_BYTES + 1)).unwrap();
+
+        let mut result = FileIncludeResult::default();
+        let cycle = format!("@file({}, keep)", dir.path().join("a.md").display());
+        let err = expand_file_directives(&cycle, &mut result).unwrap_err();
+        assert!(err.contains("Circular @file include"), "{err}");
+
+        let err =
+            expand_file_directives(&format!("@file({})", big.display()), &mut result).unwrap_err();
+        assert!(err.contains("exceed"), "{err}");
+
+        let missing = format!("@files({}/*.rs)", dir.path().display());
+        let err = expand_file_directives(&missing, &mut result).unwrap_err();
+        assert!(err.contains("matched no files"), "{err}");
+        assert!(result.files_to_delete.is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -2747,6 +4356,7 @@ mod dataflow_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2872,6 +4482,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -2893,6 +4504,7 @@ mod parent_id_tests {
                 parent_id: Some(Some(parent_id)),
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -2927,6 +4539,7 @@ mod parent_id_tests {
                 parent_id: Some(None), // Explicit null = root
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         // Create executor with a default parent
@@ -2944,6 +4557,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
         let executor = PlanExecutor::new(&ctx.pool);
         let parent_result = executor.execute(&parent_request).await.unwrap();
@@ -2997,6 +4611,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let executor = PlanExecutor::new(&ctx.pool);
@@ -3052,6 +4667,7 @@ mod parent_id_tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3081,6 +4697,7 @@ mod parent_id_tests {
                 parent_id: Some(Some(task_a_id)), // Set parent to Task A
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3130,6 +4747,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3149,6 +4767,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3194,6 +4813,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3213,6 +4833,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3232,6 +4853,7 @@ mod parent_id_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result3 = executor.execute(&request3).await.unwrap();
@@ -3264,6 +4886,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3289,9 +4912,18 @@ mod delete_tests {
                 id: Some(task_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3325,9 +4957,18 @@ mod delete_tests {
                 id: None, // No id!
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result = executor.execute(&request).await.unwrap();
@@ -3357,6 +4998,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3413,6 +5055,7 @@ mod delete_tests {
                     ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3458,10 +5101,19 @@ mod delete_tests {
                     id: Some(delete_id),
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
             ],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3495,9 +5147,18 @@ mod delete_tests {
                 id: Some(99999), // Non-existent ID
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result = executor.execute(&request).await.unwrap();
@@ -3566,6 +5227,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3587,9 +5249,18 @@ mod delete_tests {
                 id: Some(parent_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3669,6 +5340,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3687,9 +5359,18 @@ mod delete_tests {
                 id: Some(root_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3724,6 +5405,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3741,6 +5423,14 @@ mod delete_tests {
                     id: Some(existing_id),
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
@@ -3753,10 +5443,19 @@ mod delete_tests {
                     id: Some(88888), // Non-existent
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
             ],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3798,6 +5497,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3828,9 +5528,18 @@ mod delete_tests {
                 id: Some(task_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3877,6 +5586,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -3895,6 +5605,14 @@ mod delete_tests {
                     id: Some(task_id),
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
@@ -3907,10 +5625,19 @@ mod delete_tests {
                     id: Some(task_id), // Same ID again
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
             ],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -3981,6 +5708,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -4012,9 +5740,18 @@ mod delete_tests {
                 id: Some(parent_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -4085,6 +5822,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -4105,6 +5843,14 @@ mod delete_tests {
                     id: Some(parent_id),
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
@@ -4117,10 +5863,19 @@ mod delete_tests {
                     id: Some(child_id),
                     status: None,
                     active_form: None,
+                    estimate_minutes: None,
+                    milestone: None,
+                    needs_human: None,
+                    blocked_reason: None,
+                    outcome: None,
+                    artifacts: None,
+                    links: None,
+                    checklist: None,
                     parent_id: None,
                     delete: Some(true),
                 },
             ],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -4202,6 +5957,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -4229,9 +5985,18 @@ mod delete_tests {
                 id: Some(root_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -4282,9 +6047,18 @@ mod delete_tests {
                 id: Some(99999), // Non-existent
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result = executor.execute(&request).await.unwrap();
@@ -4325,6 +6099,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -4342,9 +6117,18 @@ mod delete_tests {
                 id: Some(task_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -4398,6 +6182,7 @@ mod delete_tests {
                 parent_id: None,
                 ..Default::default()
             }],
+            ..Default::default()
         };
 
         let result1 = executor.execute(&request1).await.unwrap();
@@ -4431,9 +6216,18 @@ mod delete_tests {
                 id: Some(task_id),
                 status: None,
                 active_form: None,
+                estimate_minutes: None,
+                milestone: None,
+                needs_human: None,
+                blocked_reason: None,
+                outcome: None,
+                artifacts: None,
+                links: None,
+                checklist: None,
                 parent_id: None,
                 delete: Some(true),
             }],
+            ..Default::default()
         };
 
         let result2 = executor.execute(&request2).await.unwrap();
@@ -0,0 +1,451 @@
+//! Plan application journal for crash recovery
+//!
+//! `ie plan` changes the database in one transaction, but some steps run after
+//! the commit: the Dashboard notification and auto-focusing the `doing` task.
+//! If the process dies in between, the tasks are saved while the focus is not.
+//!
+//! Every execution is journaled in `plan_journal`:
+//!
+//! - `started`: written before the transaction begins
+//! - `committed`: written *inside* the plan transaction, so it is durable if and
+//!   only if the plan's changes are
+//! - `finished` / `failed`: written once the execution has returned
+//!
+//! An entry left in `started` or `committed` by a process that is gone was
+//! interrupted. [`PlanJournal::recover`] settles such entries: `started` means
+//! nothing was applied, `committed` means the changes are in and only the
+//! post-commit steps need finishing.
+
+use crate::error::Result;
+use crate::plan::{PlanRequest, PlanResult};
+use crate::tasks::TaskManager;
+use crate::workspace::WorkspaceManager;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Entries older than this are treated as interrupted even if their process
+/// id is in use again (or cannot be checked on this platform)
+const STALE_AFTER_MINUTES: i64 = 10;
+
+/// Settled entries kept for inspection
+const KEEP_ENTRIES: i64 = 100;
+
+/// What recovery found and did for one interrupted plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecoveredPlan {
+    pub journal_id: i64,
+    pub request_hash: String,
+    /// Phase the entry was stuck in: `started` or `committed`
+    pub phase: String,
+    pub started_at: DateTime<Utc>,
+    pub summary: String,
+}
+
+impl RecoveredPlan {
+    /// One-line report for the user
+    pub fn message(&self) -> String {
+        format!(
+            "Recovered interrupted plan from {} (journal #{}): {}",
+            self.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            self.journal_id,
+            self.summary
+        )
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PendingEntry {
+    id: i64,
+    request_hash: String,
+    phase: String,
+    session_id: String,
+    pid: i64,
+    task_id_map: Option<String>,
+    focus_task_id: Option<i64>,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+pub struct PlanJournal<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> PlanJournal<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stable hash of a plan request, to recognise a retried plan
+    pub fn request_hash(request: &PlanRequest) -> String {
+        let json = serde_json::to_string(request).unwrap_or_default();
+        format!("{:x}", Sha256::digest(json.as_bytes()))
+    }
+
+    /// Record that a plan is about to be applied; returns the journal id
+    pub async fn start(&self, request: &PlanRequest) -> Result<i64> {
+        let now = Utc::now();
+        let id = sqlx::query(
+            r#"
+            INSERT INTO plan_journal (request_hash, phase, session_id, pid, started_at, updated_at)
+            VALUES (?, 'started', ?, ?, ?, ?)
+            "#,
+        )
+        .bind(Self::request_hash(request))
+        .bind(crate::workspace::resolve_session_id(None))
+        .bind(std::process::id() as i64)
+        .bind(now)
+        .bind(now)
+        .execute(self.pool)
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// Mark the entry committed, as part of the plan's own transaction
+    ///
+    /// Stores what the post-commit steps need: the created/updated task ids
+    /// and the task to focus.
+    pub async fn mark_committed_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        journal_id: i64,
+        task_id_map: &HashMap<String, i64>,
+        focus_task_id: Option<i64>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE plan_journal
+            SET phase = 'committed', task_id_map = ?, focus_task_id = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(serde_json::to_string(task_id_map)?)
+        .bind(focus_task_id)
+        .bind(Utc::now())
+        .bind(journal_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Settle the entry once execution has returned
+    ///
+    /// An error after the commit still counts as `finished`: the changes are
+    /// applied, and the error is kept as a note.
+    pub async fn finish(&self, journal_id: i64, outcome: &Result<PlanResult>) -> Result<()> {
+        let error = match outcome {
+            Ok(result) if result.success => None,
+            Ok(result) => Some(result.error.clone().unwrap_or_default()),
+            Err(e) => Some(e.to_string()),
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE plan_journal
+            SET phase = CASE
+                    WHEN phase = 'committed' OR ? IS NULL THEN 'finished'
+                    ELSE 'failed'
+                END,
+                note = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&error)
+        .bind(&error)
+        .bind(Utc::now())
+        .bind(journal_id)
+        .execute(self.pool)
+        .await?;
+
+        self.prune().await
+    }
+
+    /// Find plans interrupted by a crash and settle them
+    ///
+    /// Entries still owned by a live process are left alone. For a committed
+    /// entry the post-commit steps are redone: the Dashboard is notified and
+    /// the `doing` task is focused again for the session that ran the plan,
+    /// if it still exists, is still `doing` and that session has no other focus.
+    pub async fn recover(&self, task_mgr: &TaskManager<'_>) -> Result<Vec<RecoveredPlan>> {
+        let pending: Vec<PendingEntry> = sqlx::query_as(
+            r#"
+            SELECT id, request_hash, phase, session_id, pid, task_id_map, focus_task_id,
+                   started_at, updated_at
+            FROM plan_journal
+            WHERE phase IN ('started', 'committed')
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut recovered = Vec::new();
+        for entry in pending.into_iter().filter(is_interrupted) {
+            let (phase, summary) = if entry.phase == "committed" {
+                ("recovered", self.finish_committed(task_mgr, &entry).await?)
+            } else {
+                (
+                    "interrupted",
+                    "Interrupted before commit; no changes were applied. Re-run the plan."
+                        .to_string(),
+                )
+            };
+
+            sqlx::query(
+                "UPDATE plan_journal SET phase = ?, note = ?, updated_at = ? WHERE id = ? AND phase = ?",
+            )
+            .bind(phase)
+            .bind(&summary)
+            .bind(Utc::now())
+            .bind(entry.id)
+            .bind(&entry.phase)
+            .execute(self.pool)
+            .await?;
+
+            recovered.push(RecoveredPlan {
+                journal_id: entry.id,
+                request_hash: entry.request_hash,
+                phase: entry.phase,
+                started_at: entry.started_at,
+                summary,
+            });
+        }
+
+        Ok(recovered)
+    }
+
+    /// Verify a committed plan's tasks and redo its post-commit steps
+    async fn finish_committed(
+        &self,
+        task_mgr: &TaskManager<'_>,
+        entry: &PendingEntry,
+    ) -> Result<String> {
+        let task_id_map: HashMap<String, i64> = entry
+            .task_id_map
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+
+        let mut missing = 0;
+        for id in task_id_map.values() {
+            if task_mgr.get_task(*id).await.is_err() {
+                missing += 1;
+            }
+        }
+        let mut summary = format!(
+            "Committed {} task(s) before the interruption",
+            task_id_map.len()
+        );
+        if missing > 0 {
+            summary.push_str(&format!(" ({} since deleted by later changes)", missing));
+        }
+
+        task_mgr.notify_batch_changed().await;
+
+        if let Some(focus_id) = entry.focus_task_id {
+            let workspace_mgr = WorkspaceManager::new(self.pool);
+            let current = workspace_mgr
+                .get_current_task(Some(&entry.session_id))
+                .await?
+                .current_task_id;
+            let still_doing = task_mgr
+                .get_task(focus_id)
+                .await
+                .is_ok_and(|task| task.status == "doing");
+
+            if current == Some(focus_id) {
+                summary.push_str(&format!("; task #{} is already focused", focus_id));
+            } else if still_doing && current.is_none() {
+                workspace_mgr
+                    .set_current_task(focus_id, Some(&entry.session_id))
+                    .await?;
+                summary.push_str(&format!(
+                    "; restored focus on task #{} for session '{}'",
+                    focus_id, entry.session_id
+                ));
+            } else {
+                summary.push_str(&format!(
+                    "; focus on task #{} was not restored (task changed or session moved on)",
+                    focus_id
+                ));
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Drop old settled entries
+    async fn prune(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM plan_journal
+            WHERE phase NOT IN ('started', 'committed')
+              AND id <= (SELECT MAX(id) FROM plan_journal) - ?
+            "#,
+        )
+        .bind(KEEP_ENTRIES)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// An unsettled entry is interrupted when its process is gone, or when it has
+/// not moved for longer than any plan takes
+fn is_interrupted(entry: &PendingEntry) -> bool {
+    if Utc::now() - entry.updated_at > Duration::minutes(STALE_AFTER_MINUTES) {
+        return true;
+    }
+    !process_alive(entry.pid)
+}
+
+#[cfg(unix)]
+pub(crate) fn process_alive(pid: i64) -> bool {
+    use nix::errno::Errno;
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    if pid == std::process::id() as i64 {
+        return true;
+    }
+    match i32::try_from(pid) {
+        // Signal 0 only checks existence; EPERM means it exists but isn't ours.
+        // Non-positive ids address process groups, never a single process.
+        Ok(pid) if pid > 0 => !matches!(kill(Pid::from_raw(pid), None), Err(Errno::ESRCH)),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn process_alive(_pid: i64) -> bool {
+    // Without a cheap liveness check, fall back to the staleness timeout
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::{PlanExecutor, TaskStatus, TaskTree};
+    use crate::test_utils::test_helpers::TestContext;
+
+    fn doing_plan(name: &str) -> PlanRequest {
+        PlanRequest {
+            tasks: vec![TaskTree {
+                name: Some(name.to_string()),
+                spec: Some("Goal".to_string()),
+                status: Some(TaskStatus::Doing),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Pretend the process that owns an entry died
+    async fn orphan(pool: &SqlitePool, journal_id: i64) {
+        sqlx::query("UPDATE plan_journal SET pid = -1 WHERE id = ?")
+            .bind(journal_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn phase(pool: &SqlitePool, journal_id: i64) -> String {
+        sqlx::query_scalar("SELECT phase FROM plan_journal WHERE id = ?")
+            .bind(journal_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_journals_finished_and_failed() {
+        let ctx = TestContext::new().await;
+        let executor = PlanExecutor::new(ctx.pool());
+
+        let ok = executor.execute(&doing_plan("Journaled")).await.unwrap();
+        assert!(ok.success);
+        let bad = PlanRequest {
+            tasks: vec![TaskTree {
+                name: Some("No spec".to_string()),
+                status: Some(TaskStatus::Doing),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(!executor.execute(&bad).await.unwrap().success);
+
+        let phases: Vec<String> = sqlx::query_scalar("SELECT phase FROM plan_journal ORDER BY id")
+            .fetch_all(ctx.pool())
+            .await
+            .unwrap();
+        assert_eq!(phases, vec!["finished", "failed"]);
+    }
+
+    #[tokio::test]
+    async fn test_recover_started_entry_reports_nothing_applied() {
+        let ctx = TestContext::new().await;
+        let journal = PlanJournal::new(ctx.pool());
+        let id = journal.start(&doing_plan("Lost")).await.unwrap();
+
+        // Still owned by this (live) process: not touched
+        let task_mgr = TaskManager::new(ctx.pool());
+        assert!(journal.recover(&task_mgr).await.unwrap().is_empty());
+
+        orphan(ctx.pool(), id).await;
+        let recovered = journal.recover(&task_mgr).await.unwrap();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].phase, "started");
+        assert!(recovered[0].summary.contains("no changes were applied"));
+        assert_eq!(phase(ctx.pool(), id).await, "interrupted");
+
+        // Settled entries are not reported twice
+        assert!(journal.recover(&task_mgr).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_committed_entry_restores_focus() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let journal = PlanJournal::new(ctx.pool());
+
+        // Simulate a crash right after commit: task is doing, focus never set
+        let task = task_mgr
+            .add_task("Half applied", Some("Goal"), None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET status = 'doing' WHERE id = ?")
+            .bind(task.id)
+            .execute(ctx.pool())
+            .await
+            .unwrap();
+        let id = journal.start(&doing_plan("Half applied")).await.unwrap();
+        let mut tx = ctx.pool().begin().await.unwrap();
+        let map = HashMap::from([("Half applied".to_string(), task.id)]);
+        journal
+            .mark_committed_in_tx(&mut tx, id, &map, Some(task.id))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        orphan(ctx.pool(), id).await;
+
+        let recovered = journal.recover(&task_mgr).await.unwrap();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].phase, "committed");
+        assert!(recovered[0]
+            .summary
+            .contains(&format!("restored focus on task #{}", task.id)));
+        assert_eq!(phase(ctx.pool(), id).await, "recovered");
+
+        let current = WorkspaceManager::new(ctx.pool())
+            .get_current_task(None)
+            .await
+            .unwrap();
+        assert_eq!(current.current_task_id, Some(task.id));
+    }
+}
@@ -4,29 +4,74 @@
 //! All functions are stateless — no database access.
 
 use crate::error::{IntentError, Result};
-use crate::plan::{FlatTask, TaskStatus};
+use crate::i18n::t;
+use crate::plan::{find_duplicate_names, flatten_task_tree, FlatTask, PlanRequest, TaskStatus};
 use std::collections::{HashMap, HashSet};
 
+/// Run every check that does not need the database, as `ie plan --validate` does.
+///
+/// Returns the number of tasks in the plan.
+pub fn validate_request(request: &PlanRequest) -> Result<usize> {
+    let duplicates = find_duplicate_names(&request.tasks);
+    if !duplicates.is_empty() {
+        return Err(IntentError::InvalidInput(t(
+            "plan.duplicate_names",
+            &[("names", &format!("{:?}", duplicates))],
+        )));
+    }
+
+    let flat_tasks = flatten_task_tree(&request.tasks);
+    for (index, task) in flat_tasks.iter().enumerate() {
+        // The executor skips such entries silently
+        if task.name.is_none() && task.id.is_none() {
+            return Err(IntentError::InvalidInput(t(
+                "plan.missing_name",
+                &[("index", &(index + 1))],
+            )));
+        }
+    }
+
+    validate_dependencies(&flat_tasks)?;
+    detect_circular_dependencies(&flat_tasks)?;
+    validate_batch_single_doing(&flat_tasks)?;
+
+    Ok(flat_tasks.len())
+}
+
 /// Validate that all dependency references exist within the plan.
 pub fn validate_dependencies(flat_tasks: &[FlatTask]) -> Result<()> {
+    match dependency_errors(flat_tasks).into_iter().next() {
+        Some((_, message)) => Err(IntentError::InvalidInput(message)),
+        None => Ok(()),
+    }
+}
+
+/// Every dependency reference that is not in the plan, as (task index, message).
+pub fn dependency_errors(flat_tasks: &[FlatTask]) -> Vec<(usize, String)> {
     let task_names: HashSet<&str> = flat_tasks
         .iter()
         .filter_map(|t| t.name.as_deref())
         .collect();
 
-    for task in flat_tasks {
-        for dep_name in &task.depends_on {
-            if !task_names.contains(dep_name.as_str()) {
-                let task_name = task.name.as_deref().unwrap_or("<unknown>");
-                return Err(IntentError::InvalidInput(format!(
-                    "Task '{}' depends on '{}', but '{}' is not in the plan",
-                    task_name, dep_name, dep_name
-                )));
-            }
+    let mut errors = Vec::new();
+    for (index, task) in flat_tasks.iter().enumerate() {
+        if let Some(dep_name) = task
+            .depends_on
+            .iter()
+            .find(|dep_name| !task_names.contains(dep_name.as_str()))
+        {
+            let task_name = task.name.as_deref().unwrap_or("<unknown>");
+            errors.push((
+                index,
+                t(
+                    "plan.unknown_dependency",
+                    &[("task", &task_name), ("dependency", dep_name)],
+                ),
+            ));
         }
     }
 
-    Ok(())
+    errors
 }
 
 /// Validate that at most one task in the batch has status='doing'.
@@ -41,9 +86,9 @@ pub fn validate_batch_single_doing(flat_tasks: &[FlatTask]) -> Result<()> {
             .iter()
             .map(|t| t.name.as_deref().unwrap_or("<unknown>"))
             .collect();
-        return Err(IntentError::InvalidInput(format!(
-            "Batch single doing constraint violated: only one task per batch can have status='doing'. Found: {}",
-            names.join(", ")
+        return Err(IntentError::InvalidInput(t(
+            "plan.multiple_doing",
+            &[("names", &names.join(", "))],
         )));
     }
 
@@ -76,9 +121,9 @@ pub fn detect_circular_dependencies(flat_tasks: &[FlatTask]) -> Result<()> {
     for task in flat_tasks {
         if let Some(name) = &task.name {
             if task.depends_on.contains(name) {
-                return Err(IntentError::InvalidInput(format!(
-                    "Circular dependency detected: task '{}' depends on itself",
-                    name
+                return Err(IntentError::InvalidInput(t(
+                    "plan.self_dependency",
+                    &[("task", name)],
                 )));
             }
         }
@@ -92,9 +137,9 @@ pub fn detect_circular_dependencies(flat_tasks: &[FlatTask]) -> Result<()> {
                 .iter()
                 .map(|&idx| flat_tasks[idx].name.as_deref().unwrap_or("<unknown>"))
                 .collect();
-            return Err(IntentError::InvalidInput(format!(
-                "Circular dependency detected: {}",
-                cycle_names.join(" → ")
+            return Err(IntentError::InvalidInput(t(
+                "plan.dependency_cycle",
+                &[("cycle", &cycle_names.join(" → "))],
             )));
         }
     }
@@ -102,6 +147,44 @@ pub fn detect_circular_dependencies(flat_tasks: &[FlatTask]) -> Result<()> {
     Ok(())
 }
 
+/// The cycle that `task` depending on `dependency` would close, if any.
+///
+/// `depends_on` maps a task ID to the IDs it depends on. Returns the path
+/// `task → dependency → … → task`.
+pub fn find_dependency_cycle(
+    depends_on: &HashMap<i64, Vec<i64>>,
+    task: i64,
+    dependency: i64,
+) -> Option<Vec<i64>> {
+    if task == dependency {
+        return Some(vec![task, task]);
+    }
+    // Breadth-first from the dependency back to the task, for the shortest path
+    let mut came_from: HashMap<i64, i64> = HashMap::new();
+    let mut queue = std::collections::VecDeque::from([dependency]);
+    while let Some(current) = queue.pop_front() {
+        for &next in depends_on.get(&current).into_iter().flatten() {
+            if next == dependency || came_from.contains_key(&next) {
+                continue;
+            }
+            came_from.insert(next, current);
+            if next == task {
+                let mut path = vec![task];
+                let mut node = task;
+                while node != dependency {
+                    node = came_from[&node];
+                    path.push(node);
+                }
+                path.push(task);
+                path.reverse();
+                return Some(path);
+            }
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
 /// Tarjan's algorithm for finding strongly connected components.
 ///
 /// Returns a list of SCCs, where each SCC is a list of node indices.
@@ -115,6 +198,7 @@ pub fn tarjan_scc(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
     let mut lowlink = vec![0; n];
     let mut result = Vec::new();
 
+    #[allow(clippy::too_many_arguments)]
     fn strongconnect(
         v: usize,
         graph: &[Vec<usize>],
@@ -185,6 +269,42 @@ pub fn tarjan_scc(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_request() {
+        let parse = |json: &str| crate::plan::parse_request(json).unwrap();
+
+        let request = parse(
+            r#"{"tasks":[{"name":"A","children":[{"name":"B","depends_on":["C"]}]},{"name":"C"}]}"#,
+        );
+        assert_eq!(validate_request(&request).unwrap(), 3);
+
+        for (json, expected) in [
+            (
+                r#"{"tasks":[{"name":"A"},{"name":"A"}]}"#,
+                "Duplicate task names",
+            ),
+            (
+                r#"{"tasks":[{"name":"A","depends_on":["Z"]}]}"#,
+                "is not in the plan",
+            ),
+            (
+                r#"{"tasks":[{"name":"A","depends_on":["B"]},{"name":"B","depends_on":["A"]}]}"#,
+                "ircular",
+            ),
+            (
+                r#"{"tasks":[{"name":"A","status":"doing"},{"name":"B","status":"doing"}]}"#,
+                "single doing",
+            ),
+            (
+                r#"{"tasks":[{"spec":"no name"}]}"#,
+                "neither a name nor an id",
+            ),
+        ] {
+            let err = validate_request(&parse(json)).unwrap_err().to_string();
+            assert!(err.contains(expected), "{json}: {err}");
+        }
+    }
+
     #[test]
     fn test_validate_batch_single_doing_ok() {
         let tasks = vec![
@@ -261,6 +381,18 @@ mod tests {
         assert!(validate_dependencies(&[]).is_ok());
     }
 
+    #[test]
+    fn test_find_dependency_cycle() {
+        // 1 depends on 2, 2 on 3
+        let depends_on = HashMap::from([(1, vec![2]), (2, vec![3])]);
+        assert_eq!(
+            find_dependency_cycle(&depends_on, 3, 1),
+            Some(vec![3, 1, 2, 3])
+        );
+        assert_eq!(find_dependency_cycle(&depends_on, 1, 3), None);
+        assert_eq!(find_dependency_cycle(&depends_on, 4, 4), Some(vec![4, 4]));
+    }
+
     #[test]
     fn test_detect_circular_no_cycle() {
         let tasks = vec![
@@ -0,0 +1,459 @@
+//! Plan workspaces: alternative plans kept beside the task tree
+//!
+//! `ie plan` merges a plan into the task tree by name, so two alternative
+//! plans ("approach A" vs "approach B") overwrite each other's tasks. A plan
+//! workspace keeps a plan under its own name instead of executing it:
+//!
+//! - `ie plan --workspace <name>` saves (or replaces) the workspace's plan,
+//! - `ie workspace diff <a> <b>` compares two workspaces, or a workspace with
+//!   `main`, the task tree itself,
+//! - `ie workspace promote <name>` executes the plan into the task tree.
+//!
+//! Workspaces live in the `plan_workspaces` table of SQLite projects and are
+//! not synced.
+
+use crate::error::{IntentError, Result};
+use crate::plan::{flatten_task_tree, PlanRequest, PriorityValue};
+use crate::plan_validation::validate_request;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Name standing for the task tree itself in comparisons
+pub const MAIN_WORKSPACE: &str = "main";
+
+/// A named alternative plan
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlanWorkspace {
+    pub name: String,
+    pub plan: PlanRequest,
+    /// Number of tasks in the plan, nested ones included
+    pub task_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// When the plan was last executed into the task tree; reset when the
+    /// plan is replaced
+    pub promoted_at: Option<DateTime<Utc>>,
+}
+
+/// A task field that differs between two sides of a comparison
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+/// A task present on both sides with different fields
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TaskChange {
+    pub name: String,
+    pub fields: Vec<FieldChange>,
+}
+
+/// Differences between two workspaces, by task name
+///
+/// When one side is `main`, a plan field left unset does not count as a
+/// difference (executing the plan keeps the task's value), and tasks only in
+/// `main` are not listed: promoting a plan does not remove them.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WorkspaceDiff {
+    pub left: String,
+    pub right: String,
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+    pub changed: Vec<TaskChange>,
+}
+
+impl WorkspaceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_left.is_empty() && self.only_right.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compared fields of one task, by field name
+type TaskFields = BTreeMap<&'static str, Option<String>>;
+
+pub struct PlanWorkspaceManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> PlanWorkspaceManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Save `plan` as workspace `name`, replacing its previous plan
+    ///
+    /// The plan gets the checks of `ie plan --validate`.
+    pub async fn save(&self, name: &str, plan: &PlanRequest) -> Result<PlanWorkspace> {
+        validate_name(name)?;
+        validate_request(plan)?;
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO plan_workspaces (name, plan, created_at, updated_at, promoted_at)
+            VALUES (?, ?, ?, ?, NULL)
+            ON CONFLICT(name) DO UPDATE SET
+                plan = excluded.plan,
+                updated_at = excluded.updated_at,
+                promoted_at = NULL
+            "#,
+        )
+        .bind(name)
+        .bind(serde_json::to_string(plan)?)
+        .bind(now)
+        .bind(now)
+        .execute(self.pool)
+        .await?;
+        self.get(name).await
+    }
+
+    pub async fn get(&self, name: &str) -> Result<PlanWorkspace> {
+        let row: Option<WorkspaceRow> = sqlx::query_as(
+            "SELECT name, plan, created_at, updated_at, promoted_at FROM plan_workspaces WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(self.pool)
+        .await?;
+        row.ok_or_else(|| IntentError::InvalidInput(format!("Workspace '{}' not found", name)))?
+            .into_workspace()
+    }
+
+    /// All workspaces, most recently updated first
+    pub async fn list(&self) -> Result<Vec<PlanWorkspace>> {
+        let rows: Vec<WorkspaceRow> = sqlx::query_as(
+            "SELECT name, plan, created_at, updated_at, promoted_at FROM plan_workspaces ORDER BY updated_at DESC, name",
+        )
+        .fetch_all(self.pool)
+        .await?;
+        rows.into_iter().map(WorkspaceRow::into_workspace).collect()
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        let deleted = sqlx::query("DELETE FROM plan_workspaces WHERE name = ?")
+            .bind(name)
+            .execute(self.pool)
+            .await?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(IntentError::InvalidInput(format!(
+                "Workspace '{}' not found",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Record that the workspace's plan was executed into the task tree
+    pub async fn mark_promoted(&self, name: &str) -> Result<PlanWorkspace> {
+        sqlx::query("UPDATE plan_workspaces SET promoted_at = ? WHERE name = ?")
+            .bind(Utc::now())
+            .bind(name)
+            .execute(self.pool)
+            .await?;
+        self.get(name).await
+    }
+
+    /// Compare two workspaces; either may be `main`
+    pub async fn diff(&self, left: &str, right: &str) -> Result<WorkspaceDiff> {
+        let left_tasks = self.tasks_of(left).await?;
+        let right_tasks = self.tasks_of(right).await?;
+        let left_is_main = left == MAIN_WORKSPACE;
+        let right_is_main = right == MAIN_WORKSPACE;
+
+        let mut diff = WorkspaceDiff {
+            left: left.to_string(),
+            right: right.to_string(),
+            only_left: Vec::new(),
+            only_right: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (name, left_fields) in &left_tasks {
+            let Some(right_fields) = right_tasks.get(name) else {
+                if !left_is_main {
+                    diff.only_left.push(name.clone());
+                }
+                continue;
+            };
+            let mut fields = Vec::new();
+            for (field, left_value) in left_fields {
+                let right_value = right_fields.get(field).cloned().flatten();
+                // Against main, an unset plan field keeps the task's value
+                let unset_in_plan = (right_is_main && left_value.is_none())
+                    || (left_is_main && right_value.is_none());
+                if *left_value != right_value && !unset_in_plan {
+                    fields.push(FieldChange {
+                        field: field.to_string(),
+                        left: left_value.clone(),
+                        right: right_value,
+                    });
+                }
+            }
+            if !fields.is_empty() {
+                diff.changed.push(TaskChange {
+                    name: name.clone(),
+                    fields,
+                });
+            }
+        }
+        if !right_is_main {
+            diff.only_right = right_tasks
+                .keys()
+                .filter(|name| !left_tasks.contains_key(*name))
+                .cloned()
+                .collect();
+        }
+        Ok(diff)
+    }
+
+    /// The named tasks of a workspace, or of the task tree for `main`
+    async fn tasks_of(&self, workspace: &str) -> Result<BTreeMap<String, TaskFields>> {
+        if workspace == MAIN_WORKSPACE {
+            return self.main_tasks().await;
+        }
+        let plan = self.get(workspace).await?.plan;
+        let mut tasks = BTreeMap::new();
+        for task in flatten_task_tree(&plan.tasks) {
+            let Some(name) = task.name.clone() else {
+                continue;
+            };
+            if task.delete {
+                continue;
+            }
+            let depends_on = (!task.depends_on.is_empty()).then(|| {
+                let mut names = task.depends_on.clone();
+                names.sort();
+                names.join(", ")
+            });
+            tasks.insert(
+                name,
+                task_fields(
+                    task.parent_name,
+                    task.spec,
+                    task.priority.map(|p| p.as_str().to_string()),
+                    task.status.map(|s| s.as_str().to_string()),
+                    task.estimate_minutes.map(|m| m.to_string()),
+                    depends_on,
+                ),
+            );
+        }
+        Ok(tasks)
+    }
+
+    async fn main_tasks(&self) -> Result<BTreeMap<String, TaskFields>> {
+        let rows: Vec<MainTaskRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT t.name, p.name AS parent_name, t.spec, t.priority, t.status, t.estimate_minutes,
+                   (SELECT GROUP_CONCAT(name, ', ') FROM (
+                       SELECT b.name FROM dependencies d
+                       JOIN tasks b ON b.id = d.blocking_task_id
+                       WHERE d.blocked_task_id = t.id
+                       ORDER BY b.name
+                   )) AS depends_on
+            FROM (SELECT * FROM tasks WHERE {}) t
+            LEFT JOIN tasks p ON p.id = t.parent_id
+            ORDER BY t.id
+            "#,
+            crate::project::task_scope().sql_filter()
+        ))
+        .fetch_all(self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.name,
+                    task_fields(
+                        row.parent_name,
+                        row.spec,
+                        row.priority
+                            .and_then(|p| PriorityValue::from_int(p as i32))
+                            .map(|p| p.as_str().to_string()),
+                        Some(row.status),
+                        row.estimate_minutes.map(|m| m.to_string()),
+                        row.depends_on,
+                    ),
+                )
+            })
+            .collect())
+    }
+}
+
+fn task_fields(
+    parent: Option<String>,
+    spec: Option<String>,
+    priority: Option<String>,
+    status: Option<String>,
+    estimate_minutes: Option<String>,
+    depends_on: Option<String>,
+) -> TaskFields {
+    BTreeMap::from([
+        ("parent", parent),
+        ("spec", spec),
+        ("priority", priority),
+        ("status", status),
+        ("estimate_minutes", estimate_minutes),
+        ("depends_on", depends_on),
+    ])
+}
+
+/// Workspace names are short identifiers; `main` is the task tree
+fn validate_name(name: &str) -> Result<()> {
+    if name == MAIN_WORKSPACE {
+        return Err(IntentError::InvalidInput(
+            "'main' is the task tree itself; run `ie plan` without --workspace to change it"
+                .to_string(),
+        ));
+    }
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(IntentError::InvalidInput(format!(
+            "Invalid workspace name '{}': use up to 64 letters, digits, '-', '_' or '.'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct WorkspaceRow {
+    name: String,
+    plan: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    promoted_at: Option<DateTime<Utc>>,
+}
+
+impl WorkspaceRow {
+    fn into_workspace(self) -> Result<PlanWorkspace> {
+        let plan: PlanRequest = serde_json::from_str(&self.plan)?;
+        Ok(PlanWorkspace {
+            name: self.name,
+            task_count: flatten_task_tree(&plan.tasks).len(),
+            plan,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            promoted_at: self.promoted_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MainTaskRow {
+    name: String,
+    parent_name: Option<String>,
+    spec: Option<String>,
+    priority: Option<i64>,
+    status: String,
+    estimate_minutes: Option<i64>,
+    depends_on: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::PlanExecutor;
+    use crate::test_utils::test_helpers::TestContext;
+
+    fn plan(json: serde_json::Value) -> PlanRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_workspaces_coexist_and_promote() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let workspaces = PlanWorkspaceManager::new(pool);
+        PlanExecutor::new(pool)
+            .execute(&plan(serde_json::json!({
+                "tasks": [{"name": "Auth", "spec": "Add login", "priority": "high"}]
+            })))
+            .await
+            .unwrap();
+
+        let a = workspaces
+            .save(
+                "approach-a",
+                &plan(serde_json::json!({"tasks": [{"name": "Auth", "children": [
+                    {"name": "Sessions", "spec": "Cookie sessions"}
+                ]}]})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(a.task_count, 2);
+        workspaces
+            .save(
+                "approach-b",
+                &plan(
+                    serde_json::json!({"tasks": [{"name": "Auth", "priority": "low", "children": [
+                        {"name": "Tokens", "spec": "JWT"}
+                    ]}]}),
+                ),
+            )
+            .await
+            .unwrap();
+        assert!(workspaces
+            .save("main", &PlanRequest::default())
+            .await
+            .is_err());
+        assert!(workspaces
+            .save("bad name", &PlanRequest::default())
+            .await
+            .is_err());
+
+        let diff = workspaces.diff("approach-a", "approach-b").await.unwrap();
+        assert_eq!(diff.only_left, vec!["Sessions"]);
+        assert_eq!(diff.only_right, vec!["Tokens"]);
+        assert_eq!(
+            diff.changed,
+            vec![TaskChange {
+                name: "Auth".to_string(),
+                fields: vec![FieldChange {
+                    field: "priority".to_string(),
+                    left: None,
+                    right: Some("low".to_string()),
+                }],
+            }]
+        );
+
+        // Against main: what promoting B would create and change
+        let diff = workspaces.diff("approach-b", MAIN_WORKSPACE).await.unwrap();
+        assert_eq!(diff.only_left, vec!["Tokens"]);
+        assert!(diff.only_right.is_empty());
+        assert_eq!(diff.changed[0].fields[0].left.as_deref(), Some("low"));
+        assert_eq!(diff.changed[0].fields[0].right.as_deref(), Some("high"));
+
+        // Nothing reached the task tree so far
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let b = workspaces.get("approach-b").await.unwrap();
+        let result = PlanExecutor::new(pool).execute(&b.plan).await.unwrap();
+        assert!(result.success);
+        let b = workspaces.mark_promoted("approach-b").await.unwrap();
+        assert!(b.promoted_at.is_some());
+        assert!(workspaces
+            .diff("approach-b", MAIN_WORKSPACE)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Replacing a plan resets its promotion
+        let b = workspaces.save("approach-b", &b.plan).await.unwrap();
+        assert_eq!(b.promoted_at, None);
+
+        assert_eq!(workspaces.list().await.unwrap().len(), 2);
+        workspaces.delete("approach-a").await.unwrap();
+        assert!(workspaces.get("approach-a").await.is_err());
+        assert!(workspaces.delete("approach-a").await.is_err());
+    }
+}
@@ -0,0 +1,321 @@
+//! Policy rules for task transitions
+//!
+//! Teams differ in what "ready to start" or "done" means, so beyond the
+//! built-in checks (blockers, children, human task protection) a project can
+//! declare extra rules in the `[policy]` table of its config.toml:
+//!
+//! ```toml
+//! [policy]
+//! min_spec_chars = 50                   # doing requires a spec this long
+//! done_requires_event = ["milestone"]   # done requires one event of these types
+//! max_doing_per_parent = 3              # at most this many doing subtasks per parent
+//! ```
+//!
+//! Rules see the task as it will be after the transition, inside the same
+//! transaction, and a violation rolls the transition back with
+//! `IntentError::PolicyViolation`. `start_task`, `update_task`, `done_task`,
+//! `done_task_by_id` and `PlanExecutor` enforce them. Library users can add
+//! their own rules with [`PolicyEngine::with_rule`].
+
+use crate::config::{PolicyConfig, ProjectConfig};
+use crate::error::{IntentError, Result};
+use futures_util::future::BoxFuture;
+use sqlx::{SqliteConnection, SqlitePool};
+
+/// A task right after a transition to `status`
+#[derive(Debug, Clone)]
+pub struct TransitionContext {
+    pub task_id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+    /// Status the task is moving to
+    pub status: String,
+    pub spec: Option<String>,
+}
+
+/// A check run on task transitions
+pub trait PolicyRule: Send + Sync {
+    /// Name reported in violations, e.g. `min_spec_chars`
+    fn name(&self) -> &'static str;
+
+    /// Whether the rule cares about transitions to `status`
+    fn applies_to(&self, status: &str) -> bool;
+
+    /// Why the transition is not allowed, or None when it is
+    fn check<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        task: &'a TransitionContext,
+    ) -> BoxFuture<'a, Result<Option<String>>>;
+}
+
+/// Doing requires a spec of at least `min_chars` characters
+pub struct MinSpecChars {
+    pub min_chars: usize,
+}
+
+impl PolicyRule for MinSpecChars {
+    fn name(&self) -> &'static str {
+        "min_spec_chars"
+    }
+
+    fn applies_to(&self, status: &str) -> bool {
+        status == "doing"
+    }
+
+    fn check<'a>(
+        &'a self,
+        _conn: &'a mut SqliteConnection,
+        task: &'a TransitionContext,
+    ) -> BoxFuture<'a, Result<Option<String>>> {
+        Box::pin(async move {
+            let chars = task
+                .spec
+                .as_deref()
+                .map(|s| s.trim().chars().count())
+                .unwrap_or(0);
+            Ok((chars < self.min_chars).then(|| {
+                format!(
+                    "a spec of at least {} characters is required to start a task (has {})",
+                    self.min_chars, chars
+                )
+            }))
+        })
+    }
+}
+
+/// Done requires at least one event of one of `log_types`
+pub struct DoneRequiresEvent {
+    pub log_types: Vec<String>,
+}
+
+impl PolicyRule for DoneRequiresEvent {
+    fn name(&self) -> &'static str {
+        "done_requires_event"
+    }
+
+    fn applies_to(&self, status: &str) -> bool {
+        status == "done"
+    }
+
+    fn check<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        task: &'a TransitionContext,
+    ) -> BoxFuture<'a, Result<Option<String>>> {
+        Box::pin(async move {
+            let placeholders = vec!["?"; self.log_types.len()].join(", ");
+            let sql = format!(
+                "SELECT EXISTS(SELECT 1 FROM events WHERE task_id = ? AND log_type IN ({}))",
+                placeholders
+            );
+            let mut query = sqlx::query_scalar::<_, bool>(&sql).bind(task.task_id);
+            for log_type in &self.log_types {
+                query = query.bind(log_type);
+            }
+            let found = query.fetch_one(&mut *conn).await?;
+            Ok((!found).then(|| {
+                format!(
+                    "completing a task requires at least one {} event (ie log {} \"...\" --task {})",
+                    self.log_types.join(" or "),
+                    self.log_types[0],
+                    task.task_id
+                )
+            }))
+        })
+    }
+}
+
+/// A parent may have at most `max` subtasks in doing
+pub struct MaxDoingPerParent {
+    pub max: usize,
+}
+
+impl PolicyRule for MaxDoingPerParent {
+    fn name(&self) -> &'static str {
+        "max_doing_per_parent"
+    }
+
+    fn applies_to(&self, status: &str) -> bool {
+        status == "doing"
+    }
+
+    fn check<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+        task: &'a TransitionContext,
+    ) -> BoxFuture<'a, Result<Option<String>>> {
+        Box::pin(async move {
+            let Some(parent_id) = task.parent_id else {
+                return Ok(None);
+            };
+            let doing: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM tasks WHERE parent_id = ? AND status = 'doing'",
+            )
+            .bind(parent_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            Ok((doing as usize > self.max).then(|| {
+                format!(
+                    "task #{} would have {} subtasks in doing (max {})",
+                    parent_id, doing, self.max
+                )
+            }))
+        })
+    }
+}
+
+/// The rules of a project
+#[derive(Default)]
+pub struct PolicyEngine {
+    rules: Vec<Box<dyn PolicyRule>>,
+}
+
+impl PolicyEngine {
+    /// Rules declared in a `[policy]` table
+    pub fn from_config(config: &PolicyConfig) -> Self {
+        let mut engine = Self::default();
+        if let Some(min_chars) = config.min_spec_chars {
+            engine = engine.with_rule(MinSpecChars { min_chars });
+        }
+        if !config.done_requires_event.is_empty() {
+            engine = engine.with_rule(DoneRequiresEvent {
+                log_types: config.done_requires_event.clone(),
+            });
+        }
+        if let Some(max) = config.max_doing_per_parent {
+            engine = engine.with_rule(MaxDoingPerParent { max });
+        }
+        engine
+    }
+
+    /// Rules of the project whose database `pool` is
+    pub fn for_pool(pool: &SqlitePool) -> Result<Self> {
+        Ok(Self::from_config(&ProjectConfig::for_pool(pool)?.policy))
+    }
+
+    /// Add a rule
+    pub fn with_rule(mut self, rule: impl PolicyRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check the transition of `task_id` to its current status
+    ///
+    /// Call after writing the transition, inside its transaction; the first
+    /// violated rule is returned as `IntentError::PolicyViolation`.
+    pub async fn check_in_tx(&self, conn: &mut SqliteConnection, task_id: i64) -> Result<()> {
+        if self.rules.is_empty() {
+            return Ok(());
+        }
+        let (name, parent_id, status, spec): (String, Option<i64>, String, Option<String>) =
+            sqlx::query_as("SELECT name, parent_id, status, spec FROM tasks WHERE id = ?")
+                .bind(task_id)
+                .fetch_optional(&mut *conn)
+                .await?
+                .ok_or(IntentError::TaskNotFound(task_id))?;
+        let task = TransitionContext {
+            task_id,
+            name,
+            parent_id,
+            status,
+            spec,
+        };
+
+        for rule in self.rules.iter().filter(|r| r.applies_to(&task.status)) {
+            if let Some(message) = rule.check(conn, &task).await? {
+                return Err(IntentError::PolicyViolation {
+                    rule: rule.name().to_string(),
+                    task_id,
+                    status: task.status.clone(),
+                    message,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::plan::{PlanExecutor, PlanRequest};
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_configured_rules_are_enforced() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine/config.toml"),
+            "[policy]\nmin_spec_chars = 10\ndone_requires_event = [\"milestone\"]\nmax_doing_per_parent = 1\n",
+        )
+        .unwrap();
+        let tasks = TaskManager::new(pool);
+
+        let parent = tasks
+            .add_task("Parent", Some("The whole feature"), None, None, None, None)
+            .await
+            .unwrap();
+        let short = tasks
+            .add_task("Short", Some("tiny"), Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+        let err = tasks.start_task(short.id, false).await.unwrap_err();
+        assert!(matches!(
+            &err,
+            IntentError::PolicyViolation { rule, .. } if rule == "min_spec_chars"
+        ));
+        assert_eq!(tasks.get_task(short.id).await.unwrap().status, "todo");
+
+        // A spec given in the same update counts
+        tasks
+            .update_task(
+                short.id,
+                TaskUpdate {
+                    spec: Some("Long enough now"),
+                    status: Some("doing"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let sibling = tasks
+            .add_task(
+                "Sibling",
+                Some("Also long enough"),
+                Some(parent.id),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let err = tasks.start_task(sibling.id, false).await.unwrap_err();
+        assert_eq!(err.to_error_code(), "POLICY_VIOLATION");
+        assert!(err.to_string().contains("max_doing_per_parent"));
+
+        assert!(tasks.done_task_by_id(short.id).await.is_err());
+        EventManager::new(pool)
+            .add_event(short.id, "milestone", "Shipped")
+            .await
+            .unwrap();
+        tasks.done_task_by_id(short.id).await.unwrap();
+
+        // Plans are checked once parents are in place
+        let request: PlanRequest = serde_json::from_str(
+            r#"{"tasks": [{"name": "Planned", "spec": "short", "status": "doing"}]}"#,
+        )
+        .unwrap();
+        let result = PlanExecutor::new(pool).execute(&request).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("min_spec_chars"));
+    }
+}
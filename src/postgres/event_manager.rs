@@ -0,0 +1,196 @@
+use crate::db::models::{Event, EventVisibility, EventsSummary, NewEvent};
+use crate::error::{IntentError, Result};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+
+/// Columns of an `Event`, in `SELECT` order
+pub(crate) const EVENT_COLUMNS: &str =
+    "e.id, e.task_id, e.timestamp, e.log_type, e.discussion_data";
+
+/// Event management backed by the Postgres `events` table.
+///
+/// Only `normal` events are stored, as with Neo4j; IDs come from the shared
+/// per-project counter.
+pub struct PostgresEventManager {
+    pool: PgPool,
+    project_id: String,
+}
+
+impl PostgresEventManager {
+    pub fn new(pool: PgPool, project_id: String) -> Self {
+        Self { pool, project_id }
+    }
+
+    /// Add a new event linked to a task.
+    ///
+    /// Returns TaskNotFound if the task doesn't exist.
+    pub async fn add_event(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+    ) -> Result<Event> {
+        self.check_task_exists(task_id).await?;
+
+        let id = super::next_id(&self.pool, &self.project_id, "event").await?;
+        let event = sqlx::query_as::<_, Event>(&format!(
+            "INSERT INTO events AS e (project_id, id, task_id, log_type, discussion_data) \
+             VALUES ($1, $2, $3, $4, $5) \
+             RETURNING {EVENT_COLUMNS}"
+        ))
+        .bind(&self.project_id)
+        .bind(id)
+        .bind(task_id)
+        .bind(log_type)
+        .bind(discussion_data)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(event)
+    }
+
+    /// Get a single event by ID.
+    pub async fn get_event(&self, id: i64) -> Result<Option<Event>> {
+        let event = sqlx::query_as::<_, Event>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM events e WHERE e.project_id = $1 AND e.id = $2"
+        ))
+        .bind(&self.project_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(event)
+    }
+
+    /// List events with optional filters.
+    ///
+    /// Supports filtering by task_id, log_type, and since (duration string).
+    /// Results are ordered by timestamp DESC.
+    pub async fn list_events(
+        &self,
+        task_id: Option<i64>,
+        limit: Option<i64>,
+        log_type: Option<String>,
+        since: Option<String>,
+    ) -> Result<Vec<Event>> {
+        if let Some(tid) = task_id {
+            self.check_task_exists(tid).await?;
+        }
+
+        let limit = limit.unwrap_or(50);
+
+        let since_timestamp = match &since {
+            Some(duration_str) => Some(crate::time_utils::parse_duration(duration_str)?),
+            None => None,
+        };
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+            "SELECT {EVENT_COLUMNS} FROM events e WHERE e.project_id = "
+        ));
+        query.push_bind(&self.project_id);
+        if let Some(tid) = task_id {
+            query.push(" AND e.task_id = ").push_bind(tid);
+        }
+        if let Some(log_type) = log_type {
+            query.push(" AND e.log_type = ").push_bind(log_type);
+        }
+        if let Some(since) = since_timestamp {
+            query.push(" AND e.timestamp >= ").push_bind(since);
+        }
+        query
+            .push(" ORDER BY e.timestamp DESC, e.id DESC LIMIT ")
+            .push_bind(limit);
+
+        let events = query
+            .build_query_as::<Event>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(events)
+    }
+
+    /// Get events summary for a task: total count + most recent 10 events.
+    ///
+    /// One round-trip: the total comes from a window count over the same rows.
+    pub async fn get_events_summary(&self, task_id: i64) -> Result<EventsSummary> {
+        let rows = sqlx::query(&format!(
+            "SELECT {EVENT_COLUMNS}, count(*) OVER () AS total_count \
+             FROM events e WHERE e.project_id = $1 AND e.task_id = $2 \
+             ORDER BY e.timestamp DESC, e.id DESC LIMIT 10"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total_count = match rows.first() {
+            Some(row) => row.try_get("total_count")?,
+            None => 0,
+        };
+        let recent_events = rows
+            .iter()
+            .map(sqlx::FromRow::from_row)
+            .collect::<std::result::Result<Vec<Event>, _>>()?;
+
+        Ok(EventsSummary {
+            total_count,
+            recent_events,
+        })
+    }
+
+    async fn check_task_exists(&self, task_id: i64) -> Result<()> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE project_id = $1 AND id = $2)",
+        )
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(IntentError::TaskNotFound(task_id))
+        }
+    }
+}
+
+impl crate::backend::EventBackend for PostgresEventManager {
+    fn add_event(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+    ) -> impl std::future::Future<Output = Result<Event>> + Send {
+        self.add_event(task_id, log_type, discussion_data)
+    }
+
+    fn list_events(
+        &self,
+        task_id: Option<i64>,
+        limit: Option<i64>,
+        log_type: Option<String>,
+        since: Option<String>,
+    ) -> impl std::future::Future<Output = Result<Vec<Event>>> + Send {
+        self.list_events(task_id, limit, log_type, since)
+    }
+
+    async fn add_event_with_visibility(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        discussion_data: &str,
+        visibility: EventVisibility,
+    ) -> Result<Event> {
+        if !visibility.is_normal() {
+            return Err(IntentError::InvalidInput(format!(
+                "Event visibility '{}' is not supported by the Postgres backend",
+                visibility.as_str()
+            )));
+        }
+        self.add_event(task_id, log_type, discussion_data).await
+    }
+
+    async fn add_events(&self, _events: &[NewEvent]) -> Result<Vec<Event>> {
+        Err(IntentError::InvalidInput(
+            "Batch logging is not supported by the Postgres backend".to_string(),
+        ))
+    }
+}
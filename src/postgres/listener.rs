@@ -0,0 +1,140 @@
+//! Dashboard notifications from Postgres `NOTIFY`.
+//!
+//! The row triggers from `schema.rs` publish every task/event change on
+//! [`CHANGES_CHANNEL`], whichever client made it. The Dashboard listens there
+//! and forwards the changes of its project as `db_operation` messages, so
+//! teammates' edits show up live.
+
+use crate::dashboard::websocket::{DatabaseOperationPayload, WebSocketState};
+use crate::error::Result;
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+
+use super::schema::CHANGES_CHANNEL;
+use super::{PostgresConfig, PostgresEventManager, PostgresTaskManager};
+
+/// A change published by the row triggers
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChangeNotification {
+    pub project_id: String,
+    /// "task" or "event"
+    pub entity: String,
+    /// "create", "update" or "delete"
+    pub operation: String,
+    pub id: i64,
+    /// Task of an event
+    #[serde(default)]
+    pub task_id: Option<i64>,
+}
+
+impl ChangeNotification {
+    /// Parse the JSON payload of a notification
+    pub fn parse(payload: &str) -> Result<Self> {
+        Ok(serde_json::from_str(payload)?)
+    }
+
+    /// Dashboard payload for this change; `data` is the changed row, None
+    /// for deletes
+    pub fn to_payload(
+        &self,
+        data: Option<serde_json::Value>,
+        project_path: &str,
+    ) -> DatabaseOperationPayload {
+        let data = match (self.operation.as_str(), self.task_id) {
+            ("delete", Some(task_id)) => Some(serde_json::json!({ "task_id": task_id })),
+            ("delete", None) => None,
+            _ => data,
+        };
+        DatabaseOperationPayload::new(
+            self.operation.clone(),
+            self.entity.clone(),
+            vec![self.id],
+            data,
+            project_path,
+        )
+    }
+}
+
+/// Forward the changes of the configured project to Dashboard clients
+///
+/// Runs until the connection fails for good. `PgListener` reconnects by
+/// itself; notifications sent while it was disconnected are lost, which
+/// clients recover from with their usual resync.
+pub async fn forward_to_dashboard(ws_state: WebSocketState, project_path: String) -> Result<()> {
+    let config = PostgresConfig::from_env()?;
+    let pool = super::connect_pool(&config.url).await?;
+    let tasks = PostgresTaskManager::new(pool.clone(), config.project_id.clone());
+    let events = PostgresEventManager::new(pool.clone(), config.project_id.clone());
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen(CHANGES_CHANNEL).await?;
+    tracing::info!(project_id = %config.project_id, "Listening for Postgres changes");
+
+    loop {
+        let notification = listener.recv().await?;
+        let change = match ChangeNotification::parse(notification.payload()) {
+            Ok(change) => change,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring malformed Postgres notification");
+                continue;
+            },
+        };
+        if change.project_id != config.project_id {
+            continue;
+        }
+
+        // The row may be gone again by now; then there is nothing to send
+        let data = match (change.operation.as_str(), change.entity.as_str()) {
+            ("delete", _) => None,
+            (_, "task") => match tasks.get_task(change.id).await {
+                Ok(task) => Some(serde_json::to_value(task)?),
+                Err(_) => continue,
+            },
+            (_, "event") => match events.get_event(change.id).await? {
+                Some(event) => Some(serde_json::to_value(event)?),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        ws_state
+            .broadcast_db_operation(&change.to_payload(data, &project_path))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trigger_payload() {
+        let change = ChangeNotification::parse(
+            r#"{"project_id": "web", "entity": "event", "operation": "create", "id": 7, "task_id": 3}"#,
+        )
+        .unwrap();
+        assert_eq!(change.entity, "event");
+        assert_eq!(change.task_id, Some(3));
+
+        let task = ChangeNotification::parse(
+            r#"{"project_id": "web", "entity": "task", "operation": "update", "id": 3, "task_id": null}"#,
+        )
+        .unwrap();
+        assert_eq!(task.task_id, None);
+    }
+
+    #[test]
+    fn test_to_payload_deletes_carry_event_task() {
+        let change = ChangeNotification {
+            project_id: "web".into(),
+            entity: "event".into(),
+            operation: "delete".into(),
+            id: 7,
+            task_id: Some(3),
+        };
+        let payload = change.to_payload(None, "/work/web");
+        assert_eq!(payload.affected_ids, vec![7]);
+        assert_eq!(payload.data, Some(serde_json::json!({ "task_id": 3 })));
+        assert_eq!(payload.project_path, "/work/web");
+    }
+}
@@ -0,0 +1,142 @@
+//! Postgres backend for intent-engine.
+//!
+//! Stores tasks, events and sessions of many projects in one shared Postgres
+//! database, so a team of developers and agents can work on the same task
+//! tree. Reuses the `Task`, `Event` and CLI types of the main crate.
+//!
+//! Full-text search uses generated `tsvector` columns, and row triggers
+//! publish every task/event change with `NOTIFY`, which [`listener`] turns
+//! into Dashboard notifications.
+//!
+//! Activated by the `postgres` feature flag; used by `ie` in projects whose
+//! config sets `[storage] backend = "postgres"`.
+
+pub mod config;
+pub mod event_manager;
+pub mod listener;
+pub mod plan_executor;
+pub mod schema;
+pub mod search_manager;
+pub mod task_manager;
+pub mod workspace_manager;
+
+use crate::error::Result;
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
+use std::str::FromStr;
+
+pub use config::PostgresConfig;
+pub use event_manager::PostgresEventManager;
+pub use plan_executor::PostgresPlanExecutor;
+pub use search_manager::PostgresSearchManager;
+pub use task_manager::PostgresTaskManager;
+pub use workspace_manager::PostgresWorkspaceManager;
+
+/// Atomically allocate the next sequential ID for an entity type.
+///
+/// IDs are per project, like the SQLite ones; the counter row is created on
+/// first use.
+pub(crate) async fn next_id(pool: &PgPool, project_id: &str, entity: &str) -> Result<i64> {
+    let id = sqlx::query_scalar(
+        "INSERT INTO counters (project_id, entity, next_id) VALUES ($1, $2, 2) \
+         ON CONFLICT (project_id, entity) DO UPDATE SET next_id = counters.next_id + 1 \
+         RETURNING next_id - 1",
+    )
+    .bind(project_id)
+    .bind(entity)
+    .fetch_one(pool)
+    .await?;
+    Ok(id)
+}
+
+/// Central context holding the Postgres pool and project identity.
+pub struct PostgresContext {
+    pub pool: PgPool,
+    pub project_id: String,
+}
+
+impl PostgresContext {
+    /// Connect using environment variable configuration, then apply
+    /// pending migrations.
+    pub async fn connect() -> Result<Self> {
+        let config = PostgresConfig::from_env()?;
+        let pool = connect_pool(&config.url).await?;
+
+        schema::migrate(&pool).await?;
+
+        Ok(Self {
+            pool,
+            project_id: config.project_id,
+        })
+    }
+
+    /// Create a PostgresTaskManager from this context.
+    pub fn task_manager(&self) -> PostgresTaskManager {
+        PostgresTaskManager::new(self.pool.clone(), self.project_id.clone())
+    }
+
+    /// Create a PostgresWorkspaceManager from this context.
+    pub fn workspace_manager(&self) -> PostgresWorkspaceManager {
+        PostgresWorkspaceManager::new(self.pool.clone(), self.project_id.clone())
+    }
+
+    /// Create a PostgresEventManager from this context.
+    pub fn event_manager(&self) -> PostgresEventManager {
+        PostgresEventManager::new(self.pool.clone(), self.project_id.clone())
+    }
+
+    /// Create a PostgresPlanExecutor from this context.
+    pub fn plan_executor(&self) -> PostgresPlanExecutor {
+        PostgresPlanExecutor::new(self.pool.clone(), self.project_id.clone())
+    }
+
+    /// Create a PostgresSearchManager from this context.
+    pub fn search_manager(&self) -> PostgresSearchManager {
+        PostgresSearchManager::new(self.pool.clone(), self.project_id.clone())
+    }
+}
+
+/// Open a pool whose connections resolve table names in [`schema::SCHEMA`]
+pub async fn connect_pool(url: &str) -> Result<PgPool> {
+    let options = PgConnectOptions::from_str(url)?.options([("search_path", schema::SCHEMA)]);
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect_with(options)
+        .await?;
+    Ok(pool)
+}
+
+impl crate::backend::Backend for PostgresContext {
+    type Tasks<'a> = PostgresTaskManager;
+    type Workspace<'a> = PostgresWorkspaceManager;
+    type Events<'a> = PostgresEventManager;
+    type Plans<'a> = PostgresPlanExecutor;
+    type Search<'a> = PostgresSearchManager;
+
+    fn name(&self) -> &'static str {
+        crate::config::StorageBackend::Postgres.as_str()
+    }
+
+    fn tasks(&self) -> Self::Tasks<'_> {
+        self.task_manager()
+    }
+
+    fn workspace(&self) -> Self::Workspace<'_> {
+        self.workspace_manager()
+    }
+
+    fn events(&self) -> Self::Events<'_> {
+        self.event_manager()
+    }
+
+    fn plans(&self, default_parent: Option<i64>) -> Self::Plans<'_> {
+        let executor = self.plan_executor();
+        match default_parent {
+            Some(parent_id) => executor.with_default_parent(parent_id),
+            None => executor,
+        }
+    }
+
+    fn search(&self) -> Self::Search<'_> {
+        self.search_manager()
+    }
+}
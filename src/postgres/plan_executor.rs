@@ -0,0 +1,649 @@
+//! Plan executor backed by Postgres.
+//!
+//! Provides batch create/update/delete of task hierarchies, mirroring the
+//! behavior of the SQLite `PlanExecutor` in `src/plan.rs`.
+//!
+//! Key differences from SQLite version:
+//! - Uses `PostgresTaskManager` methods, as the Neo4j executor does
+//! - Individual operations are NOT wrapped in a transaction (no atomicity guarantee)
+//! - Dashboard notifications come from the database triggers, not the executor
+
+use crate::db::models::TaskWithEvents;
+use crate::error::{IntentError, Result};
+use crate::plan::{
+    extract_all_names, find_duplicate_names, flatten_task_tree, ExistingTaskInfo, FlatTask,
+    PlanRequest, PlanResult, TaskStatus,
+};
+use crate::plan_validation;
+use crate::tasks::TaskUpdate;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+/// Plan executor for batch task operations on Postgres.
+pub struct PostgresPlanExecutor {
+    pool: PgPool,
+    project_id: String,
+    /// Default parent ID for auto-parenting new root tasks to the focused task.
+    default_parent_id: Option<i64>,
+}
+
+impl PostgresPlanExecutor {
+    pub fn new(pool: PgPool, project_id: String) -> Self {
+        Self {
+            pool,
+            project_id,
+            default_parent_id: None,
+        }
+    }
+
+    /// Set default parent ID for auto-parenting new root tasks.
+    pub fn with_default_parent(mut self, parent_id: i64) -> Self {
+        self.default_parent_id = Some(parent_id);
+        self
+    }
+
+    /// Execute a plan request: batch create/update/delete tasks.
+    pub async fn execute(&self, request: &PlanRequest) -> Result<PlanResult> {
+        let task_mgr = super::PostgresTaskManager::new(self.pool.clone(), self.project_id.clone());
+
+        if !request.on_error.is_abort() {
+            return Ok(PlanResult::error(format!(
+                "on_error '{}' is not supported by the Postgres backend; plans are applied atomically",
+                request.on_error.as_str()
+            )));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.milestone.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Milestones are not supported by the Postgres backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.needs_human.is_some())
+        {
+            return Ok(PlanResult::error(
+                "needs_human is not supported by the Postgres backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.blocked_reason.is_some())
+        {
+            return Ok(PlanResult::error(
+                "blocked_reason is not supported by the Postgres backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.checklist.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Checklists are not supported by the Postgres backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.links.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Links are not supported by the Postgres backend",
+            ));
+        }
+
+        if flatten_task_tree(&request.tasks)
+            .iter()
+            .any(|t| t.outcome.is_some() || t.artifacts.is_some())
+        {
+            return Ok(PlanResult::error(
+                "Completion outcomes are not supported by the Postgres backend",
+            ));
+        }
+
+        if !request.scope.is_project() {
+            return Ok(PlanResult::error(
+                "Plan scopes are not supported by the Postgres backend; names are matched project-wide",
+            ));
+        }
+
+        // ── 1. Validate: duplicate names ──
+        let duplicates = find_duplicate_names(&request.tasks);
+        if !duplicates.is_empty() {
+            return Ok(PlanResult::error(format!(
+                "Duplicate task names in request: {:?}",
+                duplicates
+            )));
+        }
+
+        // ── 2. Extract all names ──
+        let all_names = extract_all_names(&request.tasks);
+
+        // ── 3. Flatten task tree ──
+        let flat_tasks = flatten_task_tree(&request.tasks);
+
+        // ── 4. Validate dependencies exist in plan ──
+        if let Err(e) = plan_validation::validate_dependencies(&flat_tasks) {
+            return Ok(PlanResult::error(e.to_string()));
+        }
+
+        // ── 5. Detect circular dependencies ──
+        if let Err(e) = plan_validation::detect_circular_dependencies(&flat_tasks) {
+            return Ok(PlanResult::error(e.to_string()));
+        }
+
+        // ── 6. Validate batch-level single doing constraint ──
+        if let Err(e) = plan_validation::validate_batch_single_doing(&flat_tasks) {
+            return Ok(PlanResult::error(e.to_string()));
+        }
+
+        // ── 7. Find existing tasks by name (outside transaction) ──
+        let existing = self.find_tasks_by_names(&all_names).await?;
+
+        // ── 8. Separate delete vs normal operations ──
+        let (delete_tasks, normal_tasks): (Vec<&FlatTask>, Vec<&FlatTask>) =
+            flat_tasks.iter().partition(|t| t.delete);
+
+        // Validate delete operations: each must have an id
+        for task in &delete_tasks {
+            if task.id.is_none() {
+                return Ok(PlanResult::error(
+                    "Delete operation requires 'id' field. Use {\"id\": <task_id>, \"delete\": true}",
+                ));
+            }
+        }
+
+        // ── 9. Focus protection check (before any deletions) ──
+        for task in &delete_tasks {
+            if let Some(id) = task.id {
+                if let Some((focused_id, session_id)) = task_mgr.find_focused_in_subtree(id).await?
+                {
+                    if focused_id == id {
+                        return Ok(PlanResult::error(format!(
+                            "Task #{} is the current focus of session '{}'. That session must switch focus first.",
+                            id, session_id
+                        )));
+                    } else {
+                        return Ok(PlanResult::error(format!(
+                            "Task #{} is the current focus of session '{}' and would be deleted by cascade (descendant of #{}). That session must switch focus first.",
+                            focused_id, session_id, id
+                        )));
+                    }
+                }
+            }
+        }
+
+        // ── 10. Execute operations ──
+        let mut task_id_map: HashMap<String, i64> = HashMap::new();
+        let mut created_count = 0;
+        let mut updated_count = 0;
+        let mut deleted_count = 0;
+        let mut cascade_deleted_count: i64 = 0;
+        let mut warnings: Vec<String> = Vec::new();
+        let mut newly_created_names: HashSet<String> = HashSet::new();
+
+        // ── 10a. Process deletes first ──
+        for task in &delete_tasks {
+            if let Some(id) = task.id {
+                // Check if task exists
+                match task_mgr.get_task(id).await {
+                    Ok(_) => {
+                        // Count descendants before deletion
+                        let descendants = task_mgr.get_descendants(id).await?;
+                        let desc_count = descendants.len() as i64;
+
+                        task_mgr.delete_task_cascade(id).await?;
+                        deleted_count += 1;
+
+                        if desc_count > 0 {
+                            cascade_deleted_count += desc_count;
+                            warnings.push(format!(
+                                "Task #{} had {} descendant(s) that were also deleted (cascade)",
+                                id, desc_count
+                            ));
+                        }
+                    },
+                    Err(_) => {
+                        warnings.push(format!(
+                            "Task #{} not found (may have been already deleted)",
+                            id
+                        ));
+                    },
+                }
+            }
+        }
+
+        // ── 10b. Process create/update operations ──
+        for task in &normal_tasks {
+            let task_name = match &task.name {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let is_becoming_doing = task.status.as_ref() == Some(&TaskStatus::Doing);
+            let has_spec = task
+                .spec
+                .as_ref()
+                .map(|s| !s.trim().is_empty())
+                .unwrap_or(false);
+
+            if let Some(existing_info) = existing.get(task_name) {
+                // ── UPDATE existing task ──
+
+                // Validate spec required for doing transition
+                if is_becoming_doing && !has_spec {
+                    let existing_is_doing = existing_info.status == "doing";
+                    let existing_has_spec = existing_info
+                        .spec
+                        .as_ref()
+                        .map(|s| !s.trim().is_empty())
+                        .unwrap_or(false);
+
+                    if !existing_is_doing && !existing_has_spec {
+                        return Ok(PlanResult::error(format!(
+                            "Task '{}': spec (description) is required when starting a task (status: doing).\n\n\
+                            Before starting a task, please describe:\n  \
+                            • What is the goal of this task\n  \
+                            • How do you plan to approach it",
+                            task_name
+                        )));
+                    }
+                }
+
+                let is_becoming_done = task.status.as_ref() == Some(&TaskStatus::Done);
+
+                // Build update
+                let status_for_update = if is_becoming_done {
+                    None // done handled separately
+                } else {
+                    task.status.as_ref().map(|s| s.as_db_str())
+                };
+
+                // Note: task was found by name match, so name is already correct.
+                // No need to fetch task again just to compare names.
+                let update = TaskUpdate {
+                    spec: task.spec.as_deref(),
+                    priority: task.priority.as_ref().map(|p| p.to_int()),
+                    status: status_for_update,
+                    active_form: task.active_form.as_deref(),
+                    estimate_minutes: task.estimate_minutes.map(i64::from),
+                    ..Default::default()
+                };
+
+                task_mgr.update_task(existing_info.id, update).await?;
+
+                // Handle done transition
+                if is_becoming_done {
+                    if let Err(e) = task_mgr.done_task_by_id(existing_info.id).await {
+                        return Ok(PlanResult::error(format!(
+                            "Cannot complete task '{}': {}\n\n\
+                            Please complete all subtasks before marking the parent as done.",
+                            task_name, e
+                        )));
+                    }
+                }
+
+                task_id_map.insert(task_name.clone(), existing_info.id);
+                updated_count += 1;
+            } else {
+                // ── CREATE new task ──
+
+                if is_becoming_doing && !has_spec {
+                    return Ok(PlanResult::error(format!(
+                        "Task '{}': spec (description) is required when starting a task (status: doing).\n\n\
+                        Before starting a task, please describe:\n  \
+                        • What is the goal of this task\n  \
+                        • How do you plan to approach it",
+                        task_name
+                    )));
+                }
+
+                // Determine initial status (doing is handled via start_task later)
+                let initial_status = match &task.status {
+                    Some(TaskStatus::Doing) => None, // will use start_task
+                    Some(s) => Some(s.as_db_str()),
+                    None => None,
+                };
+
+                let new_task = task_mgr
+                    .add_task(
+                        task_name,
+                        task.spec.as_deref(),
+                        None, // parent set later
+                        Some("ai"),
+                        task.priority.as_ref().map(|p| p.to_int()),
+                        None, // metadata
+                    )
+                    .await?;
+
+                // Set status, active_form and estimate in a single update if needed
+                let needs_status = initial_status
+                    .as_ref()
+                    .map(|s| *s != "todo")
+                    .unwrap_or(false);
+                if needs_status || task.active_form.is_some() || task.estimate_minutes.is_some() {
+                    task_mgr
+                        .update_task(
+                            new_task.id,
+                            TaskUpdate {
+                                status: if needs_status { initial_status } else { None },
+                                active_form: task.active_form.as_deref(),
+                                estimate_minutes: task.estimate_minutes.map(i64::from),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+                }
+
+                task_id_map.insert(task_name.clone(), new_task.id);
+                newly_created_names.insert(task_name.clone());
+                created_count += 1;
+
+                if !has_spec && !is_becoming_doing {
+                    warnings.push(format!(
+                        "Task '{}' has no description. Consider adding one for better context.",
+                        task_name
+                    ));
+                }
+            }
+        }
+
+        // ── 11. Build parent-child relationships ──
+
+        // 11a. Children nesting (parent_name from tree structure)
+        for task in &normal_tasks {
+            if let Some(parent_name) = &task.parent_name {
+                if let Some(task_name) = &task.name {
+                    let task_id = task_id_map.get(task_name).ok_or_else(|| {
+                        IntentError::InvalidInput(format!("Task not found: {}", task_name))
+                    })?;
+                    let parent_id = task_id_map.get(parent_name).ok_or_else(|| {
+                        IntentError::InvalidInput(format!("Parent task not found: {}", parent_name))
+                    })?;
+                    task_mgr
+                        .update_task(
+                            *task_id,
+                            TaskUpdate {
+                                parent_id: Some(Some(*parent_id)),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        // 11b. Explicit parent_id (takes precedence over auto-parent)
+        for task in &normal_tasks {
+            if task.parent_name.is_some() {
+                continue; // already handled
+            }
+
+            if let Some(explicit_parent) = &task.explicit_parent_id {
+                if let Some(task_name) = &task.name {
+                    let task_id = task_id_map.get(task_name).ok_or_else(|| {
+                        IntentError::InvalidInput(format!("Task not found: {}", task_name))
+                    })?;
+
+                    match explicit_parent {
+                        None => {
+                            // parent_id: null → root task
+                            task_mgr
+                                .update_task(
+                                    *task_id,
+                                    TaskUpdate {
+                                        parent_id: Some(None),
+                                        ..Default::default()
+                                    },
+                                )
+                                .await?;
+                        },
+                        Some(parent_id) => {
+                            task_mgr
+                                .update_task(
+                                    *task_id,
+                                    TaskUpdate {
+                                        parent_id: Some(Some(*parent_id)),
+                                        ..Default::default()
+                                    },
+                                )
+                                .await?;
+                        },
+                    }
+                }
+            }
+        }
+
+        // 11c. Auto-parent newly created root tasks to default_parent_id
+        if let Some(default_parent) = self.default_parent_id {
+            for task in &normal_tasks {
+                if let Some(task_name) = &task.name {
+                    if newly_created_names.contains(task_name)
+                        && task.parent_name.is_none()
+                        && task.explicit_parent_id.is_none()
+                    {
+                        if let Some(&task_id) = task_id_map.get(task_name) {
+                            task_mgr
+                                .update_task(
+                                    task_id,
+                                    TaskUpdate {
+                                        parent_id: Some(Some(default_parent)),
+                                        ..Default::default()
+                                    },
+                                )
+                                .await?;
+                        }
+                    }
+                }
+            }
+        }
+
+        // ── 12. Build dependency rows ──
+        let dep_count = match self.build_dependencies(&flat_tasks, &task_id_map).await {
+            Ok(count) => count,
+            Err(IntentError::InvalidInput(message)) => return Ok(PlanResult::error(message)),
+            Err(e) => return Err(e),
+        };
+
+        // ── 13. Auto-focus the doing task ──
+        let doing_task = normal_tasks
+            .iter()
+            .find(|task| matches!(task.status, Some(TaskStatus::Doing)));
+
+        let focused_task_response: Option<TaskWithEvents> = if let Some(doing_task) = doing_task {
+            if let Some(task_name) = &doing_task.name {
+                if let Some(&task_id) = task_id_map.get(task_name) {
+                    let response = task_mgr.start_task(task_id, true).await?;
+                    Some(response)
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        // ── 14. Return result ──
+        Ok(PlanResult::success_with_warnings(
+            task_id_map,
+            created_count,
+            updated_count,
+            deleted_count,
+            cascade_deleted_count,
+            dep_count,
+            focused_task_response,
+            warnings,
+        ))
+    }
+
+    /// Insert dependency rows from depends_on declarations.
+    ///
+    /// Same rules as the SQLite executor: new edges are checked against the
+    /// stored ones before any is written (a cycle fails with its full path),
+    /// and edges that already exist are skipped, so re-running a plan is
+    /// idempotent and the count only covers new edges.
+    async fn build_dependencies(
+        &self,
+        flat_tasks: &[FlatTask],
+        task_id_map: &HashMap<String, i64>,
+    ) -> Result<usize> {
+        // (blocked, blocking) pairs
+        let mut edges = Vec::new();
+        for task in flat_tasks {
+            if task.delete || task.depends_on.is_empty() {
+                continue;
+            }
+            let task_name = match &task.name {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let blocked_id = match task_id_map.get(task_name) {
+                Some(&id) => id,
+                None => continue,
+            };
+
+            for dep_name in &task.depends_on {
+                let blocking_id = task_id_map.get(dep_name).ok_or_else(|| {
+                    IntentError::InvalidInput(format!(
+                        "Dependency '{}' not found for task '{}'",
+                        dep_name, task_name
+                    ))
+                })?;
+                edges.push((blocked_id, *blocking_id));
+            }
+        }
+        if edges.is_empty() {
+            return Ok(0);
+        }
+
+        let mut depends_on = self.load_dependency_graph(&edges).await?;
+        for &(blocked_id, blocking_id) in &edges {
+            if let Some(cycle) =
+                plan_validation::find_dependency_cycle(&depends_on, blocked_id, blocking_id)
+            {
+                let names = self.find_task_names(&cycle).await?;
+                let path: Vec<String> = cycle
+                    .iter()
+                    .map(|id| {
+                        format!(
+                            "{} (#{})",
+                            names.get(id).map(String::as_str).unwrap_or("?"),
+                            id
+                        )
+                    })
+                    .collect();
+                return Err(IntentError::InvalidInput(format!(
+                    "Circular dependency detected with existing dependencies: {}",
+                    path.join(" → ")
+                )));
+            }
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
+        }
+
+        let (blocked_ids, blocking_ids): (Vec<i64>, Vec<i64>) = edges.into_iter().unzip();
+        let created = sqlx::query(
+            "INSERT INTO dependencies (project_id, blocked_task_id, blocking_task_id) \
+             SELECT $1, blocked, blocking FROM UNNEST($2::bigint[], $3::bigint[]) AS e(blocked, blocking) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(&self.project_id)
+        .bind(&blocked_ids)
+        .bind(&blocking_ids)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(created.rows_affected() as usize)
+    }
+
+    /// Stored dependency edges reachable from the tasks of `edges`, as
+    /// task → dependencies (the input of `find_dependency_cycle`)
+    async fn load_dependency_graph(&self, edges: &[(i64, i64)]) -> Result<HashMap<i64, Vec<i64>>> {
+        let seeds: Vec<i64> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            "WITH RECURSIVE reachable(id) AS ( \
+                 SELECT UNNEST($2::bigint[]) \
+                 UNION \
+                 SELECT d.blocking_task_id FROM dependencies d \
+                 JOIN reachable r ON d.project_id = $1 AND d.blocked_task_id = r.id \
+             ) \
+             SELECT d.blocked_task_id, d.blocking_task_id FROM dependencies d \
+             JOIN reachable r ON d.project_id = $1 AND d.blocked_task_id = r.id",
+        )
+        .bind(&self.project_id)
+        .bind(&seeds)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut depends_on: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (blocked_id, blocking_id) in rows {
+            depends_on.entry(blocked_id).or_default().push(blocking_id);
+        }
+        Ok(depends_on)
+    }
+
+    /// Names of the given tasks, by ID
+    async fn find_task_names(&self, ids: &[i64]) -> Result<HashMap<i64, String>> {
+        let rows: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, name FROM tasks WHERE project_id = $1 AND id = ANY($2)")
+                .bind(&self.project_id)
+                .bind(ids)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Find existing tasks by names in one query.
+    async fn find_tasks_by_names(
+        &self,
+        names: &[String],
+    ) -> Result<HashMap<String, ExistingTaskInfo>> {
+        if names.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(String, i64, String, Option<String>, String)> = sqlx::query_as(
+            "SELECT name, id, status, spec, owner FROM tasks \
+             WHERE project_id = $1 AND name = ANY($2)",
+        )
+        .bind(&self.project_id)
+        .bind(names)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, id, status, spec, owner)| {
+                (
+                    name,
+                    ExistingTaskInfo {
+                        id,
+                        status,
+                        spec,
+                        owner,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+impl crate::backend::PlanBackend for PostgresPlanExecutor {
+    fn execute(
+        &self,
+        request: &PlanRequest,
+    ) -> impl std::future::Future<Output = Result<crate::plan::PlanResult>> + Send {
+        self.execute(request)
+    }
+}
+
+// Validation functions (validate_dependencies, validate_batch_single_doing,
+// detect_circular_dependencies, tarjan_scc) are in crate::plan_validation.
@@ -0,0 +1,193 @@
+//! Postgres schema migrations.
+//!
+//! All tables live in the `intent_engine` schema (the connection's
+//! `search_path`, see `PostgresContext::connect`) and are keyed by
+//! `project_id`, so one database can hold many projects. Migrations are
+//! numbered, recorded in `schema_migrations`, and applied under an advisory
+//! lock so that developers starting `ie` at the same time don't race.
+
+use crate::error::Result;
+use sqlx::PgPool;
+
+/// Postgres schema holding the intent-engine tables
+pub const SCHEMA: &str = "intent_engine";
+
+/// Channel the change triggers notify (see [`super::listener`])
+pub const CHANGES_CHANNEL: &str = "intent_engine_changes";
+
+/// Advisory lock key serializing migrations across clients
+const MIGRATION_LOCK: i64 = 0x1e_5c_be_0a;
+
+/// Migrations in order; append new ones, never edit applied ones.
+const MIGRATIONS: &[(i32, &str)] = &[(
+    1,
+    r#"
+        CREATE TABLE tasks (
+            project_id TEXT NOT NULL,
+            id BIGINT NOT NULL,
+            parent_id BIGINT,
+            name TEXT NOT NULL,
+            spec TEXT,
+            status TEXT NOT NULL DEFAULT 'todo',
+            complexity INTEGER,
+            priority INTEGER,
+            first_todo_at TIMESTAMPTZ,
+            first_doing_at TIMESTAMPTZ,
+            first_done_at TIMESTAMPTZ,
+            active_form TEXT,
+            owner TEXT NOT NULL DEFAULT 'human',
+            metadata TEXT,
+            sort_order BIGINT,
+            estimate_minutes BIGINT,
+            search_vector tsvector GENERATED ALWAYS AS (
+                setweight(to_tsvector('simple', name), 'A') ||
+                setweight(to_tsvector('simple', coalesce(spec, '')), 'B')
+            ) STORED,
+            PRIMARY KEY (project_id, id),
+            FOREIGN KEY (project_id, parent_id) REFERENCES tasks (project_id, id) ON DELETE CASCADE,
+            CHECK (status IN ('todo', 'doing', 'review', 'done')),
+            CHECK (owner <> '')
+        );
+        CREATE INDEX tasks_parent_idx ON tasks (project_id, parent_id);
+        CREATE INDEX tasks_status_idx ON tasks (project_id, status);
+        CREATE INDEX tasks_search_idx ON tasks USING GIN (search_vector);
+
+        CREATE TABLE events (
+            project_id TEXT NOT NULL,
+            id BIGINT NOT NULL,
+            task_id BIGINT NOT NULL,
+            timestamp TIMESTAMPTZ NOT NULL DEFAULT now(),
+            log_type TEXT NOT NULL,
+            discussion_data TEXT NOT NULL,
+            search_vector tsvector GENERATED ALWAYS AS (
+                to_tsvector('simple', discussion_data)
+            ) STORED,
+            PRIMARY KEY (project_id, id),
+            FOREIGN KEY (project_id, task_id) REFERENCES tasks (project_id, id) ON DELETE CASCADE
+        );
+        CREATE INDEX events_task_idx ON events (project_id, task_id, timestamp);
+        CREATE INDEX events_search_idx ON events USING GIN (search_vector);
+
+        CREATE TABLE dependencies (
+            project_id TEXT NOT NULL,
+            blocking_task_id BIGINT NOT NULL,
+            blocked_task_id BIGINT NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            PRIMARY KEY (project_id, blocking_task_id, blocked_task_id),
+            FOREIGN KEY (project_id, blocking_task_id) REFERENCES tasks (project_id, id) ON DELETE CASCADE,
+            FOREIGN KEY (project_id, blocked_task_id) REFERENCES tasks (project_id, id) ON DELETE CASCADE,
+            CHECK (blocking_task_id <> blocked_task_id)
+        );
+        CREATE INDEX dependencies_blocked_idx ON dependencies (project_id, blocked_task_id);
+
+        CREATE TABLE sessions (
+            project_id TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            current_task_id BIGINT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            last_active_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            PRIMARY KEY (project_id, session_id)
+        );
+
+        CREATE TABLE counters (
+            project_id TEXT NOT NULL,
+            entity TEXT NOT NULL,
+            next_id BIGINT NOT NULL,
+            PRIMARY KEY (project_id, entity)
+        );
+
+        CREATE FUNCTION notify_change() RETURNS trigger AS $$
+        DECLARE
+            changed jsonb;
+        BEGIN
+            IF TG_OP = 'DELETE' THEN
+                changed := to_jsonb(OLD);
+            ELSE
+                changed := to_jsonb(NEW);
+            END IF;
+            PERFORM pg_notify('intent_engine_changes', jsonb_build_object(
+                'project_id', changed ->> 'project_id',
+                'entity', TG_ARGV[0],
+                'operation', CASE TG_OP
+                    WHEN 'INSERT' THEN 'create'
+                    WHEN 'UPDATE' THEN 'update'
+                    ELSE 'delete'
+                END,
+                'id', (changed ->> 'id')::bigint,
+                'task_id', (changed ->> 'task_id')::bigint
+            )::text);
+            RETURN NULL;
+        END;
+        $$ LANGUAGE plpgsql;
+
+        CREATE TRIGGER tasks_notify AFTER INSERT OR UPDATE OR DELETE ON tasks
+            FOR EACH ROW EXECUTE FUNCTION notify_change('task');
+        CREATE TRIGGER events_notify AFTER INSERT OR UPDATE OR DELETE ON events
+            FOR EACH ROW EXECUTE FUNCTION notify_change('event');
+        "#,
+)];
+
+/// Apply the migrations this database has not seen yet.
+///
+/// Idempotent and safe to call on every startup; returns how many
+/// migrations were applied.
+pub async fn migrate(pool: &PgPool) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(MIGRATION_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::raw_sql(&format!(
+        "CREATE SCHEMA IF NOT EXISTS {SCHEMA}; \
+         CREATE TABLE IF NOT EXISTS {SCHEMA}.schema_migrations ( \
+             version INTEGER PRIMARY KEY, \
+             applied_at TIMESTAMPTZ NOT NULL DEFAULT now() \
+         )"
+    ))
+    .execute(&mut *tx)
+    .await?;
+
+    let applied: Vec<i32> =
+        sqlx::query_scalar(&format!("SELECT version FROM {SCHEMA}.schema_migrations"))
+            .fetch_all(&mut *tx)
+            .await?;
+
+    let mut count = 0;
+    for (version, sql) in MIGRATIONS {
+        if applied.contains(version) {
+            continue;
+        }
+        sqlx::raw_sql(sql).execute(&mut *tx).await?;
+        sqlx::query(&format!(
+            "INSERT INTO {SCHEMA}.schema_migrations (version) VALUES ($1)"
+        ))
+        .bind(version)
+        .execute(&mut *tx)
+        .await?;
+        count += 1;
+    }
+
+    tx.commit().await?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_numbered_in_order() {
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|(v, _)| *v).collect();
+        let expected: Vec<i32> = (1..=MIGRATIONS.len() as i32).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_trigger_notifies_changes_channel() {
+        assert!(MIGRATIONS[0]
+            .1
+            .contains(&format!("pg_notify('{CHANGES_CHANNEL}'")));
+    }
+}
@@ -0,0 +1,291 @@
+//! Full-text search backed by Postgres `tsvector` columns.
+//!
+//! Matches `plainto_tsquery('simple', ...)` against the generated
+//! `search_vector` columns from `schema.rs` (every word must match), ranked
+//! with `ts_rank`. The `simple` parser keeps a run of CJK characters as one
+//! word, so queries containing CJK fall back to `ILIKE` substring matching.
+
+use crate::db::models::{Event, PaginatedSearchResults, SearchResult, Task};
+use crate::error::{IntentError, Result};
+use crate::search::{build_context_snippet, is_cjk_char};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool, Row};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use super::event_manager::EVENT_COLUMNS;
+use super::task_manager::TASK_COLUMNS;
+
+/// `ts_headline` options producing SQLite-style `**match**` snippets
+const HEADLINE_OPTIONS: &str = "StartSel=**, StopSel=**, MaxWords=15, MinWords=5";
+
+/// Search manager backed by Postgres full-text search.
+pub struct PostgresSearchManager {
+    pool: PgPool,
+    project_id: String,
+}
+
+impl PostgresSearchManager {
+    pub fn new(pool: PgPool, project_id: String) -> Self {
+        Self { pool, project_id }
+    }
+
+    /// Unified search across tasks and events.
+    pub async fn search(
+        &self,
+        query_str: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<PaginatedSearchResults> {
+        let limit = limit.unwrap_or(20);
+        let offset = offset.unwrap_or(0);
+
+        let has_searchable = query_str
+            .chars()
+            .any(|c| c.is_alphanumeric() || is_cjk_char(c));
+        if !has_searchable {
+            return Ok(PaginatedSearchResults {
+                results: Vec::new(),
+                total_tasks: 0,
+                total_events: 0,
+                has_more: false,
+                limit,
+                offset,
+            });
+        }
+
+        let use_like = query_str.chars().any(is_cjk_char);
+
+        let mut total_tasks: i64 = 0;
+        let mut total_events: i64 = 0;
+        let mut all_results: Vec<(SearchResult, f64)> = Vec::new();
+
+        if include_tasks {
+            let (tasks, count) = self
+                .search_tasks(query_str, use_like, limit, offset)
+                .await?;
+            total_tasks = count;
+            all_results.extend(tasks);
+        }
+
+        if include_events {
+            let (events, count) = self
+                .search_events(query_str, use_like, limit, offset)
+                .await?;
+            total_events = count;
+            all_results.extend(events);
+        }
+
+        all_results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let results: Vec<SearchResult> =
+            all_results.into_iter().map(|(result, _)| result).collect();
+
+        let total_count = total_tasks + total_events;
+        let has_more = offset + (results.len() as i64) < total_count;
+
+        Ok(PaginatedSearchResults {
+            results,
+            total_tasks,
+            total_events,
+            has_more,
+            limit,
+            offset,
+        })
+    }
+
+    async fn search_tasks(
+        &self,
+        query_str: &str,
+        use_like: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<(SearchResult, f64)>, i64)> {
+        let sql = if use_like {
+            format!(
+                "SELECT {TASK_COLUMNS}, 1.0::float8 AS score, \
+                     CASE WHEN t.name ILIKE $2 THEN 'name' ELSE 'spec' END AS match_field, \
+                     NULL::text AS headline, count(*) OVER () AS total \
+                 FROM tasks t \
+                 WHERE t.project_id = $1 AND (t.name ILIKE $2 OR t.spec ILIKE $2) \
+                 ORDER BY t.id ASC LIMIT $3 OFFSET $4"
+            )
+        } else {
+            format!(
+                "SELECT {TASK_COLUMNS}, ts_rank(t.search_vector, q)::float8 AS score, \
+                     CASE WHEN to_tsvector('simple', t.name) @@ q THEN 'name' ELSE 'spec' END \
+                         AS match_field, \
+                     ts_headline('simple', coalesce(t.spec, ''), q, '{HEADLINE_OPTIONS}') \
+                         AS headline, \
+                     count(*) OVER () AS total \
+                 FROM tasks t, plainto_tsquery('simple', $2) q \
+                 WHERE t.project_id = $1 AND t.search_vector @@ q \
+                 ORDER BY score DESC, t.id ASC LIMIT $3 OFFSET $4"
+            )
+        };
+
+        let rows = sqlx::query(&sql)
+            .bind(&self.project_id)
+            .bind(search_term(query_str, use_like))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let task = Task::from_row(row)?;
+            let score: f64 = row.try_get("score")?;
+            let match_field: String = row.try_get("match_field")?;
+            let headline: Option<String> = row.try_get("headline")?;
+            let match_snippet = match (match_field.as_str(), headline) {
+                ("name", _) => task.name.clone(),
+                (_, Some(headline)) => headline,
+                (_, None) => build_context_snippet(task.spec.as_deref().unwrap_or(""), query_str),
+            };
+            results.push((
+                SearchResult::Task {
+                    task,
+                    match_snippet,
+                    match_field,
+                    score,
+                },
+                score,
+            ));
+        }
+
+        Ok((results, total_of(&rows)?))
+    }
+
+    async fn search_events(
+        &self,
+        query_str: &str,
+        use_like: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<(SearchResult, f64)>, i64)> {
+        let sql = if use_like {
+            format!(
+                "SELECT {EVENT_COLUMNS}, 1.0::float8 AS score, NULL::text AS headline, \
+                     count(*) OVER () AS total \
+                 FROM events e \
+                 WHERE e.project_id = $1 AND e.discussion_data ILIKE $2 \
+                 ORDER BY e.id ASC LIMIT $3 OFFSET $4"
+            )
+        } else {
+            format!(
+                "SELECT {EVENT_COLUMNS}, ts_rank(e.search_vector, q)::float8 AS score, \
+                     ts_headline('simple', e.discussion_data, q, '{HEADLINE_OPTIONS}') AS headline, \
+                     count(*) OVER () AS total \
+                 FROM events e, plainto_tsquery('simple', $2) q \
+                 WHERE e.project_id = $1 AND e.search_vector @@ q \
+                 ORDER BY score DESC, e.id ASC LIMIT $3 OFFSET $4"
+            )
+        };
+
+        let rows = sqlx::query(&sql)
+            .bind(&self.project_id)
+            .bind(search_term(query_str, use_like))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let task_mgr = super::PostgresTaskManager::new(self.pool.clone(), self.project_id.clone());
+        let mut ancestry: HashMap<i64, Vec<Task>> = HashMap::new();
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let event = Event::from_row(row)?;
+            let score: f64 = row.try_get("score")?;
+            let headline: Option<String> = row.try_get("headline")?;
+            let match_snippet = headline
+                .unwrap_or_else(|| build_context_snippet(&event.discussion_data, query_str));
+
+            if let Entry::Vacant(slot) = ancestry.entry(event.task_id) {
+                let mut chain = vec![task_mgr.get_task(event.task_id).await?];
+                chain.extend(task_mgr.get_task_ancestry(event.task_id).await?);
+                slot.insert(chain);
+            }
+            let task_chain = ancestry[&event.task_id].clone();
+
+            results.push((
+                SearchResult::Event {
+                    event,
+                    task_chain,
+                    match_snippet,
+                    score,
+                },
+                score,
+            ));
+        }
+
+        Ok((results, total_of(&rows)?))
+    }
+}
+
+impl crate::backend::SearchBackend for PostgresSearchManager {
+    fn search(
+        &self,
+        query: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<PaginatedSearchResults>> + Send {
+        self.search(query, include_tasks, include_events, limit, offset)
+    }
+
+    fn filter_event_types(self, event_types: Vec<String>) -> Result<Self> {
+        if event_types.is_empty() {
+            return Ok(self);
+        }
+        Err(IntentError::InvalidInput(
+            "Filtering events by type is not supported by the Postgres backend".to_string(),
+        ))
+    }
+}
+
+/// The `$2` parameter: the query itself, or an `ILIKE` pattern matching it
+/// anywhere
+fn search_term(query: &str, use_like: bool) -> String {
+    if !use_like {
+        return query.to_string();
+    }
+    let mut pattern = String::with_capacity(query.len() + 2);
+    pattern.push('%');
+    for ch in query.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Total match count carried by every row of a page (0 for an empty page)
+fn total_of(rows: &[PgRow]) -> Result<i64> {
+    match rows.first() {
+        Some(row) => Ok(row.try_get("total")?),
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_term_plain() {
+        assert_eq!(search_term("login page", false), "login page");
+    }
+
+    #[test]
+    fn test_search_term_like_escapes_wildcards() {
+        assert_eq!(search_term("认证", true), "%认证%");
+        assert_eq!(search_term(r"50%_a\b", true), r"%50\%\_a\\b%");
+    }
+}
@@ -0,0 +1,1238 @@
+use crate::db::models::{
+    ChecklistItem, ChildrenPage, CloneSubtreeResponse, CompletionOutcome, DoneTaskResponse,
+    EstimateRollup, NextStepSuggestion, PaginatedTasks, PickNextResponse, SpecRevision,
+    StatusResponse, Task, TaskBrief, TaskContext, TaskDependencies, TaskLease, TaskSortBy,
+    TaskTransition, TaskWithEvents, WorkspaceStatus,
+};
+use crate::error::{IntentError, Result};
+use crate::tasks::TaskUpdate;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// Columns of a `Task` (table alias `t`), in `SELECT` order
+pub(crate) const TASK_COLUMNS: &str = "t.id, t.parent_id, t.name, t.spec, t.status, \
+     t.complexity, t.priority, t.first_todo_at, t.first_doing_at, t.first_done_at, \
+     t.active_form, t.owner, t.metadata, t.sort_order, t.estimate_minutes";
+
+/// SQL predicate: task `t` has no unfinished dependency.
+/// Same rule as the SQLite `pick_next` (any blocking task not yet `done`).
+const UNBLOCKED: &str = "NOT EXISTS ( \
+     SELECT 1 FROM dependencies d \
+     JOIN tasks blocking ON blocking.project_id = d.project_id AND blocking.id = d.blocking_task_id \
+     WHERE d.project_id = t.project_id AND d.blocked_task_id = t.id AND blocking.status <> 'done')";
+
+/// Recursive CTE `subtree(id)`: task `$2` and all of its descendants
+const SUBTREE: &str = "WITH RECURSIVE subtree(id) AS ( \
+     SELECT id FROM tasks WHERE project_id = $1 AND id = $2 \
+     UNION ALL \
+     SELECT c.id FROM tasks c JOIN subtree s ON c.project_id = $1 AND c.parent_id = s.id)";
+
+/// Task management backed by Postgres.
+///
+/// Every query is scoped by `project_id`; the hierarchy is the `parent_id`
+/// column (deleting a task deletes its subtree through the foreign key, as in
+/// SQLite) and dependencies live in the `dependencies` table.
+pub struct PostgresTaskManager {
+    pool: PgPool,
+    project_id: String,
+}
+
+impl PostgresTaskManager {
+    pub fn new(pool: PgPool, project_id: String) -> Self {
+        Self { pool, project_id }
+    }
+
+    // ── Read Operations ─────────────────────────────────────────
+
+    /// Get all root tasks (no parent) ordered by status priority then id.
+    pub async fn get_root_tasks(&self) -> Result<Vec<Task>> {
+        self.fetch_tasks(
+            "WHERE t.project_id = $1 AND t.parent_id IS NULL \
+             ORDER BY CASE t.status \
+                 WHEN 'doing' THEN 0 WHEN 'todo' THEN 1 WHEN 'review' THEN 2 ELSE 3 \
+             END, t.priority ASC, t.id ASC",
+            None,
+        )
+        .await
+    }
+
+    /// Get a single task by ID.
+    pub async fn get_task(&self, task_id: i64) -> Result<Task> {
+        sqlx::query_as::<_, Task>(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks t WHERE t.project_id = $1 AND t.id = $2"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(IntentError::TaskNotFound(task_id))
+    }
+
+    /// Get ancestor chain from a task up to root (immediate parent first).
+    pub async fn get_task_ancestry(&self, task_id: i64) -> Result<Vec<Task>> {
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            "WITH RECURSIVE chain(id, depth) AS ( \
+                 SELECT parent_id, 1 FROM tasks WHERE project_id = $1 AND id = $2 \
+                 UNION ALL \
+                 SELECT p.parent_id, c.depth + 1 FROM tasks p \
+                 JOIN chain c ON p.project_id = $1 AND p.id = c.id \
+             ) \
+             SELECT {TASK_COLUMNS} FROM tasks t \
+             JOIN chain c ON t.project_id = $1 AND t.id = c.id \
+             ORDER BY c.depth ASC"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tasks)
+    }
+
+    /// Get sibling tasks (same parent, excluding self).
+    pub async fn get_siblings(&self, task_id: i64, parent_id: Option<i64>) -> Result<Vec<Task>> {
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks t \
+             WHERE t.project_id = $1 AND t.parent_id IS NOT DISTINCT FROM $2 AND t.id <> $3 \
+             ORDER BY t.id ASC"
+        ))
+        .bind(&self.project_id)
+        .bind(parent_id)
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tasks)
+    }
+
+    /// Get direct children of a task.
+    pub async fn get_children(&self, task_id: i64) -> Result<Vec<Task>> {
+        self.fetch_tasks(
+            "WHERE t.project_id = $1 AND t.parent_id = $2 ORDER BY t.id ASC",
+            Some(task_id),
+        )
+        .await
+    }
+
+    /// Get all descendants recursively.
+    pub async fn get_descendants(&self, task_id: i64) -> Result<Vec<Task>> {
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            "{SUBTREE} \
+             SELECT {TASK_COLUMNS} FROM tasks t \
+             JOIN subtree s ON t.project_id = $1 AND t.id = s.id \
+             WHERE t.id <> $2 \
+             ORDER BY t.id ASC"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tasks)
+    }
+
+    /// Build a full StatusResponse for `ie status <id>`.
+    pub async fn get_status(&self, task_id: i64, with_events: bool) -> Result<StatusResponse> {
+        let task = self.get_task(task_id).await?;
+        let ancestors = self.get_task_ancestry(task_id).await?;
+        let siblings_full = self.get_siblings(task_id, task.parent_id).await?;
+        let descendants_full = self.get_descendants(task_id).await?;
+
+        let siblings: Vec<TaskBrief> = siblings_full.iter().map(TaskBrief::from).collect();
+        let descendants: Vec<TaskBrief> = descendants_full.iter().map(TaskBrief::from).collect();
+
+        let events = if with_events {
+            let evts = self
+                .event_manager()
+                .list_events(Some(task_id), Some(20), None, None)
+                .await?;
+            if evts.is_empty() {
+                None
+            } else {
+                Some(evts)
+            }
+        } else {
+            None
+        };
+
+        Ok(StatusResponse {
+            focused_task: task,
+            ancestors,
+            siblings,
+            descendants,
+            events,
+        })
+    }
+
+    // ── Write Operations ────────────────────────────────────────
+
+    /// Add a new task in 'todo'. Returns the created Task.
+    pub async fn add_task(
+        &self,
+        name: &str,
+        spec: Option<&str>,
+        parent_id: Option<i64>,
+        owner: Option<&str>,
+        priority: Option<i32>,
+        metadata: Option<&str>,
+    ) -> Result<Task> {
+        if let Some(pid) = parent_id {
+            self.check_task_exists(pid).await?;
+        }
+
+        let id = super::next_id(&self.pool, &self.project_id, "task").await?;
+        let task = sqlx::query_as::<_, Task>(&format!(
+            "INSERT INTO tasks AS t \
+                 (project_id, id, name, spec, parent_id, owner, priority, metadata, first_todo_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, now()) \
+             RETURNING {TASK_COLUMNS}"
+        ))
+        .bind(&self.project_id)
+        .bind(id)
+        .bind(name)
+        .bind(spec)
+        .bind(parent_id)
+        .bind(owner.unwrap_or("human"))
+        .bind(priority)
+        .bind(metadata)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(task)
+    }
+
+    /// Update a task using the TaskUpdate struct (partial updates).
+    pub async fn update_task(&self, id: i64, update: TaskUpdate<'_>) -> Result<Task> {
+        let task = self.get_task(id).await?;
+
+        if let Some(s) = update.status {
+            if !["todo", "doing", "review", "done"].contains(&s) {
+                return Err(IntentError::InvalidInput(format!("Invalid status: {}", s)));
+            }
+        }
+
+        if update.estimate_minutes.is_some_and(|m| m < 0) {
+            return Err(IntentError::InvalidInput(
+                "estimate_minutes cannot be negative".to_string(),
+            ));
+        }
+
+        if update.needs_human.is_some() {
+            return Err(IntentError::InvalidInput(
+                "needs_human is not supported by the Postgres backend".to_string(),
+            ));
+        }
+
+        if update.blocked_reason.is_some() {
+            return Err(IntentError::InvalidInput(
+                "blocked_reason is not supported by the Postgres backend".to_string(),
+            ));
+        }
+
+        if update.visibility.is_some() {
+            return Err(IntentError::InvalidInput(
+                "visibility is not supported by the Postgres backend".to_string(),
+            ));
+        }
+
+        if update.owner.is_some_and(str::is_empty) {
+            return Err(IntentError::InvalidInput(
+                "owner cannot be empty".to_string(),
+            ));
+        }
+
+        if let Some(Some(pid)) = update.parent_id {
+            if pid == id {
+                return Err(IntentError::CircularDependency {
+                    blocking_task_id: pid,
+                    blocked_task_id: id,
+                });
+            }
+            self.check_task_exists(pid).await?;
+            self.check_circular_dependency(id, pid).await?;
+        }
+
+        let mut query: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE tasks SET ");
+        let mut sets = query.separated(", ");
+
+        if let Some(name) = update.name {
+            sets.push("name = ").push_bind_unseparated(name);
+        }
+        if let Some(spec) = update.spec {
+            sets.push("spec = ").push_bind_unseparated(spec);
+        }
+        if let Some(parent_id) = update.parent_id {
+            sets.push("parent_id = ").push_bind_unseparated(parent_id);
+        }
+        if let Some(complexity) = update.complexity {
+            sets.push("complexity = ").push_bind_unseparated(complexity);
+        }
+        if let Some(priority) = update.priority {
+            sets.push("priority = ").push_bind_unseparated(priority);
+        }
+        if let Some(active_form) = update.active_form {
+            sets.push("active_form = ")
+                .push_bind_unseparated(active_form);
+        }
+        if let Some(owner) = update.owner {
+            sets.push("owner = ").push_bind_unseparated(owner);
+        }
+        if let Some(metadata) = update.metadata {
+            sets.push("metadata = ").push_bind_unseparated(metadata);
+        }
+        if let Some(sort_order) = update.sort_order {
+            sets.push("sort_order = ").push_bind_unseparated(sort_order);
+        }
+        if let Some(estimate) = update.estimate_minutes {
+            sets.push("estimate_minutes = ")
+                .push_bind_unseparated(estimate);
+        }
+        if let Some(status) = update.status {
+            sets.push("status = ").push_bind_unseparated(status);
+            match status {
+                "todo" => {
+                    sets.push("first_todo_at = COALESCE(first_todo_at, now())");
+                },
+                "doing" => {
+                    sets.push("first_doing_at = COALESCE(first_doing_at, now())");
+                },
+                "done" => {
+                    sets.push("first_done_at = COALESCE(first_done_at, now())");
+                },
+                _ => {},
+            }
+        }
+
+        let has_changes = update.name.is_some()
+            || update.spec.is_some()
+            || update.parent_id.is_some()
+            || update.complexity.is_some()
+            || update.priority.is_some()
+            || update.active_form.is_some()
+            || update.owner.is_some()
+            || update.metadata.is_some()
+            || update.sort_order.is_some()
+            || update.estimate_minutes.is_some()
+            || update.status.is_some();
+        if !has_changes {
+            return Ok(task);
+        }
+
+        query
+            .push(" WHERE project_id = ")
+            .push_bind(&self.project_id)
+            .push(" AND id = ")
+            .push_bind(id);
+        query.build().execute(&self.pool).await?;
+
+        self.get_task(id).await
+    }
+
+    /// Delete a task by ID; its subtree goes with it, as in SQLite.
+    ///
+    /// Refuses to delete tasks that are focused by any session.
+    pub async fn delete_task(&self, id: i64) -> Result<()> {
+        self.check_task_exists(id).await?;
+
+        if let Some(session_id) = self.focused_by(id).await? {
+            return Err(IntentError::ActionNotAllowed(format!(
+                "Task #{} is focused by session '{}'. Unfocus it first.",
+                id, session_id
+            )));
+        }
+
+        sqlx::query("DELETE FROM tasks WHERE project_id = $1 AND id = $2")
+            .bind(&self.project_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete a task and all its descendants (cascade).
+    ///
+    /// Refuses if any task in the subtree is focused by any session.
+    /// Returns the number of descendants deleted.
+    pub async fn delete_task_cascade(&self, id: i64) -> Result<usize> {
+        self.check_task_exists(id).await?;
+
+        if let Some((tid, session_id)) = self.find_focused_in_subtree(id).await? {
+            return Err(IntentError::ActionNotAllowed(format!(
+                "Cannot cascade delete: task #{} is focused by session '{}'. Unfocus it first.",
+                tid, session_id
+            )));
+        }
+
+        let count = self.get_descendants(id).await?.len();
+
+        sqlx::query("DELETE FROM tasks WHERE project_id = $1 AND id = $2")
+            .bind(&self.project_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Add a dependency: blocking_id blocks blocked_id.
+    ///
+    /// Rejects the edge if blocking_id already depends (transitively) on
+    /// blocked_id; adding an existing edge is a no-op.
+    pub async fn add_dependency(&self, blocking_id: i64, blocked_id: i64) -> Result<()> {
+        self.check_task_exists(blocking_id).await?;
+        self.check_task_exists(blocked_id).await?;
+
+        let is_cycle: bool = blocking_id == blocked_id
+            || sqlx::query_scalar(
+                "WITH RECURSIVE upstream(id) AS ( \
+                     SELECT blocking_task_id FROM dependencies \
+                     WHERE project_id = $1 AND blocked_task_id = $2 \
+                     UNION \
+                     SELECT d.blocking_task_id FROM dependencies d \
+                     JOIN upstream u ON d.project_id = $1 AND d.blocked_task_id = u.id \
+                 ) \
+                 SELECT EXISTS(SELECT 1 FROM upstream WHERE id = $3)",
+            )
+            .bind(&self.project_id)
+            .bind(blocking_id)
+            .bind(blocked_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if is_cycle {
+            return Err(IntentError::CircularDependency {
+                blocking_task_id: blocking_id,
+                blocked_task_id: blocked_id,
+            });
+        }
+
+        sqlx::query(
+            "INSERT INTO dependencies (project_id, blocking_task_id, blocked_task_id) \
+             VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(&self.project_id)
+        .bind(blocking_id)
+        .bind(blocked_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a dependency: blocking_id no longer blocks blocked_id.
+    pub async fn remove_dependency(&self, blocking_id: i64, blocked_id: i64) -> Result<()> {
+        sqlx::query(
+            "DELETE FROM dependencies \
+             WHERE project_id = $1 AND blocking_task_id = $2 AND blocked_task_id = $3",
+        )
+        .bind(&self.project_id)
+        .bind(blocking_id)
+        .bind(blocked_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Find tasks with optional filters, sorting, and pagination.
+    pub async fn find_tasks(
+        &self,
+        status: Option<&str>,
+        parent_id: Option<Option<i64>>,
+        sort_by: Option<TaskSortBy>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<PaginatedTasks> {
+        let sort_by = sort_by.unwrap_or_default();
+        let limit = limit.unwrap_or(100);
+        let offset = offset.unwrap_or(0);
+
+        let push_filters = |query: &mut QueryBuilder<'_, Postgres>| {
+            query
+                .push(" WHERE t.project_id = ")
+                .push_bind(self.project_id.clone());
+            if let Some(status) = status {
+                query.push(" AND t.status = ").push_bind(status.to_string());
+            }
+            match parent_id {
+                Some(Some(pid)) => {
+                    query.push(" AND t.parent_id = ").push_bind(pid);
+                },
+                Some(None) => {
+                    query.push(" AND t.parent_id IS NULL");
+                },
+                None => {},
+            }
+        };
+
+        let mut count_query = QueryBuilder::new("SELECT count(*) FROM tasks t");
+        push_filters(&mut count_query);
+        let total_count: i64 = count_query
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await?;
+
+        let order_clause = match sort_by {
+            TaskSortBy::Id => " ORDER BY t.id ASC",
+            TaskSortBy::Priority => {
+                " ORDER BY COALESCE(t.priority, 999) ASC, COALESCE(t.complexity, 5) ASC, t.id ASC"
+            },
+            TaskSortBy::Time => {
+                " ORDER BY CASE t.status \
+                     WHEN 'doing' THEN t.first_doing_at \
+                     WHEN 'review' THEN t.first_doing_at \
+                     WHEN 'todo' THEN t.first_todo_at \
+                     WHEN 'done' THEN t.first_done_at \
+                 END ASC, t.id ASC"
+            },
+            TaskSortBy::FocusAware => {
+                " ORDER BY CASE t.status \
+                     WHEN 'doing' THEN 0 WHEN 'todo' THEN 1 WHEN 'done' THEN 2 ELSE 3 \
+                 END ASC, COALESCE(t.priority, 999) ASC, t.id ASC"
+            },
+        };
+
+        let mut query = QueryBuilder::new(format!("SELECT {TASK_COLUMNS} FROM tasks t"));
+        push_filters(&mut query);
+        query
+            .push(order_clause)
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+        let tasks = query.build_query_as::<Task>().fetch_all(&self.pool).await?;
+
+        let has_more = offset + (tasks.len() as i64) < total_count;
+
+        Ok(PaginatedTasks {
+            tasks,
+            total_count,
+            has_more,
+            limit,
+            offset,
+        })
+    }
+
+    /// Start a task: set status to 'doing' and focus the session on it.
+    pub async fn start_task(&self, id: i64, with_events: bool) -> Result<TaskWithEvents> {
+        self.check_task_exists(id).await?;
+
+        let blocking_ids = self.get_blocking_task_ids(id).await?;
+        if !blocking_ids.is_empty() {
+            return Err(IntentError::TaskBlocked {
+                task_id: id,
+                blocking_task_ids: blocking_ids,
+            });
+        }
+
+        let session_id = crate::workspace::resolve_session_id(None);
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE tasks SET status = 'doing', first_doing_at = COALESCE(first_doing_at, now()) \
+             WHERE project_id = $1 AND id = $2",
+        )
+        .bind(&self.project_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        super::workspace_manager::set_session_focus(
+            &mut *tx,
+            &self.project_id,
+            &session_id,
+            Some(id),
+        )
+        .await?;
+        tx.commit().await?;
+
+        if with_events {
+            self.get_task_with_events(id).await
+        } else {
+            Ok(TaskWithEvents {
+                task: self.get_task(id).await?,
+                events_summary: None,
+                outcome: None,
+            })
+        }
+    }
+
+    /// Complete a task by ID. Validates children are done first.
+    ///
+    /// State changes (status + focus) run in a single transaction.
+    pub async fn done_task_by_id(&self, id: i64) -> Result<DoneTaskResponse> {
+        let task = self.get_task(id).await?;
+
+        let incomplete: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM tasks WHERE project_id = $1 AND parent_id = $2 AND status <> 'done'",
+        )
+        .bind(&self.project_id)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+        if incomplete > 0 {
+            return Err(IntentError::UncompletedChildren);
+        }
+
+        let session_id = crate::workspace::resolve_session_id(None);
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE tasks SET status = 'done', first_done_at = COALESCE(first_done_at, now()) \
+             WHERE project_id = $1 AND id = $2",
+        )
+        .bind(&self.project_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE sessions SET current_task_id = NULL, last_active_at = now() \
+             WHERE project_id = $1 AND session_id = $2 AND current_task_id = $3",
+        )
+        .bind(&self.project_id)
+        .bind(&session_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let current_task_id = self.current_focus(&session_id).await?;
+        let next_step_suggestion = self
+            .build_next_step_suggestion(id, &task.name, task.parent_id)
+            .await?;
+        let completed_task = self.get_task(id).await?;
+
+        Ok(DoneTaskResponse {
+            completed_task,
+            workspace_status: WorkspaceStatus { current_task_id },
+            next_step_suggestion,
+            warnings: Vec::new(),
+            newly_unblocked: Vec::new(),
+        })
+    }
+
+    /// Complete the current focused task.
+    pub async fn done_task(&self) -> Result<DoneTaskResponse> {
+        let session_id = crate::workspace::resolve_session_id(None);
+        let id = self.current_focus(&session_id).await?.ok_or_else(|| {
+            IntentError::InvalidInput(
+                "No current task is set. Use 'ie task start <ID>' to set a task first.".to_string(),
+            )
+        })?;
+
+        self.done_task_by_id(id).await
+    }
+
+    /// Suggest the next task to work on based on context.
+    ///
+    /// Priority order:
+    /// 1. Doing subtasks of current focus
+    /// 2. Todo subtasks of current focus
+    /// 3. Top-level doing tasks
+    /// 4. Top-level todo tasks
+    ///
+    /// Tasks blocked by unfinished dependencies are skipped at every level.
+    pub async fn pick_next(&self) -> Result<PickNextResponse> {
+        let session_id = crate::workspace::resolve_session_id(None);
+        let current_task_id = self.current_focus(&session_id).await?;
+
+        if let Some(current_id) = current_task_id {
+            for status in ["doing", "todo"] {
+                if let Some(task) = self.find_next(Some(current_id), status, None).await? {
+                    return Ok(PickNextResponse::focused_subtask(task));
+                }
+            }
+        }
+
+        if let Some(task) = self.find_next(None, "doing", current_task_id).await? {
+            return Ok(PickNextResponse::top_level_task(task));
+        }
+        if let Some(task) = self.find_next(None, "todo", None).await? {
+            return Ok(PickNextResponse::top_level_task(task));
+        }
+
+        let (total, incomplete): (i64, i64) = sqlx::query_as(
+            "SELECT count(*), count(*) FILTER (WHERE status IN ('todo', 'doing')) \
+             FROM tasks WHERE project_id = $1",
+        )
+        .bind(&self.project_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if total == 0 {
+            Ok(PickNextResponse::no_tasks_in_project())
+        } else if incomplete == 0 {
+            Ok(PickNextResponse::all_tasks_completed())
+        } else {
+            Ok(PickNextResponse::no_available_todos())
+        }
+    }
+
+    // ── Context / Events ────────────────────────────────────────
+
+    /// Get tasks that block the given task (this task depends on them).
+    pub async fn get_blocking_tasks(&self, task_id: i64) -> Result<Vec<Task>> {
+        self.fetch_tasks(
+            "JOIN dependencies d ON d.project_id = t.project_id AND d.blocking_task_id = t.id \
+             WHERE t.project_id = $1 AND d.blocked_task_id = $2 ORDER BY t.id ASC",
+            Some(task_id),
+        )
+        .await
+    }
+
+    /// Get tasks blocked by the given task (they depend on this task).
+    pub async fn get_blocked_by_tasks(&self, task_id: i64) -> Result<Vec<Task>> {
+        self.fetch_tasks(
+            "JOIN dependencies d ON d.project_id = t.project_id AND d.blocked_task_id = t.id \
+             WHERE t.project_id = $1 AND d.blocking_task_id = $2 ORDER BY t.id ASC",
+            Some(task_id),
+        )
+        .await
+    }
+
+    /// Get full task context: task + ancestors + siblings + children + dependencies.
+    ///
+    /// The queries after the first run concurrently on the pool.
+    pub async fn get_task_context(&self, id: i64) -> Result<TaskContext> {
+        let task = self.get_task(id).await?;
+
+        let (ancestors, siblings, children, blocking_tasks, blocked_by_tasks, descendants) = tokio::try_join!(
+            self.get_task_ancestry(id),
+            self.get_siblings(id, task.parent_id),
+            self.get_children(id),
+            self.get_blocking_tasks(id),
+            self.get_blocked_by_tasks(id),
+            self.get_descendants(id),
+        )?;
+        let estimate = EstimateRollup::from_tasks(std::iter::once(&task).chain(&descendants));
+
+        Ok(TaskContext {
+            task,
+            ancestors,
+            siblings,
+            children,
+            dependencies: TaskDependencies {
+                blocking_tasks,
+                blocked_by_tasks,
+            },
+            estimate,
+            links: Vec::new(),
+        })
+    }
+
+    /// Get task with events summary (count + recent 10).
+    pub async fn get_task_with_events(&self, id: i64) -> Result<TaskWithEvents> {
+        let task = self.get_task(id).await?;
+        let events_summary = self.event_manager().get_events_summary(id).await?;
+
+        Ok(TaskWithEvents {
+            task,
+            events_summary: Some(events_summary),
+            outcome: None,
+        })
+    }
+
+    // ── Internal Helpers ────────────────────────────────────────
+
+    fn event_manager(&self) -> super::PostgresEventManager {
+        super::PostgresEventManager::new(self.pool.clone(), self.project_id.clone())
+    }
+
+    /// Tasks matching `clause` (after `FROM tasks t`), with `$1` the project
+    /// and `$2` the optional `arg`
+    async fn fetch_tasks(&self, clause: &str, arg: Option<i64>) -> Result<Vec<Task>> {
+        let sql = format!("SELECT {TASK_COLUMNS} FROM tasks t {clause}");
+        let mut query = sqlx::query_as::<_, Task>(&sql).bind(&self.project_id);
+        if let Some(arg) = arg {
+            query = query.bind(arg);
+        }
+        Ok(query.fetch_all(&self.pool).await?)
+    }
+
+    /// Focused task of a session
+    async fn current_focus(&self, session_id: &str) -> Result<Option<i64>> {
+        let id = sqlx::query_scalar(
+            "SELECT current_task_id FROM sessions WHERE project_id = $1 AND session_id = $2",
+        )
+        .bind(&self.project_id)
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(id.flatten())
+    }
+
+    /// Session focusing exactly this task, if any
+    async fn focused_by(&self, task_id: i64) -> Result<Option<String>> {
+        let session_id = sqlx::query_scalar(
+            "SELECT session_id FROM sessions \
+             WHERE project_id = $1 AND current_task_id = $2 LIMIT 1",
+        )
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(session_id)
+    }
+
+    /// Check if any task in the subtree (self + descendants) is focused by any session.
+    ///
+    /// Returns `Some((focused_task_id, session_id))` if found, `None` otherwise.
+    pub(crate) async fn find_focused_in_subtree(
+        &self,
+        task_id: i64,
+    ) -> Result<Option<(i64, String)>> {
+        let focused = sqlx::query_as(&format!(
+            "{SUBTREE} \
+             SELECT s.current_task_id, s.session_id FROM sessions s \
+             JOIN subtree ON s.project_id = $1 AND s.current_task_id = subtree.id \
+             LIMIT 1"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(focused)
+    }
+
+    /// IDs of `todo`/`doing` tasks that block the given task.
+    ///
+    /// Mirrors `dependencies::get_incomplete_blocking_tasks`.
+    async fn get_blocking_task_ids(&self, task_id: i64) -> Result<Vec<i64>> {
+        let ids = sqlx::query_scalar(
+            "SELECT b.id FROM dependencies d \
+             JOIN tasks b ON b.project_id = d.project_id AND b.id = d.blocking_task_id \
+             WHERE d.project_id = $1 AND d.blocked_task_id = $2 AND b.status IN ('todo', 'doing') \
+             ORDER BY b.id",
+        )
+        .bind(&self.project_id)
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids)
+    }
+
+    async fn check_task_exists(&self, id: i64) -> Result<()> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE project_id = $1 AND id = $2)",
+        )
+        .bind(&self.project_id)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(IntentError::TaskNotFound(id))
+        }
+    }
+
+    /// Reject making `new_parent_id` the parent of `task_id` when it lies in
+    /// `task_id`'s subtree.
+    async fn check_circular_dependency(&self, task_id: i64, new_parent_id: i64) -> Result<()> {
+        let is_cycle: bool = sqlx::query_scalar(&format!(
+            "{SUBTREE} SELECT EXISTS(SELECT 1 FROM subtree WHERE id = $3)"
+        ))
+        .bind(&self.project_id)
+        .bind(task_id)
+        .bind(new_parent_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if is_cycle {
+            return Err(IntentError::CircularDependency {
+                blocking_task_id: new_parent_id,
+                blocked_task_id: task_id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Best unblocked task with `status`: a child of `parent_id`, or a root
+    /// task other than `exclude_id` (for pick_next).
+    async fn find_next(
+        &self,
+        parent_id: Option<i64>,
+        status: &str,
+        exclude_id: Option<i64>,
+    ) -> Result<Option<Task>> {
+        let task = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks t \
+             WHERE t.project_id = $1 AND t.parent_id IS NOT DISTINCT FROM $2 \
+               AND t.status = $3 AND t.id IS DISTINCT FROM $4 AND {UNBLOCKED} \
+             ORDER BY COALESCE(t.priority, 999) ASC, t.id ASC \
+             LIMIT 1"
+        ))
+        .bind(&self.project_id)
+        .bind(parent_id)
+        .bind(status)
+        .bind(exclude_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(task)
+    }
+
+    /// Build a next-step suggestion after completing a task.
+    async fn build_next_step_suggestion(
+        &self,
+        id: i64,
+        task_name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<NextStepSuggestion> {
+        if let Some(parent_task_id) = parent_id {
+            let remaining_siblings: i64 = sqlx::query_scalar(
+                "SELECT count(*) FROM tasks \
+                 WHERE project_id = $1 AND parent_id = $2 AND status <> 'done' AND id <> $3",
+            )
+            .bind(&self.project_id)
+            .bind(parent_task_id)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+            let parent_name = self.get_task(parent_task_id).await?.name;
+
+            if remaining_siblings == 0 {
+                Ok(NextStepSuggestion::ParentIsReady {
+                    message: crate::i18n::t(
+                        "next.parent_ready",
+                        &[("parent_id", &parent_task_id), ("parent", &parent_name)],
+                    ),
+                    parent_task_id,
+                    parent_task_name: parent_name,
+                })
+            } else {
+                Ok(NextStepSuggestion::SiblingTasksRemain {
+                    message: crate::i18n::t(
+                        "next.siblings_remain",
+                        &[
+                            ("id", &id),
+                            ("parent_id", &parent_task_id),
+                            ("parent", &parent_name),
+                        ],
+                    ),
+                    parent_task_id,
+                    parent_task_name: parent_name,
+                    remaining_siblings_count: remaining_siblings,
+                })
+            }
+        } else {
+            let (child_count, remaining_tasks): (i64, i64) = sqlx::query_as(
+                "SELECT count(*) FILTER (WHERE parent_id = $2), \
+                        count(*) FILTER (WHERE status <> 'done' AND id <> $2) \
+                 FROM tasks WHERE project_id = $1",
+            )
+            .bind(&self.project_id)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+            if child_count > 0 {
+                Ok(NextStepSuggestion::TopLevelTaskCompleted {
+                    message: crate::i18n::t(
+                        "next.top_level_done",
+                        &[("id", &id), ("name", &task_name)],
+                    ),
+                    completed_task_id: id,
+                    completed_task_name: task_name.to_string(),
+                })
+            } else if remaining_tasks == 0 {
+                Ok(NextStepSuggestion::WorkspaceIsClear {
+                    message: crate::i18n::t("next.workspace_clear", &[("id", &id)]),
+                    completed_task_id: id,
+                })
+            } else {
+                Ok(NextStepSuggestion::NoParentContext {
+                    message: crate::i18n::t("next.task_done", &[("id", &id), ("name", &task_name)]),
+                    completed_task_id: id,
+                    completed_task_name: task_name.to_string(),
+                })
+            }
+        }
+    }
+}
+
+// ── Backend Trait Impl ──────────────────────────────────────────
+
+impl crate::backend::TaskBackend for PostgresTaskManager {
+    fn get_task(&self, id: i64) -> impl std::future::Future<Output = Result<Task>> + Send {
+        self.get_task(id)
+    }
+
+    fn get_task_with_events(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<TaskWithEvents>> + Send {
+        self.get_task_with_events(id)
+    }
+
+    fn get_task_ancestry(
+        &self,
+        task_id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_task_ancestry(task_id)
+    }
+
+    fn get_task_context(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<TaskContext>> + Send {
+        self.get_task_context(id)
+    }
+
+    fn get_siblings(
+        &self,
+        id: i64,
+        parent_id: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_siblings(id, parent_id)
+    }
+
+    fn get_children(&self, id: i64) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_children(id)
+    }
+
+    fn get_blocking_tasks(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_blocking_tasks(id)
+    }
+
+    fn get_blocked_by_tasks(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_blocked_by_tasks(id)
+    }
+
+    fn get_descendants(
+        &self,
+        task_id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_descendants(task_id)
+    }
+
+    fn get_status(
+        &self,
+        task_id: i64,
+        with_events: bool,
+    ) -> impl std::future::Future<Output = Result<StatusResponse>> + Send {
+        self.get_status(task_id, with_events)
+    }
+
+    fn get_root_tasks(&self) -> impl std::future::Future<Output = Result<Vec<Task>>> + Send {
+        self.get_root_tasks()
+    }
+
+    fn find_tasks(
+        &self,
+        status: Option<&str>,
+        parent_id: Option<Option<i64>>,
+        sort_by: Option<TaskSortBy>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<PaginatedTasks>> + Send {
+        self.find_tasks(status, parent_id, sort_by, limit, offset)
+    }
+
+    fn add_task(
+        &self,
+        name: &str,
+        spec: Option<&str>,
+        parent_id: Option<i64>,
+        owner: Option<&str>,
+        priority: Option<i32>,
+        metadata: Option<&str>,
+    ) -> impl std::future::Future<Output = Result<Task>> + Send {
+        self.add_task(name, spec, parent_id, owner, priority, metadata)
+    }
+
+    fn update_task(
+        &self,
+        id: i64,
+        update: TaskUpdate<'_>,
+    ) -> impl std::future::Future<Output = Result<Task>> + Send {
+        self.update_task(id, update)
+    }
+
+    fn delete_task(&self, id: i64) -> impl std::future::Future<Output = Result<()>> + Send {
+        self.delete_task(id)
+    }
+
+    fn delete_task_cascade(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<usize>> + Send {
+        self.delete_task_cascade(id)
+    }
+
+    fn add_dependency(
+        &self,
+        blocking_id: i64,
+        blocked_id: i64,
+    ) -> impl std::future::Future<Output = Result<()>> + Send {
+        self.add_dependency(blocking_id, blocked_id)
+    }
+
+    fn remove_dependency(
+        &self,
+        blocking_id: i64,
+        blocked_id: i64,
+    ) -> impl std::future::Future<Output = Result<()>> + Send {
+        self.remove_dependency(blocking_id, blocked_id)
+    }
+
+    fn start_task(
+        &self,
+        id: i64,
+        with_events: bool,
+    ) -> impl std::future::Future<Output = Result<TaskWithEvents>> + Send {
+        self.start_task(id, with_events)
+    }
+
+    fn done_task(&self) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task()
+    }
+
+    fn done_task_by_id(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task_by_id(id)
+    }
+
+    fn pick_next(&self) -> impl std::future::Future<Output = Result<PickNextResponse>> + Send {
+        self.pick_next()
+    }
+
+    async fn spec_history(&self, _id: i64) -> Result<Vec<SpecRevision>> {
+        Err(IntentError::InvalidInput(
+            "Spec history is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn find_children(
+        &self,
+        _parent_id: Option<i64>,
+        _cursor: Option<i64>,
+        _limit: Option<i64>,
+    ) -> Result<ChildrenPage> {
+        Err(IntentError::InvalidInput(
+            "Paginated children are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn transitions(&self, _id: i64) -> Result<Vec<TaskTransition>> {
+        Err(IntentError::InvalidInput(
+            "Status history is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn done_task_with_outcome(
+        &self,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
+        if outcome.is_some() {
+            return Err(IntentError::InvalidInput(
+                "Completion outcomes are not supported by the Postgres backend".to_string(),
+            ));
+        }
+        self.done_task().await
+    }
+
+    async fn done_task_by_id_with_outcome(
+        &self,
+        id: i64,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
+        if outcome.is_some() {
+            return Err(IntentError::InvalidInput(
+                "Completion outcomes are not supported by the Postgres backend".to_string(),
+            ));
+        }
+        self.done_task_by_id(id).await
+    }
+
+    async fn claim_next_for(
+        &self,
+        _session_id: &str,
+        _lease: chrono::Duration,
+    ) -> Result<Option<Task>> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn renew_lease(
+        &self,
+        _task_id: i64,
+        _session_id: &str,
+        _lease: chrono::Duration,
+    ) -> Result<TaskLease> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn release_lease(&self, _task_id: i64, _session_id: &str) -> Result<bool> {
+        Err(IntentError::InvalidInput(
+            "Task leases are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn explain_next(&self) -> Result<PickNextResponse> {
+        Err(IntentError::InvalidInput(
+            "Explaining the next task is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn age_priorities(&self) -> Result<crate::priority::AgingResult> {
+        Err(IntentError::InvalidInput(
+            "Priority aging is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn sweep_stale(
+        &self,
+        _days: Option<f64>,
+        _demote: bool,
+    ) -> Result<crate::stale::StaleSweep> {
+        Err(IntentError::InvalidInput(
+            "Stale task detection is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn estimate_complexity(
+        &self,
+        _id: i64,
+        _apply: bool,
+    ) -> Result<Option<crate::priority::ComplexityEstimate>> {
+        Err(IntentError::InvalidInput(
+            "Complexity estimation is not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn checklist(&self, _task_id: i64) -> Result<Vec<ChecklistItem>> {
+        Err(IntentError::InvalidInput(
+            "Checklists are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn set_checklist_item(
+        &self,
+        _task_id: i64,
+        _position: i64,
+        _checked: bool,
+    ) -> Result<ChecklistItem> {
+        Err(IntentError::InvalidInput(
+            "Checklists are not supported by the Postgres backend".to_string(),
+        ))
+    }
+
+    async fn clone_subtree(
+        &self,
+        _id: i64,
+        _options: crate::tasks::CloneOptions,
+    ) -> Result<CloneSubtreeResponse> {
+        Err(IntentError::InvalidInput(
+            "Cloning tasks is not supported by the Postgres backend".to_string(),
+        ))
+    }
+}
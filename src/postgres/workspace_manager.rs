@@ -0,0 +1,128 @@
+use crate::db::models::Task;
+use crate::error::Result;
+use crate::workspace::CurrentTaskResponse;
+use sqlx::PgPool;
+
+use super::task_manager::TASK_COLUMNS;
+
+/// Workspace/session management backed by the Postgres `sessions` table.
+///
+/// Session ID resolution delegates to `crate::workspace::resolve_session_id`
+/// to ensure consistent behavior with the other backends.
+pub struct PostgresWorkspaceManager {
+    pool: PgPool,
+    project_id: String,
+}
+
+impl PostgresWorkspaceManager {
+    pub fn new(pool: PgPool, project_id: String) -> Self {
+        Self { pool, project_id }
+    }
+
+    /// Get the current focused task for a session.
+    pub async fn get_current_task(&self, session_id: Option<&str>) -> Result<CurrentTaskResponse> {
+        let session_id = crate::workspace::resolve_session_id(session_id);
+
+        let current_task_id: Option<i64> = sqlx::query_scalar(
+            "SELECT current_task_id FROM sessions WHERE project_id = $1 AND session_id = $2",
+        )
+        .bind(&self.project_id)
+        .bind(&session_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .flatten();
+
+        let task = match current_task_id {
+            Some(id) => {
+                sqlx::query_as::<_, Task>(&format!(
+                    "SELECT {TASK_COLUMNS} FROM tasks t WHERE t.project_id = $1 AND t.id = $2"
+                ))
+                .bind(&self.project_id)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?
+            },
+            None => None,
+        };
+
+        Ok(CurrentTaskResponse {
+            current_task_id,
+            task,
+            session_id: Some(session_id),
+        })
+    }
+
+    /// Set the current focused task for a session.
+    pub async fn set_current_task(
+        &self,
+        task_id: i64,
+        session_id: Option<&str>,
+    ) -> Result<CurrentTaskResponse> {
+        let session_id = crate::workspace::resolve_session_id(session_id);
+        set_session_focus(&self.pool, &self.project_id, &session_id, Some(task_id)).await?;
+        self.get_current_task(Some(&session_id)).await
+    }
+
+    /// Clear the current focused task for a session.
+    pub async fn clear_current_task(&self, session_id: Option<&str>) -> Result<()> {
+        let session_id = crate::workspace::resolve_session_id(session_id);
+
+        sqlx::query(
+            "UPDATE sessions SET current_task_id = NULL, last_active_at = now() \
+             WHERE project_id = $1 AND session_id = $2",
+        )
+        .bind(&self.project_id)
+        .bind(&session_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Create or update a session row with the given focus
+pub(crate) async fn set_session_focus<'e, E>(
+    executor: E,
+    project_id: &str,
+    session_id: &str,
+    task_id: Option<i64>,
+) -> Result<()>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query(
+        "INSERT INTO sessions (project_id, session_id, current_task_id) VALUES ($1, $2, $3) \
+         ON CONFLICT (project_id, session_id) \
+         DO UPDATE SET current_task_id = EXCLUDED.current_task_id, last_active_at = now()",
+    )
+    .bind(project_id)
+    .bind(session_id)
+    .bind(task_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+impl crate::backend::WorkspaceBackend for PostgresWorkspaceManager {
+    fn get_current_task(
+        &self,
+        session_id: Option<&str>,
+    ) -> impl std::future::Future<Output = crate::error::Result<CurrentTaskResponse>> + Send {
+        self.get_current_task(session_id)
+    }
+
+    fn set_current_task(
+        &self,
+        task_id: i64,
+        session_id: Option<&str>,
+    ) -> impl std::future::Future<Output = crate::error::Result<CurrentTaskResponse>> + Send {
+        self.set_current_task(task_id, session_id)
+    }
+
+    fn clear_current_task(
+        &self,
+        session_id: Option<&str>,
+    ) -> impl std::future::Future<Output = crate::error::Result<()>> + Send {
+        self.clear_current_task(session_id)
+    }
+}
@@ -1,4 +1,8 @@
 use crate::error::{IntentError, Result};
+use crate::settings::SettingsManager;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
 use std::str::FromStr;
 
 /// Priority levels mapped to integers for storage and sorting
@@ -55,6 +59,306 @@ impl PriorityLevel {
     }
 }
 
+// ============================================================================
+// Priority aging
+// ============================================================================
+//
+// A low-priority task created months ago would never be picked while newer,
+// higher-priority work keeps arriving. `age_tasks` gives every todo task a
+// `priority_boost` that grows with the time it has waited, and queries that
+// rank work order by the effective priority (the stored priority minus the
+// boost) instead of the raw one.
+
+/// Effective priority of a `tasks` row, as a SQL expression
+///
+/// Unset priorities rank after low; only todo tasks keep their boost, and
+/// nothing ages past critical.
+pub const EFFECTIVE_PRIORITY_SQL: &str = "MAX(1.0, COALESCE(priority, 5) - CASE WHEN status = 'todo' THEN COALESCE(priority_boost, 0.0) ELSE 0.0 END)";
+
+/// How the boost grows with the time a task has waited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgingCurve {
+    /// No aging
+    Off,
+    /// One level per `days_per_level`
+    Linear,
+    /// Fast at first, then slower: log2(1 + age / days_per_level)
+    Log,
+}
+
+impl FromStr for AgingCurve {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "linear" => Ok(Self::Linear),
+            "log" => Ok(Self::Log),
+            _ => Err(IntentError::InvalidInput(format!(
+                "Invalid aging curve '{}'. Valid values: linear, log, off",
+                s
+            ))),
+        }
+    }
+}
+
+/// Aging configuration, read from the `priority.aging.*` settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AgingPolicy {
+    pub curve: AgingCurve,
+    /// Days of waiting worth one priority level
+    pub days_per_level: f64,
+    /// Most levels a task can gain
+    pub max_boost: f64,
+}
+
+impl Default for AgingPolicy {
+    fn default() -> Self {
+        Self {
+            curve: AgingCurve::Linear,
+            days_per_level: 30.0,
+            max_boost: 2.0,
+        }
+    }
+}
+
+impl AgingPolicy {
+    /// Load the policy from project settings
+    ///
+    /// An unknown curve name disables aging rather than failing the
+    /// commands that rank tasks.
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let settings = SettingsManager::new(pool);
+        let curve = match settings.get_text("priority.aging.curve").await? {
+            Some(name) => name.parse().unwrap_or_else(|e| {
+                tracing::warn!("{}; priority aging is off", e);
+                AgingCurve::Off
+            }),
+            None => AgingCurve::Linear,
+        };
+        Ok(Self {
+            curve,
+            days_per_level: settings.get_number("priority.aging.days").await?,
+            max_boost: settings.get_number("priority.aging.max_boost").await?,
+        })
+    }
+
+    /// Priority levels gained after waiting `age_days`
+    pub fn boost(&self, age_days: f64) -> f64 {
+        if age_days <= 0.0 || self.days_per_level <= 0.0 {
+            return 0.0;
+        }
+        let levels = age_days / self.days_per_level;
+        let boost = match self.curve {
+            AgingCurve::Off => 0.0,
+            AgingCurve::Linear => levels,
+            AgingCurve::Log => (1.0 + levels).log2(),
+        };
+        // Stored with two decimals so repeated runs do not rewrite every row
+        (boost.min(self.max_boost) * 100.0).round() / 100.0
+    }
+}
+
+/// Outcome of `age_tasks`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AgingResult {
+    pub policy: AgingPolicy,
+    /// Todo tasks whose boost changed
+    pub updated: usize,
+    /// Todo tasks with a non-zero boost after the run
+    pub boosted: usize,
+}
+
+/// Recompute the priority boost of every todo task with the project's policy
+pub async fn age_tasks(pool: &SqlitePool) -> Result<AgingResult> {
+    let policy = AgingPolicy::load(pool).await?;
+    age_tasks_with(pool, policy, Utc::now()).await
+}
+
+/// Recompute priority boosts as of `now`
+///
+/// The age of a task is the time since it was first made todo. Boosts of
+/// tasks that left todo are cleared.
+pub async fn age_tasks_with(
+    pool: &SqlitePool,
+    policy: AgingPolicy,
+    now: DateTime<Utc>,
+) -> Result<AgingResult> {
+    let rows: Vec<(i64, Option<DateTime<Utc>>, Option<f64>)> =
+        sqlx::query_as("SELECT id, first_todo_at, priority_boost FROM tasks WHERE status = 'todo'")
+            .fetch_all(pool)
+            .await?;
+
+    let mut tx = pool.begin().await?;
+    let mut updated = 0;
+    let mut boosted = 0;
+    for (id, first_todo_at, stored) in rows {
+        let age_days = first_todo_at
+            .map(|at| (now - at).num_seconds() as f64 / 86_400.0)
+            .unwrap_or(0.0);
+        let boost = Some(policy.boost(age_days)).filter(|b| *b > 0.0);
+        if boost.is_some() {
+            boosted += 1;
+        }
+        if boost != stored {
+            sqlx::query("UPDATE tasks SET priority_boost = ? WHERE id = ?")
+                .bind(boost)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            updated += 1;
+        }
+    }
+    sqlx::query(
+        "UPDATE tasks SET priority_boost = NULL WHERE status != 'todo' AND priority_boost IS NOT NULL",
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(AgingResult {
+        policy,
+        updated,
+        boosted,
+    })
+}
+
+/// Completed tasks an estimate is based on, at most
+const ESTIMATE_SAMPLES: usize = 5;
+/// Similarity below which a completed task says nothing about another
+const ESTIMATE_MIN_SIMILARITY: f64 = 0.2;
+/// Estimated complexity from which `ie plan` suggests splitting a new task
+pub const HIGH_COMPLEXITY: i32 = 8;
+
+/// Suggested complexity of a task, from `estimate_complexity`
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexityEstimate {
+    pub task_id: i64,
+    /// 1 (trivial) to 10
+    pub complexity: i32,
+    /// 0 to 1: mean similarity of the samples, lowered when there are
+    /// fewer than three
+    pub confidence: f64,
+    /// Completed tasks the estimate is based on, most similar first
+    pub based_on: Vec<ComplexitySample>,
+}
+
+/// A completed task an estimate is based on
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexitySample {
+    pub task_id: i64,
+    pub name: String,
+    pub similarity: f64,
+    /// The task's recorded complexity, else one derived from its cycle time
+    pub complexity: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_minutes: Option<i64>,
+}
+
+/// Complexity of a task that took `minutes` from start to done: 1 up to
+/// half an hour, one more for every doubling, at most 10
+pub fn complexity_from_cycle_minutes(minutes: i64) -> i32 {
+    let doublings = (minutes.max(1) as f64 / 30.0).log2().max(0.0);
+    (1.0 + doublings).round().clamp(1.0, 10.0) as i32
+}
+
+/// Completed tasks with a known complexity or cycle time, loaded once to
+/// estimate many tasks
+pub struct ComplexityHistory {
+    samples: Vec<(ComplexitySample, crate::similarity::TermVector)>,
+}
+
+type HistoryRow = (
+    i64,
+    String,
+    Option<String>,
+    Option<i32>,
+    Option<DateTime<Utc>>,
+    Option<DateTime<Utc>>,
+);
+
+impl ComplexityHistory {
+    /// Done tasks visible to the process-wide `--scope`
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let rows: Vec<HistoryRow> = sqlx::query_as(&format!(
+            "SELECT id, name, spec, complexity, first_doing_at, first_done_at \
+             FROM tasks WHERE status = 'done' AND {}",
+            crate::project::task_scope().sql_filter()
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        let samples = rows
+            .into_iter()
+            .filter_map(|(id, name, spec, complexity, doing_at, done_at)| {
+                let cycle_minutes = doing_at
+                    .zip(done_at)
+                    .map(|(start, end)| (end - start).num_minutes().max(0));
+                let complexity = complexity.or(cycle_minutes.map(complexity_from_cycle_minutes))?;
+                let vector = crate::similarity::TermVector::for_task(&name, spec.as_deref());
+                let sample = ComplexitySample {
+                    task_id: id,
+                    name,
+                    similarity: 0.0,
+                    complexity: complexity.clamp(1, 10),
+                    cycle_minutes,
+                };
+                Some((sample, vector))
+            })
+            .collect();
+        Ok(Self { samples })
+    }
+
+    /// Similarity-weighted complexity of the completed tasks most like
+    /// `task`, or None when none is similar enough
+    pub fn estimate(&self, task: &crate::db::models::Task) -> Option<ComplexityEstimate> {
+        let vector = crate::similarity::TermVector::for_task(&task.name, task.spec.as_deref());
+        let mut based_on: Vec<ComplexitySample> = self
+            .samples
+            .iter()
+            .filter(|(sample, _)| sample.task_id != task.id)
+            .map(|(sample, other)| ComplexitySample {
+                similarity: (vector.cosine(other) * 100.0).round() / 100.0,
+                ..sample.clone()
+            })
+            .filter(|sample| sample.similarity >= ESTIMATE_MIN_SIMILARITY)
+            .collect();
+        if based_on.is_empty() {
+            return None;
+        }
+        based_on.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then(a.task_id.cmp(&b.task_id))
+        });
+        based_on.truncate(ESTIMATE_SAMPLES);
+
+        let weight: f64 = based_on.iter().map(|s| s.similarity).sum();
+        let weighted: f64 = based_on
+            .iter()
+            .map(|s| s.similarity * f64::from(s.complexity))
+            .sum();
+        let coverage = (based_on.len() as f64 / 3.0).min(1.0);
+        let confidence = weight / based_on.len() as f64 * coverage;
+        Some(ComplexityEstimate {
+            task_id: task.id,
+            complexity: (weighted / weight).round().clamp(1.0, 10.0) as i32,
+            confidence: (confidence * 100.0).round() / 100.0,
+            based_on,
+        })
+    }
+}
+
+/// Suggest a complexity for `task` from similar completed tasks: their
+/// recorded complexity, or their cycle time when none was recorded
+pub async fn estimate_complexity(
+    pool: &SqlitePool,
+    task: &crate::db::models::Task,
+) -> Result<Option<ComplexityEstimate>> {
+    Ok(ComplexityHistory::load(pool).await?.estimate(task))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +406,86 @@ mod tests {
         assert_eq!(PriorityLevel::to_str(999), "unknown");
     }
 
+    #[test]
+    fn test_aging_curves() {
+        let linear = AgingPolicy::default();
+        assert_eq!(linear.boost(0.0), 0.0);
+        assert_eq!(linear.boost(15.0), 0.5);
+        assert_eq!(linear.boost(365.0), 2.0); // capped
+
+        let log = AgingPolicy {
+            curve: AgingCurve::Log,
+            ..linear
+        };
+        assert_eq!(log.boost(30.0), 1.0);
+        assert_eq!(log.boost(90.0), 2.0);
+
+        let off = AgingPolicy {
+            curve: AgingCurve::Off,
+            ..linear
+        };
+        assert_eq!(off.boost(365.0), 0.0);
+        assert!("sideways".parse::<AgingCurve>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_age_tasks_surfaces_old_low_priority_work() {
+        use crate::tasks::TaskManager;
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let old = tasks
+            .add_task("Old chore", None, None, None, Some(4), None)
+            .await
+            .unwrap();
+        let fresh = tasks
+            .add_task("Fresh work", None, None, None, Some(3), None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET first_todo_at = ? WHERE id = ?")
+            .bind(Utc::now() - chrono::Duration::days(90))
+            .bind(old.id)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let by_priority = || async {
+            tasks
+                .find_tasks(
+                    None,
+                    None,
+                    Some(crate::db::models::TaskSortBy::Priority),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap()
+                .tasks[0]
+                .id
+        };
+        assert_eq!(by_priority().await, fresh.id);
+
+        let result = age_tasks(pool).await.unwrap();
+        assert_eq!((result.updated, result.boosted), (1, 1));
+        assert_eq!(by_priority().await, old.id);
+        // pick_next ages on its own; nothing changed, so nothing is rewritten
+        assert_eq!(tasks.pick_next().await.unwrap().task.unwrap().id, old.id);
+        assert_eq!(age_tasks(pool).await.unwrap().updated, 0);
+
+        // Boosts only apply while a task waits in todo
+        tasks.start_task(old.id, false).await.unwrap();
+        age_tasks(pool).await.unwrap();
+        let boost: Option<f64> =
+            sqlx::query_scalar("SELECT priority_boost FROM tasks WHERE id = ?")
+                .bind(old.id)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        assert_eq!(boost, None);
+    }
+
     #[test]
     fn test_parse_optional() {
         assert_eq!(
@@ -111,4 +495,62 @@ mod tests {
         assert_eq!(PriorityLevel::parse_optional(None).unwrap(), None);
         assert!(PriorityLevel::parse_optional(Some("invalid")).is_err());
     }
+
+    #[test]
+    fn test_complexity_from_cycle_minutes() {
+        assert_eq!(complexity_from_cycle_minutes(0), 1);
+        assert_eq!(complexity_from_cycle_minutes(30), 1);
+        assert_eq!(complexity_from_cycle_minutes(60), 2);
+        assert_eq!(complexity_from_cycle_minutes(8 * 60), 5);
+        assert_eq!(complexity_from_cycle_minutes(100_000), 10);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_complexity() {
+        let ctx = crate::test_utils::test_helpers::TestContext::new().await;
+        let done = |name: &'static str, complexity: Option<i32>, minutes: i64| {
+            let pool = ctx.pool().clone();
+            async move {
+                let start = Utc::now() - chrono::Duration::minutes(minutes);
+                sqlx::query(
+                    "INSERT INTO tasks (name, status, complexity, first_doing_at, first_done_at) \
+                     VALUES (?, 'done', ?, ?, ?)",
+                )
+                .bind(name)
+                .bind(complexity)
+                .bind(start)
+                .bind(Utc::now())
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+        };
+        done("Payment webhook handler", Some(8), 10).await;
+        // No complexity recorded: two days of work count as 8
+        done("Refund webhook handler", None, 48 * 60).await;
+        done("Fix typo in footer", Some(1), 5).await;
+
+        let task_mgr = crate::tasks::TaskManager::new(ctx.pool());
+        let task = task_mgr
+            .add_task("Invoice webhook handler", None, None, None, None, None)
+            .await
+            .unwrap();
+        let estimate = estimate_complexity(ctx.pool(), &task)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(estimate.complexity, 8);
+        assert_eq!(estimate.based_on.len(), 2);
+        assert_eq!(estimate.based_on[1].cycle_minutes, Some(48 * 60));
+        assert!(estimate.confidence > 0.0 && estimate.confidence < 1.0);
+
+        let unrelated = task_mgr
+            .add_task("Dark mode", None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(estimate_complexity(ctx.pool(), &unrelated)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
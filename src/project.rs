@@ -1,9 +1,10 @@
-use crate::db::{create_pool, run_migrations};
+use crate::db::{create_pool, create_readonly_pool, run_migrations, SCHEMA_VERSION};
 use crate::error::{IntentError, Result};
 use crate::global_projects;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 const INTENT_DIR: &str = ".intent-engine";
 const DB_FILE: &str = "project.db";
@@ -21,6 +22,41 @@ const PROJECT_ROOT_MARKERS: &[&str] = &[
     "build.gradle",   // Gradle (Java/Kotlin)
 ];
 
+/// Set by `ie --read-only`; see `set_read_only`
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Make every later `load` and `load_or_init` open the database read-only
+///
+/// Used by the global `--read-only` flag: any write the command attempts
+/// fails instead of changing the database, and nothing is initialized,
+/// migrated or registered on the way.
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Set by `ie --scope private`; see `set_task_scope`
+static PRIVATE_SCOPE: AtomicBool = AtomicBool::new(false);
+
+/// Let every later `TaskManager` (and search, reports, exports) see private tasks
+///
+/// Used by the global `--scope` flag. Without it private tasks are left out,
+/// so agents only get them when they are explicitly asked for.
+pub fn set_task_scope(scope: crate::db::models::TaskVisibility) {
+    PRIVATE_SCOPE.store(!scope.is_shared(), Ordering::Relaxed);
+}
+
+pub fn task_scope() -> crate::db::models::TaskVisibility {
+    if PRIVATE_SCOPE.load(Ordering::Relaxed) {
+        crate::db::models::TaskVisibility::Private
+    } else {
+        crate::db::models::TaskVisibility::Shared
+    }
+}
+
 #[derive(Debug)]
 pub struct ProjectContext {
     pub root: PathBuf,
@@ -150,6 +186,11 @@ impl ProjectContext {
     /// **Important**: This function now respects project boundaries to prevent
     /// nested projects from accidentally using parent project databases.
     pub fn find_project_root() -> Option<PathBuf> {
+        Self::search_project_root(true)
+    }
+
+    /// [`Self::find_project_root`], printing where the project was found if `announce`
+    fn search_project_root(announce: bool) -> Option<PathBuf> {
         // Strategy 1: Search upwards from current directory
         // BUT respect project boundaries (don't cross into parent projects)
         // UNLESS we're not inside any project (to support MCP server startup)
@@ -179,7 +220,7 @@ impl ProjectContext {
                         }
                     }
 
-                    if current != start_dir {
+                    if announce && current != start_dir {
                         eprintln!("✓ Found project: {}", current.display());
                     }
                     return Some(current);
@@ -191,7 +232,9 @@ impl ProjectContext {
                     if current == *boundary {
                         // We've reached the boundary without finding .intent-engine
                         // Return the project root to enable lazy initialization
-                        eprintln!("✓ Detected project root: {}", boundary.display());
+                        if announce {
+                            eprintln!("✓ Detected project root: {}", boundary.display());
+                        }
                         return Some(boundary.clone());
                     }
                 }
@@ -208,7 +251,9 @@ impl ProjectContext {
             let home_path = PathBuf::from(home);
             let intent_dir = home_path.join(INTENT_DIR);
             if intent_dir.exists() && intent_dir.is_dir() {
-                eprintln!("✓ Using home project: {}", home_path.display());
+                if announce {
+                    eprintln!("✓ Using home project: {}", home_path.display());
+                }
                 return Some(home_path);
             }
         }
@@ -219,7 +264,9 @@ impl ProjectContext {
             let home_path = PathBuf::from(userprofile);
             let intent_dir = home_path.join(INTENT_DIR);
             if intent_dir.exists() && intent_dir.is_dir() {
-                eprintln!("✓ Using home project: {}", home_path.display());
+                if announce {
+                    eprintln!("✓ Using home project: {}", home_path.display());
+                }
                 return Some(home_path);
             }
         }
@@ -267,7 +314,7 @@ impl ProjectContext {
     /// 2. Check each directory for project markers (in priority order)
     /// 3. Return the first directory that contains any marker
     /// 4. If no marker found, return None (fallback to CWD handled by caller)
-    fn infer_project_root() -> Option<PathBuf> {
+    pub(crate) fn infer_project_root() -> Option<PathBuf> {
         let cwd = std::env::current_dir().ok()?;
         Self::infer_project_root_from(&cwd)
     }
@@ -379,16 +426,10 @@ impl ProjectContext {
 
     /// Load an existing project context
     pub async fn load() -> Result<Self> {
-        let root = Self::find_project_root().ok_or(IntentError::NotAProject)?;
-        let intent_dir = root.join(INTENT_DIR);
-
-        // Check if .intent-engine directory exists
-        // If not, this is not an initialized project yet
-        if !intent_dir.exists() || !intent_dir.is_dir() {
-            return Err(IntentError::NotAProject);
+        if is_read_only() {
+            return Self::load_read_only().await;
         }
-
-        let db_path = intent_dir.join(DB_FILE);
+        let (root, db_path) = Self::locate_database()?;
 
         let pool = create_pool(&db_path).await?;
 
@@ -403,8 +444,101 @@ impl ProjectContext {
         })
     }
 
+    /// Load project context with a read-only pool (for reports and search)
+    ///
+    /// Heavy reads then never hold a write lock. An outdated schema is
+    /// migrated first through a normal pool, unless `ie --read-only` forbids
+    /// it.
+    pub async fn load_read_only() -> Result<Self> {
+        let (root, db_path) = Self::locate_database()?;
+
+        if !db_path.exists() || !Self::schema_is_current(&db_path).await? {
+            if is_read_only() {
+                return Err(IntentError::InvalidInput(
+                    "The database needs a migration; run any command without --read-only first"
+                        .to_string(),
+                ));
+            }
+            let pool = create_pool(&db_path).await?;
+            run_migrations(&pool).await?;
+            pool.close().await;
+        }
+
+        let pool = create_readonly_pool(&db_path).await?;
+        Ok(ProjectContext {
+            root,
+            db_path,
+            pool,
+        })
+    }
+
+    /// Storage backend selected by the current project's config
+    ///
+    /// Outside an initialized project this is SQLite, which `ie` then
+    /// initializes as usual.
+    pub fn configured_storage() -> Result<crate::config::StorageBackend> {
+        match Self::search_project_root(false) {
+            Some(root) if root.join(INTENT_DIR).is_dir() => {
+                Ok(crate::config::ProjectConfig::for_project_root(&root)?
+                    .storage
+                    .backend)
+            },
+            _ => Ok(crate::config::StorageBackend::Sqlite),
+        }
+    }
+
+    /// `[ui] locale` of the project around the current directory, if any
+    ///
+    /// A config the parser rejects counts as unset here: the commands that
+    /// read the rest of it report the error, and `ie config` must still run
+    /// to fix it.
+    pub fn configured_locale() -> Option<crate::i18n::Locale> {
+        let root = Self::search_project_root(false)?;
+        crate::config::ProjectConfig::for_project_root(&root)
+            .ok()?
+            .ui
+            .locale
+    }
+
+    /// Hooks of the project around the current directory (none outside one)
+    pub fn configured_hooks() -> Result<crate::hooks::Hooks> {
+        match Self::search_project_root(false) {
+            Some(root) => crate::hooks::Hooks::for_project_root(&root),
+            None => Ok(crate::hooks::Hooks::new(PathBuf::new(), Default::default())),
+        }
+    }
+
+    /// Project root and database path of an initialized project
+    pub(crate) fn locate_database() -> Result<(PathBuf, PathBuf)> {
+        let root = Self::find_project_root().ok_or(IntentError::NotAProject)?;
+        let intent_dir = root.join(INTENT_DIR);
+
+        // Check if .intent-engine directory exists
+        // If not, this is not an initialized project yet
+        if !intent_dir.exists() || !intent_dir.is_dir() {
+            return Err(IntentError::NotAProject);
+        }
+
+        let db_path = intent_dir.join(DB_FILE);
+        Ok((root, db_path))
+    }
+
+    async fn schema_is_current(db_path: &Path) -> Result<bool> {
+        let pool = create_readonly_pool(db_path).await?;
+        let version: Option<String> =
+            sqlx::query_scalar("SELECT value FROM workspace_state WHERE key = 'schema_version'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap_or(None);
+        pool.close().await;
+        Ok(version.as_deref() == Some(SCHEMA_VERSION))
+    }
+
     /// Load project context, initializing if necessary (for write commands)
     pub async fn load_or_init() -> Result<Self> {
+        if is_read_only() {
+            return Self::load_read_only().await;
+        }
         let ctx = match Self::load().await {
             Ok(ctx) => ctx,
             Err(IntentError::NotAProject) => Self::initialize_project().await?,
@@ -416,6 +550,48 @@ impl ProjectContext {
 
         Ok(ctx)
     }
+
+    /// Project root as the string notifications are tagged with
+    pub fn project_path(&self) -> String {
+        self.root.to_string_lossy().to_string()
+    }
+}
+
+impl crate::backend::Backend for ProjectContext {
+    type Tasks<'a> = crate::tasks::TaskManager<'a>;
+    type Workspace<'a> = crate::workspace::WorkspaceManager<'a>;
+    type Events<'a> = crate::events::EventManager<'a>;
+    type Plans<'a> = crate::plan::PlanExecutor<'a>;
+    type Search<'a> = crate::search::SearchManager<'a>;
+
+    fn name(&self) -> &'static str {
+        crate::config::StorageBackend::Sqlite.as_str()
+    }
+
+    fn tasks(&self) -> Self::Tasks<'_> {
+        crate::tasks::TaskManager::with_project_path(&self.pool, self.project_path())
+    }
+
+    fn workspace(&self) -> Self::Workspace<'_> {
+        crate::workspace::WorkspaceManager::new(&self.pool)
+    }
+
+    fn events(&self) -> Self::Events<'_> {
+        crate::events::EventManager::with_project_path(&self.pool, self.project_path())
+    }
+
+    fn plans(&self, default_parent: Option<i64>) -> Self::Plans<'_> {
+        let executor =
+            crate::plan::PlanExecutor::with_project_path(&self.pool, self.project_path());
+        match default_parent {
+            Some(parent_id) => executor.with_default_parent(parent_id),
+            None => executor,
+        }
+    }
+
+    fn search(&self) -> Self::Search<'_> {
+        crate::search::SearchManager::new(&self.pool)
+    }
 }
 
 #[cfg(test)]
@@ -1,15 +1,54 @@
-use crate::db::models::{DateRange, Event, Report, ReportSummary, StatusBreakdown, Task};
-use crate::error::Result;
-use chrono::Utc;
+use crate::db::models::{
+    AgingTask, AtRiskTask, BlockedTask, CompletedTask, CompletionOutcome, CycleTimePercentile,
+    CycleTimeReport, DateRange, EstimateRollup, Event, InProgressTask, Milestone, MilestoneReport,
+    PrReport, Report, ReportSummary, ReportedOutcome, StandupBlocker, StandupReport,
+    StatusBreakdown, Task, TaskCycleTime, TaskVisibility,
+};
+use crate::error::{IntentError, Result};
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
+/// Percentiles `ie report cycle-time --percentiles` reports
+pub const CYCLE_TIME_PERCENTILES: &[u8] = &[50, 75, 90, 95];
+
 pub struct ReportManager<'a> {
     pool: &'a SqlitePool,
+    /// Whether private tasks are reported on
+    scope: TaskVisibility,
+    /// Event types the report covers; empty means all
+    event_types: Vec<String>,
 }
 
 impl<'a> ReportManager<'a> {
     pub fn new(pool: &'a SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            scope: crate::project::task_scope(),
+            event_types: Vec::new(),
+        }
+    }
+
+    /// Use an explicit task scope instead of the process-wide `--scope`
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Only report events of these types
+    pub fn with_event_types(mut self, event_types: Vec<String>) -> Self {
+        self.event_types = event_types;
+        self
+    }
+
+    /// `AND log_type IN (?, ...)` for the event type filter, or nothing
+    fn event_type_filter(&self) -> String {
+        if self.event_types.is_empty() {
+            return String::new();
+        }
+        format!(
+            " AND log_type IN ({})",
+            vec!["?"; self.event_types.len()].join(", ")
+        )
     }
 
     /// Generate a report with optional filters
@@ -25,7 +64,7 @@ impl<'a> ReportManager<'a> {
         let since_datetime = since.and_then(|s| crate::time_utils::parse_duration(&s).ok());
 
         // Build task query
-        let mut task_query = String::from("SELECT id FROM tasks WHERE 1=1");
+        let mut task_query = format!("SELECT id FROM tasks WHERE {}", self.scope.sql_filter());
         let mut task_conditions = Vec::new();
 
         if let Some(ref status) = status {
@@ -51,7 +90,7 @@ impl<'a> ReportManager<'a> {
                 " AND id IN ({})",
                 task_ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ")
             ));
-            let full_query = task_query.replace("SELECT id", "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata");
+            let full_query = task_query.replace("SELECT id", "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason");
             let mut q = sqlx::query_as::<_, Task>(&full_query);
             for cond in &task_conditions {
                 q = q.bind(cond);
@@ -61,7 +100,7 @@ impl<'a> ReportManager<'a> {
             }
             q.fetch_all(self.pool).await?
         } else if filter_name.is_none() && filter_spec.is_none() {
-            let full_query = task_query.replace("SELECT id", "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata");
+            let full_query = task_query.replace("SELECT id", "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason");
             let mut q = sqlx::query_as::<_, Task>(&full_query);
             for cond in &task_conditions {
                 q = q.bind(cond);
@@ -74,14 +113,21 @@ impl<'a> ReportManager<'a> {
         // Count tasks by status from filtered results
         let todo_count = tasks.iter().filter(|t| t.status == "todo").count() as i64;
         let doing_count = tasks.iter().filter(|t| t.status == "doing").count() as i64;
+        let review_count = tasks.iter().filter(|t| t.status == "review").count() as i64;
         let done_count = tasks.iter().filter(|t| t.status == "done").count() as i64;
 
         let total_tasks = tasks.len() as i64;
 
         // Get events
         let events = if !summary_only {
-            let mut event_query = String::from(crate::sql_constants::SELECT_EVENT_BASE);
-            let mut event_conditions = Vec::new();
+            // Digests only cover shared history (see EventVisibility)
+            let mut event_query = format!(
+                "{} AND visibility = 'normal' AND {}{}",
+                crate::sql_constants::SELECT_EVENT_BASE,
+                self.scope.sql_task_filter(),
+                self.event_type_filter()
+            );
+            let mut event_conditions = self.event_types.clone();
 
             if let Some(ref dt) = since_datetime {
                 event_query.push_str(" AND timestamp >= ?");
@@ -103,9 +149,22 @@ impl<'a> ReportManager<'a> {
         let total_events = if let Some(ref evts) = events {
             evts.len() as i64
         } else {
-            sqlx::query_scalar::<_, i64>(crate::sql_constants::COUNT_EVENTS_TOTAL)
-                .fetch_one(self.pool)
-                .await?
+            let count_sql = format!(
+                "SELECT COUNT(*) FROM events WHERE visibility = 'normal' AND {}{}",
+                self.scope.sql_task_filter(),
+                self.event_type_filter()
+            );
+            let mut q = sqlx::query_scalar::<_, i64>(&count_sql);
+            for event_type in &self.event_types {
+                q = q.bind(event_type);
+            }
+            q.fetch_one(self.pool).await?
+        };
+
+        let outcomes = if summary_only {
+            Vec::new()
+        } else {
+            self.outcomes_of(&tasks, since_datetime).await?
         };
 
         let date_range = since_datetime.map(|from| DateRange {
@@ -119,16 +178,501 @@ impl<'a> ReportManager<'a> {
                 tasks_by_status: StatusBreakdown {
                     todo: todo_count,
                     doing: doing_count,
+                    review: review_count,
                     done: done_count,
                 },
                 total_events,
                 date_range,
+                estimate: EstimateRollup::from_tasks(&tasks),
             },
             tasks: if summary_only { None } else { Some(tasks) },
             events,
+            outcomes,
+        })
+    }
+
+    /// Outcomes recorded for `tasks` (since `since`), newest first
+    async fn outcomes_of(
+        &self,
+        tasks: &[Task],
+        since: Option<chrono::DateTime<Utc>>,
+    ) -> Result<Vec<ReportedOutcome>> {
+        // Scope is already applied: only outcomes of the reported tasks are kept
+        let mut query = String::from(
+            r#"
+            SELECT e.task_id, t.name, e.timestamp, e.discussion_data
+            FROM events e
+            JOIN tasks t ON t.id = e.task_id
+            WHERE e.log_type = ? AND e.visibility = 'normal'
+            "#,
+        );
+        if since.is_some() {
+            query.push_str(" AND e.timestamp >= ?");
+        }
+        query.push_str(" ORDER BY e.timestamp DESC, e.id DESC");
+
+        let mut q = sqlx::query_as::<_, (i64, String, chrono::DateTime<Utc>, String)>(&query)
+            .bind(CompletionOutcome::LOG_TYPE);
+        if let Some(dt) = since {
+            q = q.bind(dt.to_rfc3339());
+        }
+        let rows = q.fetch_all(self.pool).await?;
+
+        let reported: std::collections::HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+        Ok(rows
+            .into_iter()
+            .filter(|(task_id, ..)| reported.contains(task_id))
+            .map(|(task_id, task_name, recorded_at, text)| ReportedOutcome {
+                task_id,
+                task_name,
+                recorded_at,
+                outcome: CompletionOutcome::from_event_text(&text),
+            })
+            .collect())
+    }
+
+    /// Progress of a milestone: completion, blocking chains and at-risk tasks
+    ///
+    /// A task is at risk when it is not done and either waits on incomplete
+    /// work outside the milestone, or is still todo without a spec.
+    pub async fn milestone_report(&self, name: &str) -> Result<MilestoneReport> {
+        let milestone = sqlx::query_as::<_, Milestone>(
+            "SELECT id, name, created_at FROM milestones WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or_else(|| IntentError::InvalidInput(format!("Milestone '{}' not found", name)))?;
+
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {} FROM tasks t JOIN milestone_tasks m ON m.task_id = t.id \
+             WHERE m.milestone_id = ? AND {} ORDER BY t.id",
+            crate::sql_constants::TASK_COLUMNS_PREFIXED,
+            self.scope.sql_filter()
+        ))
+        .bind(milestone.id)
+        .fetch_all(self.pool)
+        .await?;
+
+        let total_tasks = tasks.len() as i64;
+        let done_tasks = tasks.iter().filter(|t| t.status == "done").count() as i64;
+        let completion_percent = if total_tasks == 0 {
+            0.0
+        } else {
+            done_tasks as f64 * 100.0 / total_tasks as f64
+        };
+
+        let member_ids: std::collections::HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+        let mut blocked = Vec::new();
+        let mut at_risk = Vec::new();
+        for task in tasks.iter().filter(|t| t.status != "done") {
+            let blocking_chain = self.incomplete_blockers(task.id).await?;
+
+            let mut reasons = Vec::new();
+            let outside = blocking_chain
+                .iter()
+                .filter(|b| !member_ids.contains(&b.id))
+                .count();
+            if outside > 0 {
+                reasons.push(format!(
+                    "blocked by {} incomplete task(s) outside the milestone",
+                    outside
+                ));
+            }
+            if task.status == "todo" && task.spec.as_deref().is_none_or(|s| s.trim().is_empty()) {
+                reasons.push("not started and has no spec".to_string());
+            }
+            if !reasons.is_empty() {
+                at_risk.push(AtRiskTask {
+                    task: task.clone(),
+                    reasons,
+                });
+            }
+
+            if !blocking_chain.is_empty() {
+                blocked.push(BlockedTask {
+                    task: task.clone(),
+                    blocking_chain,
+                });
+            }
+        }
+
+        Ok(MilestoneReport {
+            milestone,
+            total_tasks,
+            done_tasks,
+            completion_percent,
+            estimate: EstimateRollup::from_tasks(&tasks),
+            tasks,
+            blocked,
+            at_risk,
         })
     }
 
+    /// Tasks in 'doing', longest running first
+    pub async fn aging_report(&self) -> Result<Vec<AgingTask>> {
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {} FROM tasks WHERE status = 'doing' AND {} \
+             ORDER BY first_doing_at IS NULL, first_doing_at, id",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter()
+        ))
+        .fetch_all(self.pool)
+        .await?;
+
+        let now = Utc::now();
+        Ok(tasks
+            .into_iter()
+            .map(|task| AgingTask {
+                age_minutes: task
+                    .first_doing_at
+                    .map_or(0, |started| (now - started).num_minutes()),
+                task,
+            })
+            .collect())
+    }
+
+    /// Time from first start to first completion of done tasks
+    ///
+    /// `since` (e.g. "7d") limits it to tasks completed in that window. Tasks
+    /// that were never started are left out.
+    pub async fn cycle_time_report(
+        &self,
+        since: Option<&str>,
+        percentiles: bool,
+    ) -> Result<CycleTimeReport> {
+        let since = since.map(crate::time_utils::parse_duration).transpose()?;
+        let mut query = format!(
+            "SELECT {} FROM tasks WHERE status = 'done' AND first_doing_at IS NOT NULL \
+             AND first_done_at IS NOT NULL AND {}",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter()
+        );
+        if since.is_some() {
+            query.push_str(" AND first_done_at >= ?");
+        }
+        query.push_str(" ORDER BY first_done_at DESC, id");
+        let mut q = sqlx::query_as::<_, Task>(&query);
+        if let Some(since) = since {
+            q = q.bind(since.to_rfc3339());
+        }
+        let mut tasks: Vec<TaskCycleTime> = q
+            .fetch_all(self.pool)
+            .await?
+            .into_iter()
+            .filter_map(|task| {
+                let cycle = task.first_done_at? - task.first_doing_at?;
+                Some(TaskCycleTime {
+                    cycle_minutes: cycle.num_minutes().max(0),
+                    active_minutes: None,
+                    task,
+                })
+            })
+            .collect();
+        let active = self
+            .active_minutes(tasks.iter().map(|t| t.task.id).collect())
+            .await?;
+        for item in &mut tasks {
+            item.active_minutes = active.get(&item.task.id).copied();
+        }
+
+        let percentiles = if percentiles && !tasks.is_empty() {
+            let mut minutes: Vec<i64> = tasks.iter().map(|t| t.cycle_minutes).collect();
+            minutes.sort_unstable();
+            CYCLE_TIME_PERCENTILES
+                .iter()
+                .map(|&p| CycleTimePercentile {
+                    percentile: p,
+                    minutes: percentile(&minutes, p),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(CycleTimeReport { tasks, percentiles })
+    }
+
+    /// Minutes each task spent in `doing`, summed over its status history
+    ///
+    /// Tasks whose history does not start with a move into `doing` (they were
+    /// started before transitions were recorded) are left out.
+    async fn active_minutes(
+        &self,
+        task_ids: Vec<i64>,
+    ) -> Result<std::collections::HashMap<i64, i64>> {
+        let transitions: Vec<(i64, String, String, chrono::DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT task_id, from_status, to_status, changed_at
+            FROM task_transitions
+            WHERE task_id IN (SELECT value FROM json_each(?))
+              AND (from_status = 'doing' OR to_status = 'doing')
+            ORDER BY task_id, id
+            "#,
+        )
+        .bind(serde_json::to_string(&task_ids)?)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut active = std::collections::HashMap::new();
+        for history in transitions.chunk_by(|a, b| a.0 == b.0) {
+            let mut started: Option<chrono::DateTime<Utc>> = None;
+            let mut minutes = 0;
+            let mut known = true;
+            for (_, from, to, at) in history {
+                if from == "doing" {
+                    match started.take() {
+                        Some(start) => minutes += (*at - start).num_minutes().max(0),
+                        None => known = false,
+                    }
+                }
+                if to == "doing" {
+                    started = Some(*at);
+                }
+            }
+            if known {
+                active.insert(history[0].0, minutes);
+            }
+        }
+        Ok(active)
+    }
+
+    /// Pull request description of a task and its subtree
+    ///
+    /// Collects the task's spec, its completed subtasks with their outcomes,
+    /// the `decision` events of the subtree and the task's own outcome.
+    /// `template` replaces the built-in layout; it can use `{title}`,
+    /// `{task_id}`, `{spec}`, `{changes}`, `{decisions}`, `{outcome}` and
+    /// `{remaining}`.
+    pub async fn pr_report(&self, task_id: i64, template: Option<&str>) -> Result<PrReport> {
+        let task = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {} FROM tasks WHERE id = ? AND {}",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter()
+        ))
+        .bind(task_id)
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(IntentError::TaskNotFound(task_id))?;
+
+        // Ids of the task and everything below it; binds the task id
+        let subtree = r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT ?
+                UNION ALL
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT id FROM subtree
+        "#;
+        let descendants = sqlx::query_as::<_, Task>(&format!(
+            "SELECT {} FROM tasks WHERE id IN ({}) AND id != ? AND {} ORDER BY id",
+            crate::sql_constants::TASK_COLUMNS,
+            subtree,
+            self.scope.sql_filter()
+        ))
+        .bind(task_id)
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        // Latest outcome of each task, as `ie task done --outcome` may run again
+        let outcome_rows = sqlx::query_as::<_, (i64, String)>(&format!(
+            "SELECT task_id, discussion_data FROM events \
+             WHERE log_type = ? AND visibility = 'normal' AND task_id IN ({}) \
+             ORDER BY timestamp, id",
+            subtree
+        ))
+        .bind(CompletionOutcome::LOG_TYPE)
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?;
+        let mut outcomes: std::collections::HashMap<i64, CompletionOutcome> = outcome_rows
+            .into_iter()
+            .map(|(id, text)| (id, CompletionOutcome::from_event_text(&text)))
+            .collect();
+
+        let decisions = sqlx::query_as::<_, Event>(&format!(
+            "{} AND log_type = 'decision' AND visibility = 'normal' AND task_id IN ({}) \
+             AND {} ORDER BY timestamp, id",
+            crate::sql_constants::SELECT_EVENT_BASE,
+            subtree,
+            self.scope.sql_task_filter()
+        ))
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        let (done, remaining): (Vec<Task>, Vec<Task>) =
+            descendants.into_iter().partition(|t| t.status == "done");
+        let changes = done
+            .into_iter()
+            .map(|task| CompletedTask {
+                outcome: outcomes.remove(&task.id),
+                task,
+            })
+            .collect();
+
+        let mut report = PrReport {
+            outcome: outcomes.remove(&task.id),
+            task,
+            changes,
+            decisions,
+            remaining,
+            markdown: String::new(),
+        };
+        report.markdown = render_pr(&report, template);
+        Ok(report)
+    }
+
+    /// Standup summary: what was completed since `since`, what is in
+    /// progress and what is blocked, optionally for tasks of one owner
+    ///
+    /// A task counts as blocked when it has a blocked reason or a `blocker`
+    /// event was logged on it since `since`.
+    pub async fn standup_report(
+        &self,
+        since: DateTime<Utc>,
+        owner: Option<&str>,
+    ) -> Result<StandupReport> {
+        let owner_filter = if owner.is_some() {
+            " AND owner = ?"
+        } else {
+            ""
+        };
+
+        let query = format!(
+            "SELECT {} FROM tasks WHERE status = 'done' AND first_done_at >= ? AND {}{} \
+             ORDER BY first_done_at, id",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter(),
+            owner_filter
+        );
+        let mut q = sqlx::query_as::<_, Task>(&query).bind(since);
+        if let Some(owner) = owner {
+            q = q.bind(owner);
+        }
+        let mut completed = Vec::new();
+        for task in q.fetch_all(self.pool).await? {
+            let outcome = self
+                .latest_event(task.id, CompletionOutcome::LOG_TYPE, None)
+                .await?
+                .map(|e| CompletionOutcome::from_event_text(&e.discussion_data));
+            completed.push(CompletedTask { task, outcome });
+        }
+
+        let query = format!(
+            "SELECT {} FROM tasks WHERE status = 'doing' AND {}{} ORDER BY first_doing_at, id",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter(),
+            owner_filter
+        );
+        let mut q = sqlx::query_as::<_, Task>(&query);
+        if let Some(owner) = owner {
+            q = q.bind(owner);
+        }
+        let mut in_progress = Vec::new();
+        for task in q.fetch_all(self.pool).await? {
+            let latest_event = self.latest_event(task.id, "", None).await?;
+            in_progress.push(InProgressTask { task, latest_event });
+        }
+
+        let query = format!(
+            "SELECT {} FROM tasks WHERE status != 'done' AND (blocked_reason IS NOT NULL \
+             OR id IN (SELECT task_id FROM events WHERE log_type = 'blocker' \
+             AND visibility = 'normal' AND timestamp >= ?)) AND {}{} ORDER BY id",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter(),
+            owner_filter
+        );
+        let mut q = sqlx::query_as::<_, Task>(&query).bind(since);
+        if let Some(owner) = owner {
+            q = q.bind(owner);
+        }
+        let mut blockers = Vec::new();
+        for task in q.fetch_all(self.pool).await? {
+            let reason = match &task.blocked_reason {
+                Some(reason) => reason.clone(),
+                None => self
+                    .latest_event(task.id, "blocker", Some(since))
+                    .await?
+                    .map(|e| e.discussion_data)
+                    .unwrap_or_default(),
+            };
+            blockers.push(StandupBlocker { task, reason });
+        }
+
+        let mut report = StandupReport {
+            since,
+            completed,
+            in_progress,
+            blockers,
+            markdown: String::new(),
+        };
+        report.markdown = render_standup(&report);
+        Ok(report)
+    }
+
+    /// Latest normal event on a task, of `log_type` unless it is empty
+    async fn latest_event(
+        &self,
+        task_id: i64,
+        log_type: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Option<Event>> {
+        let mut query = format!(
+            "{} AND task_id = ? AND visibility = 'normal'",
+            crate::sql_constants::SELECT_EVENT_BASE
+        );
+        if !log_type.is_empty() {
+            query.push_str(" AND log_type = ?");
+        }
+        if since.is_some() {
+            query.push_str(" AND timestamp >= ?");
+        }
+        query.push_str(" ORDER BY timestamp DESC, id DESC LIMIT 1");
+        let mut q = sqlx::query_as::<_, Event>(&query).bind(task_id);
+        if !log_type.is_empty() {
+            q = q.bind(log_type);
+        }
+        if let Some(since) = since {
+            q = q.bind(since);
+        }
+        Ok(q.fetch_optional(self.pool).await?)
+    }
+
+    /// Every incomplete task that `task_id` depends on, directly or through
+    /// other incomplete tasks, nearest first
+    async fn incomplete_blockers(&self, task_id: i64) -> Result<Vec<Task>> {
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            r#"
+            WITH RECURSIVE chain(task_id, depth) AS (
+                SELECT d.blocking_task_id, 1
+                FROM dependencies d
+                JOIN tasks b ON b.id = d.blocking_task_id
+                WHERE d.blocked_task_id = ? AND b.status != 'done'
+                UNION
+                SELECT d.blocking_task_id, c.depth + 1
+                FROM dependencies d
+                JOIN chain c ON d.blocked_task_id = c.task_id
+                JOIN tasks b ON b.id = d.blocking_task_id
+                WHERE b.status != 'done' AND c.depth < 100
+            )
+            SELECT {}
+            FROM tasks t
+            JOIN (SELECT task_id, MIN(depth) AS depth FROM chain GROUP BY task_id) c
+                ON c.task_id = t.id
+            WHERE {}
+            ORDER BY c.depth, t.id
+            "#,
+            crate::sql_constants::TASK_COLUMNS_PREFIXED,
+            self.scope.sql_filter()
+        ))
+        .bind(task_id)
+        .fetch_all(self.pool)
+        .await?;
+        Ok(tasks)
+    }
+
     /// Filter tasks using FTS5
     async fn filter_tasks_by_fts(
         &self,
@@ -164,6 +708,138 @@ impl<'a> ReportManager<'a> {
     }
 }
 
+/// Markdown of a PR report, in `template` or the built-in layout
+fn render_pr(report: &PrReport, template: Option<&str>) -> String {
+    let task = &report.task;
+    let spec = task.spec.as_deref().unwrap_or_default().trim().to_string();
+    let changes = markdown_list(report.changes.iter().map(|change| match &change.outcome {
+        Some(outcome) => format!(
+            "#{} {}: {}",
+            change.task.id,
+            change.task.name,
+            outcome.summary.trim()
+        ),
+        None => format!("#{} {}", change.task.id, change.task.name),
+    }));
+    let decisions = markdown_list(
+        report
+            .decisions
+            .iter()
+            .map(|event| event.discussion_data.trim().to_string()),
+    );
+    let outcome = report
+        .outcome
+        .as_ref()
+        .map(|outcome| {
+            let mut text = outcome.summary.trim().to_string();
+            if !outcome.artifacts.is_empty() {
+                text.push_str("\n\nArtifacts:\n");
+                text.push_str(&markdown_list(outcome.artifacts.iter().cloned()));
+            }
+            text
+        })
+        .unwrap_or_default();
+    let remaining = markdown_list(
+        report
+            .remaining
+            .iter()
+            .map(|t| format!("#{} {} ({})", t.id, t.name, t.status)),
+    );
+
+    if let Some(template) = template {
+        return crate::i18n::fill(
+            template,
+            &[
+                ("title", &task.name),
+                ("task_id", &task.id),
+                ("spec", &spec),
+                ("changes", &changes),
+                ("decisions", &decisions),
+                ("outcome", &outcome),
+                ("remaining", &remaining),
+            ],
+        );
+    }
+
+    // Built-in layout: sections with nothing to say are left out
+    let mut out = format!("# {}\n", task.name);
+    for (heading, body) in [
+        (None, &spec),
+        (Some("Changes"), &changes),
+        (Some("Key decisions"), &decisions),
+        (Some("Outcome"), &outcome),
+        (Some("Still open"), &remaining),
+    ] {
+        if body.is_empty() {
+            continue;
+        }
+        if let Some(heading) = heading {
+            out.push_str(&format!("\n## {}\n", heading));
+        }
+        out.push_str(&format!("\n{}\n", body));
+    }
+    out
+}
+
+/// Markdown of a standup report; sections with nothing in them are left out
+fn render_standup(report: &StandupReport) -> String {
+    let since = report
+        .since
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M");
+    let completed = markdown_list(report.completed.iter().map(|c| match &c.outcome {
+        Some(outcome) => format!("#{} {}: {}", c.task.id, c.task.name, outcome.summary.trim()),
+        None => format!("#{} {}", c.task.id, c.task.name),
+    }));
+    let in_progress = markdown_list(report.in_progress.iter().map(|p| match &p.latest_event {
+        Some(event) => format!(
+            "#{} {} ({}: {})",
+            p.task.id,
+            p.task.name,
+            event.log_type,
+            event.discussion_data.trim()
+        ),
+        None => format!("#{} {}", p.task.id, p.task.name),
+    }));
+    let blockers = markdown_list(
+        report
+            .blockers
+            .iter()
+            .map(|b| format!("#{} {}: {}", b.task.id, b.task.name, b.reason.trim())),
+    );
+
+    let mut out = format!("# Standup since {}\n", since);
+    let mut empty = true;
+    for (heading, body) in [
+        ("Completed", &completed),
+        ("In progress", &in_progress),
+        ("Blockers", &blockers),
+    ] {
+        if !body.is_empty() {
+            out.push_str(&format!("\n## {}\n\n{}\n", heading, body));
+            empty = false;
+        }
+    }
+    if empty {
+        out.push_str("\nNo activity since then.\n");
+    }
+    out
+}
+
+/// Markdown bullet list, continuation lines indented under their bullet
+fn markdown_list(items: impl Iterator<Item = String>) -> String {
+    items
+        .map(|item| format!("- {}", item.trim().replace('\n', "\n  ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Nearest-rank percentile of an ascending, non-empty list
+fn percentile(sorted: &[i64], p: u8) -> i64 {
+    let rank = (f64::from(p) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,7 +868,7 @@ mod tests {
             .await
             .unwrap();
         task_mgr.start_task(done.id, false).await.unwrap();
-        task_mgr.done_task(false).await.unwrap();
+        task_mgr.done_task().await.unwrap();
 
         let report = report_mgr
             .generate_report(None, None, None, None, true)
@@ -640,6 +1316,78 @@ mod tests {
         assert_eq!(full_report.events.unwrap().len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_report_filters_event_types() {
+        let ctx = TestContext::new().await;
+        let task = TaskManager::new(ctx.pool())
+            .add_task("Task", None, None, None, None, None)
+            .await
+            .unwrap();
+        let event_mgr = EventManager::new(ctx.pool());
+        for log_type in ["decision", "note", "blocker"] {
+            event_mgr
+                .add_event(task.id, log_type, "Event")
+                .await
+                .unwrap();
+        }
+
+        let report_mgr = ReportManager::new(ctx.pool())
+            .with_event_types(vec!["decision".to_string(), "blocker".to_string()]);
+        let summary = report_mgr
+            .generate_report(None, None, None, None, true)
+            .await
+            .unwrap();
+        assert_eq!(summary.summary.total_events, 2);
+
+        let full = report_mgr
+            .generate_report(Some("1d".to_string()), None, None, None, false)
+            .await
+            .unwrap();
+        let events = full.events.unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.log_type != "note"));
+    }
+
+    #[tokio::test]
+    async fn test_report_lists_outcomes() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let shipped = task_mgr
+            .add_task("Shipped", None, None, None, None, None)
+            .await
+            .unwrap();
+        let other = task_mgr
+            .add_task("Other", None, None, None, None, None)
+            .await
+            .unwrap();
+        let outcome = CompletionOutcome {
+            summary: "Released v2".to_string(),
+            artifacts: vec!["CHANGELOG.md".to_string()],
+        };
+        task_mgr
+            .done_task_by_id_with_outcome(shipped.id, Some(outcome.clone()))
+            .await
+            .unwrap();
+        task_mgr.done_task_by_id(other.id).await.unwrap();
+
+        let report_mgr = ReportManager::new(ctx.pool());
+        let report = report_mgr
+            .generate_report(None, Some("done".to_string()), None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(report.outcomes.len(), 1);
+        assert_eq!(report.outcomes[0].task_id, shipped.id);
+        assert_eq!(report.outcomes[0].task_name, "Shipped");
+        assert_eq!(report.outcomes[0].outcome, outcome);
+
+        // Outcomes only follow reported tasks
+        let todo = report_mgr
+            .generate_report(None, Some("todo".to_string()), None, None, false)
+            .await
+            .unwrap();
+        assert!(todo.outcomes.is_empty());
+    }
+
     #[tokio::test]
     async fn test_generate_report_all_filters_combined() {
         let ctx = TestContext::new().await;
@@ -687,4 +1435,288 @@ mod tests {
         assert_eq!(tasks[0].status, "doing");
         assert!(tasks[0].name.contains("Auth"));
     }
+
+    #[tokio::test]
+    async fn test_milestone_report() {
+        let ctx = TestContext::new().await;
+        let request = crate::plan::parse_request(
+            r#"{"tasks":[
+                {"name":"Schema","spec":"Tables","status":"done","milestone":"v1"},
+                {"name":"API","spec":"Endpoints","milestone":"v1","depends_on":["Auth"]},
+                {"name":"Auth","spec":"Login"},
+                {"name":"Docs","milestone":"v1"}
+            ]}"#,
+        )
+        .unwrap();
+        let ids = crate::plan::PlanExecutor::new(ctx.pool())
+            .execute(&request)
+            .await
+            .unwrap()
+            .task_id_map;
+
+        let report = ReportManager::new(ctx.pool())
+            .milestone_report("v1")
+            .await
+            .unwrap();
+        assert_eq!((report.done_tasks, report.total_tasks), (1, 3));
+        assert!((report.completion_percent - 100.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(report.blocked.len(), 1);
+        assert_eq!(report.blocked[0].task.id, ids["API"]);
+        assert_eq!(report.blocked[0].blocking_chain[0].id, ids["Auth"]);
+
+        let at_risk: Vec<(i64, &str)> = report
+            .at_risk
+            .iter()
+            .map(|r| (r.task.id, r.reasons[0].as_str()))
+            .collect();
+        assert_eq!(
+            at_risk,
+            vec![
+                (
+                    ids["API"],
+                    "blocked by 1 incomplete task(s) outside the milestone"
+                ),
+                (ids["Docs"], "not started and has no spec"),
+            ]
+        );
+
+        let err = ReportManager::new(ctx.pool())
+            .milestone_report("v2")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Milestone 'v2' not found"));
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let minutes = [10, 20, 30, 40];
+        assert_eq!(percentile(&minutes, 50), 20);
+        assert_eq!(percentile(&minutes, 75), 30);
+        assert_eq!(percentile(&minutes, 95), 40);
+        assert_eq!(percentile(&[7], 50), 7);
+    }
+
+    #[tokio::test]
+    async fn test_aging_and_cycle_time_reports() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let mut ids = Vec::new();
+        for name in ["Recent", "Old", "Fast", "Slow"] {
+            ids.push(
+                task_mgr
+                    .add_task(name, None, None, Some("ai"), None, None)
+                    .await
+                    .unwrap()
+                    .id,
+            );
+        }
+        let now = Utc::now();
+        let ago = |minutes: i64| (now - chrono::Duration::minutes(minutes)).to_rfc3339();
+        for (id, status, started, done) in [
+            (ids[0], "doing", ago(30), None),
+            (ids[1], "doing", ago(600), None),
+            (ids[2], "done", ago(100), Some(ago(90))),
+            (ids[3], "done", ago(500), Some(ago(20))),
+        ] {
+            sqlx::query(
+                "UPDATE tasks SET status = ?, first_doing_at = ?, first_done_at = ? WHERE id = ?",
+            )
+            .bind(status)
+            .bind(started)
+            .bind(done)
+            .bind(id)
+            .execute(ctx.pool())
+            .await
+            .unwrap();
+        }
+        let report_mgr = ReportManager::new(ctx.pool());
+
+        let aging = report_mgr.aging_report().await.unwrap();
+        let aging: Vec<(i64, i64)> = aging.iter().map(|a| (a.task.id, a.age_minutes)).collect();
+        assert_eq!(aging, vec![(ids[1], 600), (ids[0], 30)]);
+
+        let report = report_mgr.cycle_time_report(None, true).await.unwrap();
+        let cycles: Vec<(i64, i64)> = report
+            .tasks
+            .iter()
+            .map(|t| (t.task.id, t.cycle_minutes))
+            .collect();
+        assert_eq!(cycles, vec![(ids[3], 480), (ids[2], 10)]);
+        // Started before status history was recorded
+        assert!(report.tasks.iter().all(|t| t.active_minutes.is_none()));
+        assert_eq!(
+            report.percentiles[0],
+            CycleTimePercentile {
+                percentile: 50,
+                minutes: 10
+            }
+        );
+
+        let recent = report_mgr
+            .cycle_time_report(Some("1h"), false)
+            .await
+            .unwrap();
+        assert_eq!(recent.tasks.len(), 1);
+        assert!(recent.percentiles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cycle_time_active_minutes_skip_time_paused() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let id = task_mgr
+            .add_task("Paused", None, None, Some("ai"), None, None)
+            .await
+            .unwrap()
+            .id;
+        let now = Utc::now();
+        let ago = |minutes: i64| now - chrono::Duration::minutes(minutes);
+        sqlx::query(
+            "UPDATE tasks SET status = 'done', first_doing_at = ?, first_done_at = ? WHERE id = ?",
+        )
+        .bind(ago(300).to_rfc3339())
+        .bind(ago(0).to_rfc3339())
+        .bind(id)
+        .execute(ctx.pool())
+        .await
+        .unwrap();
+        // 60 minutes in doing, paused for 200, then 40 more
+        for (from, to, at) in [
+            ("todo", "doing", ago(300)),
+            ("doing", "todo", ago(240)),
+            ("todo", "doing", ago(40)),
+            ("doing", "done", ago(0)),
+        ] {
+            sqlx::query(
+                "INSERT INTO task_transitions (task_id, from_status, to_status, source, changed_at) \
+                 VALUES (?, ?, ?, 'cli', ?)",
+            )
+            .bind(id)
+            .bind(from)
+            .bind(to)
+            .bind(at)
+            .execute(ctx.pool())
+            .await
+            .unwrap();
+        }
+
+        let report = ReportManager::new(ctx.pool())
+            .cycle_time_report(None, false)
+            .await
+            .unwrap();
+        assert_eq!(report.tasks[0].cycle_minutes, 300);
+        assert_eq!(report.tasks[0].active_minutes, Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_pr_report() {
+        let ctx = TestContext::new().await;
+        let request = crate::plan::parse_request(
+            r#"{"tasks":[{"name":"Login","spec":"Password login","children":[
+                {"name":"Form","spec":"Fields"},
+                {"name":"Cookie","spec":"Session"},
+                {"name":"Rate limit"}
+            ]}]}"#,
+        )
+        .unwrap();
+        let ids = crate::plan::PlanExecutor::new(ctx.pool())
+            .execute(&request)
+            .await
+            .unwrap()
+            .task_id_map;
+        let task_mgr = TaskManager::new(ctx.pool());
+        task_mgr.start_task(ids["Form"], false).await.unwrap();
+        let outcome = CompletionOutcome {
+            summary: "Form validates input".to_string(),
+            artifacts: vec!["src/form.rs".to_string()],
+        };
+        task_mgr
+            .done_task_by_id_with_outcome(ids["Form"], Some(outcome))
+            .await
+            .unwrap();
+        task_mgr.start_task(ids["Cookie"], false).await.unwrap();
+        task_mgr.done_task_by_id(ids["Cookie"]).await.unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(ids["Cookie"], "decision", "Signed cookies over JWT")
+            .await
+            .unwrap();
+
+        let reports = ReportManager::new(ctx.pool());
+        let report = reports.pr_report(ids["Login"], None).await.unwrap();
+        assert_eq!(report.changes.len(), 2);
+        assert_eq!(
+            report.changes[0].outcome.as_ref().unwrap().artifacts,
+            ["src/form.rs"]
+        );
+        assert_eq!(report.remaining[0].id, ids["Rate limit"]);
+        assert!(report.markdown.starts_with("# Login\n\nPassword login\n"));
+        assert!(report.markdown.contains(&format!(
+            "- #{} Form: Form validates input\n- #{} Cookie\n",
+            ids["Form"], ids["Cookie"]
+        )));
+        assert!(report
+            .markdown
+            .contains("## Key decisions\n\n- Signed cookies over JWT\n"));
+        // Nothing was recorded for the task itself
+        assert!(!report.markdown.contains("## Outcome"));
+
+        let custom = reports
+            .pr_report(ids["Login"], Some("{title} ({task_id})\n{decisions}"))
+            .await
+            .unwrap();
+        assert_eq!(
+            custom.markdown,
+            format!("Login ({})\n- Signed cookies over JWT", ids["Login"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_standup_report() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let event_mgr = EventManager::new(ctx.pool());
+        let since = Utc::now() - chrono::Duration::hours(1);
+
+        let reports = ReportManager::new(ctx.pool());
+        let report = reports.standup_report(since, None).await.unwrap();
+        assert!(report.markdown.ends_with("No activity since then.\n"));
+
+        let shipped = task_mgr
+            .add_task("Ship", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr.start_task(shipped.id, false).await.unwrap();
+        task_mgr.done_task_by_id(shipped.id).await.unwrap();
+        let working = task_mgr
+            .add_task("Refactor", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr.start_task(working.id, false).await.unwrap();
+        event_mgr
+            .add_event(working.id, "note", "Halfway there")
+            .await
+            .unwrap();
+        let stuck = task_mgr
+            .add_task("Deploy", None, None, None, None, None)
+            .await
+            .unwrap();
+        event_mgr
+            .add_event(stuck.id, "blocker", "No staging access")
+            .await
+            .unwrap();
+
+        let report = reports.standup_report(since, None).await.unwrap();
+        assert_eq!(report.completed[0].task.id, shipped.id);
+        assert_eq!(report.in_progress[0].task.id, working.id);
+        assert_eq!(report.blockers[0].reason, "No staging access");
+        assert!(report.markdown.contains(&format!(
+            "## In progress\n\n- #{} Refactor (note: Halfway there)\n",
+            working.id
+        )));
+        assert!(!report.markdown.contains("No activity"));
+
+        let report = reports.standup_report(since, Some("nobody")).await.unwrap();
+        assert!(report.completed.is_empty() && report.blockers.is_empty());
+    }
 }
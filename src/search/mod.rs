@@ -12,6 +12,9 @@
 //! **Solution**: For short CJK queries, we fallback to LIKE search which supports
 //! any length substring matching, albeit slower.
 
+#[cfg(feature = "semantic")]
+pub mod semantic;
+
 /// Check if a character is a CJK character
 pub fn is_cjk_char(c: char) -> bool {
     let code = c as u32;
@@ -61,6 +64,40 @@ pub fn needs_like_fallback(query: &str) -> bool {
     false
 }
 
+/// Extract a context window around the first occurrence of `needle` in `haystack`.
+/// Returns up to ~120 chars of context centered on the match.
+///
+/// For backends without a snippet function of their own.
+pub fn build_context_snippet(haystack: &str, needle: &str) -> String {
+    let needle_lower = needle.to_lowercase();
+    let haystack_lower = haystack.to_lowercase();
+
+    if let Some(pos) = haystack_lower.find(&needle_lower) {
+        let chars: Vec<char> = haystack.chars().collect();
+        let char_pos = haystack[..pos].chars().count();
+        let context_chars = 60;
+
+        let start = char_pos.saturating_sub(context_chars);
+        let end = (char_pos + needle.chars().count() + context_chars).min(chars.len());
+
+        let snippet: String = chars[start..end].iter().collect();
+
+        let prefix = if start > 0 { "..." } else { "" };
+        let suffix = if end < chars.len() { "..." } else { "" };
+
+        format!("{}{}{}", prefix, snippet, suffix)
+    } else {
+        // No match found — return truncated haystack
+        let chars: Vec<char> = haystack.chars().collect();
+        if chars.len() > 120 {
+            let truncated: String = chars[..117].iter().collect();
+            format!("{}...", truncated)
+        } else {
+            haystack.to_string()
+        }
+    }
+}
+
 /// Escape FTS5 special characters in a query string
 ///
 /// FTS5 queries support advanced syntax (AND, OR, NOT, *, #, "phrase search", etc.).
@@ -92,22 +129,149 @@ pub fn escape_fts5(query: &str) -> String {
     format!("\"{}\"", query.replace('"', "\"\""))
 }
 
+/// A user search query translated to an FTS5 MATCH expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct FtsQuery {
+    pub expression: String,
+    /// Some term is scoped to a task column (`name:`/`spec:`), so events
+    /// cannot match
+    pub task_columns_only: bool,
+}
+
+/// Task columns a term can be scoped to
+const TASK_COLUMNS: &[&str] = &["name", "spec"];
+
+/// Translate user input into a safe FTS5 expression
+///
+/// Terms separated by whitespace must all match. Each term is one of:
+/// - `word`: searched literally, so `login-page`, `#123` or `AND` are fine
+/// - `"exact phrase"`
+/// - `auth*`: prefix match
+/// - `name:login` or `spec:"token refresh"`: only that task column
+///
+/// Every term is emitted as a quoted FTS5 string, so no input can produce an
+/// FTS5 syntax error. Returns None when no term has searchable characters.
+///
+/// # Example
+/// ```ignore
+/// let q = parse_query(r#"name:"login page" auth*"#).unwrap();
+/// assert_eq!(q.expression, r#"name : "login page" "auth" *"#);
+/// ```
+pub fn parse_query(query: &str) -> Option<FtsQuery> {
+    let mut terms = Vec::new();
+    let mut task_columns_only = false;
+
+    for token in split_terms(query) {
+        let (column, rest) = match token.split_once(':') {
+            Some((prefix, rest)) => {
+                match TASK_COLUMNS.iter().find(|c| c.eq_ignore_ascii_case(prefix)) {
+                    Some(column) => (Some(*column), rest),
+                    None => (None, token.as_str()),
+                }
+            },
+            None => (None, token.as_str()),
+        };
+        let prefix = rest.ends_with('*');
+        let text: String = rest
+            .trim_end_matches('*')
+            .chars()
+            .filter(|c| *c != '"')
+            .collect();
+        if !text.chars().any(|c| c.is_alphanumeric() || is_cjk_char(c)) {
+            continue;
+        }
+
+        let mut term = format!("\"{}\"", text.trim());
+        if prefix {
+            term.push_str(" *");
+        }
+        if let Some(column) = column {
+            term = format!("{} : {}", column, term);
+            task_columns_only = true;
+        }
+        terms.push(term);
+    }
+
+    if terms.is_empty() {
+        return None;
+    }
+    Some(FtsQuery {
+        expression: terms.join(" "),
+        task_columns_only,
+    })
+}
+
+/// Split on whitespace outside double quotes; quotes are kept
+fn split_terms(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in query.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
 // ============================================================================
 // Unified Search
 // ============================================================================
 
-use crate::db::models::{Event, PaginatedSearchResults, SearchResult, Task};
+use crate::db::models::{Event, PaginatedSearchResults, SearchResult, Task, TaskVisibility};
 use crate::error::Result;
 use crate::tasks::TaskManager;
-use sqlx::{Row, SqlitePool};
+use sqlx::{FromRow, Row, SqlitePool};
 
 pub struct SearchManager<'a> {
     pool: &'a SqlitePool,
+    scope: TaskVisibility,
+    /// Event types results are limited to; empty means all
+    event_types: Vec<String>,
 }
 
 impl<'a> SearchManager<'a> {
     pub fn new(pool: &'a SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            scope: crate::project::task_scope(),
+            event_types: Vec::new(),
+        }
+    }
+
+    /// Search with an explicit task scope instead of the process-wide one
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Only return events of these types; tasks are not affected
+    pub fn with_event_types(mut self, event_types: Vec<String>) -> Self {
+        self.event_types = event_types;
+        self
+    }
+
+    /// `AND <column> IN (?, ...)` for the event type filter, or nothing;
+    /// bind `self.event_types` right after the search term
+    fn event_type_filter(&self, column: &str) -> String {
+        if self.event_types.is_empty() {
+            return String::new();
+        }
+        format!(
+            " AND {} IN ({})",
+            column,
+            vec!["?"; self.event_types.len()].join(", ")
+        )
     }
 
     /// Unified search across tasks and events with pagination support
@@ -129,6 +293,7 @@ impl<'a> SearchManager<'a> {
     ///
     /// # Returns
     /// PaginatedSearchResults with mixed task and event results, ordered by relevance (FTS5 rank)
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn search(
         &self,
         query: &str,
@@ -154,8 +319,7 @@ impl<'a> SearchManager<'a> {
         }
 
         // Handle queries with no searchable content (only special characters)
-        let has_searchable = query.chars().any(|c| c.is_alphanumeric() || is_cjk_char(c));
-        if !has_searchable {
+        let Some(fts_query) = parse_query(query) else {
             return Ok(PaginatedSearchResults {
                 results: Vec::new(),
                 total_tasks: 0,
@@ -164,10 +328,10 @@ impl<'a> SearchManager<'a> {
                 limit,
                 offset,
             });
-        }
-
-        // Escape FTS5 special characters
-        let escaped_query = escape_fts5(query);
+        };
+        let escaped_query = fts_query.expression;
+        // Events have no name or spec column
+        let include_events = include_events && !fts_query.task_columns_only;
 
         let mut total_tasks: i64 = 0;
         let mut total_events: i64 = 0;
@@ -183,9 +347,10 @@ impl<'a> SearchManager<'a> {
             // Search tasks if enabled
             if include_tasks {
                 // Get total count
-                let count_result = sqlx::query_scalar::<_, i64>(
-                    "SELECT COUNT(*) FROM tasks WHERE name LIKE ? OR spec LIKE ?",
-                )
+                let count_result = sqlx::query_scalar::<_, i64>(&format!(
+                    "SELECT COUNT(*) FROM tasks WHERE (name LIKE ? OR spec LIKE ?) AND {}",
+                    self.scope.sql_filter()
+                ))
                 .bind(&like_pattern)
                 .bind(&like_pattern)
                 .fetch_one(self.pool)
@@ -215,12 +380,17 @@ impl<'a> SearchManager<'a> {
                         first_done_at,
                         active_form,
                         owner,
-                        metadata
+                        metadata,
+                        sort_order,
+                        estimate_minutes,
+                        visibility,
+                        blocked_reason
                     FROM tasks
-                    WHERE name LIKE ? OR spec LIKE ?
+                    WHERE (name LIKE ? OR spec LIKE ?) AND {}
                     {}
                     LIMIT ? OFFSET ?
                     "#,
+                    self.scope.sql_filter(),
                     order_by
                 );
 
@@ -247,6 +417,11 @@ impl<'a> SearchManager<'a> {
                         active_form: row.get("active_form"),
                         owner: row.get("owner"),
                         metadata: row.get("metadata"),
+                        sort_order: row.get("sort_order"),
+                        estimate_minutes: row.get("estimate_minutes"),
+                        visibility: row.get("visibility"),
+                        blocked_reason: row.get("blocked_reason"),
+                        checklist: None,
                     };
 
                     // Determine match field and create snippet
@@ -267,6 +442,7 @@ impl<'a> SearchManager<'a> {
                             task,
                             match_snippet,
                             match_field,
+                            score: 0.0,
                         },
                         1.0, // Constant rank for LIKE results
                     ));
@@ -276,44 +452,49 @@ impl<'a> SearchManager<'a> {
             // Search events if enabled
             if include_events {
                 // Get total count
-                let count_result = sqlx::query_scalar::<_, i64>(
-                    "SELECT COUNT(*) FROM events WHERE discussion_data LIKE ?",
-                )
-                .bind(&like_pattern)
-                .fetch_one(self.pool)
-                .await?;
-                total_events = count_result;
+                let count_sql = format!(
+                    "SELECT COUNT(*) FROM events WHERE discussion_data LIKE ? AND visibility = 'normal' AND {}{}",
+                    self.scope.sql_task_filter(),
+                    self.event_type_filter("log_type")
+                );
+                let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql).bind(&like_pattern);
+                for event_type in &self.event_types {
+                    count_query = count_query.bind(event_type);
+                }
+                total_events = count_query.fetch_one(self.pool).await?;
 
                 // Query events with pagination
-                let rows = sqlx::query(
+                let event_sql = format!(
                     r#"
                     SELECT
                         id,
                         task_id,
                         timestamp,
                         log_type,
-                        discussion_data
+                        discussion_data,
+                        visibility,
+                        actor
                     FROM events
-                    WHERE discussion_data LIKE ?
+                    WHERE discussion_data LIKE ? AND visibility = 'normal' AND {}{}
                     ORDER BY id ASC
                     LIMIT ? OFFSET ?
                     "#,
-                )
-                .bind(&like_pattern)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(self.pool)
-                .await?;
+                    self.scope.sql_task_filter(),
+                    self.event_type_filter("log_type")
+                );
+                let mut event_query = sqlx::query(&event_sql).bind(&like_pattern);
+                for event_type in &self.event_types {
+                    event_query = event_query.bind(event_type);
+                }
+                let rows = event_query
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(self.pool)
+                    .await?;
 
-                let task_mgr = TaskManager::new(self.pool);
+                let task_mgr = TaskManager::new(self.pool).with_scope(self.scope);
                 for row in rows {
-                    let event = Event {
-                        id: row.get("id"),
-                        task_id: row.get("task_id"),
-                        timestamp: row.get("timestamp"),
-                        log_type: row.get("log_type"),
-                        discussion_data: row.get("discussion_data"),
-                    };
+                    let event = Event::from_row(&row)?;
 
                     // Create match snippet
                     let match_snippet = event.discussion_data.clone();
@@ -326,6 +507,7 @@ impl<'a> SearchManager<'a> {
                             event,
                             task_chain,
                             match_snippet,
+                            score: 0.0,
                         },
                         1.0, // Constant rank for LIKE results
                     ));
@@ -336,9 +518,14 @@ impl<'a> SearchManager<'a> {
             // Search tasks if enabled
             if include_tasks {
                 // Get total count
-                let count_result = sqlx::query_scalar::<_, i64>(
-                    "SELECT COUNT(*) FROM tasks_fts WHERE tasks_fts MATCH ?",
-                )
+                let count_result = sqlx::query_scalar::<_, i64>(&format!(
+                    r#"
+                    SELECT COUNT(*) FROM tasks_fts
+                    INNER JOIN tasks t ON tasks_fts.rowid = t.id
+                    WHERE tasks_fts MATCH ? AND {}
+                    "#,
+                    self.scope.sql_filter()
+                ))
                 .bind(&escaped_query)
                 .fetch_one(self.pool)
                 .await?;
@@ -368,17 +555,20 @@ impl<'a> SearchManager<'a> {
                     t.active_form,
                     t.owner,
                     t.metadata,
-                    COALESCE(
-                        snippet(tasks_fts, 1, '**', '**', '...', 15),
-                        snippet(tasks_fts, 0, '**', '**', '...', 15)
-                    ) as match_snippet,
+                    t.sort_order,
+                    t.estimate_minutes,
+                    t.visibility,
+                    t.blocked_reason,
+                    snippet(tasks_fts, -1, '**', '**', '...', 15) as match_snippet,
+                    highlight(tasks_fts, 0, '**', '**') != t.name as name_matched,
                     rank
                 FROM tasks_fts
                 INNER JOIN tasks t ON tasks_fts.rowid = t.id
-                WHERE tasks_fts MATCH ?
+                WHERE tasks_fts MATCH ? AND {}
                 {}
                 LIMIT ? OFFSET ?
                 "#,
+                    self.scope.sql_filter(),
                     order_by
                 );
 
@@ -404,27 +594,25 @@ impl<'a> SearchManager<'a> {
                         active_form: row.get("active_form"),
                         owner: row.get("owner"),
                         metadata: row.get("metadata"),
+                        sort_order: row.get("sort_order"),
+                        estimate_minutes: row.get("estimate_minutes"),
+                        visibility: row.get("visibility"),
+                        blocked_reason: row.get("blocked_reason"),
+                        checklist: None,
                     };
                     let match_snippet: String = row.get("match_snippet");
+                    let name_matched: bool = row.get("name_matched");
                     let rank: f64 = row.get("rank");
 
-                    // Determine match field based on snippet content
-                    let match_field = if task
-                        .spec
-                        .as_ref()
-                        .map(|s| match_snippet.to_lowercase().contains(&s.to_lowercase()))
-                        .unwrap_or(false)
-                    {
-                        "spec".to_string()
-                    } else {
-                        "name".to_string()
-                    };
+                    // A name hit wins: names are what users scan results by
+                    let match_field = if name_matched { "name" } else { "spec" }.to_string();
 
                     all_results.push((
                         SearchResult::Task {
                             task,
                             match_snippet,
                             match_field,
+                            score: -rank,
                         },
                         rank,
                     ));
@@ -434,16 +622,23 @@ impl<'a> SearchManager<'a> {
             // Search events if enabled
             if include_events {
                 // Get total count
-                let count_result = sqlx::query_scalar::<_, i64>(
-                    "SELECT COUNT(*) FROM events_fts WHERE events_fts MATCH ?",
-                )
-                .bind(&escaped_query)
-                .fetch_one(self.pool)
-                .await?;
-                total_events = count_result;
+                let count_sql = format!(
+                    r#"
+                    SELECT COUNT(*) FROM events_fts
+                    INNER JOIN events e ON events_fts.rowid = e.id
+                    WHERE events_fts MATCH ? AND e.visibility = 'normal' AND {}{}
+                    "#,
+                    self.scope.sql_task_filter(),
+                    self.event_type_filter("e.log_type")
+                );
+                let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql).bind(&escaped_query);
+                for event_type in &self.event_types {
+                    count_query = count_query.bind(event_type);
+                }
+                total_events = count_query.fetch_one(self.pool).await?;
 
                 // Query events with pagination
-                let rows = sqlx::query(
+                let event_sql = format!(
                     r#"
                 SELECT
                     e.id,
@@ -451,30 +646,32 @@ impl<'a> SearchManager<'a> {
                     e.timestamp,
                     e.log_type,
                     e.discussion_data,
+                    e.visibility,
+                    e.actor,
                     snippet(events_fts, 0, '**', '**', '...', 15) as match_snippet,
                     rank
                 FROM events_fts
                 INNER JOIN events e ON events_fts.rowid = e.id
-                WHERE events_fts MATCH ?
+                WHERE events_fts MATCH ? AND e.visibility = 'normal' AND {}{}
                 ORDER BY rank ASC, e.id ASC
                 LIMIT ? OFFSET ?
                 "#,
-                )
-                .bind(&escaped_query)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(self.pool)
-                .await?;
+                    self.scope.sql_task_filter(),
+                    self.event_type_filter("e.log_type")
+                );
+                let mut event_query = sqlx::query(&event_sql).bind(&escaped_query);
+                for event_type in &self.event_types {
+                    event_query = event_query.bind(event_type);
+                }
+                let rows = event_query
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(self.pool)
+                    .await?;
 
-                let task_mgr = TaskManager::new(self.pool);
+                let task_mgr = TaskManager::new(self.pool).with_scope(self.scope);
                 for row in rows {
-                    let event = Event {
-                        id: row.get("id"),
-                        task_id: row.get("task_id"),
-                        timestamp: row.get("timestamp"),
-                        log_type: row.get("log_type"),
-                        discussion_data: row.get("discussion_data"),
-                    };
+                    let event = Event::from_row(&row)?;
                     let match_snippet: String = row.get("match_snippet");
                     let rank: f64 = row.get("rank");
 
@@ -486,6 +683,7 @@ impl<'a> SearchManager<'a> {
                             event,
                             task_chain,
                             match_snippet,
+                            score: -rank,
                         },
                         rank,
                     ));
@@ -503,6 +701,7 @@ impl<'a> SearchManager<'a> {
         // Calculate has_more
         let total_count = total_tasks + total_events;
         let has_more = offset + (results.len() as i64) < total_count;
+        tracing::Span::current().record("rows", results.len());
 
         Ok(PaginatedSearchResults {
             results,
@@ -515,6 +714,23 @@ impl<'a> SearchManager<'a> {
     }
 }
 
+impl crate::backend::SearchBackend for SearchManager<'_> {
+    fn search(
+        &self,
+        query: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<PaginatedSearchResults>> + Send {
+        self.search(query, include_tasks, include_events, limit, offset, false)
+    }
+
+    fn filter_event_types(self, event_types: Vec<String>) -> Result<Self> {
+        Ok(self.with_event_types(event_types))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -727,6 +943,188 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_query_syntax() {
+        let q = parse_query(r#"name:"login page" auth*"#).unwrap();
+        assert_eq!(q.expression, r#"name : "login page" "auth" *"#);
+        assert!(q.task_columns_only);
+
+        let q = parse_query("JWT auth").unwrap();
+        assert_eq!(q.expression, r#""JWT" "auth""#);
+        assert!(!q.task_columns_only);
+
+        // Unknown prefixes are just text
+        assert_eq!(
+            parse_query("http://x.io").unwrap().expression,
+            r#""http://x.io""#
+        );
+        assert_eq!(
+            parse_query("SPEC:token").unwrap().expression,
+            r#"spec : "token""#
+        );
+    }
+
+    #[test]
+    fn test_parse_query_neutralizes_fts_syntax() {
+        assert_eq!(
+            parse_query("login-page").unwrap().expression,
+            r#""login-page""#
+        );
+        assert_eq!(
+            parse_query("a AND NOT b").unwrap().expression,
+            r#""a" "AND" "NOT" "b""#
+        );
+        // Unbalanced quote runs to the end of the input
+        assert_eq!(
+            parse_query(r#""open phrase"#).unwrap().expression,
+            r#""open phrase""#
+        );
+        assert_eq!(
+            parse_query(r#"say "hi"#).unwrap().expression,
+            r#""say" "hi""#
+        );
+
+        assert!(parse_query("").is_none());
+        assert!(parse_query(r#"- "" * name:"#).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_advanced_queries() {
+        use crate::events::EventManager;
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let tasks = TaskManager::new(ctx.pool());
+        let login = tasks
+            .add_task(
+                "Fix login-page bug",
+                Some("token refresh flow"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let docs = tasks
+            .add_task(
+                "Write docs",
+                Some("explain the login page"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(docs.id, "note", "login page screenshots")
+            .await
+            .unwrap();
+        let search = SearchManager::new(ctx.pool());
+        let task_ids = |results: &PaginatedSearchResults| -> Vec<i64> {
+            results
+                .results
+                .iter()
+                .filter_map(|r| match r {
+                    SearchResult::Task { task, .. } => Some(task.id),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Punctuation that used to break FTS5 parsing
+        let results = search
+            .search(r#"login-page "bug"#, true, true, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&results), vec![login.id]);
+
+        // Field scope: only names, and no events
+        let results = search
+            .search("name:login", true, true, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&results), vec![login.id]);
+        assert_eq!(results.total_events, 0);
+
+        // Phrase vs words
+        let results = search
+            .search(r#"spec:"login page""#, true, true, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&results), vec![docs.id]);
+
+        // Prefix
+        let results = search
+            .search("screensh*", true, true, None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(results.total_events, 1);
+
+        // Highlighted snippet, match field and relevance score
+        let results = search
+            .search("refresh", true, false, None, None, false)
+            .await
+            .unwrap();
+        match &results.results[0] {
+            SearchResult::Task {
+                match_snippet,
+                match_field,
+                score,
+                ..
+            } => {
+                assert!(match_snippet.contains("**refresh**"), "{}", match_snippet);
+                assert_eq!(match_field, "spec");
+                assert!(*score > 0.0);
+            },
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_event_types() {
+        use crate::events::EventManager;
+        use crate::test_utils::test_helpers::TestContext;
+
+        let ctx = TestContext::new().await;
+        let task = TaskManager::new(ctx.pool())
+            .add_task("Cache rollout", None, None, None, None, None)
+            .await
+            .unwrap();
+        let events = EventManager::new(ctx.pool());
+        events
+            .add_event(task.id, "decision", "cache keys by tenant")
+            .await
+            .unwrap();
+        events
+            .add_event(task.id, "note", "cache hit rate 80%")
+            .await
+            .unwrap();
+
+        // FTS and short CJK (LIKE) paths both honour the filter
+        for (query, logged) in [("cache", None), ("缓存", Some("缓存 miss"))] {
+            if let Some(text) = logged {
+                events.add_event(task.id, "note", text).await.unwrap();
+            }
+            let results = SearchManager::new(ctx.pool())
+                .with_event_types(vec!["decision".to_string()])
+                .search(query, false, true, None, None, false)
+                .await
+                .unwrap();
+            let types: Vec<&str> = results
+                .results
+                .iter()
+                .filter_map(|r| match r {
+                    SearchResult::Event { event, .. } => Some(event.log_type.as_str()),
+                    _ => None,
+                })
+                .collect();
+            assert!(types.iter().all(|t| *t == "decision"), "{}", query);
+            assert_eq!(results.total_events, types.len() as i64);
+        }
+    }
+
     #[test]
     fn test_needs_like_fallback_unicode_normalization() {
         // Test with different Unicode representations
@@ -0,0 +1,530 @@
+//! Semantic search over task specs and events (feature `semantic`)
+//!
+//! Keyword search misses "we already solved this" when the wording differs.
+//! Here every task (name and spec) and every normal event gets an embedding
+//! vector in the `embeddings` table, and a query is answered by cosine
+//! similarity against them.
+//!
+//! Vectors come from an `Embedder`:
+//! - `Embedder::Api`: any OpenAI-compatible `/embeddings` endpoint, configured
+//!   with `embedding.endpoint` / `embedding.model` (and optionally
+//!   `embedding.api_key`) or the matching `IE_EMBEDDING_*` variables
+//! - `Embedder::Local`: a dependency-free hashing model of words and
+//!   character trigrams. It runs offline and catches shared stems and
+//!   reordered phrasing, but not synonyms; configure an API model for that.
+//!
+//! The index is brought up to date lazily before each search: only items
+//! whose text or model changed since their vector was stored are embedded.
+
+use crate::cli_handlers::config_commands::config_get;
+use crate::db::models::{Event, PaginatedSearchResults, SearchResult};
+use crate::error::{IntentError, Result};
+use crate::tasks::TaskManager;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Dimensions of the local hashing model
+const LOCAL_DIMS: usize = 384;
+/// Texts sent to the embedding API per request
+const EMBED_BATCH_SIZE: usize = 64;
+
+const KIND_TASK: &str = "task";
+const KIND_EVENT: &str = "event";
+
+/// Configuration of an OpenAI-compatible embeddings API
+#[derive(Debug, Clone)]
+pub struct EmbeddingConfig {
+    pub endpoint: String,
+    pub api_key: Option<String>,
+    pub model: String,
+}
+
+impl EmbeddingConfig {
+    /// Resolve from env vars (priority) and workspace_state; None unless
+    /// both endpoint and model are set
+    pub async fn resolve(pool: &SqlitePool) -> Result<Option<Self>> {
+        let endpoint = resolve_field(pool, "IE_EMBEDDING_ENDPOINT", "embedding.endpoint").await?;
+        let model = resolve_field(pool, "IE_EMBEDDING_MODEL", "embedding.model").await?;
+        let api_key = resolve_field(pool, "IE_EMBEDDING_API_KEY", "embedding.api_key").await?;
+        match (endpoint, model) {
+            (Some(endpoint), Some(model)) => Ok(Some(Self {
+                endpoint,
+                api_key,
+                model,
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
+async fn resolve_field(pool: &SqlitePool, env_var: &str, key: &str) -> Result<Option<String>> {
+    if let Ok(val) = std::env::var(env_var) {
+        if !val.is_empty() {
+            return Ok(Some(val));
+        }
+    }
+    config_get(pool, key).await
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Source of embedding vectors
+pub enum Embedder {
+    Local,
+    Api {
+        config: EmbeddingConfig,
+        client: reqwest::Client,
+    },
+}
+
+impl Embedder {
+    /// The configured API model, or the local model when none is set
+    pub async fn from_pool(pool: &SqlitePool) -> Result<Self> {
+        let Some(config) = EmbeddingConfig::resolve(pool).await? else {
+            return Ok(Embedder::Local);
+        };
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| IntentError::OtherError(e.into()))?;
+        Ok(Embedder::Api { config, client })
+    }
+
+    /// Stored with every vector; vectors of another model are re-embedded
+    pub fn model_id(&self) -> String {
+        match self {
+            Embedder::Local => format!("local-hash-{}", LOCAL_DIMS),
+            Embedder::Api { config, .. } => config.model.clone(),
+        }
+    }
+
+    /// One unit-length vector per text
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let vectors = match self {
+            Embedder::Local => texts.iter().map(|t| hash_embedding(t)).collect(),
+            Embedder::Api { config, client } => {
+                let mut vectors = Vec::with_capacity(texts.len());
+                for batch in texts.chunks(EMBED_BATCH_SIZE) {
+                    vectors.extend(embed_api(config, client, batch).await?);
+                }
+                vectors
+            },
+        };
+        Ok(vectors.into_iter().map(normalize).collect())
+    }
+}
+
+async fn embed_api(
+    config: &EmbeddingConfig,
+    client: &reqwest::Client,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>> {
+    let mut request = client.post(&config.endpoint).json(&EmbeddingRequest {
+        model: &config.model,
+        input: texts,
+    });
+    if let Some(key) = &config.api_key {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| IntentError::OtherError(e.into()))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "(no body)".to_string());
+        return Err(IntentError::OtherError(anyhow::anyhow!(
+            "Embedding API error (HTTP {}): {}",
+            status,
+            body
+        )));
+    }
+
+    let mut data = response
+        .json::<EmbeddingResponse>()
+        .await
+        .map_err(|e| IntentError::OtherError(e.into()))?
+        .data;
+    if data.len() != texts.len() {
+        return Err(IntentError::OtherError(anyhow::anyhow!(
+            "Embedding API returned {} vectors for {} inputs",
+            data.len(),
+            texts.len()
+        )));
+    }
+    data.sort_by_key(|d| d.index);
+    Ok(data.into_iter().map(|d| d.embedding).collect())
+}
+
+/// Feature-hashed bag of words and character trigrams
+fn hash_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; LOCAL_DIMS];
+    let mut add = |feature: &str, weight: f32| {
+        let hash = fnv1a(feature.as_bytes());
+        let slot = (hash % LOCAL_DIMS as u64) as usize;
+        // A second hash bit as sign keeps collisions from only adding up
+        let sign = if hash & (1 << 63) == 0 { 1.0 } else { -1.0 };
+        vector[slot] += sign * weight;
+    };
+
+    let lower = text.to_lowercase();
+    for word in lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        add(word, 1.0);
+        let padded: Vec<char> = format!(" {} ", word).chars().collect();
+        for gram in padded.windows(3) {
+            add(&gram.iter().collect::<String>(), 0.5);
+        }
+    }
+    vector
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+    vector
+}
+
+fn to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Text embedded for a task
+fn task_text(name: &str, spec: Option<&str>) -> String {
+    match spec {
+        Some(spec) if !spec.is_empty() => format!("{}\n\n{}", name, spec),
+        _ => name.to_string(),
+    }
+}
+
+pub struct SemanticSearch<'a> {
+    pool: &'a SqlitePool,
+    embedder: Embedder,
+}
+
+impl<'a> SemanticSearch<'a> {
+    pub fn new(pool: &'a SqlitePool, embedder: Embedder) -> Self {
+        Self { pool, embedder }
+    }
+
+    /// Embed new and changed items and drop vectors of deleted ones
+    ///
+    /// Returns the number of items embedded.
+    pub async fn sync(&self) -> Result<usize> {
+        let model = self.embedder.model_id();
+
+        sqlx::query(
+            r#"
+            DELETE FROM embeddings
+            WHERE (kind = 'task' AND item_id NOT IN (SELECT id FROM tasks))
+               OR (kind = 'event' AND item_id NOT IN
+                    (SELECT id FROM events WHERE visibility = 'normal'))
+            "#,
+        )
+        .execute(self.pool)
+        .await?;
+
+        let stored: HashMap<(String, i64), (String, String)> =
+            sqlx::query_as::<_, (String, i64, String, String)>(
+                "SELECT kind, item_id, model, content_hash FROM embeddings",
+            )
+            .fetch_all(self.pool)
+            .await?
+            .into_iter()
+            .map(|(kind, id, model, hash)| ((kind, id), (model, hash)))
+            .collect();
+
+        let tasks: Vec<(i64, String, Option<String>)> =
+            sqlx::query_as("SELECT id, name, spec FROM tasks")
+                .fetch_all(self.pool)
+                .await?;
+        let events: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, discussion_data FROM events WHERE visibility = 'normal'")
+                .fetch_all(self.pool)
+                .await?;
+
+        let items = tasks
+            .into_iter()
+            .map(|(id, name, spec)| (KIND_TASK, id, task_text(&name, spec.as_deref())))
+            .chain(events.into_iter().map(|(id, data)| (KIND_EVENT, id, data)));
+        let mut pending = Vec::new();
+        for (kind, id, text) in items {
+            let hash = content_hash(&text);
+            let current = stored
+                .get(&(kind.to_string(), id))
+                .is_some_and(|(m, h)| *m == model && *h == hash);
+            if !current {
+                pending.push((kind, id, text, hash));
+            }
+        }
+
+        for batch in pending.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|(_, _, text, _)| text.clone()).collect();
+            let vectors = self.embedder.embed(&texts).await?;
+            let mut tx = self.pool.begin().await?;
+            for ((kind, id, _, hash), vector) in batch.iter().zip(vectors) {
+                sqlx::query(
+                    r#"
+                    INSERT INTO embeddings (kind, item_id, model, content_hash, vector)
+                    VALUES (?, ?, ?, ?, ?)
+                    ON CONFLICT(kind, item_id) DO UPDATE SET
+                        model = excluded.model,
+                        content_hash = excluded.content_hash,
+                        vector = excluded.vector
+                    "#,
+                )
+                .bind(kind)
+                .bind(id)
+                .bind(&model)
+                .bind(hash)
+                .bind(to_blob(&vector))
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+        }
+
+        Ok(pending.len())
+    }
+
+    /// Tasks and events most similar to `query`, best first
+    ///
+    /// `score` is the cosine similarity. Items are only considered once
+    /// `sync` has embedded them with the current model.
+    pub async fn search(
+        &self,
+        query: &str,
+        include_tasks: bool,
+        include_events: bool,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<PaginatedSearchResults> {
+        let limit = limit.unwrap_or(20);
+        let offset = offset.unwrap_or(0);
+        let mut empty = PaginatedSearchResults {
+            results: Vec::new(),
+            total_tasks: 0,
+            total_events: 0,
+            has_more: false,
+            limit,
+            offset,
+        };
+        if query.trim().is_empty() {
+            return Ok(empty);
+        }
+
+        let query_vector = self
+            .embedder
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .unwrap_or_default();
+
+        let rows: Vec<(String, i64, Vec<u8>)> =
+            sqlx::query_as("SELECT kind, item_id, vector FROM embeddings WHERE model = ?")
+                .bind(self.embedder.model_id())
+                .fetch_all(self.pool)
+                .await?;
+        let mut hits: Vec<(String, i64, f32)> = rows
+            .into_iter()
+            .filter(|(kind, _, _)| match kind.as_str() {
+                KIND_TASK => include_tasks,
+                KIND_EVENT => include_events,
+                _ => false,
+            })
+            .map(|(kind, id, blob)| {
+                let score = dot(&query_vector, &from_blob(&blob));
+                (kind, id, score)
+            })
+            .filter(|(_, _, score)| *score > 0.0)
+            .collect();
+        hits.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.1.cmp(&b.1)));
+
+        empty.total_tasks = hits.iter().filter(|h| h.0 == KIND_TASK).count() as i64;
+        empty.total_events = hits.len() as i64 - empty.total_tasks;
+        let mut results = empty;
+
+        let task_mgr = TaskManager::new(self.pool);
+        for (kind, id, score) in hits
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+        {
+            let score = score as f64;
+            if kind == KIND_TASK {
+                let task = task_mgr.get_task(id).await?;
+                let (match_field, match_snippet) = match &task.spec {
+                    Some(spec) if !spec.is_empty() => ("spec", spec.clone()),
+                    _ => ("name", task.name.clone()),
+                };
+                results.results.push(SearchResult::Task {
+                    task,
+                    match_snippet,
+                    match_field: match_field.to_string(),
+                    score,
+                });
+            } else {
+                let event: Event = sqlx::query_as(
+                    r#"
+                    SELECT id, task_id, timestamp, log_type, discussion_data, visibility
+                    FROM events WHERE id = ?
+                    "#,
+                )
+                .bind(id)
+                .fetch_one(self.pool)
+                .await?;
+                let task_chain = task_mgr.get_task_ancestry(event.task_id).await?;
+                results.results.push(SearchResult::Event {
+                    match_snippet: event.discussion_data.clone(),
+                    event,
+                    task_chain,
+                    score,
+                });
+            }
+        }
+
+        let total = results.total_tasks + results.total_events;
+        results.has_more = offset + (results.results.len() as i64) < total;
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[test]
+    fn test_hash_embedding_similarity() {
+        let embed = |t: &str| normalize(hash_embedding(t));
+        let query = embed("retry failed uploads");
+        let related = embed("Uploads that failed are retried with backoff");
+        let unrelated = embed("Dark mode for the settings page");
+        assert!(dot(&query, &related) > dot(&query, &unrelated));
+        assert!((dot(&query, &query) - 1.0).abs() < 1e-5);
+
+        let vector = embed("roundtrip");
+        assert_eq!(from_blob(&to_blob(&vector)), vector);
+    }
+
+    #[tokio::test]
+    async fn test_sync_and_search() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let upload = tasks
+            .add_task(
+                "Handle flaky uploads",
+                Some("Retry failed uploads with exponential backoff"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let theme = tasks
+            .add_task(
+                "Dark mode",
+                Some("Theme toggle in settings"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        EventManager::new(pool)
+            .add_event(
+                theme.id,
+                "decision",
+                "Uploading retries happen in the worker",
+            )
+            .await
+            .unwrap();
+
+        let search = SemanticSearch::new(pool, Embedder::Local);
+        assert_eq!(search.sync().await.unwrap(), 3);
+        // Nothing changed, nothing to embed
+        assert_eq!(search.sync().await.unwrap(), 0);
+
+        let results = search
+            .search("uploads retried after failure", true, true, None, None)
+            .await
+            .unwrap();
+        match &results.results[0] {
+            SearchResult::Task { task, score, .. } => {
+                assert_eq!(task.id, upload.id);
+                assert!(*score > 0.0);
+            },
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(results.total_events, 1);
+
+        // Edits are re-embedded, deletions dropped
+        tasks
+            .update_task(
+                theme.id,
+                crate::tasks::TaskUpdate {
+                    spec: Some("Contrast tweaks"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(search.sync().await.unwrap(), 1);
+        tasks.delete_task(upload.id).await.unwrap();
+        search.sync().await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM embeddings WHERE kind = 'task'")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,407 @@
+//! Reproducible sample data
+//!
+//! Fills an empty database with task trees, dependencies and events generated
+//! from a seed, so the Dashboard, reports and benchmarks can be worked on
+//! against the same data everywhere. The same profile and seed always give the
+//! same tasks, statuses, priorities, dependencies and event texts; only the
+//! timestamps follow the clock (events are spread over the 30 days before the
+//! run). `ie dev seed` (built with the `dev` feature) is the CLI for it.
+
+use crate::db::models::{EventVisibility, NewEvent};
+use crate::error::{IntentError, Result};
+use crate::events::EventManager;
+use crate::plan::{PlanExecutor, PlanRequest, PriorityValue, TaskStatus, TaskTree};
+use chrono::{Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::ops::RangeInclusive;
+
+/// Seed used when none is given
+pub const DEFAULT_SEED: u64 = 42;
+
+const AREAS: &[&str] = &[
+    "Auth",
+    "Billing",
+    "Search",
+    "Dashboard",
+    "Sync",
+    "Import",
+    "Notifications",
+    "Onboarding",
+    "Reporting",
+    "Storage",
+];
+const VERBS: &[&str] = &[
+    "Design",
+    "Implement",
+    "Test",
+    "Document",
+    "Review",
+    "Refactor",
+    "Benchmark",
+    "Migrate",
+];
+const OBJECTS: &[&str] = &[
+    "schema",
+    "API",
+    "settings page",
+    "cache",
+    "parser",
+    "index",
+    "retry logic",
+    "permissions",
+    "telemetry",
+    "error handling",
+];
+
+/// Shape of the generated data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeedProfile {
+    /// A handful of milestones with a few tasks each (~20 tasks)
+    Small,
+    /// Hundreds of milestones, some tasks with subtasks (~6,000 tasks)
+    Large,
+    /// A few milestones whose first task nests 24 levels deep
+    Deep,
+}
+
+impl SeedProfile {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SeedProfile::Small => "small",
+            SeedProfile::Large => "large",
+            SeedProfile::Deep => "deep",
+        }
+    }
+
+    fn shape(self) -> Shape {
+        match self {
+            SeedProfile::Small => Shape {
+                milestones: 5,
+                children: 2..=5,
+                depth: 1,
+                spine: false,
+                events: 0..=3,
+            },
+            SeedProfile::Large => Shape {
+                milestones: 250,
+                children: 4..=10,
+                depth: 2,
+                spine: false,
+                events: 1..=4,
+            },
+            SeedProfile::Deep => Shape {
+                milestones: 3,
+                children: 2..=3,
+                depth: 24,
+                spine: true,
+                events: 0..=2,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for SeedProfile {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "small" => Ok(Self::Small),
+            "large" => Ok(Self::Large),
+            "deep" => Ok(Self::Deep),
+            other => Err(IntentError::InvalidInput(format!(
+                "Invalid seed profile '{}'. Expected small, large or deep",
+                other
+            ))),
+        }
+    }
+}
+
+struct Shape {
+    /// Root tasks
+    milestones: usize,
+    /// Children of a task that has any
+    children: RangeInclusive<usize>,
+    /// Levels below the milestones
+    depth: usize,
+    /// Whether only the first child of a task has children (a long chain)
+    /// instead of about a third of them
+    spine: bool,
+    events: RangeInclusive<usize>,
+}
+
+/// What `seed` created
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeedSummary {
+    pub profile: SeedProfile,
+    pub seed: u64,
+    pub tasks: usize,
+    pub dependencies: usize,
+    pub events: usize,
+}
+
+/// The plan a profile and seed generate: milestones with nested tasks, the
+/// first 40% of milestones done, one in progress, the rest still todo
+pub fn plan(profile: SeedProfile, seed: u64) -> PlanRequest {
+    let shape = profile.shape();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut milestones: Vec<TaskTree> = (0..shape.milestones)
+        .map(|m| {
+            let area = AREAS[m % AREAS.len()];
+            let path = (m + 1).to_string();
+            let mut milestone = TaskTree {
+                name: Some(format!("{} {}", path, area)),
+                spec: Some(format!(
+                    "Milestone {} of the {} work.\n\nDone when every task below is done.",
+                    path, area
+                )),
+                priority: Some(priority(&mut rng)),
+                ..Default::default()
+            };
+            let children = children(&mut rng, &shape, area, &path, 1, true);
+            if !children.is_empty() {
+                milestone.children = Some(children);
+            }
+            milestone
+        })
+        .collect();
+
+    for m in 1..milestones.len() {
+        if rng.random_bool(0.5) {
+            let previous = milestones[m - 1].name.clone();
+            milestones[m].depends_on = previous.map(|name| vec![name]);
+        }
+    }
+
+    // Done milestones come first, so dependencies never point from a started
+    // task to an unfinished one; in the active milestone a prefix of the
+    // children is done and the next one is in progress
+    let finished = milestones.len() * 2 / 5;
+    for milestone in &mut milestones[..finished] {
+        set_status(milestone, TaskStatus::Done);
+    }
+    if let Some(active) = milestones.get_mut(finished) {
+        if let Some(children) = active.children.as_mut() {
+            let done = rng.random_range(0..children.len());
+            for child in &mut children[..done] {
+                set_status(child, TaskStatus::Done);
+            }
+            children[done].status = Some(TaskStatus::Doing);
+        }
+    }
+
+    PlanRequest {
+        tasks: milestones,
+        ignore_wip_limits: true,
+        ..Default::default()
+    }
+}
+
+fn children(
+    rng: &mut StdRng,
+    shape: &Shape,
+    area: &str,
+    parent_path: &str,
+    level: usize,
+    on_spine: bool,
+) -> Vec<TaskTree> {
+    let has_children = level <= shape.depth
+        && (level == 1
+            || if shape.spine {
+                on_spine
+            } else {
+                rng.random_bool(1.0 / 3.0)
+            });
+    let count = if has_children {
+        rng.random_range(shape.children.clone())
+    } else {
+        0
+    };
+
+    let mut tasks: Vec<TaskTree> = (0..count)
+        .map(|i| {
+            let path = format!("{}.{}", parent_path, i + 1);
+            let verb = VERBS[rng.random_range(0..VERBS.len())];
+            let object = OBJECTS[rng.random_range(0..OBJECTS.len())];
+            let nested = children(rng, shape, area, &path, level + 1, on_spine && i == 0);
+            TaskTree {
+                name: Some(format!("{} {} {}", path, verb, object)),
+                spec: Some(format!(
+                    "{} the {} for {}.\n\nDone when it is covered by tests.",
+                    verb, object, area
+                )),
+                priority: Some(priority(rng)),
+                estimate_minutes: nested.is_empty().then(|| rng.random_range(1..=16) * 30),
+                children: (!nested.is_empty()).then_some(nested),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    for i in 1..tasks.len() {
+        if rng.random_bool(1.0 / 3.0) {
+            let previous = tasks[i - 1].name.clone();
+            tasks[i].depends_on = previous.map(|name| vec![name]);
+        }
+    }
+    tasks
+}
+
+fn priority(rng: &mut StdRng) -> PriorityValue {
+    match rng.random_range(0..10) {
+        0 => PriorityValue::Critical,
+        1..=3 => PriorityValue::High,
+        4..=7 => PriorityValue::Medium,
+        _ => PriorityValue::Low,
+    }
+}
+
+fn set_status(task: &mut TaskTree, status: TaskStatus) {
+    for child in task.children.iter_mut().flatten() {
+        set_status(child, status.clone());
+    }
+    task.status = Some(status);
+}
+
+fn names(tasks: &[TaskTree], out: &mut Vec<String>) {
+    for task in tasks {
+        out.extend(task.name.clone());
+        names(task.children.as_deref().unwrap_or_default(), out);
+    }
+}
+
+/// Fill an empty database with the data of `profile` and `seed`
+pub async fn seed(pool: &SqlitePool, profile: SeedProfile, seed: u64) -> Result<SeedSummary> {
+    let existing: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+        .fetch_one(pool)
+        .await?;
+    if existing > 0 {
+        return Err(IntentError::InvalidInput(format!(
+            "The database already has {} task(s); seed data goes into an empty project",
+            existing
+        )));
+    }
+
+    let request = plan(profile, seed);
+    let result = PlanExecutor::new(pool).execute(&request).await?;
+    if !result.success {
+        return Err(IntentError::OtherError(anyhow::anyhow!(
+            "Seed plan failed: {}",
+            result.error.unwrap_or_default()
+        )));
+    }
+
+    // Events get their own generator, so changing them leaves the tasks alone
+    let shape = profile.shape();
+    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(1));
+    let mut task_names = Vec::new();
+    names(&request.tasks, &mut task_names);
+    let now = Utc::now();
+    let mut events = Vec::new();
+    for name in &task_names {
+        let Some(&task_id) = result.task_id_map.get(name) else {
+            continue;
+        };
+        let count = rng.random_range(shape.events.clone());
+        let mut minutes_ago: Vec<i64> = (0..count)
+            .map(|_| rng.random_range(1..30 * 24 * 60))
+            .collect();
+        minutes_ago.sort_unstable_by(|a, b| b.cmp(a));
+        for minutes in minutes_ago {
+            let (log_type, text) = event_text(&mut rng, name);
+            events.push(NewEvent {
+                task_id,
+                log_type: log_type.to_string(),
+                discussion_data: text,
+                visibility: EventVisibility::Normal,
+                timestamp: Some(now - Duration::minutes(minutes)),
+            });
+        }
+    }
+    EventManager::new(pool).add_events(&events).await?;
+
+    Ok(SeedSummary {
+        profile,
+        seed,
+        tasks: result.created_count,
+        dependencies: result.dependency_count,
+        events: events.len(),
+    })
+}
+
+fn event_text(rng: &mut StdRng, task: &str) -> (&'static str, String) {
+    let object = OBJECTS[rng.random_range(0..OBJECTS.len())];
+    match rng.random_range(0..20) {
+        0..=11 => (
+            "note",
+            format!("Progress on '{}': the {} part is in place", task, object),
+        ),
+        12..=16 => (
+            "decision",
+            format!(
+                "Keep the {} as it is for now; revisit after '{}'",
+                object, task
+            ),
+        ),
+        17..=18 => (
+            "blocker",
+            format!("Waiting for the {} change before continuing", object),
+        ),
+        _ => ("milestone", format!("'{}' reached review", task)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[test]
+    fn test_same_seed_same_plan() {
+        for profile in [SeedProfile::Small, SeedProfile::Large, SeedProfile::Deep] {
+            assert_eq!(plan(profile, 7), plan(profile, 7));
+        }
+        assert_ne!(plan(SeedProfile::Small, 7), plan(SeedProfile::Small, 8));
+    }
+
+    #[test]
+    fn test_deep_profile_nests_deeply() {
+        fn depth(task: &TaskTree) -> usize {
+            1 + task.children.iter().flatten().map(depth).max().unwrap_or(0)
+        }
+        let request = plan(SeedProfile::Deep, DEFAULT_SEED);
+        assert_eq!(request.tasks.iter().map(depth).max(), Some(25));
+    }
+
+    #[tokio::test]
+    async fn test_seed_fills_empty_database() {
+        let ctx = TestContext::new().await;
+        let summary = seed(ctx.pool(), SeedProfile::Small, DEFAULT_SEED)
+            .await
+            .unwrap();
+
+        let (tasks, done, doing): (i64, i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*), SUM(status = 'done'), SUM(status = 'doing') FROM tasks",
+        )
+        .fetch_one(ctx.pool())
+        .await
+        .unwrap();
+        assert_eq!(tasks as usize, summary.tasks);
+        assert!(done > 0);
+        assert_eq!(doing, 1);
+        let events: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events")
+            .fetch_one(ctx.pool())
+            .await
+            .unwrap();
+        assert!(events as usize >= summary.events);
+
+        let err = seed(ctx.pool(), SeedProfile::Small, DEFAULT_SEED)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("empty project"));
+    }
+}
@@ -1,10 +1,27 @@
-use crate::db::models::{TaskSortBy, WorkspaceStats};
+use crate::compaction::DIGEST_LOG_TYPE;
+use crate::db::models::{Event, Handoff, SubtreeStats, Task, TaskSortBy, WorkspaceStats};
 use crate::error::Result;
 use crate::events::EventManager;
+use crate::focus::FocusStack;
+use crate::handoff::HandoffManager;
 use crate::tasks::TaskManager;
 use crate::workspace::WorkspaceManager;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// Rough characters per token, used to turn a token budget into characters
+pub const CHARS_PER_TOKEN: usize = 4;
+/// Budget of `RestoreBudget::default()`
+pub const DEFAULT_BUDGET_TOKENS: usize = 2000;
+
+/// Most recent events of the focused task considered for a bundle
+const BUNDLE_EVENT_CANDIDATES: i64 = 200;
+/// Pending tasks listed in a bundle when nothing is focused
+const BUNDLE_PENDING_TASKS: i64 = 10;
+/// Characters kept of each event and of non-focused task specs
+const BUNDLE_EVENT_CHARS: usize = 600;
+const BUNDLE_SPEC_CHARS: usize = 200;
 
 /// Session restoration status
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -95,8 +112,17 @@ pub struct SessionRestoreResult {
     pub siblings: Option<SiblingsInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<ChildrenInfo>,
+    /// Rollup over the focused task's whole subtree, when it has children
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtree: Option<SubtreeStats>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recent_events: Option<Vec<EventInfo>>,
+    /// Latest handoff note left by a previous session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_handoff: Option<Handoff>,
+    /// Tasks interrupted with `ie focus push`, top first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_stack: Option<Vec<TaskInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub suggested_commands: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -115,6 +141,102 @@ pub struct SessionRestoreResult {
     pub recovery_suggestion: Option<String>,
 }
 
+/// Size limit of a `RestoreBundle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreBudget {
+    Chars(usize),
+    /// Converted at `CHARS_PER_TOKEN` characters per token
+    Tokens(usize),
+}
+
+impl RestoreBudget {
+    pub fn max_chars(self) -> usize {
+        match self {
+            RestoreBudget::Chars(n) => n,
+            RestoreBudget::Tokens(n) => n.saturating_mul(CHARS_PER_TOKEN),
+        }
+    }
+}
+
+impl Default for RestoreBudget {
+    fn default() -> Self {
+        RestoreBudget::Tokens(DEFAULT_BUDGET_TOKENS)
+    }
+}
+
+/// Why an item is in a restore bundle; variants are in ranking order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreItemKind {
+    /// The focused task with its full spec
+    FocusedTask,
+    /// The latest handoff note left by a previous session
+    Handoff,
+    /// An unfinished task the focused task waits on, directly or transitively
+    BlockingTask,
+    /// A task interrupted with `ie focus push`, waiting on the focus stack
+    SuspendedTask,
+    Decision,
+    /// A digest written by event compaction
+    Digest,
+    Blocker,
+    /// Any other event of the focused task
+    Event,
+    ParentTask,
+    ChildTask,
+    /// A todo task, listed when nothing is focused
+    PendingTask,
+}
+
+impl RestoreItemKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RestoreItemKind::FocusedTask => "focused_task",
+            RestoreItemKind::Handoff => "handoff",
+            RestoreItemKind::BlockingTask => "blocking_task",
+            RestoreItemKind::SuspendedTask => "suspended_task",
+            RestoreItemKind::Decision => "decision",
+            RestoreItemKind::Digest => "digest",
+            RestoreItemKind::Blocker => "blocker",
+            RestoreItemKind::Event => "event",
+            RestoreItemKind::ParentTask => "parent_task",
+            RestoreItemKind::ChildTask => "child_task",
+            RestoreItemKind::PendingTask => "pending_task",
+        }
+    }
+}
+
+/// One piece of context in a restore bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreItem {
+    pub kind: RestoreItemKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handoff_id: Option<i64>,
+    pub text: String,
+    /// Only the focused task is ever cut short to fit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Context for resuming work, ranked and cut to a budget
+///
+/// Items are in ranking order. Items that did not fit are counted in
+/// `omitted`; smaller items ranked below them may still be included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreBundle {
+    pub status: SessionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focused_task_id: Option<i64>,
+    pub budget_chars: usize,
+    pub used_chars: usize,
+    pub items: Vec<RestoreItem>,
+    pub omitted: usize,
+}
+
 /// Session restoration manager
 pub struct SessionRestoreManager<'a> {
     pool: &'a SqlitePool,
@@ -210,6 +332,11 @@ impl<'a> SessionRestoreManager<'a> {
                 .await?;
             Self::build_children_info(&result.tasks)
         };
+        let subtree = if children.is_some() {
+            Some(task_mgr.get_subtree_stats(current_task_id).await?)
+        } else {
+            None
+        };
 
         // Get recent events
         let events = event_mgr
@@ -235,7 +362,10 @@ impl<'a> SessionRestoreManager<'a> {
             parent_task,
             siblings,
             children,
+            subtree,
             recent_events: Some(recent_events),
+            last_handoff: HandoffManager::new(self.pool).latest().await?,
+            focus_stack: self.focus_stack().await?,
             suggested_commands: Some(suggested_commands),
             stats: None,
             recommended_task: None,
@@ -246,6 +376,200 @@ impl<'a> SessionRestoreManager<'a> {
         })
     }
 
+    /// Build a `RestoreBundle` for `task_id` (default: the focused task)
+    ///
+    /// Candidates are ranked: the task itself, the latest handoff, the chain
+    /// of unfinished tasks it is blocked on, the tasks on the focus stack,
+    /// its decisions (newest first), digests, blockers, other events, then
+    /// its parent and children. Without a task, the bundle lists the focus
+    /// stack and pending tasks by priority.
+    pub async fn restore_bundle(
+        &self,
+        task_id: Option<i64>,
+        budget: RestoreBudget,
+    ) -> Result<RestoreBundle> {
+        let task_id = match task_id {
+            Some(id) => Some(id),
+            None => {
+                WorkspaceManager::new(self.pool)
+                    .get_current_task(None)
+                    .await?
+                    .current_task_id
+            },
+        };
+
+        let (status, mut candidates) = match task_id {
+            Some(id) => (SessionStatus::Success, self.focus_candidates(id).await?),
+            None => (SessionStatus::NoFocus, self.pending_candidates().await?),
+        };
+        if let Some(handoff) = HandoffManager::new(self.pool).latest().await? {
+            // Right after the focused task, which is always first
+            candidates.insert(usize::from(task_id.is_some()), handoff_item(&handoff));
+        }
+
+        Ok(Self::fill_bundle(status, task_id, candidates, budget))
+    }
+
+    async fn focus_candidates(&self, task_id: i64) -> Result<Vec<RestoreItem>> {
+        let task_mgr = TaskManager::new(self.pool);
+        let event_mgr = EventManager::new(self.pool);
+        let task = task_mgr.get_task(task_id).await?;
+
+        let mut items = vec![RestoreItem {
+            kind: RestoreItemKind::FocusedTask,
+            task_id: Some(task_id),
+            event_id: None,
+            handoff_id: None,
+            text: match task.spec.as_deref() {
+                Some(spec) if !spec.is_empty() => format!("{}\n{}", task_line(&task), spec),
+                _ => task_line(&task),
+            },
+            truncated: false,
+        }];
+
+        // Breadth-first, so direct blockers rank above transitive ones
+        let mut seen = HashSet::from([task_id]);
+        let mut frontier = vec![task_id];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for id in frontier {
+                for blocking in task_mgr.get_blocking_tasks(id).await? {
+                    if blocking.status == "done" || !seen.insert(blocking.id) {
+                        continue;
+                    }
+                    next.push(blocking.id);
+                    items.push(task_item(RestoreItemKind::BlockingTask, &blocking, true));
+                }
+            }
+            frontier = next;
+        }
+        for suspended in FocusStack::new(self.pool).tasks().await? {
+            items.push(task_item(RestoreItemKind::SuspendedTask, &suspended, false));
+        }
+
+        let events = event_mgr
+            .list_events(Some(task_id), Some(BUNDLE_EVENT_CANDIDATES), None, None)
+            .await?;
+        let mut ranked: Vec<(RestoreItemKind, &Event)> = events
+            .iter()
+            .map(|e| {
+                let kind = match e.log_type.as_str() {
+                    "decision" => RestoreItemKind::Decision,
+                    DIGEST_LOG_TYPE => RestoreItemKind::Digest,
+                    "blocker" => RestoreItemKind::Blocker,
+                    _ => RestoreItemKind::Event,
+                };
+                (kind, e)
+            })
+            .collect();
+        // Stable: events keep their newest-first order within a kind
+        ranked.sort_by_key(|(kind, _)| *kind);
+        items.extend(ranked.into_iter().map(|(kind, e)| RestoreItem {
+            kind,
+            task_id: Some(task_id),
+            event_id: Some(e.id),
+            handoff_id: None,
+            // The kind already names decisions, digests and blockers
+            text: format!(
+                "{}{}: {}",
+                e.timestamp.format("%Y-%m-%d %H:%M"),
+                if kind == RestoreItemKind::Event {
+                    format!(" {}", e.log_type)
+                } else {
+                    String::new()
+                },
+                truncate_chars(&e.discussion_data, BUNDLE_EVENT_CHARS)
+            ),
+            truncated: false,
+        }));
+
+        if let Some(parent_id) = task.parent_id {
+            let parent = task_mgr.get_task(parent_id).await?;
+            items.push(task_item(RestoreItemKind::ParentTask, &parent, true));
+        }
+        for child in task_mgr.get_children(task_id).await? {
+            items.push(task_item(RestoreItemKind::ChildTask, &child, false));
+        }
+
+        Ok(items)
+    }
+
+    async fn pending_candidates(&self) -> Result<Vec<RestoreItem>> {
+        let mut items: Vec<RestoreItem> = FocusStack::new(self.pool)
+            .tasks()
+            .await?
+            .iter()
+            .map(|t| task_item(RestoreItemKind::SuspendedTask, t, false))
+            .collect();
+        let pending = TaskManager::new(self.pool)
+            .find_tasks(
+                Some("todo"),
+                None,
+                Some(TaskSortBy::Priority),
+                Some(BUNDLE_PENDING_TASKS),
+                None,
+            )
+            .await?;
+        items.extend(
+            pending
+                .tasks
+                .iter()
+                .map(|t| task_item(RestoreItemKind::PendingTask, t, true)),
+        );
+        Ok(items)
+    }
+
+    /// The session's focus stack, top first; `None` when empty
+    async fn focus_stack(&self) -> Result<Option<Vec<TaskInfo>>> {
+        let tasks = FocusStack::new(self.pool).tasks().await?;
+        Ok((!tasks.is_empty()).then(|| {
+            tasks
+                .into_iter()
+                .map(|t| TaskInfo {
+                    id: t.id,
+                    name: t.name,
+                    status: Some(t.status),
+                })
+                .collect()
+        }))
+    }
+
+    /// Take candidates in order while they fit; the focused task is always
+    /// included, truncated if needed
+    fn fill_bundle(
+        status: SessionStatus,
+        focused_task_id: Option<i64>,
+        candidates: Vec<RestoreItem>,
+        budget: RestoreBudget,
+    ) -> RestoreBundle {
+        let budget_chars = budget.max_chars();
+        let mut bundle = RestoreBundle {
+            status,
+            focused_task_id,
+            budget_chars,
+            used_chars: 0,
+            items: Vec::new(),
+            omitted: 0,
+        };
+
+        for mut item in candidates {
+            let remaining = budget_chars - bundle.used_chars;
+            let size = item.text.chars().count();
+            if size > remaining {
+                if item.kind != RestoreItemKind::FocusedTask {
+                    bundle.omitted += 1;
+                    continue;
+                }
+                item.text = item.text.chars().take(remaining).collect();
+                item.truncated = true;
+            }
+            bundle.used_chars += size.min(remaining);
+            bundle.items.push(item);
+        }
+
+        bundle
+    }
+
     /// Restore when no focus exists
     ///
     /// Uses efficient SQL aggregation for stats and limited queries for task lists.
@@ -287,10 +611,15 @@ impl<'a> SessionRestoreManager<'a> {
             })
             .collect();
 
-        let suggested_commands = vec![
+        // A finished interruption leaves the focus empty and the stack full
+        let focus_stack = self.focus_stack().await?;
+        let mut suggested_commands = vec![
             "ie pick-next".to_string(),
             "ie task list --status todo".to_string(),
         ];
+        if focus_stack.is_some() {
+            suggested_commands.insert(0, "ie focus pop".to_string());
+        }
 
         Ok(SessionRestoreResult {
             status: SessionStatus::NoFocus,
@@ -299,7 +628,10 @@ impl<'a> SessionRestoreManager<'a> {
             parent_task: None,
             siblings: None,
             children: None,
+            subtree: None,
             recent_events: None,
+            last_handoff: HandoffManager::new(self.pool).latest().await?,
+            focus_stack,
             suggested_commands: Some(suggested_commands),
             stats: Some(stats),
             recommended_task,
@@ -420,7 +752,10 @@ impl<'a> SessionRestoreManager<'a> {
             parent_task: None,
             siblings: None,
             children: None,
+            subtree: None,
             recent_events: None,
+            last_handoff: None,
+            focus_stack: None,
             suggested_commands: Some(suggested_commands),
             stats: None,
             recommended_task: None,
@@ -432,6 +767,58 @@ impl<'a> SessionRestoreManager<'a> {
     }
 }
 
+/// "#12 Name [status]"
+fn task_line(task: &Task) -> String {
+    format!("#{} {} [{}]", task.id, task.name, task.status)
+}
+
+fn task_item(kind: RestoreItemKind, task: &Task, with_spec: bool) -> RestoreItem {
+    let text = match task.spec.as_deref() {
+        Some(spec) if with_spec && !spec.is_empty() => format!(
+            "{}\n{}",
+            task_line(task),
+            truncate_chars(spec, BUNDLE_SPEC_CHARS)
+        ),
+        _ => task_line(task),
+    };
+    RestoreItem {
+        kind,
+        task_id: Some(task.id),
+        event_id: None,
+        handoff_id: None,
+        text,
+        truncated: false,
+    }
+}
+
+fn handoff_item(handoff: &Handoff) -> RestoreItem {
+    let mut text = format!(
+        "{}: {}",
+        handoff.created_at.format("%Y-%m-%d %H:%M"),
+        handoff.summary
+    );
+    if let Some(next_steps) = &handoff.next_steps {
+        text.push_str(&format!("\nNext: {}", next_steps));
+    }
+    RestoreItem {
+        kind: RestoreItemKind::Handoff,
+        task_id: handoff.task_id,
+        event_id: None,
+        handoff_id: Some(handoff.id),
+        text,
+        truncated: false,
+    }
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars).collect();
+    cut.push_str("...");
+    cut
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -806,4 +1193,87 @@ mod tests {
         let result = SessionRestoreManager::build_children_info(&children);
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn test_restore_bundle_ranking_and_budget() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let task_mgr = TaskManager::new(pool);
+        let event_mgr = EventManager::new(pool);
+
+        let focused = task_mgr
+            .add_task("Ship sync", Some("Sync spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let schema = task_mgr
+            .add_task("Schema", Some("Schema spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let design = task_mgr
+            .add_task("Design", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .add_dependency(schema.id, focused.id)
+            .await
+            .unwrap();
+        task_mgr.add_dependency(design.id, schema.id).await.unwrap();
+        WorkspaceManager::new(pool)
+            .set_current_task(focused.id, None)
+            .await
+            .unwrap();
+
+        event_mgr
+            .add_event(focused.id, "note", "A note")
+            .await
+            .unwrap();
+        event_mgr
+            .add_event(focused.id, "decision", "Use CRDTs")
+            .await
+            .unwrap();
+
+        HandoffManager::new(pool)
+            .create(None, "Paused mid-migration", Some("Finish schema"))
+            .await
+            .unwrap();
+
+        let restore_mgr = SessionRestoreManager::new(pool);
+        let bundle = restore_mgr
+            .restore_bundle(None, RestoreBudget::default())
+            .await
+            .unwrap();
+        assert_eq!(bundle.focused_task_id, Some(focused.id));
+        let kinds: Vec<(RestoreItemKind, Option<i64>)> =
+            bundle.items.iter().map(|i| (i.kind, i.task_id)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (RestoreItemKind::FocusedTask, Some(focused.id)),
+                (RestoreItemKind::Handoff, None),
+                (RestoreItemKind::BlockingTask, Some(schema.id)),
+                (RestoreItemKind::BlockingTask, Some(design.id)),
+                (RestoreItemKind::Decision, Some(focused.id)),
+                (RestoreItemKind::Event, Some(focused.id)),
+            ]
+        );
+        assert!(bundle.items[1].text.ends_with("Next: Finish schema"));
+        assert_eq!(bundle.omitted, 0);
+
+        // A tight budget keeps the focused task and drops what does not fit
+        let bundle = restore_mgr
+            .restore_bundle(Some(focused.id), RestoreBudget::Chars(45))
+            .await
+            .unwrap();
+        assert!(bundle.used_chars <= 45);
+        assert_eq!(bundle.items[0].kind, RestoreItemKind::FocusedTask);
+        assert!(bundle.omitted > 0);
+
+        let bundle = restore_mgr
+            .restore_bundle(Some(focused.id), RestoreBudget::Chars(5))
+            .await
+            .unwrap();
+        assert_eq!(bundle.items.len(), 1);
+        assert!(bundle.items[0].truncated);
+        assert_eq!(bundle.used_chars, 5);
+    }
 }
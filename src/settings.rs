@@ -0,0 +1,880 @@
+//! Project settings: schema, validation and audit trail
+//!
+//! Most settings live in the `workspace_state` key-value store (the same one
+//! `ie config` uses). The capacity and policy settings are keys of the
+//! project's config.toml (see `crate::config`) and are read from and written
+//! to that file, so there is one place for each value. This module describes
+//! the keys intent-engine understands, validates values written to them, and
+//! records every change in `settings_audit`.
+//!
+//! Consumers read settings at the moment they need them, so a change takes
+//! effect immediately — unless an environment variable overrides it.
+
+use crate::cli_handlers::config_commands::{config_get, mask_value};
+use crate::config::{ConfigEditor, ProjectConfig};
+use crate::error::{IntentError, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Value type of a setting, used for validation and by the Dashboard form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingKind {
+    Bool,
+    Text,
+    Url,
+    /// Non-negative decimal number
+    Number,
+    /// Text that is never shown or audited in clear
+    Secret,
+    /// Comma-separated values
+    List,
+}
+
+/// Where a setting is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingStore {
+    /// The `workspace_state` table
+    State,
+    /// The project's `.intent-engine/config.toml`
+    ConfigFile,
+}
+
+/// Schema entry for a known setting
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingSpec {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub category: &'static str,
+    pub description: &'static str,
+    /// Effective value when the key is not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<&'static str>,
+    /// Environment variable that takes priority over the stored value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_var: Option<&'static str>,
+    pub store: SettingStore,
+}
+
+/// Whether completed AI-owned tasks wait for `ie task approve`
+pub const REVIEW_REQUIRED: &str = "review.required";
+
+/// Every setting intent-engine reads
+pub const SETTINGS: &[SettingSpec] = &[
+    SettingSpec {
+        key: "llm.endpoint",
+        kind: SettingKind::Url,
+        category: "llm",
+        description: "OpenAI-compatible chat completions URL",
+        default: None,
+        env_var: Some("IE_LLM_ENDPOINT"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "llm.api_key",
+        kind: SettingKind::Secret,
+        category: "llm",
+        description: "API key sent to the LLM endpoint",
+        default: None,
+        env_var: Some("IE_LLM_API_KEY"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "llm.model",
+        kind: SettingKind::Text,
+        category: "llm",
+        description: "Model name sent to the LLM endpoint",
+        default: None,
+        env_var: Some("IE_LLM_MODEL"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "embedding.endpoint",
+        kind: SettingKind::Url,
+        category: "search",
+        description: "OpenAI-compatible embeddings URL for semantic search",
+        default: None,
+        env_var: Some("IE_EMBEDDING_ENDPOINT"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "embedding.api_key",
+        kind: SettingKind::Secret,
+        category: "search",
+        description: "API key sent to the embeddings endpoint, if it needs one",
+        default: None,
+        env_var: Some("IE_EMBEDDING_API_KEY"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "embedding.model",
+        kind: SettingKind::Text,
+        category: "search",
+        description: "Embedding model name; without it the built-in local model is used",
+        default: None,
+        env_var: Some("IE_EMBEDDING_MODEL"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "notify.desktop",
+        kind: SettingKind::Bool,
+        category: "notifications",
+        description: "Show desktop notifications for this project",
+        default: Some("false"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "notify.desktop.unblocked",
+        kind: SettingKind::Bool,
+        category: "notifications",
+        description: "Notify when a human-owned task's last blocker is done",
+        default: Some("true"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "notify.desktop.review_requested",
+        kind: SettingKind::Bool,
+        category: "notifications",
+        description: "Notify when an AI asks you to review or complete a task",
+        default: Some("true"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "notify.desktop.plan_failed",
+        kind: SettingKind::Bool,
+        category: "notifications",
+        description: "Notify when a plan is rejected or fails to apply",
+        default: Some("true"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "notify.desktop.focus_ended",
+        kind: SettingKind::Bool,
+        category: "notifications",
+        description: "Notify when the time of a focus session is up",
+        default: Some("true"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "review.required",
+        kind: SettingKind::Bool,
+        category: "workflow",
+        description: "Completed AI-owned tasks wait in review until a human approves them",
+        default: Some("false"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "priority.aging.curve",
+        kind: SettingKind::Text,
+        category: "workflow",
+        description: "How waiting todo tasks gain priority: linear, log or off",
+        default: Some("linear"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "priority.aging.days",
+        kind: SettingKind::Number,
+        category: "workflow",
+        description: "Days of waiting that raise a todo task by one priority level",
+        default: Some("30"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "priority.aging.max_boost",
+        kind: SettingKind::Number,
+        category: "workflow",
+        description: "Most priority levels a todo task can gain by waiting",
+        default: Some("2"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "stale.days",
+        kind: SettingKind::Number,
+        category: "workflow",
+        description:
+            "Days without activity after which a doing task is flagged stale; 0 turns it off",
+        default: Some("14"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "stale.demote",
+        kind: SettingKind::Bool,
+        category: "workflow",
+        description: "Move stale doing tasks back to todo",
+        default: Some("false"),
+        env_var: None,
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "otel.endpoint",
+        kind: SettingKind::Url,
+        category: "telemetry",
+        description: "OTLP/HTTP collector receiving the task timeline as traces",
+        default: None,
+        env_var: Some("OTEL_EXPORTER_OTLP_ENDPOINT"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "otel.service_name",
+        kind: SettingKind::Text,
+        category: "telemetry",
+        description: "service.name of exported traces",
+        default: Some("intent-engine"),
+        env_var: Some("OTEL_SERVICE_NAME"),
+        store: SettingStore::State,
+    },
+    SettingSpec {
+        key: "tasks.capacity_limit",
+        kind: SettingKind::Number,
+        category: "capacity",
+        description: "Most tasks in doing across the project",
+        default: None,
+        env_var: None,
+        store: SettingStore::ConfigFile,
+    },
+    SettingSpec {
+        key: "policy.max_doing_per_parent",
+        kind: SettingKind::Number,
+        category: "capacity",
+        description: "Most subtasks of one parent in doing at a time",
+        default: None,
+        env_var: None,
+        store: SettingStore::ConfigFile,
+    },
+    SettingSpec {
+        key: "policy.min_spec_chars",
+        kind: SettingKind::Number,
+        category: "policy",
+        description: "Shortest spec a task may have when it starts",
+        default: None,
+        env_var: None,
+        store: SettingStore::ConfigFile,
+    },
+    SettingSpec {
+        key: "policy.done_requires_event",
+        kind: SettingKind::List,
+        category: "policy",
+        description: "Event types of which a task needs at least one to be done",
+        default: None,
+        env_var: None,
+        store: SettingStore::ConfigFile,
+    },
+];
+
+/// Look up the schema entry for a key
+pub fn find_spec(key: &str) -> Option<&'static SettingSpec> {
+    SETTINGS.iter().find(|spec| spec.key == key)
+}
+
+/// Parse a boolean setting value (`true/false`, `1/0`, `yes/no`, `on/off`)
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+impl SettingSpec {
+    /// Validate a value for this setting and return its normalized form
+    pub fn validate(&self, value: &str) -> std::result::Result<String, String> {
+        match self.kind {
+            SettingKind::Bool => parse_bool(value)
+                .map(|b| b.to_string())
+                .ok_or_else(|| format!("expected true or false, got '{}'", value)),
+            SettingKind::Url => {
+                let url = reqwest::Url::parse(value.trim())
+                    .map_err(|e| format!("invalid URL '{}': {}", value, e))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err(format!("URL must use http or https, got '{}'", value));
+                }
+                Ok(value.trim().to_string())
+            },
+            // config.toml only holds whole numbers
+            SettingKind::Number if self.store == SettingStore::ConfigFile => {
+                match value.trim().parse::<u32>() {
+                    Ok(n) => Ok(n.to_string()),
+                    _ => Err(format!("expected a whole number, got '{}'", value)),
+                }
+            },
+            SettingKind::Number => match value.trim().parse::<f64>() {
+                Ok(n) if n.is_finite() && n >= 0.0 => Ok(value.trim().to_string()),
+                _ => Err(format!("expected a non-negative number, got '{}'", value)),
+            },
+            SettingKind::List => {
+                let items: Vec<&str> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect();
+                if items.is_empty() {
+                    Err("value cannot be empty".to_string())
+                } else {
+                    Ok(items.join(", "))
+                }
+            },
+            SettingKind::Text | SettingKind::Secret => {
+                let value = value.trim();
+                if value.is_empty() {
+                    Err("value cannot be empty".to_string())
+                } else if value.contains('\n') {
+                    Err("value must be a single line".to_string())
+                } else {
+                    Ok(value.to_string())
+                }
+            },
+        }
+    }
+
+    fn display(&self, value: &str) -> String {
+        if self.kind == SettingKind::Secret {
+            mask_value(value)
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// A normalized value as written to config.toml
+    fn to_toml(&self, value: &str) -> toml_edit::Value {
+        match self.kind {
+            SettingKind::Bool => parse_bool(value).unwrap_or_default().into(),
+            SettingKind::Number => value.parse::<i64>().unwrap_or_default().into(),
+            SettingKind::List => value
+                .split(',')
+                .map(str::trim)
+                .collect::<toml_edit::Array>()
+                .into(),
+            SettingKind::Text | SettingKind::Url | SettingKind::Secret => value.into(),
+        }
+    }
+
+    /// A config.toml value in the form `validate` gives
+    fn from_toml(value: &toml_edit::Value) -> String {
+        match value {
+            toml_edit::Value::String(s) => s.value().clone(),
+            toml_edit::Value::Integer(n) => n.value().to_string(),
+            toml_edit::Value::Float(f) => f.value().to_string(),
+            toml_edit::Value::Boolean(b) => b.value().to_string(),
+            toml_edit::Value::Array(items) => items
+                .iter()
+                .map(Self::from_toml)
+                .collect::<Vec<_>>()
+                .join(", "),
+            other => other.clone().decorated("", "").to_string(),
+        }
+    }
+}
+
+/// Validate a batch of changes against the schema
+///
+/// Returns the normalized values, or one message per invalid entry.
+pub fn validate_changes(
+    changes: &BTreeMap<String, Option<String>>,
+) -> std::result::Result<Vec<(&str, Option<String>)>, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut normalized = Vec::new();
+    for (key, value) in changes {
+        let Some(spec) = find_spec(key) else {
+            errors.push(format!("{}: unknown setting", key));
+            continue;
+        };
+        match value.as_deref().map(|v| spec.validate(v)).transpose() {
+            Ok(value) => normalized.push((key.as_str(), value)),
+            Err(e) => errors.push(format!("{}: {}", key, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(normalized)
+    } else {
+        Err(errors)
+    }
+}
+
+/// A setting with its current value, as shown by the Dashboard
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingView {
+    #[serde(flatten)]
+    pub spec: SettingSpec,
+    /// Stored value (masked for secrets), `None` when unset
+    pub value: Option<String>,
+    /// True when `env_var` is set, so the stored value has no effect
+    pub env_override: bool,
+}
+
+/// One recorded settings change
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct SettingChange {
+    pub id: i64,
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    /// Who made the change: `dashboard` or `cli`
+    pub source: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+pub struct SettingsManager<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> SettingsManager<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// All known settings with their current values
+    pub async fn list(&self) -> Result<Vec<SettingView>> {
+        let mut stored: BTreeMap<String, String> = sqlx::query_as::<_, (String, String)>(
+            "SELECT key, value FROM workspace_state WHERE key != 'schema_version'",
+        )
+        .fetch_all(self.pool)
+        .await?
+        .into_iter()
+        .filter(|(key, _)| find_spec(key).is_none_or(|spec| spec.store == SettingStore::State))
+        .collect();
+        if let Some(path) = ProjectConfig::path_for_pool(self.pool) {
+            let config = ConfigEditor::open(&path)?;
+            for spec in SETTINGS
+                .iter()
+                .filter(|s| s.store == SettingStore::ConfigFile)
+            {
+                if let Some(value) = config.get(spec.key) {
+                    stored.insert(spec.key.to_string(), SettingSpec::from_toml(value));
+                }
+            }
+        }
+
+        Ok(SETTINGS
+            .iter()
+            .map(|spec| SettingView {
+                spec: spec.clone(),
+                value: stored.get(spec.key).map(|v| spec.display(v)),
+                env_override: spec
+                    .env_var
+                    .and_then(|var| std::env::var(var).ok())
+                    .is_some_and(|v| !v.is_empty()),
+            })
+            .collect())
+    }
+
+    /// Stored value of a key, from config.toml for settings kept there
+    ///
+    /// Unlike [`list`](Self::list) secrets are not masked.
+    pub async fn get(&self, key: &str) -> Result<Option<String>> {
+        if find_spec(key).is_some_and(|spec| spec.store == SettingStore::ConfigFile) {
+            let Some(path) = ProjectConfig::path_for_pool(self.pool) else {
+                return Ok(None);
+            };
+            return Ok(ConfigEditor::open(&path)?
+                .get(key)
+                .map(SettingSpec::from_toml));
+        }
+        config_get(self.pool, key).await
+    }
+
+    /// Effective value of a boolean setting, falling back to its default
+    ///
+    /// Unparseable stored values count as the default.
+    pub async fn get_bool(&self, key: &str) -> Result<bool> {
+        let default = find_spec(key)
+            .and_then(|spec| spec.default)
+            .and_then(parse_bool)
+            .unwrap_or(false);
+        let stored = config_get(self.pool, key).await?;
+        Ok(stored.as_deref().and_then(parse_bool).unwrap_or(default))
+    }
+
+    /// Effective value of a numeric setting, falling back to its default
+    ///
+    /// Unparseable stored values count as the default.
+    pub async fn get_number(&self, key: &str) -> Result<f64> {
+        let default = find_spec(key)
+            .and_then(|spec| spec.default)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.0);
+        let stored = config_get(self.pool, key).await?;
+        Ok(stored
+            .as_deref()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|n: &f64| n.is_finite() && *n >= 0.0)
+            .unwrap_or(default))
+    }
+
+    /// Effective value of a text setting, falling back to its default
+    pub async fn get_text(&self, key: &str) -> Result<Option<String>> {
+        let stored = config_get(self.pool, key).await?;
+        Ok(stored.or_else(|| find_spec(key).and_then(|spec| spec.default.map(str::to_string))))
+    }
+
+    /// Apply a batch of changes atomically (`None` unsets a key)
+    ///
+    /// Only known settings are accepted, and every value is validated before
+    /// anything is written. Unchanged values are not audited.
+    pub async fn apply(
+        &self,
+        changes: &BTreeMap<String, Option<String>>,
+        source: &str,
+    ) -> Result<Vec<SettingChange>> {
+        let normalized = validate_changes(changes)
+            .map_err(|errors| IntentError::InvalidInput(errors.join("; ")))?;
+        self.write(normalized, source).await
+    }
+
+    /// Set or unset a single key
+    ///
+    /// Known settings are validated; unknown keys are stored as-is, since
+    /// `ie config` doubles as a free-form key-value store.
+    pub async fn set(
+        &self,
+        key: &str,
+        value: Option<&str>,
+        source: &str,
+    ) -> Result<Option<SettingChange>> {
+        let value = match (find_spec(key), value) {
+            (Some(spec), Some(v)) => Some(
+                spec.validate(v)
+                    .map_err(|e| IntentError::InvalidInput(format!("{}: {}", key, e)))?,
+            ),
+            (_, v) => v.map(str::to_string),
+        };
+
+        Ok(self
+            .write(vec![(key, value)], source)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// Write validated values to their stores, all or nothing
+    ///
+    /// config.toml is only saved when the whole file is still a valid
+    /// config, and before the database changes are committed.
+    async fn write(
+        &self,
+        values: Vec<(&str, Option<String>)>,
+        source: &str,
+    ) -> Result<Vec<SettingChange>> {
+        let mut config = None;
+        let mut tx = self.pool.begin().await?;
+        let mut recorded = Vec::new();
+        for (key, value) in values {
+            let change = match find_spec(key) {
+                Some(spec) if spec.store == SettingStore::ConfigFile => {
+                    if config.is_none() {
+                        let path = ProjectConfig::path_for_pool(self.pool).ok_or_else(|| {
+                            IntentError::ActionNotAllowed(format!(
+                                "{}: an in-memory project has no config.toml",
+                                key
+                            ))
+                        })?;
+                        config = Some(ConfigEditor::open(&path)?);
+                    }
+                    let editor = config.as_mut().expect("opened above");
+                    let old = editor.get(key).map(SettingSpec::from_toml);
+                    if old == value {
+                        continue;
+                    }
+                    editor.set(key, value.as_deref().map(|v| spec.to_toml(v)))?;
+                    Some(audit_in_tx(&mut tx, key, old.as_deref(), value.as_deref(), source).await?)
+                },
+                _ => write_in_tx(&mut tx, key, value.as_deref(), source).await?,
+            };
+            recorded.extend(change);
+        }
+        if let Some(editor) = config {
+            editor.save()?;
+        }
+        tx.commit().await?;
+        Ok(recorded)
+    }
+
+    /// Most recent settings changes, newest first
+    pub async fn history(&self, limit: i64) -> Result<Vec<SettingChange>> {
+        let changes = sqlx::query_as(
+            "SELECT id, key, old_value, new_value, source, changed_at FROM settings_audit ORDER BY id DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await?;
+        Ok(changes)
+    }
+}
+
+/// Write one key and its audit row; returns `None` when nothing changed
+async fn write_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    key: &str,
+    value: Option<&str>,
+    source: &str,
+) -> Result<Option<SettingChange>> {
+    let old: Option<String> = sqlx::query_scalar("SELECT value FROM workspace_state WHERE key = ?")
+        .bind(key)
+        .fetch_optional(&mut **tx)
+        .await?;
+    if old.as_deref() == value {
+        return Ok(None);
+    }
+
+    match value {
+        Some(v) => {
+            sqlx::query(
+                "INSERT INTO workspace_state (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            )
+            .bind(key)
+            .bind(v)
+            .execute(&mut **tx)
+            .await?;
+        },
+        None => {
+            sqlx::query("DELETE FROM workspace_state WHERE key = ?")
+                .bind(key)
+                .execute(&mut **tx)
+                .await?;
+        },
+    }
+
+    audit_in_tx(tx, key, old.as_deref(), value, source)
+        .await
+        .map(Some)
+}
+
+/// Record one change in `settings_audit`, masking secrets
+async fn audit_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    key: &str,
+    old: Option<&str>,
+    value: Option<&str>,
+    source: &str,
+) -> Result<SettingChange> {
+    let is_secret = find_spec(key).is_some_and(|spec| spec.kind == SettingKind::Secret)
+        || crate::cli_handlers::config_commands::is_sensitive_key(key);
+    let audited = |v: Option<&str>| {
+        v.map(|v| {
+            if is_secret {
+                mask_value(v)
+            } else {
+                v.to_string()
+            }
+        })
+    };
+
+    let change = sqlx::query_as(
+        r#"
+        INSERT INTO settings_audit (key, old_value, new_value, source, changed_at)
+        VALUES (?, ?, ?, ?, ?)
+        RETURNING id, key, old_value, new_value, source, changed_at
+        "#,
+    )
+    .bind(key)
+    .bind(audited(old))
+    .bind(audited(value))
+    .bind(source)
+    .bind(Utc::now())
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::desktop::DesktopEventKind;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[test]
+    fn test_desktop_notification_keys_are_known() {
+        for kind in [
+            DesktopEventKind::Unblocked,
+            DesktopEventKind::ReviewRequested,
+            DesktopEventKind::PlanFailed,
+            DesktopEventKind::FocusEnded,
+        ] {
+            assert!(find_spec(&kind.config_key()).is_some(), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let desktop = find_spec("notify.desktop").unwrap();
+        assert_eq!(desktop.validate("On").unwrap(), "true");
+        assert!(desktop.validate("maybe").is_err());
+
+        let endpoint = find_spec("llm.endpoint").unwrap();
+        assert!(endpoint.validate("http://localhost:8080/v1").is_ok());
+        assert!(endpoint.validate("ftp://example.com").is_err());
+        assert!(endpoint.validate("not a url").is_err());
+
+        let model = find_spec("llm.model").unwrap();
+        assert!(model.validate("  ").is_err());
+
+        let days = find_spec("priority.aging.days").unwrap();
+        assert_eq!(days.validate(" 14.5 ").unwrap(), "14.5");
+        assert!(days.validate("-1").is_err());
+        assert!(days.validate("soon").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_validates_all_before_writing() {
+        let ctx = TestContext::new().await;
+        let settings = SettingsManager::new(ctx.pool());
+
+        let changes = BTreeMap::from([
+            ("llm.model".to_string(), Some("gpt-4".to_string())),
+            ("notify.desktop".to_string(), Some("sometimes".to_string())),
+            ("no.such.key".to_string(), Some("x".to_string())),
+        ]);
+        let err = settings.apply(&changes, "dashboard").await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("notify.desktop"));
+        assert!(message.contains("no.such.key"));
+
+        let views = settings.list().await.unwrap();
+        assert!(views.iter().all(|v| v.value.is_none()));
+        assert!(settings.history(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_audits_changes_and_masks_secrets() {
+        let ctx = TestContext::new().await;
+        let settings = SettingsManager::new(ctx.pool());
+
+        let changes = BTreeMap::from([
+            (
+                "llm.api_key".to_string(),
+                Some("sk-secret-value".to_string()),
+            ),
+            ("notify.desktop".to_string(), Some("yes".to_string())),
+        ]);
+        let recorded = settings.apply(&changes, "dashboard").await.unwrap();
+        assert_eq!(recorded.len(), 2);
+
+        // Re-applying the same values records nothing
+        assert!(settings
+            .apply(&changes, "dashboard")
+            .await
+            .unwrap()
+            .is_empty());
+
+        settings.set("notify.desktop", None, "cli").await.unwrap();
+
+        let history = settings.history(10).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].key, "notify.desktop");
+        assert_eq!(history[0].old_value.as_deref(), Some("true"));
+        assert_eq!(history[0].new_value, None);
+        assert_eq!(history[0].source, "cli");
+
+        let secret = history.iter().find(|c| c.key == "llm.api_key").unwrap();
+        assert_eq!(secret.new_value.as_deref(), Some("sk-s...********"));
+
+        let views = settings.list().await.unwrap();
+        let key = views.iter().find(|v| v.spec.key == "llm.api_key").unwrap();
+        assert_eq!(key.value.as_deref(), Some("sk-s...********"));
+    }
+
+    #[tokio::test]
+    async fn test_set_allows_free_form_keys() {
+        let ctx = TestContext::new().await;
+        let settings = SettingsManager::new(ctx.pool());
+
+        assert!(settings
+            .set("custom.key", Some("anything"), "cli")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(settings
+            .set("notify.desktop", Some("loud"), "cli")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_config_file_settings_edit_config_toml() {
+        let ctx = TestContext::new().await;
+        let settings = SettingsManager::new(ctx.pool());
+        let path = ProjectConfig::path_for_pool(ctx.pool()).unwrap();
+        std::fs::write(&path, "# team limits\n[wip]\nmax_doing = 4\n").unwrap();
+
+        let view = |views: &[SettingView], key: &str| {
+            views
+                .iter()
+                .find(|v| v.spec.key == key)
+                .unwrap()
+                .value
+                .clone()
+        };
+        let views = settings.list().await.unwrap();
+        assert_eq!(view(&views, "tasks.capacity_limit").as_deref(), Some("4"));
+
+        let changes = BTreeMap::from([
+            ("tasks.capacity_limit".to_string(), Some("3".to_string())),
+            (
+                "policy.done_requires_event".to_string(),
+                Some("milestone,decision".to_string()),
+            ),
+        ]);
+        assert_eq!(
+            settings.apply(&changes, "dashboard").await.unwrap().len(),
+            2
+        );
+
+        let config = ProjectConfig::for_pool(ctx.pool()).unwrap();
+        assert_eq!(config.tasks.capacity_limit, Some(3));
+        assert_eq!(config.policy.done_requires_event, ["milestone", "decision"]);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("# team limits"));
+        assert!(!text.contains("max_doing"));
+        assert_eq!(
+            settings
+                .get("policy.done_requires_event")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("milestone, decision")
+        );
+        assert_eq!(
+            config_get(ctx.pool(), "tasks.capacity_limit")
+                .await
+                .unwrap(),
+            None
+        );
+
+        // The whole file must stay valid; nothing is written otherwise
+        let err = settings
+            .set("tasks.capacity_limit", Some("0"), "cli")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("tasks.capacity_limit: must be at least 1"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+        assert_eq!(settings.history(10).await.unwrap().len(), 2);
+
+        settings
+            .set("tasks.capacity_limit", None, "cli")
+            .await
+            .unwrap();
+        assert_eq!(
+            ProjectConfig::for_pool(ctx.pool())
+                .unwrap()
+                .tasks
+                .capacity_limit,
+            None
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Lexical similarity of task texts
+//!
+//! Task names and specs are compared as bags of terms: lowercase words with
+//! common English stop words dropped and a trailing plural "s" stripped, plus
+//! every CJK character as a term of its own. Name terms count twice, as a name
+//! says more about a task than a line deep in its spec. This needs no index
+//! and no model, so it works in every build; it catches shared vocabulary,
+//! not synonyms.
+
+use std::collections::{HashMap, HashSet};
+
+/// Words too common to say anything about a task
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "can", "do", "for", "from", "has", "have",
+    "in", "into", "is", "it", "its", "make", "new", "not", "of", "on", "or", "should", "so",
+    "that", "the", "then", "this", "to", "use", "we", "when", "will", "with",
+];
+
+/// Weight of a name term relative to a spec term
+const NAME_WEIGHT: f64 = 2.0;
+
+/// Terms of a text, in order, repeats kept
+pub fn terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let lower = text.to_lowercase();
+    for word in lower.split(|c: char| !c.is_alphanumeric()) {
+        let mut latin = String::new();
+        for c in word.chars() {
+            if crate::search::is_cjk_char(c) {
+                push_word(&mut terms, std::mem::take(&mut latin));
+                terms.push(c.to_string());
+            } else {
+                latin.push(c);
+            }
+        }
+        push_word(&mut terms, latin);
+    }
+    terms
+}
+
+fn push_word(terms: &mut Vec<String>, word: String) {
+    if word.is_empty() || STOP_WORDS.contains(&word.as_str()) {
+        return;
+    }
+    let stem = match word.strip_suffix('s') {
+        Some(stem) if stem.chars().count() >= 3 && !stem.ends_with('s') => stem.to_string(),
+        _ => word,
+    };
+    terms.push(stem);
+}
+
+/// Weighted term counts of a task's name and spec
+#[derive(Debug, Clone, Default)]
+pub struct TermVector {
+    weights: HashMap<String, f64>,
+}
+
+impl TermVector {
+    pub fn for_task(name: &str, spec: Option<&str>) -> Self {
+        let mut weights = HashMap::new();
+        for term in terms(name) {
+            *weights.entry(term).or_insert(0.0) += NAME_WEIGHT;
+        }
+        for term in terms(spec.unwrap_or_default()) {
+            *weights.entry(term).or_insert(0.0) += 1.0;
+        }
+        Self { weights }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Cosine similarity, from 0 (nothing shared) to 1 (same proportions)
+    pub fn cosine(&self, other: &TermVector) -> f64 {
+        let dot: f64 = self
+            .weights
+            .iter()
+            .filter_map(|(term, w)| other.weights.get(term).map(|o| w * o))
+            .sum();
+        let norm = |v: &TermVector| v.weights.values().map(|w| w * w).sum::<f64>().sqrt();
+        let denominator = norm(self) * norm(other);
+        if denominator == 0.0 {
+            0.0
+        } else {
+            (dot / denominator).min(1.0)
+        }
+    }
+
+    /// Terms found in both, heaviest first
+    pub fn shared_terms(&self, other: &TermVector) -> Vec<String> {
+        let mut shared: Vec<(&String, f64)> = self
+            .weights
+            .iter()
+            .filter_map(|(term, w)| other.weights.get(term).map(|o| (term, w * o)))
+            .collect();
+        shared.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        shared.into_iter().map(|(term, _)| term.clone()).collect()
+    }
+}
+
+/// Jaccard similarity of the term sets of two names
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a: HashSet<String> = terms(a).into_iter().collect();
+    let b: HashSet<String> = terms(b).into_iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms() {
+        assert_eq!(
+            terms("Add the login forms to API"),
+            ["add", "login", "form", "api"]
+        );
+        // "class" keeps its s; CJK characters stand alone
+        assert_eq!(terms("class 用户login"), ["class", "用", "户", "login"]);
+    }
+
+    #[test]
+    fn test_cosine() {
+        let login = TermVector::for_task("Login form", Some("Email and password"));
+        let same = TermVector::for_task("Login forms", Some("Email and password"));
+        let other = TermVector::for_task("Billing export", None);
+        assert!((login.cosine(&same) - 1.0).abs() < 1e-9);
+        assert_eq!(login.cosine(&other), 0.0);
+        assert_eq!(login.cosine(&TermVector::default()), 0.0);
+        assert_eq!(login.shared_terms(&same)[..2], ["form", "login"]);
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        assert_eq!(name_similarity("User login", "Login for users"), 1.0);
+        assert_eq!(name_similarity("User login", "User logout"), 1.0 / 3.0);
+        assert_eq!(name_similarity("the", "a"), 0.0);
+    }
+}
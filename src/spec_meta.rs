@@ -0,0 +1,268 @@
+//! Structured metadata in spec frontmatter
+//!
+//! Specs are Markdown, and may start with a YAML frontmatter block:
+//!
+//! ```text
+//! ---
+//! acceptance_criteria:
+//!   - Tokens expire after 15 minutes
+//!   - Refresh rotates the token
+//! links: [https://datatracker.ietf.org/doc/html/rfc7519]
+//! estimate: 2h
+//! ---
+//! Implement JWT login ...
+//! ```
+//!
+//! Whenever a spec is written its frontmatter is parsed into the `spec_meta`
+//! table, one row per task, so it can be queried with SQL:
+//!
+//! ```sql
+//! SELECT t.name FROM tasks t JOIN spec_meta m ON m.task_id = t.id
+//! WHERE json_array_length(m.acceptance_criteria) = 0;
+//! ```
+//!
+//! `acceptance_criteria` and `links` take a list or a single string, and
+//! `estimate` minutes or a duration like `90m`, `2h` or `1h30m`. Other keys
+//! are kept as JSON in `fields`. Frontmatter that is not valid YAML is
+//! rejected, so the spec is not written.
+
+use crate::error::{IntentError, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use serde_yaml::Value as YamlValue;
+use sqlx::{SqliteConnection, SqlitePool};
+
+/// Parsed spec frontmatter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecMeta {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<i64>,
+    /// Any other frontmatter keys
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, JsonValue>,
+}
+
+/// Split a spec into its frontmatter (without the `---` lines) and body
+///
+/// Frontmatter must start on the first line; a spec without it is all body.
+pub fn split_frontmatter(spec: &str) -> (Option<&str>, &str) {
+    let Some(rest) = spec
+        .strip_prefix("---\n")
+        .or_else(|| spec.strip_prefix("---\r\n"))
+    else {
+        return (None, spec);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let body = &rest[offset + line.len()..];
+            return (Some(&rest[..offset]), body);
+        }
+        offset += line.len();
+    }
+    (None, spec)
+}
+
+impl SpecMeta {
+    /// Metadata of a spec, or None when it has no frontmatter
+    pub fn parse(spec: &str) -> Result<Option<Self>> {
+        let Some(frontmatter) = split_frontmatter(spec).0 else {
+            return Ok(None);
+        };
+        let invalid = |msg: String| IntentError::InvalidInput(format!("Spec frontmatter: {}", msg));
+        let mapping = match serde_yaml::from_str::<YamlValue>(frontmatter) {
+            Ok(YamlValue::Mapping(mapping)) => mapping,
+            Ok(YamlValue::Null) => return Ok(Some(Self::default())),
+            // A leading horizontal rule, not frontmatter
+            Ok(_) => return Ok(None),
+            Err(e) => return Err(invalid(e.to_string())),
+        };
+
+        let mut meta = Self::default();
+        for (key, value) in mapping {
+            let key = match key {
+                YamlValue::String(key) => key,
+                other => return Err(invalid(format!("keys must be strings, got {:?}", other))),
+            };
+            match key.as_str() {
+                "acceptance_criteria" | "acceptance" => {
+                    meta.acceptance_criteria = strings(&key, value).map_err(invalid)?
+                },
+                "links" => meta.links = strings(&key, value).map_err(invalid)?,
+                "estimate" | "estimate_minutes" => {
+                    meta.estimate_minutes = Some(minutes(value).map_err(invalid)?)
+                },
+                _ => {
+                    let value = serde_json::to_value(&value).map_err(|e| invalid(e.to_string()))?;
+                    meta.fields.insert(key, value);
+                },
+            }
+        }
+        Ok(Some(meta))
+    }
+}
+
+fn strings(key: &str, value: YamlValue) -> std::result::Result<Vec<String>, String> {
+    let scalar = |value: YamlValue| match value {
+        YamlValue::String(s) => Ok(s),
+        YamlValue::Number(n) => Ok(n.to_string()),
+        YamlValue::Bool(b) => Ok(b.to_string()),
+        _ => Err(format!("{} must be a string or a list of strings", key)),
+    };
+    match value {
+        YamlValue::Sequence(items) => items.into_iter().map(scalar).collect(),
+        YamlValue::Null => Ok(Vec::new()),
+        other => scalar(other).map(|s| vec![s]),
+    }
+}
+
+fn minutes(value: YamlValue) -> std::result::Result<i64, String> {
+    let invalid = || "estimate must be minutes or a duration like 90m, 2h or 1h30m".to_string();
+    match value {
+        YamlValue::Number(n) => n.as_i64().filter(|m| *m >= 0).ok_or_else(invalid),
+        YamlValue::String(s) => {
+            let mut total = 0;
+            let mut digits = String::new();
+            for c in s.chars().filter(|c| !c.is_whitespace()) {
+                match c {
+                    '0'..='9' => digits.push(c),
+                    'h' | 'm' if !digits.is_empty() => {
+                        let n: i64 = digits.parse().map_err(|_| invalid())?;
+                        total += if c == 'h' { n * 60 } else { n };
+                        digits.clear();
+                    },
+                    _ => return Err(invalid()),
+                }
+            }
+            if !digits.is_empty() || s.trim().is_empty() {
+                return Err(invalid());
+            }
+            Ok(total)
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Rewrite the `spec_meta` row of a task from its new spec
+///
+/// Call wherever a spec is written, in the same transaction.
+pub async fn sync(conn: &mut SqliteConnection, task_id: i64, spec: Option<&str>) -> Result<()> {
+    let Some(meta) = spec.map(SpecMeta::parse).transpose()?.flatten() else {
+        sqlx::query("DELETE FROM spec_meta WHERE task_id = ?")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+        return Ok(());
+    };
+    store(conn, task_id, &meta).await
+}
+
+/// Write the `spec_meta` row of a task
+pub async fn store(conn: &mut SqliteConnection, task_id: i64, meta: &SpecMeta) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO spec_meta (task_id, acceptance_criteria, links, estimate_minutes, fields, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(task_id) DO UPDATE SET
+            acceptance_criteria = excluded.acceptance_criteria,
+            links = excluded.links,
+            estimate_minutes = excluded.estimate_minutes,
+            fields = excluded.fields,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(task_id)
+    .bind(serde_json::to_string(&meta.acceptance_criteria)?)
+    .bind(serde_json::to_string(&meta.links)?)
+    .bind(meta.estimate_minutes)
+    .bind(serde_json::to_string(&meta.fields)?)
+    .bind(Utc::now())
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// The stored metadata of a task, if its spec has frontmatter
+pub async fn get(pool: &SqlitePool, task_id: i64) -> Result<Option<SpecMeta>> {
+    let row: Option<(String, String, Option<i64>, String)> = sqlx::query_as(
+        "SELECT acceptance_criteria, links, estimate_minutes, fields FROM spec_meta WHERE task_id = ?",
+    )
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await?;
+    row.map(|(criteria, links, estimate_minutes, fields)| {
+        Ok(SpecMeta {
+            acceptance_criteria: serde_json::from_str(&criteria)?,
+            links: serde_json::from_str(&links)?,
+            estimate_minutes,
+            fields: serde_json::from_str(&fields)?,
+        })
+    })
+    .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    const SPEC: &str = "---\nacceptance: Tokens expire\nlinks:\n  - https://example.com/rfc\nestimate: 1h 30m\nteam: auth\n---\n# Login\n";
+
+    #[test]
+    fn test_parse_frontmatter() {
+        let meta = SpecMeta::parse(SPEC).unwrap().unwrap();
+        assert_eq!(meta.acceptance_criteria, vec!["Tokens expire".to_string()]);
+        assert_eq!(meta.links, vec!["https://example.com/rfc".to_string()]);
+        assert_eq!(meta.estimate_minutes, Some(90));
+        assert_eq!(meta.fields["team"], "auth");
+        assert_eq!(split_frontmatter(SPEC).1, "# Login\n");
+
+        assert_eq!(SpecMeta::parse("# No frontmatter").unwrap(), None);
+        assert_eq!(
+            SpecMeta::parse("---\nJust a rule\n---\ntext").unwrap(),
+            None
+        );
+        assert!(SpecMeta::parse("---\nkey: [unclosed\n---\n").is_err());
+        assert!(SpecMeta::parse("---\nestimate: soon\n---\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_spec_meta_follows_spec_writes() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+
+        let task = tasks
+            .add_task("Login", Some(SPEC), None, None, None, None)
+            .await
+            .unwrap();
+        let meta = get(pool, task.id).await.unwrap().unwrap();
+        assert_eq!(meta.estimate_minutes, Some(90));
+
+        // Invalid frontmatter leaves the spec untouched
+        let update = |spec| TaskUpdate {
+            spec: Some(spec),
+            ..Default::default()
+        };
+        assert!(tasks
+            .update_task(task.id, update("---\nestimate: soon\n---\n"))
+            .await
+            .is_err());
+        assert_eq!(
+            tasks.get_task(task.id).await.unwrap().spec.as_deref(),
+            Some(SPEC)
+        );
+
+        tasks
+            .update_task(task.id, update("Plain now"))
+            .await
+            .unwrap();
+        assert_eq!(get(pool, task.id).await.unwrap(), None);
+    }
+}
@@ -21,24 +21,24 @@
 ///
 /// Used when fetching complete task data with specification.
 /// Columns: id, parent_id, name, spec, status, complexity, priority,
-///          first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+///          first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
 pub const TASK_COLUMNS: &str =
-    "id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata";
+    "id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason";
 
 /// Task column list with `t.` table prefix for JOIN queries
 ///
 /// Same columns as TASK_COLUMNS but each prefixed with `t.` to avoid
 /// ambiguity when joining with other tables (e.g. dependencies).
 pub const TASK_COLUMNS_PREFIXED: &str =
-    "t.id, t.parent_id, t.name, t.spec, t.status, t.complexity, t.priority, t.first_todo_at, t.first_doing_at, t.first_done_at, t.active_form, t.owner, t.metadata";
+    "t.id, t.parent_id, t.name, t.spec, t.status, t.complexity, t.priority, t.first_todo_at, t.first_doing_at, t.first_done_at, t.active_form, t.owner, t.metadata, t.sort_order, t.estimate_minutes, t.visibility, t.blocked_reason";
 
 /// Task column list without spec (uses NULL placeholder)
 ///
 /// Used when spec is not needed but schema compatibility is required.
 /// Columns: id, parent_id, name, NULL as spec, status, complexity, priority,
-///          first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+///          first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
 pub const TASK_COLUMNS_NO_SPEC: &str =
-    "id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata";
+    "id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason";
 
 /// Base SELECT query for tasks (with spec)
 ///
@@ -94,8 +94,9 @@ pub const COUNT_INCOMPLETE_TASKS_EXCLUDE: &str =
 
 /// Standard column list for event queries
 ///
-/// Columns: id, task_id, timestamp, log_type, discussion_data
-pub const EVENT_COLUMNS: &str = "id, task_id, timestamp, log_type, discussion_data";
+/// Columns: id, task_id, timestamp, log_type, discussion_data, visibility, actor
+pub const EVENT_COLUMNS: &str =
+    "id, task_id, timestamp, log_type, discussion_data, visibility, actor";
 
 /// Base SELECT query for events
 ///
@@ -162,7 +163,7 @@ mod tests {
     fn test_select_task_full() {
         assert_eq!(
             SELECT_TASK_FULL,
-            "SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata FROM tasks"
+            "SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason FROM tasks"
         );
     }
 
@@ -170,7 +171,7 @@ mod tests {
     fn test_select_event_by_id() {
         assert_eq!(
             SELECT_EVENT_BY_ID,
-            "SELECT id, task_id, timestamp, log_type, discussion_data FROM events WHERE id = ?"
+            "SELECT id, task_id, timestamp, log_type, discussion_data, visibility, actor FROM events WHERE id = ?"
         );
     }
 
@@ -0,0 +1,173 @@
+//! Stale task detection
+//!
+//! A task left in doing by an abandoned session stays there for good and
+//! keeps ranking first in focus-aware lists. A doing task is stale once
+//! nothing happened to it — no event, no move to doing — for `stale.days`
+//! days (default 14; 0 turns detection off). Each stale spell of a task is
+//! flagged with one `stale` event, and with `stale.demote` the task is moved
+//! back to todo. `ie task stale`, `ie db maintain` and the Dashboard's
+//! periodic maintenance run the sweep (`TaskManager::sweep_stale`).
+
+use crate::error::Result;
+use crate::settings::SettingsManager;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// `log_type` of the events flagging a stale task
+pub const STALE_LOG_TYPE: &str = "stale";
+
+/// When doing tasks count as stale, and what happens to them
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct StalePolicy {
+    /// Days without activity after which a doing task is stale; 0 is off
+    pub days: f64,
+    /// Whether stale tasks are moved back to todo
+    pub demote: bool,
+}
+
+impl StalePolicy {
+    /// Load the policy from project settings (`stale.days`, `stale.demote`)
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let settings = SettingsManager::new(pool);
+        Ok(Self {
+            days: settings.get_number("stale.days").await?,
+            demote: settings.get_bool("stale.demote").await?,
+        })
+    }
+
+    pub fn is_off(&self) -> bool {
+        self.days <= 0.0
+    }
+}
+
+/// A doing task without recent activity
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StaleTask {
+    pub id: i64,
+    pub name: String,
+    pub owner: String,
+    /// Latest event (other than `stale`) or move to doing
+    pub last_activity_at: DateTime<Utc>,
+    /// Days since `last_activity_at`, one decimal
+    pub idle_days: f64,
+    /// Whether this sweep logged the `stale` event; false when an earlier
+    /// sweep already flagged the same spell
+    pub flagged: bool,
+    /// Whether this sweep moved the task back to todo
+    pub demoted: bool,
+}
+
+/// Outcome of a stale sweep
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StaleSweep {
+    pub policy: StalePolicy,
+    /// Stale tasks, longest idle first
+    pub tasks: Vec<StaleTask>,
+}
+
+impl StaleSweep {
+    pub fn demoted(&self) -> usize {
+        self.tasks.iter().filter(|t| t.demoted).count()
+    }
+}
+
+/// Text of the `stale` event of a task idle for `idle_days`
+pub fn event_text(idle_days: f64, demoted: bool) -> String {
+    let text = format!("No activity for {:.0} days", idle_days.floor());
+    if demoted {
+        format!("{}; moved back to todo", text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_sweep_flags_once_then_demotes() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let idle = tasks
+            .add_task("Idle", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        let busy = tasks
+            .add_task("Busy", Some("spec"), None, None, None, None)
+            .await
+            .unwrap();
+        tasks.start_task(idle.id, false).await.unwrap();
+        tasks.start_task(busy.id, false).await.unwrap();
+
+        // Three weeks on, Busy got a note five days ago
+        let now = Utc::now() + chrono::Duration::days(21);
+        sqlx::query(
+            "INSERT INTO events (task_id, log_type, discussion_data, timestamp) VALUES (?, 'note', 'x', ?)",
+        )
+        .bind(busy.id)
+        .bind(now - chrono::Duration::days(5))
+        .execute(pool)
+        .await
+        .unwrap();
+        let policy = StalePolicy {
+            days: 14.0,
+            demote: false,
+        };
+
+        let sweep = tasks.sweep_stale_with(policy, now).await.unwrap();
+        assert_eq!(sweep.tasks.len(), 1);
+        assert_eq!(sweep.tasks[0].id, idle.id);
+        assert!(sweep.tasks[0].flagged && !sweep.tasks[0].demoted);
+        assert!(sweep.tasks[0].idle_days >= 21.0);
+
+        // The stale event itself is no activity, and is not repeated
+        let again = tasks.sweep_stale_with(policy, now).await.unwrap();
+        assert_eq!(again.tasks.len(), 1);
+        assert!(!again.tasks[0].flagged);
+
+        let demoted = tasks
+            .sweep_stale_with(
+                StalePolicy {
+                    demote: true,
+                    ..policy
+                },
+                now,
+            )
+            .await
+            .unwrap();
+        assert_eq!(demoted.demoted(), 1);
+        assert_eq!(tasks.get_task(idle.id).await.unwrap().status, "todo");
+        assert_eq!(tasks.get_task(busy.id).await.unwrap().status, "doing");
+        let stale_events: Vec<String> = sqlx::query_scalar(
+            "SELECT discussion_data FROM events WHERE task_id = ? AND log_type = ? ORDER BY id",
+        )
+        .bind(idle.id)
+        .bind(STALE_LOG_TYPE)
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            stale_events,
+            vec![
+                "No activity for 21 days".to_string(),
+                "No activity for 21 days; moved back to todo".to_string()
+            ]
+        );
+
+        let off = StalePolicy {
+            days: 0.0,
+            demote: true,
+        };
+        assert!(tasks
+            .sweep_stale_with(off, now)
+            .await
+            .unwrap()
+            .tasks
+            .is_empty());
+    }
+}
@@ -0,0 +1,808 @@
+//! Sync between a local SQLite project and a remote hub (`ie sync`).
+//!
+//! **Change tracking.** Triggers created by `run_migrations` record every
+//! insert, update and delete of a task, event or dependency in `sync_rows`:
+//! a stable `uid` (`<replica id>-<entity>-<local id>` for rows made here),
+//! the value of the database's Lamport clock at the change, the replica that
+//! made it and a wall-clock `updated_at`. Deletes leave a tombstone. `synced_lamport`
+//! is the version last agreed with the hub, so a row whose `lamport` differs
+//! from it has local changes to push.
+//!
+//! **Hub.** The hub is a single JSON [`Snapshot`] of every row's latest
+//! version, stored by a [`Remote`]. Rows reference each other by uid, so
+//! replicas keep their own local IDs. Writes are conditional on the version
+//! read (an ETag), and a push that loses the race pulls and tries again.
+//!
+//! **Merging.** A pull applies every hub version the replica has not seen
+//! yet. A row changed on both sides since the last sync is a conflict,
+//! settled by the [`ConflictPolicy`]. A push first pulls, then uploads the
+//! local versions of all changed rows.
+//!
+//! Sessions, focus and other per-machine state are not synced. Neither are
+//! private tasks with their events and dependencies, nor private events; they
+//! are held back until made shared.
+
+mod remote;
+mod s3;
+mod store;
+
+pub use remote::Remote;
+
+use crate::error::{IntentError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// Current snapshot format version
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// Object name of the snapshot under the remote location
+pub const SNAPSHOT_FILE: &str = "intent-engine-sync.json";
+
+/// How often a push retries after another replica wrote the hub first
+const PUSH_ATTEMPTS: usize = 5;
+
+/// Kinds of rows that are synced, in the order they are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Entity {
+    Task,
+    Event,
+    Dependency,
+}
+
+impl Entity {
+    pub const ALL: [Entity; 3] = [Entity::Task, Entity::Event, Entity::Dependency];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Entity::Task => "task",
+            Entity::Event => "event",
+            Entity::Dependency => "dependency",
+        }
+    }
+
+    /// Table holding rows of this kind
+    pub fn table(self) -> &'static str {
+        match self {
+            Entity::Task => "tasks",
+            Entity::Event => "events",
+            Entity::Dependency => "dependencies",
+        }
+    }
+
+    /// Columns holding task IDs, which the hub stores as task uids
+    pub fn task_references(self) -> &'static [&'static str] {
+        match self {
+            Entity::Task => &["parent_id"],
+            Entity::Event => &["task_id"],
+            Entity::Dependency => &["blocking_task_id", "blocked_task_id"],
+        }
+    }
+
+    /// SQL condition on this table selecting rows that may leave the machine
+    ///
+    /// Private tasks stay local along with their events and dependencies, as in
+    /// `ie export`; so do private events of shared tasks.
+    pub fn shared_filter(self) -> &'static str {
+        match self {
+            Entity::Task => "visibility = 'shared'",
+            Entity::Event => {
+                "visibility != 'private' \
+                 AND task_id IN (SELECT id FROM tasks WHERE visibility = 'shared')"
+            },
+            Entity::Dependency => {
+                "blocking_task_id IN (SELECT id FROM tasks WHERE visibility = 'shared') \
+                 AND blocked_task_id IN (SELECT id FROM tasks WHERE visibility = 'shared')"
+            },
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == s)
+    }
+}
+
+/// One row version on the hub
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub uid: String,
+    pub entity: Entity,
+    pub lamport: i64,
+    /// Replica that made this version
+    pub origin: String,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub deleted: bool,
+    /// Column values without `id`; task references hold uids.
+    /// None for tombstones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Everything the hub holds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub format: u32,
+    pub records: Vec<SyncRecord>,
+}
+
+impl Snapshot {
+    fn parse(bytes: &[u8]) -> Result<Self> {
+        let snapshot: Snapshot = serde_json::from_slice(bytes)?;
+        if snapshot.format > SNAPSHOT_FORMAT {
+            return Err(IntentError::InvalidInput(format!(
+                "Hub snapshot format {} is newer than supported format {}; upgrade ie",
+                snapshot.format, SNAPSHOT_FORMAT
+            )));
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Which side wins when a row changed both locally and on the hub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Higher Lamport clock wins (ties: higher replica ID), so every
+    /// replica settles on the same version
+    #[default]
+    Lamport,
+    /// Later wall-clock `updated_at` wins
+    Newest,
+    /// The local version always wins
+    Local,
+    /// The hub version always wins
+    Remote,
+}
+
+impl ConflictPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConflictPolicy::Lamport => "lamport",
+            ConflictPolicy::Newest => "newest",
+            ConflictPolicy::Local => "local",
+            ConflictPolicy::Remote => "remote",
+        }
+    }
+}
+
+impl std::str::FromStr for ConflictPolicy {
+    type Err = IntentError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "lamport" => Ok(Self::Lamport),
+            "newest" => Ok(Self::Newest),
+            "local" => Ok(Self::Local),
+            "remote" => Ok(Self::Remote),
+            other => Err(IntentError::InvalidInput(format!(
+                "Invalid conflict policy '{}'. Expected lamport, newest, local or remote",
+                other
+            ))),
+        }
+    }
+}
+
+/// A row changed on both sides, and which side was kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub uid: String,
+    pub entity: Entity,
+    /// Local ID of the row, if it exists here
+    pub local_id: Option<i64>,
+    /// "local" or "remote"
+    pub kept: String,
+}
+
+/// Outcome of a pull
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullReport {
+    /// Hub versions written locally
+    pub applied: usize,
+    /// Of those, tombstones
+    pub deleted: usize,
+    /// Versions waiting for a task this replica does not have (yet)
+    pub skipped: usize,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Outcome of a push
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PushReport {
+    /// The pull every push starts with
+    pub pull: PullReport,
+    /// Local versions uploaded
+    pub pushed: usize,
+    /// Hub writes tried (more than one when another replica got there first)
+    pub attempts: usize,
+}
+
+/// Local and hub state of sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub replica_id: String,
+    pub remote: String,
+    pub policy: ConflictPolicy,
+    /// Rows with local changes not on the hub
+    pub pending: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_push_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_pull_at: Option<String>,
+    /// Hub versions a pull would apply (None if the hub was not reachable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incoming: Option<usize>,
+    /// Of the incoming versions, rows also changed locally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_error: Option<String>,
+}
+
+/// What a pull does with one hub version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Merge {
+    /// Already seen, or only changed here
+    Skip,
+    /// Same version as the local one; only record it as synced
+    Same,
+    Apply,
+    /// Changed on both sides
+    Conflict,
+}
+
+fn classify(record: &SyncRecord, local: Option<&store::LocalRow>) -> Merge {
+    let Some(local) = local else {
+        return if record.deleted {
+            Merge::Skip
+        } else {
+            Merge::Apply
+        };
+    };
+    if local.lamport == record.lamport && local.origin == record.origin {
+        return if local.synced_lamport == Some(record.lamport) {
+            Merge::Skip
+        } else {
+            Merge::Same
+        };
+    }
+    if local.synced_lamport == Some(record.lamport) {
+        Merge::Skip
+    } else if local.synced_lamport == Some(local.lamport) {
+        Merge::Apply
+    } else {
+        Merge::Conflict
+    }
+}
+
+impl ConflictPolicy {
+    /// Whether the hub version wins over the local one
+    fn remote_wins(self, record: &SyncRecord, local: &store::LocalRow) -> bool {
+        let by_clock = (record.lamport, record.origin.as_str()) > (local.lamport, &local.origin);
+        match self {
+            ConflictPolicy::Lamport => by_clock,
+            ConflictPolicy::Newest => match record.updated_at.cmp(&local.updated_at) {
+                std::cmp::Ordering::Equal => by_clock,
+                order => order.is_gt(),
+            },
+            ConflictPolicy::Local => false,
+            ConflictPolicy::Remote => true,
+        }
+    }
+}
+
+pub struct SyncManager<'a> {
+    pool: &'a SqlitePool,
+    remote: Remote,
+    policy: ConflictPolicy,
+}
+
+impl<'a> SyncManager<'a> {
+    pub fn new(pool: &'a SqlitePool, remote: Remote, policy: ConflictPolicy) -> Self {
+        Self {
+            pool,
+            remote,
+            policy,
+        }
+    }
+
+    /// Apply the hub's changes locally
+    pub async fn pull(&self) -> Result<PullReport> {
+        let report = match self.remote.fetch().await? {
+            Some(fetched) => self.merge(&Snapshot::parse(&fetched.body)?).await?,
+            None => PullReport::default(),
+        };
+        store::set_state(self.pool, "last_pull_at", &Utc::now().to_rfc3339()).await?;
+        Ok(report)
+    }
+
+    /// Pull, then upload local changes
+    pub async fn push(&self) -> Result<PushReport> {
+        let mut report = PushReport::default();
+        for _ in 0..PUSH_ATTEMPTS {
+            let fetched = self.remote.fetch().await?;
+            let mut records: BTreeMap<String, SyncRecord> = BTreeMap::new();
+            if let Some(fetched) = &fetched {
+                let snapshot = Snapshot::parse(&fetched.body)?;
+                merge_reports(&mut report.pull, self.merge(&snapshot).await?);
+                records.extend(snapshot.records.into_iter().map(|r| (r.uid.clone(), r)));
+            }
+
+            let outgoing = store::outgoing(self.pool).await?;
+            if outgoing.is_empty() {
+                break;
+            }
+            for record in &outgoing {
+                records.insert(record.uid.clone(), record.clone());
+            }
+
+            let body = serde_json::to_vec(&Snapshot {
+                format: SNAPSHOT_FORMAT,
+                records: records.into_values().collect(),
+            })?;
+            report.attempts += 1;
+            let expected = fetched.as_ref().map(|f| f.etag.as_deref());
+            if self.remote.store(body, expected).await? {
+                store::mark_synced(self.pool, &outgoing).await?;
+                report.pushed = outgoing.len();
+                store::set_state(self.pool, "last_push_at", &Utc::now().to_rfc3339()).await?;
+                return Ok(report);
+            }
+            tracing::debug!("Hub changed during push, retrying");
+        }
+
+        if report.attempts == PUSH_ATTEMPTS {
+            return Err(IntentError::OtherError(anyhow::anyhow!(
+                "The hub kept changing while pushing ({} attempts); try again",
+                PUSH_ATTEMPTS
+            )));
+        }
+        store::set_state(self.pool, "last_push_at", &Utc::now().to_rfc3339()).await?;
+        Ok(report)
+    }
+
+    /// Pending local changes, and what a pull would bring
+    pub async fn status(&self) -> Result<SyncStatus> {
+        let rows = store::load_rows(self.pool).await?;
+        let pending = store::outgoing(self.pool).await?.len();
+        let mut status = SyncStatus {
+            replica_id: store::replica_id(self.pool).await?,
+            remote: self.remote.to_string(),
+            policy: self.policy,
+            pending,
+            last_push_at: store::get_state(self.pool, "last_push_at").await?,
+            last_pull_at: store::get_state(self.pool, "last_pull_at").await?,
+            incoming: None,
+            conflicts: None,
+            remote_error: None,
+        };
+
+        let fetched = match self.remote.fetch().await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                status.remote_error = Some(e.to_string());
+                return Ok(status);
+            },
+        };
+        let records = match fetched {
+            Some(fetched) => Snapshot::parse(&fetched.body)?.records,
+            None => Vec::new(),
+        };
+        let (mut incoming, mut conflicts) = (0, 0);
+        for record in &records {
+            match classify(record, rows.get(&record.uid)) {
+                Merge::Apply => incoming += 1,
+                Merge::Conflict => {
+                    incoming += 1;
+                    conflicts += 1;
+                },
+                Merge::Skip | Merge::Same => {},
+            }
+        }
+        status.incoming = Some(incoming);
+        status.conflicts = Some(conflicts);
+        Ok(status)
+    }
+
+    /// Apply a hub snapshot in one transaction
+    async fn merge(&self, snapshot: &Snapshot) -> Result<PullReport> {
+        let mut tx = self.pool.begin().await?;
+        let replica = store::replica_id(&mut *tx).await?;
+        let mut rows = store::load_rows(&mut *tx).await?;
+        store::set_applying(&mut tx, true).await?;
+
+        let mut records: Vec<&SyncRecord> = snapshot.records.iter().collect();
+        records.sort_by_key(|r| r.entity);
+
+        let mut report = PullReport::default();
+        let mut applier = store::Applier::new(&mut tx).await?;
+        let mut max_lamport = 0;
+        for record in records {
+            max_lamport = max_lamport.max(record.lamport);
+            let local = rows.get(&record.uid);
+
+            // Only a copy of this database can have written our versions ahead of us
+            if record.origin == replica && local.is_none_or(|l| record.lamport > l.lamport) {
+                return Err(IntentError::InvalidInput(format!(
+                    "The hub has changes made by this replica ({}) that this database lacks; \
+                     it was probably copied from another machine. Start from a fresh \
+                     'ie init' and 'ie sync pull' instead",
+                    replica
+                )));
+            }
+
+            let apply = match classify(record, local) {
+                Merge::Skip => continue,
+                Merge::Same => {
+                    store::record_synced(applier.conn(), &record.uid, record.lamport).await?;
+                    continue;
+                },
+                Merge::Apply => true,
+                Merge::Conflict => {
+                    let local = local.expect("conflicts have a local row");
+                    let remote_wins = self.policy.remote_wins(record, local);
+                    report.conflicts.push(SyncConflict {
+                        uid: record.uid.clone(),
+                        entity: record.entity,
+                        local_id: local.local_id,
+                        kept: if remote_wins { "remote" } else { "local" }.to_string(),
+                    });
+                    if !remote_wins {
+                        // Outrank the hub version, so the push that follows wins everywhere
+                        let lamport = store::tick(applier.conn()).await?.max(record.lamport + 1);
+                        store::keep_local(applier.conn(), &record.uid, lamport, &replica, record)
+                            .await?;
+                    }
+                    remote_wins
+                },
+            };
+            if !apply {
+                continue;
+            }
+
+            match applier.apply(record, &rows).await? {
+                Some(row) => {
+                    report.applied += 1;
+                    if record.deleted {
+                        report.deleted += 1;
+                    }
+                    rows.insert(record.uid.clone(), row);
+                },
+                None => report.skipped += 1,
+            }
+        }
+        applier.finish(&rows).await?;
+
+        store::advance_clock(&mut tx, max_lamport).await?;
+        store::set_applying(&mut tx, false).await?;
+        tx.commit().await?;
+        Ok(report)
+    }
+}
+
+fn merge_reports(total: &mut PullReport, report: PullReport) {
+    total.applied += report.applied;
+    total.deleted += report.deleted;
+    total.skipped = report.skipped;
+    total.conflicts.extend(report.conflicts);
+}
+
+/// Entity of a `sync_rows.entity` value
+fn parse_entity(name: &str) -> Result<Entity> {
+    Entity::parse(name)
+        .ok_or_else(|| IntentError::OtherError(anyhow::anyhow!("Unknown sync entity '{}'", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+    use tempfile::TempDir;
+
+    fn sync<'a>(ctx: &'a TestContext, hub: &TempDir, policy: ConflictPolicy) -> SyncManager<'a> {
+        SyncManager::new(ctx.pool(), Remote::Dir(hub.path().to_path_buf()), policy)
+    }
+
+    async fn task_named(ctx: &TestContext, name: &str) -> Option<crate::db::models::Task> {
+        let tasks = TaskManager::new(ctx.pool());
+        // Listings leave the spec out
+        let id = tasks
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap()
+            .tasks
+            .into_iter()
+            .find(|t| t.name == name)?
+            .id;
+        Some(tasks.get_task(id).await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_push_then_pull_copies_tasks_events_and_dependencies() {
+        let (laptop, desktop, hub) = (
+            TestContext::new().await,
+            TestContext::new().await,
+            TempDir::new().unwrap(),
+        );
+        // Different local IDs on each machine
+        TaskManager::new(desktop.pool())
+            .add_task("Desktop only", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let tasks = TaskManager::new(laptop.pool());
+        let auth = tasks
+            .add_task("Auth", Some("JWT"), None, None, None, None)
+            .await
+            .unwrap();
+        let login = tasks
+            .add_task("Login form", None, Some(auth.id), None, None, None)
+            .await
+            .unwrap();
+        crate::dependencies::add_dependency(laptop.pool(), auth.id, login.id)
+            .await
+            .unwrap();
+        EventManager::new(laptop.pool())
+            .add_event(auth.id, "decision", "Use JWT")
+            .await
+            .unwrap();
+
+        let pushed = sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        assert_eq!(pushed.pushed, 4);
+
+        let pulled = sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        assert_eq!(pulled.applied, 4);
+        assert!(pulled.conflicts.is_empty());
+
+        let auth = task_named(&desktop, "Auth").await.unwrap();
+        let login = task_named(&desktop, "Login form").await.unwrap();
+        assert_eq!(auth.spec.as_deref(), Some("JWT"));
+        assert_eq!(login.parent_id, Some(auth.id));
+        let events = EventManager::new(desktop.pool())
+            .list_events(Some(auth.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].discussion_data, "Use JWT");
+        let blocking = TaskManager::new(desktop.pool())
+            .get_blocking_tasks(login.id)
+            .await
+            .unwrap();
+        assert_eq!(blocking.iter().map(|t| t.id).collect::<Vec<_>>(), [auth.id]);
+
+        // Pulled rows are not echoed back; the desktop's own task is pending
+        let status = sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .status()
+            .await
+            .unwrap();
+        assert_eq!(status.pending, 1);
+        assert_eq!(status.incoming, Some(0));
+        let again = sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        assert_eq!(again.applied, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_edits_follow_policy() {
+        let (laptop, desktop, hub) = (
+            TestContext::new().await,
+            TestContext::new().await,
+            TempDir::new().unwrap(),
+        );
+        let task = TaskManager::new(laptop.pool())
+            .add_task("Deploy", Some("v1"), None, None, None, None)
+            .await
+            .unwrap();
+        sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        let desktop_id = task_named(&desktop, "Deploy").await.unwrap().id;
+
+        let edit = |ctx, id, spec| async move {
+            TaskManager::new(ctx)
+                .update_task(
+                    id,
+                    TaskUpdate {
+                        spec: Some(spec),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        };
+        edit(laptop.pool(), task.id, "laptop").await;
+        edit(desktop.pool(), desktop_id, "desktop").await;
+
+        sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        // The desktop keeps its version and pushes it over the laptop's
+        let report = sync(&desktop, &hub, ConflictPolicy::Local)
+            .push()
+            .await
+            .unwrap();
+        assert_eq!(report.pull.conflicts.len(), 1);
+        assert_eq!(report.pull.conflicts[0].kept, "local");
+        assert_eq!(report.pushed, 1);
+
+        let pulled = sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        assert!(pulled.conflicts.is_empty());
+        let spec = task_named(&laptop, "Deploy").await.unwrap().spec;
+        assert_eq!(spec.as_deref(), Some("desktop"));
+
+        // Both changed again; the hub wins for the laptop
+        edit(laptop.pool(), task.id, "laptop 2").await;
+        edit(desktop.pool(), desktop_id, "desktop 2").await;
+        sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        let pulled = sync(&laptop, &hub, ConflictPolicy::Remote)
+            .pull()
+            .await
+            .unwrap();
+        assert_eq!(pulled.conflicts[0].kept, "remote");
+        let spec = task_named(&laptop, "Deploy").await.unwrap().spec;
+        assert_eq!(spec.as_deref(), Some("desktop 2"));
+    }
+
+    #[tokio::test]
+    async fn test_deletes_propagate_as_tombstones() {
+        let (laptop, desktop, hub) = (
+            TestContext::new().await,
+            TestContext::new().await,
+            TempDir::new().unwrap(),
+        );
+        let tasks = TaskManager::new(laptop.pool());
+        let task = tasks
+            .add_task("Spike", None, None, None, None, None)
+            .await
+            .unwrap();
+        EventManager::new(laptop.pool())
+            .add_event(task.id, "note", "Tried it")
+            .await
+            .unwrap();
+        sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        assert!(task_named(&desktop, "Spike").await.is_some());
+
+        tasks.delete_task(task.id).await.unwrap();
+        let pushed = sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        // The task and, through the cascade, its event
+        assert_eq!(pushed.pushed, 2);
+
+        let pulled = sync(&desktop, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap();
+        assert_eq!(pulled.deleted, 2);
+        assert!(task_named(&desktop, "Spike").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_private_rows_never_reach_the_hub() {
+        use crate::db::models::TaskVisibility;
+
+        let (laptop, hub) = (TestContext::new().await, TempDir::new().unwrap());
+        let tasks = TaskManager::new(laptop.pool());
+        let public = tasks
+            .add_task("Release", None, None, None, None, None)
+            .await
+            .unwrap();
+        let secret = tasks
+            .add_task(
+                "Salary talk",
+                Some("confidential spec"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        tasks
+            .update_task(
+                secret.id,
+                TaskUpdate {
+                    visibility: Some(TaskVisibility::Private),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let events = EventManager::new(laptop.pool()).with_scope(TaskVisibility::Private);
+        events
+            .add_event(secret.id, "note", "confidential note")
+            .await
+            .unwrap();
+        events
+            .add_event_with_visibility(
+                public.id,
+                "note",
+                "scratch thoughts",
+                crate::db::models::EventVisibility::Private,
+            )
+            .await
+            .unwrap();
+        crate::dependencies::add_dependency(laptop.pool(), secret.id, public.id)
+            .await
+            .unwrap();
+
+        let pushed = sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+        assert_eq!(pushed.pushed, 1);
+        let snapshot = std::fs::read_to_string(hub.path().join(SNAPSHOT_FILE)).unwrap();
+        assert!(snapshot.contains("Release"));
+        for secret in ["Salary talk", "confidential", "scratch thoughts"] {
+            assert!(!snapshot.contains(secret), "{} reached the hub", secret);
+        }
+        let status = sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .status()
+            .await
+            .unwrap();
+        assert_eq!(status.pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_copied_database_is_refused() {
+        let (laptop, copy, hub) = (
+            TestContext::new().await,
+            TestContext::new().await,
+            TempDir::new().unwrap(),
+        );
+        TaskManager::new(laptop.pool())
+            .add_task("Original", None, None, None, None, None)
+            .await
+            .unwrap();
+        sync(&laptop, &hub, ConflictPolicy::Lamport)
+            .push()
+            .await
+            .unwrap();
+
+        let replica = store::replica_id(laptop.pool()).await.unwrap();
+        store::set_state(copy.pool(), "replica_id", &replica)
+            .await
+            .unwrap();
+        let err = sync(&copy, &hub, ConflictPolicy::Lamport)
+            .pull()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("copied from another machine"),
+            "{}",
+            err
+        );
+    }
+}
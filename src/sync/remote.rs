@@ -0,0 +1,295 @@
+//! Where the hub snapshot lives: a directory, an HTTP URL or an S3 prefix.
+
+use super::s3::{self, S3Location};
+use super::SNAPSHOT_FILE;
+use crate::error::{IntentError, Result};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Timeout of one hub request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Environment variable holding a bearer token for HTTP hubs
+pub const TOKEN_ENV: &str = "IE_SYNC_TOKEN";
+
+/// A hub location, parsed from `[sync] remote` or `--remote`
+///
+/// - `s3://bucket/prefix`: S3, with the usual `AWS_*` credentials
+///   (`AWS_ENDPOINT_URL` for S3-compatible stores)
+/// - `https://host/path`: plain `GET`/`PUT`, with `IE_SYNC_TOKEN` as bearer
+///   token if set
+/// - anything else: a directory, e.g. on a shared or synced drive
+#[derive(Debug, Clone, PartialEq)]
+pub enum Remote {
+    Dir(PathBuf),
+    Http(reqwest::Url),
+    S3(S3Location),
+}
+
+/// The snapshot as read from the hub
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    pub body: Vec<u8>,
+    /// Version tag for conditional writes (None if the server sends none)
+    pub etag: Option<String>,
+}
+
+impl Remote {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err(IntentError::InvalidInput(
+                "Sync remote must not be empty".to_string(),
+            ));
+        }
+        if let Some(rest) = spec.strip_prefix("s3://") {
+            return Ok(Remote::S3(S3Location::parse(rest)?));
+        }
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            let url = reqwest::Url::parse(spec).map_err(|e| {
+                IntentError::InvalidInput(format!("Invalid sync remote '{}': {}", spec, e))
+            })?;
+            return Ok(Remote::Http(url));
+        }
+        if spec.contains("://") {
+            return Err(IntentError::InvalidInput(format!(
+                "Unsupported sync remote '{}'. Expected s3://, http(s):// or a directory",
+                spec
+            )));
+        }
+        Ok(Remote::Dir(PathBuf::from(spec)))
+    }
+
+    /// Read the snapshot; None if the hub has none yet
+    pub async fn fetch(&self) -> Result<Option<Fetched>> {
+        match self {
+            Remote::Dir(dir) => match tokio::fs::read(dir.join(SNAPSHOT_FILE)).await {
+                Ok(body) => Ok(Some(Fetched {
+                    etag: Some(content_tag(&body)),
+                    body,
+                })),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            Remote::Http(base) => {
+                let request = client()?.get(object_url(base)?);
+                receive(with_token(request).send().await.map_err(request_error)?).await
+            },
+            Remote::S3(location) => {
+                let request = s3::signed_request(
+                    &client()?,
+                    reqwest::Method::GET,
+                    location,
+                    &[],
+                    Vec::new(),
+                )?;
+                receive(request.send().await.map_err(request_error)?).await
+            },
+        }
+    }
+
+    /// Write the snapshot if the hub still holds the version `expected`
+    /// describes (`None`: no snapshot; `Some(None)`: any)
+    ///
+    /// Returns false when another replica wrote the hub in the meantime.
+    pub async fn store(&self, body: Vec<u8>, expected: Option<Option<&str>>) -> Result<bool> {
+        match self {
+            Remote::Dir(dir) => {
+                tokio::fs::create_dir_all(dir).await?;
+                let path = dir.join(SNAPSHOT_FILE);
+                let current = match tokio::fs::read(&path).await {
+                    Ok(current) => Some(content_tag(&current)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(e.into()),
+                };
+                let unchanged = match expected {
+                    None => current.is_none(),
+                    Some(None) => true,
+                    Some(Some(tag)) => current.as_deref() == Some(tag),
+                };
+                if !unchanged {
+                    return Ok(false);
+                }
+                // Readers never see a half-written snapshot
+                let tmp = dir.join(format!("{}.{}.tmp", SNAPSHOT_FILE, std::process::id()));
+                tokio::fs::write(&tmp, &body).await?;
+                tokio::fs::rename(&tmp, &path).await?;
+                Ok(true)
+            },
+            Remote::Http(base) => {
+                let request = client()?
+                    .put(object_url(base)?)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json");
+                let request = match precondition(expected) {
+                    Some((name, value)) => request.header(name, value),
+                    None => request,
+                };
+                stored(
+                    with_token(request.body(body))
+                        .send()
+                        .await
+                        .map_err(request_error)?,
+                )
+                .await
+            },
+            Remote::S3(location) => {
+                let headers: Vec<(&str, String)> = precondition(expected)
+                    .map(|(name, value)| (name, value.to_string()))
+                    .into_iter()
+                    .collect();
+                let request =
+                    s3::signed_request(&client()?, reqwest::Method::PUT, location, &headers, body)?;
+                stored(request.send().await.map_err(request_error)?).await
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Remote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Remote::Dir(dir) => write!(f, "{}", dir.display()),
+            Remote::Http(url) => write!(f, "{}", url),
+            Remote::S3(location) => write!(f, "{}", location),
+        }
+    }
+}
+
+fn client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| IntentError::OtherError(e.into()))
+}
+
+fn request_error(e: reqwest::Error) -> IntentError {
+    IntentError::OtherError(anyhow::anyhow!("Sync hub request failed: {}", e))
+}
+
+fn with_token(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match std::env::var(TOKEN_ENV) {
+        Ok(token) if !token.is_empty() => request.bearer_auth(token),
+        _ => request,
+    }
+}
+
+/// URL of the snapshot under an HTTP base URL
+fn object_url(base: &reqwest::Url) -> Result<reqwest::Url> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|_| IntentError::InvalidInput(format!("Invalid sync remote '{}'", base)))?
+        .pop_if_empty()
+        .push(SNAPSHOT_FILE);
+    Ok(url)
+}
+
+/// Conditional-write header for the expected hub version
+fn precondition(expected: Option<Option<&str>>) -> Option<(&'static str, &str)> {
+    match expected {
+        None => Some(("if-none-match", "*")),
+        Some(Some(tag)) => Some(("if-match", tag)),
+        Some(None) => None,
+    }
+}
+
+async fn receive(response: reqwest::Response) -> Result<Option<Fetched>> {
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(hub_error(response).await);
+    }
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = response.bytes().await.map_err(request_error)?.to_vec();
+    Ok(Some(Fetched { body, etag }))
+}
+
+async fn stored(response: reqwest::Response) -> Result<bool> {
+    match response.status() {
+        // S3 answers 409 when a concurrent conditional write is in flight
+        reqwest::StatusCode::PRECONDITION_FAILED | reqwest::StatusCode::CONFLICT => Ok(false),
+        status if status.is_success() => Ok(true),
+        _ => Err(hub_error(response).await),
+    }
+}
+
+async fn hub_error(response: reqwest::Response) -> IntentError {
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "(no body)".to_string());
+    IntentError::OtherError(anyhow::anyhow!(
+        "Sync hub error (HTTP {}): {}",
+        status,
+        body.trim()
+    ))
+}
+
+/// Version tag of a snapshot in a directory hub
+fn content_tag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_kinds() {
+        assert_eq!(
+            Remote::parse("/mnt/share/ie").unwrap(),
+            Remote::Dir(PathBuf::from("/mnt/share/ie"))
+        );
+        assert!(matches!(
+            Remote::parse("https://hub.example.com/ie/web").unwrap(),
+            Remote::Http(_)
+        ));
+        let Remote::S3(location) = Remote::parse("s3://team-bucket/ie/web/").unwrap() else {
+            panic!("expected an S3 remote");
+        };
+        assert_eq!(location.key(), "ie/web/intent-engine-sync.json");
+        assert!(Remote::parse("ftp://example.com").is_err());
+        assert!(Remote::parse(" ").is_err());
+    }
+
+    #[test]
+    fn test_object_url_appends_snapshot_file() {
+        let url = reqwest::Url::parse("https://hub.example.com/ie/web/").unwrap();
+        assert_eq!(
+            object_url(&url).unwrap().as_str(),
+            "https://hub.example.com/ie/web/intent-engine-sync.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dir_store_refuses_stale_writes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let remote = Remote::Dir(dir.path().join("hub"));
+        assert!(remote.fetch().await.unwrap().is_none());
+
+        assert!(remote.store(b"one".to_vec(), None).await.unwrap());
+        // Someone else created it first
+        assert!(!remote.store(b"two".to_vec(), None).await.unwrap());
+
+        let fetched = remote.fetch().await.unwrap().unwrap();
+        assert_eq!(fetched.body, b"one");
+        let tag = fetched.etag.unwrap();
+        assert!(remote
+            .store(b"two".to_vec(), Some(Some(&tag)))
+            .await
+            .unwrap());
+        assert!(!remote
+            .store(b"three".to_vec(), Some(Some(&tag)))
+            .await
+            .unwrap());
+        assert_eq!(remote.fetch().await.unwrap().unwrap().body, b"two");
+    }
+}
@@ -0,0 +1,493 @@
+//! Local side of sync: `sync_state`, `sync_rows` and writing hub versions
+//! into the project tables.
+
+use super::{parse_entity, Entity, SyncRecord};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Sqlite, SqliteConnection, SqliteExecutor, SqlitePool};
+use std::collections::{HashMap, HashSet};
+
+/// Tracking state of one row, keyed by uid
+#[derive(Debug, Clone)]
+pub(super) struct LocalRow {
+    pub entity: Entity,
+    /// None for tombstones of rows this replica never had
+    pub local_id: Option<i64>,
+    pub lamport: i64,
+    pub origin: String,
+    pub updated_at: DateTime<Utc>,
+    pub deleted: bool,
+    pub synced_lamport: Option<i64>,
+}
+
+type RowTuple = (
+    String,
+    String,
+    Option<i64>,
+    i64,
+    String,
+    DateTime<Utc>,
+    bool,
+    Option<i64>,
+);
+
+pub(super) async fn load_rows<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+) -> Result<HashMap<String, LocalRow>> {
+    let tuples: Vec<RowTuple> = sqlx::query_as(
+        "SELECT uid, entity, local_id, lamport, origin, updated_at, deleted, synced_lamport \
+         FROM sync_rows",
+    )
+    .fetch_all(executor)
+    .await?;
+
+    let mut rows = HashMap::with_capacity(tuples.len());
+    for (uid, entity, local_id, lamport, origin, updated_at, deleted, synced_lamport) in tuples {
+        rows.insert(
+            uid,
+            LocalRow {
+                entity: parse_entity(&entity)?,
+                local_id,
+                lamport,
+                origin,
+                updated_at,
+                deleted,
+                synced_lamport,
+            },
+        );
+    }
+    Ok(rows)
+}
+
+pub(super) async fn get_state<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    key: &str,
+) -> Result<Option<String>> {
+    Ok(
+        sqlx::query_scalar("SELECT value FROM sync_state WHERE key = ?")
+            .bind(key)
+            .fetch_optional(executor)
+            .await?,
+    )
+}
+
+pub(super) async fn set_state<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    key: &str,
+    value: &str,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO sync_state (key, value) VALUES (?, ?) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub(super) async fn replica_id<'e, E: SqliteExecutor<'e>>(executor: E) -> Result<String> {
+    get_state(executor, "replica_id").await?.ok_or_else(|| {
+        crate::error::IntentError::OtherError(anyhow::anyhow!(
+            "Database has no sync replica ID; run any 'ie' command to migrate it"
+        ))
+    })
+}
+
+/// Switch the change-tracking triggers off (while applying hub versions) or on
+pub(super) async fn set_applying(conn: &mut SqliteConnection, applying: bool) -> Result<()> {
+    if applying {
+        set_state(&mut *conn, "applying", "1").await
+    } else {
+        sqlx::query("DELETE FROM sync_state WHERE key = 'applying'")
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Advance the Lamport clock by one and return the new value
+pub(super) async fn tick(conn: &mut SqliteConnection) -> Result<i64> {
+    Ok(sqlx::query_scalar(
+        "UPDATE sync_state SET value = CAST(value AS INTEGER) + 1 WHERE key = 'clock' \
+         RETURNING CAST(value AS INTEGER)",
+    )
+    .fetch_one(&mut *conn)
+    .await?)
+}
+
+/// Move the clock past every version seen on the hub
+pub(super) async fn advance_clock(conn: &mut SqliteConnection, seen: i64) -> Result<()> {
+    sqlx::query("UPDATE sync_state SET value = MAX(CAST(value AS INTEGER), ?) WHERE key = 'clock'")
+        .bind(seen)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// The local version is the hub version
+pub(super) async fn record_synced(
+    conn: &mut SqliteConnection,
+    uid: &str,
+    lamport: i64,
+) -> Result<()> {
+    sqlx::query("UPDATE sync_rows SET synced_lamport = ? WHERE uid = ?")
+        .bind(lamport)
+        .bind(uid)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Keep the local version of a conflicting row, re-stamped as newer than `record`
+pub(super) async fn keep_local(
+    conn: &mut SqliteConnection,
+    uid: &str,
+    lamport: i64,
+    replica: &str,
+    record: &SyncRecord,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE sync_rows SET lamport = ?, origin = ?, updated_at = ?, synced_lamport = ? \
+         WHERE uid = ?",
+    )
+    .bind(lamport)
+    .bind(replica)
+    .bind(Utc::now().max(record.updated_at))
+    .bind(record.lamport)
+    .bind(uid)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Hub records for every row with local changes
+pub(super) async fn outgoing(pool: &SqlitePool) -> Result<Vec<SyncRecord>> {
+    let mut conn = pool.acquire().await?;
+    let rows = load_rows(&mut *conn).await?;
+    let task_uids: HashMap<i64, &str> = rows
+        .iter()
+        .filter(|(_, row)| row.entity == Entity::Task)
+        .filter_map(|(uid, row)| row.local_id.map(|id| (id, uid.as_str())))
+        .collect();
+
+    let mut data: HashMap<(Entity, i64), serde_json::Map<String, Value>> = HashMap::new();
+    let mut private = HashSet::new();
+    for entity in Entity::ALL {
+        let columns = columns(&mut conn, entity).await?;
+        let pairs = columns
+            .iter()
+            .map(|c| format!("'{}', \"{}\"", c, c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let changed: Vec<(i64, bool, String)> = sqlx::query_as(&format!(
+            "SELECT id, {shared}, json_object({pairs}) FROM {table} WHERE id IN ( \
+                 SELECT local_id FROM sync_rows \
+                 WHERE entity = ? AND synced_lamport IS NOT lamport)",
+            shared = entity.shared_filter(),
+            table = entity.table()
+        ))
+        .bind(entity.as_str())
+        .fetch_all(&mut *conn)
+        .await?;
+        for (id, shared, json) in changed {
+            if !shared {
+                private.insert((entity, id));
+            } else if let Value::Object(map) = serde_json::from_str(&json)? {
+                data.insert((entity, id), map);
+            }
+        }
+    }
+
+    let mut records = Vec::new();
+    for (uid, row) in &rows {
+        if row.synced_lamport == Some(row.lamport) {
+            continue;
+        }
+        // Private rows are held back until they are shared
+        if row
+            .local_id
+            .is_some_and(|id| private.contains(&(row.entity, id)))
+        {
+            continue;
+        }
+        // A row gone without a tombstone was removed by a cascade during a pull
+        let mut values = match row.local_id.and_then(|id| data.remove(&(row.entity, id))) {
+            Some(values) if !row.deleted => Some(values),
+            _ => None,
+        };
+        if let Some(values) = values.as_mut() {
+            let mut complete = true;
+            for column in row.entity.task_references() {
+                let Some(value) = values.get_mut(*column) else {
+                    continue;
+                };
+                if let Some(id) = value.as_i64() {
+                    match task_uids.get(&id) {
+                        Some(uid) => *value = Value::String(uid.to_string()),
+                        None => complete = false,
+                    }
+                }
+            }
+            if !complete {
+                tracing::warn!(uid = %uid, "Not pushing a row that references an untracked task");
+                continue;
+            }
+        }
+        records.push(SyncRecord {
+            uid: uid.clone(),
+            entity: row.entity,
+            lamport: row.lamport,
+            origin: row.origin.clone(),
+            updated_at: row.updated_at,
+            deleted: values.is_none(),
+            data: values,
+        });
+    }
+    Ok(records)
+}
+
+/// Record pushed versions as synced, unless the row changed again meanwhile
+pub(super) async fn mark_synced(pool: &SqlitePool, records: &[SyncRecord]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    for record in records {
+        sqlx::query("UPDATE sync_rows SET synced_lamport = lamport WHERE uid = ? AND lamport = ?")
+            .bind(&record.uid)
+            .bind(record.lamport)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Synced columns of a table: all but `id`
+async fn columns(conn: &mut SqliteConnection, entity: Entity) -> Result<Vec<String>> {
+    Ok(
+        sqlx::query_scalar("SELECT name FROM pragma_table_info(?) WHERE name != 'id' ORDER BY cid")
+            .bind(entity.table())
+            .fetch_all(&mut *conn)
+            .await?,
+    )
+}
+
+/// Writes hub versions into the project tables
+pub(super) struct Applier<'t> {
+    conn: &'t mut SqliteConnection,
+    columns: HashMap<Entity, Vec<String>>,
+    /// Tasks whose parent comes later in the snapshot: (local ID, parent uid)
+    orphans: Vec<(i64, String)>,
+}
+
+impl<'t> Applier<'t> {
+    pub async fn new(conn: &'t mut SqliteConnection) -> Result<Self> {
+        let mut columns_by_entity = HashMap::new();
+        for entity in Entity::ALL {
+            columns_by_entity.insert(entity, columns(conn, entity).await?);
+        }
+        Ok(Self {
+            conn,
+            columns: columns_by_entity,
+            orphans: Vec::new(),
+        })
+    }
+
+    pub fn conn(&mut self) -> &mut SqliteConnection {
+        self.conn
+    }
+
+    /// Write one hub version; None if it has to wait for a task this
+    /// replica does not have
+    pub async fn apply(
+        &mut self,
+        record: &SyncRecord,
+        rows: &HashMap<String, LocalRow>,
+    ) -> Result<Option<LocalRow>> {
+        let table = record.entity.table();
+        let local_id = rows.get(&record.uid).and_then(|row| row.local_id);
+
+        let data = match (&record.data, record.deleted) {
+            (Some(data), false) => data,
+            _ => {
+                if let Some(id) = local_id {
+                    sqlx::query(&format!("DELETE FROM {} WHERE id = ?", table))
+                        .bind(id)
+                        .execute(&mut *self.conn)
+                        .await?;
+                }
+                return self.track(record, local_id).await.map(Some);
+            },
+        };
+
+        let known = &self.columns[&record.entity];
+        let mut values: Vec<(&str, Value)> = Vec::new();
+        let mut missing_parent = None;
+        for (column, value) in data {
+            // Columns of newer versions of ie are left out
+            let Some(column) = known.iter().find(|c| *c == column) else {
+                continue;
+            };
+            let value = if record.entity.task_references().contains(&column.as_str()) {
+                match value {
+                    Value::String(uid) => match task_id(rows, uid) {
+                        Some(id) => Value::from(id),
+                        None if record.entity == Entity::Task => {
+                            missing_parent = Some(uid.clone());
+                            Value::Null
+                        },
+                        None => return Ok(None),
+                    },
+                    _ => Value::Null,
+                }
+            } else {
+                value.clone()
+            };
+            values.push((column.as_str(), value));
+        }
+
+        let exists = match local_id {
+            Some(id) => {
+                sqlx::query_scalar::<_, bool>(&format!(
+                    "SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?)",
+                    table
+                ))
+                .bind(id)
+                .fetch_one(&mut *self.conn)
+                .await?
+            },
+            None => false,
+        };
+
+        let id = if exists {
+            let id = local_id.expect("existing rows have an ID");
+            let assignments = values
+                .iter()
+                .map(|(c, _)| format!("\"{}\" = ?", c))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!("UPDATE {} SET {} WHERE id = ?", table, assignments);
+            let mut query = sqlx::query(&sql);
+            for (_, value) in &values {
+                query = bind_json(query, value);
+            }
+            query.bind(id).execute(&mut *self.conn).await?;
+            id
+        } else {
+            // A row deleted here comes back under its old ID
+            let mut columns: Vec<&str> = values.iter().map(|(c, _)| *c).collect();
+            if local_id.is_some() {
+                columns.push("id");
+            }
+            let sql = format!(
+                "INSERT {} INTO {} ({}) VALUES ({})",
+                // The same dependency may exist under another uid
+                if record.entity == Entity::Dependency {
+                    "OR IGNORE"
+                } else {
+                    ""
+                },
+                table,
+                columns
+                    .iter()
+                    .map(|c| format!("\"{}\"", c))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                vec!["?"; columns.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql);
+            for (_, value) in &values {
+                query = bind_json(query, value);
+            }
+            if let Some(id) = local_id {
+                query = query.bind(id);
+            }
+            let result = query.execute(&mut *self.conn).await?;
+            if result.rows_affected() == 0 {
+                return Ok(None);
+            }
+            local_id.unwrap_or_else(|| result.last_insert_rowid())
+        };
+
+        if record.entity == Entity::Task {
+            let spec = data.get("spec").and_then(Value::as_str);
+            crate::spec_meta::sync(self.conn, id, spec).await?;
+            if let Some(parent) = missing_parent {
+                self.orphans.push((id, parent));
+            }
+        }
+        self.track(record, Some(id)).await.map(Some)
+    }
+
+    /// Attach tasks whose parent arrived after them
+    pub async fn finish(self, rows: &HashMap<String, LocalRow>) -> Result<()> {
+        for (id, parent) in &self.orphans {
+            if let Some(parent_id) = task_id(rows, parent) {
+                sqlx::query("UPDATE tasks SET parent_id = ? WHERE id = ?")
+                    .bind(parent_id)
+                    .bind(id)
+                    .execute(&mut *self.conn)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a hub version as this row's synced state
+    async fn track(&mut self, record: &SyncRecord, local_id: Option<i64>) -> Result<LocalRow> {
+        sqlx::query(
+            "INSERT INTO sync_rows \
+                 (uid, entity, local_id, lamport, origin, updated_at, deleted, synced_lamport) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(uid) DO UPDATE SET local_id = excluded.local_id, \
+                 lamport = excluded.lamport, origin = excluded.origin, \
+                 updated_at = excluded.updated_at, deleted = excluded.deleted, \
+                 synced_lamport = excluded.synced_lamport",
+        )
+        .bind(&record.uid)
+        .bind(record.entity.as_str())
+        .bind(local_id)
+        .bind(record.lamport)
+        .bind(&record.origin)
+        .bind(record.updated_at)
+        .bind(record.deleted)
+        .bind(record.lamport)
+        .execute(&mut *self.conn)
+        .await?;
+
+        Ok(LocalRow {
+            entity: record.entity,
+            local_id,
+            lamport: record.lamport,
+            origin: record.origin.clone(),
+            updated_at: record.updated_at,
+            deleted: record.deleted,
+            synced_lamport: Some(record.lamport),
+        })
+    }
+}
+
+/// Local ID of a live task
+fn task_id(rows: &HashMap<String, LocalRow>, uid: &str) -> Option<i64> {
+    rows.get(uid)
+        .filter(|row| row.entity == Entity::Task && !row.deleted)
+        .and_then(|row| row.local_id)
+}
+
+fn bind_json<'q>(
+    query: sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: &Value,
+) -> sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        Value::Null => query.bind(None::<i64>),
+        Value::Bool(b) => query.bind(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => query.bind(i),
+            None => query.bind(n.as_f64()),
+        },
+        Value::String(s) => query.bind(s.clone()),
+        other => query.bind(other.to_string()),
+    }
+}
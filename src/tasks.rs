@@ -1,15 +1,36 @@
 use crate::db::models::{
-    DoneTaskResponse, Event, EventsSummary, NextStepSuggestion, PaginatedTasks, ParentTaskInfo,
-    PickNextResponse, SpawnSubtaskResponse, SubtaskInfo, Task, TaskSortBy, TaskWithEvents,
-    WorkspaceStats, WorkspaceStatus,
+    Actor, ChildrenPage, CloneSubtreeResponse, CompletionOutcome, DoneTaskResponse, EstimateRollup,
+    Event, EventVisibility, EventsSummary, NextStepSuggestion, PaginatedTasks, ParentTaskInfo,
+    PickNextCandidate, PickNextResponse, SpawnSubtaskResponse, SpecRevision, SubtaskInfo,
+    SubtreeStats, Task, TaskLease, TaskSortBy, TaskTransition, TaskTreeNode, TaskVisibility,
+    TaskWithEvents, TransitionSource, UnblockedTask, WorkspaceStats, WorkspaceStatus,
 };
 use crate::error::{IntentError, Result};
+use crate::notifications::desktop::DesktopNotifier;
+use crate::policy::PolicyEngine;
+use crate::settings::SettingsManager;
+use crate::stale::{StalePolicy, StaleSweep, StaleTask};
+use crate::wip::{WipBatch, WipLimits};
 use chrono::Utc;
+use sqlx::types::Json;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 
 pub use crate::db::models::TaskContext;
 
+/// Lease length of `TaskManager::claim_next`
+pub const DEFAULT_LEASE_MINUTES: i64 = 30;
+
+/// Upper bound for a single page of [`TaskManager::find_children`]
+pub const MAX_CHILDREN_PAGE_SIZE: i64 = 500;
+
+/// Sort key of children pages: priority ascending with unset priorities last
+const CHILD_SORT_KEY: &str = "COALESCE(priority, 2147483647)";
+
+/// Rows per multi-row statement of the batch methods, well below SQLite's
+/// limit on bound parameters
+pub(crate) const BATCH_ROWS: usize = 500;
+
 /// Result of a delete operation within a transaction
 #[derive(Debug, Clone)]
 pub struct DeleteTaskResult {
@@ -19,6 +40,19 @@ pub struct DeleteTaskResult {
     pub descendant_count: i64,
 }
 
+/// A task for `TaskManager::create_tasks_in_tx`
+#[derive(Debug, Default)]
+pub struct NewTask<'a> {
+    pub name: &'a str,
+    pub spec: Option<&'a str>,
+    /// 1=critical, 2=high, 3=medium (default), 4=low
+    pub priority: Option<i32>,
+    /// Defaults to "todo"
+    pub status: Option<&'a str>,
+    pub active_form: Option<&'a str>,
+    pub parent_id: Option<i64>,
+}
+
 /// Parameter struct for `TaskManager::update_task`.
 /// Only set the fields you want to change; the rest default to `None` (no change).
 #[derive(Debug, Default)]
@@ -32,6 +66,27 @@ pub struct TaskUpdate<'a> {
     pub active_form: Option<&'a str>,
     pub owner: Option<&'a str>,
     pub metadata: Option<&'a str>,
+    pub sort_order: Option<i64>,
+    /// Estimated effort in minutes
+    pub estimate_minutes: Option<i64>,
+    /// Why the task is waiting on a human; "" clears the flag
+    pub needs_human: Option<&'a str>,
+    /// Why the task cannot move; "" unblocks it
+    pub blocked_reason: Option<&'a str>,
+    /// Applies to the whole subtree
+    pub visibility: Option<TaskVisibility>,
+}
+
+/// Options for `TaskManager::clone_subtree`
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    /// Name of the copied root task; defaults to "<original name> (copy)", so
+    /// that plans naming the original keep finding it
+    pub name: Option<String>,
+    /// Copy the events of every task too
+    pub include_events: bool,
+    /// Keep statuses (and their timestamps) instead of resetting everything to todo
+    pub keep_status: bool,
 }
 
 pub struct TaskManager<'a> {
@@ -39,6 +94,14 @@ pub struct TaskManager<'a> {
     notifier: crate::notifications::NotificationSender,
     cli_notifier: Option<crate::dashboard::cli_notifier::CliNotifier>,
     project_path: Option<String>,
+    /// Highest event visibility included in task details and status
+    event_visibility: EventVisibility,
+    /// Whether private tasks are visible
+    scope: TaskVisibility,
+    /// Recorded with every status change
+    source: TransitionSource,
+    /// Who is making changes; also decides whether human-owned tasks may be completed
+    actor: Actor,
 }
 
 impl<'a> TaskManager<'a> {
@@ -48,6 +111,10 @@ impl<'a> TaskManager<'a> {
             notifier: crate::notifications::NotificationSender::new(None),
             cli_notifier: Some(crate::dashboard::cli_notifier::CliNotifier::new()),
             project_path: None,
+            event_visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            source: TransitionSource::Cli,
+            actor: Actor::from_env(),
         }
     }
 
@@ -55,9 +122,14 @@ impl<'a> TaskManager<'a> {
     pub fn with_project_path(pool: &'a SqlitePool, project_path: String) -> Self {
         Self {
             pool,
-            notifier: crate::notifications::NotificationSender::new(None),
+            notifier: crate::notifications::NotificationSender::new(None)
+                .with_offline_queue(pool.clone()),
             cli_notifier: Some(crate::dashboard::cli_notifier::CliNotifier::new()),
             project_path: Some(project_path),
+            event_visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            source: TransitionSource::Cli,
+            actor: Actor::from_env(),
         }
     }
 
@@ -69,17 +141,52 @@ impl<'a> TaskManager<'a> {
     ) -> Self {
         Self {
             pool,
-            notifier: crate::notifications::NotificationSender::new(Some(ws_state)),
+            notifier: crate::notifications::NotificationSender::new(Some(ws_state))
+                .with_offline_queue(pool.clone()),
             cli_notifier: None, // Dashboard context doesn't need CLI notifier
             project_path: Some(project_path),
+            event_visibility: EventVisibility::Normal,
+            scope: crate::project::task_scope(),
+            source: TransitionSource::Dashboard,
+            actor: Actor::human().with_client("dashboard"),
         }
     }
 
+    /// Include `verbose` (and, at `Private`, `private`) events in task details and status
+    pub fn with_event_visibility(mut self, visibility: EventVisibility) -> Self {
+        self.event_visibility = visibility;
+        self
+    }
+
+    /// Use an explicit task scope instead of the process-wide `--scope`
+    pub fn with_scope(mut self, scope: TaskVisibility) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Attribute status changes to `source` (CLI by default, Dashboard for `with_websocket`)
+    pub fn with_source(mut self, source: TransitionSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Act as `actor` instead of the one from the environment (see `Actor::from_env`)
+    pub fn with_actor(mut self, actor: Actor) -> Self {
+        self.actor = actor;
+        self
+    }
+
     /// Internal helper: Notify UI about task creation
     async fn notify_task_created(&self, task: &Task) {
         use crate::dashboard::websocket::DatabaseOperationPayload;
 
+        // Private tasks never reach the Dashboard, which runs in the shared scope
+        if !task.visibility.is_shared() {
+            return;
+        }
+
         // WebSocket notification (Dashboard context)
+        let mut payload = None;
         if let Some(project_path) = &self.project_path {
             let task_json = match serde_json::to_value(task) {
                 Ok(json) => json,
@@ -89,24 +196,28 @@ impl<'a> TaskManager<'a> {
                 },
             };
 
-            let payload =
+            let changed =
                 DatabaseOperationPayload::task_created(task.id, task_json, project_path.clone());
-            self.notifier.send(payload).await;
+            self.notifier.send(changed.clone()).await;
+            payload = Some(changed);
         }
 
         // CLI → Dashboard HTTP notification (CLI context)
-        if let Some(cli_notifier) = &self.cli_notifier {
-            cli_notifier
-                .notify_task_changed(Some(task.id), "created", self.project_path.clone())
-                .await;
-        }
+        self.notify_cli_task_changed(Some(task.id), "created", payload)
+            .await;
     }
 
     /// Internal helper: Notify UI about task update
     async fn notify_task_updated(&self, task: &Task) {
         use crate::dashboard::websocket::DatabaseOperationPayload;
 
+        // To the Dashboard, a task that turned private is gone
+        if !task.visibility.is_shared() {
+            return self.notify_task_deleted(task.id).await;
+        }
+
         // WebSocket notification (Dashboard context)
+        let mut payload = None;
         if let Some(project_path) = &self.project_path {
             let task_json = match serde_json::to_value(task) {
                 Ok(json) => json,
@@ -116,17 +227,15 @@ impl<'a> TaskManager<'a> {
                 },
             };
 
-            let payload =
+            let changed =
                 DatabaseOperationPayload::task_updated(task.id, task_json, project_path.clone());
-            self.notifier.send(payload).await;
+            self.notifier.send(changed.clone()).await;
+            payload = Some(changed);
         }
 
         // CLI → Dashboard HTTP notification (CLI context)
-        if let Some(cli_notifier) = &self.cli_notifier {
-            cli_notifier
-                .notify_task_changed(Some(task.id), "updated", self.project_path.clone())
-                .await;
-        }
+        self.notify_cli_task_changed(Some(task.id), "updated", payload)
+            .await;
     }
 
     /// Internal helper: Notify UI about task deletion
@@ -134,17 +243,85 @@ impl<'a> TaskManager<'a> {
         use crate::dashboard::websocket::DatabaseOperationPayload;
 
         // WebSocket notification (Dashboard context)
-        if let Some(project_path) = &self.project_path {
-            let payload = DatabaseOperationPayload::task_deleted(task_id, project_path.clone());
-            self.notifier.send(payload).await;
+        let payload = self.project_path.as_ref().map(|project_path| {
+            DatabaseOperationPayload::task_deleted(task_id, project_path.clone())
+        });
+        if let Some(payload) = &payload {
+            self.notifier.send(payload.clone()).await;
         }
 
         // CLI → Dashboard HTTP notification (CLI context)
-        if let Some(cli_notifier) = &self.cli_notifier {
-            cli_notifier
-                .notify_task_changed(Some(task_id), "deleted", self.project_path.clone())
-                .await;
+        self.notify_cli_task_changed(Some(task_id), "deleted", payload)
+            .await;
+    }
+
+    /// Internal helper: Notify Dashboard over HTTP (CLI context)
+    ///
+    /// When no Dashboard UI received it, `payload` is queued for the next
+    /// one to connect (see `notifications::queue`).
+    async fn notify_cli_task_changed(
+        &self,
+        task_id: Option<i64>,
+        operation: &str,
+        payload: Option<crate::dashboard::websocket::DatabaseOperationPayload>,
+    ) {
+        let Some(cli_notifier) = &self.cli_notifier else {
+            return;
+        };
+        let delivered = cli_notifier
+            .notify_task_changed(task_id, operation, self.project_path.clone())
+            .await;
+        if let (false, Some(payload)) = (delivered, payload) {
+            self.notifier.queue(payload).await;
+        }
+    }
+
+    /// Internal helper: Announce tasks that just became ready to start
+    ///
+    /// Shared tasks go to the Dashboard UI and to the project's MCP client;
+    /// all of them to the desktop and webhooks (see `notifications::desktop`).
+    pub(crate) async fn notify_unblocked(&self, unblocked: &[UnblockedTask]) {
+        use crate::dashboard::websocket::{DatabaseOperationPayload, TasksUnblockedPayload};
+
+        let shared: Vec<UnblockedTask> = unblocked
+            .iter()
+            .filter(|task| task.visibility.is_shared())
+            .cloned()
+            .collect();
+        if !shared.is_empty() {
+            // WebSocket notification (Dashboard context)
+            let mut payload = None;
+            if let Some(project_path) = &self.project_path {
+                let changed =
+                    DatabaseOperationPayload::tasks_unblocked(&shared, project_path.clone());
+                self.notifier.send(changed.clone()).await;
+                self.notifier
+                    .send_to_mcp(
+                        project_path,
+                        "tasks_unblocked",
+                        TasksUnblockedPayload {
+                            project_path: project_path.clone(),
+                            tasks: shared.clone(),
+                        },
+                    )
+                    .await;
+                payload = Some(changed);
+            }
+
+            // CLI → Dashboard HTTP notification (CLI context)
+            if let Some(cli_notifier) = &self.cli_notifier {
+                let delivered = cli_notifier
+                    .notify_tasks_unblocked(shared, self.project_path.clone())
+                    .await;
+                if let (false, Some(payload)) = (delivered, payload) {
+                    self.notifier.queue(payload).await;
+                }
+            }
         }
+
+        DesktopNotifier::new(self.pool)
+            .tasks_unblocked(unblocked)
+            .await;
     }
 
     /// Add a new task
@@ -165,8 +342,18 @@ impl<'a> TaskManager<'a> {
         }
 
         let now = Utc::now();
-        let owner = owner.unwrap_or("human");
+        let default_owner;
+        let owner = match owner {
+            Some(owner) => owner,
+            None => {
+                default_owner = crate::config::ProjectConfig::for_pool(self.pool)?
+                    .tasks
+                    .default_owner;
+                default_owner.as_str()
+            },
+        };
 
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             r#"
             INSERT INTO tasks (name, spec, parent_id, status, first_todo_at, owner, priority, metadata)
@@ -180,10 +367,12 @@ impl<'a> TaskManager<'a> {
         .bind(owner)
         .bind(priority)
         .bind(metadata)
-        .execute(self.pool)
+        .execute(&mut *tx)
         .await?;
 
         let id = result.last_insert_rowid();
+        crate::spec_meta::sync(&mut tx, id, spec).await?;
+        tx.commit().await?;
         let task = self.get_task(id).await?;
 
         // Notify WebSocket clients about the new task
@@ -197,55 +386,70 @@ impl<'a> TaskManager<'a> {
     // These methods do NOT notify - caller is responsible for notifications
     // =========================================================================
 
-    /// Create a task within a transaction (no notification)
+    /// Create tasks within a transaction (no notification)
     ///
     /// This is used by PlanExecutor for batch operations where:
     /// - Multiple tasks need atomic creation
     /// - Notification should happen after all tasks are committed
     ///
+    /// Rows are inserted `BATCH_ROWS` at a time, so a large plan costs a
+    /// handful of statements instead of one per task.
+    ///
     /// # Arguments
     /// * `tx` - The active transaction
-    /// * `name` - Task name
-    /// * `spec` - Optional task specification
-    /// * `priority` - Optional priority (1=critical, 2=high, 3=medium, 4=low)
-    /// * `status` - Optional status string ("todo", "doing", "done")
-    /// * `active_form` - Optional active form description
+    /// * `tasks` - The tasks to create; parents must already exist
     /// * `owner` - Task owner (e.g. "human", "ai", or any custom string)
     ///
     /// # Returns
-    /// The ID of the created task
-    #[allow(clippy::too_many_arguments)]
-    pub async fn create_task_in_tx(
+    /// The IDs of the created tasks, in the order of `tasks`
+    pub async fn create_tasks_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
-        name: &str,
-        spec: Option<&str>,
-        priority: Option<i32>,
-        status: Option<&str>,
-        active_form: Option<&str>,
+        tasks: &[NewTask<'_>],
         owner: &str,
-    ) -> Result<i64> {
+    ) -> Result<Vec<i64>> {
         let now = Utc::now();
-        let status = status.unwrap_or("todo");
-        let priority = priority.unwrap_or(3); // Default: medium
+        let mut ids = Vec::with_capacity(tasks.len());
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO tasks (name, spec, priority, status, active_form, first_todo_at, owner)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(name)
-        .bind(spec)
-        .bind(priority)
-        .bind(status)
-        .bind(active_form)
-        .bind(now)
-        .bind(owner)
-        .execute(&mut **tx)
-        .await?;
+        for chunk in tasks.chunks(BATCH_ROWS) {
+            let rows = vec!["(?, ?, ?, ?, ?, ?, ?, ?)"; chunk.len()].join(", ");
+            let sql = format!(
+                "INSERT INTO tasks (name, spec, priority, status, active_form, parent_id, first_todo_at, owner) VALUES {}",
+                rows
+            );
+            let mut query = sqlx::query(&sql);
+            for task in chunk {
+                query = query
+                    .bind(task.name)
+                    .bind(task.spec)
+                    .bind(task.priority.unwrap_or(3)) // Default: medium
+                    .bind(task.status.unwrap_or("todo"))
+                    .bind(task.active_form)
+                    .bind(task.parent_id)
+                    .bind(now)
+                    .bind(owner);
+            }
+            let result = query.execute(&mut **tx).await?;
+
+            // The rows of one statement get consecutive ids (AUTOINCREMENT, and
+            // the transaction holds the write lock), ending at the last one
+            let last = result.last_insert_rowid();
+            let first = last - chunk.len() as i64 + 1;
+            ids.extend(first..=last);
+        }
+
+        // Fresh tasks have no spec_meta row to clear, only frontmatter to store
+        for (task, &id) in tasks.iter().zip(&ids) {
+            let meta = task
+                .spec
+                .map(crate::spec_meta::SpecMeta::parse)
+                .transpose()?;
+            if let Some(meta) = meta.flatten() {
+                crate::spec_meta::store(tx, id, &meta).await?;
+            }
+        }
 
-        Ok(result.last_insert_rowid())
+        Ok(ids)
     }
 
     /// Update a task within a transaction (no notification)
@@ -271,11 +475,13 @@ impl<'a> TaskManager<'a> {
     ) -> Result<()> {
         // Update spec if provided
         if let Some(spec) = spec {
+            self.record_spec_revision_in_tx(tx, task_id, spec).await?;
             sqlx::query("UPDATE tasks SET spec = ? WHERE id = ?")
                 .bind(spec)
                 .bind(task_id)
                 .execute(&mut **tx)
                 .await?;
+            crate::spec_meta::sync(tx, task_id, Some(spec)).await?;
         }
 
         // Update priority if provided
@@ -289,6 +495,7 @@ impl<'a> TaskManager<'a> {
 
         // Update status if provided
         if let Some(status) = status {
+            self.record_transition_in_tx(tx, task_id, status).await?;
             sqlx::query("UPDATE tasks SET status = ? WHERE id = ?")
                 .bind(status)
                 .bind(task_id)
@@ -308,36 +515,205 @@ impl<'a> TaskManager<'a> {
         Ok(())
     }
 
-    /// Set parent_id for a task within a transaction (no notification)
-    ///
-    /// Used to establish parent-child relationships after tasks are created.
-    pub async fn set_parent_in_tx(
+    /// Set a task's effort estimate within a transaction
+    pub async fn set_estimate_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
         task_id: i64,
-        parent_id: i64,
+        estimate_minutes: i64,
     ) -> Result<()> {
-        sqlx::query("UPDATE tasks SET parent_id = ? WHERE id = ?")
-            .bind(parent_id)
+        sqlx::query("UPDATE tasks SET estimate_minutes = ? WHERE id = ?")
+            .bind(estimate_minutes)
             .bind(task_id)
             .execute(&mut **tx)
             .await?;
+        Ok(())
+    }
 
+    /// Flag a task as waiting on a human within a transaction; "" clears the flag
+    pub async fn set_needs_human_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        reason: &str,
+    ) -> Result<()> {
+        sqlx::query("UPDATE tasks SET needs_human = NULLIF(TRIM(?), '') WHERE id = ?")
+            .bind(reason)
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
         Ok(())
     }
 
-    /// Clear parent_id for a task in a transaction (make it a root task)
-    ///
-    /// Used when explicitly setting parent_id to null in JSON.
-    pub async fn clear_parent_in_tx(
+    /// Mark a task as blocked within a transaction; "" unblocks it
+    pub async fn set_blocked_reason_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
         task_id: i64,
+        reason: &str,
     ) -> Result<()> {
-        sqlx::query("UPDATE tasks SET parent_id = NULL WHERE id = ?")
+        sqlx::query("UPDATE tasks SET blocked_reason = NULLIF(TRIM(?), '') WHERE id = ?")
+            .bind(reason)
             .bind(task_id)
             .execute(&mut **tx)
             .await?;
+        Ok(())
+    }
+
+    /// Put a task in the named milestone, creating the milestone if needed
+    ///
+    /// A task belongs to at most one milestone, so this moves it out of any
+    /// other. An empty name removes the task from its milestone.
+    pub async fn assign_milestone_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        milestone: &str,
+    ) -> Result<()> {
+        let milestone = milestone.trim();
+        if milestone.is_empty() {
+            sqlx::query("DELETE FROM milestone_tasks WHERE task_id = ?")
+                .bind(task_id)
+                .execute(&mut **tx)
+                .await?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO milestones (name, created_at) VALUES (?, ?) ON CONFLICT(name) DO NOTHING",
+        )
+        .bind(milestone)
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO milestone_tasks (task_id, milestone_id)
+            SELECT ?, id FROM milestones WHERE name = ?
+            ON CONFLICT(task_id) DO UPDATE SET milestone_id = excluded.milestone_id
+            "#,
+        )
+        .bind(task_id)
+        .bind(milestone)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a status change in `task_transitions` (call before writing the status)
+    ///
+    /// Unchanged statuses are not recorded.
+    pub async fn record_transition_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        new_status: &str,
+    ) -> Result<()> {
+        let old_status: Option<String> =
+            sqlx::query_scalar("SELECT status FROM tasks WHERE id = ?")
+                .bind(task_id)
+                .fetch_optional(&mut **tx)
+                .await?;
+        let Some(old_status) = old_status.filter(|old| old != new_status) else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            "INSERT INTO task_transitions (task_id, from_status, to_status, source, actor, changed_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(old_status)
+        .bind(new_status)
+        .bind(self.source)
+        .bind(Json(&self.actor))
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a spec change in `spec_revisions` (call before writing the spec)
+    ///
+    /// Unchanged specs are not recorded. The first recorded change also stores
+    /// the spec it replaces as revision 1, so the original is never lost.
+    pub async fn record_spec_revision_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        new_spec: &str,
+    ) -> Result<()> {
+        let old_spec: Option<String> =
+            sqlx::query_scalar::<_, Option<String>>("SELECT spec FROM tasks WHERE id = ?")
+                .bind(task_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .flatten();
+        if old_spec.as_deref() == Some(new_spec) {
+            return Ok(());
+        }
+
+        let latest: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(revision) FROM spec_revisions WHERE task_id = ?")
+                .bind(task_id)
+                .fetch_one(&mut **tx)
+                .await?;
+        let now = Utc::now();
+        let mut next = latest.unwrap_or(0) + 1;
+
+        if latest.is_none() {
+            if let Some(original) = old_spec.filter(|s| !s.is_empty()) {
+                sqlx::query(
+                    "INSERT INTO spec_revisions (task_id, revision, spec, changed_at) VALUES (?, ?, ?, ?)",
+                )
+                .bind(task_id)
+                .bind(next)
+                .bind(original)
+                .bind(now)
+                .execute(&mut **tx)
+                .await?;
+                next += 1;
+            }
+        }
+
+        sqlx::query(
+            "INSERT INTO spec_revisions (task_id, revision, spec, changed_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(next)
+        .bind(new_spec)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set parent_id of many tasks within a transaction (no notification)
+    ///
+    /// Used to establish parent-child relationships after tasks are created.
+    /// Each pair is (task, new parent); a `None` parent makes the task a root.
+    pub async fn set_parents_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        parents: &[(i64, Option<i64>)],
+    ) -> Result<()> {
+        for chunk in parents.chunks(BATCH_ROWS) {
+            let rows = vec!["(?, ?)"; chunk.len()].join(", ");
+            let sql = format!(
+                r#"
+                UPDATE tasks SET parent_id = new_parent.column2
+                FROM (VALUES {}) AS new_parent
+                WHERE tasks.id = new_parent.column1
+                "#,
+                rows
+            );
+            let mut query = sqlx::query(&sql);
+            for &(task_id, parent_id) in chunk {
+                query = query.bind(task_id).bind(parent_id);
+            }
+            query.execute(&mut **tx).await?;
+        }
 
         Ok(())
     }
@@ -475,20 +851,33 @@ impl<'a> TaskManager<'a> {
     /// - Validates all children are complete
     /// - Updates status to 'done'
     /// - Sets first_done_at timestamp
+    /// - Unblocks the task and the tasks it was the last unfinished dependency of
+    /// - Records the outcome, if one is given
     ///
-    /// Called by both `done_task()` and `PlanExecutor`.
+    /// Returns the tasks that became ready to start (none if the task was
+    /// already done). Called by both `done_task()` and `PlanExecutor`.
     pub async fn complete_task_in_tx(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
         task_id: i64,
-    ) -> Result<()> {
+        outcome: Option<&CompletionOutcome>,
+    ) -> Result<Vec<UnblockedTask>> {
         // Check if all children are done
         let incomplete_count = self.count_incomplete_children_in_tx(tx, task_id).await?;
         if incomplete_count > 0 {
             return Err(IntentError::UncompletedChildren);
         }
+        let was_done: bool = sqlx::query_scalar("SELECT status = 'done' FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .unwrap_or(false);
+        if let Some(outcome) = outcome {
+            self.record_outcome_in_tx(tx, task_id, outcome).await?;
+        }
 
         // Update task status to done
+        self.record_transition_in_tx(tx, task_id, "done").await?;
         let now = chrono::Utc::now();
         sqlx::query(
             r#"
@@ -502,19 +891,146 @@ impl<'a> TaskManager<'a> {
         .execute(&mut **tx)
         .await?;
 
+        sqlx::query("DELETE FROM task_leases WHERE task_id = ?")
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tasks SET blocked_reason = NULL
+            WHERE blocked_reason IS NOT NULL
+              AND (id = ?
+                   OR (id IN (SELECT blocked_task_id FROM dependencies WHERE blocking_task_id = ?)
+                       AND NOT EXISTS (
+                           SELECT 1 FROM dependencies d
+                           JOIN tasks bt ON d.blocking_task_id = bt.id
+                           WHERE d.blocked_task_id = tasks.id
+                             AND bt.status != 'done'
+                       )))
+            "#,
+        )
+        .bind(task_id)
+        .bind(task_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if was_done {
+            return Ok(Vec::new());
+        }
+        crate::dependencies::newly_unblocked(&mut **tx, task_id, self.scope).await
+    }
+
+    /// Move a task to 'review' within a transaction
+    ///
+    /// Same child validation as `complete_task_in_tx`, but the task waits for a
+    /// human to approve it instead of becoming done.
+    pub async fn submit_for_review_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        outcome: Option<&CompletionOutcome>,
+    ) -> Result<()> {
+        let incomplete_count = self.count_incomplete_children_in_tx(tx, task_id).await?;
+        if incomplete_count > 0 {
+            return Err(IntentError::UncompletedChildren);
+        }
+        if let Some(outcome) = outcome {
+            self.record_outcome_in_tx(tx, task_id, outcome).await?;
+        }
+
+        let is_done: bool = sqlx::query_scalar("SELECT status = 'done' FROM tasks WHERE id = ?")
+            .bind(task_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .unwrap_or(false);
+        if is_done {
+            return Ok(());
+        }
+        self.record_transition_in_tx(tx, task_id, "review").await?;
+        sqlx::query("UPDATE tasks SET status = 'review' WHERE id = ?")
+            .bind(task_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record what a task produced as an `outcome` event
+    ///
+    /// The summary must not be blank; artifacts are trimmed and blank ones
+    /// rejected.
+    pub async fn record_outcome_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        task_id: i64,
+        outcome: &CompletionOutcome,
+    ) -> Result<()> {
+        let summary = outcome.summary.trim();
+        if summary.is_empty() {
+            return Err(IntentError::InvalidInput(
+                "Outcome summary cannot be empty".to_string(),
+            ));
+        }
+        let artifacts: Vec<String> = outcome
+            .artifacts
+            .iter()
+            .map(|a| a.trim().to_string())
+            .collect();
+        if artifacts.iter().any(String::is_empty) {
+            return Err(IntentError::InvalidInput(
+                "Outcome artifacts cannot be empty".to_string(),
+            ));
+        }
+        let text = CompletionOutcome {
+            summary: summary.to_string(),
+            artifacts,
+        }
+        .to_event_text();
+
+        sqlx::query(
+            r#"
+            INSERT INTO events (task_id, log_type, discussion_data, timestamp, visibility, actor)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(CompletionOutcome::LOG_TYPE)
+        .bind(text)
+        .bind(Utc::now())
+        .bind(EventVisibility::Normal.as_str())
+        .bind(Json(&self.actor))
+        .execute(&mut **tx)
+        .await?;
+
         Ok(())
     }
 
+    /// Whether completed AI-owned tasks should stop at 'review' (`review.required`)
+    pub async fn review_required(&self) -> Result<bool> {
+        SettingsManager::new(self.pool)
+            .get_bool(crate::settings::REVIEW_REQUIRED)
+            .await
+    }
+
     /// Notify Dashboard about a batch operation
     ///
     /// Call this after committing a transaction that created/updated multiple tasks.
     /// Sends a single "batch_update" notification instead of per-task notifications.
     pub async fn notify_batch_changed(&self) {
-        if let Some(cli_notifier) = &self.cli_notifier {
-            cli_notifier
-                .notify_task_changed(None, "batch_update", self.project_path.clone())
-                .await;
-        }
+        use crate::dashboard::websocket::DatabaseOperationPayload;
+
+        let payload = self.project_path.as_ref().map(|project_path| {
+            DatabaseOperationPayload::new(
+                "batch_update",
+                "task",
+                vec![],
+                None,
+                project_path.clone(),
+            )
+        });
+        self.notify_cli_task_changed(None, "batch_update", payload)
+            .await;
     }
 
     // =========================================================================
@@ -524,32 +1040,102 @@ impl<'a> TaskManager<'a> {
     /// Get a task by ID
     #[tracing::instrument(skip(self))]
     pub async fn get_task(&self, id: i64) -> Result<Task> {
-        let task = sqlx::query_as::<_, Task>(
+        self.get_task_in_scope(id, self.scope).await
+    }
+
+    async fn get_task_in_scope(&self, id: i64, scope: TaskVisibility) -> Result<Task> {
+        let task = sqlx::query_as::<_, Task>(&format!(
             r#"
-            SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+            SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
             FROM tasks
-            WHERE id = ?
+            WHERE id = ? AND {}
             "#,
-        )
+            scope.sql_filter()
+        ))
         .bind(id)
         .fetch_optional(self.pool)
         .await?
         .ok_or(IntentError::TaskNotFound(id))?;
 
+        let mut tasks = [task];
+        crate::checklist::attach_progress(self.pool, &mut tasks).await?;
+        let [task] = tasks;
         Ok(task)
     }
 
-    /// Get a task with events summary
+    /// All recorded versions of a task's spec, oldest first
+    ///
+    /// Empty until the spec is changed for the first time.
+    pub async fn spec_history(&self, id: i64) -> Result<Vec<SpecRevision>> {
+        self.check_task_exists(id).await?;
+
+        let revisions = sqlx::query_as::<_, SpecRevision>(
+            r#"
+            SELECT task_id, revision, spec, changed_at
+            FROM spec_revisions
+            WHERE task_id = ?
+            ORDER BY revision
+            "#,
+        )
+        .bind(id)
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    /// Every recorded status change of a task, oldest first
+    pub async fn transitions(&self, id: i64) -> Result<Vec<TaskTransition>> {
+        self.get_task(id).await?;
+
+        let transitions = sqlx::query_as::<_, TaskTransition>(
+            r#"
+            SELECT id, task_id, from_status, to_status, source, actor, changed_at
+            FROM task_transitions
+            WHERE task_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(transitions)
+    }
+
+    /// Get a task with events summary
+    #[tracing::instrument(skip(self))]
     pub async fn get_task_with_events(&self, id: i64) -> Result<TaskWithEvents> {
         let task = self.get_task(id).await?;
         let events_summary = self.get_events_summary(id).await?;
+        let outcome = self.latest_outcome(id).await?;
 
         Ok(TaskWithEvents {
             task,
             events_summary: Some(events_summary),
+            outcome,
         })
     }
 
+    /// The outcome recorded when the task was last completed
+    async fn latest_outcome(&self, task_id: i64) -> Result<Option<CompletionOutcome>> {
+        let text: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT discussion_data
+            FROM events
+            WHERE task_id = ? AND log_type = ?
+            ORDER BY timestamp DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(task_id)
+        .bind(CompletionOutcome::LOG_TYPE)
+        .fetch_optional(self.pool)
+        .await?;
+
+        Ok(text.as_deref().map(CompletionOutcome::from_event_text))
+    }
+
     /// Get full ancestry chain for a task
     ///
     /// Returns a vector of tasks from the given task up to the root:
@@ -578,6 +1164,7 @@ impl<'a> TaskManager<'a> {
     /// - ancestors: Parent chain up to root (ordered from immediate parent to root)
     /// - siblings: Other tasks at the same level (same parent_id)
     /// - children: Direct subtasks of this task
+    /// - estimate: Estimate rollup over the task and all its descendants
     pub async fn get_task_context(&self, id: i64) -> Result<TaskContext> {
         let task = self.get_task(id).await?;
 
@@ -594,6 +1181,9 @@ impl<'a> TaskManager<'a> {
         let children = self.get_children(id).await?;
         let blocking_tasks = self.get_blocking_tasks(id).await?;
         let blocked_by_tasks = self.get_blocked_by_tasks(id).await?;
+        let descendants = self.get_descendants(id).await?;
+        let estimate = EstimateRollup::from_tasks(std::iter::once(&task).chain(&descendants));
+        let links = crate::links::LinkManager::new(self.pool).list(id).await?;
 
         Ok(TaskContext {
             task,
@@ -604,6 +1194,8 @@ impl<'a> TaskManager<'a> {
                 blocking_tasks,
                 blocked_by_tasks,
             },
+            estimate,
+            links,
         })
     }
 
@@ -611,8 +1203,9 @@ impl<'a> TaskManager<'a> {
     pub async fn get_siblings(&self, id: i64, parent_id: Option<i64>) -> Result<Vec<Task>> {
         if let Some(parent_id) = parent_id {
             sqlx::query_as::<_, Task>(&format!(
-                "SELECT {} FROM tasks WHERE parent_id = ? AND id != ? ORDER BY priority ASC NULLS LAST, id ASC",
-                crate::sql_constants::TASK_COLUMNS
+                "SELECT {} FROM tasks WHERE parent_id = ? AND id != ? AND {} ORDER BY priority ASC NULLS LAST, id ASC",
+                crate::sql_constants::TASK_COLUMNS,
+                self.scope.sql_filter()
             ))
             .bind(parent_id)
             .bind(id)
@@ -621,8 +1214,9 @@ impl<'a> TaskManager<'a> {
             .map_err(Into::into)
         } else {
             sqlx::query_as::<_, Task>(&format!(
-                "SELECT {} FROM tasks WHERE parent_id IS NULL AND id != ? ORDER BY priority ASC NULLS LAST, id ASC",
-                crate::sql_constants::TASK_COLUMNS
+                "SELECT {} FROM tasks WHERE parent_id IS NULL AND id != ? AND {} ORDER BY priority ASC NULLS LAST, id ASC",
+                crate::sql_constants::TASK_COLUMNS,
+                self.scope.sql_filter()
             ))
             .bind(id)
             .fetch_all(self.pool)
@@ -634,8 +1228,9 @@ impl<'a> TaskManager<'a> {
     /// Get direct children of a task.
     pub async fn get_children(&self, id: i64) -> Result<Vec<Task>> {
         sqlx::query_as::<_, Task>(&format!(
-            "SELECT {} FROM tasks WHERE parent_id = ? ORDER BY priority ASC NULLS LAST, id ASC",
-            crate::sql_constants::TASK_COLUMNS
+            "SELECT {} FROM tasks WHERE parent_id = ? AND {} ORDER BY priority ASC NULLS LAST, id ASC",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter()
         ))
         .bind(id)
         .fetch_all(self.pool)
@@ -643,14 +1238,100 @@ impl<'a> TaskManager<'a> {
         .map_err(Into::into)
     }
 
+    /// List one page of a task's children (root tasks when `parent_id` is None)
+    ///
+    /// Children come in the same order as [`Self::get_children`], each with its
+    /// own child count so a tree can expand nodes on demand. `cursor` is the ID
+    /// of the last task of the previous page; pass the returned `next_cursor`.
+    pub async fn find_children(
+        &self,
+        parent_id: Option<i64>,
+        cursor: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<ChildrenPage> {
+        if let Some(pid) = parent_id {
+            self.check_task_exists(pid).await?;
+        }
+        let limit = limit.unwrap_or(100).clamp(1, MAX_CHILDREN_PAGE_SIZE);
+
+        let cursor = match cursor {
+            Some(task_id) => {
+                let key: Option<i64> = sqlx::query_scalar(&format!(
+                    "SELECT {} FROM tasks WHERE id = ? AND parent_id IS ?",
+                    CHILD_SORT_KEY
+                ))
+                .bind(task_id)
+                .bind(parent_id)
+                .fetch_optional(self.pool)
+                .await?;
+                let key = key.ok_or_else(|| {
+                    IntentError::InvalidInput(format!(
+                        "Cursor {} is not a child of this parent",
+                        task_id
+                    ))
+                })?;
+                Some((key, task_id))
+            },
+            None => None,
+        };
+
+        let filter = format!("parent_id IS ? AND {}", self.scope.sql_filter());
+        let total_count =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM tasks WHERE {}", filter))
+                .bind(parent_id)
+                .fetch_one(self.pool)
+                .await?;
+
+        let mut sql = format!(
+            "SELECT {}, \
+             (SELECT COUNT(*) FROM tasks c WHERE c.parent_id = tasks.id AND {}) AS child_count \
+             FROM tasks WHERE {}",
+            crate::sql_constants::TASK_COLUMNS,
+            self.scope.sql_filter(),
+            filter
+        );
+        if cursor.is_some() {
+            sql.push_str(&format!(
+                " AND ({key} > ? OR ({key} = ? AND id > ?))",
+                key = CHILD_SORT_KEY
+            ));
+        }
+        sql.push_str(&format!(" ORDER BY {}, id LIMIT ?", CHILD_SORT_KEY));
+
+        let mut query = sqlx::query_as::<_, TaskTreeNode>(&sql).bind(parent_id);
+        if let Some((key, id)) = cursor {
+            query = query.bind(key).bind(key).bind(id);
+        }
+        // Fetch one extra row to know whether another page exists
+        let mut tasks = query.bind(limit + 1).fetch_all(self.pool).await?;
+
+        let has_more = tasks.len() as i64 > limit;
+        tasks.truncate(limit as usize);
+        let next_cursor = if has_more {
+            tasks.last().map(|node| node.task.id)
+        } else {
+            None
+        };
+
+        Ok(ChildrenPage {
+            parent_id,
+            tasks,
+            total_count,
+            has_more,
+            limit,
+            next_cursor,
+        })
+    }
+
     /// Get tasks that this task depends on (blocking tasks).
     pub async fn get_blocking_tasks(&self, id: i64) -> Result<Vec<Task>> {
         sqlx::query_as::<_, Task>(&format!(
             "SELECT {} FROM tasks t \
              JOIN dependencies d ON t.id = d.blocking_task_id \
-             WHERE d.blocked_task_id = ? \
+             WHERE d.blocked_task_id = ? AND {} \
              ORDER BY t.priority ASC NULLS LAST, t.id ASC",
-            crate::sql_constants::TASK_COLUMNS_PREFIXED
+            crate::sql_constants::TASK_COLUMNS_PREFIXED,
+            self.scope.sql_filter()
         ))
         .bind(id)
         .fetch_all(self.pool)
@@ -663,9 +1344,10 @@ impl<'a> TaskManager<'a> {
         sqlx::query_as::<_, Task>(&format!(
             "SELECT {} FROM tasks t \
              JOIN dependencies d ON t.id = d.blocked_task_id \
-             WHERE d.blocking_task_id = ? \
+             WHERE d.blocking_task_id = ? AND {} \
              ORDER BY t.priority ASC NULLS LAST, t.id ASC",
-            crate::sql_constants::TASK_COLUMNS_PREFIXED
+            crate::sql_constants::TASK_COLUMNS_PREFIXED,
+            self.scope.sql_filter()
         ))
         .bind(id)
         .fetch_all(self.pool)
@@ -675,33 +1357,90 @@ impl<'a> TaskManager<'a> {
 
     /// Get all descendants of a task recursively (children, grandchildren, etc.)
     /// Uses recursive CTE for efficient querying
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn get_descendants(&self, task_id: i64) -> Result<Vec<Task>> {
-        let descendants = sqlx::query_as::<_, Task>(
+        let descendants = sqlx::query_as::<_, Task>(&format!(
             r#"
             WITH RECURSIVE descendants AS (
                 SELECT id, parent_id, name, spec, status, complexity, priority,
-                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                 FROM tasks
                 WHERE parent_id = ?
 
                 UNION ALL
 
                 SELECT t.id, t.parent_id, t.name, t.spec, t.status, t.complexity, t.priority,
-                       t.first_todo_at, t.first_doing_at, t.first_done_at, t.active_form, t.owner, t.metadata
+                       t.first_todo_at, t.first_doing_at, t.first_done_at, t.active_form, t.owner, t.metadata, t.sort_order, t.estimate_minutes, t.visibility, t.blocked_reason
                 FROM tasks t
                 INNER JOIN descendants d ON t.parent_id = d.id
             )
             SELECT * FROM descendants
+            WHERE {}
             ORDER BY parent_id NULLS FIRST, priority ASC NULLS LAST, id ASC
             "#,
-        )
+            self.scope.sql_filter()
+        ))
         .bind(task_id)
         .fetch_all(self.pool)
         .await?;
+        tracing::Span::current().record("rows", descendants.len());
 
         Ok(descendants)
     }
 
+    /// Counts, completion, estimates and complexity over a task and all its
+    /// descendants, plus the longest chain of incomplete dependencies holding
+    /// up any unfinished task in the subtree
+    pub async fn get_subtree_stats(&self, root_id: i64) -> Result<SubtreeStats> {
+        let root = self.get_task(root_id).await?;
+        let mut tasks = vec![root];
+        tasks.extend(self.get_descendants(root_id).await?);
+        let mut stats = SubtreeStats::from_tasks(root_id, &tasks);
+
+        // Walk incomplete blockers from every unfinished subtree task; the path
+        // string keeps cycles from recursing forever
+        let path = sqlx::query_scalar::<_, String>(&format!(
+            r#"
+            WITH RECURSIVE subtree(id) AS (
+                SELECT ?
+                UNION ALL
+                SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            ),
+            open_tasks(id) AS (
+                SELECT id FROM tasks WHERE status != 'done' AND {}
+            ),
+            chain(task_id, depth, path) AS (
+                SELECT d.blocking_task_id, 1,
+                       ',' || d.blocked_task_id || ',' || d.blocking_task_id || ','
+                FROM dependencies d
+                WHERE d.blocked_task_id IN (SELECT id FROM subtree)
+                  AND d.blocked_task_id IN (SELECT id FROM open_tasks)
+                  AND d.blocking_task_id IN (SELECT id FROM open_tasks)
+                UNION ALL
+                SELECT d.blocking_task_id, c.depth + 1, c.path || d.blocking_task_id || ','
+                FROM dependencies d
+                JOIN chain c ON d.blocked_task_id = c.task_id
+                WHERE d.blocking_task_id IN (SELECT id FROM open_tasks)
+                  AND instr(c.path, ',' || d.blocking_task_id || ',') = 0
+                  AND c.depth < 100
+            )
+            SELECT path FROM chain ORDER BY depth DESC, path LIMIT 1
+            "#,
+            self.scope.sql_filter()
+        ))
+        .bind(root_id)
+        .fetch_optional(self.pool)
+        .await?;
+
+        if let Some(path) = path {
+            for id in path.split(',').filter_map(|id| id.parse::<i64>().ok()) {
+                stats.blocking_chain.push(self.get_task(id).await?);
+            }
+        }
+
+        Ok(stats)
+    }
+
     /// Get status response for a task (the "spotlight" view)
     /// This is the main method for `ie status` command
     pub async fn get_status(
@@ -723,7 +1462,8 @@ impl<'a> TaskManager<'a> {
 
         // Get events if requested
         let events = if with_events {
-            let event_mgr = crate::events::EventManager::new(self.pool);
+            let event_mgr =
+                crate::events::EventManager::new(self.pool).with_visibility(self.event_visibility);
             Some(
                 event_mgr
                     .list_events(Some(task_id), Some(50), None, None)
@@ -744,22 +1484,24 @@ impl<'a> TaskManager<'a> {
 
     /// Get root tasks (tasks with no parent) for NoFocusResponse
     pub async fn get_root_tasks(&self) -> Result<Vec<Task>> {
-        let tasks = sqlx::query_as::<_, Task>(
+        let tasks = sqlx::query_as::<_, Task>(&format!(
             r#"
             SELECT id, parent_id, name, spec, status, complexity, priority,
-                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
             FROM tasks
-            WHERE parent_id IS NULL
+            WHERE parent_id IS NULL AND {}
             ORDER BY
                 CASE status
                     WHEN 'doing' THEN 0
                     WHEN 'todo' THEN 1
-                    WHEN 'done' THEN 2
+                    WHEN 'review' THEN 2
+                    WHEN 'done' THEN 3
                 END,
                 priority ASC NULLS LAST,
                 id ASC
             "#,
-        )
+            self.scope.sql_filter()
+        ))
         .fetch_all(self.pool)
         .await?;
 
@@ -768,20 +1510,21 @@ impl<'a> TaskManager<'a> {
 
     /// Get events summary for a task
     async fn get_events_summary(&self, task_id: i64) -> Result<EventsSummary> {
-        let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events WHERE task_id = ?")
-            .bind(task_id)
-            .fetch_one(self.pool)
-            .await?;
+        let visibility = self.event_visibility.sql_filter();
 
-        let recent_events = sqlx::query_as::<_, Event>(
-            r#"
-            SELECT id, task_id, timestamp, log_type, discussion_data
-            FROM events
-            WHERE task_id = ?
-            ORDER BY timestamp DESC
-            LIMIT 10
-            "#,
-        )
+        let total_count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM events WHERE task_id = ? AND {}",
+            visibility
+        ))
+        .bind(task_id)
+        .fetch_one(self.pool)
+        .await?;
+
+        let recent_events = sqlx::query_as::<_, Event>(&format!(
+            "{} AND task_id = ? AND {} ORDER BY timestamp DESC LIMIT 10",
+            crate::sql_constants::SELECT_EVENT_BASE,
+            visibility
+        ))
         .bind(task_id)
         .fetch_all(self.pool)
         .await?;
@@ -793,6 +1536,7 @@ impl<'a> TaskManager<'a> {
     }
 
     /// Update a task
+    #[tracing::instrument(skip(self, update))]
     pub async fn update_task(&self, id: i64, update: TaskUpdate<'_>) -> Result<Task> {
         let TaskUpdate {
             name,
@@ -804,18 +1548,78 @@ impl<'a> TaskManager<'a> {
             active_form,
             owner,
             metadata,
+            sort_order,
+            estimate_minutes,
+            needs_human,
+            blocked_reason,
+            visibility,
         } = update;
 
         // Check task exists
         let task = self.get_task(id).await?;
 
+        // A shared task cannot sit under a private one
+        let new_parent = parent_id.unwrap_or(task.parent_id);
+        let parent_is_private = match new_parent {
+            Some(pid) => sqlx::query_scalar::<_, bool>(
+                "SELECT visibility = 'private' FROM tasks WHERE id = ?",
+            )
+            .bind(pid)
+            .fetch_optional(self.pool)
+            .await?
+            .unwrap_or(false),
+            None => false,
+        };
+        if visibility == Some(TaskVisibility::Shared) && parent_is_private {
+            return Err(IntentError::InvalidInput(format!(
+                "Cannot share task #{} while its parent #{} is private",
+                id,
+                new_parent.unwrap_or_default()
+            )));
+        }
+        // Moving under a private parent makes the subtree private too
+        let visibility = visibility
+            .or((parent_id.is_some() && parent_is_private).then_some(TaskVisibility::Private));
+
+        if estimate_minutes.is_some_and(|m| m < 0) {
+            return Err(IntentError::InvalidInput(
+                "estimate_minutes cannot be negative".to_string(),
+            ));
+        }
+
         // Validate status if provided
         if let Some(s) = status {
-            if !["todo", "doing", "done"].contains(&s) {
+            if !["todo", "doing", "review", "done"].contains(&s) {
                 return Err(IntentError::InvalidInput(format!("Invalid status: {}", s)));
             }
         }
 
+        // A status change may not get around the review gate or approval
+        if let Some(s) = status.filter(|s| *s != task.status) {
+            if task.status == "review" && self.actor.is_ai() {
+                return Err(IntentError::ActionNotAllowed(format!(
+                    "Task #{} is awaiting review; only a human can move it on",
+                    id
+                )));
+            }
+            if s == "done" && task.owner == "human" && self.actor.is_ai() {
+                return Err(IntentError::HumanTaskCannotBeCompletedByAI {
+                    task_id: id,
+                    task_name: task.name.clone(),
+                });
+            }
+            if s == "done"
+                && task.status != "review"
+                && task.owner == "ai"
+                && self.review_required().await?
+            {
+                return Err(IntentError::ActionNotAllowed(format!(
+                    "Task #{} must be reviewed before it is done; submit it with: ie task done {}",
+                    id, id
+                )));
+            }
+        }
+
         // Check for circular dependency if parent_id is being changed
         if let Some(Some(pid)) = parent_id {
             if pid == id {
@@ -909,6 +1713,44 @@ impl<'a> TaskManager<'a> {
             has_updates = true;
         }
 
+        if let Some(so) = sort_order {
+            if has_updates {
+                builder.push(", ");
+            }
+            builder.push("sort_order = ").push_bind(so);
+            has_updates = true;
+        }
+
+        if let Some(em) = estimate_minutes {
+            if has_updates {
+                builder.push(", ");
+            }
+            builder.push("estimate_minutes = ").push_bind(em);
+            has_updates = true;
+        }
+
+        if let Some(reason) = needs_human {
+            if has_updates {
+                builder.push(", ");
+            }
+            builder
+                .push("needs_human = NULLIF(TRIM(")
+                .push_bind(reason)
+                .push("), '')");
+            has_updates = true;
+        }
+
+        if let Some(reason) = blocked_reason {
+            if has_updates {
+                builder.push(", ");
+            }
+            builder
+                .push("blocked_reason = NULLIF(TRIM(")
+                .push_bind(reason)
+                .push("), '')");
+            has_updates = true;
+        }
+
         if let Some(s) = status {
             if has_updates {
                 builder.push(", ");
@@ -933,23 +1775,106 @@ impl<'a> TaskManager<'a> {
             }
         }
 
-        if !has_updates {
+        if !has_updates && visibility.is_none() {
             return Ok(task);
         }
 
         builder.push(" WHERE id = ").push_bind(id);
 
-        builder.build().execute(self.pool).await?;
+        // Reject bad frontmatter before the transaction takes the write lock
+        if let Some(s) = spec {
+            crate::spec_meta::SpecMeta::parse(s)?;
+        }
 
-        let task = self.get_task(id).await?;
+        let mut tx = self.pool.begin().await?;
+        if let Some(s) = spec {
+            self.record_spec_revision_in_tx(&mut tx, id, s).await?;
+        }
+        if let Some(s) = status {
+            self.record_transition_in_tx(&mut tx, id, s).await?;
+        }
+        if has_updates {
+            builder.build().execute(&mut *tx).await?;
+        }
+        if let Some(v) = visibility {
+            sqlx::query(
+                r#"
+                WITH RECURSIVE subtree(id) AS (
+                    SELECT ?
+                    UNION ALL
+                    SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+                )
+                UPDATE tasks SET visibility = ? WHERE id IN (SELECT id FROM subtree)
+                "#,
+            )
+            .bind(id)
+            .bind(v)
+            .execute(&mut *tx)
+            .await?;
+        }
+        if let Some(s) = spec {
+            crate::spec_meta::sync(&mut tx, id, Some(s)).await?;
+        }
+        if status.is_some_and(|s| s != task.status) {
+            PolicyEngine::for_pool(self.pool)?
+                .check_in_tx(&mut tx, id)
+                .await?;
+        }
+        let unblocked = if status == Some("done") && task.status != "done" {
+            crate::dependencies::newly_unblocked(&mut *tx, id, self.scope).await?
+        } else {
+            Vec::new()
+        };
+        tx.commit().await?;
+
+        // The caller may just have made the task private; it was in scope a moment ago
+        let task = self.get_task_in_scope(id, TaskVisibility::Private).await?;
 
         // Notify WebSocket clients about the task update
         self.notify_task_updated(&task).await;
+        self.notify_unblocked(&unblocked).await;
 
         Ok(task)
     }
 
+    /// Persist a manual ordering for a set of tasks (e.g. one kanban column)
+    ///
+    /// Each task's `sort_order` is set to its index in `task_ids`. All IDs are
+    /// validated before anything is written, and the update is atomic.
+    pub async fn reorder_tasks(&self, task_ids: &[i64]) -> Result<Vec<Task>> {
+        let mut seen = std::collections::HashSet::new();
+        for id in task_ids {
+            if !seen.insert(*id) {
+                return Err(IntentError::InvalidInput(format!(
+                    "Task #{} appears more than once in the ordering",
+                    id
+                )));
+            }
+            self.check_task_exists(*id).await?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+        for (position, id) in task_ids.iter().enumerate() {
+            sqlx::query("UPDATE tasks SET sort_order = ? WHERE id = ?")
+                .bind(position as i64)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        let mut tasks = Vec::with_capacity(task_ids.len());
+        for id in task_ids {
+            let task = self.get_task(*id).await?;
+            self.notify_task_updated(&task).await;
+            tasks.push(task);
+        }
+
+        Ok(tasks)
+    }
+
     /// Delete a task. Refuses if the task is focused by any session.
+    #[tracing::instrument(skip(self))]
     pub async fn delete_task(&self, id: i64) -> Result<()> {
         self.check_task_exists(id).await?;
 
@@ -976,6 +1901,7 @@ impl<'a> TaskManager<'a> {
     ///
     /// Refuses if any task in the subtree is focused by any session.
     /// Returns the number of descendants deleted.
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn delete_task_cascade(&self, id: i64) -> Result<usize> {
         let descendants = self.get_descendants(id).await?;
 
@@ -992,6 +1918,7 @@ impl<'a> TaskManager<'a> {
 
         let count = descendants.len();
         self.delete_task(id).await?;
+        tracing::Span::current().record("rows", count + 1);
         Ok(count)
     }
 
@@ -1035,6 +1962,7 @@ impl<'a> TaskManager<'a> {
     }
 
     /// Find tasks with optional filters, sorting, and pagination
+    #[tracing::instrument(skip(self), fields(rows))]
     pub async fn find_tasks(
         &self,
         status: Option<&str>,
@@ -1044,7 +1972,15 @@ impl<'a> TaskManager<'a> {
         offset: Option<i64>,
     ) -> Result<PaginatedTasks> {
         // Apply defaults
-        let sort_by = sort_by.unwrap_or_default(); // Default: FocusAware
+        let sort_by = match sort_by {
+            Some(sort_by) => sort_by,
+            // Project default, FocusAware unless config.toml says otherwise
+            None => {
+                crate::config::ProjectConfig::for_pool(self.pool)?
+                    .tasks
+                    .default_sort
+            },
+        };
         let limit = limit.unwrap_or(100);
         let offset = offset.unwrap_or(0);
 
@@ -1052,12 +1988,19 @@ impl<'a> TaskManager<'a> {
         let session_id = crate::workspace::resolve_session_id(None);
 
         // Build WHERE clause
-        let mut where_clause = String::from("WHERE 1=1");
+        let mut where_clause = format!("WHERE {}", self.scope.sql_filter());
         let mut conditions = Vec::new();
 
-        if let Some(s) = status {
-            where_clause.push_str(" AND status = ?");
-            conditions.push(s.to_string());
+        match status {
+            // Not a stored status: unfinished tasks with a blocked_reason
+            Some("blocked") => {
+                where_clause.push_str(" AND blocked_reason IS NOT NULL AND status != 'done'")
+            },
+            Some(s) => {
+                where_clause.push_str(" AND status = ?");
+                conditions.push(s.to_string());
+            },
+            None => {},
         }
 
         if let Some(pid) = parent_id {
@@ -1079,15 +2022,18 @@ impl<'a> TaskManager<'a> {
                 "ORDER BY id ASC".to_string()
             },
             TaskSortBy::Priority => {
-                // ORDER BY priority ASC, complexity ASC, id ASC
-                "ORDER BY COALESCE(priority, 999) ASC, COALESCE(complexity, 5) ASC, id ASC"
-                    .to_string()
+                // Manual (kanban) order first, then effective (aged) priority ASC, complexity ASC, id ASC
+                format!(
+                    "ORDER BY sort_order ASC NULLS LAST, {} ASC, COALESCE(complexity, 5) ASC, id ASC",
+                    crate::priority::EFFECTIVE_PRIORITY_SQL
+                )
             },
             TaskSortBy::Time => {
                 // ORDER BY timestamp based on status
                 r#"ORDER BY
                     CASE status
                         WHEN 'doing' THEN first_doing_at
+                        WHEN 'review' THEN first_doing_at
                         WHEN 'todo' THEN first_todo_at
                         WHEN 'done' THEN first_done_at
                     END ASC NULLS LAST,
@@ -1096,16 +2042,18 @@ impl<'a> TaskManager<'a> {
             },
             TaskSortBy::FocusAware => {
                 // Focus-aware: current focused task → doing tasks → todo tasks
-                r#"ORDER BY
+                format!(
+                    r#"ORDER BY
                     CASE
                         WHEN t.id = (SELECT current_task_id FROM sessions WHERE session_id = ?) THEN 0
                         WHEN t.status = 'doing' THEN 1
                         WHEN t.status = 'todo' THEN 2
                         ELSE 3
                     END ASC,
-                    COALESCE(t.priority, 999) ASC,
-                    t.id ASC"#
-                    .to_string()
+                    {} ASC,
+                    t.id ASC"#,
+                    crate::priority::EFFECTIVE_PRIORITY_SQL
+                )
             },
         };
 
@@ -1119,7 +2067,7 @@ impl<'a> TaskManager<'a> {
 
         // Build main query with pagination
         let main_query = format!(
-            "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata FROM tasks t {} {} LIMIT ? OFFSET ?",
+            "SELECT id, parent_id, name, NULL as spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason FROM tasks t {} {} LIMIT ? OFFSET ?",
             where_clause, order_clause
         );
 
@@ -1134,7 +2082,9 @@ impl<'a> TaskManager<'a> {
         q = q.bind(limit);
         q = q.bind(offset);
 
-        let tasks = q.fetch_all(self.pool).await?;
+        let mut tasks = q.fetch_all(self.pool).await?;
+        tracing::Span::current().record("rows", tasks.len());
+        crate::checklist::attach_progress(self.pool, &mut tasks).await?;
 
         // Calculate has_more
         let has_more = offset + (tasks.len() as i64) < total_count;
@@ -1153,14 +2103,15 @@ impl<'a> TaskManager<'a> {
     /// This is much more efficient than loading all tasks just to count them.
     /// Used by session restore when there's no focused task.
     pub async fn get_stats(&self) -> Result<WorkspaceStats> {
-        let row = sqlx::query_as::<_, (i64, i64, i64, i64)>(
+        let row = sqlx::query_as::<_, (i64, i64, i64, i64)>(&format!(
             r#"SELECT
                 COUNT(*) as total,
                 COALESCE(SUM(CASE WHEN status = 'todo' THEN 1 ELSE 0 END), 0),
                 COALESCE(SUM(CASE WHEN status = 'doing' THEN 1 ELSE 0 END), 0),
                 COALESCE(SUM(CASE WHEN status = 'done' THEN 1 ELSE 0 END), 0)
-            FROM tasks"#,
-        )
+            FROM tasks WHERE {}"#,
+            self.scope.sql_filter()
+        ))
         .fetch_one(self.pool)
         .await?;
 
@@ -1173,33 +2124,91 @@ impl<'a> TaskManager<'a> {
     }
 
     /// Start a task (atomic: update status + set current)
+    ///
+    /// Fails with `IntentError::WipLimitExceeded` when the task would go past
+    /// a WIP limit of the project (see `crate::wip`).
     #[tracing::instrument(skip(self))]
     pub async fn start_task(&self, id: i64, with_events: bool) -> Result<TaskWithEvents> {
-        // Check if task exists first
-        let task_exists: bool =
-            sqlx::query_scalar::<_, bool>(crate::sql_constants::CHECK_TASK_EXISTS)
-                .bind(id)
-                .fetch_one(self.pool)
-                .await?;
+        self.start_task_checked(id, with_events, true).await
+    }
 
-        if !task_exists {
-            return Err(IntentError::TaskNotFound(id));
-        }
+    /// [`start_task`](Self::start_task) that ignores the project's WIP limits
+    pub async fn start_task_over_wip_limit(
+        &self,
+        id: i64,
+        with_events: bool,
+    ) -> Result<TaskWithEvents> {
+        self.start_task_checked(id, with_events, false).await
+    }
+
+    async fn start_task_checked(
+        &self,
+        id: i64,
+        with_events: bool,
+        enforce_wip: bool,
+    ) -> Result<TaskWithEvents> {
+        // Check if task exists first
+        self.check_task_exists(id).await?;
 
         // Check if task is blocked by incomplete dependencies
         use crate::dependencies::get_incomplete_blocking_tasks;
         if let Some(blocking_tasks) = get_incomplete_blocking_tasks(self.pool, id).await? {
+            // Record why, unless the task already says why it is blocked
+            let ids: Vec<String> = blocking_tasks.iter().map(|b| format!("#{}", b)).collect();
+            sqlx::query(
+                "UPDATE tasks SET blocked_reason = COALESCE(blocked_reason, ?) WHERE id = ?",
+            )
+            .bind(format!("Waiting on {}", ids.join(", ")))
+            .bind(id)
+            .execute(self.pool)
+            .await?;
             return Err(IntentError::TaskBlocked {
                 task_id: id,
                 blocking_task_ids: blocking_tasks,
             });
         }
 
+        // ...or by tasks of other projects
+        let elsewhere = crate::global_projects::CrossProjectLinks::new(self.pool)
+            .incomplete_blockers(id)
+            .await?;
+        if !elsewhere.is_empty() {
+            let blockers: Vec<String> = elsewhere.iter().map(|l| l.blocking_ref()).collect();
+            sqlx::query(
+                "UPDATE tasks SET blocked_reason = COALESCE(blocked_reason, ?) WHERE id = ?",
+            )
+            .bind(format!("Waiting on {}", blockers.join(", ")))
+            .bind(id)
+            .execute(self.pool)
+            .await?;
+            return Err(IntentError::CrossProjectBlocked {
+                task_id: id,
+                blockers,
+            });
+        }
+
+        // Another agent session holds the task
+        let session_id = crate::workspace::resolve_session_id(None);
+        if let Some(lease) = self.get_lease(id).await? {
+            if lease.assignee != session_id {
+                return Err(IntentError::TaskLeased {
+                    task_id: id,
+                    assignee: lease.assignee,
+                    expires_at: lease.lease_expires_at.to_rfc3339(),
+                });
+            }
+        }
+
         let mut tx = self.pool.begin().await?;
 
         let now = Utc::now();
+        let was_doing: bool = sqlx::query_scalar("SELECT status = 'doing' FROM tasks WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
 
         // Update task status to doing
+        self.record_transition_in_tx(&mut tx, id, "doing").await?;
         sqlx::query(
             r#"
             UPDATE tasks
@@ -1212,9 +2221,19 @@ impl<'a> TaskManager<'a> {
         .execute(&mut *tx)
         .await?;
 
+        // Refocusing a task already in doing is not a transition
+        if !was_doing {
+            PolicyEngine::for_pool(self.pool)?
+                .check_in_tx(&mut tx, id)
+                .await?;
+            if enforce_wip {
+                WipLimits::for_pool(self.pool)?
+                    .check_in_tx(&mut tx, id)
+                    .await?;
+            }
+        }
+
         // Set as current task in sessions table
-        // Use session_id from environment if available
-        let session_id = crate::workspace::resolve_session_id(None);
         sqlx::query(
             r#"
             INSERT INTO sessions (session_id, current_task_id, created_at, last_active_at)
@@ -1241,6 +2260,7 @@ impl<'a> TaskManager<'a> {
             Ok(TaskWithEvents {
                 task,
                 events_summary: None,
+                outcome: None,
             })
         }
     }
@@ -1271,18 +2291,22 @@ impl<'a> TaskManager<'a> {
 
             if remaining_siblings == 0 {
                 Ok(NextStepSuggestion::ParentIsReady {
-                    message: format!(
-                        "All sub-tasks of parent #{} '{}' are now complete. The parent task is ready for your attention.",
-                        parent_task_id, parent_name
+                    message: crate::i18n::t(
+                        "next.parent_ready",
+                        &[("parent_id", &parent_task_id), ("parent", &parent_name)],
                     ),
                     parent_task_id,
                     parent_task_name: parent_name,
                 })
             } else {
                 Ok(NextStepSuggestion::SiblingTasksRemain {
-                    message: format!(
-                        "Task #{} completed. Parent task #{} '{}' has other sub-tasks remaining.",
-                        id, parent_task_id, parent_name
+                    message: crate::i18n::t(
+                        "next.siblings_remain",
+                        &[
+                            ("id", &id),
+                            ("parent_id", &parent_task_id),
+                            ("parent", &parent_name),
+                        ],
                     ),
                     parent_task_id,
                     parent_task_name: parent_name,
@@ -1298,9 +2322,9 @@ impl<'a> TaskManager<'a> {
 
             if child_count > 0 {
                 Ok(NextStepSuggestion::TopLevelTaskCompleted {
-                    message: format!(
-                        "Top-level task #{} '{}' has been completed. Well done!",
-                        id, task_name
+                    message: crate::i18n::t(
+                        "next.top_level_done",
+                        &[("id", &id), ("name", &task_name)],
                     ),
                     completed_task_id: id,
                     completed_task_name: task_name.to_string(),
@@ -1315,15 +2339,15 @@ impl<'a> TaskManager<'a> {
 
                 if remaining_tasks == 0 {
                     Ok(NextStepSuggestion::WorkspaceIsClear {
-                        message: format!(
-                            "Project complete! Task #{} was the last remaining task. There are no more 'todo' or 'doing' tasks.",
-                            id
-                        ),
+                        message: crate::i18n::t("next.workspace_clear", &[("id", &id)]),
                         completed_task_id: id,
                     })
                 } else {
                     Ok(NextStepSuggestion::NoParentContext {
-                        message: format!("Task #{} '{}' has been completed.", id, task_name),
+                        message: crate::i18n::t(
+                            "next.task_done",
+                            &[("id", &id), ("name", &task_name)],
+                        ),
                         completed_task_id: id,
                         completed_task_name: task_name.to_string(),
                     })
@@ -1336,13 +2360,27 @@ impl<'a> TaskManager<'a> {
     /// This command only operates on the current_task_id.
     /// Prerequisites: A task must be set as current
     ///
-    /// # Arguments
-    /// * `is_ai_caller` - Whether this is called from AI (MCP) or human (CLI/Dashboard).
-    ///   When true and task is human-owned, the operation will fail.
-    ///   Human tasks can only be completed via CLI or Dashboard.
+    /// With `review.required` on, an AI-owned task moves to 'review' instead of
+    /// 'done' and waits for `approve_task`.
+    ///
+    /// An AI actor cannot complete a human-owned task; humans complete their
+    /// own tasks.
+    #[tracing::instrument(skip(self))]
+    pub async fn done_task(&self) -> Result<DoneTaskResponse> {
+        self.done_task_with_outcome(None).await
+    }
+
+    /// Complete the current focused task, recording what it produced
+    ///
+    /// Same as `done_task`; the outcome is stored as an `outcome` event, also
+    /// when the task only moves to 'review'.
     #[tracing::instrument(skip(self))]
-    pub async fn done_task(&self, is_ai_caller: bool) -> Result<DoneTaskResponse> {
+    pub async fn done_task_with_outcome(
+        &self,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
         let session_id = crate::workspace::resolve_session_id(None);
+        let review_required = self.review_required().await?;
         let mut tx = self.pool.begin().await?;
 
         // Get the current task ID from sessions table
@@ -1368,22 +2406,42 @@ impl<'a> TaskManager<'a> {
 
         // Human Task Protection: AI cannot complete human-owned tasks
         // Human must complete their own tasks via CLI or Dashboard
-        if owner == "human" && is_ai_caller {
+        if owner == "human" && self.actor.is_ai() {
+            DesktopNotifier::new(self.pool)
+                .review_requested(id, &task_name)
+                .await;
             return Err(IntentError::HumanTaskCannotBeCompletedByAI {
                 task_id: id,
                 task_name: task_name.clone(),
             });
         }
 
-        // Complete the task (validates children + updates status)
-        self.complete_task_in_tx(&mut tx, id).await?;
-
+        // Complete the task, or hand it to a human for review (both validate children)
+        let in_review = review_required && owner == "ai";
+        let newly_unblocked = if in_review {
+            self.submit_for_review_in_tx(&mut tx, id, outcome.as_ref())
+                .await?;
+            Vec::new()
+        } else {
+            let unblocked = self
+                .complete_task_in_tx(&mut tx, id, outcome.as_ref())
+                .await?;
+            PolicyEngine::for_pool(self.pool)?
+                .check_in_tx(&mut tx, id)
+                .await?;
+            unblocked
+        };
+
         // Clear the current task in sessions table for this session
         sqlx::query("UPDATE sessions SET current_task_id = NULL, last_active_at = datetime('now') WHERE session_id = ?")
             .bind(&session_id)
             .execute(&mut *tx)
             .await?;
 
+        let warnings = crate::checklist::unchecked_warning(&mut tx, id)
+            .await?
+            .into_iter()
+            .collect();
         let next_step_suggestion =
             Self::build_next_step_suggestion(&mut tx, id, &task_name, parent_id).await?;
 
@@ -1392,6 +2450,13 @@ impl<'a> TaskManager<'a> {
         // Fetch the completed task to notify UI
         let completed_task = self.get_task(id).await?;
         self.notify_task_updated(&completed_task).await;
+        if in_review {
+            DesktopNotifier::new(self.pool)
+                .review_requested(id, &task_name)
+                .await;
+        } else {
+            self.notify_unblocked(&newly_unblocked).await;
+        }
 
         Ok(DoneTaskResponse {
             completed_task,
@@ -1399,6 +2464,8 @@ impl<'a> TaskManager<'a> {
                 current_task_id: None,
             },
             next_step_suggestion,
+            warnings,
+            newly_unblocked,
         })
     }
 
@@ -1408,11 +2475,51 @@ impl<'a> TaskManager<'a> {
     /// completes a task by ID. If the task happens to be the current session's focus,
     /// the focus is cleared. Otherwise, the current focus is left unchanged.
     ///
+    /// Like `done_task`, an AI-owned task stops at 'review' when `review.required` is on.
+    ///
     /// # Arguments
-    /// * `id` - The task ID to complete
-    /// * `is_ai_caller` - Whether this is called from AI. When true and task is human-owned, fails.
+    /// * `id` - The task ID to complete; fails for a human-owned task when the actor is an AI
+    #[tracing::instrument(skip(self))]
+    pub async fn done_task_by_id(&self, id: i64) -> Result<DoneTaskResponse> {
+        self.done_task_by_id_with_outcome(id, None).await
+    }
+
+    /// Complete a task by its ID, recording what it produced
+    #[tracing::instrument(skip(self))]
+    pub async fn done_task_by_id_with_outcome(
+        &self,
+        id: i64,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
+        let review_required = self.review_required().await?;
+        self.finish_task_by_id(id, review_required, outcome).await
+    }
+
+    /// Approve a task waiting in 'review', completing it; only humans approve
     #[tracing::instrument(skip(self))]
-    pub async fn done_task_by_id(&self, id: i64, is_ai_caller: bool) -> Result<DoneTaskResponse> {
+    pub async fn approve_task(&self, id: i64) -> Result<DoneTaskResponse> {
+        let task = self.get_task(id).await?;
+        if task.status != "review" {
+            return Err(IntentError::ActionNotAllowed(format!(
+                "Task #{} is not awaiting review (status: {})",
+                id, task.status
+            )));
+        }
+        if self.actor.is_ai() {
+            return Err(IntentError::ActionNotAllowed(format!(
+                "Task #{} must be approved by a human, not an AI agent",
+                id
+            )));
+        }
+        self.finish_task_by_id(id, false, None).await
+    }
+
+    async fn finish_task_by_id(
+        &self,
+        id: i64,
+        review_required: bool,
+        outcome: Option<CompletionOutcome>,
+    ) -> Result<DoneTaskResponse> {
         let session_id = crate::workspace::resolve_session_id(None);
         let mut tx = self.pool.begin().await?;
 
@@ -1426,15 +2533,36 @@ impl<'a> TaskManager<'a> {
         let (task_name, parent_id, owner) = task_info;
 
         // Human Task Protection: AI cannot complete human-owned tasks
-        if owner == "human" && is_ai_caller {
+        if owner == "human" && self.actor.is_ai() {
+            DesktopNotifier::new(self.pool)
+                .review_requested(id, &task_name)
+                .await;
             return Err(IntentError::HumanTaskCannotBeCompletedByAI {
                 task_id: id,
                 task_name: task_name.clone(),
             });
         }
 
-        // Complete the task (validates children + updates status)
-        self.complete_task_in_tx(&mut tx, id).await?;
+        // Complete the task, or hand it to a human for review (both validate children)
+        let in_review = review_required && owner == "ai";
+        let newly_unblocked = if in_review {
+            self.submit_for_review_in_tx(&mut tx, id, outcome.as_ref())
+                .await?;
+            Vec::new()
+        } else {
+            let unblocked = self
+                .complete_task_in_tx(&mut tx, id, outcome.as_ref())
+                .await?;
+            PolicyEngine::for_pool(self.pool)?
+                .check_in_tx(&mut tx, id)
+                .await?;
+            unblocked
+        };
+
+        let warnings = crate::checklist::unchecked_warning(&mut tx, id)
+            .await?
+            .into_iter()
+            .collect();
 
         // If this task is the current session's focus, clear it (otherwise leave focus untouched)
         sqlx::query(
@@ -1476,6 +2604,13 @@ impl<'a> TaskManager<'a> {
         crate::llm::analyze_task_structure_background(self.pool.clone());
 
         self.notify_task_updated(&completed_task).await;
+        if in_review {
+            DesktopNotifier::new(self.pool)
+                .review_requested(id, &task_name)
+                .await;
+        } else {
+            self.notify_unblocked(&newly_unblocked).await;
+        }
 
         Ok(DoneTaskResponse {
             completed_task,
@@ -1483,15 +2618,21 @@ impl<'a> TaskManager<'a> {
                 current_task_id: actual_current_task_id,
             },
             next_step_suggestion,
+            warnings,
+            newly_unblocked,
         })
     }
 
     /// Check if a task exists
     async fn check_task_exists(&self, id: i64) -> Result<()> {
-        let exists: bool = sqlx::query_scalar::<_, bool>(crate::sql_constants::CHECK_TASK_EXISTS)
-            .bind(id)
-            .fetch_one(self.pool)
-            .await?;
+        // Tasks outside the scope are reported as missing rather than forbidden
+        let exists: bool = sqlx::query_scalar::<_, bool>(&format!(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND {})",
+            self.scope.sql_filter()
+        ))
+        .bind(id)
+        .fetch_one(self.pool)
+        .await?;
 
         if !exists {
             return Err(IntentError::TaskNotFound(id));
@@ -1579,6 +2720,149 @@ impl<'a> TaskManager<'a> {
         })
     }
 
+    /// Copy a task and all its descendants
+    ///
+    /// The copy of the root becomes a sibling of the original. Specs, priorities,
+    /// owners, metadata, estimates and checklists are copied, and dependencies
+    /// between tasks of the subtree are remapped to the copies; dependencies on
+    /// tasks outside it are not. Unless `keep_status` is set every copy starts
+    /// as todo with its checklist unchecked.
+    pub async fn clone_subtree(
+        &self,
+        id: i64,
+        options: CloneOptions,
+    ) -> Result<CloneSubtreeResponse> {
+        self.check_task_exists(id).await?;
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        // Parents come before their children
+        let originals: Vec<(i64, Option<i64>, String, Option<String>)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE subtree(id, depth) AS (
+                SELECT ?, 0
+                UNION ALL
+                SELECT t.id, s.depth + 1 FROM tasks t JOIN subtree s ON t.parent_id = s.id
+            )
+            SELECT t.id, t.parent_id, t.name, t.spec
+            FROM tasks t JOIN subtree s ON t.id = s.id
+            ORDER BY s.depth, t.id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut id_map = std::collections::BTreeMap::new();
+        for (original_id, parent_id, name, spec) in &originals {
+            let (parent_id, name) = if *original_id == id {
+                (
+                    *parent_id,
+                    options
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("{} (copy)", name)),
+                )
+            } else {
+                (parent_id.map(|p| id_map[&p]), name.clone())
+            };
+            let status_sql = if options.keep_status {
+                "status, first_todo_at, first_doing_at, first_done_at"
+            } else {
+                "'todo', ?, NULL, NULL"
+            };
+            let sql = format!(
+                r#"
+                INSERT INTO tasks (parent_id, name, spec, status, first_todo_at, first_doing_at,
+                                   first_done_at, complexity, priority, active_form, owner,
+                                   metadata, sort_order, estimate_minutes, visibility)
+                SELECT ?, ?, spec, {}, complexity, priority, active_form, owner,
+                       metadata, sort_order, estimate_minutes, visibility
+                FROM tasks WHERE id = ?
+                "#,
+                status_sql
+            );
+            let mut query = sqlx::query(&sql).bind(parent_id).bind(&name);
+            if !options.keep_status {
+                query = query.bind(now);
+            }
+            let copy_id = query
+                .bind(original_id)
+                .execute(&mut *tx)
+                .await?
+                .last_insert_rowid();
+            id_map.insert(*original_id, copy_id);
+
+            crate::spec_meta::sync(&mut tx, copy_id, spec.as_deref()).await?;
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO checklist_items (task_id, position, text, checked, checked_at)
+                SELECT ?, position, text, {}
+                FROM checklist_items WHERE task_id = ?
+                "#,
+                if options.keep_status {
+                    "checked, checked_at"
+                } else {
+                    "0, NULL"
+                }
+            ))
+            .bind(copy_id)
+            .bind(original_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let ids_json = serde_json::to_string(&id_map.keys().collect::<Vec<_>>())?;
+        let edges: Vec<(i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT blocking_task_id, blocked_task_id FROM dependencies
+            WHERE blocking_task_id IN (SELECT value FROM json_each(?))
+              AND blocked_task_id IN (SELECT value FROM json_each(?))
+            "#,
+        )
+        .bind(&ids_json)
+        .bind(&ids_json)
+        .fetch_all(&mut *tx)
+        .await?;
+        for (blocking, blocked) in &edges {
+            sqlx::query(
+                "INSERT INTO dependencies (blocking_task_id, blocked_task_id) VALUES (?, ?)",
+            )
+            .bind(id_map[blocking])
+            .bind(id_map[blocked])
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut event_count = 0;
+        if options.include_events {
+            for (original_id, copy_id) in &id_map {
+                event_count += sqlx::query(
+                    r#"
+                    INSERT INTO events (task_id, timestamp, log_type, discussion_data, visibility, actor)
+                    SELECT ?, timestamp, log_type, discussion_data, visibility, actor
+                    FROM events WHERE task_id = ? ORDER BY id
+                    "#,
+                )
+                .bind(copy_id)
+                .bind(original_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected() as usize;
+            }
+        }
+
+        tx.commit().await?;
+        self.notify_batch_changed().await;
+
+        Ok(CloneSubtreeResponse {
+            root_task: self.get_task(id_map[&id]).await?,
+            task_id_map: id_map,
+            dependency_count: edges.len(),
+            event_count,
+        })
+    }
+
     /// Intelligently pick tasks from 'todo' and transition them to 'doing'
     /// Returns tasks that were successfully transitioned
     ///
@@ -1586,6 +2870,9 @@ impl<'a> TaskManager<'a> {
     /// * `max_count` - Maximum number of tasks to pick
     /// * `capacity_limit` - Maximum total number of tasks allowed in 'doing' status
     ///
+    /// Tasks that would go past a WIP limit of the project (see `crate::wip`)
+    /// are passed over.
+    ///
     /// # Logic
     /// 1. Check current 'doing' task count
     /// 2. Calculate available capacity
@@ -1595,6 +2882,22 @@ impl<'a> TaskManager<'a> {
         &self,
         max_count: usize,
         capacity_limit: usize,
+    ) -> Result<Vec<Task>> {
+        self.pick_next_tasks_within_budget(max_count, capacity_limit, None)
+            .await
+    }
+
+    /// Like [`pick_next_tasks`](Self::pick_next_tasks), but also bounded by a time budget
+    ///
+    /// With `budget_minutes`, the remaining estimate of tasks already in
+    /// 'doing' is subtracted first; todo tasks are then taken in priority order
+    /// while their estimates still fit. Tasks without an estimate are never
+    /// picked under a budget, since there is no telling whether they fit.
+    pub async fn pick_next_tasks_within_budget(
+        &self,
+        max_count: usize,
+        capacity_limit: usize,
+        budget_minutes: Option<i64>,
     ) -> Result<Vec<Task>> {
         let mut tx = self.pool.begin().await?;
 
@@ -1612,23 +2915,63 @@ impl<'a> TaskManager<'a> {
 
         let limit = std::cmp::min(max_count, available);
 
-        // Select tasks from todo, prioritizing by priority DESC, complexity ASC
-        let todo_tasks = sqlx::query_as::<_, Task>(
+        let wip = WipLimits::for_pool(self.pool)?;
+
+        // Select tasks from todo, prioritizing by priority DESC, complexity ASC.
+        // Under a budget or WIP limits every candidate is needed, as some may
+        // not fit.
+        let candidates = sqlx::query_as::<_, Task>(&format!(
             r#"
-                        SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                        SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                         FROM tasks
-                        WHERE status = 'todo'
+                        WHERE status = 'todo' AND {}
                         ORDER BY
                             COALESCE(priority, 0) ASC,
                             COALESCE(complexity, 5) ASC,
                             id ASC
                         LIMIT ?
                         "#,
-        )
-        .bind(limit as i64)
+            self.scope.sql_filter()
+        ))
+        .bind(if budget_minutes.is_some() || !wip.is_empty() {
+            -1
+        } else {
+            limit as i64
+        })
         .fetch_all(&mut *tx)
         .await?;
 
+        let mut left = match budget_minutes {
+            Some(budget) => {
+                let doing_minutes: i64 = sqlx::query_scalar(
+                    "SELECT COALESCE(SUM(estimate_minutes), 0) FROM tasks WHERE status = 'doing'",
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                Some(budget - doing_minutes)
+            },
+            None => None,
+        };
+        let mut wip_batch = WipBatch::default();
+        let mut todo_tasks: Vec<Task> = Vec::new();
+        for task in candidates {
+            if todo_tasks.len() == limit {
+                break;
+            }
+            let fits_budget = match (left, task.estimate_minutes) {
+                (None, _) => true,
+                (Some(left), Some(minutes)) => minutes <= left,
+                (Some(_), None) => false,
+            };
+            if !fits_budget || !wip.admit_in_tx(&mut tx, &mut wip_batch, task.id).await? {
+                continue;
+            }
+            if let (Some(left), Some(minutes)) = (left.as_mut(), task.estimate_minutes) {
+                *left -= minutes;
+            }
+            todo_tasks.push(task);
+        }
+
         if todo_tasks.is_empty() {
             return Ok(vec![]);
         }
@@ -1637,6 +2980,8 @@ impl<'a> TaskManager<'a> {
 
         // Transition selected tasks to 'doing'
         for task in &todo_tasks {
+            self.record_transition_in_tx(&mut tx, task.id, "doing")
+                .await?;
             sqlx::query(
                 r#"
                 UPDATE tasks
@@ -1657,7 +3002,7 @@ impl<'a> TaskManager<'a> {
         let task_ids: Vec<i64> = todo_tasks.iter().map(|t| t.id).collect();
         let placeholders = vec!["?"; task_ids.len()].join(",");
         let query = format!(
-            "SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+            "SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                          FROM tasks WHERE id IN ({})
                          ORDER BY
                              COALESCE(priority, 0) ASC,
@@ -1675,6 +3020,347 @@ impl<'a> TaskManager<'a> {
         Ok(updated_tasks)
     }
 
+    /// Claim the next available task for `session_id` with the default lease
+    pub async fn claim_next(&self, session_id: &str) -> Result<Option<Task>> {
+        self.claim_next_for(session_id, chrono::Duration::minutes(DEFAULT_LEASE_MINUTES))
+            .await
+    }
+
+    /// Lease the next available task to `session_id` and start it
+    ///
+    /// Candidates are todo tasks that are not blocked, have no unfinished
+    /// children and hold no live lease, taken in `pick_next_tasks` order.
+    /// Tasks left in 'doing' by a session whose lease ran out come first, so
+    /// abandoned work is picked up again; once `tasks.capacity_limit` tasks
+    /// (the project-wide WIP limit, see `crate::wip`) are in doing, only those are. Selecting and
+    /// leasing is a single statement, so two sessions claiming at once never
+    /// get the same task.
+    /// The task moves to 'doing' and becomes the session's focus. Returns
+    /// None when nothing is available.
+    pub async fn claim_next_for(
+        &self,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<Option<Task>> {
+        let now = Utc::now();
+        // Past the project's capacity only abandoned doing tasks are claimed
+        let capacity = crate::config::ProjectConfig::for_pool(self.pool)?
+            .tasks
+            .capacity_limit
+            .map_or(i64::MAX, |limit| limit as i64);
+        let mut tx = self.pool.begin().await?;
+
+        let claimed: Option<i64> = sqlx::query_scalar(&format!(
+            r#"
+            INSERT INTO task_leases (task_id, assignee, claimed_at, lease_expires_at)
+            SELECT t.id, ?, ?, ?
+            FROM tasks t
+            WHERE (
+                  (t.status = 'todo' AND (SELECT COUNT(*) FROM tasks WHERE status = 'doing') < ?)
+                  OR (t.status = 'doing' AND EXISTS (
+                      SELECT 1 FROM task_leases l
+                      WHERE l.task_id = t.id AND l.lease_expires_at <= ?
+                  ))
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM task_leases l
+                  WHERE l.task_id = t.id AND l.lease_expires_at > ?
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM dependencies d
+                  JOIN tasks b ON b.id = d.blocking_task_id
+                  WHERE d.blocked_task_id = t.id AND b.status != 'done'
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM tasks c WHERE c.parent_id = t.id AND c.status != 'done'
+              )
+              AND {}
+            ORDER BY
+                t.status = 'doing' DESC,
+                COALESCE(t.priority, 0) ASC,
+                COALESCE(t.complexity, 5) ASC,
+                t.id ASC
+            LIMIT 1
+            ON CONFLICT(task_id) DO UPDATE SET
+                assignee = excluded.assignee,
+                claimed_at = excluded.claimed_at,
+                lease_expires_at = excluded.lease_expires_at
+            RETURNING task_id
+            "#,
+            self.scope.sql_filter()
+        ))
+        .bind(session_id)
+        .bind(now)
+        .bind(now + lease)
+        .bind(capacity)
+        .bind(now)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(task_id) = claimed else {
+            return Ok(None);
+        };
+
+        self.record_transition_in_tx(&mut tx, task_id, "doing")
+            .await?;
+        sqlx::query(
+            "UPDATE tasks SET status = 'doing', first_doing_at = COALESCE(first_doing_at, ?) WHERE id = ?",
+        )
+        .bind(now)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO sessions (session_id, current_task_id, created_at, last_active_at)
+            VALUES (?, ?, datetime('now'), datetime('now'))
+            ON CONFLICT(session_id) DO UPDATE SET
+                current_task_id = excluded.current_task_id,
+                last_active_at = datetime('now')
+            "#,
+        )
+        .bind(session_id)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let task = self.get_task(task_id).await?;
+        self.notify_task_updated(&task).await;
+        Ok(Some(task))
+    }
+
+    /// Extend the lease `session_id` holds on `task_id` to `lease` from now
+    ///
+    /// An expired lease can be renewed as long as no other session has
+    /// claimed the task since.
+    pub async fn renew_lease(
+        &self,
+        task_id: i64,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> Result<TaskLease> {
+        let renewed: Option<TaskLease> = sqlx::query_as(
+            r#"
+            UPDATE task_leases SET lease_expires_at = ?
+            WHERE task_id = ? AND assignee = ?
+            RETURNING task_id, assignee, claimed_at, lease_expires_at
+            "#,
+        )
+        .bind(Utc::now() + lease)
+        .bind(task_id)
+        .bind(session_id)
+        .fetch_optional(self.pool)
+        .await?;
+
+        renewed.ok_or_else(|| {
+            IntentError::ActionNotAllowed(format!(
+                "Session '{}' holds no lease on task #{}",
+                session_id, task_id
+            ))
+        })
+    }
+
+    /// Give up the lease `session_id` holds on `task_id`; false if it held none
+    pub async fn release_lease(&self, task_id: i64, session_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM task_leases WHERE task_id = ? AND assignee = ?")
+            .bind(task_id)
+            .bind(session_id)
+            .execute(self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Recompute priority boosts of waiting todo tasks (see `crate::priority`)
+    pub async fn age_priorities(&self) -> Result<crate::priority::AgingResult> {
+        crate::priority::age_tasks(self.pool).await
+    }
+
+    /// Suggest a complexity for a task from similar completed tasks (see
+    /// `crate::priority::estimate_complexity`); with `apply`, store it
+    pub async fn estimate_complexity(
+        &self,
+        id: i64,
+        apply: bool,
+    ) -> Result<Option<crate::priority::ComplexityEstimate>> {
+        let task = self.get_task(id).await?;
+        let estimate = crate::priority::estimate_complexity(self.pool, &task).await?;
+        if let (true, Some(estimate)) = (apply, &estimate) {
+            self.update_task(
+                id,
+                TaskUpdate {
+                    complexity: Some(estimate.complexity),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+        Ok(estimate)
+    }
+
+    /// Flag doing tasks without activity for the project's `stale.days` and,
+    /// with `stale.demote`, move them back to todo (see `crate::stale`)
+    ///
+    /// `days` overrides the setting; `demote` demotes even when the setting
+    /// is off.
+    pub async fn sweep_stale(&self, days: Option<f64>, demote: bool) -> Result<StaleSweep> {
+        let mut policy = StalePolicy::load(self.pool).await?;
+        if let Some(days) = days {
+            policy.days = days;
+        }
+        policy.demote |= demote;
+        self.sweep_stale_with(policy, Utc::now()).await
+    }
+
+    /// Stale sweep with an explicit policy, as of `now`
+    ///
+    /// A task's last activity is its latest event other than `stale`, or its
+    /// latest move to doing when that is newer. Each stale spell gets one
+    /// `stale` event, plus one more when the task is demoted.
+    pub async fn sweep_stale_with(
+        &self,
+        policy: StalePolicy,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<StaleSweep> {
+        if policy.is_off() {
+            return Ok(StaleSweep {
+                policy,
+                tasks: Vec::new(),
+            });
+        }
+
+        type Row = (
+            i64,
+            String,
+            String,
+            Option<chrono::DateTime<Utc>>,
+            Option<chrono::DateTime<Utc>>,
+            Option<chrono::DateTime<Utc>>,
+            Option<chrono::DateTime<Utc>>,
+        );
+        let rows: Vec<Row> = sqlx::query_as(&format!(
+            r#"
+            SELECT t.id, t.name, t.owner, t.first_doing_at,
+                (SELECT MAX(e.timestamp) FROM events e
+                 WHERE e.task_id = t.id AND e.log_type != ?1),
+                (SELECT MAX(tr.changed_at) FROM task_transitions tr
+                 WHERE tr.task_id = t.id AND tr.to_status = 'doing'),
+                (SELECT MAX(e.timestamp) FROM events e
+                 WHERE e.task_id = t.id AND e.log_type = ?1)
+            FROM tasks t
+            WHERE t.status = 'doing' AND {}
+            "#,
+            self.scope.sql_filter()
+        ))
+        .bind(crate::stale::STALE_LOG_TYPE)
+        .fetch_all(self.pool)
+        .await?;
+
+        let threshold = chrono::Duration::seconds((policy.days * 86_400.0) as i64);
+        let mut tasks = Vec::new();
+        let mut tx = self.pool.begin().await?;
+        for (id, name, owner, first_doing_at, last_event, last_doing, last_stale) in rows {
+            let Some(last_activity_at) = [first_doing_at, last_event, last_doing]
+                .into_iter()
+                .flatten()
+                .max()
+            else {
+                continue;
+            };
+            let idle = now - last_activity_at;
+            if idle < threshold {
+                continue;
+            }
+            let idle_days = (idle.num_seconds() as f64 / 86_400.0 * 10.0).round() / 10.0;
+            let already_flagged = matches!(last_stale, Some(at) if at >= last_activity_at);
+            let flagged = !already_flagged || policy.demote;
+
+            if policy.demote {
+                self.record_transition_in_tx(&mut tx, id, "todo").await?;
+                sqlx::query("UPDATE tasks SET status = 'todo' WHERE id = ?")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("DELETE FROM task_leases WHERE task_id = ?")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("UPDATE sessions SET current_task_id = NULL WHERE current_task_id = ?")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            if flagged {
+                sqlx::query(
+                    r#"
+                    INSERT INTO events (task_id, log_type, discussion_data, timestamp, visibility, actor)
+                    VALUES (?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(id)
+                .bind(crate::stale::STALE_LOG_TYPE)
+                .bind(crate::stale::event_text(idle_days, policy.demote))
+                .bind(now)
+                .bind(EventVisibility::Normal.as_str())
+                .bind(Json(&self.actor))
+                .execute(&mut *tx)
+                .await?;
+            }
+            tasks.push(StaleTask {
+                id,
+                name,
+                owner,
+                last_activity_at,
+                idle_days,
+                flagged,
+                demoted: policy.demote,
+            });
+        }
+        tx.commit().await?;
+
+        tasks.sort_by(|a, b| {
+            a.last_activity_at
+                .cmp(&b.last_activity_at)
+                .then(a.id.cmp(&b.id))
+        });
+        let sweep = StaleSweep { policy, tasks };
+        if sweep.demoted() > 0 {
+            self.notify_batch_changed().await;
+        }
+        Ok(sweep)
+    }
+
+    /// The live lease on a task, if any
+    pub async fn get_lease(&self, task_id: i64) -> Result<Option<TaskLease>> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT task_id, assignee, claimed_at, lease_expires_at
+            FROM task_leases WHERE task_id = ? AND lease_expires_at > ?
+            "#,
+        )
+        .bind(task_id)
+        .bind(Utc::now())
+        .fetch_optional(self.pool)
+        .await?)
+    }
+
+    /// All live leases, soonest to expire first
+    pub async fn active_leases(&self) -> Result<Vec<TaskLease>> {
+        Ok(sqlx::query_as(
+            r#"
+            SELECT task_id, assignee, claimed_at, lease_expires_at
+            FROM task_leases WHERE lease_expires_at > ?
+            ORDER BY lease_expires_at ASC
+            "#,
+        )
+        .bind(Utc::now())
+        .fetch_all(self.pool)
+        .await?)
+    }
+
     /// Intelligently recommend the next task to work on based on context-aware priority model.
     ///
     /// Priority logic:
@@ -1682,8 +3368,14 @@ impl<'a> TaskManager<'a> {
     /// 2. Second priority: Top-level tasks (breadth-first)
     /// 3. No recommendation: Return appropriate empty state
     ///
-    /// This command does NOT modify task status.
+    /// This command does NOT modify task status. Todo tasks are ranked by
+    /// effective priority, refreshed first unless the database is read-only.
+    #[tracing::instrument(skip(self))]
     pub async fn pick_next(&self) -> Result<PickNextResponse> {
+        if !crate::project::is_read_only() {
+            crate::priority::age_tasks(self.pool).await?;
+        }
+
         // Step 1: Check if there's a current focused task for this session
         let session_id = crate::workspace::resolve_session_id(None);
         let current_task_id: Option<i64> = sqlx::query_scalar::<_, Option<i64>>(
@@ -1697,12 +3389,12 @@ impl<'a> TaskManager<'a> {
         if let Some(current_id) = current_task_id {
             // Step 1a: First priority - Get **doing** subtasks of current focused task
             // Exclude tasks blocked by incomplete dependencies
-            let doing_subtasks = sqlx::query_as::<_, Task>(
+            let doing_subtasks = sqlx::query_as::<_, Task>(&format!(
                 r#"
                         SELECT id, parent_id, name, spec, status, complexity, priority,
-                               first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                               first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                         FROM tasks
-                        WHERE parent_id = ? AND status = 'doing'
+                        WHERE parent_id = ? AND status = 'doing' AND {}
                           AND NOT EXISTS (
                             SELECT 1 FROM dependencies d
                             JOIN tasks bt ON d.blocking_task_id = bt.id
@@ -1712,7 +3404,8 @@ impl<'a> TaskManager<'a> {
                         ORDER BY COALESCE(priority, 999999) ASC, id ASC
                         LIMIT 1
                         "#,
-            )
+                self.scope.sql_filter()
+            ))
             .bind(current_id)
             .fetch_optional(self.pool)
             .await?;
@@ -1722,22 +3415,24 @@ impl<'a> TaskManager<'a> {
             }
 
             // Step 1b: Second priority - Get **todo** subtasks if no doing subtasks
-            let todo_subtasks = sqlx::query_as::<_, Task>(
+            let todo_subtasks = sqlx::query_as::<_, Task>(&format!(
                 r#"
                             SELECT id, parent_id, name, spec, status, complexity, priority,
-                                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                             FROM tasks
-                            WHERE parent_id = ? AND status = 'todo'
+                            WHERE parent_id = ? AND status = 'todo' AND {}
                               AND NOT EXISTS (
                                 SELECT 1 FROM dependencies d
                                 JOIN tasks bt ON d.blocking_task_id = bt.id
                                 WHERE d.blocked_task_id = tasks.id
                                   AND bt.status != 'done'
                               )
-                            ORDER BY COALESCE(priority, 999999) ASC, id ASC
+                            ORDER BY {} ASC, id ASC
                             LIMIT 1
                             "#,
-            )
+                self.scope.sql_filter(),
+                crate::priority::EFFECTIVE_PRIORITY_SQL
+            ))
             .bind(current_id)
             .fetch_optional(self.pool)
             .await?;
@@ -1750,12 +3445,12 @@ impl<'a> TaskManager<'a> {
         // Step 2a: Third priority - Get top-level **doing** tasks (excluding current task)
         // Exclude tasks blocked by incomplete dependencies
         let doing_top_level = if let Some(current_id) = current_task_id {
-            sqlx::query_as::<_, Task>(
+            sqlx::query_as::<_, Task>(&format!(
                 r#"
                 SELECT id, parent_id, name, spec, status, complexity, priority,
-                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                 FROM tasks
-                WHERE parent_id IS NULL AND status = 'doing' AND id != ?
+                WHERE parent_id IS NULL AND status = 'doing' AND id != ? AND {}
                   AND NOT EXISTS (
                     SELECT 1 FROM dependencies d
                     JOIN tasks bt ON d.blocking_task_id = bt.id
@@ -1765,17 +3460,18 @@ impl<'a> TaskManager<'a> {
                 ORDER BY COALESCE(priority, 999999) ASC, id ASC
                 LIMIT 1
                 "#,
-            )
+                self.scope.sql_filter()
+            ))
             .bind(current_id)
             .fetch_optional(self.pool)
             .await?
         } else {
-            sqlx::query_as::<_, Task>(
+            sqlx::query_as::<_, Task>(&format!(
                 r#"
                 SELECT id, parent_id, name, spec, status, complexity, priority,
-                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                       first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                 FROM tasks
-                WHERE parent_id IS NULL AND status = 'doing'
+                WHERE parent_id IS NULL AND status = 'doing' AND {}
                   AND NOT EXISTS (
                     SELECT 1 FROM dependencies d
                     JOIN tasks bt ON d.blocking_task_id = bt.id
@@ -1785,7 +3481,8 @@ impl<'a> TaskManager<'a> {
                 ORDER BY COALESCE(priority, 999999) ASC, id ASC
                 LIMIT 1
                 "#,
-            )
+                self.scope.sql_filter()
+            ))
             .fetch_optional(self.pool)
             .await?
         };
@@ -1796,22 +3493,24 @@ impl<'a> TaskManager<'a> {
 
         // Step 2b: Fourth priority - Get top-level **todo** tasks
         // Exclude tasks blocked by incomplete dependencies
-        let todo_top_level = sqlx::query_as::<_, Task>(
+        let todo_top_level = sqlx::query_as::<_, Task>(&format!(
             r#"
             SELECT id, parent_id, name, spec, status, complexity, priority,
-                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                   first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
             FROM tasks
-            WHERE parent_id IS NULL AND status = 'todo'
+            WHERE parent_id IS NULL AND status = 'todo' AND {}
               AND NOT EXISTS (
                 SELECT 1 FROM dependencies d
                 JOIN tasks bt ON d.blocking_task_id = bt.id
                 WHERE d.blocked_task_id = tasks.id
                   AND bt.status != 'done'
               )
-            ORDER BY COALESCE(priority, 999999) ASC, id ASC
+            ORDER BY {} ASC, id ASC
             LIMIT 1
             "#,
-        )
+            self.scope.sql_filter(),
+            crate::priority::EFFECTIVE_PRIORITY_SQL
+        ))
         .fetch_optional(self.pool)
         .await?;
 
@@ -1821,19 +3520,22 @@ impl<'a> TaskManager<'a> {
 
         // Step 3: No recommendation - determine why
         // Check if there are any tasks at all
-        let total_tasks: i64 =
-            sqlx::query_scalar::<_, i64>(crate::sql_constants::COUNT_TASKS_TOTAL)
-                .fetch_one(self.pool)
-                .await?;
+        let total_tasks: i64 = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM tasks WHERE {}",
+            self.scope.sql_filter()
+        ))
+        .fetch_one(self.pool)
+        .await?;
 
         if total_tasks == 0 {
             return Ok(PickNextResponse::no_tasks_in_project());
         }
 
         // Check if all tasks are completed
-        let todo_or_doing_count: i64 = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM tasks WHERE status IN ('todo', 'doing')",
-        )
+        let todo_or_doing_count: i64 = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM tasks WHERE status IN ('todo', 'doing') AND {}",
+            self.scope.sql_filter()
+        ))
         .fetch_one(self.pool)
         .await?;
 
@@ -1845,6 +3547,125 @@ impl<'a> TaskManager<'a> {
         Ok(PickNextResponse::no_available_todos())
     }
 
+    /// `pick_next`, plus every open task scored and explained
+    ///
+    /// Candidates mirror the tiers of `pick_next`: doing then todo subtasks
+    /// of the focused task, then top-level doing then todo tasks, each
+    /// ordered by effective priority and then age. Tasks `pick_next` would
+    /// never suggest score 0 and say why.
+    pub async fn explain_next(&self) -> Result<PickNextResponse> {
+        let mut response = self.pick_next().await?;
+        let selected_id = response.task.as_ref().map(|t| t.id);
+
+        let session_id = crate::workspace::resolve_session_id(None);
+        let current_task_id: Option<i64> = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT current_task_id FROM sessions WHERE session_id = ?",
+        )
+        .bind(&session_id)
+        .fetch_optional(self.pool)
+        .await?
+        .flatten();
+
+        type Row = (
+            i64,
+            String,
+            String,
+            Option<i64>,
+            Option<i32>,
+            f64,
+            Option<chrono::DateTime<Utc>>,
+            Option<String>,
+        );
+        let rows: Vec<Row> = sqlx::query_as(&format!(
+            r#"
+            SELECT id, name, status, parent_id, priority, {} AS effective_priority, first_todo_at,
+                   (SELECT GROUP_CONCAT('#' || b.id, ', ')
+                    FROM dependencies d JOIN tasks b ON b.id = d.blocking_task_id
+                    WHERE d.blocked_task_id = t.id AND b.status != 'done') AS blockers
+            FROM tasks t
+            WHERE status IN ('todo', 'doing') AND {}
+            "#,
+            crate::priority::EFFECTIVE_PRIORITY_SQL,
+            self.scope.sql_filter()
+        ))
+        .fetch_all(self.pool)
+        .await?;
+
+        let now = Utc::now();
+        let mut ranked: Vec<(Option<u8>, PickNextCandidate)> = rows
+            .into_iter()
+            .map(
+                |(id, name, status, parent_id, priority, effective, first_todo_at, blockers)| {
+                    let doing = status == "doing";
+                    let mut reasons = Vec::new();
+                    let tier = if let Some(blockers) = blockers {
+                        reasons.push(format!("blocked by {}", blockers));
+                        None
+                    } else if doing && Some(id) == current_task_id {
+                        reasons.push("current focus".to_string());
+                        None
+                    } else if current_task_id.is_some() && parent_id == current_task_id {
+                        reasons.push(format!(
+                            "subtask of focused task #{}",
+                            parent_id.unwrap_or_default()
+                        ));
+                        Some(if doing { 0 } else { 1 })
+                    } else if let Some(parent) = parent_id {
+                        reasons.push(format!("subtask of #{}, which is not focused", parent));
+                        None
+                    } else {
+                        reasons.push("top-level task".to_string());
+                        Some(if doing { 2 } else { 3 })
+                    };
+
+                    if tier.is_some() {
+                        if doing {
+                            reasons.push("already in progress".to_string());
+                        }
+                        let level = priority
+                            .map(crate::priority::PriorityLevel::to_str)
+                            .unwrap_or("unset");
+                        let base = f64::from(priority.unwrap_or(5));
+                        if effective < base {
+                            reasons.push(format!("priority {}, aged to {:.2}", level, effective));
+                        } else {
+                            reasons.push(format!("priority {}", level));
+                        }
+                        if let Some(at) = first_todo_at {
+                            reasons.push(format!("waiting {} days", (now - at).num_days()));
+                        }
+                    }
+
+                    // Each tier outranks any priority within the tier below
+                    let score = tier
+                        .map(|tier| f64::from(4 - tier) * 100.0 + (5.0 - effective) * 10.0)
+                        .unwrap_or(0.0);
+                    (
+                        tier,
+                        PickNextCandidate {
+                            task_id: id,
+                            name,
+                            status,
+                            score,
+                            effective_priority: effective,
+                            selected: Some(id) == selected_id,
+                            reasons,
+                        },
+                    )
+                },
+            )
+            .collect();
+
+        ranked.sort_by(|(a_tier, a), (b_tier, b)| {
+            (a_tier.is_none(), a_tier)
+                .cmp(&(b_tier.is_none(), b_tier))
+                .then(a.effective_priority.total_cmp(&b.effective_priority))
+                .then(a.task_id.cmp(&b.task_id))
+        });
+        response.candidates = ranked.into_iter().map(|(_, c)| c).collect();
+        Ok(response)
+    }
+
     /// Try to synthesize task description using LLM
     ///
     /// Returns Ok(None) if LLM is not configured (graceful degradation)
@@ -1939,6 +3760,36 @@ impl crate::backend::TaskBackend for TaskManager<'_> {
         self.get_task_with_events(id)
     }
 
+    fn spec_history(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<SpecRevision>>> + Send {
+        self.spec_history(id)
+    }
+
+    fn find_children(
+        &self,
+        parent_id: Option<i64>,
+        cursor: Option<i64>,
+        limit: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<ChildrenPage>> + Send {
+        self.find_children(parent_id, cursor, limit)
+    }
+
+    fn transitions(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<TaskTransition>>> + Send {
+        self.transitions(id)
+    }
+
+    fn get_subtree_stats(
+        &self,
+        root_id: i64,
+    ) -> impl std::future::Future<Output = Result<SubtreeStats>> + Send {
+        self.get_subtree_stats(root_id)
+    }
+
     fn get_task_ancestry(
         &self,
         task_id: i64,
@@ -2064,23 +3915,122 @@ impl crate::backend::TaskBackend for TaskManager<'_> {
         self.start_task(id, with_events)
     }
 
-    fn done_task(
+    fn start_task_over_wip_limit(
+        &self,
+        id: i64,
+        with_events: bool,
+    ) -> impl std::future::Future<Output = Result<TaskWithEvents>> + Send {
+        self.start_task_over_wip_limit(id, with_events)
+    }
+
+    fn done_task(&self) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task()
+    }
+
+    fn done_task_by_id(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task_by_id(id)
+    }
+
+    fn done_task_with_outcome(
+        &self,
+        outcome: Option<CompletionOutcome>,
+    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task_with_outcome(outcome)
+    }
+
+    fn done_task_by_id_with_outcome(
+        &self,
+        id: i64,
+        outcome: Option<CompletionOutcome>,
+    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.done_task_by_id_with_outcome(id, outcome)
+    }
+
+    fn approve_task(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
+        self.approve_task(id)
+    }
+
+    fn pick_next(&self) -> impl std::future::Future<Output = Result<PickNextResponse>> + Send {
+        self.pick_next()
+    }
+
+    fn claim_next_for(
+        &self,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> impl std::future::Future<Output = Result<Option<Task>>> + Send {
+        self.claim_next_for(session_id, lease)
+    }
+
+    fn renew_lease(
+        &self,
+        task_id: i64,
+        session_id: &str,
+        lease: chrono::Duration,
+    ) -> impl std::future::Future<Output = Result<TaskLease>> + Send {
+        self.renew_lease(task_id, session_id, lease)
+    }
+
+    fn release_lease(
+        &self,
+        task_id: i64,
+        session_id: &str,
+    ) -> impl std::future::Future<Output = Result<bool>> + Send {
+        self.release_lease(task_id, session_id)
+    }
+
+    fn age_priorities(
+        &self,
+    ) -> impl std::future::Future<Output = Result<crate::priority::AgingResult>> + Send {
+        self.age_priorities()
+    }
+
+    fn estimate_complexity(
         &self,
-        is_ai_caller: bool,
-    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
-        self.done_task(is_ai_caller)
+        id: i64,
+        apply: bool,
+    ) -> impl std::future::Future<Output = Result<Option<crate::priority::ComplexityEstimate>>> + Send
+    {
+        self.estimate_complexity(id, apply)
     }
 
-    fn done_task_by_id(
+    fn sweep_stale(
         &self,
-        id: i64,
-        is_ai_caller: bool,
-    ) -> impl std::future::Future<Output = Result<DoneTaskResponse>> + Send {
-        self.done_task_by_id(id, is_ai_caller)
+        days: Option<f64>,
+        demote: bool,
+    ) -> impl std::future::Future<Output = Result<StaleSweep>> + Send {
+        self.sweep_stale(days, demote)
     }
 
-    fn pick_next(&self) -> impl std::future::Future<Output = Result<PickNextResponse>> + Send {
-        self.pick_next()
+    fn explain_next(&self) -> impl std::future::Future<Output = Result<PickNextResponse>> + Send {
+        self.explain_next()
+    }
+
+    async fn clone_subtree(&self, id: i64, options: CloneOptions) -> Result<CloneSubtreeResponse> {
+        self.clone_subtree(id, options).await
+    }
+
+    async fn checklist(&self, task_id: i64) -> Result<Vec<crate::db::models::ChecklistItem>> {
+        crate::checklist::ChecklistManager::new(self.pool)
+            .list(task_id)
+            .await
+    }
+
+    async fn set_checklist_item(
+        &self,
+        task_id: i64,
+        position: i64,
+        checked: bool,
+    ) -> Result<crate::db::models::ChecklistItem> {
+        crate::checklist::ChecklistManager::new(self.pool)
+            .set_checked(task_id, position, checked)
+            .await
     }
 }
 
@@ -2274,6 +4224,279 @@ mod tests {
         assert!(updated.first_doing_at.is_some());
     }
 
+    #[tokio::test]
+    async fn test_spec_history_records_changes() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+
+        let task = manager
+            .add_task(
+                "Spec task",
+                Some("Original criteria"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(manager.spec_history(task.id).await.unwrap().is_empty());
+
+        let update = |spec| TaskUpdate {
+            spec: Some(spec),
+            ..Default::default()
+        };
+        manager
+            .update_task(task.id, update("Second"))
+            .await
+            .unwrap();
+        // Unchanged spec is not a new revision
+        manager
+            .update_task(task.id, update("Second"))
+            .await
+            .unwrap();
+
+        // Transaction path used by plans
+        let mut tx = ctx.pool().begin().await.unwrap();
+        manager
+            .update_task_in_tx(&mut tx, task.id, Some("Third"), None, None, None)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let history = manager.spec_history(task.id).await.unwrap();
+        let specs: Vec<(i64, &str)> = history
+            .iter()
+            .map(|r| (r.revision, r.spec.as_str()))
+            .collect();
+        assert_eq!(
+            specs,
+            vec![(1, "Original criteria"), (2, "Second"), (3, "Third")]
+        );
+
+        assert!(matches!(
+            manager.spec_history(9999).await,
+            Err(IntentError::TaskNotFound(9999))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_find_children_pages_one_level_with_child_counts() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let root = manager
+            .add_task("Root", None, None, None, None, None)
+            .await
+            .unwrap();
+        let mut children = Vec::new();
+        for (name, priority) in [
+            ("P3", Some(3)),
+            ("None", None),
+            ("P1", Some(1)),
+            ("P3b", Some(3)),
+        ] {
+            let child = manager
+                .add_task(name, None, Some(root.id), None, priority, None)
+                .await
+                .unwrap();
+            children.push(child);
+        }
+        manager
+            .add_task("Grandchild", None, Some(children[0].id), None, None, None)
+            .await
+            .unwrap();
+
+        // Same order as get_children, walked two at a time
+        let expected: Vec<i64> = manager
+            .get_children(root.id)
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = manager
+                .find_children(Some(root.id), cursor, Some(2))
+                .await
+                .unwrap();
+            assert_eq!(page.total_count, 4);
+            seen.extend(page.tasks.iter().map(|n| n.task.id));
+            if !page.has_more {
+                assert!(page.next_cursor.is_none());
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen, expected);
+        assert_eq!(
+            seen,
+            vec![
+                children[2].id,
+                children[0].id,
+                children[3].id,
+                children[1].id
+            ]
+        );
+
+        let page = manager
+            .find_children(Some(root.id), None, None)
+            .await
+            .unwrap();
+        let counts: Vec<i64> = page.tasks.iter().map(|n| n.child_count).collect();
+        assert_eq!(counts, vec![0, 1, 0, 0]);
+
+        // Without a parent, root tasks
+        let roots = manager.find_children(None, None, None).await.unwrap();
+        assert_eq!(roots.tasks.len(), 1);
+        assert_eq!(roots.tasks[0].child_count, 4);
+
+        // A cursor must belong to the listed parent
+        assert!(matches!(
+            manager
+                .find_children(None, Some(children[0].id), None)
+                .await,
+            Err(IntentError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            manager.find_children(Some(9999), None, None).await,
+            Err(IntentError::TaskNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_subtree_stats_roll_up_descendants_and_blocking_chain() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let root = manager
+            .add_task("Root", None, None, None, None, None)
+            .await
+            .unwrap();
+        let done = manager
+            .add_task("Done", None, Some(root.id), None, None, None)
+            .await
+            .unwrap();
+        let waiting = manager
+            .add_task("Waiting", None, Some(root.id), None, None, None)
+            .await
+            .unwrap();
+        let grandchild = manager
+            .add_task("Grandchild", None, Some(waiting.id), None, None, None)
+            .await
+            .unwrap();
+        let outside = manager
+            .add_task("Outside", None, None, None, None, None)
+            .await
+            .unwrap();
+        let far = manager
+            .add_task("Far", None, None, None, None, None)
+            .await
+            .unwrap();
+        for (id, minutes, complexity) in [(done.id, 30, 2), (grandchild.id, 60, 5)] {
+            manager
+                .update_task(
+                    id,
+                    TaskUpdate {
+                        estimate_minutes: Some(minutes),
+                        complexity: Some(complexity),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        manager
+            .update_task(
+                done.id,
+                TaskUpdate {
+                    status: Some("done"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        // grandchild <- outside <- far; the finished task's blocker is ignored
+        manager
+            .add_dependency(outside.id, grandchild.id)
+            .await
+            .unwrap();
+        manager.add_dependency(far.id, outside.id).await.unwrap();
+        manager.add_dependency(far.id, done.id).await.unwrap();
+
+        let stats = manager.get_subtree_stats(root.id).await.unwrap();
+        assert_eq!(stats.root_id, root.id);
+        assert_eq!((stats.total_tasks, stats.todo, stats.done), (4, 3, 1));
+        assert_eq!(stats.completion_percent, 25.0);
+        assert_eq!(stats.estimate.total_minutes, 90);
+        assert_eq!(stats.estimate.remaining_minutes, 60);
+        assert_eq!(stats.estimate.unestimated, 2);
+        assert_eq!(stats.total_complexity, 7);
+        let chain: Vec<i64> = stats.blocking_chain.iter().map(|t| t.id).collect();
+        assert_eq!(chain, vec![grandchild.id, outside.id, far.id]);
+
+        // Leaves roll up to themselves
+        let leaf = manager.get_subtree_stats(far.id).await.unwrap();
+        assert_eq!(leaf.total_tasks, 1);
+        assert!(leaf.blocking_chain.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transitions_record_every_status_change() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let task = manager
+            .add_task("Churn", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        manager.start_task(task.id, false).await.unwrap();
+        // Refocusing a task already in doing is not a change
+        manager.start_task(task.id, false).await.unwrap();
+        TaskManager::new(ctx.pool())
+            .with_source(TransitionSource::Dashboard)
+            .update_task(
+                task.id,
+                TaskUpdate {
+                    status: Some("todo"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        manager.start_task(task.id, false).await.unwrap();
+        let mut tx = ctx.pool().begin().await.unwrap();
+        TaskManager::new(ctx.pool())
+            .with_source(TransitionSource::Plan)
+            .with_actor(Actor::ai().with_session("s-1"))
+            .complete_task_in_tx(&mut tx, task.id, None)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let transitions = manager.transitions(task.id).await.unwrap();
+        let changes: Vec<(&str, &str, TransitionSource)> = transitions
+            .iter()
+            .map(|t| (t.from_status.as_str(), t.to_status.as_str(), t.source))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("todo", "doing", TransitionSource::Cli),
+                ("doing", "todo", TransitionSource::Dashboard),
+                ("todo", "doing", TransitionSource::Cli),
+                ("doing", "done", TransitionSource::Plan),
+            ]
+        );
+        assert!(transitions[1].actor.as_ref().is_some_and(|a| !a.is_ai()));
+        assert_eq!(transitions[3].actor, Some(Actor::ai().with_session("s-1")));
+
+        assert!(matches!(
+            manager.transitions(9999).await,
+            Err(IntentError::TaskNotFound(9999))
+        ));
+    }
+
     #[tokio::test]
     async fn test_delete_task() {
         let ctx = TestContext::new().await;
@@ -2417,7 +4640,7 @@ mod tests {
             .await
             .unwrap();
         manager.start_task(task.id, false).await.unwrap();
-        let response = manager.done_task(false).await.unwrap();
+        let response = manager.done_task().await.unwrap();
 
         assert_eq!(response.completed_task.status, "done");
         assert!(response.completed_task.first_done_at.is_some());
@@ -2460,7 +4683,7 @@ mod tests {
         // Set parent as current task
         manager.start_task(parent.id, false).await.unwrap();
 
-        let result = manager.done_task(false).await;
+        let result = manager.done_task().await;
         assert!(matches!(result, Err(IntentError::UncompletedChildren)));
     }
 
@@ -2480,7 +4703,7 @@ mod tests {
 
         // Complete child first
         manager.start_task(child.id, false).await.unwrap();
-        let child_response = manager.done_task(false).await.unwrap();
+        let child_response = manager.done_task().await.unwrap();
 
         // Child completion should suggest parent is ready
         match child_response.next_step_suggestion {
@@ -2492,7 +4715,7 @@ mod tests {
 
         // Now parent can be completed
         manager.start_task(parent.id, false).await.unwrap();
-        let parent_response = manager.done_task(false).await.unwrap();
+        let parent_response = manager.done_task().await.unwrap();
         assert_eq!(parent_response.completed_task.status, "done");
 
         // Parent completion should indicate top-level task completed (since it had children)
@@ -2773,6 +4996,61 @@ mod tests {
         assert_eq!(picked[2].priority, Some(10)); // highest number = lowest priority
     }
 
+    #[tokio::test]
+    async fn test_pick_next_tasks_within_budget() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+
+        // In priority order: 60, unestimated, 45, 20 minutes
+        let mut ids = Vec::new();
+        for estimate in [Some(60), None, Some(45), Some(20)] {
+            let task = manager
+                .add_task(&format!("Task {}", ids.len()), None, None, None, None, None)
+                .await
+                .unwrap();
+            if estimate.is_some() {
+                manager
+                    .update_task(
+                        task.id,
+                        TaskUpdate {
+                            estimate_minutes: estimate,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .unwrap();
+            }
+            ids.push(task.id);
+        }
+
+        // 90 minutes: the 60-minute task fits, the 45 no longer does, the 20 does
+        let picked = manager
+            .pick_next_tasks_within_budget(10, 10, Some(90))
+            .await
+            .unwrap();
+        let picked_ids: Vec<i64> = picked.iter().map(|t| t.id).collect();
+        assert_eq!(picked_ids, vec![ids[0], ids[3]]);
+
+        // Work in progress already uses 80 of the next 90 minutes
+        let picked = manager
+            .pick_next_tasks_within_budget(10, 10, Some(90))
+            .await
+            .unwrap();
+        assert!(picked.is_empty());
+
+        let err = manager
+            .update_task(
+                ids[1],
+                TaskUpdate {
+                    estimate_minutes: Some(-1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, IntentError::InvalidInput(_)));
+    }
+
     #[tokio::test]
     async fn test_pick_next_tasks_complexity_ordering() {
         let ctx = TestContext::new().await;
@@ -2862,7 +5140,7 @@ mod tests {
 
         // Complete first child
         manager.start_task(child1.id, false).await.unwrap();
-        let response = manager.done_task(false).await.unwrap();
+        let response = manager.done_task().await.unwrap();
 
         // Should indicate siblings remain
         match response.next_step_suggestion {
@@ -2879,7 +5157,7 @@ mod tests {
 
         // Complete second child
         manager.start_task(child2.id, false).await.unwrap();
-        let response2 = manager.done_task(false).await.unwrap();
+        let response2 = manager.done_task().await.unwrap();
 
         // Should still indicate siblings remain
         match response2.next_step_suggestion {
@@ -2910,11 +5188,11 @@ mod tests {
 
         // Complete child first
         manager.start_task(child.id, false).await.unwrap();
-        manager.done_task(false).await.unwrap();
+        manager.done_task().await.unwrap();
 
         // Complete parent
         manager.start_task(parent.id, false).await.unwrap();
-        let response = manager.done_task(false).await.unwrap();
+        let response = manager.done_task().await.unwrap();
 
         // Should be TOP_LEVEL_TASK_COMPLETED
         match response.next_step_suggestion {
@@ -2931,36 +5209,190 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_done_task_no_parent_context() {
+    async fn test_done_task_no_parent_context() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+
+        // Create multiple standalone tasks
+        let task1 = manager
+            .add_task("Standalone Task 1", None, None, None, None, None)
+            .await
+            .unwrap();
+        let _task2 = manager
+            .add_task("Standalone Task 2", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        // Complete first task
+        manager.start_task(task1.id, false).await.unwrap();
+        let response = manager.done_task().await.unwrap();
+
+        // Should be NO_PARENT_CONTEXT since task2 is still pending
+        match response.next_step_suggestion {
+            NextStepSuggestion::NoParentContext {
+                completed_task_id,
+                completed_task_name,
+                ..
+            } => {
+                assert_eq!(completed_task_id, task1.id);
+                assert_eq!(completed_task_name, "Standalone Task 1");
+            },
+            _ => panic!("Expected NoParentContext suggestion"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_done_reports_newly_unblocked_tasks() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let schema = manager
+            .add_task("Schema", None, None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        let fixtures = manager
+            .add_task("Fixtures", None, None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        let migrate = manager
+            .add_task("Migrate", None, None, Some("ai"), None, None)
+            .await
+            .unwrap();
+        manager.add_dependency(schema.id, migrate.id).await.unwrap();
+        manager
+            .add_dependency(fixtures.id, migrate.id)
+            .await
+            .unwrap();
+
+        // One blocker is still open
+        let done = manager.done_task_by_id(schema.id).await.unwrap();
+        assert!(done.newly_unblocked.is_empty());
+
+        let done = manager.done_task_by_id(fixtures.id).await.unwrap();
+        assert_eq!(
+            done.newly_unblocked,
+            vec![UnblockedTask {
+                id: migrate.id,
+                name: "Migrate".to_string(),
+                owner: "ai".to_string(),
+                visibility: TaskVisibility::Shared,
+            }]
+        );
+
+        // Completing an already finished task again is not news
+        let done = manager.done_task_by_id(fixtures.id).await.unwrap();
+        assert!(done.newly_unblocked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blocked_reason_follows_dependencies() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let api = manager
+            .add_task("API", None, None, None, None, None)
+            .await
+            .unwrap();
+        let client = manager
+            .add_task("Client", None, None, None, None, None)
+            .await
+            .unwrap();
+        let design = manager
+            .add_task("Design", None, None, None, None, None)
+            .await
+            .unwrap();
+        manager.add_dependency(api.id, client.id).await.unwrap();
+
+        // Starting it fails on the dependency and says why
+        assert!(manager.start_task(client.id, false).await.is_err());
+        let blocked = manager.get_task(client.id).await.unwrap();
+        assert_eq!(
+            blocked.blocked_reason,
+            Some(format!("Waiting on #{}", api.id))
+        );
+
+        manager
+            .update_task(
+                design.id,
+                TaskUpdate {
+                    blocked_reason: Some("Waiting for brand colors"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let listed = manager
+            .find_tasks(Some("blocked"), None, Some(TaskSortBy::Id), None, None)
+            .await
+            .unwrap();
+        let ids: Vec<i64> = listed.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![client.id, design.id]);
+
+        // Finishing the last dependency unblocks; hand-set reasons stay
+        manager.done_task_by_id(api.id).await.unwrap();
+        assert_eq!(
+            manager.get_task(client.id).await.unwrap().blocked_reason,
+            None
+        );
+        assert_eq!(
+            manager
+                .get_task(design.id)
+                .await
+                .unwrap()
+                .blocked_reason
+                .as_deref(),
+            Some("Waiting for brand colors")
+        );
+
+        manager
+            .update_task(
+                design.id,
+                TaskUpdate {
+                    blocked_reason: Some(""),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_task(design.id).await.unwrap().blocked_reason,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_done_task_with_outcome() {
         let ctx = TestContext::new().await;
         let manager = TaskManager::new(ctx.pool());
-
-        // Create multiple standalone tasks
-        let task1 = manager
-            .add_task("Standalone Task 1", None, None, None, None, None)
+        let task = manager
+            .add_task("Login", None, None, None, None, None)
             .await
             .unwrap();
-        let _task2 = manager
-            .add_task("Standalone Task 2", None, None, None, None, None)
+
+        let blank = CompletionOutcome {
+            summary: "  ".to_string(),
+            artifacts: Vec::new(),
+        };
+        assert!(manager
+            .done_task_by_id_with_outcome(task.id, Some(blank))
             .await
-            .unwrap();
+            .is_err());
+        assert_eq!(manager.get_task(task.id).await.unwrap().status, "todo");
 
-        // Complete first task
-        manager.start_task(task1.id, false).await.unwrap();
-        let response = manager.done_task(false).await.unwrap();
+        let outcome = CompletionOutcome {
+            summary: "JWT login works ".to_string(),
+            artifacts: vec![" src/auth.rs".to_string()],
+        };
+        manager
+            .done_task_by_id_with_outcome(task.id, Some(outcome))
+            .await
+            .unwrap();
 
-        // Should be NO_PARENT_CONTEXT since task2 is still pending
-        match response.next_step_suggestion {
-            NextStepSuggestion::NoParentContext {
-                completed_task_id,
-                completed_task_name,
-                ..
-            } => {
-                assert_eq!(completed_task_id, task1.id);
-                assert_eq!(completed_task_name, "Standalone Task 1");
-            },
-            _ => panic!("Expected NoParentContext suggestion"),
-        }
+        let result = manager.get_task_with_events(task.id).await.unwrap();
+        assert_eq!(result.task.status, "done");
+        let recorded = result.outcome.unwrap();
+        assert_eq!(recorded.summary, "JWT login works");
+        assert_eq!(recorded.artifacts, vec!["src/auth.rs".to_string()]);
+        let events = result.events_summary.unwrap().recent_events;
+        assert_eq!(events[0].log_type, CompletionOutcome::LOG_TYPE);
     }
 
     // =========================================================================
@@ -2984,7 +5416,7 @@ mod tests {
         manager.start_task(task_a.id, false).await.unwrap();
 
         // Complete task_b by ID (not the focused task)
-        let response = manager.done_task_by_id(task_b.id, false).await.unwrap();
+        let response = manager.done_task_by_id(task_b.id).await.unwrap();
 
         // task_b should be done
         assert_eq!(response.completed_task.status, "done");
@@ -3018,7 +5450,7 @@ mod tests {
         manager.start_task(task.id, false).await.unwrap();
 
         // Complete the focused task by ID
-        let response = manager.done_task_by_id(task.id, false).await.unwrap();
+        let response = manager.done_task_by_id(task.id).await.unwrap();
 
         assert_eq!(response.completed_task.status, "done");
         assert_eq!(response.workspace_status.current_task_id, None);
@@ -3058,14 +5490,17 @@ mod tests {
             .unwrap();
 
         // AI caller should be rejected
-        let result = manager.done_task_by_id(task.id, true).await;
+        let result = TaskManager::new(ctx.pool())
+            .with_actor(Actor::ai())
+            .done_task_by_id(task.id)
+            .await;
         assert!(matches!(
             result,
             Err(IntentError::HumanTaskCannotBeCompletedByAI { .. })
         ));
 
         // Human caller should succeed
-        let response = manager.done_task_by_id(task.id, false).await.unwrap();
+        let response = manager.done_task_by_id(task.id).await.unwrap();
         assert_eq!(response.completed_task.status, "done");
     }
 
@@ -3083,7 +5518,7 @@ mod tests {
             .await
             .unwrap();
 
-        let result = manager.done_task_by_id(parent.id, false).await;
+        let result = manager.done_task_by_id(parent.id).await;
         assert!(matches!(result, Err(IntentError::UncompletedChildren)));
     }
 
@@ -3092,7 +5527,7 @@ mod tests {
         let ctx = TestContext::new().await;
         let manager = TaskManager::new(ctx.pool());
 
-        let result = manager.done_task_by_id(99999, false).await;
+        let result = manager.done_task_by_id(99999).await;
         assert!(matches!(result, Err(IntentError::TaskNotFound(99999))));
     }
 
@@ -3125,7 +5560,7 @@ mod tests {
         manager.start_task(task.id, false).await.unwrap();
 
         // Should complete successfully even without LLM
-        let result = manager.done_task_by_id(task.id, false).await;
+        let result = manager.done_task_by_id(task.id).await;
         assert!(result.is_ok(), "Task completion should succeed without LLM");
 
         // Verify task is actually done
@@ -3165,11 +5600,11 @@ mod tests {
 
         // Both should complete successfully
         manager.start_task(ai_task.id, false).await.unwrap();
-        let result = manager.done_task_by_id(ai_task.id, false).await;
+        let result = manager.done_task_by_id(ai_task.id).await;
         assert!(result.is_ok());
 
         manager.start_task(human_task.id, false).await.unwrap();
-        let result = manager.done_task_by_id(human_task.id, false).await;
+        let result = manager.done_task_by_id(human_task.id).await;
         assert!(result.is_ok());
     }
 
@@ -3319,7 +5754,7 @@ mod tests {
             .await
             .unwrap();
         manager.start_task(task.id, false).await.unwrap();
-        manager.done_task(false).await.unwrap();
+        manager.done_task().await.unwrap();
 
         // Pick next should indicate all tasks completed
         let response = manager.pick_next().await.unwrap();
@@ -3386,6 +5821,49 @@ mod tests {
         assert_eq!(response.task.as_ref().unwrap().status, "doing");
     }
 
+    #[tokio::test]
+    async fn test_explain_next_scores_candidates() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+
+        let epic = manager
+            .add_task("Epic", None, None, None, Some(2), None)
+            .await
+            .unwrap();
+        let blocked = manager
+            .add_task("Blocked", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        let child = manager
+            .add_task("Child", None, Some(epic.id), None, Some(1), None)
+            .await
+            .unwrap();
+        let chore = manager
+            .add_task("Chore", None, None, None, Some(4), None)
+            .await
+            .unwrap();
+        manager.add_dependency(epic.id, blocked.id).await.unwrap();
+
+        let response = manager.explain_next().await.unwrap();
+        assert_eq!(response.task.as_ref().unwrap().id, epic.id);
+        let order: Vec<i64> = response.candidates.iter().map(|c| c.task_id).collect();
+        assert_eq!(order, vec![epic.id, chore.id, blocked.id, child.id]);
+
+        let top = &response.candidates[0];
+        assert!(top.selected);
+        assert!(top.score > response.candidates[1].score);
+        assert!(top.reasons.contains(&"priority high".to_string()));
+        assert_eq!(response.candidates[2].score, 0.0);
+        assert_eq!(
+            response.candidates[2].reasons,
+            vec![format!("blocked by #{}", epic.id)]
+        );
+        assert!(response.candidates[3].reasons[0].contains("not focused"));
+
+        // Plain pick_next leaves the candidates out
+        assert!(manager.pick_next().await.unwrap().candidates.is_empty());
+    }
+
     #[tokio::test]
     async fn test_pick_next_priority_ordering() {
         let ctx = TestContext::new().await;
@@ -3492,7 +5970,7 @@ mod tests {
 
         // Add some events
         event_mgr
-            .add_event(task.id, "progress", "Event 1")
+            .add_event(task.id, "milestone", "Event 1")
             .await
             .unwrap();
         event_mgr
@@ -3509,7 +5987,7 @@ mod tests {
         assert_eq!(summary.total_count, 2);
         assert_eq!(summary.recent_events.len(), 2);
         assert_eq!(summary.recent_events[0].log_type, "decision"); // Most recent first
-        assert_eq!(summary.recent_events[1].log_type, "progress");
+        assert_eq!(summary.recent_events[1].log_type, "milestone");
     }
 
     #[tokio::test]
@@ -3535,7 +6013,7 @@ mod tests {
         // Add 20 events
         for i in 0..20 {
             event_mgr
-                .add_event(task.id, "test", &format!("Event {}", i))
+                .add_event(task.id, "note", &format!("Event {}", i))
                 .await
                 .unwrap();
         }
@@ -4131,7 +6609,7 @@ mod tests {
 
         // Verify both tasks are in doing status
         let doing_tasks: Vec<Task> = sqlx::query_as(
-            r#"SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+            r#"SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
              FROM tasks WHERE status = 'doing' ORDER BY id"#
         )
         .fetch_all(ctx.pool())
@@ -4175,6 +6653,368 @@ mod tests {
         assert!(!page2.has_more);
         assert_eq!(page2.offset, 10);
     }
+
+    #[tokio::test]
+    async fn test_reorder_tasks_respected_in_priority_sort() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+
+        let a = task_mgr
+            .add_task("A", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        let b = task_mgr
+            .add_task("B", None, None, None, Some(2), None)
+            .await
+            .unwrap();
+        let c = task_mgr
+            .add_task("C", None, None, None, Some(3), None)
+            .await
+            .unwrap();
+
+        // Manual ordering overrides priority
+        let reordered = task_mgr.reorder_tasks(&[c.id, a.id]).await.unwrap();
+        assert_eq!(reordered[0].sort_order, Some(0));
+        assert_eq!(reordered[1].sort_order, Some(1));
+
+        let result = task_mgr
+            .find_tasks(None, None, Some(TaskSortBy::Priority), None, None)
+            .await
+            .unwrap();
+        let ids: Vec<i64> = result.tasks.iter().map(|t| t.id).collect();
+        // Unordered tasks come after manually ordered ones
+        assert_eq!(ids, vec![c.id, a.id, b.id]);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_tasks_rejects_invalid_input() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+
+        let a = task_mgr
+            .add_task("A", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let result = task_mgr.reorder_tasks(&[a.id, a.id]).await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+
+        let result = task_mgr.reorder_tasks(&[a.id, 9999]).await;
+        assert!(matches!(result, Err(IntentError::TaskNotFound(9999))));
+
+        // Nothing was written on failure
+        let task = task_mgr.get_task(a.id).await.unwrap();
+        assert_eq!(task.sort_order, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_task_sort_order() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+
+        let task = task_mgr
+            .add_task("Movable", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let moved = task_mgr
+            .update_task(
+                task.id,
+                TaskUpdate {
+                    status: Some("doing"),
+                    sort_order: Some(5),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(moved.status, "doing");
+        assert_eq!(moved.sort_order, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_claim_next_leases_tasks() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let first = manager
+            .add_task("First", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        let second = manager
+            .add_task("Second", None, None, None, Some(2), None)
+            .await
+            .unwrap();
+        let blocked = manager
+            .add_task("Blocked", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        manager.add_dependency(second.id, blocked.id).await.unwrap();
+
+        let a = manager.claim_next("agent-a").await.unwrap().unwrap();
+        assert_eq!(a.id, first.id);
+        assert_eq!(a.status, "doing");
+        let b = manager.claim_next("agent-b").await.unwrap().unwrap();
+        assert_eq!(b.id, second.id);
+        // The only todo task left is blocked
+        assert!(manager.claim_next("agent-c").await.unwrap().is_none());
+
+        let focus = WorkspaceManager::new(ctx.pool())
+            .get_current_task(Some("agent-a"))
+            .await
+            .unwrap();
+        assert_eq!(focus.current_task_id, Some(first.id));
+
+        // Another session cannot start a leased task
+        assert!(matches!(
+            manager.start_task(first.id, false).await,
+            Err(IntentError::TaskLeased { task_id, .. }) if task_id == first.id
+        ));
+
+        assert!(manager
+            .renew_lease(first.id, "agent-b", chrono::Duration::minutes(5))
+            .await
+            .is_err());
+        assert!(!manager.release_lease(first.id, "agent-b").await.unwrap());
+
+        // An expired lease frees the task for another session
+        manager
+            .renew_lease(first.id, "agent-a", chrono::Duration::minutes(-1))
+            .await
+            .unwrap();
+        assert!(manager.get_lease(first.id).await.unwrap().is_none());
+        let c = manager.claim_next("agent-c").await.unwrap().unwrap();
+        assert_eq!(c.id, first.id);
+        assert_eq!(
+            manager.get_lease(first.id).await.unwrap().unwrap().assignee,
+            "agent-c"
+        );
+
+        assert!(manager.release_lease(second.id, "agent-b").await.unwrap());
+        assert_eq!(manager.active_leases().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_clone_subtree() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+
+        let outside = manager
+            .add_task("Outside", None, None, None, None, None)
+            .await
+            .unwrap();
+        let release = manager
+            .add_task("Release 1.2", Some("Ship it"), None, None, Some(1), None)
+            .await
+            .unwrap();
+        let build = manager
+            .add_task("Build", None, Some(release.id), None, None, None)
+            .await
+            .unwrap();
+        let publish = manager
+            .add_task("Publish", None, Some(release.id), None, None, None)
+            .await
+            .unwrap();
+        manager.add_dependency(build.id, publish.id).await.unwrap();
+        manager.add_dependency(outside.id, build.id).await.unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(build.id, "decision", "Use cargo-dist")
+            .await
+            .unwrap();
+        manager.done_task_by_id(publish.id).await.unwrap();
+
+        let options = CloneOptions {
+            name: Some("Release 1.3".to_string()),
+            include_events: true,
+            ..Default::default()
+        };
+        let result = manager.clone_subtree(release.id, options).await.unwrap();
+        assert_eq!(result.root_task.name, "Release 1.3");
+        assert_eq!(result.root_task.priority, Some(1));
+        assert_eq!(result.task_id_map.len(), 3);
+        assert_eq!(result.dependency_count, 1);
+        assert_eq!(result.event_count, 1);
+
+        let publish_copy = manager
+            .get_task(result.task_id_map[&publish.id])
+            .await
+            .unwrap();
+        assert_eq!(publish_copy.status, "todo");
+        assert_eq!(publish_copy.parent_id, Some(result.root_task.id));
+        let blockers = manager.get_blocking_tasks(publish_copy.id).await.unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].id, result.task_id_map[&build.id]);
+        // Only dependencies inside the subtree are copied
+        let build_copy = result.task_id_map[&build.id];
+        assert!(manager
+            .get_blocking_tasks(build_copy)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_after_clone_updates_original() {
+        use crate::plan::{PlanExecutor, PlanRequest};
+
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool());
+        let auth = manager
+            .add_task("Auth", None, None, None, None, None)
+            .await
+            .unwrap();
+        let login = manager
+            .add_task("Login", None, Some(auth.id), None, None, None)
+            .await
+            .unwrap();
+
+        let result = manager
+            .clone_subtree(auth.id, CloneOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.root_task.name, "Auth (copy)");
+
+        let request: PlanRequest = serde_json::from_str(
+            r#"{"tasks": [{"name": "Auth", "spec": "OAuth", "children": [
+                {"name": "Login", "spec": "Login form"}
+            ]}]}"#,
+        )
+        .unwrap();
+        PlanExecutor::new(ctx.pool())
+            .execute(&request)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.get_task(auth.id).await.unwrap().spec.as_deref(),
+            Some("OAuth")
+        );
+        assert_eq!(
+            manager.get_task(login.id).await.unwrap().spec.as_deref(),
+            Some("Login form")
+        );
+        let login_copy = result.task_id_map[&login.id];
+        assert_eq!(manager.get_task(login_copy).await.unwrap().spec, None);
+    }
+
+    #[tokio::test]
+    async fn test_private_tasks_hidden_from_shared_scope() {
+        let ctx = TestContext::new().await;
+        let shared = TaskManager::new(ctx.pool()).with_scope(TaskVisibility::Shared);
+        let private = TaskManager::new(ctx.pool()).with_scope(TaskVisibility::Private);
+
+        let public = shared
+            .add_task("Public", None, None, None, None, None)
+            .await
+            .unwrap();
+        let secret = shared
+            .add_task("Salary review", None, None, None, None, None)
+            .await
+            .unwrap();
+        let secret = shared
+            .update_task(
+                secret.id,
+                TaskUpdate {
+                    visibility: Some(TaskVisibility::Private),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(secret.visibility, TaskVisibility::Private);
+
+        assert!(matches!(
+            shared.get_task(secret.id).await,
+            Err(IntentError::TaskNotFound(_))
+        ));
+        let found = shared
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(found.total_count, 1);
+        assert_eq!(found.tasks[0].id, public.id);
+        assert_eq!(shared.get_stats().await.unwrap().total_tasks, 1);
+
+        assert_eq!(
+            private.get_task(secret.id).await.unwrap().name,
+            "Salary review"
+        );
+        let found = private
+            .find_tasks(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(found.total_count, 2);
+
+        // Only the shared task is ever picked in the shared scope
+        let picked = shared.pick_next_tasks(5, 5).await.unwrap();
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].id, public.id);
+    }
+
+    #[tokio::test]
+    async fn test_private_visibility_covers_subtree() {
+        let ctx = TestContext::new().await;
+        let manager = TaskManager::new(ctx.pool()).with_scope(TaskVisibility::Private);
+
+        let parent = manager
+            .add_task("Parent", None, None, None, None, None)
+            .await
+            .unwrap();
+        let child = manager
+            .add_task("Child", None, Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+        manager
+            .update_task(
+                parent.id,
+                TaskUpdate {
+                    visibility: Some(TaskVisibility::Private),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_task(child.id).await.unwrap().visibility,
+            TaskVisibility::Private
+        );
+
+        // New children inherit it
+        let late = manager
+            .add_task("Late child", None, Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(late.visibility, TaskVisibility::Private);
+
+        // A child cannot be shared while its parent is private
+        let result = manager
+            .update_task(
+                child.id,
+                TaskUpdate {
+                    visibility: Some(TaskVisibility::Shared),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(IntentError::InvalidInput(_))));
+
+        // Moving a shared task under the private parent makes it private
+        let other = manager
+            .add_task("Other", None, None, None, None, None)
+            .await
+            .unwrap();
+        let moved = manager
+            .update_task(
+                other.id,
+                TaskUpdate {
+                    parent_id: Some(Some(parent.id)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(moved.visibility, TaskVisibility::Private);
+    }
 }
 
 // Re-export TaskContext for cli_handlers
@@ -0,0 +1,119 @@
+//! Line-based text diff
+//!
+//! Small LCS diff for comparing spec revisions. Specs are short, so the
+//! quadratic table is not a concern.
+
+use serde::Serialize;
+
+/// What happened to a line between the old and new text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Same,
+    Removed,
+    Added,
+}
+
+impl DiffOp {
+    /// Unified-diff style line prefix
+    pub fn prefix(&self) -> char {
+        match self {
+            DiffOp::Same => ' ',
+            DiffOp::Removed => '-',
+            DiffOp::Added => '+',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine<'a> {
+    pub op: DiffOp,
+    pub text: &'a str,
+}
+
+/// Diff two texts line by line; removals come before additions at each change
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine {
+                op: DiffOp::Same,
+                text: old[i],
+            });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine {
+                op: DiffOp::Removed,
+                text: old[i],
+            });
+            i += 1;
+        } else {
+            lines.push(DiffLine {
+                op: DiffOp::Added,
+                text: new[j],
+            });
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|text| DiffLine {
+        op: DiffOp::Removed,
+        text,
+    }));
+    lines.extend(new[j..].iter().map(|text| DiffLine {
+        op: DiffOp::Added,
+        text,
+    }));
+
+    lines
+}
+
+/// Render a diff with `-`/`+`/` ` prefixes, one line per entry
+pub fn render(lines: &[DiffLine<'_>]) -> String {
+    lines
+        .iter()
+        .map(|line| format!("{}{}", line.op.prefix(), line.text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_marks_changes() {
+        let old = "Goal: login\nUse OAuth\nAdd tests";
+        let new = "Goal: login\nUse passkeys\nAdd tests\nDocument it";
+
+        assert_eq!(
+            render(&diff_lines(old, new)),
+            " Goal: login\n-Use OAuth\n+Use passkeys\n Add tests\n+Document it"
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_identical_and_empty() {
+        assert!(diff_lines("a\nb", "a\nb")
+            .iter()
+            .all(|line| line.op == DiffOp::Same));
+        assert_eq!(render(&diff_lines("", "new")), "+new");
+        assert_eq!(render(&diff_lines("old", "")), "-old");
+    }
+}
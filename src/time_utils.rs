@@ -64,12 +64,29 @@ pub fn parse_duration(duration: &str) -> Result<DateTime<Utc>> {
     Ok(Utc::now() - offset)
 }
 
-/// Parse a date filter string — either a duration (e.g. "7d", "1w") or a date (e.g. "2025-01-01").
+/// Parse a date filter string — either a duration (e.g. "7d", "1w"), a date (e.g. "2025-01-01"),
+/// or "today" / "yesterday" (local midnight).
 ///
 /// Tries duration format first via `parse_duration`, then falls back to YYYY-MM-DD date parsing.
 pub fn parse_date_filter(input: &str) -> std::result::Result<DateTime<Utc>, String> {
     let input = input.trim();
 
+    let days_back = match input {
+        "today" => Some(0),
+        "yesterday" => Some(1),
+        _ => None,
+    };
+    if let Some(days) = days_back {
+        let date = chrono::Local::now().date_naive() - Duration::days(days);
+        return chrono::TimeZone::from_local_datetime(
+            &chrono::Local,
+            &date.and_hms_opt(0, 0, 0).unwrap(),
+        )
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| format!("No local midnight on {}", date));
+    }
+
     // Try duration format first (e.g., "7d", "1w")
     if let Ok(dt) = parse_duration(input) {
         return Ok(dt);
@@ -82,11 +99,28 @@ pub fn parse_date_filter(input: &str) -> std::result::Result<DateTime<Utc>, Stri
     }
 
     Err(format!(
-        "Invalid date format '{}'. Use duration (7d, 1w) or date (2025-01-01)",
+        "Invalid date format '{}'. Use duration (7d, 1w), date (2025-01-01), today or yesterday",
         input
     ))
 }
 
+/// Parse a point in time: RFC 3339 (e.g. "2025-01-01T09:30:00Z") or a
+/// duration ago (e.g. "30m", "2h").
+pub fn parse_timestamp(input: &str) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    parse_duration(input).map_err(|_| {
+        IntentError::InvalidInput(format!(
+            "Invalid timestamp '{}'. Use RFC 3339 (2025-01-01T09:30:00Z) or a duration ago (30m, 2h)",
+            input
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,10 +225,37 @@ mod tests {
         assert_eq!(dt.format("%Y-%m-%d").to_string(), "2025-06-15");
     }
 
+    #[test]
+    fn test_parse_date_filter_days() {
+        let today = parse_date_filter("today").unwrap();
+        let yesterday = parse_date_filter("yesterday").unwrap();
+        assert!(today <= Utc::now());
+        assert_eq!(
+            today.with_timezone(&chrono::Local).date_naive(),
+            chrono::Local::now().date_naive()
+        );
+        // 23 to 25 hours apart, depending on daylight saving
+        assert!((today - yesterday).num_hours().abs_diff(24) <= 1);
+    }
+
     #[test]
     fn test_parse_date_filter_invalid() {
         let result = parse_date_filter("not-a-date");
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid date format"));
     }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let dt = parse_timestamp("2025-06-15T09:30:00+02:00").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2025-06-15T07:30:00+00:00");
+
+        let dt = parse_timestamp("2h").unwrap();
+        assert!(((Utc::now() - dt) - Duration::hours(2)).num_seconds().abs() <= 1);
+
+        assert!(matches!(
+            parse_timestamp("yesterday"),
+            Err(IntentError::InvalidInput(_))
+        ));
+    }
 }
@@ -0,0 +1,243 @@
+//! Timing of database operations
+//!
+//! Database work in `TaskManager`, `EventManager`, `PlanExecutor` and search
+//! runs in `tracing` spans (`#[tracing::instrument]`); spans that read or
+//! write a known number of rows record it in a `rows` field. `TimingLayer`
+//! adds up, per span, how often it ran, its total and longest wall time and
+//! its rows. Work that is not a span, such as the transaction of a plan, is
+//! added with [`record`].
+//!
+//! `ie -v <command>` installs the layer and prints the summary to stderr when
+//! the command ends, so a slow plan shows where its time went.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Operations shown in the summary, slowest first
+const SUMMARY_LIMIT: usize = 25;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<BTreeMap<String, OpTiming>> = Mutex::new(BTreeMap::new());
+
+/// Accumulated timing of one operation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OpTiming {
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+    /// Rows read or written, for operations that record them
+    pub rows: Option<u64>,
+}
+
+/// Add one run of an operation to the summary (no-op unless timing is on)
+pub fn record(operation: &str, elapsed: Duration, rows: Option<u64>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let timing = timings.entry(operation.to_string()).or_default();
+    timing.calls += 1;
+    timing.total += elapsed;
+    timing.max = timing.max.max(elapsed);
+    if let Some(rows) = rows {
+        *timing.rows.get_or_insert(0) += rows;
+    }
+}
+
+/// Operations recorded so far, by total time, slowest first
+pub fn summary() -> Vec<(String, OpTiming)> {
+    let timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries: Vec<_> = timings
+        .iter()
+        .map(|(name, timing)| (name.clone(), *timing))
+        .collect();
+    entries.sort_by(|a, b| b.1.total.cmp(&a.1.total).then_with(|| a.0.cmp(&b.0)));
+    entries
+}
+
+/// Print the summary to stderr; nothing when timing is off
+///
+/// Nested operations are counted in their callers too, so totals overlap.
+pub fn print_summary(command_elapsed: Duration) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let entries = summary();
+    let mut out = std::io::stderr().lock();
+    let _ = writeln!(
+        out,
+        "\nTiming summary ({} total)",
+        format_duration(command_elapsed)
+    );
+    if entries.is_empty() {
+        let _ = writeln!(out, "  No database operations recorded");
+        return;
+    }
+    let width = entries
+        .iter()
+        .take(SUMMARY_LIMIT)
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("operation".len());
+    let _ = writeln!(
+        out,
+        "  {:<width$}  {:>6}  {:>10}  {:>10}  {:>8}",
+        "operation", "calls", "total", "max", "rows"
+    );
+    for (name, timing) in entries.iter().take(SUMMARY_LIMIT) {
+        let _ = writeln!(
+            out,
+            "  {:<width$}  {:>6}  {:>10}  {:>10}  {:>8}",
+            name,
+            timing.calls,
+            format_duration(timing.total),
+            format_duration(timing.max),
+            timing
+                .rows
+                .map(|rows| rows.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        );
+    }
+    if entries.len() > SUMMARY_LIMIT {
+        let _ = writeln!(out, "  ... {} more", entries.len() - SUMMARY_LIMIT);
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    if ms >= 1000.0 {
+        format!("{:.2}s", ms / 1000.0)
+    } else {
+        format!("{:.1}ms", ms)
+    }
+}
+
+/// `tracing` layer feeding span timings into the summary
+pub struct TimingLayer {
+    _private: (),
+}
+
+impl TimingLayer {
+    /// Create the layer; from now on [`record`] collects timings
+    pub fn install() -> Self {
+        ENABLED.store(true, Ordering::Relaxed);
+        Self { _private: () }
+    }
+}
+
+/// Start time and rows of an open span
+struct SpanTiming {
+    started: Instant,
+    rows: Option<u64>,
+}
+
+/// Picks the `rows` field out of span fields
+struct RowsVisitor<'a>(&'a mut Option<u64>);
+
+impl Visit for RowsVisitor<'_> {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "rows" {
+            *self.0 = Some(value);
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if field.name() == "rows" {
+            *self.0 = Some(value.max(0) as u64);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut rows = None;
+        attrs.record(&mut RowsVisitor(&mut rows));
+        span.extensions_mut().insert(SpanTiming {
+            started: Instant::now(),
+            rows,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timing) = extensions.get_mut::<SpanTiming>() {
+            values.record(&mut RowsVisitor(&mut timing.rows));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else {
+            return;
+        };
+        let target = span.metadata().target();
+        let module = target.strip_prefix("intent_engine::").unwrap_or(target);
+        record(
+            &format!("{}::{}", module, span.name()),
+            timing.started.elapsed(),
+            timing.rows,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // Async spans that reach SQLite are released by its connection threads
+    // through the global subscriber, so they are covered by the CLI tests
+    // (tests/timing_cli_tests.rs); this checks the layer itself.
+    #[test]
+    fn test_layer_adds_up_spans_and_rows() {
+        let subscriber = tracing_subscriber::registry().with(TimingLayer::install());
+        tracing::subscriber::with_default(subscriber, || {
+            for rows in [3_u64, 4] {
+                let span = tracing::info_span!("load_rows", rows = tracing::field::Empty);
+                span.in_scope(|| {
+                    tracing::Span::current().record("rows", rows);
+                });
+            }
+        });
+        record("timing_test::manual", Duration::from_millis(5), None);
+
+        let entries = summary();
+        let (_, timing) = entries
+            .iter()
+            .find(|(op, _)| op == "timing::tests::load_rows")
+            .unwrap();
+        assert_eq!(timing.calls, 2);
+        assert_eq!(timing.rows, Some(7));
+        assert!(timing.max <= timing.total);
+        let (_, manual) = entries
+            .iter()
+            .find(|(op, _)| op == "timing_test::manual")
+            .unwrap();
+        assert_eq!(manual.rows, None);
+        assert!(manual.total >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(8020)), "8.02s");
+    }
+}
@@ -0,0 +1,521 @@
+//! Interactive terminal UI (`ie tui`)
+//!
+//! Reads the same database as the CLI and shows the task tree, the focused
+//! task and a tail of recent events. Writes from any process (CLI, dashboard,
+//! another TUI) are picked up by polling SQLite's `data_version`, so the view
+//! stays live without the dashboard running.
+
+use crate::cli_handlers::utils::{format_minutes, status_icon};
+use crate::db::models::{Event, Task};
+use crate::error::Result;
+use crate::events::EventManager;
+use crate::tasks::{TaskManager, TaskUpdate};
+use crate::workspace::WorkspaceManager;
+use ratatui::crossterm::event::{
+    self, Event as TermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// How long to wait for a key press before checking the database for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of recent events shown in the event tail
+const EVENT_TAIL: i64 = 20;
+
+const KEY_HELP: &str = "↑/↓ move  s start  d done  t todo  l log note  r refresh  q quit";
+
+/// One line of the task tree
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    pub task: Task,
+    pub depth: usize,
+}
+
+/// Flatten tasks into tree order
+///
+/// Parents come before their children; siblings are ordered by sort_order,
+/// then priority, then ID. Tasks whose parent is not in `tasks` become roots.
+pub fn build_rows(tasks: &[Task]) -> Vec<TreeRow> {
+    let ids: HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+    let mut children: HashMap<Option<i64>, Vec<&Task>> = HashMap::new();
+    for task in tasks {
+        let parent = task.parent_id.filter(|p| ids.contains(p));
+        children.entry(parent).or_default().push(task);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_key(|t| {
+            (
+                t.sort_order.is_none(),
+                t.sort_order,
+                t.priority.is_none(),
+                t.priority,
+                t.id,
+            )
+        });
+    }
+
+    let mut rows = Vec::with_capacity(tasks.len());
+    let mut stack: Vec<(&Task, usize)> = children
+        .get(&None)
+        .map(|roots| roots.iter().rev().map(|t| (*t, 0)).collect())
+        .unwrap_or_default();
+    while let Some((task, depth)) = stack.pop() {
+        rows.push(TreeRow {
+            task: task.clone(),
+            depth,
+        });
+        if let Some(kids) = children.get(&Some(task.id)) {
+            stack.extend(kids.iter().rev().map(|t| (*t, depth + 1)));
+        }
+    }
+    rows
+}
+
+pub struct App<'a> {
+    pool: &'a SqlitePool,
+    project_path: String,
+    rows: Vec<TreeRow>,
+    list: ListState,
+    focus: Option<i64>,
+    events: Vec<Event>,
+    /// Note being typed, while in log mode
+    input: Option<String>,
+    /// Outcome of the last action, shown in the status line
+    message: Option<String>,
+}
+
+impl<'a> App<'a> {
+    pub fn new(pool: &'a SqlitePool, project_path: String) -> Self {
+        Self {
+            pool,
+            project_path,
+            rows: Vec::new(),
+            list: ListState::default(),
+            focus: None,
+            events: Vec::new(),
+            input: None,
+            message: None,
+        }
+    }
+
+    /// Re-read tasks, focus and events, keeping the selection on the same task
+    pub async fn reload(&mut self) -> Result<()> {
+        let tasks = sqlx::query_as::<_, Task>(crate::sql_constants::SELECT_TASK_FULL)
+            .fetch_all(self.pool)
+            .await?;
+        let selected_id = self.selected().map(|row| row.task.id);
+        self.rows = build_rows(&tasks);
+
+        let index = selected_id
+            .and_then(|id| self.rows.iter().position(|row| row.task.id == id))
+            .or_else(|| {
+                let last = self.rows.len().checked_sub(1)?;
+                Some(self.list.selected().unwrap_or(0).min(last))
+            });
+        self.list.select(index);
+
+        self.focus = WorkspaceManager::new(self.pool)
+            .get_current_task(None)
+            .await?
+            .current_task_id;
+        self.events = EventManager::new(self.pool)
+            .list_events(None, Some(EVENT_TAIL), None, None)
+            .await?;
+        Ok(())
+    }
+
+    pub fn selected(&self) -> Option<&TreeRow> {
+        self.list.selected().and_then(|i| self.rows.get(i))
+    }
+
+    /// Apply one key press; returns `false` when the UI should exit
+    ///
+    /// Failed actions are reported in the status line rather than returned.
+    pub async fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(false);
+        }
+
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Enter => {
+                    let note = self.input.take().unwrap_or_default();
+                    if !note.trim().is_empty() {
+                        self.log_note(note.trim()).await?;
+                    }
+                },
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                },
+                KeyCode::Char(c) => input.push(c),
+                _ => {},
+            }
+            return Ok(true);
+        }
+
+        let selected_id = self.selected().map(|row| row.task.id);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+            KeyCode::Char('r') => {
+                self.message = None;
+                self.reload().await?;
+            },
+            KeyCode::Char('l') if selected_id.is_some() => self.input = Some(String::new()),
+            KeyCode::Char('s') => {
+                if let Some(id) = selected_id {
+                    let result = self
+                        .task_manager()
+                        .start_task(id, false)
+                        .await
+                        .map(|started| format!("Started #{}: {}", id, started.task.name));
+                    self.finish(result).await?;
+                }
+            },
+            KeyCode::Char('d') => {
+                if let Some(id) = selected_id {
+                    let result = self
+                        .task_manager()
+                        .done_task_by_id(id)
+                        .await
+                        .map(|done| format!("Completed #{}: {}", id, done.completed_task.name));
+                    self.finish(result).await?;
+                }
+            },
+            KeyCode::Char('t') => {
+                if let Some(id) = selected_id {
+                    let result = self
+                        .task_manager()
+                        .update_task(
+                            id,
+                            TaskUpdate {
+                                status: Some("todo"),
+                                ..Default::default()
+                            },
+                        )
+                        .await
+                        .map(|task| format!("Moved #{} back to todo: {}", id, task.name));
+                    self.finish(result).await?;
+                }
+            },
+            _ => {},
+        }
+        Ok(true)
+    }
+
+    fn task_manager(&self) -> TaskManager<'a> {
+        TaskManager::with_project_path(self.pool, self.project_path.clone())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let Some(last) = self.rows.len().checked_sub(1) else {
+            return;
+        };
+        let current = self.list.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(last);
+        self.list.select(Some(next));
+    }
+
+    async fn log_note(&mut self, note: &str) -> Result<()> {
+        let Some(id) = self.selected().map(|row| row.task.id) else {
+            return Ok(());
+        };
+        let result = EventManager::with_project_path(self.pool, self.project_path.clone())
+            .add_event(id, "note", note)
+            .await
+            .map(|_| format!("Logged note on #{}", id));
+        self.finish(result).await
+    }
+
+    /// Show the outcome of an action and refresh the view
+    async fn finish(&mut self, result: Result<String>) -> Result<()> {
+        self.message = Some(match result {
+            Ok(message) => message,
+            Err(e) => format!("Error: {}", e),
+        });
+        self.reload().await
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [main, status_line] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [tree_area, side] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(main);
+        let [detail_area, events_area] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(side);
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let is_focus = self.focus == Some(row.task.id);
+                let mut style = match row.task.status.as_str() {
+                    "done" => Style::default().fg(Color::DarkGray),
+                    "doing" => Style::default().fg(Color::Yellow),
+                    "review" => Style::default().fg(Color::Magenta),
+                    _ => Style::default(),
+                };
+                if is_focus {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                ListItem::new(Line::from(vec![
+                    Span::raw(if is_focus { "▶ " } else { "  " }),
+                    Span::raw("  ".repeat(row.depth)),
+                    Span::styled(
+                        format!(
+                            "{} #{} {}",
+                            status_icon(&row.task.status),
+                            row.task.id,
+                            row.task.name
+                        ),
+                        style,
+                    ),
+                ]))
+            })
+            .collect();
+        let tree = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Tasks ({}) ", self.rows.len())),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(tree, tree_area, &mut self.list);
+
+        frame.render_widget(
+            Paragraph::new(self.detail_lines())
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(" Details ")),
+            detail_area,
+        );
+
+        let names: HashMap<i64, &str> = self
+            .rows
+            .iter()
+            .map(|row| (row.task.id, row.task.name.as_str()))
+            .collect();
+        let event_lines: Vec<Line> = self
+            .events
+            .iter()
+            .map(|event| {
+                let summary = event.discussion_data.lines().next().unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        event
+                            .timestamp
+                            .with_timezone(&chrono::Local)
+                            .format("%m-%d %H:%M ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("[{}] ", event.log_type),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(format!(
+                        "#{} {}: {}",
+                        event.task_id,
+                        names.get(&event.task_id).copied().unwrap_or("?"),
+                        summary
+                    )),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(event_lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Recent events "),
+            ),
+            events_area,
+        );
+
+        let status = match (&self.input, &self.message) {
+            (Some(input), _) => format!("Note: {}▏ (Enter to log, Esc to cancel)", input),
+            (None, Some(message)) => format!("{}  |  {}", message, KEY_HELP),
+            (None, None) => KEY_HELP.to_string(),
+        };
+        frame.render_widget(Paragraph::new(status), status_line);
+    }
+
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let Some(row) = self.selected() else {
+            return vec![Line::from(
+                "No tasks yet. Create some with 'ie plan' or 'ie task create'.",
+            )];
+        };
+        let task = &row.task;
+        let mut lines = vec![
+            Line::styled(
+                format!("#{} {}", task.id, task.name),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(format!("Status: {}", task.status)),
+        ];
+        if let Some(priority) = task.priority {
+            lines.push(Line::from(format!("Priority: {}", priority)));
+        }
+        if let Some(minutes) = task.estimate_minutes {
+            lines.push(Line::from(format!("Estimate: {}", format_minutes(minutes))));
+        }
+        lines.push(Line::from(format!("Owner: {}", task.owner)));
+        if let Some(spec) = task.spec.as_deref().filter(|s| !s.trim().is_empty()) {
+            lines.push(Line::from(""));
+            lines.extend(spec.lines().map(|line| Line::from(line.to_string())));
+        }
+        lines
+    }
+}
+
+/// Run the terminal UI until the user quits
+pub async fn run(pool: &SqlitePool, project_path: String) -> Result<()> {
+    let mut app = App::new(pool, project_path);
+    app.reload().await?;
+
+    // data_version is per connection, so one connection is kept for polling
+    let mut watcher = pool.acquire().await?;
+    let mut version = crate::db::data_version(&mut watcher).await?;
+
+    let mut terminal = ratatui::init();
+    let result = async {
+        loop {
+            terminal.draw(|frame| app.draw(frame))?;
+
+            if tokio::task::block_in_place(|| event::poll(POLL_INTERVAL))? {
+                if let TermEvent::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && !app.handle_key(key).await? {
+                        return Ok(());
+                    }
+                }
+            }
+
+            let current = crate::db::data_version(&mut watcher).await?;
+            if current != version {
+                version = current;
+                app.reload().await?;
+            }
+        }
+    }
+    .await;
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_helpers::TestContext;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::from(KeyCode::Char(c))
+    }
+
+    #[tokio::test]
+    async fn test_build_rows_orders_tree() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let parent = task_mgr
+            .add_task("Parent", None, None, None, Some(2), None)
+            .await
+            .unwrap();
+        let urgent = task_mgr
+            .add_task("Urgent", None, None, None, Some(1), None)
+            .await
+            .unwrap();
+        let child = task_mgr
+            .add_task("Child", None, Some(parent.id), None, None, None)
+            .await
+            .unwrap();
+
+        let tasks = vec![child.clone(), parent.clone(), urgent.clone()];
+        let rows: Vec<(i64, usize)> = build_rows(&tasks)
+            .iter()
+            .map(|row| (row.task.id, row.depth))
+            .collect();
+        assert_eq!(rows, vec![(urgent.id, 0), (parent.id, 0), (child.id, 1)]);
+
+        // Without its parent, a child is shown as a root
+        let rows = build_rows(&tasks[..1]);
+        assert_eq!((rows[0].task.id, rows[0].depth), (child.id, 0));
+    }
+
+    #[tokio::test]
+    async fn test_keys_start_and_log_note() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        task_mgr
+            .add_task("First", None, None, None, None, None)
+            .await
+            .unwrap();
+        let second = task_mgr
+            .add_task("Second", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let mut app = App::new(ctx.pool(), String::new());
+        app.reload().await.unwrap();
+        assert!(app.handle_key(key('j')).await.unwrap());
+        assert_eq!(app.selected().unwrap().task.id, second.id);
+
+        app.handle_key(key('s')).await.unwrap();
+        assert_eq!(app.focus, Some(second.id));
+        assert_eq!(app.selected().unwrap().task.status, "doing");
+
+        for c in "l hi".chars() {
+            app.handle_key(key(c)).await.unwrap();
+        }
+        app.handle_key(KeyEvent::from(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.input.is_none());
+        assert_eq!(app.events.len(), 1);
+        assert_eq!(app.events[0].discussion_data, "hi");
+
+        assert!(!app.handle_key(key('q')).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_draw_shows_tree_and_details() {
+        let ctx = TestContext::new().await;
+        TaskManager::new(ctx.pool())
+            .add_task(
+                "Write the parser",
+                Some("Use a PEG grammar"),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let mut app = App::new(ctx.pool(), String::new());
+        app.reload().await.unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Write the parser"));
+        assert!(screen.contains("Use a PEG grammar"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Change feed for `ie watch`
+//!
+//! Works directly on the database rather than through the dashboard, so it
+//! sees writes from every process whether or not the dashboard is running.
+//! Tasks are diffed against the previous snapshot; events are append-only and
+//! tracked by ID. Only `normal` events are reported (see `EventVisibility`).
+
+use crate::db::models::{Event, Task};
+use crate::error::Result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+
+/// One change, serialized as a single NDJSON line
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    TaskCreated {
+        task: Task,
+    },
+    TaskUpdated {
+        task: Task,
+        /// Names of the fields that differ from the previous snapshot
+        changed: Vec<String>,
+    },
+    TaskDeleted {
+        task_id: i64,
+        name: String,
+    },
+    EventAdded {
+        event: Event,
+    },
+}
+
+pub struct Watcher<'a> {
+    pool: &'a SqlitePool,
+    /// Only report this task and its descendants
+    root: Option<i64>,
+    tasks: HashMap<i64, Task>,
+    last_event_id: i64,
+}
+
+impl<'a> Watcher<'a> {
+    /// Take the baseline snapshot; existing state is not reported
+    pub async fn new(pool: &'a SqlitePool, root: Option<i64>) -> Result<Self> {
+        if let Some(id) = root {
+            crate::tasks::TaskManager::new(pool).get_task(id).await?;
+        }
+        let last_event_id = sqlx::query_scalar::<_, i64>("SELECT COALESCE(MAX(id), 0) FROM events")
+            .fetch_one(pool)
+            .await?;
+        Ok(Self {
+            pool,
+            root,
+            tasks: load_tasks(pool).await?,
+            last_event_id,
+        })
+    }
+
+    /// Changes since the previous call (or since `new`)
+    pub async fn poll(&mut self) -> Result<Vec<Change>> {
+        let current = load_tasks(self.pool).await?;
+        let previous = std::mem::replace(&mut self.tasks, current);
+        let in_scope = |id: i64| match self.root {
+            None => true,
+            Some(root) => in_subtree(id, root, &self.tasks) || in_subtree(id, root, &previous),
+        };
+
+        let mut changes = Vec::new();
+        let mut ids: Vec<i64> = self.tasks.keys().chain(previous.keys()).copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        for id in ids.into_iter().filter(|id| in_scope(*id)) {
+            match (previous.get(&id), self.tasks.get(&id)) {
+                (None, Some(task)) => changes.push(Change::TaskCreated { task: task.clone() }),
+                (Some(old), None) => changes.push(Change::TaskDeleted {
+                    task_id: id,
+                    name: old.name.clone(),
+                }),
+                (Some(old), Some(task)) if old != task => changes.push(Change::TaskUpdated {
+                    task: task.clone(),
+                    changed: changed_fields(old, task)?,
+                }),
+                _ => {},
+            }
+        }
+
+        let events = sqlx::query_as::<_, Event>(&format!(
+            "{} AND visibility = 'normal' AND id > ? ORDER BY id",
+            crate::sql_constants::SELECT_EVENT_BASE
+        ))
+        .bind(self.last_event_id)
+        .fetch_all(self.pool)
+        .await?;
+        if let Some(last) = events.last() {
+            self.last_event_id = last.id;
+        }
+        changes.extend(
+            events
+                .into_iter()
+                .filter(|event| in_scope(event.task_id))
+                .map(|event| Change::EventAdded { event }),
+        );
+
+        Ok(changes)
+    }
+}
+
+async fn load_tasks(pool: &SqlitePool) -> Result<HashMap<i64, Task>> {
+    let tasks = sqlx::query_as::<_, Task>(crate::sql_constants::SELECT_TASK_FULL)
+        .fetch_all(pool)
+        .await?;
+    Ok(tasks.into_iter().map(|task| (task.id, task)).collect())
+}
+
+/// Whether `id` is `root` or one of its descendants in `tasks`
+fn in_subtree(id: i64, root: i64, tasks: &HashMap<i64, Task>) -> bool {
+    let mut current = Some(id);
+    // The depth bound guards against a corrupt parent cycle
+    for _ in 0..=tasks.len() {
+        match current {
+            Some(c) if c == root => return true,
+            Some(c) => current = tasks.get(&c).and_then(|t| t.parent_id),
+            None => return false,
+        }
+    }
+    false
+}
+
+fn changed_fields(old: &Task, new: &Task) -> Result<Vec<String>> {
+    let old: BTreeMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::to_value(old)?)?;
+    let new: BTreeMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::to_value(new)?)?;
+    Ok(old
+        .keys()
+        .chain(new.keys())
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventManager;
+    use crate::tasks::{TaskManager, TaskUpdate};
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_watcher_reports_task_and_event_changes() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let existing = task_mgr
+            .add_task("Existing", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let mut watcher = Watcher::new(ctx.pool(), None).await.unwrap();
+        assert!(watcher.poll().await.unwrap().is_empty());
+
+        let created = task_mgr
+            .add_task("New", None, None, None, None, None)
+            .await
+            .unwrap();
+        task_mgr
+            .update_task(
+                existing.id,
+                TaskUpdate {
+                    priority: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        EventManager::new(ctx.pool())
+            .add_event(created.id, "decision", "Use SQLite")
+            .await
+            .unwrap();
+
+        let changes = watcher.poll().await.unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(
+            &changes[0],
+            Change::TaskUpdated { task, changed } if task.id == existing.id && changed == &["priority"]
+        ));
+        assert!(matches!(&changes[1], Change::TaskCreated { task } if task.id == created.id));
+        assert!(matches!(
+            &changes[2],
+            Change::EventAdded { event } if event.discussion_data == "Use SQLite"
+        ));
+
+        task_mgr.delete_task(created.id).await.unwrap();
+        let changes = watcher.poll().await.unwrap();
+        assert_eq!(
+            changes,
+            vec![Change::TaskDeleted {
+                task_id: created.id,
+                name: "New".to_string()
+            }]
+        );
+        let json = serde_json::to_value(&changes[0]).unwrap();
+        assert_eq!(json["type"], "task_deleted");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_filters_to_subtree() {
+        let ctx = TestContext::new().await;
+        let task_mgr = TaskManager::new(ctx.pool());
+        let root = task_mgr
+            .add_task("Root", None, None, None, None, None)
+            .await
+            .unwrap();
+        let other = task_mgr
+            .add_task("Other", None, None, None, None, None)
+            .await
+            .unwrap();
+
+        let mut watcher = Watcher::new(ctx.pool(), Some(root.id)).await.unwrap();
+        let child = task_mgr
+            .add_task("Child", None, Some(root.id), None, None, None)
+            .await
+            .unwrap();
+        let events = EventManager::new(ctx.pool());
+        events
+            .add_event(other.id, "note", "elsewhere")
+            .await
+            .unwrap();
+        events.add_event(child.id, "note", "inside").await.unwrap();
+
+        let changes = watcher.poll().await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(&changes[0], Change::TaskCreated { task } if task.id == child.id));
+        assert!(matches!(
+            &changes[1],
+            Change::EventAdded { event } if event.discussion_data == "inside"
+        ));
+
+        assert!(Watcher::new(ctx.pool(), Some(9999)).await.is_err());
+    }
+}
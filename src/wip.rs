@@ -0,0 +1,301 @@
+//! Work-in-progress limits
+//!
+//! Any number of tasks may be in doing at once, so an agent can easily put
+//! thirty of them in flight. config.toml caps how many are in doing, across
+//! the project and below given tasks:
+//!
+//! ```toml
+//! [tasks]
+//! capacity_limit = 5  # tasks in doing in the whole project
+//!
+//! [wip.subtrees]
+//! 12 = 2              # tasks in doing below task #12
+//! ```
+//!
+//! `wip.max_doing` is still read as a deprecated spelling of
+//! `tasks.capacity_limit`.
+//!
+//! `start_task` and `PlanExecutor` refuse to start a task past a limit with
+//! `IntentError::WipLimitExceeded`, unless told to go over it
+//! (`ie task start --over-wip`, `"ignore_wip_limits": true` in a plan).
+//! `pick_next_tasks` only starts as many tasks as the limits leave room for, and
+//! `claim_next` only starts todo tasks below the project-wide limit.
+//! Per-parent limits on direct children are the `max_doing_per_parent`
+//! policy (see `crate::policy`).
+
+use crate::config::ProjectConfig;
+use crate::error::{IntentError, Result};
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+
+/// A limit that applies to a task, with the tasks in doing within it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WipUsage {
+    /// Task whose subtree is limited; None for the project-wide limit
+    pub root_id: Option<i64>,
+    pub limit: usize,
+    pub doing: usize,
+}
+
+/// Tasks a batch has admitted so far, per limit
+#[derive(Debug, Default)]
+pub struct WipBatch {
+    admitted: HashMap<Option<i64>, usize>,
+}
+
+/// The WIP limits of a project
+#[derive(Debug, Clone, Default)]
+pub struct WipLimits {
+    /// Most tasks in doing across the project
+    max_doing: Option<usize>,
+    /// Most tasks in doing below a task, by task ID
+    subtrees: BTreeMap<i64, usize>,
+}
+
+impl WipLimits {
+    /// Limits declared in a project's config
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        Self {
+            max_doing: config.tasks.capacity_limit,
+            subtrees: config.wip.subtrees.clone(),
+        }
+    }
+
+    /// Limits of the project whose database `pool` is
+    pub fn for_pool(pool: &SqlitePool) -> Result<Self> {
+        Ok(Self::from_config(&ProjectConfig::for_pool(pool)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_doing.is_none() && self.subtrees.is_empty()
+    }
+
+    /// Limits that apply to `task_id`: the project's and those of its
+    /// ancestors, with the tasks in doing within each
+    pub async fn usage_in_tx(
+        &self,
+        conn: &mut SqliteConnection,
+        task_id: i64,
+    ) -> Result<Vec<WipUsage>> {
+        let mut usage = Vec::new();
+        if let Some(limit) = self.max_doing {
+            let doing: i64 = sqlx::query_scalar(crate::sql_constants::COUNT_TASKS_DOING)
+                .fetch_one(&mut *conn)
+                .await?;
+            usage.push(WipUsage {
+                root_id: None,
+                limit,
+                doing: doing as usize,
+            });
+        }
+        if self.subtrees.is_empty() {
+            return Ok(usage);
+        }
+
+        let ancestors: Vec<i64> = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE ancestors(id) AS (
+                SELECT parent_id FROM tasks WHERE id = ? AND parent_id IS NOT NULL
+                UNION
+                SELECT t.parent_id FROM tasks t
+                JOIN ancestors a ON t.id = a.id
+                WHERE t.parent_id IS NOT NULL
+            )
+            SELECT id FROM ancestors
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        for root_id in ancestors {
+            let Some(&limit) = self.subtrees.get(&root_id) else {
+                continue;
+            };
+            let doing: i64 = sqlx::query_scalar(
+                r#"
+                WITH RECURSIVE subtree(id) AS (
+                    SELECT id FROM tasks WHERE parent_id = ?
+                    UNION ALL
+                    SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id
+                )
+                SELECT COUNT(*) FROM tasks
+                WHERE status = 'doing' AND id IN (SELECT id FROM subtree)
+                "#,
+            )
+            .bind(root_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            usage.push(WipUsage {
+                root_id: Some(root_id),
+                limit,
+                doing: doing as usize,
+            });
+        }
+        Ok(usage)
+    }
+
+    /// Check the start of `task_id`
+    ///
+    /// Call after moving the task to doing, inside the same transaction; the
+    /// first exceeded limit is returned as `IntentError::WipLimitExceeded`.
+    pub async fn check_in_tx(&self, conn: &mut SqliteConnection, task_id: i64) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        for usage in self.usage_in_tx(conn, task_id).await? {
+            if usage.doing > usage.limit {
+                return Err(IntentError::WipLimitExceeded {
+                    task_id,
+                    root_id: usage.root_id,
+                    limit: usage.limit,
+                    doing: usage.doing - 1,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the todo task `task_id` can start along with the tasks
+    /// `batch` already admitted; if so it is admitted too
+    pub async fn admit_in_tx(
+        &self,
+        conn: &mut SqliteConnection,
+        batch: &mut WipBatch,
+        task_id: i64,
+    ) -> Result<bool> {
+        if self.is_empty() {
+            return Ok(true);
+        }
+        let usage = self.usage_in_tx(conn, task_id).await?;
+        let fits = usage
+            .iter()
+            .all(|u| u.doing + batch.admitted.get(&u.root_id).copied().unwrap_or(0) < u.limit);
+        if fits {
+            for u in &usage {
+                *batch.admitted.entry(u.root_id).or_default() += 1;
+            }
+        }
+        Ok(fits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::{PlanExecutor, PlanRequest};
+    use crate::tasks::TaskManager;
+    use crate::test_utils::test_helpers::TestContext;
+
+    #[tokio::test]
+    async fn test_wip_limits_are_enforced() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let parent = tasks
+            .add_task("Parent", None, None, None, None, None)
+            .await
+            .unwrap();
+        let mut children = Vec::new();
+        for name in ["A", "B"] {
+            children.push(
+                tasks
+                    .add_task(name, None, Some(parent.id), None, None, None)
+                    .await
+                    .unwrap()
+                    .id,
+            );
+        }
+        let outside = tasks
+            .add_task("Outside", None, None, None, None, None)
+            .await
+            .unwrap();
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine/config.toml"),
+            format!(
+                "[tasks]\ncapacity_limit = 3\n\n[wip.subtrees]\n{} = 1\n",
+                parent.id
+            ),
+        )
+        .unwrap();
+
+        tasks.start_task(children[0], false).await.unwrap();
+        let err = tasks.start_task(children[1], false).await.unwrap_err();
+        match err {
+            IntentError::WipLimitExceeded {
+                task_id,
+                root_id,
+                limit,
+                doing,
+            } => {
+                assert_eq!(task_id, children[1]);
+                assert_eq!(root_id, Some(parent.id));
+                assert_eq!((limit, doing), (1, 1));
+            },
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert_eq!(tasks.get_task(children[1]).await.unwrap().status, "todo");
+
+        // The parent itself is not below its own limit
+        tasks.start_task(parent.id, false).await.unwrap();
+        tasks.start_task(outside.id, false).await.unwrap();
+
+        // Going over on purpose
+        tasks
+            .start_task_over_wip_limit(children[1], false)
+            .await
+            .unwrap();
+
+        // Plans refuse too, unless they ignore the limits
+        let plan = |ignore: bool| -> PlanRequest {
+            serde_json::from_value(serde_json::json!({
+                "tasks": [{"name": "Extra", "status": "doing", "spec": "go"}],
+                "ignore_wip_limits": ignore,
+            }))
+            .unwrap()
+        };
+        let result = PlanExecutor::new(pool).execute(&plan(false)).await.unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("WIP limit of the project: 4 tasks already in doing (limit 3)"));
+        let result = PlanExecutor::new(pool).execute(&plan(true)).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+    }
+
+    #[tokio::test]
+    async fn test_pick_next_tasks_leaves_room_for_limits() {
+        let ctx = TestContext::new().await;
+        let pool = ctx.pool();
+        let tasks = TaskManager::new(pool);
+        let parent = tasks
+            .add_task("Parent", None, None, None, None, None)
+            .await
+            .unwrap();
+        for name in ["A", "B"] {
+            tasks
+                .add_task(name, None, Some(parent.id), None, None, None)
+                .await
+                .unwrap();
+        }
+        tasks
+            .add_task("Other", None, None, None, None, None)
+            .await
+            .unwrap();
+        std::fs::write(
+            ctx._temp_dir.path().join(".intent-engine/config.toml"),
+            format!(
+                "[tasks]\ncapacity_limit = 2\n\n[wip.subtrees]\n{} = 1\n",
+                parent.id
+            ),
+        )
+        .unwrap();
+
+        let picked = tasks.pick_next_tasks(10, 10).await.unwrap();
+        let names: Vec<&str> = picked.iter().map(|t| t.name.as_str()).collect();
+        // Parent and A fill the project limit; B would be a second below Parent
+        assert_eq!(names, vec!["Parent", "A"]);
+        assert!(tasks.pick_next_tasks(10, 10).await.unwrap().is_empty());
+    }
+}
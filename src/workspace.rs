@@ -56,8 +56,9 @@ impl<'a> WorkspaceManager<'a> {
         .await?
         .flatten();
 
-        // Update last_active_at if session exists
-        if current_task_id.is_some() {
+        // Update last_active_at if session exists (bookkeeping only, so
+        // skipped under `ie --read-only`)
+        if current_task_id.is_some() && !crate::project::is_read_only() {
             sqlx::query(
                 "UPDATE sessions SET last_active_at = datetime('now') WHERE session_id = ?",
             )
@@ -67,13 +68,15 @@ impl<'a> WorkspaceManager<'a> {
         }
 
         let task = if let Some(id) = current_task_id {
-            sqlx::query_as::<_, Task>(
+            // A private focus reads as no task outside the private scope
+            sqlx::query_as::<_, Task>(&format!(
                 r#"
-                SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata
+                SELECT id, parent_id, name, spec, status, complexity, priority, first_todo_at, first_doing_at, first_done_at, active_form, owner, metadata, sort_order, estimate_minutes, visibility, blocked_reason
                 FROM tasks
-                WHERE id = ?
+                WHERE id = ? AND {}
                 "#,
-            )
+                crate::project::task_scope().sql_filter()
+            ))
             .bind(id)
             .fetch_optional(self.pool)
             .await?
@@ -98,11 +101,13 @@ impl<'a> WorkspaceManager<'a> {
         let session_id = resolve_session_id(session_id);
 
         // Check if task exists
-        let task_exists: bool =
-            sqlx::query_scalar::<_, bool>(crate::sql_constants::CHECK_TASK_EXISTS)
-                .bind(task_id)
-                .fetch_one(self.pool)
-                .await?;
+        let task_exists: bool = sqlx::query_scalar::<_, bool>(&format!(
+            "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND {})",
+            crate::project::task_scope().sql_filter()
+        ))
+        .bind(task_id)
+        .fetch_one(self.pool)
+        .await?;
 
         if !task_exists {
             return Err(IntentError::TaskNotFound(task_id));
@@ -148,6 +153,7 @@ impl<'a> WorkspaceManager<'a> {
         ))
         .execute(self.pool)
         .await?;
+        self.delete_orphan_focus_state().await?;
 
         Ok(result.rows_affected())
     }
@@ -167,9 +173,30 @@ impl<'a> WorkspaceManager<'a> {
         .bind(max_sessions)
         .execute(self.pool)
         .await?;
+        self.delete_orphan_focus_state().await?;
 
         Ok(result.rows_affected())
     }
+
+    /// Drop the focus stacks and timeboxes of sessions that no longer exist
+    async fn delete_orphan_focus_state(&self) -> Result<()> {
+        for prefix in [
+            crate::focus::FOCUS_STACK_KEY_PREFIX,
+            crate::focus::FOCUS_SESSION_KEY_PREFIX,
+        ] {
+            sqlx::query(
+                r#"
+                DELETE FROM workspace_state
+                WHERE substr(key, 1, length(?1)) = ?1
+                  AND substr(key, length(?1) + 1) NOT IN (SELECT session_id FROM sessions)
+                "#,
+            )
+            .bind(prefix)
+            .execute(self.pool)
+            .await?;
+        }
+        Ok(())
+    }
 }
 
 impl crate::backend::WorkspaceBackend for WorkspaceManager<'_> {
@@ -0,0 +1,139 @@
+//! Integration tests for `ie export archive|anonymized` / `ie import`
+
+mod common;
+
+use serde_json::Value;
+
+fn create_task(dir: &std::path::Path, name: &str, extra: &[&str]) {
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "create", name])
+        .args(extra)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_export_import_roundtrip_with_conflicts() {
+    let source = common::setup_test_env();
+    create_task(source.path(), "Auth", &["--description", "JWT auth"]);
+    create_task(source.path(), "Login form", &["--parent", "1"]);
+
+    let archive_path = source.path().join("archive.json");
+    common::ie_command_with_project_dir(source.path())
+        .args(["export", "archive", "-o"])
+        .arg(&archive_path)
+        .assert()
+        .success();
+
+    let target = common::setup_test_env();
+    create_task(target.path(), "Auth", &["--description", "OAuth"]);
+
+    // Dry run reports the conflict and writes nothing
+    let output = common::ie_command_with_project_dir(target.path())
+        .arg("import")
+        .arg(&archive_path)
+        .args(["--dry-run", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["applied"], false);
+    assert_eq!(report["unresolved"], 1);
+    assert_eq!(report["conflicts"][0]["kind"], "content_mismatch");
+    assert_eq!(report["conflicts"][0]["fields"][0], "spec");
+
+    // Without a resolution the import is refused
+    common::ie_command_with_project_dir(target.path())
+        .arg("import")
+        .arg(&archive_path)
+        .assert()
+        .failure();
+
+    let output = common::ie_command_with_project_dir(target.path())
+        .arg("import")
+        .arg(&archive_path)
+        .args(["--resolve", "1=overwrite", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["applied"], true);
+    assert_eq!(report["tasks_updated"], 1);
+    assert_eq!(report["tasks_created"], 1);
+
+    let output = common::ie_command_with_project_dir(target.path())
+        .args(["task", "get", "2", "--format", "json"])
+        .output()
+        .unwrap();
+    let child: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(child["task"]["name"], "Login form");
+    assert_eq!(child["task"]["parent_id"], 1);
+}
+
+#[test]
+fn test_export_ndjson_to_stdout_imports_back() {
+    let source = common::setup_test_env();
+    create_task(source.path(), "Auth", &["--description", "JWT auth"]);
+    create_task(source.path(), "Login form", &["--parent", "1"]);
+
+    let output = common::ie_command_with_project_dir(source.path())
+        .args(["export", "archive", "--format", "ndjson"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let records: Vec<Value> = output
+        .stdout
+        .split(|&b| b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    let types: Vec<&str> = records
+        .iter()
+        .map(|record| record["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, vec!["header", "task", "task"]);
+    assert_eq!(records[2]["name"], "Login form");
+
+    let archive_path = source.path().join("archive.ndjson");
+    std::fs::write(&archive_path, &output.stdout).unwrap();
+    let target = common::setup_test_env();
+    let output = common::ie_command_with_project_dir(target.path())
+        .arg("import")
+        .arg(&archive_path)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["tasks_created"], 2);
+}
+
+#[test]
+fn test_export_anonymized_writes_readable_copy() {
+    let source = common::setup_test_env();
+    create_task(
+        source.path(),
+        "Secret project",
+        &["--description", "Top secret"],
+    );
+
+    let output = source.path().join("repro.db");
+    let result = common::ie_command_with_project_dir(source.path())
+        .args(["export", "anonymized", "--format", "json", "-o"])
+        .arg(&output)
+        .output()
+        .unwrap();
+    assert!(result.status.success(), "{:?}", result);
+    let report: Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(report["tasks"], 1);
+
+    let raw = std::fs::read(&output).unwrap();
+    assert!(!raw.windows(6).any(|w| w == b"Secret"));
+
+    // A second export to the same file is refused
+    common::ie_command_with_project_dir(source.path())
+        .args(["export", "anonymized", "-o"])
+        .arg(&output)
+        .assert()
+        .failure();
+}
@@ -0,0 +1,326 @@
+//! Conformance tests shared by the storage backends
+//!
+//! Each scenario is written once against the backend traits and run on
+//! SQLite always, on Neo4j with `--features neo4j-tests` (see
+//! neo4j_integration_tests.rs for the required environment), and on
+//! Postgres with `--features postgres-tests` and `POSTGRES_URL` set.
+
+mod test_helpers_rewrite;
+
+use intent_engine::backend::{Backend, PlanBackend, TaskBackend, WorkspaceBackend};
+use intent_engine::cli_handlers::execute_under_focus;
+use intent_engine::error::IntentError;
+use intent_engine::plan::PlanRequest;
+
+fn plan(value: serde_json::Value) -> PlanRequest {
+    serde_json::from_value(serde_json::json!({ "tasks": value })).unwrap()
+}
+
+/// A task cannot start while a todo/doing dependency blocks it
+async fn start_waits_for_dependencies(tasks: &impl TaskBackend) {
+    let blocking = tasks
+        .add_task("Schema", None, None, Some("human"), None, None)
+        .await
+        .unwrap();
+    let blocked = tasks
+        .add_task("API", None, None, Some("human"), None, None)
+        .await
+        .unwrap();
+    tasks.add_dependency(blocking.id, blocked.id).await.unwrap();
+
+    match tasks.start_task(blocked.id, false).await {
+        Err(IntentError::TaskBlocked {
+            task_id,
+            blocking_task_ids,
+        }) => {
+            assert_eq!(task_id, blocked.id);
+            assert_eq!(blocking_task_ids, vec![blocking.id]);
+        },
+        other => panic!("expected TaskBlocked, got {:?}", other.map(|t| t.task.id)),
+    }
+
+    tasks.start_task(blocking.id, false).await.unwrap();
+    tasks.done_task_by_id(blocking.id).await.unwrap();
+    let started = tasks.start_task(blocked.id, false).await.unwrap();
+    assert_eq!(started.task.status, "doing");
+}
+
+/// pick_next passes over blocked tasks, even higher-priority ones
+async fn pick_next_skips_blocked(tasks: &impl TaskBackend) {
+    let blocked = tasks
+        .add_task("Deploy", None, None, Some("human"), Some(1), None)
+        .await
+        .unwrap();
+    let blocking = tasks
+        .add_task("Build", None, None, Some("human"), Some(3), None)
+        .await
+        .unwrap();
+    tasks.add_dependency(blocking.id, blocked.id).await.unwrap();
+
+    let next = tasks.pick_next().await.unwrap();
+    assert_eq!(next.task.map(|t| t.id), Some(blocking.id));
+
+    tasks.start_task(blocking.id, false).await.unwrap();
+    tasks.done_task_by_id(blocking.id).await.unwrap();
+    let next = tasks.pick_next().await.unwrap();
+    assert_eq!(next.task.map(|t| t.id), Some(blocked.id));
+}
+
+/// Plan dependencies are created once, and cycles through stored ones are rejected
+async fn plan_dependencies_idempotent_and_acyclic(plans: &impl PlanBackend) {
+    let request = plan(serde_json::json!([
+        {"name": "Design"},
+        {"name": "Implement", "depends_on": ["Design"]},
+    ]));
+
+    let first = plans.execute(&request).await.unwrap();
+    assert!(first.success, "{:?}", first.error);
+    assert_eq!(first.dependency_count, 1);
+
+    let again = plans.execute(&request).await.unwrap();
+    assert!(again.success, "{:?}", again.error);
+    assert_eq!(again.dependency_count, 0);
+
+    let cyclic = plans
+        .execute(&plan(serde_json::json!([
+            {"name": "Design", "depends_on": ["Implement"]},
+            {"name": "Implement"},
+        ])))
+        .await
+        .unwrap();
+    assert!(!cyclic.success);
+    let error = cyclic.error.unwrap();
+    assert!(
+        error.starts_with("Circular dependency detected with existing dependencies"),
+        "{}",
+        error
+    );
+    assert!(error.contains("Design (#") && error.contains("Implement (#"));
+}
+
+/// Plans go through the umbrella trait and land under the focused task
+async fn plan_under_focus(backend: &impl Backend) {
+    let release = backend
+        .tasks()
+        .add_task("Release", None, None, Some("human"), None, None)
+        .await
+        .unwrap();
+    backend
+        .workspace()
+        .set_current_task(release.id, None)
+        .await
+        .unwrap();
+
+    let result = execute_under_focus(backend, &plan(serde_json::json!([{"name": "Changelog"}])))
+        .await
+        .unwrap();
+    assert!(result.success, "{:?}", result.error);
+
+    let children = backend.tasks().get_children(release.id).await.unwrap();
+    let names: Vec<&str> = children.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Changelog"]);
+}
+
+mod sqlite {
+    use super::test_helpers_rewrite::TestDb;
+    use intent_engine::backend::{Backend, SearchBackend};
+    use intent_engine::plan::PlanExecutor;
+    use intent_engine::project::ProjectContext;
+    use intent_engine::tasks::TaskManager;
+
+    #[tokio::test]
+    async fn start_waits_for_dependencies() {
+        let db = TestDb::new().await;
+        super::start_waits_for_dependencies(&TaskManager::new(db.pool())).await;
+    }
+
+    #[tokio::test]
+    async fn pick_next_skips_blocked() {
+        let db = TestDb::new().await;
+        super::pick_next_skips_blocked(&TaskManager::new(db.pool())).await;
+    }
+
+    #[tokio::test]
+    async fn plan_dependencies_idempotent_and_acyclic() {
+        let db = TestDb::new().await;
+        super::plan_dependencies_idempotent_and_acyclic(&PlanExecutor::new(db.pool())).await;
+    }
+
+    #[tokio::test]
+    async fn plan_under_focus() {
+        let db = TestDb::new().await;
+        let root = db._temp_dir.path().to_path_buf();
+        let ctx = ProjectContext {
+            db_path: root.join(".intent-engine").join("project.db"),
+            root,
+            pool: db.pool.clone(),
+        };
+        assert_eq!(ctx.name(), "sqlite");
+        super::plan_under_focus(&ctx).await;
+
+        let found = SearchBackend::search(&ctx.search(), "Changelog", true, false, None, None)
+            .await
+            .unwrap();
+        assert_eq!(found.total_tasks, 1);
+    }
+}
+
+#[cfg(feature = "neo4j-tests")]
+mod neo4j {
+    use intent_engine::neo4j::{Neo4jContext, Neo4jPlanExecutor, Neo4jTaskManager};
+    use neo4rs::Graph;
+
+    /// Connect to Neo4j with a fresh project, as in neo4j_integration_tests.rs
+    async fn setup() -> (Graph, String) {
+        let uri = std::env::var("NEO4J_URI").expect("NEO4J_URI must be set for neo4j-tests");
+        let password =
+            std::env::var("NEO4J_PASSWORD").expect("NEO4J_PASSWORD must be set for neo4j-tests");
+        let user = std::env::var("NEO4J_USER").unwrap_or_else(|_| "neo4j".into());
+        let graph = Graph::new(&uri, &user, &password)
+            .await
+            .expect("Failed to connect to Neo4j");
+
+        let project_id = format!(
+            "conformance-{}-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+            std::process::id()
+        );
+        intent_engine::neo4j::schema::ensure_schema(&graph, &project_id)
+            .await
+            .expect("Schema init failed");
+        (graph, project_id)
+    }
+
+    async fn teardown(graph: &Graph, project_id: &str) {
+        graph
+            .run(
+                neo4rs::query("MATCH (n {project_id: $pid}) DETACH DELETE n")
+                    .param("pid", project_id.to_string()),
+            )
+            .await
+            .expect("Teardown failed");
+    }
+
+    #[tokio::test]
+    async fn neo4j_start_waits_for_dependencies() {
+        let (graph, pid) = setup().await;
+        super::start_waits_for_dependencies(&Neo4jTaskManager::new(graph.clone(), pid.clone()))
+            .await;
+        teardown(&graph, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn neo4j_pick_next_skips_blocked() {
+        let (graph, pid) = setup().await;
+        super::pick_next_skips_blocked(&Neo4jTaskManager::new(graph.clone(), pid.clone())).await;
+        teardown(&graph, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn neo4j_plan_dependencies_idempotent_and_acyclic() {
+        let (graph, pid) = setup().await;
+        super::plan_dependencies_idempotent_and_acyclic(&Neo4jPlanExecutor::new(
+            graph.clone(),
+            pid.clone(),
+        ))
+        .await;
+        teardown(&graph, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn neo4j_plan_under_focus() {
+        let (graph, pid) = setup().await;
+        super::plan_under_focus(&Neo4jContext {
+            graph: graph.clone(),
+            project_id: pid.clone(),
+        })
+        .await;
+        teardown(&graph, &pid).await;
+    }
+}
+
+#[cfg(feature = "postgres-tests")]
+mod postgres {
+    use intent_engine::backend::{Backend, SearchBackend};
+    use intent_engine::postgres::{PostgresContext, PostgresPlanExecutor, PostgresTaskManager};
+    use sqlx::PgPool;
+
+    /// Connect to Postgres with a fresh project
+    async fn setup() -> (PgPool, String) {
+        let url =
+            std::env::var("POSTGRES_URL").expect("POSTGRES_URL must be set for postgres-tests");
+        let pool = intent_engine::postgres::connect_pool(&url)
+            .await
+            .expect("Failed to connect to Postgres");
+        intent_engine::postgres::schema::migrate(&pool)
+            .await
+            .expect("Migration failed");
+
+        let project_id = format!(
+            "conformance-{}-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+            std::process::id()
+        );
+        (pool, project_id)
+    }
+
+    /// Events and dependencies go with their tasks
+    async fn teardown(pool: &PgPool, project_id: &str) {
+        for table in ["sessions", "tasks", "counters"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE project_id = $1"))
+                .bind(project_id)
+                .execute(pool)
+                .await
+                .expect("Teardown failed");
+        }
+    }
+
+    #[tokio::test]
+    async fn postgres_start_waits_for_dependencies() {
+        let (pool, pid) = setup().await;
+        super::start_waits_for_dependencies(&PostgresTaskManager::new(pool.clone(), pid.clone()))
+            .await;
+        teardown(&pool, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn postgres_pick_next_skips_blocked() {
+        let (pool, pid) = setup().await;
+        super::pick_next_skips_blocked(&PostgresTaskManager::new(pool.clone(), pid.clone())).await;
+        teardown(&pool, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn postgres_plan_dependencies_idempotent_and_acyclic() {
+        let (pool, pid) = setup().await;
+        super::plan_dependencies_idempotent_and_acyclic(&PostgresPlanExecutor::new(
+            pool.clone(),
+            pid.clone(),
+        ))
+        .await;
+        teardown(&pool, &pid).await;
+    }
+
+    #[tokio::test]
+    async fn postgres_plan_under_focus() {
+        let (pool, pid) = setup().await;
+        let ctx = PostgresContext {
+            pool: pool.clone(),
+            project_id: pid.clone(),
+        };
+        assert_eq!(ctx.name(), "postgres");
+        super::plan_under_focus(&ctx).await;
+
+        let found = SearchBackend::search(&ctx.search(), "Changelog", true, false, None, None)
+            .await
+            .unwrap();
+        assert_eq!(found.total_tasks, 1);
+        teardown(&pool, &pid).await;
+    }
+}
@@ -0,0 +1,41 @@
+/// Tests for `ie task check` / `ie task uncheck`
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_check_and_uncheck_items() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Release", "checklist": ["Tag", "Publish"]}]}"#)
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "check", "1", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(1/2)"))
+        .stdout(predicate::str::contains("1. [x] Tag"))
+        .stdout(predicate::str::contains("2. [ ] Publish"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "check", "1", "3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no checklist item 3"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "uncheck", "1", "1", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"checked\": false"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "get", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checklist: 0/2 (0%)"));
+}
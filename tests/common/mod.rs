@@ -71,7 +71,8 @@ pub fn ie_command() -> Command {
     cmd.env("HOME", "/nonexistent") // Prevent fallback to home on Unix
         .env("USERPROFILE", "/nonexistent") // Prevent fallback to home on Windows
         .env("INTENT_ENGINE_NO_HOME_FALLBACK", "1") // Additional flag to prevent home fallback
-        .env("INTENT_ENGINE_NO_DASHBOARD_AUTOSTART", "1"); // Disable Dashboard auto-start in tests
+        .env("INTENT_ENGINE_NO_DASHBOARD_AUTOSTART", "1") // Disable Dashboard auto-start in tests
+        .env("IE_LOCALE", "en"); // English messages whatever the host locale
     cmd
 }
 
@@ -0,0 +1,39 @@
+/// Tests for `ie config` on settings kept in config.toml
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_config_edits_capacity_and_policy_in_config_toml() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    let config = dir.join(".intent-engine").join("config.toml");
+    std::fs::write(&config, "# limits\n[policy]\nmin_spec_chars = 10\n").unwrap();
+    let ie = |args: &[&str]| common::ie_command_with_project_dir(dir).args(args).assert();
+
+    ie(&["config", "set", "tasks.capacity_limit", "2"]).success();
+    ie(&["config", "set", "policy.max_doing_per_parent", "1"]).success();
+    ie(&["config", "set", "policy.max_doing_per_parent", "0"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "policy.max_doing_per_parent: must be at least 1",
+        ));
+
+    let text = std::fs::read_to_string(&config).unwrap();
+    assert!(text.starts_with("# limits"));
+    assert!(text.contains("capacity_limit = 2"));
+    assert!(text.contains("max_doing_per_parent = 1"));
+
+    ie(&["config", "get", "tasks.capacity_limit"])
+        .success()
+        .stdout(predicate::str::contains("tasks.capacity_limit = 2"));
+    ie(&["config", "list", "--prefix", "policy."])
+        .success()
+        .stdout(predicate::str::contains("policy.max_doing_per_parent = 1"))
+        .stdout(predicate::str::contains("policy.min_spec_chars = 10"));
+
+    ie(&["config", "unset", "tasks.capacity_limit"]).success();
+    assert!(!std::fs::read_to_string(&config)
+        .unwrap()
+        .contains("capacity_limit"));
+}
@@ -92,6 +92,13 @@ impl DashboardTestServer {
         Ok(client.post(&url).json(&body).send()?)
     }
 
+    /// Make a PUT request to the server
+    fn put(&self, path: &str, body: serde_json::Value) -> Result<reqwest::blocking::Response> {
+        let url = format!("{}{}", self.base_url(), path);
+        let client = reqwest::blocking::Client::new();
+        Ok(client.put(&url).json(&body).send()?)
+    }
+
     /// Make a PATCH request to the server
     fn patch(&self, path: &str, body: serde_json::Value) -> Result<reqwest::blocking::Response> {
         let url = format!("{}{}", self.base_url(), path);
@@ -290,9 +297,17 @@ fn test_dashboard_events() -> Result<()> {
     assert_eq!(list_events_response.status(), 200);
 
     let events: serde_json::Value = list_events_response.json()?;
-    let event_list = events["data"].as_array().unwrap();
+    let event_list = events["data"]["events"].as_array().unwrap();
     assert!(!event_list.is_empty());
     assert_eq!(event_list[0]["log_type"], "decision");
+    assert_eq!(events["data"]["total_count"], 1);
+    assert_eq!(events["data"]["has_more"], false);
+
+    // Type filter
+    let filtered: serde_json::Value = server
+        .get(&format!("/api/tasks/{}/events?type=note", task_id))?
+        .json()?;
+    assert_eq!(filtered["data"]["events"].as_array().unwrap().len(), 0);
 
     Ok(())
 }
@@ -483,3 +498,147 @@ fn test_dashboard_markdown_xss_protection() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_dashboard_global_overview() -> Result<()> {
+    let host_dir = tempfile::tempdir()?;
+    init_project(host_dir.path())?;
+    let other_dir = tempfile::tempdir()?;
+    init_project(other_dir.path())?;
+
+    // Give the second project a focused task
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let pool = create_pool(&other_dir.path().join(".intent-engine/project.db")).await?;
+        let task_mgr = intent_engine::tasks::TaskManager::new(&pool);
+        task_mgr
+            .add_task("Other task", Some("spec"), None, None, None, None)
+            .await?;
+        task_mgr.start_task(1, false).await?;
+        Ok::<(), anyhow::Error>(())
+    })?;
+
+    let server = DashboardTestServer::start(3079, host_dir.path().to_path_buf())?;
+
+    // Registered after startup: picked up on the next request
+    let registry_dir = server._temp_home.path().join(".intent-engine");
+    std::fs::create_dir_all(&registry_dir)?;
+    std::fs::write(
+        registry_dir.join("projects.json"),
+        serde_json::to_string(&json!({
+            "projects": [{
+                "path": other_dir.path().to_string_lossy(),
+                "last_accessed": "2026-01-01T00:00:00Z"
+            }]
+        }))?,
+    )?;
+
+    let response = server.get("/api/global/overview")?;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json()?;
+    let projects = body["data"].as_array().unwrap();
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0]["is_host"], true);
+    assert_eq!(projects[0]["is_active"], true);
+
+    let other = &projects[1];
+    assert_eq!(other["task_counts"]["doing"], 1);
+    assert_eq!(other["focus"][0]["task_name"], "Other task");
+
+    let response = server.get("/api/global/tasks?status=doing")?;
+    let body: serde_json::Value = response.json()?;
+    let tasks = body["data"].as_array().unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["name"], "Other task");
+    assert_eq!(tasks[0]["project_path"], other["path"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_dashboard_settings_validation_and_audit() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    init_project(temp_dir.path())?;
+    let server = DashboardTestServer::start(3080, temp_dir.path().to_path_buf())?;
+
+    let response = server.get("/api/settings")?;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json()?;
+    let desktop = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["key"] == "notify.desktop")
+        .unwrap()
+        .clone();
+    assert_eq!(desktop["kind"], "bool");
+    assert_eq!(desktop["value"], serde_json::Value::Null);
+
+    // One bad value rejects the whole batch
+    let response = server.put(
+        "/api/settings",
+        json!({ "values": { "notify.desktop": "true", "llm.endpoint": "not a url" } }),
+    )?;
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json()?;
+    assert_eq!(body["code"], "INVALID_SETTINGS");
+    assert_eq!(body["error_code"], "IE0103");
+    assert_eq!(body["details"]["errors"].as_array().unwrap().len(), 1);
+
+    let response = server.put(
+        "/api/settings",
+        json!({ "values": { "notify.desktop": "on" } }),
+    )?;
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json()?;
+    assert_eq!(body["data"]["changes"][0]["new_value"], "true");
+
+    let response = server.get("/api/settings/audit")?;
+    let body: serde_json::Value = response.json()?;
+    let audit = body["data"].as_array().unwrap();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0]["key"], "notify.desktop");
+    assert_eq!(audit[0]["source"], "dashboard");
+
+    Ok(())
+}
+
+#[test]
+fn test_dashboard_settings_edit_config_toml() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    init_project(temp_dir.path())?;
+    let config = temp_dir.path().join(".intent-engine").join("config.toml");
+    let server = DashboardTestServer::start(3081, temp_dir.path().to_path_buf())?;
+
+    let response = server.put(
+        "/api/settings",
+        json!({ "values": { "tasks.capacity_limit": "0" } }),
+    )?;
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json()?;
+    assert_eq!(body["code"], "INVALID_SETTINGS");
+    assert!(!config.exists());
+
+    let response = server.put(
+        "/api/settings",
+        json!({ "values": { "tasks.capacity_limit": "4", "policy.min_spec_chars": "20" } }),
+    )?;
+    assert_eq!(response.status(), 200);
+    let text = std::fs::read_to_string(&config)?;
+    assert!(text.contains("capacity_limit = 4"));
+    assert!(text.contains("min_spec_chars = 20"));
+
+    let response = server.get("/api/settings")?;
+    let body: serde_json::Value = response.json()?;
+    let limit = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["key"] == "tasks.capacity_limit")
+        .unwrap()
+        .clone();
+    assert_eq!(limit["store"], "config_file");
+    assert_eq!(limit["value"], "4");
+
+    Ok(())
+}
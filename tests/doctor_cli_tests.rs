@@ -251,3 +251,47 @@ async fn test_doctor_corrupted_database() {
         "Should produce output when encountering corrupted database"
     );
 }
+
+#[tokio::test]
+async fn test_doctor_json_envelope() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = common::ie_command();
+    cmd.arg("init")
+        .arg("--at")
+        .arg(temp_dir.path().to_str().unwrap());
+    cmd.assert().success();
+
+    let mut cmd = common::ie_command();
+    cmd.args(["doctor", "--format", "json"])
+        .current_dir(temp_dir.path());
+    let output = cmd.assert().success();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert!(json["data"]["database_path"]
+        .as_str()
+        .unwrap()
+        .contains(".intent-engine"));
+    assert!(json["data"]["dashboard"]["running"].is_boolean());
+    assert!(json["warnings"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_doctor_reports_integrity_checks() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = common::ie_command();
+    cmd.arg("init")
+        .arg("--at")
+        .arg(temp_dir.path().to_str().unwrap());
+    cmd.assert().success();
+
+    let mut cmd = common::ie_command();
+    cmd.args(["doctor", "--fix"]).current_dir(temp_dir.path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Integrity:"))
+        .stdout(predicate::str::contains("✓ fts_index"))
+        .stdout(predicate::str::contains("✗").not());
+}
@@ -0,0 +1,98 @@
+/// Tests for `ie focus push/pop/list`
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_push_and_pop_focus() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Feature work"}, {"name": "Urgent bug"}]}"#)
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "push", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Started task #1"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "push", "2", "--over-wip"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Suspended: #1"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#1 Feature work"));
+
+    common::ie_command_with_project_dir(dir)
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Focus stack (1 interrupted"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "pop"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Back to task #1"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "pop"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("empty"));
+}
+
+#[test]
+fn test_focus_session_start_and_stop() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Parser"}]}"#)
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "start", "1"])
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "start", "--minutes", "45"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Focus session on #1 'Parser' for 45 min",
+        ));
+
+    common::ie_command_with_project_dir(dir)
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Focus session on #1 Parser"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "stop", "--note", "Tests next", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""planned_minutes": 45"#))
+        .stdout(predicate::str::contains(
+            r#""discussion_data": "Focus session: worked 0 of 45 min (stopped 45 min early)\n\nTests next""#,
+        ));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["focus", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No focus session running"));
+}
@@ -333,7 +333,7 @@ async fn test_done_removes_from_paused_pool() {
     assert_eq!(get_current_task_id(&pool).await, Some(3));
 
     // Complete Task 3
-    mgr.done_task(false).await.unwrap();
+    mgr.done_task().await.unwrap();
 
     let task_c = mgr.get_task(3).await.unwrap();
     assert_eq!(task_c.status, "done");
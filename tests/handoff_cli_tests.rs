@@ -0,0 +1,51 @@
+/// Tests for `ie handoff` and how `ie status` surfaces handoffs
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_handoff_create_list_and_status() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Auth", "status": "doing", "spec": "JWT login"}]}"#)
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args([
+            "handoff",
+            "create",
+            "--summary",
+            "Login works",
+            "--next-steps",
+            "Add refresh tokens",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Recorded handoff #1"));
+
+    // Attached to the focused task by default
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["handoff", "list", "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json[0]["task_id"], 1);
+    assert_eq!(json[0]["next_steps"], "Add refresh tokens");
+
+    common::ie_command_with_project_dir(dir)
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Handoff #1")
+                .and(predicate::str::contains("Next: Add refresh tokens")),
+        );
+
+    common::ie_command_with_project_dir(dir)
+        .args(["handoff", "create", "--summary", ""])
+        .assert()
+        .failure();
+}
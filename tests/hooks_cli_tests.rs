@@ -0,0 +1,89 @@
+/// Tests for `[hooks]` commands run on lifecycle events
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+#[cfg(unix)]
+fn test_hooks_receive_task_events() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    std::fs::write(
+        dir.join(".intent-engine").join("config.toml"),
+        "[hooks]\n\
+         task_created = [\"cat > created.json\"]\n\
+         task_completed = [\"echo \\\"$IE_HOOK_EVENT\\\" >> events.txt\", \"exit 4\"]\n",
+    )
+    .unwrap();
+    let ie = |args: &[&str]| common::ie_command_with_project_dir(dir).args(args).assert();
+
+    ie(&["task", "create", "Hooked"]).success();
+    let payload: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("created.json")).unwrap()).unwrap();
+    assert_eq!(payload["event"], "task_created");
+    assert_eq!(payload["data"]["name"], "Hooked");
+
+    // A failing hook only warns by default
+    ie(&["task", "start", "1"]).success();
+    ie(&["task", "done"])
+        .success()
+        .stderr(predicate::str::contains(
+            "Hook 'exit 4' for task_completed failed",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("events.txt")).unwrap(),
+        "task_completed\n"
+    );
+
+    ie(&["hooks", "test", "task_completed"])
+        .failure()
+        .stdout(predicate::str::contains("✗ exit 4: exited with 4"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_task_completed_waits_for_approval() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    std::fs::write(
+        dir.join(".intent-engine").join("config.toml"),
+        "[hooks]\ntask_completed = [\"echo \\\"$IE_HOOK_EVENT\\\" >> events.txt\"]\n",
+    )
+    .unwrap();
+    let ie = |args: &[&str]| common::ie_command_with_project_dir(dir).args(args).assert();
+
+    ie(&["config", "set", "review.required", "true"]).success();
+    ie(&["task", "create", "Refactor auth", "--owner", "ai"]).success();
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "done", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is awaiting review"));
+    assert!(!dir.join("events.txt").exists());
+
+    ie(&["task", "approve", "1"]).success();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("events.txt")).unwrap(),
+        "task_completed\n"
+    );
+}
+
+#[test]
+fn test_hooks_list_reports_invalid_config() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    let config = dir.join(".intent-engine").join("config.toml");
+    let ie = |args: &[&str]| common::ie_command_with_project_dir(dir).args(args).assert();
+
+    std::fs::write(&config, "hooks.task_created = [\"./created.sh\"]\n").unwrap();
+    ie(&["hooks", "list"])
+        .success()
+        .stdout(predicate::str::contains("task_created:\n  ./created.sh"));
+
+    std::fs::write(&config, "[hooks]\ntask_created = [\"./created.sh\"\n").unwrap();
+    ie(&["hooks", "list"])
+        .failure()
+        .stderr(predicate::str::contains("config.toml"))
+        .stdout(predicate::str::contains("No hooks configured").not());
+}
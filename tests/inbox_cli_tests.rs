@@ -0,0 +1,46 @@
+/// Tests for `ie inbox` and the needs_human flag
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_inbox_lists_flagged_tasks() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(
+            r#"{"tasks": [
+                {"name": "Login", "spec": "OAuth login", "needs_human": "Google or GitHub?"},
+                {"name": "Logout", "spec": "Clear the session"}
+            ]}"#,
+        )
+        .assert()
+        .success();
+
+    // Plan-created tasks are AI-owned, so only the flagged one is listed
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["inbox", "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["name"], "Login");
+    assert_eq!(json[0]["needs_human"], "Google or GitHub?");
+
+    common::ie_command_with_project_dir(dir)
+        .arg("inbox")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("needs human: Google or GitHub?"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "update", "1", "--needs-human", ""])
+        .assert()
+        .success();
+    common::ie_command_with_project_dir(dir)
+        .arg("inbox")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Inbox is empty"));
+}
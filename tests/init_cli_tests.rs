@@ -97,3 +97,87 @@ async fn test_init_success_with_force() {
     assert!(intent_dir.exists());
     assert!(intent_dir.join("project.db").exists());
 }
+
+// ============================================================================
+// JSON Envelope Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_init_json_envelope() {
+    let temp_dir = TempDir::new().unwrap();
+    let at = temp_dir.path().to_str().unwrap();
+
+    let output = common::ie_command()
+        .args(["init", "--at", at, "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert!(json["error"].is_null());
+    assert!(json["data"]["database_path"]
+        .as_str()
+        .unwrap()
+        .ends_with("project.db"));
+
+    // Failures are enveloped on stdout as well
+    let output = common::ie_command()
+        .args(["init", "--at", at, "--format", "json"])
+        .assert()
+        .failure();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["ok"], false);
+    assert!(json["data"].is_null());
+    assert_eq!(json["error"]["code"], "INVALID_INPUT");
+    assert_eq!(json["error"]["error_code"], "IE0002");
+
+    let output = common::ie_command()
+        .args(["init", "--at", at, "--force", "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 1);
+}
+
+// ============================================================================
+// Repository Analysis Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_init_analyze_proposes_and_applies_plan() {
+    let temp_dir = TempDir::new().unwrap();
+    let at = temp_dir.path().to_str().unwrap();
+    fs::write(
+        temp_dir.path().join("README.md"),
+        "# Demo\n\nA demo project.\n\n- [ ] Write docs\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("lib.py"), "# TODO: add retries\n").unwrap();
+
+    common::ie_command()
+        .args(["init", "--at", at, "--analyze"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- Demo"))
+        .stdout(predicate::str::contains("    - Write docs"))
+        .stdout(predicate::str::contains("ie plan <"));
+    let plan: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(temp_dir.path().join(".intent-engine/bootstrap-plan.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(plan["tasks"][0]["spec"], "A demo project.");
+
+    let output = common::ie_command()
+        .args(["init", "--at", at, "--force", "--analyze", "--apply"])
+        .args(["--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["data"]["analysis"]["todo_comments"], 1);
+    // Demo, the README group and its item, the TODO group and its comment
+    assert_eq!(json["data"]["applied"]["created_count"], 5);
+
+    common::ie_command()
+        .args(["init", "--at", at, "--force", "--apply"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,70 @@
+/// Tests for `ie insights`
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_insights_score_and_drill_down() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(
+            r#"{"tasks": [
+                {"name": "Schema", "spec": "Tables"},
+                {"name": "API", "spec": "Endpoints", "depends_on": ["Schema"]},
+                {"name": "Vendor access", "blocked_reason": "Waiting on legal"}
+            ]}"#,
+        )
+        .assert()
+        .success();
+
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["insights", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let insights = report["insights"].as_array().unwrap();
+    assert_eq!(insights.len(), 5);
+    let blocked = insights.iter().find(|i| i["kind"] == "blocked").unwrap();
+    assert_eq!(blocked["count"], 2);
+    assert_eq!(blocked["out_of"], 3);
+    assert_eq!(blocked["drill_down"], "ie insights blocked");
+    let specs = insights
+        .iter()
+        .find(|i| i["kind"] == "missing_specs")
+        .unwrap();
+    assert_eq!(specs["count"], 1);
+    assert!(report["score"].as_u64().unwrap() <= 100);
+
+    common::ie_command_with_project_dir(dir)
+        .arg("insights")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Project health:"))
+        .stdout(predicate::str::contains(
+            "2 of 3 unfinished tasks are blocked",
+        ));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["insights", "blocked"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#2 API [todo]"))
+        .stdout(predicate::str::contains("waiting on #1"))
+        .stdout(predicate::str::contains("blocked: Waiting on legal"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["insights", "missing-specs", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vendor access"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["insights", "speed"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid insight"));
+}
@@ -0,0 +1,126 @@
+/// Tests for task links (`"links"` in plans, `ie links add/list/remove`)
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_links_from_plan_and_cli() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(
+            r#"{"tasks": [{"name": "Auth", "links": [
+                "https://github.com/org/repo/issues/12",
+                {"url": "docs/auth.md", "title": "Auth design"}
+            ]}]}"#,
+        )
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "get", "1", "--with-context"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Links:"))
+        .stdout(predicate::str::contains(
+            "[issue] https://github.com/org/repo/issues/12",
+        ))
+        .stdout(predicate::str::contains("[doc] Auth design <docs/auth.md>"));
+
+    common::ie_command_with_project_dir(dir)
+        .args([
+            "links",
+            "add",
+            "1",
+            "https://www.figma.com/file/abc",
+            "--title",
+            "Mockups",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[design] Mockups"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "remove", "1", "docs/auth.md"])
+        .assert()
+        .success();
+
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["links", "list", "--task", "1", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let links: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let urls: Vec<&str> = links
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["url"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        urls,
+        [
+            "https://github.com/org/repo/issues/12",
+            "https://www.figma.com/file/abc"
+        ]
+    );
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "add", "1", "docs/x.md", "--kind", "video"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid link kind"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "list", "--task", "99"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_maintain_extracts_and_check_flags_dead_paths() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(
+            r#"{"tasks": [
+                {"name": "Auth", "spec": "Tracked in https://github.com/org/repo/issues/12."},
+                {"name": "Docs", "links": ["README.md", "docs/gone.md"]}
+            ]}"#,
+        )
+        .assert()
+        .success();
+    std::fs::write(dir.join("README.md"), "# Readme").unwrap();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["db", "maintain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Links: 1 new"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "list", "--task", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[issue] https://github.com/org/repo/issues/12",
+        ));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "check", "--task", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("docs/gone.md (dead: No such file"))
+        .stdout(predicate::str::contains("Checked 2 link(s): 1 dead"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["links", "list", "--task", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[doc] README.md\n"))
+        .stdout(predicate::str::contains("docs/gone.md (dead:"));
+}
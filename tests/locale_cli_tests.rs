@@ -0,0 +1,47 @@
+/// Tests for localized CLI messages (`IE_LOCALE` and `[ui] locale`)
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_ie_locale_translates_plan_errors() {
+    let temp_dir = common::setup_test_env();
+    common::ie_command_with_project_dir(temp_dir.path())
+        .env("IE_LOCALE", "zh_CN.UTF-8")
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "A", "depends_on": ["B"]}]}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "任务 'A' 依赖 'B'，但 'B' 不在计划中",
+        ));
+}
+
+#[test]
+fn test_config_locale_translates_next_step() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    std::fs::write(
+        dir.join(".intent-engine").join("config.toml"),
+        "[ui]\nlocale = \"zh\"\n",
+    )
+    .unwrap();
+    let ie = |args: &[&str]| {
+        common::ie_command_with_project_dir(dir)
+            .env_remove("IE_LOCALE")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env("LANG", "en_US.UTF-8")
+            .args(args)
+            .assert()
+    };
+
+    ie(&["task", "create", "Parent"]).success();
+    ie(&["task", "create", "Child", "--parent", "1"]).success();
+    ie(&["task", "start", "2"]).success();
+    ie(&["task", "done"])
+        .success()
+        .stdout(predicate::str::contains(
+            "父任务 #1 'Parent' 的所有子任务均已完成",
+        ));
+}
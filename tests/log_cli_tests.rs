@@ -0,0 +1,34 @@
+/// Tests for `ie log` with `@file` / `@files` includes
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_log_includes_never_delete_files() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    let docs = dir.join("docs");
+    std::fs::create_dir(&docs).unwrap();
+    std::fs::write(dir.join("analysis.md"), "Findings").unwrap();
+    std::fs::write(docs.join("guide.md"), "Guide\n@file(../analysis.md)\n").unwrap();
+    std::fs::write(dir.join("decision.md"), "Chose SQLite").unwrap();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "create", "Research"])
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["log", "note", "@files(docs/*.md)", "--task", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Findings"));
+    common::ie_command_with_project_dir(dir)
+        .args(["log", "decision", "@file(decision.md)", "--task", "1"])
+        .assert()
+        .success();
+
+    assert!(dir.join("analysis.md").exists());
+    assert!(docs.join("guide.md").exists());
+    assert!(dir.join("decision.md").exists());
+}
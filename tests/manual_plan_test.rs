@@ -54,6 +54,7 @@ async fn manual_test_plan_with_status_and_active_form() {
             parent_id: None,
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let executor = PlanExecutor::new(&ctx.pool);
@@ -113,6 +114,7 @@ async fn manual_test_plan_with_status_and_active_form() {
             parent_id: None,
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let result = executor.execute(&invalid).await.unwrap();
@@ -164,7 +164,7 @@ async fn neo4j_task_start_done_lifecycle() {
     assert_eq!(started.task.status, "doing");
 
     // Done
-    let done = tm.done_task_by_id(task.id, false).await.unwrap();
+    let done = tm.done_task_by_id(task.id).await.unwrap();
     assert_eq!(done.completed_task.status, "done");
 
     teardown(&graph, &pid).await;
@@ -188,7 +188,7 @@ async fn neo4j_task_done_blocked_by_children() {
     tm.start_task(parent.id, false).await.unwrap();
 
     // Cannot complete parent while child is not done
-    let result = tm.done_task_by_id(parent.id, false).await;
+    let result = tm.done_task_by_id(parent.id).await;
     assert!(result.is_err());
     let err = result.unwrap_err().to_string();
     assert!(
@@ -254,6 +254,7 @@ async fn neo4j_plan_create_hierarchy() {
             ]),
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -282,6 +283,7 @@ async fn neo4j_plan_idempotent_update() {
             spec: Some("Original spec".to_string()),
             ..Default::default()
         }],
+        ..Default::default()
     };
     let r1 = pe.execute(&request).await.unwrap();
     assert!(r1.success);
@@ -295,6 +297,7 @@ async fn neo4j_plan_idempotent_update() {
             spec: Some("Updated spec".to_string()),
             ..Default::default()
         }],
+        ..Default::default()
     };
     let r2 = pe.execute(&update_request).await.unwrap();
     assert!(r2.success);
@@ -320,6 +323,7 @@ async fn neo4j_plan_doing_requires_spec() {
             status: Some(TaskStatus::Doing),
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -345,6 +349,7 @@ async fn neo4j_plan_duplicate_names_rejected() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -373,6 +378,7 @@ async fn neo4j_plan_delete_by_id() {
             delete: Some(true),
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -406,6 +412,7 @@ async fn neo4j_plan_dependencies_created() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -456,6 +463,7 @@ async fn neo4j_plan_circular_dependency_rejected() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let result = pe.execute(&request).await.unwrap();
@@ -843,7 +851,7 @@ async fn neo4j_pick_next_priority_order() {
     )
     .await
     .unwrap();
-    tm.done_task_by_id(parent.id, false).await.unwrap();
+    tm.done_task_by_id(parent.id).await.unwrap();
 
     let all_done = tm.pick_next().await.unwrap();
     assert_eq!(all_done.reason_code.as_deref(), Some("ALL_TASKS_COMPLETED"));
@@ -863,7 +871,7 @@ async fn neo4j_done_task_no_id_uses_focus() {
     let wm = Neo4jWorkspaceManager::new(graph.clone(), pid.clone());
 
     // No focus → error
-    let err = tm.done_task(false).await;
+    let err = tm.done_task().await;
     assert!(err.is_err());
     let msg = err.unwrap_err().to_string();
     assert!(
@@ -892,7 +900,7 @@ async fn neo4j_done_task_no_id_uses_focus() {
     assert_eq!(focus.current_task_id, Some(task.id));
 
     // Done with no ID → uses focus
-    let result = tm.done_task(false).await.unwrap();
+    let result = tm.done_task().await.unwrap();
     assert_eq!(result.completed_task.id, task.id);
     assert_eq!(result.completed_task.status, "done");
 
@@ -980,6 +988,7 @@ async fn neo4j_get_task_context_full() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let dep_result = pe.execute(&dep_req).await.unwrap();
     assert!(
@@ -1068,6 +1077,7 @@ async fn neo4j_blocking_and_blocked_by_tasks() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let result = pe.execute(&req).await.unwrap();
     assert!(result.success);
@@ -1237,6 +1247,7 @@ async fn neo4j_plan_with_default_parent() {
                 ..Default::default()
             },
         ],
+        ..Default::default()
     };
     let result = pe.execute(&request).await.unwrap();
     assert!(result.success, "Plan failed: {:?}", result.error);
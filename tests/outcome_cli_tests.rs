@@ -0,0 +1,73 @@
+/// Tests for completion outcomes (`ie task done --outcome`, plan `outcome`)
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_done_records_outcome() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Login", "spec": "JWT login"}]}"#)
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "done", "1", "--outcome", "Login works"])
+        .args(["--artifact", "src/auth.rs", "--artifact", "docs/auth.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Outcome: Login works"))
+        .stdout(predicate::str::contains("- docs/auth.md"));
+
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["task", "get", "1", "--with-events", "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["outcome"]["summary"], "Login works");
+    assert_eq!(
+        json["outcome"]["artifacts"],
+        serde_json::json!(["src/auth.rs", "docs/auth.md"])
+    );
+}
+
+#[test]
+fn test_artifact_requires_outcome() {
+    let temp_dir = common::setup_test_env();
+    common::ie_command_with_project_dir(temp_dir.path())
+        .args(["task", "done", "1", "--artifact", "src/auth.rs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--outcome"));
+}
+
+#[test]
+fn test_plan_records_outcome() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(
+            r#"{"tasks": [{"name": "Docs", "status": "done", "outcome": "Written", "artifacts": ["README.md"]}]}"#,
+        )
+        .assert()
+        .success();
+
+    let output = common::ie_command_with_project_dir(dir)
+        .args(["task", "get", "1", "--with-events", "--format", "json"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    assert_eq!(json["outcome"]["summary"], "Written");
+    assert_eq!(json["outcome"]["artifacts"][0], "README.md");
+
+    // An outcome belongs to a completion
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Tests", "outcome": "Green"}]}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outcome can only be given"));
+}
@@ -1,7 +1,9 @@
 use intent_engine::{
+    db::models::Actor,
     error::Result,
-    plan::{PlanExecutor, PlanRequest, TaskTree},
+    plan::{PlanExecutor, PlanRequest, TaskStatus, TaskTree},
     project::ProjectContext,
+    settings::{SettingsManager, REVIEW_REQUIRED},
     tasks::{TaskManager, TaskUpdate},
     workspace::WorkspaceManager,
 };
@@ -69,6 +71,7 @@ async fn test_plan_creates_ai_owned_tasks() -> Result<()> {
             parent_id: None,
             ..Default::default()
         }],
+        ..Default::default()
     };
 
     let result = plan_executor.execute(&plan).await?;
@@ -125,8 +128,8 @@ async fn test_ai_cannot_complete_human_owned_task() -> Result<()> {
     // Set as current task
     workspace_mgr.set_current_task(task.id, None).await?;
 
-    // Try to complete as AI (is_ai_caller=true)
-    let result = task_mgr.done_task(true).await;
+    // Try to complete as AI
+    let result = task_mgr.with_actor(Actor::ai()).done_task().await;
 
     // Should fail
     assert!(
@@ -172,8 +175,8 @@ async fn test_human_can_complete_human_owned_task() -> Result<()> {
         )
         .await?;
 
-    // Complete as human (is_ai_caller=false)
-    let result = task_mgr.done_task(false).await;
+    // Complete as human
+    let result = task_mgr.done_task().await;
 
     // Should succeed
     assert!(
@@ -208,8 +211,8 @@ async fn test_ai_can_complete_ai_owned_task() -> Result<()> {
         )
         .await?;
 
-    // Complete as AI (is_ai_caller=true)
-    let result = task_mgr.done_task(true).await;
+    // Complete as AI
+    let result = task_mgr.with_actor(Actor::ai()).done_task().await;
 
     // Should succeed
     assert!(
@@ -248,3 +251,111 @@ async fn test_mixed_ownership_in_hierarchy() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_review_required_sends_ai_task_to_review() -> Result<()> {
+    let (_temp, ctx) = setup_test_project().await?;
+    SettingsManager::new(&ctx.pool)
+        .set(REVIEW_REQUIRED, Some("true"), "cli")
+        .await?;
+
+    let task_mgr = TaskManager::new(&ctx.pool).with_actor(Actor::ai());
+    let parent = task_mgr
+        .add_task("AI Parent", None, None, Some("ai"), None, None)
+        .await?;
+    let child = task_mgr
+        .add_task("AI Child", None, Some(parent.id), Some("ai"), None, None)
+        .await?;
+
+    // Completing lands in review, not done
+    let result = task_mgr.done_task_by_id(child.id).await?;
+    assert_eq!(result.completed_task.status, "review");
+
+    // A task in review still blocks its parent
+    assert!(task_mgr.done_task_by_id(parent.id).await.is_err());
+
+    // The AI cannot approve its own work
+    assert!(task_mgr.approve_task(child.id).await.is_err());
+
+    // A human's approval moves it to done; approving twice is rejected
+    let reviewer = TaskManager::new(&ctx.pool).with_actor(Actor::human());
+    let approved = reviewer.approve_task(child.id).await?;
+    assert_eq!(approved.completed_task.status, "done");
+    assert!(approved.completed_task.first_done_at.is_some());
+    assert!(reviewer.approve_task(child.id).await.is_err());
+
+    // Human-owned tasks are not gated
+    let human = task_mgr
+        .add_task("Human Task", None, None, None, None, None)
+        .await?;
+    let result = TaskManager::new(&ctx.pool)
+        .done_task_by_id(human.id)
+        .await?;
+    assert_eq!(result.completed_task.status, "done");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_review_not_required_by_default() -> Result<()> {
+    let (_temp, ctx) = setup_test_project().await?;
+    let task_mgr = TaskManager::new(&ctx.pool).with_actor(Actor::ai());
+
+    let task = task_mgr
+        .add_task("AI Task", None, None, Some("ai"), None, None)
+        .await?;
+    let result = task_mgr.done_task_by_id(task.id).await?;
+    assert_eq!(result.completed_task.status, "done");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_done_goes_to_review_when_required() -> Result<()> {
+    let (_temp, ctx) = setup_test_project().await?;
+    SettingsManager::new(&ctx.pool)
+        .set(REVIEW_REQUIRED, Some("true"), "cli")
+        .await?;
+
+    let plan_executor = PlanExecutor::new(&ctx.pool);
+    let task_mgr = TaskManager::new(&ctx.pool);
+
+    let task = |name: &str, status: TaskStatus| TaskTree {
+        name: Some(name.to_string()),
+        status: Some(status),
+        ..Default::default()
+    };
+
+    plan_executor
+        .execute(&PlanRequest {
+            tasks: vec![task("Existing", TaskStatus::Todo)],
+            ..Default::default()
+        })
+        .await?;
+
+    let result = plan_executor
+        .execute(&PlanRequest {
+            tasks: vec![
+                task("Existing", TaskStatus::Done),
+                task("Created Done", TaskStatus::Done),
+            ],
+            ..Default::default()
+        })
+        .await?;
+    assert!(result.success, "{:?}", result.error);
+
+    for name in ["Existing", "Created Done"] {
+        let id = result.task_id_map[name];
+        assert_eq!(task_mgr.get_task(id).await?.status, "review");
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.contains(&format!("ie task approve {}", id))),
+            "missing review warning for {}",
+            name
+        );
+    }
+
+    Ok(())
+}
@@ -161,7 +161,7 @@ async fn run_dataset_test(total_tasks: usize) {
                 .await
                 .unwrap();
 
-                if uncompleted_children == 0 && task_mgr.done_task(false).await.is_ok() {
+                if uncompleted_children == 0 && task_mgr.done_task().await.is_ok() {
                     completed_count += 1;
                 }
             }
@@ -189,7 +189,7 @@ async fn run_dataset_test(total_tasks: usize) {
         let _ = event_mgr
             .add_event(
                 task_id,
-                "progress",
+                "milestone",
                 "Made significant progress on this task",
             )
             .await;
@@ -228,6 +228,7 @@ async fn run_dataset_test(total_tasks: usize) {
     assert_eq!(report.summary.total_tasks, total_tasks as i64);
     let total_status = report.summary.tasks_by_status.todo
         + report.summary.tasks_by_status.doing
+        + report.summary.tasks_by_status.review
         + report.summary.tasks_by_status.done;
     assert_eq!(total_status, total_tasks as i64);
 
@@ -165,7 +165,7 @@ async fn test_massive_events() {
     // Create 10,000 events
     for i in 0..count {
         event_mgr
-            .add_event(task.id, "test", &format!("Event {}", i))
+            .add_event(task.id, "note", &format!("Event {}", i))
             .await
             .unwrap();
     }
@@ -325,7 +325,7 @@ async fn test_report_generation_performance() {
             },
             2 => {
                 task_mgr.start_task(task.id, false).await.unwrap();
-                task_mgr.done_task(false).await.unwrap();
+                task_mgr.done_task().await.unwrap();
             },
             _ => {},
         }
@@ -380,7 +380,7 @@ async fn test_concurrent_task_operations() {
                     .unwrap();
 
                 task_mgr.start_task(task.id, false).await.unwrap();
-                task_mgr.done_task(false).await.unwrap();
+                task_mgr.done_task().await.unwrap();
             }
         });
         handles.push(handle);
@@ -424,7 +424,7 @@ async fn test_stress_task_state_transitions() {
 
         // todo -> doing -> done
         task_mgr.start_task(task.id, false).await.unwrap();
-        task_mgr.done_task(false).await.unwrap();
+        task_mgr.done_task().await.unwrap();
     }
 
     let elapsed = start.elapsed();
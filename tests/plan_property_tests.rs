@@ -0,0 +1,326 @@
+//! Property tests for plan handling
+//!
+//! Generates arbitrary task trees — deep nesting, unicode and near-duplicate
+//! names, random (also cyclic or self-referencing) dependencies — and checks
+//! that `parse_request` and `PlanExecutor` never panic and always leave a
+//! consistent database: no orphan rows, the FTS index matching the tasks
+//! table, nothing applied by a plan that failed under `on_error: abort` or
+//! `collect`, and re-running a successful plan changing nothing.
+//!
+//! A failure prints the minimal plan proptest shrank it to. Raise the number
+//! of cases with `PROPTEST_CASES=1000 cargo test --test plan_property_tests`.
+
+use intent_engine::db::{create_pool, run_migrations};
+use intent_engine::plan::{
+    parse_request, OnError, PlanExecutor, PlanRequest, PriorityValue, TaskTree,
+};
+use intent_engine::plan_validation::validate_request;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+
+/// Names agents produce: arbitrary printable unicode, or one of a few names
+/// that differ only in case, whitespace, accents or lookalike letters
+fn task_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => "\\PC{1,24}",
+        1 => prop::sample::select(vec![
+            "Task", "task", "TASK", "Task ", " Task", "Tâsk", "Ta\u{0301}sk", "Тask", "任务",
+            "任务 ", "Task 1", "Task-1", "🚀 Task",
+        ])
+        .prop_map(str::to_string),
+    ]
+}
+
+fn leaf_task() -> impl Strategy<Value = TaskTree> {
+    (
+        task_name(),
+        prop::option::of("\\PC{0,60}"),
+        prop::option::of(prop::sample::select(vec![
+            PriorityValue::Critical,
+            PriorityValue::High,
+            PriorityValue::Medium,
+            PriorityValue::Low,
+        ])),
+        prop::option::of(0u32..600),
+    )
+        .prop_map(|(name, spec, priority, estimate_minutes)| TaskTree {
+            name: Some(name),
+            spec,
+            priority,
+            estimate_minutes,
+            ..Default::default()
+        })
+}
+
+fn task_tree() -> impl Strategy<Value = TaskTree> {
+    leaf_task().prop_recursive(8, 48, 4, |inner| {
+        (leaf_task(), prop::collection::vec(inner, 1..4)).prop_map(|(mut task, children)| {
+            task.children = Some(children);
+            task
+        })
+    })
+}
+
+fn collect_names<'a>(tasks: &'a [TaskTree], names: &mut Vec<&'a str>) {
+    for task in tasks {
+        names.extend(task.name.as_deref());
+        collect_names(task.children.as_deref().unwrap_or_default(), names);
+    }
+}
+
+/// The `n`-th task of the trees in pre-order, counting down `n` on the way
+fn nth_task<'a>(tasks: &'a mut [TaskTree], n: &mut usize) -> Option<&'a mut TaskTree> {
+    for task in tasks {
+        if *n == 0 {
+            return Some(task);
+        }
+        *n -= 1;
+        if let Some(found) = nth_task(task.children.as_deref_mut().unwrap_or_default(), n) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// A plan with dependencies between random tasks of its tree
+fn plan_request() -> impl Strategy<Value = PlanRequest> {
+    (
+        prop::collection::vec(task_tree(), 1..5),
+        prop::collection::vec((any::<Index>(), any::<Index>()), 0..8),
+        prop::sample::select(vec![OnError::Abort, OnError::Skip, OnError::Collect]),
+    )
+        .prop_map(|(mut tasks, edges, on_error)| {
+            let mut names = Vec::new();
+            collect_names(&tasks, &mut names);
+            let names: Vec<String> = names.into_iter().map(str::to_string).collect();
+
+            for (from, to) in edges {
+                let dependency = names[to.index(names.len())].clone();
+                let mut n = from.index(names.len());
+                if let Some(task) = nth_task(&mut tasks, &mut n) {
+                    task.depends_on
+                        .get_or_insert_with(Vec::new)
+                        .push(dependency);
+                }
+            }
+
+            PlanRequest {
+                tasks,
+                on_error,
+                ..Default::default()
+            }
+        })
+}
+
+async fn fresh_db() -> (TempDir, SqlitePool) {
+    let temp_dir = TempDir::new().unwrap();
+    let pool = create_pool(&temp_dir.path().join("intents.db"))
+        .await
+        .unwrap();
+    run_migrations(&pool).await.unwrap();
+    (temp_dir, pool)
+}
+
+async fn count(pool: &SqlitePool, sql: &str) -> i64 {
+    sqlx::query_scalar(sql).fetch_one(pool).await.unwrap()
+}
+
+/// Rows the schema alone does not keep consistent
+async fn assert_consistent(pool: &SqlitePool) -> Result<(), TestCaseError> {
+    let checks = [
+        (
+            "tasks with a missing parent",
+            "SELECT COUNT(*) FROM tasks t WHERE t.parent_id IS NOT NULL \
+             AND NOT EXISTS (SELECT 1 FROM tasks p WHERE p.id = t.parent_id)",
+        ),
+        (
+            "dependencies on missing tasks",
+            "SELECT COUNT(*) FROM dependencies d \
+             WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE id = d.blocking_task_id) \
+             OR NOT EXISTS (SELECT 1 FROM tasks WHERE id = d.blocked_task_id)",
+        ),
+        (
+            "tasks depending on themselves",
+            "SELECT COUNT(*) FROM dependencies WHERE blocking_task_id = blocked_task_id",
+        ),
+        (
+            "events of missing tasks",
+            "SELECT COUNT(*) FROM events e \
+             WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE id = e.task_id)",
+        ),
+    ];
+    for (what, sql) in checks {
+        prop_assert_eq!(count(pool, sql).await, 0, "{}", what);
+    }
+
+    // tasks_fts is an external-content index: selecting from it reads the
+    // tasks table, so check the index itself and that every task is found
+    let integrity = sqlx::query("INSERT INTO tasks_fts(tasks_fts) VALUES('integrity-check')")
+        .execute(pool)
+        .await;
+    prop_assert!(
+        integrity.is_ok(),
+        "FTS index out of sync: {:?}",
+        integrity.err()
+    );
+
+    let tasks: Vec<(i64, String)> = sqlx::query_as("SELECT id, name FROM tasks")
+        .fetch_all(pool)
+        .await
+        .unwrap();
+    for (id, name) in tasks {
+        // The trigram tokenizer cannot match fewer than three characters
+        if name.chars().count() < 3 {
+            continue;
+        }
+        let phrase = format!("\"{}\"", name.replace('"', "\"\""));
+        let found: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM tasks_fts WHERE tasks_fts MATCH ? AND rowid = ?",
+        )
+        .bind(&phrase)
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        prop_assert_eq!(found, 1, "task {} not found by its name {:?}", id, name);
+    }
+    Ok(())
+}
+
+/// (parent name, name, spec, priority) of every task, in a stable order
+async fn snapshot(pool: &SqlitePool) -> Vec<(Option<String>, String, Option<String>, Option<i64>)> {
+    sqlx::query_as(
+        "SELECT p.name, t.name, t.spec, t.priority FROM tasks t \
+         LEFT JOIN tasks p ON p.id = t.parent_id ORDER BY t.id",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn plan_execution_keeps_database_consistent(request in plan_request()) {
+        runtime().block_on(async {
+            let (_temp_dir, pool) = fresh_db().await;
+            let result = PlanExecutor::new(&pool).execute(&request).await;
+            assert_consistent(&pool).await?;
+
+            let succeeded = matches!(&result, Ok(plan) if plan.success);
+            if !succeeded && request.on_error != OnError::Skip {
+                prop_assert_eq!(count(&pool, "SELECT COUNT(*) FROM tasks").await, 0,
+                    "failed plan applied tasks: {:?}", result.as_ref().map(|r| &r.error));
+            }
+            if !succeeded {
+                return Ok(());
+            }
+
+            // Running the same plan again changes nothing
+            let before = snapshot(&pool).await;
+            let again = PlanExecutor::new(&pool).execute(&request).await;
+            let again = again.map_err(|e| TestCaseError::fail(e.to_string()))?;
+            prop_assert!(again.success, "re-run failed: {:?}", again.error);
+            prop_assert_eq!(again.created_count, 0);
+            prop_assert_eq!(snapshot(&pool).await, before);
+            assert_consistent(&pool).await
+        })?;
+    }
+
+    #[test]
+    fn serialized_plans_parse_back(request in plan_request()) {
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed = parse_request(&json).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn parse_request_never_panics(input in "\\PC{0,200}") {
+        if let Ok(request) = parse_request(&input) {
+            let _ = validate_request(&request);
+        }
+    }
+
+    #[test]
+    fn parse_request_never_panics_on_plan_shaped_json(value in plan_shaped_json()) {
+        if let Ok(request) = parse_request(&value.to_string()) {
+            let _ = validate_request(&request);
+        }
+    }
+}
+
+/// JSON objects using the plan's field names with values of any type
+fn plan_shaped_json() -> impl Strategy<Value = serde_json::Value> {
+    let key = prop::sample::select(vec![
+        "tasks",
+        "name",
+        "spec",
+        "priority",
+        "children",
+        "depends_on",
+        "id",
+        "task_id",
+        "status",
+        "active_form",
+        "estimate_minutes",
+        "milestone",
+        "needs_human",
+        "blocked_reason",
+        "checklist",
+        "outcome",
+        "artifacts",
+        "links",
+        "delete",
+        "parent_id",
+        "scope",
+        "on_error",
+        "ignore_wip_limits",
+        "unknown",
+    ]);
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<f64>().prop_map(|f| serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)),
+        prop::sample::select(vec![
+            "todo",
+            "doing",
+            "done",
+            "high",
+            "critical",
+            "abort",
+            "skip",
+            "project",
+            "focused",
+            "@file(missing.md)",
+            "",
+        ])
+        .prop_map(serde_json::Value::from),
+        "\\PC{0,16}".prop_map(serde_json::Value::from),
+    ];
+    leaf.prop_recursive(6, 64, 6, move |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..5).prop_map(serde_json::Value::Array),
+            prop::collection::vec((key.clone(), inner), 0..6).prop_map(|fields| {
+                serde_json::Value::Object(
+                    fields
+                        .into_iter()
+                        .map(|(k, v)| (k.to_string(), v))
+                        .collect(),
+                )
+            }),
+        ]
+    })
+}
@@ -0,0 +1,73 @@
+//! Integration tests for `ie prompt-segment`
+
+mod common;
+
+use serde_json::Value;
+
+fn prompt_segment(dir: &std::path::Path, extra: &[&str]) -> String {
+    let output = common::ie_command_with_project_dir(dir)
+        .arg("prompt-segment")
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_prompt_segment_empty_project_prints_nothing() {
+    let temp_dir = common::setup_test_env();
+    assert_eq!(prompt_segment(temp_dir.path(), &[]), "");
+}
+
+#[test]
+fn test_prompt_segment_outside_project_prints_nothing() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+
+    assert_eq!(prompt_segment(temp_dir.path(), &[]), "");
+    // Must not initialize a project as a side effect
+    assert!(!temp_dir.path().join(".intent-engine").exists());
+}
+
+#[test]
+fn test_prompt_segment_shows_focus_and_refreshes_on_change() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    for name in ["fix-login", "write docs"] {
+        common::ie_command_with_project_dir(dir)
+            .args(["task", "create", name])
+            .assert()
+            .success();
+    }
+    assert_eq!(prompt_segment(dir, &[]).trim(), "⚒ (2 todo)");
+
+    // Served from cache on the second call
+    assert_eq!(prompt_segment(dir, &[]).trim(), "⚒ (2 todo)");
+    assert!(dir.join(".intent-engine/prompt-cache").is_dir());
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "start", "1"])
+        .assert()
+        .success();
+    assert_eq!(prompt_segment(dir, &[]).trim(), "⚒ #1 fix-login (1 todo)");
+
+    let json: Value = serde_json::from_str(&prompt_segment(dir, &["--format", "json"])).unwrap();
+    assert_eq!(json["task_id"], 1);
+    assert_eq!(json["todo"], 1);
+    assert_eq!(json["segment"], "⚒ #1 fix-login (1 todo)");
+}
+
+#[test]
+fn test_prompt_segment_shell_init() {
+    let temp_dir = common::setup_test_env();
+
+    let bash = prompt_segment(temp_dir.path(), &["--bash"]);
+    assert!(bash.contains("PS1="));
+    assert!(bash.contains("ie prompt-segment"));
+
+    let zsh = prompt_segment(temp_dir.path(), &["--zsh"]);
+    assert!(zsh.contains("setopt PROMPT_SUBST"));
+    assert!(zsh.contains("PROMPT="));
+}
@@ -0,0 +1,60 @@
+/// Tests for the global `--read-only` flag
+mod common;
+
+use predicates::prelude::*;
+
+fn ie(dir: &std::path::Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    common::ie_command_with_project_dir(dir).args(args).assert()
+}
+
+fn task_count(dir: &std::path::Path) -> usize {
+    let output = ie(dir, &["task", "list", "--format", "json"]).success();
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout).unwrap();
+    json["tasks"].as_array().unwrap().len()
+}
+
+#[test]
+fn test_read_only_allows_reads() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    common::ie_command_with_project_dir(dir)
+        .arg("plan")
+        .write_stdin(r#"{"tasks": [{"name": "Readable needle", "milestone": "v1"}]}"#)
+        .assert()
+        .success();
+
+    ie(dir, &["--read-only", "task", "list"])
+        .success()
+        .stdout(predicate::str::contains("Readable"));
+    ie(dir, &["--read-only", "search", "needle"])
+        .success()
+        .stdout(predicate::str::contains("Readable"));
+    ie(dir, &["--read-only", "report", "milestone", "v1"])
+        .success()
+        .stdout(predicate::str::contains("Readable"));
+    ie(dir, &["--read-only", "status"]).success();
+}
+
+#[test]
+fn test_read_only_rejects_mutations() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+    ie(dir, &["task", "create", "Existing"]).success();
+
+    ie(dir, &["--read-only", "task", "create", "Blocked"]).failure();
+    ie(dir, &["--read-only", "task", "start", "1"]).failure();
+    ie(dir, &["--read-only", "doctor", "--fix"])
+        .failure()
+        .stderr(predicate::str::contains("--read-only"));
+
+    assert_eq!(task_count(dir), 1);
+}
+
+#[test]
+fn test_read_only_does_not_initialize() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+
+    ie(temp_dir.path(), &["--read-only", "task", "list"]).failure();
+    assert!(!temp_dir.path().join(".intent-engine").exists());
+}
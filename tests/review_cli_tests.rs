@@ -0,0 +1,81 @@
+/// Tests for the review gate (`review.required`, `ie task approve`)
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_ai_cannot_approve_its_own_work() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["config", "set", "review.required", "true"])
+        .assert()
+        .success();
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "create", "Refactor auth", "--owner", "ai"])
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "done", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is awaiting review"))
+        .stdout(predicate::str::contains("completed").not());
+
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "approve", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be approved by a human"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "approve", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Completed task #1"));
+}
+
+#[test]
+fn test_status_update_cannot_skip_review() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["config", "set", "review.required", "true"])
+        .assert()
+        .success();
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "create", "Refactor auth", "--owner", "ai"])
+        .assert()
+        .success();
+
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "update", "1", "--status", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "must be reviewed before it is done",
+        ));
+
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "done", "1"])
+        .assert()
+        .success();
+    common::ie_command_with_project_dir(dir)
+        .env("IE_ACTOR", "ai")
+        .args(["task", "update", "1", "--status", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only a human can move it on"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["task", "update", "1", "--status", "done"])
+        .assert()
+        .success();
+}
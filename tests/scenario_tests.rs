@@ -0,0 +1,236 @@
+//! End-to-end scenario tests driven by YAML scripts
+//!
+//! Each file in `tests/scenarios/` describes a multi-step agent flow
+//! (plan → start → log → spawn subtask → done → pick next, ...). Every step
+//! runs the real `ie` binary against a fresh temporary project and asserts on
+//! its JSON output, so regressions in cross-module workflows surface here
+//! rather than only in unit tests.
+//!
+//! # Script format
+//!
+//! ```yaml
+//! name: short-name
+//! description: What this flow verifies
+//! steps:
+//!   - name: Create plan
+//!     args: [plan, --format, json]
+//!     stdin: '{"tasks":[{"name":"Auth"}]}'
+//!     expect:
+//!       success: true                 # exit status (default: true)
+//!       json:                         # JSON pointer -> expected value
+//!         /success: true
+//!       stderr_contains: "..."        # optional substring check
+//!     capture:                        # JSON pointer -> variable name
+//!       auth_id: /task_id_map/Auth
+//!   - args: [task, start, "${auth_id}", --format, json]
+//! ```
+//!
+//! Captured values can be referenced as `${name}` in later `args` and `stdin`.
+
+mod common;
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Deserialize)]
+struct Scenario {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Step {
+    #[serde(default)]
+    name: Option<String>,
+    args: Vec<String>,
+    #[serde(default)]
+    stdin: Option<String>,
+    #[serde(default)]
+    expect: Expect,
+    #[serde(default)]
+    capture: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Expect {
+    #[serde(default = "default_success")]
+    success: bool,
+    #[serde(default)]
+    json: HashMap<String, serde_yaml::Value>,
+    #[serde(default)]
+    stderr_contains: Option<String>,
+}
+
+impl Default for Expect {
+    fn default() -> Self {
+        Self {
+            success: default_success(),
+            json: HashMap::new(),
+            stderr_contains: None,
+        }
+    }
+}
+
+fn default_success() -> bool {
+    true
+}
+
+/// Replace `${var}` placeholders with previously captured values
+fn substitute(input: &str, vars: &HashMap<String, String>) -> String {
+    let mut output = input.to_string();
+    for (key, value) in vars {
+        output = output.replace(&format!("${{{}}}", key), value);
+    }
+    output
+}
+
+/// Render a captured JSON value as a plain string (no quotes for strings)
+fn value_to_var(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Run a single scenario file, panicking with step context on failure
+fn run_scenario(path: &Path) {
+    let content = std::fs::read_to_string(path).unwrap();
+    let scenario: Scenario = serde_yaml::from_str(&content)
+        .unwrap_or_else(|e| panic!("Invalid scenario {}: {}", path.display(), e));
+
+    let temp_dir = common::setup_test_env();
+    let mut vars: HashMap<String, String> = HashMap::new();
+
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let label = format!(
+            "[{}] step {} ({})",
+            scenario.name,
+            index + 1,
+            step.name.as_deref().unwrap_or("unnamed")
+        );
+
+        let args: Vec<String> = step.args.iter().map(|a| substitute(a, &vars)).collect();
+        let mut cmd = common::ie_command_with_project_dir(temp_dir.path());
+        cmd.args(&args);
+        if let Some(stdin) = &step.stdin {
+            cmd.write_stdin(substitute(stdin, &vars));
+        }
+
+        let output = cmd.output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert_eq!(
+            output.status.success(),
+            step.expect.success,
+            "{}: unexpected exit status for `ie {}`\nstdout:\n{}\nstderr:\n{}",
+            label,
+            args.join(" "),
+            stdout,
+            stderr
+        );
+
+        if let Some(needle) = &step.expect.stderr_contains {
+            assert!(
+                stderr.contains(needle.as_str()),
+                "{}: stderr does not contain {:?}\nstderr:\n{}",
+                label,
+                needle,
+                stderr
+            );
+        }
+
+        if step.expect.json.is_empty() && step.capture.is_empty() {
+            continue;
+        }
+
+        let json: Value = serde_json::from_str(&stdout).unwrap_or_else(|e| {
+            panic!("{}: output is not JSON ({})\nstdout:\n{}", label, e, stdout)
+        });
+
+        for (pointer, expected) in &step.expect.json {
+            let expected: Value = serde_json::to_value(expected).unwrap();
+            let expected = match expected {
+                Value::String(s) => Value::String(substitute(&s, &vars)),
+                other => other,
+            };
+            let actual = json.pointer(pointer);
+            // Captured IDs are strings; allow them to match numeric output
+            let matches = match (actual, &expected) {
+                (Some(Value::Number(n)), Value::String(s)) => n.to_string() == *s,
+                (Some(a), e) => a == e,
+                (None, Value::Null) => true,
+                (None, _) => false,
+            };
+            assert!(
+                matches,
+                "{}: {} expected {} but got {}\noutput:\n{}",
+                label,
+                pointer,
+                expected,
+                actual.map(|v| v.to_string()).unwrap_or("<missing>".into()),
+                stdout
+            );
+        }
+
+        for (var, pointer) in &step.capture {
+            let value = json
+                .pointer(pointer)
+                .unwrap_or_else(|| panic!("{}: cannot capture {} from output", label, pointer));
+            vars.insert(var.clone(), value_to_var(value));
+        }
+    }
+}
+
+fn scenario_files() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("scenarios");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn test_all_scenarios() {
+    let files = scenario_files();
+    assert!(!files.is_empty(), "No scenario scripts found");
+
+    for file in files {
+        run_scenario(&file);
+    }
+}
+
+#[test]
+fn test_scenario_scripts_parse() {
+    for file in scenario_files() {
+        let content = std::fs::read_to_string(&file).unwrap();
+        let scenario: Scenario = serde_yaml::from_str(&content)
+            .unwrap_or_else(|e| panic!("Invalid scenario {}: {}", file.display(), e));
+        assert!(!scenario.steps.is_empty(), "{} has no steps", scenario.name);
+        assert!(
+            scenario.description.is_some(),
+            "{} should describe what it verifies",
+            scenario.name
+        );
+    }
+}
+
+#[test]
+fn test_substitute_replaces_captured_vars() {
+    let mut vars = HashMap::new();
+    vars.insert("parent".to_string(), "42".to_string());
+    assert_eq!(
+        substitute(r#"{"parent_id": ${parent}}"#, &vars),
+        r#"{"parent_id": 42}"#
+    );
+    assert_eq!(substitute("${missing}", &vars), "${missing}");
+}
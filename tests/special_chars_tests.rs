@@ -97,7 +97,7 @@ async fn test_sql_injection_in_event_data() {
 
     let malicious_data = "'; DROP TABLE tasks; SELECT '";
     let event = event_mgr
-        .add_event(task.id, "test", malicious_data)
+        .add_event(task.id, "note", malicious_data)
         .await
         .unwrap();
 
@@ -395,7 +395,7 @@ async fn test_very_long_event_data() {
 
     let long_data = "Event data. ".repeat(10_000);
     let event = event_mgr
-        .add_event(task.id, "test", &long_data)
+        .add_event(task.id, "note", &long_data)
         .await
         .unwrap();
 
@@ -454,7 +454,7 @@ async fn test_empty_event_data() {
         .await
         .unwrap();
 
-    let event = event_mgr.add_event(task.id, "test", "").await.unwrap();
+    let event = event_mgr.add_event(task.id, "note", "").await.unwrap();
     assert_eq!(event.discussion_data, "");
 }
 
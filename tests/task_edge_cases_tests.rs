@@ -233,7 +233,7 @@ async fn test_pick_next_with_all_tasks_completed() {
 
     // Start and complete the task
     manager.start_task(task.id, false).await.unwrap();
-    manager.done_task(false).await.unwrap();
+    manager.done_task().await.unwrap();
 
     // Pick next should return None (no tasks available)
     let result = manager.pick_next().await.unwrap();
@@ -275,7 +275,7 @@ async fn test_task_done_with_uncompleted_children() {
     manager.start_task(parent.id, false).await.unwrap();
 
     // Try to complete parent without completing child
-    let result = manager.done_task(false).await;
+    let result = manager.done_task().await;
 
     assert!(result.is_err());
     let error = result.unwrap_err();
@@ -300,7 +300,7 @@ async fn test_task_done_without_current_task() {
     assert!(current.current_task_id.is_none());
 
     // Try to complete without a focused task
-    let result = manager.done_task(false).await;
+    let result = manager.done_task().await;
 
     assert!(result.is_err());
     let error = result.unwrap_err();
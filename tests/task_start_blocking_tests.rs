@@ -65,7 +65,7 @@ async fn test_start_task_allowed_after_dependency_completed() {
 
     // Start and complete Task 1
     manager.start_task(task1.id, false).await.unwrap();
-    manager.done_task(false).await.unwrap();
+    manager.done_task().await.unwrap();
 
     // Now Task 2 should be allowed to start
     let result = manager.start_task(task2.id, false).await;
@@ -145,7 +145,7 @@ async fn test_start_task_with_partial_dependencies_completed() {
 
     // Complete Task 1 only
     manager.start_task(task1.id, false).await.unwrap();
-    manager.done_task(false).await.unwrap();
+    manager.done_task().await.unwrap();
 
     // Try to start Task 3 (should still fail because Task 2 is not done)
     let result = manager.start_task(task3.id, false).await;
@@ -0,0 +1,36 @@
+/// Tests for the `-v` timing summary
+mod common;
+
+use predicates::prelude::*;
+
+#[test]
+fn test_verbose_prints_timing_summary_on_stderr() {
+    let temp_dir = common::setup_test_env();
+    let dir = temp_dir.path();
+
+    common::ie_command_with_project_dir(dir)
+        .args(["-v", "plan"])
+        .write_stdin(r#"{"tasks": [{"name": "Timed", "children": [{"name": "Step"}]}]}"#)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Timing summary"))
+        .stderr(predicate::str::contains("plan::execute_plan"))
+        .stderr(predicate::str::contains("plan::transaction"));
+
+    common::ie_command_with_project_dir(dir)
+        .args(["-v", "task", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("tasks::find_tasks"));
+}
+
+#[test]
+fn test_no_timing_summary_without_verbose() {
+    let temp_dir = common::setup_test_env();
+
+    common::ie_command_with_project_dir(temp_dir.path())
+        .args(["task", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Timing summary").not());
+}